@@ -48,6 +48,7 @@ echo "--- e2e test w/ Rust frontend - source with kafka"
 cargo make clean-data
 cargo make ci-start ci-kafka
 ./scripts/source/prepare_ci_kafka.sh
+./scripts/source/prepare_ci_s3.sh
 sqllogictest -p 4566 -d dev  './e2e_test/source/**/*.slt'
 
 echo "--- Run CH-benCHmark"
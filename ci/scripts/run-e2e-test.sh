@@ -52,6 +52,9 @@ sqllogictest -p 4566 -d dev './e2e_test/ddl/**/*.slt' --junit "batch-ddl-${profi
 sqllogictest -p 4566 -d dev './e2e_test/batch/**/*.slt' --junit "batch-${profile}"
 sqllogictest -p 4566 -d dev './e2e_test/database/prepare.slt'
 sqllogictest -p 4566 -d test './e2e_test/database/test.slt'
+sqllogictest -p 4566 -d dev './e2e_test/copy/prepare.slt'
+./e2e_test/copy/copy.sh
+sqllogictest -p 4566 -d dev './e2e_test/copy/check.slt'
 
 echo "--- Kill cluster"
 cargo make ci-kill
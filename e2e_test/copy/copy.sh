@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+
+# Loads and dumps `t_copy` with `COPY .. FROM STDIN` and `COPY .. TO STDOUT`, which can't be sent
+# through sqllogictest. Run `prepare.slt` before and `check.slt` after this script.
+
+# Exits as soon as any line fails.
+set -euo pipefail
+
+cd "$(dirname "$0")"
+
+psql_dev() {
+    psql -h localhost -p 4566 -d dev -U root -v ON_ERROR_STOP=1 "$@"
+}
+
+psql_dev -c "copy t_copy from stdin (format csv, header true)" < data.csv
+psql_dev -c "copy t_copy from stdin" < data.txt
+printf '1\n2\n' | psql_dev -c "copy t_copy_default (v1) from stdin"
+psql_dev -c "flush"
+
+actual=$(mktemp)
+trap 'rm -f "$actual"' EXIT
+psql_dev -c "copy (select v1, v2, v3 from t_copy order by v1) to stdout" > "$actual"
+diff -u expected.txt "$actual"
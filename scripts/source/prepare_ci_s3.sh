@@ -0,0 +1,17 @@
+#!/bin/bash
+
+# Exits as soon as any line fails.
+set -e
+
+SCRIPT_PATH="$(cd "$(dirname "$0")" >/dev/null 2>&1 && pwd)"
+cd "$SCRIPT_PATH/.." || exit 1
+
+MCLI="$SCRIPT_PATH/../../.risingwave/bin/mcli -C $SCRIPT_PATH/../../.risingwave/config/mcli"
+
+# The alias `hummock-minio` is configured by RiseDev for its MinIO instance.
+echo "Create bucket s3-source-test"
+$MCLI rm --recursive --force hummock-minio/s3-source-test || true
+$MCLI mb hummock-minio/s3-source-test
+
+echo "Upload files"
+$MCLI cp "$SCRIPT_PATH"/s3_test_data/* hummock-minio/s3-source-test/
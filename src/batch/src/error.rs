@@ -38,6 +38,9 @@ pub enum BatchError {
     #[error("Failed to send result to channel")]
     SenderError,
 
+    #[error("Execution is aborted")]
+    Aborted,
+
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 
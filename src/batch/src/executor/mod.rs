@@ -25,9 +25,11 @@ mod limit;
 mod merge_sort_exchange;
 pub mod monitor;
 mod order_by;
+mod over_window;
 mod project;
 mod project_set;
 mod row_seq_scan;
+mod session_window;
 mod sort_agg;
 mod sys_row_seq_scan;
 mod table_function;
@@ -39,6 +41,9 @@ mod update;
 mod utils;
 mod values;
 
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
 use async_recursion::async_recursion;
 pub use delete::*;
 pub use expand::*;
@@ -54,6 +59,7 @@ pub use limit::*;
 pub use merge_sort_exchange::*;
 pub use monitor::*;
 pub use order_by::*;
+pub use over_window::*;
 pub use project::*;
 pub use project_set::*;
 use risingwave_common::array::DataChunk;
@@ -62,6 +68,7 @@ use risingwave_common::error::Result;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::PlanNode;
 pub use row_seq_scan::*;
+pub use session_window::*;
 pub use sort_agg::*;
 pub use table_function::*;
 pub use top_n::TopNExecutor;
@@ -119,6 +126,9 @@ pub struct ExecutorBuilder<'a, C> {
     pub task_id: &'a TaskId,
     context: C,
     epoch: u64,
+    /// Once set, executors stop with an error at their next chunk. This is how a running query is
+    /// aborted even when it never yields to the async runtime.
+    cancel_flag: Option<Arc<AtomicBool>>,
 }
 
 macro_rules! build_executor {
@@ -140,12 +150,24 @@ impl<'a, C: Clone> ExecutorBuilder<'a, C> {
             task_id,
             context,
             epoch,
+            cancel_flag: None,
         }
     }
 
+    #[must_use]
+    pub fn with_cancel_flag(mut self, cancel_flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(cancel_flag);
+        self
+    }
+
     #[must_use]
     pub fn clone_for_plan(&self, plan_node: &'a PlanNode) -> Self {
-        ExecutorBuilder::new(plan_node, self.task_id, self.context.clone(), self.epoch)
+        let builder =
+            ExecutorBuilder::new(plan_node, self.task_id, self.context.clone(), self.epoch);
+        match &self.cancel_flag {
+            Some(cancel_flag) => builder.with_cancel_flag(cancel_flag.clone()),
+            None => builder,
+        }
     }
 
     pub fn plan_node(&self) -> &PlanNode {
@@ -193,6 +215,8 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::OrderBy => OrderByExecutor,
             NodeBody::TopN => TopNExecutor,
             NodeBody::GroupTopN => GroupTopNExecutorBuilder,
+            NodeBody::OverWindow => OverWindowExecutor,
+            NodeBody::SessionWindow => SessionWindowExecutor,
             NodeBody::Limit => LimitExecutor,
             NodeBody::Values => ValuesExecutor,
             NodeBody::NestedLoopJoin => NestedLoopJoinExecutor,
@@ -210,7 +234,11 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
         }
         .await?;
         let input_desc = real_executor.identity().to_string();
-        Ok(Box::new(TraceExecutor::new(real_executor, input_desc)) as BoxedExecutor)
+        Ok(Box::new(TraceExecutor::new(
+            real_executor,
+            input_desc,
+            self.cancel_flag.clone(),
+        )) as BoxedExecutor)
     }
 }
 
@@ -0,0 +1,258 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_common::util::encoding_for_comparison::encode_chunk;
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_expr::window_function::{Partition, WindowFuncCall};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use super::{BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder};
+use crate::task::BatchTaskContext;
+
+/// Over Window Executor
+///
+/// High-level idea:
+/// 1. Load data chunks from child executor
+/// 2. Sort all rows by the partition key and then the `ORDER BY` of the window
+/// 3. Evaluate the window functions partition by partition
+/// 4. Output the input rows with the results of the window functions appended
+pub struct OverWindowExecutor {
+    child: BoxedExecutor,
+    calls: Vec<WindowFuncCall>,
+    partition_by: Vec<usize>,
+    order_by: Vec<OrderPair>,
+    identity: String,
+    schema: Schema,
+    chunk_size: usize,
+}
+
+impl Executor for OverWindowExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for OverWindowExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let [child]: [_; 1] = inputs.try_into().unwrap();
+
+        let over_window_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::OverWindow
+        )?;
+
+        let calls = over_window_node
+            .get_calls()
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .collect::<risingwave_expr::Result<_>>()?;
+        let partition_by = over_window_node
+            .get_partition_by()
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect();
+        let order_by = over_window_node
+            .get_order_by()
+            .iter()
+            .map(OrderPair::from_prost)
+            .collect();
+
+        Ok(Box::new(Self::new(
+            child,
+            calls,
+            partition_by,
+            order_by,
+            source.plan_node().get_identity().clone(),
+            source.context.get_config().developer.batch_chunk_size,
+        )))
+    }
+}
+
+impl OverWindowExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_by: Vec<usize>,
+        order_by: Vec<OrderPair>,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let mut schema = child.schema().clone();
+        calls.iter().for_each(|call| {
+            schema.fields.push(Field::unnamed(call.return_type.clone()));
+        });
+        Self {
+            child,
+            calls,
+            partition_by,
+            order_by,
+            identity,
+            schema,
+            chunk_size,
+        }
+    }
+
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut chunk_builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
+
+        // Sort by the partition key first so that each partition is a contiguous range of rows.
+        let sort_order = self
+            .partition_by
+            .iter()
+            .map(|idx| OrderPair::new(*idx, OrderType::Ascending))
+            .chain(self.order_by.iter().cloned())
+            .collect_vec();
+
+        let mut chunks = Vec::new();
+        #[for_await]
+        for chunk in self.child.execute() {
+            chunks.push(chunk?.compact());
+        }
+        let mut encoded_rows = Vec::new();
+        for chunk in &chunks {
+            let encoded_chunk = encode_chunk(chunk, &sort_order);
+            encoded_rows.extend(
+                encoded_chunk
+                    .into_iter()
+                    .enumerate()
+                    .map(|(row_id, row)| (chunk.row_at_unchecked_vis(row_id), row)),
+            );
+        }
+        encoded_rows.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+        let partitions: Vec<Vec<Row>> = encoded_rows
+            .into_iter()
+            .map(|(row, _)| row.to_owned_row())
+            .group_by(|row| row.by_indices(&self.partition_by))
+            .into_iter()
+            .map(|(_, rows)| rows.collect())
+            .collect();
+        drop(chunks);
+
+        for partition_rows in partitions {
+            let partition = Partition::new(&partition_rows, &self.order_by)?;
+            let outputs: Vec<_> = self
+                .calls
+                .iter()
+                .map(|call| call.evaluate_partition(&partition))
+                .collect::<risingwave_expr::Result<_>>()?;
+            for (i, row) in partition_rows.iter().enumerate() {
+                let datums = row.0.iter().chain(outputs.iter().map(|output| &output[i]));
+                if let Some(spilled) = chunk_builder.append_one_row_from_datums(datums) {
+                    yield spilled
+                }
+            }
+        }
+
+        if let Some(spilled) = chunk_builder.consume_all() {
+            yield spilled
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::DataType;
+    use risingwave_expr::window_function::window_function_prost;
+    use risingwave_pb::expr::agg_call::Type as AggTypeProst;
+    use risingwave_pb::expr::window_function::Type as WindowFunctionTypeProst;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    const CHUNK_SIZE: usize = 1024;
+
+    #[tokio::test]
+    async fn test_over_window_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Int32),
+            ],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i i i
+             1 3 30
+             2 1 10
+             1 1 10
+             2 2 20
+             1 2 20",
+        ));
+
+        let calls = vec![
+            WindowFuncCall::from_protobuf(&window_function_prost(
+                WindowFunctionTypeProst::RowNumber,
+                AggTypeProst::Unspecified,
+                None,
+                DataType::Int64,
+                None,
+            ))
+            .unwrap(),
+            WindowFuncCall::from_protobuf(&window_function_prost(
+                WindowFunctionTypeProst::Aggregate,
+                AggTypeProst::Sum,
+                Some((2, DataType::Int32)),
+                DataType::Int64,
+                None,
+            ))
+            .unwrap(),
+        ];
+        let executor = Box::new(OverWindowExecutor::new(
+            Box::new(mock_executor),
+            calls,
+            vec![0],
+            vec![OrderPair::new(1, OrderType::Ascending)],
+            "OverWindowExecutor".to_string(),
+            CHUNK_SIZE,
+        ));
+        assert_eq!(executor.schema().fields.len(), 5);
+
+        let mut stream = executor.execute();
+        let res = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            res,
+            DataChunk::from_pretty(
+                "i i i  I I
+                 1 1 10 1 10
+                 1 2 20 2 30
+                 1 3 30 3 60
+                 2 1 10 1 10
+                 2 2 20 2 30"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
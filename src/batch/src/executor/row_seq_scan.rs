@@ -20,7 +20,7 @@ use itertools::Itertools;
 use prometheus::Histogram;
 use risingwave_common::array::{DataChunk, Row};
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema, TableId, TableOption};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema, TableDesc, TableId, TableOption};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{DataType, Datum};
 use risingwave_common::util::select_all;
@@ -219,6 +219,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             .iter()
             .map(|&k| k as usize)
             .collect_vec();
+        let appended_value_defaults = TableDesc::appended_value_defaults_from_protobuf(table_desc);
 
         let scan_ranges = seq_scan_node.scan_ranges.clone();
         let scan_ranges = {
@@ -247,6 +248,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
                 distribution,
                 table_option,
                 value_indices,
+                appended_value_defaults,
             );
 
             Ok(Box::new(RowSeqScanExecutor::new(
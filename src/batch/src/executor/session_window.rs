@@ -0,0 +1,226 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{
+    CheckedAdd, DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl,
+};
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use super::{BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder};
+use crate::error::BatchError;
+use crate::task::BatchTaskContext;
+
+/// Session Window Executor
+///
+/// High-level idea:
+/// 1. Load data chunks from child executor
+/// 2. Sort all rows by the time column
+/// 3. Split the sorted rows into sessions, where a new session starts when the time is no less
+///    than the latest time of the current session plus `gap`
+/// 4. Output the input rows with `window_start` and `window_end` of their sessions appended
+pub struct SessionWindowExecutor {
+    child: BoxedExecutor,
+    time_col_idx: usize,
+    gap: IntervalUnit,
+    identity: String,
+    schema: Schema,
+    chunk_size: usize,
+}
+
+impl Executor for SessionWindowExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for SessionWindowExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let [child]: [_; 1] = inputs.try_into().unwrap();
+
+        let session_window_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::SessionWindow
+        )?;
+        let time_col_idx = session_window_node.get_time_col()?.column_idx as usize;
+        let gap = session_window_node.get_gap()?.into();
+
+        Ok(Box::new(Self::new(
+            child,
+            time_col_idx,
+            gap,
+            source.plan_node().get_identity().clone(),
+            source.context.get_config().developer.batch_chunk_size,
+        )))
+    }
+}
+
+impl SessionWindowExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        time_col_idx: usize,
+        gap: IntervalUnit,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let mut schema = child.schema().clone();
+        schema.fields.extend([
+            Field::with_name(DataType::Timestamp, "window_start"),
+            Field::with_name(DataType::Timestamp, "window_end"),
+        ]);
+        Self {
+            child,
+            time_col_idx,
+            gap,
+            identity,
+            schema,
+            chunk_size,
+        }
+    }
+
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut chunk_builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
+
+        let mut null_rows = vec![];
+        let mut rows = vec![];
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                let row = row.to_owned_row();
+                match row.0[self.time_col_idx] {
+                    Some(ScalarImpl::NaiveDateTime(time)) => rows.push((time, row)),
+                    None => null_rows.push(row),
+                    _ => unreachable!("the time column of session window must be a timestamp"),
+                }
+            }
+        }
+
+        // Rows with null time don't belong to any session.
+        for row in null_rows {
+            let datums = row.0.iter().chain([&None, &None]);
+            if let Some(spilled) = chunk_builder.append_one_row_from_datums(datums) {
+                yield spilled
+            }
+        }
+
+        rows.sort_by_key(|(time, _)| *time);
+        let gap = self.gap;
+        let end_of =
+            |time: NaiveDateTimeWrapper| time.checked_add(gap).ok_or(BatchError::NumericOutOfRange);
+        let mut sessions: Vec<(NaiveDateTimeWrapper, NaiveDateTimeWrapper, Vec<Row>)> = vec![];
+        for (time, row) in rows {
+            match sessions.last_mut() {
+                Some((_, end, session_rows)) if time < *end => {
+                    *end = end_of(time)?;
+                    session_rows.push(row);
+                }
+                _ => sessions.push((time, end_of(time)?, vec![row])),
+            }
+        }
+
+        for (start, end, session_rows) in sessions {
+            let window = [
+                Some(ScalarImpl::NaiveDateTime(start)),
+                Some(ScalarImpl::NaiveDateTime(end)),
+            ];
+            for row in session_rows {
+                let datums = row.0.iter().chain(window.iter());
+                if let Some(spilled) = chunk_builder.append_one_row_from_datums(datums) {
+                    yield spilled
+                }
+            }
+        }
+
+        if let Some(spilled) = chunk_builder.consume_all() {
+            yield spilled
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    const CHUNK_SIZE: usize = 1024;
+
+    #[tokio::test]
+    async fn test_session_window_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Timestamp),
+            ],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i TS
+             1 2022-01-01T10:00:00
+             2 2022-01-01T10:20:00
+             3 2022-01-01T10:04:00",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i TS
+             4 2022-01-01T10:08:00
+             5 .
+             6 2022-01-01T10:13:00",
+        ));
+
+        let executor = Box::new(SessionWindowExecutor::new(
+            Box::new(mock_executor),
+            1,
+            IntervalUnit::from_minutes(5),
+            "SessionWindowExecutor".to_string(),
+            CHUNK_SIZE,
+        ));
+        assert_eq!(executor.schema().fields.len(), 4);
+
+        let mut stream = executor.execute();
+        let res = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            res,
+            DataChunk::from_pretty(
+                "i TS                  TS                  TS
+                 5 .                   .                   .
+                 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:13:00
+                 3 2022-01-01T10:04:00 2022-01-01T10:00:00 2022-01-01T10:13:00
+                 4 2022-01-01T10:08:00 2022-01-01T10:00:00 2022-01-01T10:13:00
+                 6 2022-01-01T10:13:00 2022-01-01T10:13:00 2022-01-01T10:18:00
+                 2 2022-01-01T10:20:00 2022-01-01T10:20:00 2022-01-01T10:25:00"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use futures::stream::StreamExt;
 use futures_async_stream::try_stream;
 use minitrace::prelude::*;
@@ -20,6 +23,7 @@ use risingwave_common::catalog::Schema;
 use risingwave_common::error::RwError;
 use tracing::event;
 
+use crate::error::BatchError;
 use crate::executor::{BoxedDataChunkStream, BoxedExecutor, Executor};
 
 /// If tracing is enabled, we build a [`TraceExecutor`] on top of the underlying executor.
@@ -28,11 +32,21 @@ pub struct TraceExecutor {
     child: BoxedExecutor,
     /// Description of input executor
     input_desc: String,
+    /// Checked on every chunk of the child, see `ExecutorBuilder::with_cancel_flag`.
+    cancel_flag: Option<Arc<AtomicBool>>,
 }
 
 impl TraceExecutor {
-    pub fn new(child: BoxedExecutor, input_desc: String) -> Self {
-        Self { child, input_desc }
+    pub fn new(
+        child: BoxedExecutor,
+        input_desc: String,
+        cancel_flag: Option<Arc<AtomicBool>>,
+    ) -> Self {
+        Self {
+            child,
+            input_desc,
+            cancel_flag,
+        }
     }
 }
 
@@ -66,6 +80,9 @@ impl TraceExecutor {
 
         while let Some(chunk) = child_stream.next().in_span(span()).await {
             let chunk = chunk?;
+            if matches!(&self.cancel_flag, Some(flag) if flag.load(Ordering::Relaxed)) {
+                return Err(BatchError::Aborted.into());
+            }
             event!(tracing::Level::TRACE, prev = %input_desc, msg = "chunk", "input = \n{:#?}", 
                 chunk);
             yield chunk;
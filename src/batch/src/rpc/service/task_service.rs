@@ -149,9 +149,17 @@ impl TaskService for BatchServiceImpl {
             .await?;
         if !finish {
             self.mgr.runtime().spawn(async move {
-                match output.take_data(&mut writer).await {
-                    Ok(_) => Ok(()),
-                    Err(e) => tx.send(Err(e.into())).await,
+                tokio::select! {
+                    res = output.take_data(&mut writer) => match res {
+                        Ok(_) => Ok(()),
+                        Err(e) => tx.send(Err(e.into())).await,
+                    },
+                    _ = tx.closed() => {
+                        // The frontend has dropped the result stream, e.g. the query is canceled
+                        // or runs over `statement_timeout`, so there is no need to keep running.
+                        task.abort_task();
+                        Ok(())
+                    }
                 }
             });
         }
@@ -25,6 +25,7 @@ use crate::row::CompactedRow;
 use crate::types::{hash_datum, DataType, Datum, DatumRef, ToOwnedDatum};
 use crate::util::ordered::OrderedRowSerde;
 use crate::util::value_encoding;
+use crate::util::value_encoding::error::ValueEncodingError;
 use crate::util::value_encoding::{deserialize_datum, serialize_datum};
 
 impl DataChunk {
@@ -394,18 +395,48 @@ impl EstimateSize for Row {
 #[derive(Clone, Debug)]
 pub struct RowDeserializer {
     data_types: Vec<DataType>,
+    /// Defaults of the trailing columns appended by `ALTER TABLE ADD COLUMN`. They are missing in
+    /// the rows written before the columns are appended.
+    appended_defaults: Vec<Datum>,
 }
 
 impl RowDeserializer {
     /// Creates a new `RowDeserializer` with row schema.
     pub fn new(data_types: Vec<DataType>) -> Self {
-        RowDeserializer { data_types }
+        RowDeserializer {
+            data_types,
+            appended_defaults: vec![],
+        }
+    }
+
+    /// Creates a new `RowDeserializer` with row schema, of which the last
+    /// `appended_defaults.len()` columns are appended by `ALTER TABLE ADD COLUMN`.
+    pub fn with_appended_defaults(
+        data_types: Vec<DataType>,
+        appended_defaults: Vec<Datum>,
+    ) -> Self {
+        assert!(appended_defaults.len() <= data_types.len());
+        RowDeserializer {
+            data_types,
+            appended_defaults,
+        }
     }
 
     /// Deserialize the row from value encoding bytes.
+    ///
+    /// If the bytes run out before all columns are read, the remaining columns must be the appended
+    /// ones, which are filled with their defaults.
     pub fn deserialize(&self, mut data: impl bytes::Buf) -> value_encoding::Result<Row> {
+        let required = self.data_types.len() - self.appended_defaults.len();
         let mut values = Vec::with_capacity(self.data_types.len());
-        for typ in &self.data_types {
+        for (i, typ) in self.data_types.iter().enumerate() {
+            if !data.has_remaining() {
+                if i < required {
+                    return Err(ValueEncodingError::MissingColumn(i));
+                }
+                values.push(self.appended_defaults[i - required].clone());
+                continue;
+            }
             values.push(deserialize_datum(&mut data, typ)?);
         }
         Ok(Row(values))
@@ -453,6 +484,37 @@ mod tests {
         assert_eq!(row, row1);
     }
 
+    #[test]
+    fn row_value_decode_with_appended_columns() {
+        let row = Row(vec![
+            Some(ScalarImpl::Int32(1)),
+            Some(ScalarImpl::Utf8("string".into())),
+        ]);
+        let bytes = row.serialize(&None);
+        let data_types = vec![Ty::Int32, Ty::Varchar, Ty::Int64, Ty::Boolean];
+
+        let de = RowDeserializer::with_appended_defaults(
+            data_types.clone(),
+            vec![Some(ScalarImpl::Int64(233)), None],
+        );
+        let row1 = de.deserialize(bytes.as_ref()).unwrap();
+        assert_eq!(
+            row1,
+            Row(vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Utf8("string".into())),
+                Some(ScalarImpl::Int64(233)),
+                None,
+            ])
+        );
+
+        // Only the appended columns can be missing.
+        let de = RowDeserializer::with_appended_defaults(data_types.clone(), vec![None]);
+        assert!(de.deserialize(bytes.as_ref()).is_err());
+        let de = RowDeserializer::new(data_types);
+        assert!(de.deserialize(bytes.as_ref()).is_err());
+    }
+
     #[test]
     fn test_hash_row() {
         let hash_builder = Crc32FastBuilder {};
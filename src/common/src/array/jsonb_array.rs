@@ -0,0 +1,247 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::{Hash, Hasher};
+use std::mem::size_of;
+
+use itertools::Itertools;
+use risingwave_pb::common::buffer::CompressionType;
+use risingwave_pb::common::Buffer;
+use risingwave_pb::data::{Array as ProstArray, ArrayType};
+
+use super::{Array, ArrayBuilder, ArrayImpl, ArrayIterator, ArrayResult, NULL_VAL_FOR_HASH};
+use crate::array::{ArrayBuilderImpl, ArrayMeta};
+use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::types::{JsonbRef, JsonbVal, Scalar, ScalarRef};
+
+#[derive(Debug, Clone)]
+pub struct JsonbArray {
+    bitmap: Bitmap,
+    data: Vec<JsonbVal>,
+}
+
+impl JsonbArray {
+    pub fn from_slice(data: &[Option<JsonbRef<'_>>]) -> Self {
+        let mut builder = <Self as Array>::Builder::new(data.len());
+        for i in data {
+            builder.append(*i);
+        }
+        builder.finish()
+    }
+
+    /// Reads an array serialized by [`Array::to_protobuf`], where the values are stored as text
+    /// like `DecimalArray`.
+    pub fn from_protobuf(array: &ProstArray, cardinality: usize) -> ArrayResult<ArrayImpl> {
+        ensure!(
+            array.get_values().len() == 2,
+            "Must have exactly 2 buffers in a jsonb array"
+        );
+        let offset_buf = array.get_values()[0].get_body().as_slice();
+        let data_buf = array.get_values()[1].get_body().as_slice();
+        let bitmap: Bitmap = array.get_null_bitmap()?.into();
+        ensure!(
+            bitmap.len() == cardinality,
+            "Unexpected length of jsonb array"
+        );
+
+        let offsets = offset_buf
+            .chunks_exact(size_of::<u64>())
+            .map(|b| u64::from_be_bytes(b.try_into().unwrap()) as usize)
+            .collect_vec();
+        let mut builder = JsonbArrayBuilder::new(cardinality);
+        let mut offsets = offsets.iter().tuple_windows();
+        for not_null in bitmap.iter() {
+            if not_null {
+                let Some((&start, &end)) = offsets.next() else {
+                    bail!("Unexpected end of offset buffer of jsonb array");
+                };
+                let Some(value) = data_buf.get(start..end).and_then(JsonbRef::value_deserialize) else {
+                    bail!("Failed to read jsonb from buffer");
+                };
+                builder.append(Some(value.as_scalar_ref()));
+            } else {
+                builder.append(None);
+            }
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+impl Array for JsonbArray {
+    type Builder = JsonbArrayBuilder;
+    type Iter<'a> = ArrayIterator<'a, Self>;
+    type OwnedItem = JsonbVal;
+    type RefItem<'a> = JsonbRef<'a>;
+
+    fn value_at(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null(idx) {
+            Some(self.data[idx].as_scalar_ref())
+        } else {
+            None
+        }
+    }
+
+    unsafe fn value_at_unchecked(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null_unchecked(idx) {
+            Some(self.data.get_unchecked(idx).as_scalar_ref())
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayIterator::new(self)
+    }
+
+    fn to_protobuf(&self) -> ProstArray {
+        let mut offset_buffer = Vec::<u8>::with_capacity(self.data.len() * size_of::<usize>());
+        let mut data_buffer = Vec::<u8>::new();
+        let mut offset = 0u64;
+        for (d, not_null) in self.data.iter().zip_eq(self.null_bitmap().iter()) {
+            if not_null {
+                let b = d.as_scalar_ref().value_serialize();
+                offset_buffer.extend_from_slice(&offset.to_be_bytes());
+                data_buffer.extend_from_slice(&b);
+                offset += b.len() as u64;
+            }
+        }
+        offset_buffer.extend_from_slice(&offset.to_be_bytes());
+
+        let values = vec![
+            Buffer {
+                compression: CompressionType::None as i32,
+                body: offset_buffer,
+            },
+            Buffer {
+                compression: CompressionType::None as i32,
+                body: data_buffer,
+            },
+        ];
+        let null_bitmap = self.null_bitmap().to_protobuf();
+        ProstArray {
+            null_bitmap: Some(null_bitmap),
+            values,
+            array_type: ArrayType::Jsonb as i32,
+            struct_array_data: None,
+            list_array_data: None,
+        }
+    }
+
+    fn null_bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    fn into_null_bitmap(self) -> Bitmap {
+        self.bitmap
+    }
+
+    fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+    }
+
+    #[inline(always)]
+    fn hash_at<H: Hasher>(&self, idx: usize, state: &mut H) {
+        if !self.is_null(idx) {
+            self.data[idx].hash(state);
+        } else {
+            NULL_VAL_FOR_HASH.hash(state);
+        }
+    }
+
+    fn create_builder(&self, capacity: usize) -> ArrayBuilderImpl {
+        let array_builder = JsonbArrayBuilder::new(capacity);
+        ArrayBuilderImpl::Jsonb(array_builder)
+    }
+}
+
+/// `JsonbArrayBuilder` constructs a `JsonbArray` from `Option<JsonbRef>`.
+#[derive(Debug)]
+pub struct JsonbArrayBuilder {
+    bitmap: BitmapBuilder,
+    data: Vec<JsonbVal>,
+}
+
+impl ArrayBuilder for JsonbArrayBuilder {
+    type ArrayType = JsonbArray;
+
+    fn with_meta(capacity: usize, _meta: ArrayMeta) -> Self {
+        Self {
+            bitmap: BitmapBuilder::with_capacity(capacity),
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn append(&mut self, value: Option<JsonbRef<'_>>) {
+        match value {
+            Some(x) => {
+                self.bitmap.append(true);
+                self.data.push(x.to_owned_scalar());
+            }
+            None => {
+                self.bitmap.append(false);
+                self.data.push(JsonbVal::null());
+            }
+        }
+    }
+
+    fn append_array(&mut self, other: &JsonbArray) {
+        for bit in other.bitmap.iter() {
+            self.bitmap.append(bit);
+        }
+        self.data.extend_from_slice(&other.data);
+    }
+
+    fn pop(&mut self) -> Option<()> {
+        self.data.pop().map(|_| self.bitmap.pop().unwrap())
+    }
+
+    fn finish(self) -> JsonbArray {
+        JsonbArray {
+            bitmap: self.bitmap.finish(),
+            data: self.data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonb_array_protobuf() {
+        let values: Vec<JsonbVal> = [r#"{"a": [1, "b"]}"#, "null", r#""c""#]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let input = vec![
+            Some(values[0].as_scalar_ref()),
+            None,
+            Some(values[1].as_scalar_ref()),
+            Some(values[2].as_scalar_ref()),
+            None,
+        ];
+        let array = JsonbArray::from_slice(&input);
+        assert_eq!(array.len(), 5);
+        assert_eq!(input, array.iter().collect_vec());
+
+        let decoded = ArrayImpl::from_protobuf(&array.to_protobuf(), input.len())
+            .unwrap()
+            .into_jsonb();
+        assert_eq!(input, decoded.iter().collect_vec());
+    }
+}
@@ -19,7 +19,8 @@ use byteorder::{BigEndian, ReadBytesExt};
 
 use super::ArrayResult;
 use crate::array::{
-    Array, ArrayBuilder, DecimalArrayBuilder, PrimitiveArrayItemType, Utf8ArrayBuilder,
+    Array, ArrayBuilder, BytesArrayBuilder, DecimalArrayBuilder, PrimitiveArrayItemType,
+    Utf8ArrayBuilder,
 };
 use crate::types::{Decimal, OrderedF32, OrderedF64};
 
@@ -78,3 +79,11 @@ impl VarSizedValueReader<DecimalArrayBuilder> for DecimalValueReader {
         }
     }
 }
+
+pub struct BytesValueReader {}
+
+impl VarSizedValueReader<BytesArrayBuilder> for BytesValueReader {
+    fn read(buf: &[u8]) -> ArrayResult<&[u8]> {
+        Ok(buf)
+    }
+}
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use itertools::Itertools;
-use risingwave_pb::plan_common::ColumnDesc as ProstColumnDesc;
+use risingwave_pb::plan_common::{
+    ColumnCatalog as ProstColumnCatalog, ColumnDesc as ProstColumnDesc,
+};
 
 use crate::catalog::Field;
 use crate::error::ErrorCode;
-use crate::types::DataType;
+use crate::types::{DataType, Datum};
+use crate::util::value_encoding::deserialize_datum;
 
 /// Column ID is the unique identifier of a column in a table. Different from table ID, column ID is
 /// not globally unique.
@@ -211,6 +214,18 @@ impl From<&ColumnDesc> for ProstColumnDesc {
     }
 }
 
+/// Decodes the default of a column appended by `ALTER TABLE ADD COLUMN`. Returns `None` for the
+/// columns created with the table.
+pub fn column_default_from_protobuf(column: &ProstColumnCatalog) -> Option<Datum> {
+    if column.default_value.is_empty() {
+        return None;
+    }
+    let data_type = DataType::from(column.get_column_desc().unwrap().get_column_type().unwrap());
+    let default = deserialize_datum(column.default_value.as_slice(), &data_type)
+        .expect("invalid column default");
+    Some(default)
+}
+
 #[cfg(test)]
 pub mod tests {
     use risingwave_pb::plan_common::ColumnDesc as ProstColumnDesc;
@@ -16,6 +16,7 @@ mod column;
 mod internal_table;
 mod physical_table;
 mod schema;
+mod sink;
 pub mod test_utils;
 
 use std::collections::HashMap;
@@ -27,6 +28,7 @@ pub use internal_table::*;
 use parse_display::Display;
 pub use physical_table::*;
 pub use schema::{test_utils as schema_test_utils, Field, FieldDisplay, Schema};
+pub use sink::*;
 
 use crate::array::Row;
 pub use crate::config::constant::hummock;
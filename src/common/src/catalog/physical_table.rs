@@ -14,10 +14,13 @@
 
 use std::collections::HashMap;
 
+use itertools::Itertools;
 use risingwave_pb::plan_common::{ColumnOrder, StorageTableDesc};
 
 use super::{ColumnDesc, ColumnId, TableId};
+use crate::types::{DataType, Datum};
 use crate::util::sort_util::OrderPair;
+use crate::util::value_encoding::{deserialize_datum, serialize_datum_to_bytes};
 
 /// Includes necessary information for compute node to access data of the table.
 ///
@@ -43,6 +46,10 @@ pub struct TableDesc {
     pub retention_seconds: u32,
 
     pub value_indices: Vec<usize>,
+
+    /// Defaults of the trailing value columns appended by `ALTER TABLE ADD COLUMN`, which are
+    /// missing in the rows written before.
+    pub appended_value_defaults: Vec<Datum>,
 }
 
 impl TableDesc {
@@ -70,9 +77,28 @@ impl TableDesc {
             dist_key_indices: self.distribution_key.iter().map(|&k| k as u32).collect(),
             retention_seconds: self.retention_seconds,
             value_indices: self.value_indices.iter().map(|&v| v as u32).collect(),
+            appended_value_defaults: self
+                .appended_value_defaults
+                .iter()
+                .map(|d| serialize_datum_to_bytes(d.as_ref()))
+                .collect(),
         }
     }
 
+    /// Decodes the defaults of the appended value columns in the `StorageTableDesc`.
+    pub fn appended_value_defaults_from_protobuf(desc: &StorageTableDesc) -> Vec<Datum> {
+        let appended_indices =
+            &desc.value_indices[desc.value_indices.len() - desc.appended_value_defaults.len()..];
+        appended_indices
+            .iter()
+            .zip_eq(&desc.appended_value_defaults)
+            .map(|(&i, default)| {
+                let data_type = DataType::from(desc.columns[i as usize].get_column_type().unwrap());
+                deserialize_datum(default.as_slice(), &data_type).expect("invalid column default")
+            })
+            .collect()
+    }
+
     /// Helper function to create a mapping from `column id` to `column index`
     pub fn get_id_to_op_idx_mapping(&self) -> HashMap<ColumnId, usize> {
         let mut id_to_idx = HashMap::new();
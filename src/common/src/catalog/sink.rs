@@ -0,0 +1,121 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::error::{ErrorCode, Result, RwError};
+
+pub const SINK_TYPE_OPTION: &str = "type";
+pub const SINK_CONNECTOR_OPTION: &str = "connector";
+
+/// How the changes of the upstream are written to the sink, as the `type` option of `CREATE SINK`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SinkType {
+    /// Only the inserts are written, which requires the upstream to be append-only.
+    AppendOnly,
+    /// Inserts and updates are written as the new rows, and deletes as tombstones, both keyed by
+    /// the primary key of the upstream.
+    Upsert,
+    /// Each change is written as a Debezium change event with the rows before and after it.
+    Debezium,
+}
+
+impl SinkType {
+    /// Parses the `type` option, or returns `None` if it's not set.
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Option<Self>> {
+        properties
+            .get(SINK_TYPE_OPTION)
+            .map(|sink_type| sink_type.parse())
+            .transpose()
+    }
+
+    /// Returns the sink types implemented by the connector, or `None` if the connector is unknown.
+    /// The file sink only writes inserts, and the database sinks apply the changes to the rows.
+    pub fn supported_by(connector: &str) -> Option<&'static [Self]> {
+        match connector.to_lowercase().as_str() {
+            "kafka" => Some(&[SinkType::AppendOnly, SinkType::Upsert, SinkType::Debezium]),
+            "mysql" | "postgres" | "redis" => Some(&[SinkType::AppendOnly, SinkType::Upsert]),
+            "file" => Some(&[SinkType::AppendOnly]),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SinkType::AppendOnly => "append-only",
+            SinkType::Upsert => "upsert",
+            SinkType::Debezium => "debezium",
+        }
+    }
+}
+
+impl FromStr for SinkType {
+    type Err = RwError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "append-only" | "append_only" => Ok(SinkType::AppendOnly),
+            "upsert" => Ok(SinkType::Upsert),
+            "debezium" => Ok(SinkType::Debezium),
+            _ => Err(ErrorCode::InvalidParameterValue(format!(
+                "sink type must be one of \"append-only\", \"upsert\" and \"debezium\", got \"{}\"",
+                s
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sink_type() {
+        let sink_type = |t: &str| {
+            SinkType::from_properties(&HashMap::from([(
+                SINK_TYPE_OPTION.to_string(),
+                t.to_string(),
+            )]))
+        };
+        assert_eq!(
+            sink_type("append-only").unwrap(),
+            Some(SinkType::AppendOnly)
+        );
+        assert_eq!(
+            sink_type("APPEND_ONLY").unwrap(),
+            Some(SinkType::AppendOnly)
+        );
+        assert_eq!(sink_type("upsert").unwrap(), Some(SinkType::Upsert));
+        assert_eq!(sink_type("debezium").unwrap(), Some(SinkType::Debezium));
+        assert!(sink_type("retract").is_err());
+        assert_eq!(SinkType::from_properties(&HashMap::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_sink_type_supported_by() {
+        assert!(SinkType::supported_by("KAFKA")
+            .unwrap()
+            .contains(&SinkType::Debezium));
+        assert!(!SinkType::supported_by("redis")
+            .unwrap()
+            .contains(&SinkType::Debezium));
+        assert_eq!(
+            SinkType::supported_by("file").unwrap(),
+            &[SinkType::AppendOnly]
+        );
+        assert!(SinkType::supported_by("unknown").is_none());
+    }
+}
@@ -105,7 +105,7 @@ pub enum ErrorCode {
     #[error("Protocol error: {0}")]
     ProtocolError(String),
     #[error("Scheduler error: {0}")]
-    SchedulerError(BoxedError),
+    SchedulerError(#[source] BoxedError),
     #[error("Task not found")]
     TaskNotFound,
     #[error("Item not found: {0}")]
@@ -14,17 +14,20 @@
 
 mod query_mode;
 mod search_path;
+mod timezone;
 use std::ops::Deref;
+use std::time::Duration;
 
 use itertools::Itertools;
 pub use query_mode::QueryMode;
 pub use search_path::{SearchPath, USER_NAME_WILD_CARD};
+pub use timezone::TimeZone;
 
 use crate::error::{ErrorCode, RwError};
 
 // This is a hack, &'static str is not allowed as a const generics argument.
 // TODO: refine this using the adt_const_params feature.
-const CONFIG_KEYS: [&str; 9] = [
+const CONFIG_KEYS: [&str; 11] = [
     "RW_IMPLICIT_FLUSH",
     "CREATE_COMPACTION_GROUP_FOR_MV",
     "QUERY_MODE",
@@ -34,6 +37,8 @@ const CONFIG_KEYS: [&str; 9] = [
     "RW_BATCH_ENABLE_LOOKUP_JOIN",
     "MAX_SPLIT_RANGE_GAP",
     "SEARCH_PATH",
+    "TIMEZONE",
+    "STATEMENT_TIMEOUT",
 ];
 
 // MUST HAVE 1v1 relationship to CONFIG_KEYS. e.g. CONFIG_KEYS[IMPLICIT_FLUSH] =
@@ -47,6 +52,8 @@ const DATE_STYLE: usize = 5;
 const BATCH_ENABLE_LOOKUP_JOIN: usize = 6;
 const MAX_SPLIT_RANGE_GAP: usize = 7;
 const SEARCH_PATH: usize = 8;
+const TIMEZONE: usize = 9;
+const STATEMENT_TIMEOUT: usize = 10;
 
 trait ConfigEntry: Default + for<'a> TryFrom<&'a [&'a str], Error = RwError> {
     fn entry_name() -> &'static str;
@@ -193,6 +200,7 @@ type ExtraFloatDigit = ConfigI32<EXTRA_FLOAT_DIGITS, 1>;
 type DateStyle = ConfigString<DATE_STYLE>;
 type BatchEnableLookupJoin = ConfigBool<BATCH_ENABLE_LOOKUP_JOIN, false>;
 type MaxSplitRangeGap = ConfigI32<MAX_SPLIT_RANGE_GAP, 8>;
+type StatementTimeout = ConfigI32<STATEMENT_TIMEOUT, 0>;
 
 #[derive(Default)]
 pub struct ConfigMap {
@@ -226,6 +234,14 @@ pub struct ConfigMap {
 
     /// see <https://www.postgresql.org/docs/14/runtime-config-client.html#GUC-SEARCH-PATH>
     search_path: SearchPath,
+
+    /// see <https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-TIMEZONE>
+    timezone: TimeZone,
+
+    /// Abort any batch query that takes more than the specified number of milliseconds. Zero
+    /// disables the timeout.
+    /// see <https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-STATEMENT-TIMEOUT>
+    statement_timeout: StatementTimeout,
 }
 
 impl ConfigMap {
@@ -249,6 +265,10 @@ impl ConfigMap {
             self.max_split_range_gap = val.as_slice().try_into()?;
         } else if key.eq_ignore_ascii_case(SearchPath::entry_name()) {
             self.search_path = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(TimeZone::entry_name()) {
+            self.timezone = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StatementTimeout::entry_name()) {
+            self.statement_timeout = val.as_slice().try_into()?;
         } else {
             return Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into());
         }
@@ -275,6 +295,10 @@ impl ConfigMap {
             Ok(self.max_split_range_gap.to_string())
         } else if key.eq_ignore_ascii_case(SearchPath::entry_name()) {
             Ok(self.search_path.to_string())
+        } else if key.eq_ignore_ascii_case(TimeZone::entry_name()) {
+            Ok(self.timezone.to_string())
+        } else if key.eq_ignore_ascii_case(StatementTimeout::entry_name()) {
+            Ok(self.statement_timeout.to_string())
         } else {
             Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into())
         }
@@ -326,6 +350,16 @@ impl ConfigMap {
                 name: SearchPath::entry_name().to_lowercase(),
                 setting : self.search_path.to_string(),
                 description : String::from("Sets the order in which schemas are searched when an object (table, data type, function, etc.) is referenced by a simple name with no schema specified")
+            },
+            VariableInfo {
+                name: TimeZone::entry_name().to_lowercase(),
+                setting : self.timezone.to_string(),
+                description : String::from("Sets the time zone for displaying and interpreting time stamps.")
+            },
+            VariableInfo {
+                name: StatementTimeout::entry_name().to_lowercase(),
+                setting : self.statement_timeout.to_string(),
+                description : String::from("Sets the maximum allowed duration in milliseconds of any batch query. Zero disables the timeout.")
             }
         ]
     }
@@ -369,4 +403,17 @@ impl ConfigMap {
     pub fn get_search_path(&self) -> SearchPath {
         self.search_path.clone()
     }
+
+    pub fn get_timezone(&self) -> TimeZone {
+        self.timezone
+    }
+
+    /// Returns `None` if the statement timeout is disabled.
+    pub fn get_statement_timeout(&self) -> Option<Duration> {
+        if *self.statement_timeout <= 0 {
+            None
+        } else {
+            Some(Duration::from_millis(*self.statement_timeout as u64))
+        }
+    }
 }
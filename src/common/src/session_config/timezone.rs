@@ -0,0 +1,107 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+use super::{ConfigEntry, CONFIG_KEYS, TIMEZONE};
+use crate::error::{ErrorCode, RwError};
+
+/// see <https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-TIMEZONE>
+///
+/// Only IANA time zone names (e.g. `Asia/Shanghai`) are supported. `LOCAL` and `DEFAULT` reset
+/// the time zone to `UTC`. The name is matched case-insensitively and stored in its canonical
+/// form, so that it can be passed down to expressions as is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimeZone(Tz);
+
+impl TimeZone {
+    pub fn tz(&self) -> Tz {
+        self.0
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self(Tz::UTC)
+    }
+}
+
+impl ConfigEntry for TimeZone {
+    fn entry_name() -> &'static str {
+        CONFIG_KEYS[TIMEZONE]
+    }
+}
+
+impl TryFrom<&[&str]> for TimeZone {
+    type Error = RwError;
+
+    fn try_from(value: &[&str]) -> Result<Self, Self::Error> {
+        if value.len() != 1 {
+            return Err(ErrorCode::InternalError(format!(
+                "SET {} takes only one argument",
+                Self::entry_name()
+            ))
+            .into());
+        }
+
+        // String literals are passed in with their quotes, e.g. `'Asia/Shanghai'`.
+        let s = value[0].trim().trim_matches('\'');
+        if s.eq_ignore_ascii_case("LOCAL") || s.eq_ignore_ascii_case("DEFAULT") {
+            return Ok(Self::default());
+        }
+        s.parse::<Tz>()
+            .ok()
+            .or_else(|| {
+                TZ_VARIANTS
+                    .iter()
+                    .find(|tz| tz.name().eq_ignore_ascii_case(s))
+                    .copied()
+            })
+            .map(Self)
+            .ok_or_else(|| {
+                ErrorCode::InvalidConfigValue {
+                    config_entry: Self::entry_name().to_string(),
+                    config_value: s.to_string(),
+                }
+                .into()
+            })
+    }
+}
+
+impl std::fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timezone() {
+        let tz = TimeZone::try_from(["'Asia/Shanghai'"].as_slice()).unwrap();
+        assert_eq!(tz.name(), "Asia/Shanghai");
+        let tz = TimeZone::try_from(["america/new_york"].as_slice()).unwrap();
+        assert_eq!(tz.name(), "America/New_York");
+        let tz = TimeZone::try_from(["LOCAL"].as_slice()).unwrap();
+        assert_eq!(tz, TimeZone::default());
+        assert!(TimeZone::try_from(["'Mars/Olympus_Mons'"].as_slice()).is_err());
+        assert!(TimeZone::try_from(["UTC", "UTC"].as_slice()).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use super::{Scalar, ScalarImpl, ScalarRef};
+
+/// An owned `jsonb` value.
+///
+/// Like PostgreSQL, the keys of objects are deduplicated and sorted, so that equal values always
+/// have the same representation. Comparison and memcomparable encoding are both defined on the
+/// compact text of the value, which keeps them consistent with each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonbVal(Box<Value>);
+
+/// A reference to a `jsonb` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonbRef<'a>(&'a Value);
+
+impl JsonbVal {
+    pub fn from_serde(value: Value) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn into_serde(self) -> Value {
+        *self.0
+    }
+
+    /// The `null` literal of JSON, which is different from SQL `NULL`.
+    pub fn null() -> Self {
+        Self::from_serde(Value::Null)
+    }
+}
+
+impl FromStr for JsonbVal {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map(Self::from_serde)
+    }
+}
+
+impl Scalar for JsonbVal {
+    type ScalarRefType<'a> = JsonbRef<'a>;
+
+    fn as_scalar_ref(&self) -> JsonbRef<'_> {
+        JsonbRef(&self.0)
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Jsonb(self)
+    }
+}
+
+impl<'a> ScalarRef<'a> for JsonbRef<'a> {
+    type ScalarType = JsonbVal;
+
+    fn to_owned_scalar(&self) -> JsonbVal {
+        JsonbVal::from_serde(self.0.clone())
+    }
+}
+
+impl PartialOrd for JsonbVal {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbVal {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_scalar_ref().cmp(&other.as_scalar_ref())
+    }
+}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value_serialize().cmp(&other.value_serialize())
+    }
+}
+
+impl Hash for JsonbVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_scalar_ref().hash(state)
+    }
+}
+
+impl Hash for JsonbRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.value_serialize())
+    }
+}
+
+impl fmt::Display for JsonbVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_scalar_ref().fmt(f)
+    }
+}
+
+/// Formats the value the same as PostgreSQL, with a space after each `,` and `:`.
+impl fmt::Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn fmt_value(value: &Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match value {
+                Value::Array(array) => {
+                    f.write_str("[")?;
+                    for (i, v) in array.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        fmt_value(v, f)?;
+                    }
+                    f.write_str("]")
+                }
+                Value::Object(object) => {
+                    f.write_str("{")?;
+                    for (i, (k, v)) in object.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        write!(f, "{}: ", Value::from(k.as_str()))?;
+                        fmt_value(v, f)?;
+                    }
+                    f.write_str("}")
+                }
+                scalar => write!(f, "{}", scalar),
+            }
+        }
+        fmt_value(self.0, f)
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn value(&self) -> &'a Value {
+        self.0
+    }
+
+    /// Encodes the value as its compact text, which is used for value encoding, memcomparable
+    /// encoding and comparison.
+    pub fn value_serialize(&self) -> Vec<u8> {
+        serde_json::to_vec(self.0).unwrap()
+    }
+
+    pub fn value_deserialize(buf: &[u8]) -> Option<JsonbVal> {
+        serde_json::from_slice(buf).ok().map(JsonbVal::from_serde)
+    }
+
+    /// The type of the outermost value, as returned by `jsonb_typeof`.
+    pub fn type_name(&self) -> &'static str {
+        match self.0 {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// The number of elements of an array, or `None` if the value is not an array.
+    pub fn array_len(&self) -> Option<usize> {
+        self.0.as_array().map(Vec::len)
+    }
+
+    /// The field `key` of an object, as the `->` operator with a text operand.
+    pub fn access_object_field(&self, key: &str) -> Option<JsonbRef<'a>> {
+        self.0.as_object()?.get(key).map(JsonbRef)
+    }
+
+    /// The element at `index` of an array, as the `->` operator with an integer operand. Negative
+    /// indexes count from the end of the array.
+    pub fn access_array_element(&self, index: i32) -> Option<JsonbRef<'a>> {
+        let array = self.0.as_array()?;
+        let index = if index < 0 {
+            array.len().checked_sub(index.unsigned_abs() as usize)?
+        } else {
+            index as usize
+        };
+        array.get(index).map(JsonbRef)
+    }
+
+    /// The value as text, as the `->>` operator. Strings are unquoted and the JSON `null` becomes
+    /// SQL `NULL`.
+    pub fn as_text(&self) -> Option<String> {
+        match self.0 {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// Whether this value contains `other`, as the `@>` operator.
+    pub fn contains(&self, other: JsonbRef<'_>) -> bool {
+        fn contains(left: &Value, right: &Value) -> bool {
+            match (left, right) {
+                (Value::Object(left), Value::Object(right)) => right
+                    .iter()
+                    .all(|(k, r)| left.get(k).map_or(false, |l| contains(l, r))),
+                (Value::Array(left), Value::Array(right)) => right.iter().all(|r| {
+                    left.iter().any(|l| match (l, r) {
+                        (Value::Object(_), Value::Object(_))
+                        | (Value::Array(_), Value::Array(_)) => contains(l, r),
+                        _ => l == r,
+                    })
+                }),
+                // As a special case, an array contains a primitive value that is its element.
+                (Value::Array(left), right) if !right.is_array() && !right.is_object() => {
+                    left.contains(right)
+                }
+                (left, right) => left == right,
+            }
+        }
+        contains(self.0, other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_jsonb_display() {
+        assert_eq!(
+            jsonb(r#"{"b":[1,"x",null],"a":{"c":true}}"#).to_string(),
+            r#"{"a": {"c": true}, "b": [1, "x", null]}"#
+        );
+        assert_eq!(jsonb(r#""a\"b""#).to_string(), r#""a\"b""#);
+    }
+
+    #[test]
+    fn test_jsonb_access() {
+        let v = jsonb(r#"{"a":[1,2,{"b":"x"}],"c":null}"#);
+        let v = v.as_scalar_ref();
+        let a = v.access_object_field("a").unwrap();
+        assert_eq!(a.array_len(), Some(3));
+        assert_eq!(a.type_name(), "array");
+        assert_eq!(a.access_array_element(0).unwrap().as_text().unwrap(), "1");
+        assert_eq!(
+            a.access_array_element(-1)
+                .unwrap()
+                .access_object_field("b")
+                .unwrap()
+                .as_text()
+                .unwrap(),
+            "x"
+        );
+        assert!(a.access_array_element(3).is_none());
+        assert!(a.access_array_element(-4).is_none());
+        assert!(v.access_object_field("c").unwrap().as_text().is_none());
+        assert!(v.access_object_field("d").is_none());
+        assert!(v.access_array_element(0).is_none());
+    }
+
+    #[test]
+    fn test_jsonb_contains() {
+        let contains =
+            |l: &str, r: &str| jsonb(l).as_scalar_ref().contains(jsonb(r).as_scalar_ref());
+        assert!(contains(r#"{"a":1,"b":[1,2,3]}"#, r#"{"b":[3,1]}"#));
+        assert!(!contains(r#"{"a":1,"b":[1,2,3]}"#, r#"{"b":[4]}"#));
+        assert!(contains(r#"[1,[2,3],{"a":1,"b":2}]"#, r#"[[3],{"a":1}]"#));
+        assert!(contains(r#"["foo","bar"]"#, r#""foo""#));
+        assert!(!contains(r#""foo""#, r#"["foo"]"#));
+        assert!(contains("1", "1"));
+        assert!(!contains(r#"{"a":1}"#, r#"{"a":1,"b":2}"#));
+    }
+}
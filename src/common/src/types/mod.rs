@@ -37,6 +37,7 @@ pub use scalar_impl::*;
 pub mod chrono_wrapper;
 pub mod decimal;
 pub mod interval;
+pub mod jsonb;
 pub mod struct_type;
 
 mod ordered_float;
@@ -48,6 +49,7 @@ pub use chrono_wrapper::{
 pub use decimal::Decimal;
 pub use interval::*;
 use itertools::Itertools;
+pub use jsonb::{JsonbRef, JsonbVal};
 pub use ops::{CheckedAdd, IsNegative};
 pub use ordered_float::IntoOrdered;
 use paste::paste;
@@ -107,6 +109,10 @@ pub enum DataType {
     Timestampz,
     #[display("interval")]
     Interval,
+    #[display("bytea")]
+    Bytea,
+    #[display("jsonb")]
+    Jsonb,
     #[display("{0}")]
     Struct(Arc<StructType>),
     #[display("{datatype}[]")]
@@ -128,7 +134,9 @@ impl DataTypeName {
             | DataTypeName::Timestamp
             | DataTypeName::Timestampz
             | DataTypeName::Time
-            | DataTypeName::Interval => true,
+            | DataTypeName::Interval
+            | DataTypeName::Bytea
+            | DataTypeName::Jsonb => true,
 
             DataTypeName::Struct | DataTypeName::List => false,
         }
@@ -149,6 +157,8 @@ impl DataTypeName {
             DataTypeName::Timestampz => DataType::Timestampz,
             DataTypeName::Time => DataType::Time,
             DataTypeName::Interval => DataType::Interval,
+            DataTypeName::Bytea => DataType::Bytea,
+            DataTypeName::Jsonb => DataType::Jsonb,
             DataTypeName::Struct | DataTypeName::List => {
                 return None;
             }
@@ -186,6 +196,8 @@ impl From<&ProstDataType> for DataType {
             TypeName::Timestampz => DataType::Timestampz,
             TypeName::Decimal => DataType::Decimal,
             TypeName::Interval => DataType::Interval,
+            TypeName::Bytea => DataType::Bytea,
+            TypeName::Jsonb => DataType::Jsonb,
             TypeName::Struct => {
                 let fields: Vec<DataType> = proto.field_type.iter().map(|f| f.into()).collect_vec();
                 let field_names: Vec<String> = proto.field_names.iter().cloned().collect_vec();
@@ -217,6 +229,8 @@ impl DataType {
             DataType::Timestamp => NaiveDateTimeArrayBuilder::new(capacity).into(),
             DataType::Timestampz => PrimitiveArrayBuilder::<i64>::new(capacity).into(),
             DataType::Interval => IntervalArrayBuilder::new(capacity).into(),
+            DataType::Bytea => BytesArrayBuilder::new(capacity).into(),
+            DataType::Jsonb => JsonbArrayBuilder::new(capacity).into(),
             DataType::Struct(t) => {
                 StructArrayBuilder::with_meta(capacity, t.to_array_meta()).into()
             }
@@ -245,6 +259,8 @@ impl DataType {
             DataType::Timestampz => TypeName::Timestampz,
             DataType::Decimal => TypeName::Decimal,
             DataType::Interval => TypeName::Interval,
+            DataType::Bytea => TypeName::Bytea,
+            DataType::Jsonb => TypeName::Jsonb,
             DataType::Struct { .. } => TypeName::Struct,
             DataType::List { .. } => TypeName::List,
         }
@@ -304,7 +320,7 @@ impl DataType {
         match self {
             Boolean | Int16 | Int32 | Int64 => true,
             Float32 | Float64 | Decimal | Date | Varchar | Time | Timestamp | Timestampz
-            | Interval => false,
+            | Interval | Bytea | Jsonb => false,
             Struct(t) => t.fields.iter().all(|dt| dt.mem_cmp_eq_value_enc()),
             List { datatype } => datatype.mem_cmp_eq_value_enc(),
         }
@@ -401,6 +417,8 @@ macro_rules! for_all_scalar_variants {
             { NaiveDate, naivedate, NaiveDateWrapper, NaiveDateWrapper },
             { NaiveDateTime, naivedatetime, NaiveDateTimeWrapper, NaiveDateTimeWrapper },
             { NaiveTime, naivetime, NaiveTimeWrapper, NaiveTimeWrapper },
+            { Bytea, bytea, Box<[u8]>, &'scalar [u8] },
+            { Jsonb, jsonb, JsonbVal, JsonbRef<'scalar> },
             { Struct, struct, StructValue, StructRef<'scalar> },
             { List, list, ListValue, ListRef<'scalar> }
         }
@@ -411,22 +429,62 @@ macro_rules! for_all_scalar_variants {
 macro_rules! scalar_impl_enum {
     ($( { $variant_name:ident, $suffix_name:ident, $scalar:ty, $scalar_ref:ty } ),*) => {
         /// `ScalarImpl` embeds all possible scalars in the evaluation framework.
-        #[derive(Debug, Display, Clone, PartialEq, Eq)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
         pub enum ScalarImpl {
-            $( #[display("{0}")] $variant_name($scalar) ),*
+            $( $variant_name($scalar) ),*
         }
 
         /// `ScalarRefImpl` embeds all possible scalar references in the evaluation
         /// framework.
-        #[derive(Debug, Display, Copy, Clone, PartialEq, Eq)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
         pub enum ScalarRefImpl<'scalar> {
-            $( #[display("{0}")] $variant_name($scalar_ref) ),*
+            $( $variant_name($scalar_ref) ),*
         }
     };
 }
 
 for_all_scalar_variants! { scalar_impl_enum }
 
+impl std::fmt::Display for ScalarImpl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_scalar_ref_impl().fmt(f)
+    }
+}
+
+/// Most scalars are displayed with their own `Display`, except that `bytea`, which is a plain
+/// byte slice, is displayed in the hex format of PostgreSQL.
+impl std::fmt::Display for ScalarRefImpl<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int16(v) => v.fmt(f),
+            Self::Int32(v) => v.fmt(f),
+            Self::Int64(v) => v.fmt(f),
+            Self::Float32(v) => v.fmt(f),
+            Self::Float64(v) => v.fmt(f),
+            Self::Utf8(v) => v.fmt(f),
+            Self::Bool(v) => v.fmt(f),
+            Self::Decimal(v) => v.fmt(f),
+            Self::Interval(v) => v.fmt(f),
+            Self::NaiveDate(v) => v.fmt(f),
+            Self::NaiveDateTime(v) => v.fmt(f),
+            Self::NaiveTime(v) => v.fmt(f),
+            Self::Bytea(v) => write_bytea(v, f),
+            Self::Jsonb(v) => v.fmt(f),
+            Self::Struct(v) => v.fmt(f),
+            Self::List(v) => v.fmt(f),
+        }
+    }
+}
+
+/// Writes `bytea` in the hex format of PostgreSQL, e.g. `\x0aff`.
+pub fn write_bytea(v: &[u8], f: &mut impl std::fmt::Write) -> std::fmt::Result {
+    f.write_str("\\x")?;
+    for b in v {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
 /// Implement `PartialOrd` and `Ord` for `ScalarImpl` and `ScalarRefImpl` with macro.
 macro_rules! scalar_impl_partial_ord {
     ($( { $variant_name:ident, $suffix_name:ident, $scalar:ty, $scalar_ref:ty } ),*) => {
@@ -706,6 +764,8 @@ impl Hash for ScalarImpl {
                     // Manually implemented
                     Self::Bool(b) => b.hash(state),
                     Self::Utf8(s) => state.write(s.as_bytes()),
+                    Self::Bytea(b) => state.write(b),
+                    Self::Jsonb(j) => j.hash(state),
                     Self::Decimal(decimal) => decimal.normalize().hash(state),
                     Self::Struct(v) => v.hash(state), // TODO: check if this is consistent with `StructArray::hash_at`
                     Self::List(v) => v.hash(state),   // TODO: check if this is consistent with `ListArray::hash_at`
@@ -763,6 +823,13 @@ impl ScalarRefImpl<'_> {
             Self::Struct(_) => todo!("Don't support struct serialization yet"),
             Self::List(_) => todo!("Don't support list serialization yet"),
             Self::Interval(v) => v.to_sql(ty, &mut output).unwrap(),
+            Self::Bytea(v) => v.to_sql(ty, &mut output).unwrap(),
+            Self::Jsonb(v) => {
+                // The binary format of `jsonb` is a version number followed by the text.
+                output.put_u8(1);
+                output.put_slice(&v.value_serialize());
+                IsNull::No
+            }
         };
         output.freeze()
     }
@@ -792,6 +859,8 @@ impl ScalarRefImpl<'_> {
             Self::NaiveTime(v) => {
                 ser.serialize_naivetime(v.0.num_seconds_from_midnight(), v.0.nanosecond())?
             }
+            Self::Bytea(v) => serde::Serializer::serialize_bytes(&mut *ser, v)?,
+            Self::Jsonb(v) => serde::Serializer::serialize_bytes(&mut *ser, &v.value_serialize())?,
             Self::Struct(v) => v.serialize(ser)?,
             Self::List(v) => v.serialize(ser)?,
         };
@@ -845,6 +914,11 @@ impl ScalarImpl {
                 let days = de.deserialize_naivedate()?;
                 NaiveDateWrapper::with_days(days)?
             }),
+            Ty::Bytea => Self::Bytea(de.read_bytes()?.into()),
+            Ty::Jsonb => Self::Jsonb(
+                JsonbRef::value_deserialize(&de.read_bytes()?)
+                    .ok_or(memcomparable::Error::Message("invalid jsonb".to_string()))?,
+            ),
             Ty::Struct(t) => StructValue::deserialize(&t.fields, de)?.to_scalar_value(),
             Ty::List { datatype } => ListValue::deserialize(datatype, de)?.to_scalar_value(),
         })
@@ -887,7 +961,9 @@ impl ScalarImpl {
                         .iter()
                         .map(|field| Self::encoding_data_size(field, deserializer))
                         .try_fold(0, |a, b| b.map(|b| a + b))?,
-                    DataType::Varchar => deserializer.read_bytes_len()?,
+                    DataType::Varchar | DataType::Bytea | DataType::Jsonb => {
+                        deserializer.read_bytes_len()?
+                    }
                 };
 
                 // consume offset of fixed_type
@@ -921,6 +997,8 @@ pub fn literal_type_match(data_type: &DataType, literal: Option<&ScalarImpl>) ->
                     | (DataType::Timestampz, ScalarImpl::Int64(_))
                     | (DataType::Decimal, ScalarImpl::Decimal(_))
                     | (DataType::Interval, ScalarImpl::Interval(_))
+                    | (DataType::Bytea, ScalarImpl::Bytea(_))
+                    | (DataType::Jsonb, ScalarImpl::Jsonb(_))
                     | (DataType::Struct { .. }, ScalarImpl::Struct(_))
                     | (DataType::List { .. }, ScalarImpl::List(_))
             )
@@ -1010,6 +1088,52 @@ mod tests {
         assert_eq!(floats, decoded_floats);
     }
 
+    #[test]
+    fn test_bytea_jsonb_memcomparable() {
+        fn serialize(datum: ScalarImpl) -> Vec<u8> {
+            let mut serializer = memcomparable::Serializer::new(vec![]);
+            serialize_datum_not_null_into(&Some(datum), &mut serializer).unwrap();
+            serializer.into_inner()
+        }
+
+        fn deserialize(ty: &DataType, data: Vec<u8>) -> ScalarImpl {
+            let mut deserializer = memcomparable::Deserializer::new(data.as_slice());
+            deserialize_datum_not_null_from(ty, &mut deserializer)
+                .unwrap()
+                .unwrap()
+        }
+
+        let bytes: Vec<ScalarImpl> = [&b""[..], b"\x00", b"\x00\x01", b"\x01", b"\xff"]
+            .iter()
+            .map(|b| ScalarImpl::Bytea((*b).into()))
+            .collect();
+        assert!(bytes.is_sorted());
+        let memcomparables = bytes.iter().cloned().map(serialize).collect_vec();
+        assert!(memcomparables.is_sorted());
+        for (datum, data) in bytes.into_iter().zip_eq(memcomparables) {
+            assert_eq!(deserialize(&DataType::Bytea, data), datum);
+        }
+
+        let jsonbs: Vec<ScalarImpl> = [r#"[1, 2]"#, r#"{"a": {"b": null}}"#, r#""x""#]
+            .iter()
+            .map(|s| ScalarImpl::Jsonb(s.parse().unwrap()))
+            .collect();
+        for datum in jsonbs {
+            assert_eq!(
+                deserialize(&DataType::Jsonb, serialize(datum.clone())),
+                datum
+            );
+        }
+    }
+
+    #[test]
+    fn test_bytea_display() {
+        assert_eq!(
+            ScalarImpl::Bytea(b"\x00\x1f\xff".to_vec().into()).to_string(),
+            "\\x001fff"
+        );
+    }
+
     #[test]
     fn test_size() {
         assert_eq!(std::mem::size_of::<StructValue>(), 16);
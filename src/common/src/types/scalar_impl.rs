@@ -104,6 +104,29 @@ impl<'a> ScalarRef<'a> for &'a str {
     }
 }
 
+/// Implement `Scalar` for `Box<[u8]>`.
+/// `Box<[u8]>` could be converted to `&[u8]`.
+impl Scalar for Box<[u8]> {
+    type ScalarRefType<'a> = &'a [u8];
+
+    fn as_scalar_ref(&self) -> &[u8] {
+        self
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Bytea(self)
+    }
+}
+
+/// Implement `ScalarRef` for `&[u8]`.
+impl<'a> ScalarRef<'a> for &'a [u8] {
+    type ScalarType = Box<[u8]>;
+
+    fn to_owned_scalar(&self) -> Box<[u8]> {
+        (*self).into()
+    }
+}
+
 impl ScalarPartialOrd for Decimal {
     fn scalar_cmp(&self, other: Self) -> Option<std::cmp::Ordering> {
         self.partial_cmp(&other)
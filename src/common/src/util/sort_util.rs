@@ -175,6 +175,8 @@ pub fn compare_rows(lhs: &Row, rhs: &Row, order_pairs: &[OrderPair]) -> Result<O
                 NaiveDate,
                 NaiveDateTime,
                 NaiveTime,
+                Bytea,
+                Jsonb,
                 Struct,
                 List
             ]
@@ -240,6 +242,8 @@ pub fn compare_rows_in_chunk(
                 NaiveDate,
                 NaiveDateTime,
                 NaiveTime,
+                Bytea,
+                Jsonb,
                 Struct,
                 List
             ]
@@ -333,6 +337,8 @@ mod tests {
             Some(ScalarImpl::NaiveDate(Default::default())),
             Some(ScalarImpl::NaiveDateTime(Default::default())),
             Some(ScalarImpl::NaiveTime(Default::default())),
+            Some(ScalarImpl::Bytea(b"\x00\xff".to_vec().into())),
+            Some(ScalarImpl::Jsonb(r#"{"a": [1]}"#.parse().unwrap())),
             Some(ScalarImpl::Struct(StructValue::new(vec![
                 Some(ScalarImpl::Int32(1)),
                 Some(ScalarImpl::Float32(3.0.into())),
@@ -355,6 +361,8 @@ mod tests {
             Some(ScalarImpl::NaiveDate(Default::default())),
             Some(ScalarImpl::NaiveDateTime(Default::default())),
             Some(ScalarImpl::NaiveTime(Default::default())),
+            Some(ScalarImpl::Bytea(b"\x00\xff".to_vec().into())),
+            Some(ScalarImpl::Jsonb(r#"{"a": [1]}"#.parse().unwrap())),
             Some(ScalarImpl::Struct(StructValue::new(vec![
                 Some(ScalarImpl::Int32(1)),
                 Some(ScalarImpl::Float32(33333.0.into())), // larger than row1
@@ -392,6 +400,8 @@ mod tests {
                 DataType::Date,
                 DataType::Timestamp,
                 DataType::Time,
+                DataType::Bytea,
+                DataType::Jsonb,
                 DataType::new_struct(vec![DataType::Int32, DataType::Float32], vec![]),
                 DataType::List {
                     datatype: Box::new(DataType::Int32),
@@ -32,4 +32,8 @@ pub enum ValueEncodingError {
     InvalidStructEncoding(crate::array::ArrayError),
     #[error("Invalid list encoding: {0}")]
     InvalidListEncoding(crate::array::ArrayError),
+    #[error("Invalid jsonb encoding")]
+    InvalidJsonbEncoding,
+    #[error("Missing value encoding of column {0}")]
+    MissingColumn(usize),
 }
@@ -22,8 +22,9 @@ use itertools::Itertools;
 use crate::array::{ListRef, ListValue, StructRef, StructValue};
 use crate::types::struct_type::StructType;
 use crate::types::{
-    to_datum_ref, DataType, Datum, DatumRef, Decimal, IntervalUnit, NaiveDateTimeWrapper,
-    NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarImpl, ScalarRefImpl,
+    to_datum_ref, DataType, Datum, DatumRef, Decimal, IntervalUnit, JsonbRef, JsonbVal,
+    NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarImpl,
+    ScalarRefImpl,
 };
 
 pub mod error;
@@ -87,6 +88,8 @@ fn serialize_value(value: ScalarRefImpl<'_>, buf: &mut impl BufMut) {
         ScalarRefImpl::NaiveTime(v) => {
             serialize_naivetime(v.0.num_seconds_from_midnight(), v.0.nanosecond(), buf)
         }
+        ScalarRefImpl::Bytea(v) => serialize_str(v, buf),
+        ScalarRefImpl::Jsonb(v) => serialize_str(&v.value_serialize(), buf),
         ScalarRefImpl::Struct(s) => serialize_struct(s, buf),
         ScalarRefImpl::List(list) => serialize_list(list, buf),
     }
@@ -158,6 +161,8 @@ fn deserialize_value(ty: &DataType, data: &mut impl Buf) -> Result<Datum> {
         DataType::Timestamp => ScalarImpl::NaiveDateTime(deserialize_naivedatetime(data)?),
         DataType::Timestampz => ScalarImpl::Int64(data.get_i64_le()),
         DataType::Date => ScalarImpl::NaiveDate(deserialize_naivedate(data)?),
+        DataType::Bytea => ScalarImpl::Bytea(deserialize_bytea(data).into()),
+        DataType::Jsonb => ScalarImpl::Jsonb(deserialize_jsonb(data)?),
         DataType::Struct(struct_def) => deserialize_struct(struct_def, data)?,
         DataType::List {
             datatype: item_type,
@@ -191,6 +196,18 @@ fn deserialize_str(data: &mut impl Buf) -> Result<String> {
     String::from_utf8(bytes).map_err(ValueEncodingError::InvalidUtf8)
 }
 
+fn deserialize_bytea(data: &mut impl Buf) -> Vec<u8> {
+    let len = data.get_u32_le();
+    let mut bytes = vec![0; len as usize];
+    data.copy_to_slice(&mut bytes);
+    bytes
+}
+
+fn deserialize_jsonb(data: &mut impl Buf) -> Result<JsonbVal> {
+    let bytes = deserialize_bytea(data);
+    JsonbRef::value_deserialize(&bytes).ok_or(ValueEncodingError::InvalidJsonbEncoding)
+}
+
 fn deserialize_bool(data: &mut impl Buf) -> Result<bool> {
     match data.get_u8() {
         1 => Ok(true),
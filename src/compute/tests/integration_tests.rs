@@ -242,7 +242,7 @@ async fn test_table_materialize() -> StreamResult<()> {
             assert_eq!(col_data.value_at(0).unwrap(), 1.14.into_ordered());
             assert_eq!(col_data.value_at(1).unwrap(), 5.14.into_ordered());
         }
-        Message::Barrier(_) => panic!(),
+        Message::Barrier(_) | Message::Watermark(_) => panic!(),
     }
 
     // Send a barrier and poll again, should write changes to storage
@@ -312,7 +312,7 @@ async fn test_table_materialize() -> StreamResult<()> {
             let col_data = c.columns()[1].array_ref().as_float64();
             assert_eq!(col_data.value_at(0).unwrap(), 1.14.into_ordered());
         }
-        Message::Barrier(_) => panic!(),
+        Message::Barrier(_) | Message::Watermark(_) => panic!(),
     }
 
     // Send a barrier and poll again, should write changes to storage
@@ -0,0 +1,630 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A sink writing rows as files to an object store.
+//!
+//! The files of an epoch are written to `{file.path}/{identifier}/{epoch}/part-{n}.{format}`, and
+//! a new file is started once the current one has `file.max_row_count` rows. When the epoch is
+//! committed, a `_SUCCESS` marker listing the names of the files is written to the same directory.
+//! Readers should only read the directories with the marker, so that each of them is an
+//! exactly-once snapshot of the rows of a checkpoint.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array,
+    Int32Array, Int64Array, StringArray, Time64MicrosecondArray, TimestampMicrosecondArray,
+};
+use arrow::datatypes::{
+    DataType as ArrowDataType, Field as ArrowField, Schema as ArrowSchema,
+    SchemaRef as ArrowSchemaRef, TimeUnit,
+};
+use arrow::record_batch::RecordBatch;
+use bytes::Bytes;
+use chrono::{Datelike, Timelike};
+use itertools::Itertools;
+use parquet::arrow::ArrowWriter;
+use risingwave_common::array::{ArrayImpl, Op, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, UNIX_EPOCH_DAYS};
+use risingwave_object_store::object::object_metrics::ObjectStoreMetrics;
+use risingwave_object_store::object::{parse_remote_object_store, ObjectStoreImpl};
+use serde_json::Value;
+
+use super::kafka::record_to_json;
+use super::{datum_to_text, Sink, SinkError};
+use crate::sink::Result;
+
+pub const FILE_SINK: &str = "file";
+
+/// The default number of rows of a file.
+const DEFAULT_MAX_ROW_COUNT: usize = 1_000_000;
+
+/// The name of the marker written when an epoch is committed.
+const COMMIT_MARKER: &str = "_SUCCESS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Comma-separated values with a header line. `NULL`s are written as empty fields.
+    Csv,
+    /// Newline-delimited JSON objects, encoded the same as the Kafka sink.
+    Json,
+    /// Parquet, where the types without a Parquet counterpart are written as text.
+    Parquet,
+}
+
+impl FileFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            FileFormat::Json => "json",
+            FileFormat::Parquet => "parquet",
+        }
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = SinkError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(FileFormat::Csv),
+            "json" => Ok(FileFormat::Json),
+            "parquet" => Ok(FileFormat::Parquet),
+            _ => Err(SinkError::Config(format!(
+                "file.format must be one of \"csv\", \"json\" and \"parquet\", got \"{}\"",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileConfig {
+    /// The object store to write to, e.g. `s3://bucket`, `disk:///path/to/dir` or `memory`.
+    pub url: String,
+
+    /// The directory in the object store under which the files are written.
+    pub path: String,
+
+    pub format: FileFormat,
+
+    /// A new file is started once the current one has at least this many rows.
+    pub max_row_count: usize,
+
+    pub identifier: String,
+}
+
+impl FileConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let url = values
+            .get("file.url")
+            .ok_or_else(|| SinkError::Config("file.url must be set".to_string()))?;
+        if !(["s3://", "minio://", "disk://"]
+            .iter()
+            .any(|prefix| url.starts_with(prefix))
+            || url == "memory"
+            || url == "memory-shared")
+        {
+            return Err(SinkError::Config(format!(
+                "file.url must be an s3, minio, disk or memory object store, got \"{}\"",
+                url
+            )));
+        }
+        let path = values
+            .get("file.path")
+            .map(|path| path.trim_matches('/').to_string())
+            .unwrap_or_default();
+        let format = match values.get("file.format") {
+            Some(format) => format.parse()?,
+            None => FileFormat::Csv,
+        };
+        let max_row_count = match values.get("file.max_row_count") {
+            Some(count) => count
+                .parse()
+                .ok()
+                .filter(|count| *count > 0)
+                .ok_or_else(|| {
+                    SinkError::Config(format!(
+                        "file.max_row_count must be a positive integer, got \"{}\"",
+                        count
+                    ))
+                })?,
+            None => DEFAULT_MAX_ROW_COUNT,
+        };
+        let identifier = values
+            .get("identifier")
+            .ok_or_else(|| SinkError::Config("identifier must be set".to_string()))?;
+
+        Ok(FileConfig {
+            url: url.to_string(),
+            path,
+            format,
+            max_row_count,
+            identifier: identifier.to_string(),
+        })
+    }
+}
+
+pub struct FileSink {
+    pub config: FileConfig,
+    store: ObjectStoreImpl,
+    /// The epoch of the current transaction, which names the directory of the files.
+    in_transaction_epoch: Option<u64>,
+    /// The file being written, which is not uploaded yet.
+    writer: Option<FileWriter>,
+    /// The names of the files of the current epoch that have been uploaded.
+    uploaded_files: Vec<String>,
+}
+
+impl FileSink {
+    pub async fn new(config: FileConfig) -> Result<Self> {
+        let store =
+            parse_remote_object_store(&config.url, Arc::new(ObjectStoreMetrics::unused())).await;
+        Ok(Self::with_store(config, store))
+    }
+
+    fn with_store(config: FileConfig, store: ObjectStoreImpl) -> Self {
+        FileSink {
+            config,
+            store,
+            in_transaction_epoch: None,
+            writer: None,
+            uploaded_files: vec![],
+        }
+    }
+
+    fn epoch_dir(&self, epoch: u64) -> String {
+        if self.config.path.is_empty() {
+            format!("{}/{}", self.config.identifier, epoch)
+        } else {
+            format!("{}/{}/{}", self.config.path, self.config.identifier, epoch)
+        }
+    }
+
+    async fn upload_file(&mut self, epoch: u64, writer: FileWriter) -> Result<()> {
+        let name = format!(
+            "part-{:05}.{}",
+            self.uploaded_files.len(),
+            self.config.format.extension()
+        );
+        let path = format!("{}/{}", self.epoch_dir(epoch), name);
+        self.store.upload(&path, writer.finish()?).await?;
+        self.uploaded_files.push(name);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for FileSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        let epoch = self
+            .in_transaction_epoch
+            .ok_or_else(|| SinkError::File("write without begin_epoch".to_string()))?;
+        if let Some(op) = chunk.ops().iter().find(|op| **op != Op::Insert) {
+            return Err(SinkError::File(format!(
+                "file sink only accepts append-only input, got {:?}",
+                op
+            )));
+        }
+        if chunk.cardinality() == 0 {
+            return Ok(());
+        }
+
+        let writer = match &mut self.writer {
+            Some(writer) => writer,
+            None => self
+                .writer
+                .insert(FileWriter::new(self.config.format, schema)?),
+        };
+        writer.write(&chunk, schema)?;
+        if writer.row_count >= self.config.max_row_count {
+            let writer = self.writer.take().unwrap();
+            self.upload_file(epoch, writer).await?;
+        }
+        Ok(())
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        self.in_transaction_epoch = Some(epoch);
+        self.writer = None;
+        self.uploaded_files.clear();
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        let epoch = self
+            .in_transaction_epoch
+            .ok_or_else(|| SinkError::File("commit without begin_epoch".to_string()))?;
+        if let Some(writer) = self.writer.take() {
+            self.upload_file(epoch, writer).await?;
+        }
+        // The files are visible to readers only after the marker is written.
+        let marker = self
+            .uploaded_files
+            .iter()
+            .map(|name| format!("{}\n", name))
+            .join("");
+        self.store
+            .upload(
+                &format!("{}/{}", self.epoch_dir(epoch), COMMIT_MARKER),
+                Bytes::from(marker),
+            )
+            .await?;
+        tracing::debug!(
+            "commit epoch {} with {} files",
+            epoch,
+            self.uploaded_files.len()
+        );
+        self.in_transaction_epoch = None;
+        self.uploaded_files.clear();
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.writer = None;
+        if let Some(epoch) = self.in_transaction_epoch.take() {
+            let dir = self.epoch_dir(epoch);
+            let paths = self
+                .uploaded_files
+                .drain(..)
+                .map(|name| format!("{}/{}", dir, name))
+                .collect_vec();
+            self.store.delete_objects(&paths).await?;
+            tracing::debug!("abort epoch {}", epoch);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for FileSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileSink")
+            .field("config", &self.config)
+            .field("in_transaction_epoch", &self.in_transaction_epoch)
+            .field("uploaded_files", &self.uploaded_files)
+            .finish()
+    }
+}
+
+/// Encodes the rows of a file in memory.
+struct FileWriter {
+    inner: FileWriterInner,
+    row_count: usize,
+}
+
+enum FileWriterInner {
+    Csv(csv::Writer<Vec<u8>>),
+    Json(Vec<u8>),
+    Parquet(ArrowSchemaRef, Vec<RecordBatch>),
+}
+
+impl FileWriter {
+    fn new(format: FileFormat, schema: &Schema) -> Result<Self> {
+        let inner = match format {
+            FileFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(vec![]);
+                writer
+                    .write_record(schema.names())
+                    .map_err(|e| SinkError::File(e.to_string()))?;
+                FileWriterInner::Csv(writer)
+            }
+            FileFormat::Json => FileWriterInner::Json(vec![]),
+            FileFormat::Parquet => FileWriterInner::Parquet(schema_to_arrow(schema), vec![]),
+        };
+        Ok(FileWriter {
+            inner,
+            row_count: 0,
+        })
+    }
+
+    fn write(&mut self, chunk: &StreamChunk, schema: &Schema) -> Result<()> {
+        match &mut self.inner {
+            FileWriterInner::Csv(writer) => {
+                for (_, row) in chunk.rows() {
+                    let record = row
+                        .values()
+                        .zip_eq(schema.fields())
+                        .map(|(datum, field)| datum_to_text(&field.data_type, datum));
+                    writer
+                        .write_record(record)
+                        .map_err(|e| SinkError::File(e.to_string()))?;
+                }
+            }
+            FileWriterInner::Json(buf) => {
+                for (_, row) in chunk.rows() {
+                    let record = Value::Object(record_to_json(row, schema.fields.clone())?);
+                    serde_json::to_writer(&mut *buf, &record)
+                        .map_err(|e| SinkError::JsonParse(e.to_string()))?;
+                    buf.push(b'\n');
+                }
+            }
+            FileWriterInner::Parquet(arrow_schema, batches) => {
+                let columns = chunk
+                    .columns()
+                    .iter()
+                    .zip_eq(schema.fields())
+                    .map(|(column, field)| array_to_arrow(&field.data_type, column.array_ref()))
+                    .collect();
+                let batch = RecordBatch::try_new(arrow_schema.clone(), columns)
+                    .map_err(|e| SinkError::File(e.to_string()))?;
+                batches.push(batch);
+            }
+        }
+        self.row_count += chunk.cardinality();
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Bytes> {
+        let buf = match self.inner {
+            FileWriterInner::Csv(writer) => writer
+                .into_inner()
+                .map_err(|e| SinkError::File(e.to_string()))?,
+            FileWriterInner::Json(buf) => buf,
+            FileWriterInner::Parquet(arrow_schema, batches) => {
+                let mut buf = vec![];
+                let mut writer = ArrowWriter::try_new(&mut buf, arrow_schema, None)
+                    .map_err(|e| SinkError::File(e.to_string()))?;
+                for batch in &batches {
+                    writer
+                        .write(batch)
+                        .map_err(|e| SinkError::File(e.to_string()))?;
+                }
+                writer.close().map_err(|e| SinkError::File(e.to_string()))?;
+                buf
+            }
+        };
+        Ok(Bytes::from(buf))
+    }
+}
+
+fn schema_to_arrow(schema: &Schema) -> ArrowSchemaRef {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| ArrowField::new(&field.name, data_type_to_arrow(&field.data_type), true))
+        .collect();
+    Arc::new(ArrowSchema::new(fields))
+}
+
+fn data_type_to_arrow(data_type: &DataType) -> ArrowDataType {
+    match data_type {
+        DataType::Boolean => ArrowDataType::Boolean,
+        DataType::Int16 => ArrowDataType::Int16,
+        DataType::Int32 => ArrowDataType::Int32,
+        DataType::Int64 => ArrowDataType::Int64,
+        DataType::Float32 => ArrowDataType::Float32,
+        DataType::Float64 => ArrowDataType::Float64,
+        DataType::Date => ArrowDataType::Date32,
+        DataType::Time => ArrowDataType::Time64(TimeUnit::Microsecond),
+        // `timestamptz` is stored as microseconds since the epoch in UTC as well.
+        DataType::Timestamp | DataType::Timestampz => {
+            ArrowDataType::Timestamp(TimeUnit::Microsecond, None)
+        }
+        DataType::Bytea => ArrowDataType::Binary,
+        // `decimal` has no fixed precision, so it can't be mapped to a Parquet decimal.
+        DataType::Varchar
+        | DataType::Decimal
+        | DataType::Interval
+        | DataType::Jsonb
+        | DataType::Struct { .. }
+        | DataType::List { .. } => ArrowDataType::Utf8,
+    }
+}
+
+fn array_to_arrow(data_type: &DataType, array: &ArrayImpl) -> ArrayRef {
+    match (data_type, array) {
+        (_, ArrayImpl::Bool(array)) => Arc::new(BooleanArray::from_iter(array.iter())),
+        (_, ArrayImpl::Int16(array)) => Arc::new(Int16Array::from_iter(array.iter())),
+        (_, ArrayImpl::Int32(array)) => Arc::new(Int32Array::from_iter(array.iter())),
+        (DataType::Timestampz, ArrayImpl::Int64(array)) => {
+            Arc::new(TimestampMicrosecondArray::from_iter(array.iter()))
+        }
+        (_, ArrayImpl::Int64(array)) => Arc::new(Int64Array::from_iter(array.iter())),
+        (_, ArrayImpl::Float32(array)) => Arc::new(Float32Array::from_iter(
+            array.iter().map(|v| v.map(f32::from)),
+        )),
+        (_, ArrayImpl::Float64(array)) => Arc::new(Float64Array::from_iter(
+            array.iter().map(|v| v.map(f64::from)),
+        )),
+        (_, ArrayImpl::NaiveDate(array)) => Arc::new(Date32Array::from_iter(
+            array
+                .iter()
+                .map(|v| v.map(|v| v.0.num_days_from_ce() - UNIX_EPOCH_DAYS)),
+        )),
+        (_, ArrayImpl::NaiveTime(array)) => {
+            Arc::new(Time64MicrosecondArray::from_iter(array.iter().map(|v| {
+                v.map(|v| {
+                    v.0.num_seconds_from_midnight() as i64 * 1_000_000
+                        + v.0.nanosecond() as i64 / 1_000
+                })
+            })))
+        }
+        (_, ArrayImpl::NaiveDateTime(array)) => Arc::new(TimestampMicrosecondArray::from_iter(
+            array.iter().map(|v| v.map(|v| v.0.timestamp_micros())),
+        )),
+        (_, ArrayImpl::Bytea(array)) => Arc::new(BinaryArray::from_iter(array.iter())),
+        (_, ArrayImpl::Utf8(array)) => Arc::new(StringArray::from_iter(array.iter())),
+        (_, array) => Arc::new(StringArray::from_iter(
+            array.iter().map(|v| v.map(|v| v.to_string())),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_object_store::object::{InMemObjectStore, ObjectStore};
+
+    use super::*;
+
+    fn new_sink(format: &str, max_row_count: usize) -> FileSink {
+        let config = FileConfig::from_hashmap(hashmap! {
+            "file.url".to_string() => "memory".to_string(),
+            "file.path".to_string() => "/output/".to_string(),
+            "file.format".to_string() => format.to_string(),
+            "file.max_row_count".to_string() => max_row_count.to_string(),
+            "identifier".to_string() => "sink-1".to_string(),
+        })
+        .unwrap();
+        let store = InMemObjectStore::new().monitored(Arc::new(ObjectStoreMetrics::unused()));
+        FileSink::with_store(config, ObjectStoreImpl::InMem(store))
+    }
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::with_name(DataType::Int32, "v1"),
+            Field::with_name(DataType::Varchar, "v2"),
+        ])
+    }
+
+    async fn read(sink: &FileSink, path: &str) -> String {
+        let bytes = sink.store.read(path, None).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_csv() {
+        let mut sink = new_sink("csv", 2);
+        let schema = schema();
+
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(
+            StreamChunk::from_pretty(
+                " i T
+                + 1 a
+                + 2 .",
+            ),
+            &schema,
+        )
+        .await
+        .unwrap();
+        sink.write_batch(
+            StreamChunk::from_pretty(
+                " i T
+                + 3 c",
+            ),
+            &schema,
+        )
+        .await
+        .unwrap();
+        // The first file is rolled but the epoch is not committed yet.
+        assert!(sink
+            .store
+            .list("output/sink-1/1/_SUCCESS")
+            .await
+            .unwrap()
+            .is_empty());
+        sink.commit().await.unwrap();
+
+        assert_eq!(
+            read(&sink, "output/sink-1/1/_SUCCESS").await,
+            "part-00000.csv\npart-00001.csv\n"
+        );
+        assert_eq!(
+            read(&sink, "output/sink-1/1/part-00000.csv").await,
+            "v1,v2\n1,a\n2,\n"
+        );
+        assert_eq!(
+            read(&sink, "output/sink-1/1/part-00001.csv").await,
+            "v1,v2\n3,c\n"
+        );
+
+        // The files of an aborted epoch are removed.
+        sink.begin_epoch(2).await.unwrap();
+        sink.write_batch(
+            StreamChunk::from_pretty(
+                " i T
+                + 4 d
+                + 5 e",
+            ),
+            &schema,
+        )
+        .await
+        .unwrap();
+        sink.abort().await.unwrap();
+        assert!(sink.store.list("output/sink-1/2").await.unwrap().is_empty());
+
+        sink.begin_epoch(3).await.unwrap();
+        assert!(sink
+            .write_batch(
+                StreamChunk::from_pretty(
+                    " i T
+                    - 1 a",
+                ),
+                &schema,
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_json_and_parquet() {
+        let schema = schema();
+        let chunk = StreamChunk::from_pretty(
+            " i T
+            + 1 a
+            + . b",
+        );
+
+        let mut sink = new_sink("json", 100);
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(chunk.clone(), &schema).await.unwrap();
+        sink.commit().await.unwrap();
+        assert_eq!(
+            read(&sink, "output/sink-1/1/part-00000.json").await,
+            "{\"v1\":1,\"v2\":\"a\"}\n{\"v1\":null,\"v2\":\"b\"}\n"
+        );
+
+        let mut sink = new_sink("parquet", 100);
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(chunk, &schema).await.unwrap();
+        sink.commit().await.unwrap();
+        let bytes = sink
+            .store
+            .read("output/sink-1/1/part-00000.parquet", None)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..4], b"PAR1");
+    }
+
+    #[test]
+    fn test_file_config() {
+        let config = |values: HashMap<String, String>| FileConfig::from_hashmap(values);
+        assert!(config(hashmap! {
+            "file.url".to_string() => "hdfs://a".to_string(),
+            "identifier".to_string() => "sink-1".to_string(),
+        })
+        .is_err());
+        assert!(config(hashmap! {
+            "file.url".to_string() => "memory".to_string(),
+            "file.format".to_string() => "avro".to_string(),
+            "identifier".to_string() => "sink-1".to_string(),
+        })
+        .is_err());
+        let config = config(hashmap! {
+            "file.url".to_string() => "s3://bucket".to_string(),
+            "identifier".to_string() => "sink-1".to_string(),
+        })
+        .unwrap();
+        assert_eq!(config.format, FileFormat::Csv);
+        assert_eq!(config.max_row_count, DEFAULT_MAX_ROW_COUNT);
+        assert_eq!(config.path, "");
+    }
+}
@@ -30,7 +30,7 @@ use serde::Deserialize;
 use serde_json::{json, Map, Value};
 use tracing::warn;
 
-use super::{Sink, SinkError};
+use super::{Sink, SinkError, SinkType, SINK_TYPE_OPTION};
 use crate::sink::Result;
 
 pub const KAFKA_SINK: &str = "kafka";
@@ -48,7 +48,7 @@ pub struct KafkaConfig {
     // partition number. The partition number should set by meta.
     pub partition: Option<i32>,
 
-    pub format: String, // accept "append_only" or "debezium"
+    pub sink_type: SinkType,
 
     pub identifier: String,
 
@@ -65,12 +65,13 @@ impl KafkaConfig {
         let identifier = values
             .get("identifier")
             .expect("kafka.identifier must be set");
-        let format = values.get("format").expect("format must be set");
-        if format != "append_only" && format != "debezium" {
-            return Err(SinkError::Config(
-                "format must be set to \"append_only\" or \"debezium\"".to_string(),
-            ));
-        }
+        // `format` is the legacy name of the `type` option.
+        let sink_type = values
+            .get(SINK_TYPE_OPTION)
+            .or_else(|| values.get("format"))
+            .ok_or_else(|| SinkError::Config("type must be set".to_string()))?
+            .parse::<SinkType>()
+            .map_err(|e| SinkError::Config(e.to_string()))?;
 
         let topic = values.get("kafka.topic").expect("kafka.topic must be set");
 
@@ -82,7 +83,7 @@ impl KafkaConfig {
             timeout: Duration::from_secs(5), // default timeout is 5 seconds
             max_retry_num: 3,                // default max retry num is 3
             retry_interval: Duration::from_millis(100), // default retry interval is 100ms
-            format: format.to_string(),
+            sink_type,
         })
     }
 }
@@ -99,15 +100,18 @@ pub struct KafkaSink {
     pub conductor: KafkaTransactionConductor,
     state: KafkaSinkState,
     in_transaction_epoch: Option<u64>,
+    /// The primary key of the upstream, which is the key of the messages in upsert mode.
+    pk_indices: Vec<usize>,
 }
 
 impl KafkaSink {
-    pub async fn new(config: KafkaConfig) -> Result<Self> {
+    pub async fn new(config: KafkaConfig, pk_indices: Vec<usize>) -> Result<Self> {
         Ok(KafkaSink {
             config: config.clone(),
             conductor: KafkaTransactionConductor::new(config).await?,
             in_transaction_epoch: None,
             state: KafkaSinkState::Init,
+            pk_indices,
         })
     }
 
@@ -221,6 +225,48 @@ impl KafkaSink {
         Ok(())
     }
 
+    async fn upsert(&self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        // The key of the last `UpdateDelete`, which is written as a tombstone only if the
+        // following `UpdateInsert` changes the key.
+        let mut update_delete_key: Option<String> = None;
+        for (op, row) in chunk.rows() {
+            let key = Value::Object(pk_to_json(row.clone(), &schema.fields, &self.pk_indices)?)
+                .to_string();
+            if op == Op::UpdateDelete {
+                update_delete_key = Some(key);
+                continue;
+            }
+            if let Some(old_key) = update_delete_key.take() {
+                if old_key != key {
+                    self.send_tombstone(&old_key).await?;
+                }
+            }
+            if op == Op::Delete {
+                self.send_tombstone(&key).await?;
+            } else {
+                let payload =
+                    Value::Object(record_to_json(row, schema.fields.clone())?).to_string();
+                self.send(
+                    BaseRecord::to(self.config.topic.as_str())
+                        .key(key.as_bytes())
+                        .payload(payload.as_bytes()),
+                )
+                .await?;
+            }
+        }
+        if let Some(old_key) = update_delete_key {
+            self.send_tombstone(&old_key).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends a message without payload, which is a tombstone of the key.
+    async fn send_tombstone(&self, key: &str) -> Result<()> {
+        self.send(BaseRecord::<[u8], [u8]>::to(self.config.topic.as_str()).key(key.as_bytes()))
+            .await?;
+        Ok(())
+    }
+
     async fn append_only(&self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
         for (op, row) in chunk.rows() {
             if op == Op::Insert {
@@ -247,9 +293,10 @@ impl Sink for KafkaSink {
 
         println!("sink chunk {:?}", chunk);
 
-        match self.config.format.as_str() {
-            "append_only" => self.append_only(chunk, schema).await,
-            "debezium" => {
+        match self.config.sink_type {
+            SinkType::AppendOnly => self.append_only(chunk, schema).await,
+            SinkType::Upsert => self.upsert(chunk, schema).await,
+            SinkType::Debezium => {
                 self.debezium_update(
                     chunk,
                     schema,
@@ -260,7 +307,6 @@ impl Sink for KafkaSink {
                 )
                 .await
             }
-            _ => unreachable!(),
         }
     }
 
@@ -342,9 +388,23 @@ fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value
             // fixme
             json!(v.to_string())
         }
-        (DataType::Time, ScalarRefImpl::NaiveTime(_v)) => {
-            unimplemented!()
+        (DataType::Date, ScalarRefImpl::NaiveDate(v)) => {
+            json!(v.to_string())
+        }
+        (DataType::Time, ScalarRefImpl::NaiveTime(v)) => {
+            json!(v.to_string())
+        }
+        (DataType::Timestamp, ScalarRefImpl::NaiveDateTime(v)) => {
+            json!(v.to_string())
+        }
+        (DataType::Interval, ScalarRefImpl::Interval(v)) => {
+            json!(v.to_string())
+        }
+        (DataType::Bytea, ScalarRefImpl::Bytea(_)) => {
+            // Encoded in the hex format of PostgreSQL, e.g. `\x0aff`.
+            json!(scalar_ref.to_string())
         }
+        (DataType::Jsonb, ScalarRefImpl::Jsonb(v)) => v.value().clone(),
         (DataType::List { .. }, ScalarRefImpl::List(list_ref)) => {
             let mut vec = Vec::with_capacity(field.sub_fields.len());
             for (sub_datum_ref, sub_field) in list_ref
@@ -375,7 +435,7 @@ fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value
     Ok(value)
 }
 
-fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Value>> {
+pub(crate) fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Value>> {
     let mut mappings = Map::with_capacity(schema.len());
     for (field, datum_ref) in schema.iter().zip_eq(row.values()) {
         let key = field.name.clone();
@@ -386,6 +446,21 @@ fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Val
     Ok(mappings)
 }
 
+fn pk_to_json(
+    row: RowRef<'_>,
+    schema: &[Field],
+    pk_indices: &[usize],
+) -> Result<Map<String, Value>> {
+    let mut mappings = Map::with_capacity(pk_indices.len());
+    for idx in pk_indices {
+        let field = &schema[*idx];
+        let value = datum_to_json_object(field, row.value_at(*idx))
+            .map_err(|e| SinkError::JsonParse(e.to_string()))?;
+        mappings.insert(field.name.clone(), value);
+    }
+    Ok(mappings)
+}
+
 pub fn chunk_to_json(chunk: StreamChunk, schema: &Schema) -> Result<Vec<String>> {
     let mut records: Vec<String> = Vec::with_capacity(chunk.capacity());
     for (_, row) in chunk.rows() {
@@ -497,11 +572,11 @@ mod test {
         let properties = hashmap! {
             "kafka.brokers".to_string() => "localhost:29092".to_string(),
             "identifier".to_string() => "test_sink_1".to_string(),
-            "sink.type".to_string() => "append_only".to_string(),
+            "type".to_string() => "append-only".to_string(),
             "kafka.topic".to_string() => "test_topic".to_string(),
         };
         let kafka_config = KafkaConfig::from_hashmap(properties)?;
-        let mut sink = KafkaSink::new(kafka_config.clone()).await.unwrap();
+        let mut sink = KafkaSink::new(kafka_config.clone(), vec![]).await.unwrap();
 
         for i in 0..10 {
             let mut fail_flag = false;
@@ -585,6 +660,12 @@ mod test {
             },
         ]);
 
+        let (_, row) = chunk.rows().nth(1).unwrap();
+        assert_eq!(
+            Value::Object(pk_to_json(row, &schema.fields, &[0, 1])?).to_string(),
+            "{\"v1\":1,\"v2\":1.0}"
+        );
+
         let json_chunk = chunk_to_json(chunk, &schema).unwrap();
         let schema_json = schema_to_json(&schema);
         assert_eq!(schema_json.to_string(), "{\"fields\":[{\"field\":\"before\",\"fields\":[{\"field\":\"v1\",\"optional\":true,\"type\":\"\"},{\"field\":\"v2\",\"optional\":true,\"type\":\"\"},{\"field\":\"v3\",\"optional\":true,\"type\":\"\"}],\"optional\":true,\"type\":\"struct\"},{\"field\":\"after\",\"fields\":[{\"field\":\"v1\",\"optional\":true,\"type\":\"\"},{\"field\":\"v2\",\"optional\":true,\"type\":\"\"},{\"field\":\"v3\",\"optional\":true,\"type\":\"\"}],\"optional\":true,\"type\":\"struct\"}],\"optional\":false,\"type\":\"struct\"}");
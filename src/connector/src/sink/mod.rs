@@ -12,24 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod file;
 pub mod kafka;
 pub mod mysql;
+pub mod postgres;
 pub mod redis;
 
 use std::collections::HashMap;
 
 use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
 use enum_as_inner::EnumAsInner;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::Schema;
+pub use risingwave_common::catalog::{SinkType, SINK_TYPE_OPTION};
 use risingwave_common::error::{ErrorCode, RwError};
+use risingwave_common::types::{DataType, DatumRef, ScalarRefImpl};
+use risingwave_object_store::object::ObjectError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 pub use tracing;
 
+use crate::sink::file::{FileConfig, FileSink, FILE_SINK};
 use crate::sink::kafka::{KafkaConfig, KafkaSink, KAFKA_SINK};
 pub use crate::sink::mysql::{MySqlConfig, MySqlSink, MYSQL_SINK};
-use crate::sink::redis::{RedisConfig, RedisSink};
+use crate::sink::postgres::{PostgresConfig, PostgresSink, POSTGRES_SINK};
+use crate::sink::redis::{RedisConfig, RedisSink, REDIS_SINK};
 
 #[async_trait]
 pub trait Sink {
@@ -52,6 +60,8 @@ pub enum SinkConfig {
     Mysql(MySqlConfig),
     Redis(RedisConfig),
     Kafka(KafkaConfig),
+    File(FileConfig),
+    Postgres(PostgresConfig),
 }
 
 #[derive(Clone, Debug, EnumAsInner, Serialize, Deserialize)]
@@ -59,6 +69,8 @@ pub enum SinkState {
     Kafka,
     Mysql,
     Redis,
+    File,
+    Postgres,
 }
 
 impl SinkConfig {
@@ -70,6 +82,11 @@ impl SinkConfig {
         match sink_type.to_lowercase().as_str() {
             KAFKA_SINK => Ok(SinkConfig::Kafka(KafkaConfig::from_hashmap(properties)?)),
             MYSQL_SINK => Ok(SinkConfig::Mysql(MySqlConfig::from_hashmap(properties)?)),
+            REDIS_SINK => Ok(SinkConfig::Redis(RedisConfig::from_hashmap(properties)?)),
+            FILE_SINK => Ok(SinkConfig::File(FileConfig::from_hashmap(properties)?)),
+            POSTGRES_SINK => Ok(SinkConfig::Postgres(PostgresConfig::from_hashmap(
+                properties,
+            )?)),
             _ => unimplemented!(),
         }
     }
@@ -79,6 +96,8 @@ impl SinkConfig {
             SinkConfig::Mysql(_) => "mysql",
             SinkConfig::Kafka(_) => "kafka",
             SinkConfig::Redis(_) => "redis",
+            SinkConfig::File(_) => "file",
+            SinkConfig::Postgres(_) => "postgres",
         }
     }
 }
@@ -88,28 +107,41 @@ pub enum SinkImpl {
     MySql(Box<MySqlSink>),
     Redis(Box<RedisSink>),
     Kafka(Box<KafkaSink>),
+    File(Box<FileSink>),
+    Postgres(Box<PostgresSink>),
 }
 
 impl SinkImpl {
-    pub async fn new(cfg: SinkConfig) -> Result<Self> {
+    /// Creates the sink, where `pk_indices` is the primary key of the upstream.
+    pub async fn new(cfg: SinkConfig, pk_indices: Vec<usize>) -> Result<Self> {
         Ok(match cfg {
             SinkConfig::Mysql(cfg) => SinkImpl::MySql(Box::new(MySqlSink::new(cfg).await?)),
-            SinkConfig::Redis(cfg) => SinkImpl::Redis(Box::new(RedisSink::new(cfg)?)),
-            SinkConfig::Kafka(cfg) => SinkImpl::Kafka(Box::new(KafkaSink::new(cfg).await?)),
+            SinkConfig::Redis(cfg) => SinkImpl::Redis(Box::new(RedisSink::new(cfg).await?)),
+            SinkConfig::Kafka(cfg) => {
+                SinkImpl::Kafka(Box::new(KafkaSink::new(cfg, pk_indices).await?))
+            }
+            SinkConfig::File(cfg) => SinkImpl::File(Box::new(FileSink::new(cfg).await?)),
+            SinkConfig::Postgres(cfg) => {
+                SinkImpl::Postgres(Box::new(PostgresSink::new(cfg).await?))
+            }
         })
     }
 
     pub fn needs_preparation(&self) -> bool {
         match self {
             SinkImpl::MySql(_) => true,
-            SinkImpl::Redis(_) => false,
+            SinkImpl::Redis(_) => true,
             SinkImpl::Kafka(_) => false,
+            SinkImpl::File(_) => false,
+            SinkImpl::Postgres(_) => true,
         }
     }
 
     pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
         match self {
             SinkImpl::MySql(sink) => sink.prepare(schema).await,
+            SinkImpl::Postgres(sink) => sink.prepare(schema).await,
+            SinkImpl::Redis(sink) => sink.prepare(schema).await,
             _ => unreachable!(),
         }
     }
@@ -122,6 +154,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.write_batch(chunk, schema).await,
             SinkImpl::Redis(sink) => sink.write_batch(chunk, schema).await,
             SinkImpl::Kafka(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::File(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::Postgres(sink) => sink.write_batch(chunk, schema).await,
         }
     }
 
@@ -130,6 +164,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.begin_epoch(epoch).await,
             SinkImpl::Redis(sink) => sink.begin_epoch(epoch).await,
             SinkImpl::Kafka(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::File(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::Postgres(sink) => sink.begin_epoch(epoch).await,
         }
     }
 
@@ -138,6 +174,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.commit().await,
             SinkImpl::Redis(sink) => sink.commit().await,
             SinkImpl::Kafka(sink) => sink.commit().await,
+            SinkImpl::File(sink) => sink.commit().await,
+            SinkImpl::Postgres(sink) => sink.commit().await,
         }
     }
 
@@ -146,10 +184,25 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.abort().await,
             SinkImpl::Redis(sink) => sink.abort().await,
             SinkImpl::Kafka(sink) => sink.abort().await,
+            SinkImpl::File(sink) => sink.abort().await,
+            SinkImpl::Postgres(sink) => sink.abort().await,
         }
     }
 }
 
+/// Formats a datum in the same text format as the query results, where `NULL` is formatted as an
+/// empty string.
+pub(crate) fn datum_to_text(data_type: &DataType, datum: DatumRef<'_>) -> String {
+    match (data_type, datum) {
+        (_, None) => String::new(),
+        (DataType::Timestampz, Some(ScalarRefImpl::Int64(us))) => Utc
+            .timestamp_nanos(us * 1000)
+            .format("%Y-%m-%d %H:%M:%S%.f%:z")
+            .to_string(),
+        (_, Some(scalar)) => scalar.to_string(),
+    }
+}
+
 pub type Result<T> = std::result::Result<T, SinkError>;
 
 #[derive(Error, Debug)]
@@ -158,10 +211,18 @@ pub enum SinkError {
     MySql(String),
     #[error("MySql inner error: {0}")]
     MySqlInner(#[from] mysql_async::Error),
+    #[error("Postgres error: {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
     #[error("Kafka error: {0}")]
     Kafka(#[from] rdkafka::error::KafkaError),
     #[error("Json parse error: {0}")]
     JsonParse(String),
+    #[error("File error: {0}")]
+    File(String),
+    #[error("Object store error: {0}")]
+    ObjectStore(#[from] ObjectError),
     #[error("config error: {0}")]
     Config(String),
 }
@@ -80,15 +80,15 @@ impl MySqlSink {
     pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
         // Create a table
         let create_table = format!(
-            r"CREATE TABLE IF NOT EXISTS `{}`.`{}` ( {} );",
-            self.cfg.database.clone().unwrap(),
-            self.cfg.table,
+            r"CREATE TABLE IF NOT EXISTS {}.{} ( {} );",
+            quote_identifier(self.cfg.database.as_ref().unwrap()),
+            quote_identifier(&self.cfg.table),
             join(
                 schema
                     .names()
                     .iter()
                     .zip_eq(schema.data_types().iter())
-                    .map(|(n, dt)| format!("`{}` {}", n, MySqlDataType::from(dt)))
+                    .map(|(n, dt)| format!("{} {}", quote_identifier(n), MySqlDataType::from(dt)))
                     .collect_vec(),
                 ", ",
             )
@@ -160,6 +160,8 @@ impl TryFrom<Datum> for MySqlValue {
                 ScalarImpl::NaiveDate(v) => Ok(MySqlValue(format!("{}", v).into())),
                 ScalarImpl::NaiveTime(v) => Ok(MySqlValue(format!("{}", v).into())),
                 ScalarImpl::NaiveDateTime(v) => Ok(MySqlValue(format!("{}", v).into())),
+                ScalarImpl::Bytea(v) => Ok(MySqlValue(v.into_vec().into())),
+                ScalarImpl::Jsonb(v) => Ok(MySqlValue(v.to_string().into())),
                 // ScalarImpl::Interval(v) => Ok(MySqlValue(Value::NULL)),
                 _ => unimplemented!(),
             }
@@ -232,48 +234,45 @@ async fn write_to_mysql<'a>(
     config: &MySqlConfig,
 ) -> Result<()> {
     // Closure that takes an idx to create a vector of MySqlValues from a StreamChunk 'row'.
-    let values = |idx| -> Result<Vec<MySqlValue>> {
+    let values = |idx| -> Result<Vec<Value>> {
         chunk
             .columns()
             .iter()
-            .map(|x| MySqlValue::try_from(x.array_ref().datum_at(idx)))
-            .collect_vec()
-            .into_iter()
+            .map(|x| MySqlValue::try_from(x.array_ref().datum_at(idx)).map(|v| v.0))
             .collect()
     };
 
-    // Closure that builds a String containing WHERE conditions, i.e. 'v1=1 AND v2=2'.
-    // Perhaps better to replace this functionality with a new Sink trait method.
-    let conditions = |values: Vec<MySqlValue>| {
-        schema
-            .names()
-            .iter()
-            .zip_eq(values.iter())
-            .map(|(c, v)| format!("{}={}", c, v))
-            .collect::<Vec<String>>()
-    };
+    let table = quote_identifier(&config.table);
+    let columns = schema
+        .names()
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect_vec();
+    let placeholders = join(columns.iter().map(|_| "?"), ",");
+    // The values are bound to the `?` placeholders in the order of the columns. Rows are matched
+    // with the null-safe `<=>` so that NULL values are compared equal.
+    let assignments = join(columns.iter().map(|c| format!("{}=?", c)), ",");
+    let conditions = join(columns.iter().map(|c| format!("{}<=>?", c)), " AND ");
 
     let mut iter = chunk.ops().iter().enumerate();
     while let Some((idx, op)) = iter.next() {
-        // Get SQL statement
-        let stmt = match *op {
-            Insert => format!(
-                "INSERT INTO {} VALUES ({});",
-                &config.table,
-                join(values(idx)?, ",")
+        // Get SQL statement and its parameters
+        let (stmt, params) = match *op {
+            Insert => (
+                format!("INSERT INTO {} VALUES ({});", table, placeholders),
+                values(idx)?,
             ),
-            Delete => format!(
-                "DELETE FROM {} WHERE ({});",
-                &config.table,
-                join(conditions(values(idx)?), " AND ")
+            Delete => (
+                format!("DELETE FROM {} WHERE ({});", table, conditions),
+                values(idx)?,
             ),
             UpdateDelete => {
                 if let Some((idx2, UpdateInsert)) = iter.next() {
-                    format!(
-                        "UPDATE {} SET {} WHERE {};",
-                        &config.table,
-                        join(conditions(values(idx2)?), ","),
-                        join(conditions(values(idx)?), " AND ")
+                    let mut params = values(idx2)?;
+                    params.extend(values(idx)?);
+                    (
+                        format!("UPDATE {} SET {} WHERE {};", table, assignments, conditions),
+                        params,
                     )
                 } else {
                     return Err(SinkError::MySql(
@@ -285,12 +284,17 @@ async fn write_to_mysql<'a>(
         };
         // TODO by doc, exec_drop will simply exec query and drop the result, we may check and retry
         // for jitter or other reasons
-        txn.exec_drop(stmt, Params::Empty).await?;
+        txn.exec_drop(stmt, Params::Positional(params)).await?;
     }
 
     Ok(())
 }
 
+/// Quotes an identifier with backticks, so that it's used as is in the SQL statements.
+fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
 #[cfg(test)]
 mod test {
 
@@ -343,6 +347,12 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("t"), "`t`");
+        assert_eq!(quote_identifier("a`; drop table t"), "`a``; drop table t`");
+    }
+
     #[test]
     fn test_decimal() {
         assert_eq!(
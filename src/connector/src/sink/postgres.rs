@@ -0,0 +1,666 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{to_datum_ref, DataType, DatumRef, ScalarRefImpl};
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use tokio_postgres::{Client, NoTls};
+
+use super::datum_to_text;
+use crate::sink::{Result, Sink, SinkError};
+
+pub const POSTGRES_SINK: &str = "postgres";
+
+/// The maximum number of rows in a single `INSERT` or `DELETE` statement.
+const MAX_ROWS_PER_STATEMENT: usize = 1000;
+/// The maximum number of parameters of a statement allowed by the protocol.
+const MAX_PARAMS_PER_STATEMENT: usize = u16::MAX as usize;
+
+#[derive(Clone, Debug)]
+pub struct PostgresConfig {
+    pub endpoint: String,
+    /// The downstream table, optionally qualified by its schema as `schema.table`. Both names are
+    /// quoted, so they are case-sensitive.
+    pub table: String,
+    pub database: Option<String>,
+    pub user: Option<String>,
+    pub password: Option<String>,
+
+    /// The columns to upsert and delete rows by, which must be a primary key or have a unique
+    /// constraint in the downstream table.
+    pub primary_key: Vec<String>,
+
+    pub max_retry_num: u32,
+    pub retry_interval: Duration,
+}
+
+impl PostgresConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let endpoint = values
+            .get("endpoint")
+            .ok_or_else(|| SinkError::Config("endpoint must be set".to_string()))?;
+        let table = values
+            .get("table")
+            .ok_or_else(|| SinkError::Config("table must be set".to_string()))?;
+        let primary_key = values
+            .get("primary_key")
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .map(|column| column.trim().to_string())
+                    .filter(|column| !column.is_empty())
+                    .collect_vec()
+            })
+            .filter(|columns| !columns.is_empty())
+            .ok_or_else(|| SinkError::Config("primary_key must be set".to_string()))?;
+
+        Ok(PostgresConfig {
+            endpoint: endpoint.to_string(),
+            table: table.to_string(),
+            database: values.get("database").cloned(),
+            user: values.get("user").cloned(),
+            password: values.get("password").cloned(),
+            primary_key,
+            max_retry_num: 3,                           // default max retry num is 3
+            retry_interval: Duration::from_millis(100), // default retry interval is 100ms
+        })
+    }
+}
+
+/// A sink that upserts and deletes rows of a PostgreSQL table by primary key.
+///
+/// The changes of an epoch are buffered and written in a single transaction on commit. Only the
+/// last change of each key is kept, so the rows are written at most once per commit.
+pub struct PostgresSink {
+    cfg: PostgresConfig,
+    client: Client,
+
+    /// The schema of the rows, the indices of the primary key columns in it and the types the
+    /// parameters of each column are cast to, which are set by [`PostgresSink::prepare`].
+    schema: Schema,
+    pk_indices: Vec<usize>,
+    param_types: Vec<String>,
+    /// The last change of each primary key in the current epoch, where `None` means the row is
+    /// deleted.
+    buffer: HashMap<Row, Option<Row>>,
+}
+
+impl PostgresSink {
+    pub async fn new(cfg: PostgresConfig) -> Result<Self> {
+        let client = connect(&cfg).await?;
+        Ok(Self {
+            cfg,
+            client,
+            schema: Schema::default(),
+            pk_indices: vec![],
+            param_types: vec![],
+            buffer: HashMap::new(),
+        })
+    }
+
+    pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
+        self.pk_indices = pk_indices(schema, &self.cfg.primary_key)?;
+        self.param_types = schema
+            .data_types()
+            .iter()
+            .map(param_type)
+            .collect::<Result<_>>()?;
+        self.schema = schema.clone();
+        Ok(())
+    }
+
+    /// Writes the statements in a transaction, reconnecting and retrying on transient errors.
+    async fn write_with_retry(&mut self, statements: &[Statement]) -> Result<()> {
+        let mut retry_num = 0;
+        loop {
+            match self.write(statements).await {
+                Ok(()) => return Ok(()),
+                Err(e) if retry_num < self.cfg.max_retry_num && is_transient(&e) => {
+                    tracing::warn!("failed to write to postgres, retrying: {}", e);
+                    retry_num += 1;
+                    tokio::time::sleep(self.cfg.retry_interval).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    async fn write(
+        &mut self,
+        statements: &[Statement],
+    ) -> std::result::Result<(), tokio_postgres::Error> {
+        if self.client.is_closed() {
+            self.client = connect(&self.cfg).await?;
+        }
+        let txn = self.client.transaction().await?;
+        for statement in statements {
+            let params = statement
+                .params
+                .iter()
+                .map(|param| param as &(dyn ToSql + Sync))
+                .collect_vec();
+            txn.execute(statement.sql.as_str(), &params).await?;
+        }
+        txn.commit().await
+    }
+}
+
+impl Debug for PostgresSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresSink")
+            .field("cfg", &self.cfg)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
+    }
+}
+
+async fn connect(cfg: &PostgresConfig) -> std::result::Result<Client, tokio_postgres::Error> {
+    let mut config = tokio_postgres::Config::new();
+    let (host, port) = match cfg.endpoint.split_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (cfg.endpoint.as_str(), None),
+    };
+    config.host(host);
+    if let Some(port) = port.and_then(|port| port.parse().ok()) {
+        config.port(port);
+    }
+    if let Some(database) = &cfg.database {
+        config.dbname(database);
+    }
+    if let Some(user) = &cfg.user {
+        config.user(user);
+    }
+    if let Some(password) = &cfg.password {
+        config.password(password);
+    }
+
+    let (client, connection) = config.connect(NoTls).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::error!("postgres connection error: {}", e);
+        }
+    });
+    Ok(client)
+}
+
+/// Whether the error is caused by a lost connection or a conflict with other transactions, in
+/// which case the transaction can be retried.
+fn is_transient(e: &tokio_postgres::Error) -> bool {
+    if e.is_closed() {
+        return true;
+    }
+    match e.code() {
+        // Class 08 is connection exceptions and class 40 is transaction rollbacks, e.g.
+        // serialization failures and deadlocks.
+        Some(code) => code.code().starts_with("08") || code.code().starts_with("40"),
+        None => false,
+    }
+}
+
+fn pk_indices(schema: &Schema, primary_key: &[String]) -> Result<Vec<usize>> {
+    primary_key
+        .iter()
+        .map(|column| {
+            schema
+                .names()
+                .iter()
+                .position(|name| name == column)
+                .ok_or_else(|| {
+                    SinkError::Config(format!("primary key column {} does not exist", column))
+                })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Sink for PostgresSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, _schema: &Schema) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let pk = row.row_by_indices(&self.pk_indices);
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    self.buffer.insert(pk, Some(row.to_owned_row()));
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    self.buffer.insert(pk, None);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn begin_epoch(&mut self, _epoch: u64) -> Result<()> {
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let (deletes, upserts): (Vec<_>, Vec<_>) =
+            self.buffer.iter().partition(|(_, row)| row.is_none());
+        let deletes = deletes.into_iter().map(|(pk, _)| pk).collect_vec();
+        let upserts = upserts
+            .into_iter()
+            .filter_map(|(_, row)| row.as_ref())
+            .collect_vec();
+
+        let statements = build_statements(
+            &self.cfg.table,
+            &self.schema,
+            &self.param_types,
+            &self.pk_indices,
+            &deletes,
+            &upserts,
+        );
+        self.write_with_retry(&statements).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+/// A SQL statement and the values bound to its parameters.
+#[derive(Debug, PartialEq)]
+struct Statement {
+    sql: String,
+    params: Vec<PgValue>,
+}
+
+/// Builds the `DELETE` statements of the keys in `deletes`, followed by the
+/// `INSERT ... ON CONFLICT DO UPDATE` statements of the rows in `upserts`. The values are bound to
+/// parameters, which are cast to `param_types` of their columns.
+fn build_statements(
+    table: &str,
+    schema: &Schema,
+    param_types: &[String],
+    pk_indices: &[usize],
+    deletes: &[&Row],
+    upserts: &[&Row],
+) -> Vec<Statement> {
+    let table = table.split('.').map(quote_identifier).join(".");
+    let columns = schema
+        .names()
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect_vec();
+    let data_types = schema.data_types();
+    let pk_columns = pk_indices.iter().map(|i| &columns[*i]).join(", ");
+    let pk_data_types = pk_indices
+        .iter()
+        .map(|i| data_types[*i].clone())
+        .collect_vec();
+    let pk_param_types = pk_indices
+        .iter()
+        .map(|i| param_types[*i].clone())
+        .collect_vec();
+
+    let mut statements = vec![];
+    for keys in deletes.chunks(rows_per_statement(pk_indices.len())) {
+        let mut params = vec![];
+        let values = keys
+            .iter()
+            .map(|key| bind_row(key, &pk_data_types, &pk_param_types, &mut params))
+            .join(", ");
+        statements.push(Statement {
+            sql: format!(
+                "DELETE FROM {} WHERE ({}) IN ({})",
+                table, pk_columns, values
+            ),
+            params,
+        });
+    }
+
+    let updates = columns
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !pk_indices.contains(i))
+        .map(|(_, column)| format!("{} = EXCLUDED.{}", column, column))
+        .join(", ");
+    let on_conflict = if updates.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        format!("DO UPDATE SET {}", updates)
+    };
+    for rows in upserts.chunks(rows_per_statement(columns.len())) {
+        let mut params = vec![];
+        let values = rows
+            .iter()
+            .map(|row| bind_row(row, &data_types, param_types, &mut params))
+            .join(", ");
+        statements.push(Statement {
+            sql: format!(
+                "INSERT INTO {} ({}) VALUES {} ON CONFLICT ({}) {}",
+                table,
+                columns.join(", "),
+                values,
+                pk_columns,
+                on_conflict
+            ),
+            params,
+        });
+    }
+    statements
+}
+
+fn rows_per_statement(num_columns: usize) -> usize {
+    (MAX_PARAMS_PER_STATEMENT / num_columns.max(1)).clamp(1, MAX_ROWS_PER_STATEMENT)
+}
+
+/// Appends the values of `row` to `params`, and returns the tuple of their placeholders, e.g.
+/// `($1::INTEGER, $2::VARCHAR)`.
+fn bind_row(
+    row: &Row,
+    data_types: &[DataType],
+    param_types: &[String],
+    params: &mut Vec<PgValue>,
+) -> String {
+    let placeholders = row
+        .values()
+        .zip_eq(data_types.iter().zip_eq(param_types))
+        .map(|(datum, (data_type, param_type))| {
+            params.push(PgValue::new(data_type, to_datum_ref(datum)));
+            format!("${}::{}", params.len(), param_type)
+        })
+        .join(", ");
+    format!("({})", placeholders)
+}
+
+/// Returns the type the parameters of a column are cast to. Values of types without a binary
+/// encoding in [`PgValue`] are bound as text and cast to the column type by PostgreSQL.
+fn param_type(data_type: &DataType) -> Result<String> {
+    let param_type = match data_type {
+        DataType::Boolean
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Varchar
+        | DataType::Bytea
+        | DataType::Date
+        | DataType::Time
+        | DataType::Timestamp
+        | DataType::Timestampz => type_name(data_type)?,
+        _ => format!("TEXT::{}", type_name(data_type)?),
+    };
+    Ok(param_type)
+}
+
+fn type_name(data_type: &DataType) -> Result<String> {
+    let name = match data_type {
+        DataType::Boolean => "BOOLEAN",
+        DataType::Int16 => "SMALLINT",
+        DataType::Int32 => "INTEGER",
+        DataType::Int64 => "BIGINT",
+        DataType::Float32 => "REAL",
+        DataType::Float64 => "DOUBLE PRECISION",
+        DataType::Decimal => "NUMERIC",
+        DataType::Date => "DATE",
+        DataType::Varchar => "VARCHAR",
+        DataType::Time => "TIME",
+        DataType::Timestamp => "TIMESTAMP",
+        DataType::Timestampz => "TIMESTAMPTZ",
+        DataType::Interval => "INTERVAL",
+        DataType::Bytea => "BYTEA",
+        DataType::Jsonb => "JSONB",
+        DataType::List { datatype } => return Ok(format!("{}[]", type_name(datatype)?)),
+        DataType::Struct(_) => {
+            return Err(SinkError::Config(
+                "struct columns are not supported by postgres sink".to_string(),
+            ))
+        }
+    };
+    Ok(name.to_string())
+}
+
+/// A value bound to a parameter. The parameter is always cast to the type the value is encoded
+/// as, so the type is not checked again on encoding.
+#[derive(Debug, Clone, PartialEq)]
+enum PgValue {
+    Null,
+    Bool(bool),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Float32(f32),
+    Float64(f64),
+    Bytea(Vec<u8>),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Timestamp(NaiveDateTime),
+    Timestampz(DateTime<Utc>),
+    /// `VARCHAR`, or the text of the types bound as text by [`param_type`].
+    Text(String),
+}
+
+impl PgValue {
+    fn new(data_type: &DataType, datum: DatumRef<'_>) -> Self {
+        match (data_type, datum) {
+            (_, None) => Self::Null,
+            (DataType::Timestampz, Some(ScalarRefImpl::Int64(us))) => {
+                Self::Timestampz(Utc.timestamp_nanos(us * 1000))
+            }
+            (_, Some(ScalarRefImpl::Bool(v))) => Self::Bool(v),
+            (_, Some(ScalarRefImpl::Int16(v))) => Self::Int16(v),
+            (_, Some(ScalarRefImpl::Int32(v))) => Self::Int32(v),
+            (_, Some(ScalarRefImpl::Int64(v))) => Self::Int64(v),
+            (_, Some(ScalarRefImpl::Float32(v))) => Self::Float32(v.0),
+            (_, Some(ScalarRefImpl::Float64(v))) => Self::Float64(v.0),
+            (_, Some(ScalarRefImpl::Bytea(v))) => Self::Bytea(v.to_vec()),
+            (_, Some(ScalarRefImpl::NaiveDate(v))) => Self::Date(v.0),
+            (_, Some(ScalarRefImpl::NaiveTime(v))) => Self::Time(v.0),
+            (_, Some(ScalarRefImpl::NaiveDateTime(v))) => Self::Timestamp(v.0),
+            (_, Some(_)) => Self::Text(datum_to_text(data_type, datum)),
+        }
+    }
+}
+
+impl ToSql for PgValue {
+    to_sql_checked!();
+
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match self {
+            Self::Null => Ok(IsNull::Yes),
+            Self::Bool(v) => v.to_sql(ty, out),
+            Self::Int16(v) => v.to_sql(ty, out),
+            Self::Int32(v) => v.to_sql(ty, out),
+            Self::Int64(v) => v.to_sql(ty, out),
+            Self::Float32(v) => v.to_sql(ty, out),
+            Self::Float64(v) => v.to_sql(ty, out),
+            Self::Bytea(v) => v.to_sql(ty, out),
+            Self::Date(v) => v.to_sql(ty, out),
+            Self::Time(v) => v.to_sql(ty, out),
+            Self::Timestamp(v) => v.to_sql(ty, out),
+            Self::Timestampz(v) => v.to_sql(ty, out),
+            Self::Text(v) => v.to_sql(ty, out),
+        }
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::hashmap;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Varchar, "v"),
+        ])
+    }
+
+    fn row(id: i32, v: Option<&str>) -> Row {
+        Row::new(vec![
+            Some(ScalarImpl::Int32(id)),
+            v.map(|v| ScalarImpl::Utf8(v.to_string())),
+        ])
+    }
+
+    #[test]
+    fn test_postgres_config() {
+        let config = PostgresConfig::from_hashmap(hashmap! {
+            "endpoint".to_string() => "127.0.0.1:5432".to_string(),
+            "table".to_string() => "t".to_string(),
+            "primary_key".to_string() => "id, v".to_string(),
+        })
+        .unwrap();
+        assert_eq!(config.primary_key, vec!["id", "v"]);
+        assert_eq!(
+            pk_indices(&schema(), &config.primary_key).unwrap(),
+            vec![0, 1]
+        );
+        assert!(pk_indices(&schema(), &["x".to_string()]).is_err());
+
+        assert!(PostgresConfig::from_hashmap(hashmap! {
+            "endpoint".to_string() => "127.0.0.1:5432".to_string(),
+            "table".to_string() => "t".to_string(),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_build_statements() {
+        let schema = schema();
+        let param_types = ["INTEGER".to_string(), "VARCHAR".to_string()];
+        let deleted = Row::new(vec![Some(ScalarImpl::Int32(1))]);
+        let upserted = [row(2, Some("it's")), row(3, None)];
+        let statements = build_statements(
+            "public.t",
+            &schema,
+            &param_types,
+            &[0],
+            &[&deleted],
+            &upserted.iter().collect_vec(),
+        );
+        assert_eq!(
+            statements,
+            vec![
+                Statement {
+                    sql: r#"DELETE FROM "public"."t" WHERE ("id") IN (($1::INTEGER))"#.to_string(),
+                    params: vec![PgValue::Int32(1)],
+                },
+                Statement {
+                    sql: r#"INSERT INTO "public"."t" ("id", "v") VALUES ($1::INTEGER, $2::VARCHAR), ($3::INTEGER, $4::VARCHAR) ON CONFLICT ("id") DO UPDATE SET "v" = EXCLUDED."v""#.to_string(),
+                    params: vec![
+                        PgValue::Int32(2),
+                        PgValue::Text("it's".to_string()),
+                        PgValue::Int32(3),
+                        PgValue::Null,
+                    ],
+                },
+            ]
+        );
+
+        // All columns are in the primary key.
+        let statements =
+            build_statements("t", &schema, &param_types, &[0, 1], &[], &[&upserted[0]]);
+        assert_eq!(
+            statements
+                .iter()
+                .map(|statement| statement.sql.as_str())
+                .collect_vec(),
+            vec![
+                r#"INSERT INTO "t" ("id", "v") VALUES ($1::INTEGER, $2::VARCHAR) ON CONFLICT ("id", "v") DO NOTHING"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_param_type() {
+        assert_eq!(param_type(&DataType::Int32).unwrap(), "INTEGER");
+        assert_eq!(param_type(&DataType::Decimal).unwrap(), "TEXT::NUMERIC");
+        assert_eq!(
+            param_type(&DataType::List {
+                datatype: Box::new(DataType::Int64)
+            })
+            .unwrap(),
+            "TEXT::BIGINT[]"
+        );
+        assert_eq!(rows_per_statement(2), MAX_ROWS_PER_STATEMENT);
+        assert_eq!(rows_per_statement(1000), 65);
+    }
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_postgres_sink() -> Result<()> {
+        use risingwave_common::array::StreamChunkTestExt;
+
+        let cfg = PostgresConfig::from_hashmap(hashmap! {
+            "endpoint".to_string() => "127.0.0.1:5432".to_string(),
+            "database".to_string() => "postgres".to_string(),
+            "user".to_string() => "postgres".to_string(),
+            "table".to_string() => "t".to_string(),
+            "primary_key".to_string() => "id".to_string(),
+        })?;
+        let client = connect(&cfg).await?;
+        client
+            .batch_execute("CREATE TABLE IF NOT EXISTS t (id int PRIMARY KEY, v varchar)")
+            .await?;
+
+        let schema = schema();
+        let mut sink = PostgresSink::new(cfg).await?;
+        sink.prepare(&schema).await?;
+        sink.begin_epoch(1).await?;
+        sink.write_batch(
+            StreamChunk::from_pretty(
+                "  i T
+                +  1 a
+                +  2 b
+                -  1 a
+                U- 2 b
+                U+ 2 c",
+            ),
+            &schema,
+        )
+        .await?;
+        sink.commit().await?;
+
+        let rows = client.query("SELECT id, v FROM t", &[]).await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<_, i32>(0), 2);
+        assert_eq!(rows[0].get::<_, String>(1), "c");
+
+        client.batch_execute("DROP TABLE t").await?;
+        Ok(())
+    }
+}
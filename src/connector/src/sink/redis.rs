@@ -12,39 +12,398 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
 use async_trait::async_trait;
-use risingwave_common::array::StreamChunk;
+use itertools::Itertools;
+use redis::aio::MultiplexedConnection;
+use risingwave_common::array::{Op, RowRef, StreamChunk};
 use risingwave_common::catalog::Schema;
+use serde_json::Value;
+
+use super::datum_to_text;
+use super::kafka::record_to_json;
+use crate::sink::{Result, Sink, SinkError};
 
-use crate::sink::{Result, Sink};
+pub const REDIS_SINK: &str = "redis";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedisValueFormat {
+    /// Each row is a hash, whose fields are the non-null columns in text format.
+    Hash,
+    /// Each row is a string of a JSON object, encoded the same as the Kafka sink.
+    Json,
+}
+
+/// A segment of the key template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum KeySegment {
+    Literal(String),
+    Column(String),
+}
 
 #[derive(Clone, Debug)]
-pub struct RedisConfig;
+pub struct RedisConfig {
+    /// The url of the server, e.g. `redis://127.0.0.1:6379/0`.
+    pub url: String,
+
+    /// The template of the keys, where `{column}` is replaced by the value of the column, e.g.
+    /// `user:{id}`. The columns in the template should form a primary key of the rows.
+    pub key_template: String,
+    key_segments: Vec<KeySegment>,
+
+    pub value_format: RedisValueFormat,
+}
+
+impl RedisConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let url = values
+            .get("redis.url")
+            .ok_or_else(|| SinkError::Config("redis.url must be set".to_string()))?;
+        let key_template = values
+            .get("redis.key_template")
+            .ok_or_else(|| SinkError::Config("redis.key_template must be set".to_string()))?;
+        let value_format = match values.get("redis.value_format").map(|f| f.to_lowercase()) {
+            None => RedisValueFormat::Hash,
+            Some(f) if f == "hash" => RedisValueFormat::Hash,
+            Some(f) if f == "json" => RedisValueFormat::Json,
+            Some(f) => {
+                return Err(SinkError::Config(format!(
+                    "redis.value_format must be \"hash\" or \"json\", got \"{}\"",
+                    f
+                )))
+            }
+        };
+
+        Ok(RedisConfig {
+            url: url.to_string(),
+            key_template: key_template.to_string(),
+            key_segments: parse_key_template(key_template)?,
+            value_format,
+        })
+    }
+}
 
-#[derive(Debug)]
-pub struct RedisSink;
+fn parse_key_template(template: &str) -> Result<Vec<KeySegment>> {
+    let invalid = || {
+        SinkError::Config(format!(
+            "invalid redis.key_template \"{}\", expect columns in braces like \"user:{{id}}\"",
+            template
+        ))
+    };
+
+    let mut segments = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').ok_or_else(invalid)? + start;
+        let column = &rest[start + 1..end];
+        if column.is_empty() || column.contains('{') {
+            return Err(invalid());
+        }
+        if start > 0 {
+            segments.push(KeySegment::Literal(rest[..start].to_string()));
+        }
+        segments.push(KeySegment::Column(column.to_string()));
+        rest = &rest[end + 1..];
+    }
+    if rest.contains('}') {
+        return Err(invalid());
+    }
+    if !rest.is_empty() {
+        segments.push(KeySegment::Literal(rest.to_string()));
+    }
+    if !segments
+        .iter()
+        .any(|segment| matches!(segment, KeySegment::Column(_)))
+    {
+        return Err(invalid());
+    }
+    Ok(segments)
+}
+
+/// The key template resolved against the schema, where columns are replaced by their indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ResolvedKeySegment {
+    Literal(String),
+    Column(usize),
+}
+
+/// A sink that writes each row as a Redis key.
+///
+/// The changes of an epoch are buffered, and sent in a single `MULTI`/`EXEC` pipeline on commit,
+/// so readers never see a partially written epoch.
+pub struct RedisSink {
+    cfg: RedisConfig,
+    conn: MultiplexedConnection,
+
+    /// The schema of the rows and the resolved key template, which are set by
+    /// [`RedisSink::prepare`].
+    schema: Schema,
+    key_segments: Vec<ResolvedKeySegment>,
+    /// The value of each key changed in the current epoch, where `None` means the key is deleted.
+    buffer: HashMap<String, Option<RedisValue>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum RedisValue {
+    Hash(Vec<(String, String)>),
+    Json(String),
+}
 
 impl RedisSink {
-    pub fn new(_cfg: RedisConfig) -> Result<Self> {
-        todo!()
+    pub async fn new(cfg: RedisConfig) -> Result<Self> {
+        let client = redis::Client::open(cfg.url.as_str())?;
+        let conn = client.get_multiplexed_tokio_connection().await?;
+        Ok(Self {
+            cfg,
+            conn,
+            schema: Schema::default(),
+            key_segments: vec![],
+            buffer: HashMap::new(),
+        })
+    }
+
+    pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
+        self.key_segments = resolve_key_segments(&self.cfg.key_segments, schema)?;
+        self.schema = schema.clone();
+        Ok(())
+    }
+
+    fn key(&self, row: &RowRef<'_>) -> String {
+        self.key_segments
+            .iter()
+            .map(|segment| match segment {
+                ResolvedKeySegment::Literal(literal) => literal.clone(),
+                ResolvedKeySegment::Column(i) => {
+                    datum_to_text(&self.schema.fields[*i].data_type, row.value_at(*i))
+                }
+            })
+            .join("")
+    }
+
+    fn value(&self, row: RowRef<'_>) -> Result<RedisValue> {
+        Ok(match self.cfg.value_format {
+            RedisValueFormat::Hash => RedisValue::Hash(
+                row.values()
+                    .zip_eq(self.schema.fields())
+                    .filter(|(datum, _)| datum.is_some())
+                    .map(|(datum, field)| {
+                        (field.name.clone(), datum_to_text(&field.data_type, datum))
+                    })
+                    .collect(),
+            ),
+            RedisValueFormat::Json => RedisValue::Json(
+                Value::Object(record_to_json(row, self.schema.fields.clone())?).to_string(),
+            ),
+        })
     }
 }
 
+impl Debug for RedisSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisSink")
+            .field("cfg", &self.cfg)
+            .field("key_segments", &self.key_segments)
+            .finish()
+    }
+}
+
+fn resolve_key_segments(
+    segments: &[KeySegment],
+    schema: &Schema,
+) -> Result<Vec<ResolvedKeySegment>> {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            KeySegment::Literal(literal) => Ok(ResolvedKeySegment::Literal(literal.clone())),
+            KeySegment::Column(column) => schema
+                .names()
+                .iter()
+                .position(|name| name == column)
+                .map(ResolvedKeySegment::Column)
+                .ok_or_else(|| {
+                    SinkError::Config(format!(
+                        "column {} in redis.key_template does not exist",
+                        column
+                    ))
+                }),
+        })
+        .collect()
+}
+
+/// Builds the pipeline to apply the changes, where each key is deleted before it's written, so
+/// that a hash never keeps the fields of null columns.
+fn build_pipeline<'a>(
+    changes: impl IntoIterator<Item = (&'a String, &'a Option<RedisValue>)>,
+) -> redis::Pipeline {
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    for (key, value) in changes {
+        pipe.del(key).ignore();
+        match value {
+            Some(RedisValue::Hash(fields)) if !fields.is_empty() => {
+                pipe.hset_multiple(key, fields).ignore();
+            }
+            Some(RedisValue::Hash(_)) | None => {}
+            Some(RedisValue::Json(json)) => {
+                pipe.set(key, json).ignore();
+            }
+        }
+    }
+    pipe
+}
+
 #[async_trait]
 impl Sink for RedisSink {
-    async fn write_batch(&mut self, _chunk: StreamChunk, _schema: &Schema) -> Result<()> {
-        todo!();
+    async fn write_batch(&mut self, chunk: StreamChunk, _schema: &Schema) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let key = self.key(&row);
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    let value = self.value(row)?;
+                    self.buffer.insert(key, Some(value));
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    self.buffer.insert(key, None);
+                }
+            }
+        }
+        Ok(())
     }
 
     async fn begin_epoch(&mut self, _epoch: u64) -> Result<()> {
-        todo!()
+        Ok(())
     }
 
     async fn commit(&mut self) -> Result<()> {
-        todo!()
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        build_pipeline(&self.buffer)
+            .query_async::<_, ()>(&mut self.conn)
+            .await?;
+        tracing::debug!("commit {} keys to redis", self.buffer.len());
+        self.buffer.clear();
+        Ok(())
     }
 
     async fn abort(&mut self) -> Result<()> {
-        todo!()
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::hashmap;
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    #[test]
+    fn test_key_template() {
+        assert_eq!(
+            parse_key_template("user:{id}:{name}").unwrap(),
+            vec![
+                KeySegment::Literal("user:".to_string()),
+                KeySegment::Column("id".to_string()),
+                KeySegment::Literal(":".to_string()),
+                KeySegment::Column("name".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_key_template("{id}").unwrap(),
+            vec![KeySegment::Column("id".to_string())]
+        );
+        for invalid in ["user", "user:{id", "user:{}", "user:id}", "{{id}}"] {
+            assert!(parse_key_template(invalid).is_err(), "{}", invalid);
+        }
+
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Varchar, "name"),
+        ]);
+        assert_eq!(
+            resolve_key_segments(&parse_key_template("u:{name}").unwrap(), &schema).unwrap(),
+            vec![
+                ResolvedKeySegment::Literal("u:".to_string()),
+                ResolvedKeySegment::Column(1)
+            ]
+        );
+        assert!(resolve_key_segments(&parse_key_template("u:{x}").unwrap(), &schema).is_err());
+    }
+
+    #[test]
+    fn test_build_pipeline() {
+        let changes = vec![
+            (
+                "u:1".to_string(),
+                Some(RedisValue::Hash(vec![("v".to_string(), "a".to_string())])),
+            ),
+            ("u:2".to_string(), None),
+            ("u:3".to_string(), Some(RedisValue::Json("{}".to_string()))),
+        ];
+        let pipe = build_pipeline(changes.iter().map(|(k, v)| (k, v)));
+        let expected = redis::pipe()
+            .atomic()
+            .del("u:1")
+            .ignore()
+            .hset_multiple("u:1", &[("v", "a")])
+            .ignore()
+            .del("u:2")
+            .ignore()
+            .del("u:3")
+            .ignore()
+            .set("u:3", "{}")
+            .ignore()
+            .get_packed_pipeline();
+        assert_eq!(pipe.get_packed_pipeline(), expected);
+    }
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_redis_sink() -> Result<()> {
+        let cfg = RedisConfig::from_hashmap(hashmap! {
+            "redis.url".to_string() => "redis://127.0.0.1:6379/0".to_string(),
+            "redis.key_template".to_string() => "user:{id}".to_string(),
+        })?;
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Varchar, "name"),
+        ]);
+        let mut sink = RedisSink::new(cfg.clone()).await?;
+        sink.prepare(&schema).await?;
+        sink.begin_epoch(1).await?;
+        sink.write_batch(
+            StreamChunk::from_pretty(
+                "  i T
+                +  1 a
+                +  2 b
+                -  1 a
+                U- 2 b
+                U+ 2 .",
+            ),
+            &schema,
+        )
+        .await?;
+        sink.commit().await?;
+
+        let mut conn = redis::Client::open(cfg.url.as_str())?
+            .get_multiplexed_tokio_connection()
+            .await?;
+        let exists: bool = redis::cmd("EXISTS")
+            .arg("user:1")
+            .query_async(&mut conn)
+            .await?;
+        assert!(!exists);
+        let fields: HashMap<String, String> = redis::cmd("HGETALL")
+            .arg("user:2")
+            .query_async(&mut conn)
+            .await?;
+        assert_eq!(fields, hashmap! { "id".to_string() => "2".to_string() });
+        Ok(())
     }
 }
@@ -33,7 +33,11 @@ use crate::source::datagen::{
     DatagenProperties, DatagenSplit, DatagenSplitEnumerator, DatagenSplitReader, DATAGEN_CONNECTOR,
 };
 use crate::source::dummy_connector::DummySplitReader;
-use crate::source::filesystem::s3::{S3Properties, S3_CONNECTOR};
+use crate::source::filesystem::posix_fs::{
+    PosixFsProperties, PosixFsReader, PosixFsSplitEnumerator, POSIX_FS_CONNECTOR,
+};
+use crate::source::filesystem::s3::{S3FileReader, S3Properties, S3SplitEnumerator, S3_CONNECTOR};
+use crate::source::filesystem::FsSplit;
 use crate::source::kafka::enumerator::KafkaSplitEnumerator;
 use crate::source::kafka::source::KafkaSplitReader;
 use crate::source::kafka::{KafkaProperties, KafkaSplit, KAFKA_CONNECTOR};
@@ -91,6 +95,8 @@ pub enum SplitImpl {
     Kinesis(KinesisSplit),
     Nexmark(NexmarkSplit),
     Datagen(DatagenSplit),
+    S3(FsSplit),
+    PosixFs(FsSplit),
 }
 
 pub enum SplitReaderImpl {
@@ -100,6 +106,8 @@ pub enum SplitReaderImpl {
     Nexmark(Box<NexmarkSplitReader>),
     Pulsar(Box<PulsarSplitReader>),
     Datagen(Box<DatagenSplitReader>),
+    S3(Box<S3FileReader>),
+    PosixFs(Box<PosixFsReader>),
 }
 
 pub enum SplitEnumeratorImpl {
@@ -108,6 +116,8 @@ pub enum SplitEnumeratorImpl {
     Kinesis(KinesisSplitEnumerator),
     Nexmark(NexmarkSplitEnumerator),
     Datagen(DatagenSplitEnumerator),
+    S3(S3SplitEnumerator),
+    PosixFs(PosixFsSplitEnumerator),
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -118,6 +128,7 @@ pub enum ConnectorProperties {
     Nexmark(Box<NexmarkProperties>),
     Datagen(Box<DatagenProperties>),
     S3(Box<S3Properties>),
+    PosixFs(Box<PosixFsProperties>),
     Dummy(Box<()>),
 }
 
@@ -127,7 +138,23 @@ impl_connector_properties! {
     { Kinesis, KINESIS_CONNECTOR },
     { Nexmark, NEXMARK_CONNECTOR },
     { Datagen, DATAGEN_CONNECTOR },
-    { S3, S3_CONNECTOR }
+    { S3, S3_CONNECTOR },
+    { PosixFs, POSIX_FS_CONNECTOR }
+}
+
+impl ConnectorProperties {
+    /// Marks the first line of the files of a file source as a header. When a file is resumed from
+    /// the middle, the header line is read again and emitted before the rest of the file, so that
+    /// the fields can still be mapped by the header. Returns false if the source is not a file
+    /// source, which has no header.
+    pub fn set_header_line(&mut self) -> bool {
+        match self {
+            ConnectorProperties::S3(props) => props.header_line = true,
+            ConnectorProperties::PosixFs(props) => props.header_line = true,
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl_split_enumerator! {
@@ -135,7 +162,9 @@ impl_split_enumerator! {
     { Pulsar, PulsarSplitEnumerator },
     { Kinesis, KinesisSplitEnumerator },
     { Nexmark, NexmarkSplitEnumerator },
-    { Datagen, DatagenSplitEnumerator }
+    { Datagen, DatagenSplitEnumerator },
+    { S3, S3SplitEnumerator },
+    { PosixFs, PosixFsSplitEnumerator }
 }
 
 impl_split! {
@@ -143,7 +172,9 @@ impl_split! {
     { Pulsar, PULSAR_CONNECTOR, PulsarSplit },
     { Kinesis, KINESIS_CONNECTOR, KinesisSplit },
     { Nexmark, NEXMARK_CONNECTOR, NexmarkSplit },
-    { Datagen, DATAGEN_CONNECTOR, DatagenSplit }
+    { Datagen, DATAGEN_CONNECTOR, DatagenSplit },
+    { S3, S3_CONNECTOR, FsSplit },
+    { PosixFs, POSIX_FS_CONNECTOR, FsSplit }
 }
 
 impl_split_reader! {
@@ -152,6 +183,8 @@ impl_split_reader! {
     { Kinesis, KinesisSplitReader },
     { Nexmark, NexmarkSplitReader },
     { Datagen, DatagenSplitReader },
+    { S3, S3FileReader },
+    { PosixFs, PosixFsReader },
     { Dummy, DummySplitReader }
 }
 
@@ -251,4 +284,26 @@ mod tests {
             panic!("extract nexmark config failed");
         }
     }
+
+    #[test]
+    fn test_extract_s3_config() {
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "connector" => "s3",
+            "s3.region_name" => "us-east-1",
+            "s3.bucket_name" => "demo",
+            "match_pattern" => "data/*.json",
+            "s3.endpoint_url" => "http://127.0.0.1:9301",
+        ));
+
+        let props = ConnectorProperties::extract(props).unwrap();
+
+        if let ConnectorProperties::S3(props) = props {
+            assert_eq!(props.bucket_name, "demo");
+            assert_eq!(props.match_pattern.as_deref(), Some("data/*.json"));
+            assert_eq!(props.endpoint_url.as_deref(), Some("http://127.0.0.1:9301"));
+            assert!(props.sqs_queue_name.is_empty());
+        } else {
+            panic!("extract s3 config failed");
+        }
+    }
 }
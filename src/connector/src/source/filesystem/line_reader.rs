@@ -0,0 +1,117 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_async_stream::try_stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use crate::source::base::{SourceMessage, MAX_CHUNK_SIZE};
+use crate::source::SplitId;
+
+/// The interval to check whether a growing file has new data after its end is reached.
+const GROWING_FILE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// The partial line at the end of a growing file is emitted once the file has not changed for
+/// this long, as the writer is likely to have finished the file without a trailing newline.
+const GROWING_FILE_QUIESCENT_PERIOD: Duration = Duration::from_secs(2);
+
+/// Reads a file of a file source line by line, e.g. JSON lines or CSV, where `offset` is the byte
+/// offset of the file that `reader` starts at. Each non-empty line is a message whose offset is
+/// the byte offset after it, so that the reading is resumed from the next line after recovery.
+///
+/// If the file is `growing`, i.e. data may be appended to it, the end of the file is polled for
+/// new data, and the partial line at the end, i.e. without a newline, is kept until the rest of it
+/// is appended, or emitted after the file stays unchanged for [`GROWING_FILE_QUIESCENT_PERIOD`].
+/// Otherwise, the last line is emitted at the end of the file even if it has no newline.
+///
+/// The stream never ends after the file is fully read, as new files are discovered by the
+/// enumerator and assigned as new splits.
+#[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+pub(super) async fn read_lines<R>(reader: R, split_id: SplitId, mut offset: u64, growing: bool)
+where
+    R: AsyncBufRead + Unpin + Send + 'static,
+{
+    let mut reader = reader;
+    let mut msgs = Vec::with_capacity(MAX_CHUNK_SIZE);
+    // The line being read, which is the partial line at the end of the file if the end is reached.
+    let mut line = vec![];
+    // How long the partial line at the end of a growing file has stayed unchanged.
+    let mut quiescent = Duration::ZERO;
+    loop {
+        let read_len = line.len();
+        reader.read_until(b'\n', &mut line).await?;
+        if line.last() != Some(&b'\n') {
+            // The end of the file is reached.
+            quiescent = match line.len() == read_len {
+                true => quiescent + GROWING_FILE_POLL_INTERVAL,
+                false => Duration::ZERO,
+            };
+            if !line.is_empty() && (!growing || quiescent >= GROWING_FILE_QUIESCENT_PERIOD) {
+                offset += line.len() as u64;
+                msgs.extend(new_message(std::mem::take(&mut line), offset, &split_id));
+            }
+            if !msgs.is_empty() {
+                yield std::mem::replace(&mut msgs, Vec::with_capacity(MAX_CHUNK_SIZE));
+            }
+            if !growing {
+                break;
+            }
+            tokio::time::sleep(GROWING_FILE_POLL_INTERVAL).await;
+            continue;
+        }
+
+        offset += line.len() as u64;
+        msgs.extend(new_message(std::mem::take(&mut line), offset, &split_id));
+        if msgs.len() >= MAX_CHUNK_SIZE {
+            yield std::mem::replace(&mut msgs, Vec::with_capacity(MAX_CHUNK_SIZE));
+        }
+    }
+
+    futures::future::pending::<()>().await;
+}
+
+/// Reads the header line at the beginning of a file, i.e. `reader`, as a message at `offset`. It's
+/// emitted before the lines after `offset` when the file is resumed from the middle, and its offset
+/// is not moved backwards.
+pub(super) async fn read_header_line<R>(
+    mut reader: R,
+    split_id: &SplitId,
+    offset: u64,
+) -> anyhow::Result<Option<SourceMessage>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = vec![];
+    reader.read_until(b'\n', &mut line).await?;
+    Ok(new_message(line, offset, split_id))
+}
+
+/// Builds the message of a line with the trailing newline trimmed, or `None` if the line is empty.
+fn new_message(mut line: Vec<u8>, offset: u64, split_id: &SplitId) -> Option<SourceMessage> {
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+    if line.is_empty() {
+        return None;
+    }
+    Some(SourceMessage {
+        payload: Some(Bytes::from(line)),
+        offset: offset.to_string(),
+        split_id: split_id.clone(),
+    })
+}
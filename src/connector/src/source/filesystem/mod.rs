@@ -12,4 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 mod file_common;
+mod line_reader;
+pub mod posix_fs;
 pub mod s3;
+mod split;
+
+pub use split::FsSplit;
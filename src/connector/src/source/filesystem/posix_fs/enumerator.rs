@@ -0,0 +1,83 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use globset::{Glob, GlobMatcher};
+
+use crate::source::filesystem::posix_fs::PosixFsProperties;
+use crate::source::filesystem::FsSplit;
+use crate::source::SplitEnumerator;
+
+/// [`PosixFsSplitEnumerator`] walks the directory `root` and its subdirectories, and each regular
+/// file matching `match_pattern` is a split named by its path relative to `root`. As the
+/// enumerator is called periodically by the meta service, new files dropped into the directory
+/// are discovered and assigned to the source actors continuously. The sizes of the files are
+/// re-checked on each listing.
+#[derive(Debug, Clone)]
+pub struct PosixFsSplitEnumerator {
+    root: PathBuf,
+    matcher: Option<GlobMatcher>,
+}
+
+#[async_trait]
+impl SplitEnumerator for PosixFsSplitEnumerator {
+    type Properties = PosixFsProperties;
+    type Split = FsSplit;
+
+    async fn new(properties: Self::Properties) -> Result<Self> {
+        let matcher = match &properties.match_pattern {
+            Some(pattern) => Some(Glob::new(pattern)?.compile_matcher()),
+            None => None,
+        };
+        Ok(Self {
+            root: PathBuf::from(properties.root),
+            matcher,
+        })
+    }
+
+    async fn list_splits(&mut self) -> Result<Vec<Self::Split>> {
+        let mut splits = vec![];
+        let mut dirs = vec![self.root.clone()];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = tokio::fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                let path = entry.path();
+                if metadata.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if !metadata.is_file() {
+                    continue;
+                }
+                let relative_path = path.strip_prefix(&self.root)?;
+                if self
+                    .matcher
+                    .as_ref()
+                    .map_or(true, |matcher| matcher.is_match(relative_path))
+                {
+                    splits.push(FsSplit::new(
+                        relative_path.to_string_lossy().into_owned(),
+                        0,
+                        metadata.len(),
+                    ));
+                }
+            }
+        }
+        Ok(splits)
+    }
+}
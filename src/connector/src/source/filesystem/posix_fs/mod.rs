@@ -0,0 +1,36 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod enumerator;
+mod source;
+
+pub use enumerator::PosixFsSplitEnumerator;
+use serde::Deserialize;
+pub use source::PosixFsReader;
+
+pub const POSIX_FS_CONNECTOR: &str = "posix_fs";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PosixFsProperties {
+    /// The directory to read files from. The files in its subdirectories are read as well.
+    #[serde(rename = "posix_fs.root")]
+    pub root: String,
+    /// A glob pattern of the file paths relative to `root`, e.g. `*.json`.
+    #[serde(rename = "match_pattern", default)]
+    pub match_pattern: Option<String>,
+    /// Whether the first line of each file is a header, which is set for CSV with header rather
+    /// than by the user. See [`crate::source::ConnectorProperties::set_header_line`].
+    #[serde(skip)]
+    pub header_line: bool,
+}
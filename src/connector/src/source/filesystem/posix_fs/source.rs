@@ -0,0 +1,308 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_async_stream::try_stream;
+use tokio::fs::File;
+use tokio::io::{AsyncSeekExt, BufReader};
+
+use crate::source::base::{SourceMessage, SplitReader};
+use crate::source::filesystem::line_reader::{read_header_line, read_lines};
+use crate::source::filesystem::posix_fs::PosixFsProperties;
+use crate::source::filesystem::FsSplit;
+use crate::source::{BoxSourceStream, Column, ConnectorState, SplitImpl, SplitMetaData};
+
+/// [`PosixFsReader`] reads a file under `root` line by line from the byte offset of the split, and
+/// keeps following the file for appended lines. See [`read_lines`].
+#[derive(Debug)]
+pub struct PosixFsReader {
+    root: PathBuf,
+    header_line: bool,
+    split: FsSplit,
+}
+
+#[async_trait]
+impl SplitReader for PosixFsReader {
+    type Properties = PosixFsProperties;
+
+    async fn new(
+        props: PosixFsProperties,
+        state: ConnectorState,
+        _columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        // Only one split is assigned to a reader, see `ConnectorSource::stream_reader`.
+        let split = match state.and_then(|splits| splits.into_iter().next()) {
+            Some(SplitImpl::PosixFs(split)) => split,
+            other => return Err(anyhow!("expect a posix_fs split, got {:?}", other)),
+        };
+        Ok(Self {
+            root: PathBuf::from(props.root),
+            header_line: props.header_line,
+            split,
+        })
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+impl PosixFsReader {
+    /// Opens the file of the split, or returns `None` if the file is removed, e.g. after it's
+    /// fully read, so that the reader does not fail on it forever.
+    async fn open(&self, path: &Path) -> Result<Option<File>> {
+        match File::open(path).await {
+            Ok(file) => Ok(Some(file)),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                if self.split.offset < self.split.size {
+                    tracing::warn!(
+                        "file {} is removed before it's fully read, at offset {} of {}",
+                        path.display(),
+                        self.split.offset,
+                        self.split.size
+                    );
+                }
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_stream(self) {
+        let split_id = self.split.id();
+        let offset = self.split.offset;
+
+        let path = self.root.join(&self.split.name);
+        if self.header_line && offset > 0 {
+            if let Some(file) = self.open(&path).await? {
+                if let Some(msg) = read_header_line(BufReader::new(file), &split_id, offset).await?
+                {
+                    yield vec![msg];
+                }
+            }
+        }
+
+        // The size of the split is the one at listing, so the file is read beyond it in case data
+        // is appended.
+        let mut file = match self.open(&path).await? {
+            Some(file) => file,
+            None => {
+                // There is nothing more to read, like a fully read file, see `read_lines`.
+                futures::future::pending::<()>().await;
+                unreachable!();
+            }
+        };
+        file.seek(SeekFrom::Start(offset)).await?;
+
+        #[for_await]
+        for msgs in read_lines(BufReader::new(file), split_id, offset, true) {
+            yield msgs?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bytes::Bytes;
+    use futures::StreamExt;
+    use maplit::*;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::source::filesystem::posix_fs::PosixFsSplitEnumerator;
+    use crate::source::{ConnectorProperties, SplitEnumerator};
+
+    async fn read(props: &PosixFsProperties, split: FsSplit) -> Vec<(Bytes, String)> {
+        let reader = PosixFsReader::new(props.clone(), Some(vec![SplitImpl::PosixFs(split)]), None)
+            .await
+            .unwrap();
+        let msgs = reader.into_stream().next().await.unwrap().unwrap();
+        msgs.into_iter()
+            .map(|msg| (msg.payload.unwrap(), msg.offset))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_posix_fs_source() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        tokio::fs::create_dir(root.path().join("2022")).await?;
+        tokio::fs::write(root.path().join("a.json"), "{\"v\": 1}\n{\"v\": 2}\n").await?;
+        tokio::fs::write(
+            root.path().join("2022/b.json"),
+            "{\"v\": 3}\r\n\n{\"v\": 4}",
+        )
+        .await?;
+        tokio::fs::write(root.path().join("c.csv"), "5\n").await?;
+
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "connector" => "posix_fs",
+            "posix_fs.root" => root.path().to_str().unwrap(),
+            "match_pattern" => "*.json",
+        ));
+        let props = match ConnectorProperties::extract(props)? {
+            ConnectorProperties::PosixFs(props) => *props,
+            _ => panic!("extract posix_fs config failed"),
+        };
+
+        let mut enumerator = PosixFsSplitEnumerator::new(props.clone()).await?;
+        let mut splits = enumerator.list_splits().await?;
+        splits.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            splits,
+            vec![
+                FsSplit::new("2022/b.json".to_string(), 0, 19),
+                FsSplit::new("a.json".to_string(), 0, 18),
+            ]
+        );
+
+        // The last line without a newline may be incomplete, so it's read after the file stays
+        // unchanged for a while.
+        let reader = PosixFsReader::new(
+            props.clone(),
+            Some(vec![SplitImpl::PosixFs(splits[0].clone())]),
+            None,
+        )
+        .await?;
+        let mut stream = reader.into_stream();
+        let msgs = stream.next().await.unwrap()?;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, Some(Bytes::from("{\"v\": 3}")));
+        assert_eq!(msgs[0].offset, "10");
+        let msgs = stream.next().await.unwrap()?;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, Some(Bytes::from("{\"v\": 4}")));
+        assert_eq!(msgs[0].offset, "19");
+        // Resume from the offset after the first line.
+        assert_eq!(
+            read(&props, splits[1].copy_with_offset("9".to_string())).await,
+            vec![(Bytes::from("{\"v\": 2}"), "18".to_string())]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_posix_fs_growing_file() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        let path = root.path().join("a.json");
+        tokio::fs::write(&path, "{\"v\": 1}\n{\"v\"").await?;
+
+        let props = PosixFsProperties {
+            root: root.path().to_str().unwrap().to_string(),
+            match_pattern: None,
+            header_line: false,
+        };
+        let mut enumerator = PosixFsSplitEnumerator::new(props.clone()).await?;
+        let splits = enumerator.list_splits().await?;
+        assert_eq!(splits, vec![FsSplit::new("a.json".to_string(), 0, 13)]);
+
+        let reader = PosixFsReader::new(
+            props,
+            Some(vec![SplitImpl::PosixFs(splits[0].clone())]),
+            None,
+        )
+        .await?;
+        let mut stream = reader.into_stream();
+        let msgs = stream.next().await.unwrap()?;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, Some(Bytes::from("{\"v\": 1}")));
+        assert_eq!(msgs[0].offset, "9");
+
+        // The partial line is completed by the appended data, which is read beyond the size of
+        // the split.
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await?;
+        file.write_all(b": 2}\n{\"v\": 3}\n").await?;
+        file.flush().await?;
+        let msgs = stream.next().await.unwrap()?;
+        let msgs = msgs
+            .into_iter()
+            .map(|msg| (msg.payload.unwrap(), msg.offset))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            msgs,
+            vec![
+                (Bytes::from("{\"v\": 2}"), "18".to_string()),
+                (Bytes::from("{\"v\": 3}"), "27".to_string()),
+            ]
+        );
+
+        // The size is re-checked on listing.
+        assert_eq!(
+            enumerator.list_splits().await?,
+            vec![FsSplit::new("a.json".to_string(), 0, 27)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_posix_fs_removed_file() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        let props = PosixFsProperties {
+            root: root.path().to_str().unwrap().to_string(),
+            match_pattern: None,
+            header_line: true,
+        };
+        // The file of a fully read split is removed, which must not fail the reader.
+        let split = FsSplit::new("a.csv".to_string(), 16, 16);
+        let reader = PosixFsReader::new(props, Some(vec![SplitImpl::PosixFs(split)]), None).await?;
+        let mut stream = reader.into_stream();
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_posix_fs_resume_with_header_line() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        tokio::fs::write(root.path().join("a.csv"), "id,name\n1,a\n2,b\n").await?;
+
+        let props = PosixFsProperties {
+            root: root.path().to_str().unwrap().to_string(),
+            match_pattern: None,
+            header_line: true,
+        };
+        // Resume from the offset after the first row.
+        let split = FsSplit::new("a.csv".to_string(), 12, 16);
+        let reader = PosixFsReader::new(props, Some(vec![SplitImpl::PosixFs(split)]), None).await?;
+        let mut stream = reader.into_stream();
+
+        // The header line is emitted first without moving the offset backwards.
+        let msgs = stream.next().await.unwrap()?;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, Some(Bytes::from("id,name")));
+        assert_eq!(msgs[0].offset, "12");
+
+        let msgs = stream.next().await.unwrap()?;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].payload, Some(Bytes::from("2,b")));
+        assert_eq!(msgs[0].offset, "16");
+
+        Ok(())
+    }
+}
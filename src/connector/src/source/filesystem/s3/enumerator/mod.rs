@@ -0,0 +1,182 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use aws_sdk_s3::client::Client;
+use globset::{Glob, GlobMatcher};
+
+use crate::source::filesystem::s3::s3_dir::find_prefix;
+use crate::source::filesystem::s3::S3Properties;
+use crate::source::filesystem::FsSplit;
+use crate::source::SplitEnumerator;
+
+/// [`S3SplitEnumerator`] lists the objects in the bucket matching `match_pattern`, each of which
+/// is a split. As the enumerator is called periodically by the meta service, newly uploaded
+/// objects are discovered and assigned to the source actors continuously.
+#[derive(Debug, Clone)]
+pub struct S3SplitEnumerator {
+    bucket_name: String,
+    /// The literal prefix of `match_pattern`, used to narrow down the listing.
+    prefix: Option<String>,
+    matcher: Option<GlobMatcher>,
+    client: Client,
+}
+
+#[async_trait]
+impl SplitEnumerator for S3SplitEnumerator {
+    type Properties = S3Properties;
+    type Split = FsSplit;
+
+    async fn new(properties: Self::Properties) -> Result<Self> {
+        let (prefix, matcher) = match &properties.match_pattern {
+            Some(pattern) => {
+                let glob = Glob::new(pattern)?;
+                (Some(find_prefix(glob.glob())), Some(glob.compile_matcher()))
+            }
+            None => (None, None),
+        };
+        Ok(Self {
+            bucket_name: properties.bucket_name.clone(),
+            prefix,
+            matcher,
+            client: properties.build_client().await,
+        })
+    }
+
+    async fn list_splits(&mut self) -> Result<Vec<Self::Split>> {
+        let mut splits = vec![];
+        let mut continuation_token = None;
+        loop {
+            let response = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket_name)
+                .set_prefix(self.prefix.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+            for object in response.contents().unwrap_or_default() {
+                let Some(key) = object.key() else { continue };
+                if self
+                    .matcher
+                    .as_ref()
+                    .map_or(true, |matcher| matcher.is_match(key))
+                {
+                    splits.push(FsSplit::new(key.to_string(), 0, object.size() as u64));
+                }
+            }
+            if !response.is_truncated() {
+                break;
+            }
+            continuation_token = response.next_continuation_token().map(str::to_string);
+        }
+        Ok(splits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn mock_s3_properties(endpoint_url: String, match_pattern: &str) -> S3Properties {
+        S3Properties {
+            region_name: "us-east-1".to_string(),
+            bucket_name: "bucket".to_string(),
+            sqs_queue_name: "".to_string(),
+            match_pattern: Some(match_pattern.to_string()),
+            access: "access".to_string(),
+            secret: "secret".to_string(),
+            endpoint_url: Some(endpoint_url),
+            header_line: false,
+        }
+    }
+
+    fn list_objects_response(objects: &[(&str, u64)], next_token: Option<&str>) -> String {
+        let contents: String = objects
+            .iter()
+            .map(|(key, size)| {
+                format!(
+                    "<Contents><Key>{}</Key><Size>{}</Size></Contents>",
+                    key, size
+                )
+            })
+            .collect();
+        let next_token = next_token
+            .map(|token| format!("<NextContinuationToken>{}</NextContinuationToken>", token))
+            .unwrap_or_default();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>bucket</Name>
+    <IsTruncated>{}</IsTruncated>
+    {}
+    {}
+</ListBucketResult>"#,
+            !next_token.is_empty(),
+            contents,
+            next_token
+        )
+    }
+
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_list_splits_with_pagination_and_glob() {
+        let server = MockServer::start().await;
+        // The second page is mounted first so that it takes precedence over the first page, whose
+        // request has no continuation token.
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("prefix", "data/"))
+            .and(query_param("continuation-token", "token-1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(list_objects_response(
+                    &[("data/c.json", 30), ("data/d.json", 40)],
+                    None,
+                )),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("prefix", "data/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(list_objects_response(
+                    &[("data/a.json", 10), ("data/b.csv", 20)],
+                    Some("token-1"),
+                )),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut enumerator =
+            S3SplitEnumerator::new(mock_s3_properties(server.uri(), "data/*.json"))
+                .await
+                .unwrap();
+        let splits = enumerator.list_splits().await.unwrap();
+        assert_eq!(
+            splits,
+            vec![
+                FsSplit::new("data/a.json".to_string(), 0, 10),
+                FsSplit::new("data/c.json".to_string(), 0, 30),
+                FsSplit::new("data/d.json".to_string(), 0, 40),
+            ]
+        );
+    }
+}
@@ -11,11 +11,18 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+pub mod enumerator;
 mod s3_dir;
 mod s3_notification_event;
-mod source;
+pub mod source;
 
+use std::collections::HashMap;
+
+pub use enumerator::S3SplitEnumerator;
 use serde::Deserialize;
+pub use source::S3FileReader;
+
+use crate::aws_utils::{default_conn_config, s3_client, AwsConfigV2};
 
 pub const S3_CONNECTOR: &str = "s3";
 
@@ -25,12 +32,37 @@ pub struct S3Properties {
     pub region_name: String,
     #[serde(rename = "s3.bucket_name")]
     pub bucket_name: String,
-    #[serde(rename = "sqs_queue_name")]
+    /// Only used by the SQS-notified directory. Files are discovered by listing the bucket.
+    #[serde(rename = "sqs_queue_name", default)]
     pub sqs_queue_name: String,
+    /// A glob pattern of the object keys to read, e.g. `logs/2022-*.json`.
     #[serde(rename = "match_pattern", default)]
     pub match_pattern: Option<String>,
     #[serde(rename = "s3.credentials.access", default)]
     pub access: String,
     #[serde(rename = "s3.credentials.secret", default)]
     pub secret: String,
+    /// A custom endpoint of an S3-compatible service, e.g. `http://127.0.0.1:9301` of MinIO.
+    #[serde(rename = "s3.endpoint_url", default)]
+    pub endpoint_url: Option<String>,
+    /// Whether the first line of each file is a header, which is set for CSV with header rather
+    /// than by the user. See [`crate::source::ConnectorProperties::set_header_line`].
+    #[serde(skip)]
+    pub header_line: bool,
+}
+
+impl S3Properties {
+    /// Builds an S3 client with the region, endpoint and static credential in the properties.
+    pub(crate) async fn build_client(&self) -> aws_sdk_s3::Client {
+        let mut config_pairs = HashMap::from([("region".to_string(), self.region_name.clone())]);
+        if !self.access.is_empty() && !self.secret.is_empty() {
+            config_pairs.insert("access_key".to_string(), self.access.clone());
+            config_pairs.insert("secret_access".to_string(), self.secret.clone());
+        }
+        if let Some(endpoint_url) = &self.endpoint_url {
+            config_pairs.insert("endpoint_url".to_string(), endpoint_url.clone());
+        }
+        let shared_config = AwsConfigV2::from(config_pairs).load_config(None).await;
+        s3_client(&shared_config, Some(default_conn_config()))
+    }
 }
@@ -89,7 +89,7 @@ impl Default for SqsReceiveMsgConfig {
     }
 }
 
-fn find_prefix(match_pattern: &str) -> String {
+pub(super) fn find_prefix(match_pattern: &str) -> String {
     let mut escaped = false;
     let mut escaped_filter = false;
     match_pattern
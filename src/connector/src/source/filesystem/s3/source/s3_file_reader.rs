@@ -12,357 +12,207 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
-
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use aws_sdk_s3::client as s3_client;
-use aws_smithy_http::byte_stream::ByteStream;
-use bytes::Bytes;
-use futures::{StreamExt, TryStreamExt};
+use aws_sdk_s3::client::Client;
+use aws_sdk_s3::types::SdkError;
+use futures::TryStreamExt;
 use futures_async_stream::try_stream;
-use io::StreamReader;
-use serde::{Deserialize, Serialize};
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::ReceiverStream;
-use tokio_util::io;
-use tokio_util::io::ReaderStream;
-use tracing::{error, info};
+use tokio::io::{AsyncBufRead, BufReader};
+use tokio_util::io::StreamReader;
 
-use crate::aws_utils::{default_conn_config, s3_client, AwsConfigV2, AwsCredentialV2};
-use crate::source::base::{SourceMessage, SplitReader, MAX_CHUNK_SIZE};
-use crate::source::filesystem::file_common::EntryStat;
-use crate::source::filesystem::s3::s3_dir::FileSystemOptError::IllegalS3FilePath;
-use crate::source::filesystem::s3::s3_dir::{
-    AwsCustomConfig, S3SourceBasicConfig, S3SourceConfig, SqsReceiveMsgConfig,
-};
+use crate::source::base::{SourceMessage, SplitReader};
+use crate::source::filesystem::line_reader::{read_header_line, read_lines};
 use crate::source::filesystem::s3::S3Properties;
-use crate::source::{BoxSourceStream, Column, ConnectorState, SplitId, SplitMetaData};
-
-const MAX_CHANNEL_BUFFER_SIZE: usize = 2048;
-const STREAM_READER_CAPACITY: usize = 4096;
-
-#[derive(Debug, Clone)]
-struct S3InnerMessage {
-    msg_id: String,
-    payload: Bytes,
-}
-
-/// `S3File` contains the metadata and the start and end positions that need to be read.
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-struct S3File {
-    object: EntryStat,
-    start: i64,
-    end: Option<i64>,
-}
+use crate::source::filesystem::FsSplit;
+use crate::source::{BoxSourceStream, Column, ConnectorState, SplitImpl, SplitMetaData};
 
-impl Default for S3File {
-    fn default() -> Self {
-        S3File {
-            object: EntryStat::default(),
-            start: i64::MIN,
-            end: None,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-pub struct S3FileSplit {
-    bucket: String,
-    s3_file: S3File,
+/// [`S3FileReader`] reads an object of S3 line by line from the byte offset of the split. See
+/// [`read_lines`].
+#[derive(Debug)]
+pub struct S3FileReader {
+    bucket_name: String,
+    header_line: bool,
+    split: FsSplit,
+    client: Client,
 }
 
-impl Default for S3FileSplit {
-    fn default() -> Self {
-        S3FileSplit {
-            bucket: "".to_string(),
-            s3_file: S3File::default(),
-        }
-    }
-}
+#[async_trait]
+impl SplitReader for S3FileReader {
+    type Properties = S3Properties;
 
-impl S3FileSplit {
-    // path s3://bucket_name/path
-    fn from_path(path_string: String) -> Result<Self> {
-        let mut s3_file = S3File::default();
-        s3_file.object.path = path_string.clone();
-        let path = std::path::Path::new(path_string.as_str());
-        let parent_path = path
-            .parent()
-            .ok_or_else(|| IllegalS3FilePath(path_string.clone()))?;
+    async fn new(
+        props: S3Properties,
+        state: ConnectorState,
+        _columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        // Only one split is assigned to a reader, see `ConnectorSource::stream_reader`.
+        let split = match state.and_then(|splits| splits.into_iter().next()) {
+            Some(SplitImpl::S3(split)) => split,
+            other => return Err(anyhow!("expect an S3 split, got {:?}", other)),
+        };
         Ok(Self {
-            bucket: parent_path
-                .file_name()
-                .unwrap()
-                .to_os_string()
-                .into_string()
-                .unwrap(),
-            s3_file,
+            bucket_name: props.bucket_name.clone(),
+            header_line: props.header_line,
+            split,
+            client: props.build_client().await,
         })
     }
 
-    fn new(bucket: String, s3_files: S3File) -> Self {
-        Self {
-            bucket,
-            s3_file: s3_files,
-        }
-    }
-}
-
-impl SplitMetaData for S3FileSplit {
-    fn id(&self) -> SplitId {
-        // TODO: should avoid constructing a string every time
-        format!("{}/{}", self.bucket, self.s3_file.object.path).into()
-    }
-
-    fn encode_to_bytes(&self) -> Bytes {
-        Bytes::from(serde_json::to_string(self).unwrap())
-    }
-
-    fn restore_from_bytes(bytes: &[u8]) -> Result<Self> {
-        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
     }
 }
 
-#[derive(Debug)]
-pub struct S3FileReader {
-    client_for_s3: s3_client::Client,
-    s3_file_sender: mpsc::UnboundedSender<S3FileSplit>,
-    // Considering that there are very many files under a bucket,
-    // it is necessary to maintain multiple file offsets in a single S3FileReader .
-    split_offset: HashMap<String, u64>,
-    s3_receive_stream: ReceiverStream<S3InnerMessage>,
-    s3_msg_sender: mpsc::Sender<S3InnerMessage>,
-}
-
 impl S3FileReader {
-    fn build_from_config(s3_source_config: S3SourceConfig) -> Self {
-        let (tx, rx) = mpsc::channel(MAX_CHANNEL_BUFFER_SIZE);
-        let (split_s, mut split_r) = mpsc::unbounded_channel();
-        let s3_file_reader = S3FileReader {
-            client_for_s3: s3_client(&s3_source_config.shared_config, None),
-            s3_file_sender: split_s,
-            split_offset: HashMap::new(),
-            s3_receive_stream: ReceiverStream::from(rx),
-            s3_msg_sender: tx.clone(),
-        };
-        tokio::task::spawn(async move {
-            let s3_client = s3_client(&s3_source_config.shared_config, Some(default_conn_config()));
-            while let Some(s3_split) = split_r.recv().await {
-                _ = S3FileReader::stream_read(s3_client.clone(), s3_split.clone(), tx.clone())
-                    .await;
-            }
-        });
-        s3_file_reader
-    }
-
-    async fn stream_read(
-        client_for_s3: s3_client::Client,
-        s3_file_split: S3FileSplit,
-        s3_msg_sender: mpsc::Sender<S3InnerMessage>,
-    ) -> Result<()> {
-        let bucket = s3_file_split.bucket.clone();
-        let s3_file = s3_file_split.s3_file.clone();
-        let obj_val = S3FileReader::get_object(&client_for_s3, &s3_file, bucket.as_str()).await;
-        match obj_val {
-            Ok(byte_stream) => {
-                let stream_reader = StreamReader::new(
-                    byte_stream.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
-                );
-
-                let mut reader = Box::pin(BufReader::new(stream_reader));
-
-                let buf_empty = match reader.fill_buf().await {
-                    Ok(buffer) => {
-                        if buffer.is_empty() {
-                            info!(
-                                "current s3_file is empty.bucket={}, file_path={}",
-                                bucket, s3_file.object.path
-                            );
-                            Ok(true)
-                        } else {
-                            Ok(false)
-                        }
-                    }
-                    Err(fill_buf_err) => Err(fill_buf_err),
-                };
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_stream(self) {
+        let split_id = self.split.id();
+        let offset = self.split.offset;
 
-                match buf_empty {
-                    Ok(is_empty) => {
-                        if is_empty {
-                            return Ok(());
-                        }
-                    }
-                    Err(err) => {
-                        return Err(anyhow::Error::from(err));
-                    }
-                };
-                let mut stream = ReaderStream::with_capacity(reader, STREAM_READER_CAPACITY);
+        if offset >= self.split.size {
+            // The object is fully read.
+            futures::future::pending::<()>().await;
+        }
 
-                while let Some(result) = stream.next().await {
-                    match result {
-                        Ok(read_bytes) => {
-                            let msg_id = format!(
-                                "s3://{}/{}",
-                                s3_file_split.bucket, s3_file_split.s3_file.object.path
-                            );
-                            let s3_inner_msg = S3InnerMessage {
-                                msg_id,
-                                payload: read_bytes,
-                            };
-                            if s3_msg_sender.send(s3_inner_msg).await.is_err() {
-                                return Err(anyhow::Error::from(crate::source::filesystem::s3::s3_dir::FileSystemOptError::GetS3ObjectError(
-                                    bucket,
-                                    s3_file.object.path,
-                                )));
-                            }
-                        }
-                        Err(read_bytes_err) => {
-                            return Err(anyhow::Error::from(read_bytes_err));
-                        }
-                    }
+        if self.header_line && offset > 0 {
+            if let Some(reader) = self.get_object(0).await? {
+                if let Some(msg) = read_header_line(reader, &split_id, offset).await? {
+                    yield vec![msg];
                 }
-                Ok(())
             }
-            Err(err) => {
-                error!("S3FileReader get_object error cause by {:?}", err);
-                Err(err)
+        }
+
+        if let Some(reader) = self.get_object(offset).await? {
+            #[for_await]
+            for msgs in read_lines(reader, split_id, offset, false) {
+                yield msgs?;
             }
         }
+        // The object is deleted, and there is nothing more to read.
+        futures::future::pending::<()>().await;
     }
 
+    /// Gets the object from the byte `offset` by a range GET, or returns `None` if the object is
+    /// deleted, so that the reader does not fail on it forever.
     async fn get_object(
-        client_for_s3: &s3_client::Client,
-        s3_file: &S3File,
-        bucket: &str,
-    ) -> anyhow::Result<ByteStream> {
-        let path = s3_file.object.path.as_str();
-        let s3_object_key = std::path::Path::new(path)
-            .file_name()
-            .unwrap()
-            .to_os_string()
-            .into_string()
-            .unwrap();
-        // TODO use set_range
-        let get_object = client_for_s3
+        &self,
+        offset: u64,
+    ) -> Result<Option<impl AsyncBufRead + Unpin + Send + 'static>> {
+        let mut request = self
+            .client
             .get_object()
-            .bucket(bucket)
-            .key(s3_object_key)
-            .send()
-            .await;
-        match get_object {
-            Ok(get_object_out) => Ok(get_object_out.body),
-            Err(sdk_err) => Err(anyhow::Error::from(
-                crate::source::filesystem::s3::s3_dir::FileSystemOptError::AwsSdkInnerError(
-                    format!("S3 GetObject from {} error:", bucket),
-                    sdk_err.to_string(),
-                ),
-            )),
+            .bucket(&self.bucket_name)
+            .key(&self.split.name);
+        if offset > 0 {
+            request = request.range(format!("bytes={}-", offset));
         }
-    }
-}
-
-#[async_trait]
-impl SplitReader for S3FileReader {
-    type Properties = S3Properties;
-
-    /// 1. The config include all information about the connection to S3, for example:
-    /// `s3.region_name, s3.bucket_name, s3-dd-storage-notify-queue` and the credential's
-    /// `access_key` and secret. For now, only static credential is supported.
-    /// 2. The identifier of the State is the Path of S3 - <S3://bucket_name/object_key>
-    async fn new(
-        props: S3Properties,
-        _state: ConnectorState,
-        _columns: Option<Vec<Column>>,
-    ) -> Result<Self> {
-        let s3_basic_config = S3SourceBasicConfig::from(props);
-        let credential = if s3_basic_config.secret.is_empty() || s3_basic_config.access.is_empty() {
-            AwsCredentialV2::None
-        } else {
-            AwsCredentialV2::Static {
-                access_key: s3_basic_config.access.clone(),
-                secret_access: s3_basic_config.secret.clone(),
-                session_token: None,
+        let body = match request.send().await {
+            Ok(output) => output.body,
+            Err(SdkError::ServiceError { err, .. }) if err.is_no_such_key() => {
+                tracing::warn!(
+                    "object {} is deleted before it's fully read, at offset {} of {}",
+                    self.split.name,
+                    self.split.offset,
+                    self.split.size
+                );
+                return Ok(None);
             }
+            Err(e) => return Err(e.into()),
         };
-        let aws_config = AwsConfigV2 {
-            region: None,
-            arn: None,
-            credential,
-            endpoint: None,
-        };
-        // TODO: should be use external_id parameter.
-        let shared_config = aws_config.load_config(None).await;
-        let s3_source_config = S3SourceConfig {
-            basic_config: s3_basic_config.clone(),
-            shared_config,
-            custom_config: Some(AwsCustomConfig::default()),
-            sqs_config: SqsReceiveMsgConfig::default(),
-        };
-        let s3_file_reader = S3FileReader::build_from_config(s3_source_config);
-        // TODO: new s3 reader with ConnectorState
-        Ok(s3_file_reader)
-    }
-
-    fn into_stream(self) -> BoxSourceStream {
-        self.into_stream()
-    }
-}
-
-impl S3FileReader {
-    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
-    async fn into_stream(mut self) {
-        #[for_await]
-        for msgs in self.s3_receive_stream.ready_chunks(MAX_CHUNK_SIZE) {
-            yield msgs
-                .into_iter()
-                .map(|msg| {
-                    let msg_id = msg.msg_id;
-                    let new_offset = if !self.split_offset.contains_key(msg_id.as_str()) {
-                        1_u64
-                    } else {
-                        let curr_offset = self.split_offset.get(msg_id.as_str()).unwrap();
-                        curr_offset + 1_u64
-                    };
-                    self.split_offset.insert(msg_id.clone(), new_offset);
-                    SourceMessage {
-                        payload: Some(msg.payload),
-                        offset: new_offset.to_string(),
-                        split_id: msg_id.into(),
-                    }
-                })
-                .collect();
-        }
+        let stream_reader =
+            StreamReader::new(body.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)));
+        Ok(Some(BufReader::new(stream_reader)))
     }
 }
 
 #[cfg(test)]
-mod test {
-
-    use crate::source::filesystem::s3::source::s3_file_reader::S3FileSplit;
-    use crate::source::filesystem::s3::S3Properties;
-
-    const TEST_REGION_NAME: &str = "cn-north-1";
-    const BUCKET_NAME: &str = "dd-storage-s3";
-
-    const EMPTY_JSON_FILE_NAME: &str = "EMPTY-2022-03-23-03:35:28.json";
-    const SMALL_JSON_FILE_NAME: &str = "2022-02-28-09:32:34-example.json";
-    const EMPTY_JSON_DATA: &str = r#""#;
+mod tests {
+    use futures::StreamExt;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_resume_from_offset() {
+        let content = "{\"v\":1}\n{\"v\":2}\n{\"v\":3}\n";
+        let server = MockServer::start().await;
+        // Only the part after the offset is requested by a range GET.
+        Mock::given(method("GET"))
+            .and(path("/bucket/data/a.json"))
+            .and(header("range", "bytes=8-"))
+            .respond_with(ResponseTemplate::new(206).set_body_string(&content[8..]))
+            .expect(1)
+            .mount(&server)
+            .await;
 
-    fn test_config_map() -> S3Properties {
-        S3Properties {
-            region_name: TEST_REGION_NAME.to_string(),
-            bucket_name: BUCKET_NAME.to_string(),
-            sqs_queue_name: "s3-dd-storage-notify-queue".to_string(),
+        let props = S3Properties {
+            region_name: "us-east-1".to_string(),
+            bucket_name: "bucket".to_string(),
+            sqs_queue_name: "".to_string(),
             match_pattern: None,
-            access: "".to_string(),
-            secret: "".to_string(),
-        }
+            access: "access".to_string(),
+            secret: "secret".to_string(),
+            endpoint_url: Some(server.uri()),
+            header_line: false,
+        };
+        let split = FsSplit::new("data/a.json".to_string(), 8, content.len() as u64);
+        let reader = S3FileReader::new(props, Some(vec![SplitImpl::S3(split)]), None)
+            .await
+            .unwrap();
+        let msgs = reader.into_stream().next().await.unwrap().unwrap();
+
+        let payloads = msgs
+            .iter()
+            .map(|msg| msg.payload.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            payloads,
+            vec![b"{\"v\":2}".as_slice(), b"{\"v\":3}".as_slice()]
+        );
+        let offsets = msgs
+            .iter()
+            .map(|msg| msg.offset.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec!["16", "24"]);
     }
 
-    fn new_test_s3_file_split(split_str: &str) -> S3FileSplit {
-        let s3_file_split: S3FileSplit = serde_json::from_str(split_str).unwrap();
-        s3_file_split
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_deleted_object() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/data/a.json"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message></Error>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let props = S3Properties {
+            region_name: "us-east-1".to_string(),
+            bucket_name: "bucket".to_string(),
+            sqs_queue_name: "".to_string(),
+            match_pattern: None,
+            access: "access".to_string(),
+            secret: "secret".to_string(),
+            endpoint_url: Some(server.uri()),
+            header_line: false,
+        };
+        // The object is deleted after it's partially read, which must not fail the reader.
+        let split = FsSplit::new("data/a.json".to_string(), 8, 24);
+        let reader = S3FileReader::new(props, Some(vec![SplitImpl::S3(split)]), None)
+            .await
+            .unwrap();
+        let mut stream = reader.into_stream();
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(500), stream.next())
+                .await
+                .is_err()
+        );
     }
 }
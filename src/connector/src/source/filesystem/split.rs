@@ -0,0 +1,58 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::{SplitId, SplitMetaData};
+
+/// [`FsSplit`] describes a file of a file source. A file is read from the beginning to the end
+/// exactly once, and `offset` is the number of bytes read so far. `size` is the size of the file
+/// when it's listed, and a local file may grow beyond it, see `PosixFsReader`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Hash)]
+pub struct FsSplit {
+    /// The path of the file, e.g. the object key in S3.
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+impl SplitMetaData for FsSplit {
+    fn id(&self) -> SplitId {
+        self.name.as_str().into()
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+impl FsSplit {
+    pub fn new(name: String, offset: u64, size: u64) -> Self {
+        Self { name, offset, size }
+    }
+
+    pub fn copy_with_offset(&self, start_offset: String) -> Self {
+        Self::new(
+            self.name.clone(),
+            start_offset.as_str().parse::<u64>().unwrap(),
+            self.size,
+        )
+    }
+}
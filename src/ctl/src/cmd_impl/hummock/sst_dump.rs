@@ -213,7 +213,10 @@ fn print_table_column(
         .iter()
         .map(|idx| table_catalog.columns[*idx].data_type().clone())
         .collect_vec();
-    let row_deserializer = RowDeserializer::new(data_types);
+    let row_deserializer = RowDeserializer::with_appended_defaults(
+        data_types,
+        table_catalog.appended_value_defaults(),
+    );
     let row = row_deserializer.deserialize(user_val)?;
     for (c, v) in column_desc.iter().zip_eq(row.0.iter()) {
         println!(
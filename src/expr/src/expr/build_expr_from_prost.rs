@@ -18,12 +18,15 @@ use risingwave_pb::expr::expr_node::RexNode;
 use risingwave_pb::expr::ExprNode;
 
 use crate::expr::expr_binary_bytes::{
-    new_ltrim_characters, new_repeat, new_rtrim_characters, new_substr_start, new_to_char,
-    new_trim_characters,
+    new_ltrim_characters, new_repeat, new_rtrim_characters, new_substr_start,
+    new_timestampz_to_string_at_time_zone, new_to_char, new_trim_characters,
+};
+use crate::expr::expr_binary_nonnull::{
+    new_binary_expr, new_date_trunc_expr, new_like_default, new_str_to_timestampz_at_time_zone,
 };
-use crate::expr::expr_binary_nonnull::{new_binary_expr, new_like_default};
 use crate::expr::expr_binary_nullable::new_nullable_binary_expr;
 use crate::expr::expr_quaternary_bytes::new_overlay_for_exp;
+use crate::expr::expr_ternary::{new_date_trunc_at_time_zone, new_extract_at_time_zone};
 use crate::expr::expr_ternary_bytes::{
     new_overlay_exp, new_replace_expr, new_split_part_expr, new_substr_start_end,
     new_translate_expr,
@@ -210,6 +213,52 @@ pub fn build_split_part_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     ))
 }
 
+pub fn build_extract_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 2 || children.len() == 3);
+    let time_unit = expr_build_from_prost(&children[0])?;
+    let source = expr_build_from_prost(&children[1])?;
+    if children.len() == 2 {
+        new_binary_expr(prost.get_expr_type().unwrap(), ret_type, time_unit, source)
+    } else {
+        let time_zone = expr_build_from_prost(&children[2])?;
+        new_extract_at_time_zone(time_unit, source, time_zone, ret_type)
+    }
+}
+
+pub fn build_date_trunc_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 2 || children.len() == 3);
+    let field = expr_build_from_prost(&children[0])?;
+    let source = expr_build_from_prost(&children[1])?;
+    if children.len() == 2 {
+        new_date_trunc_expr(ret_type, field, source)
+    } else {
+        let time_zone = expr_build_from_prost(&children[2])?;
+        new_date_trunc_at_time_zone(field, source, time_zone, ret_type)
+    }
+}
+
+pub fn build_cast_with_time_zone_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    let [child, time_zone]: [_; 2] = children.try_into().unwrap();
+    let child = expr_build_from_prost(&child)?;
+    let time_zone = expr_build_from_prost(&time_zone)?;
+    match (child.return_type(), &ret_type) {
+        (DataType::Varchar, DataType::Timestampz) => Ok(new_str_to_timestampz_at_time_zone(
+            child, time_zone, ret_type,
+        )),
+        (DataType::Timestampz, DataType::Varchar) => Ok(new_timestampz_to_string_at_time_zone(
+            child, time_zone, ret_type,
+        )),
+        (from, to) => bail!(
+            "Unsupported cast with time zone from {:?} to {:?}",
+            from,
+            to
+        ),
+    }
+}
+
 pub fn build_to_char_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     let (children, ret_type) = get_children_and_return_type(prost)?;
     ensure!(children.len() == 2);
@@ -14,7 +14,7 @@
 
 //! For expression that only accept two arguments + 1 bytes writer as input.
 
-use risingwave_common::array::{I32Array, NaiveDateTimeArray, Utf8Array};
+use risingwave_common::array::{I32Array, I64Array, NaiveDateTimeArray, Utf8Array};
 use risingwave_common::types::DataType;
 
 use super::Expression;
@@ -23,6 +23,7 @@ use crate::expr::BoxedExpression;
 use crate::vector_op::concat_op::concat_op;
 use crate::vector_op::repeat::repeat;
 use crate::vector_op::substr::*;
+use crate::vector_op::timestampz::timestampz_to_string_at_time_zone;
 use crate::vector_op::to_char::to_char_timestamp;
 use crate::vector_op::trim_characters::{ltrim_characters, rtrim_characters, trim_characters};
 
@@ -70,6 +71,20 @@ pub fn new_to_char(
     .boxed()
 }
 
+pub fn new_timestampz_to_string_at_time_zone(
+    expr_ia1: BoxedExpression,
+    time_zone: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    BinaryBytesExpression::<I64Array, Utf8Array, _>::new(
+        expr_ia1,
+        time_zone,
+        return_type,
+        timestampz_to_string_at_time_zone,
+    )
+    .boxed()
+}
+
 pub fn new_repeat(
     expr_ia1: BoxedExpression,
     expr_ia2: BoxedExpression,
@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use risingwave_common::array::{
-    Array, BoolArray, DecimalArray, I32Array, I64Array, IntervalArray, ListArray, NaiveDateArray,
-    NaiveDateTimeArray, StructArray, Utf8Array,
+    Array, BoolArray, BytesArray, DecimalArray, I32Array, I64Array, IntervalArray, JsonbArray,
+    ListArray, NaiveDateArray, NaiveDateTimeArray, StructArray, Utf8Array,
 };
 use risingwave_common::types::*;
 use risingwave_pb::expr::expr_node::Type;
@@ -25,12 +25,15 @@ use crate::expr::BoxedExpression;
 use crate::vector_op::arithmetic_op::*;
 use crate::vector_op::bitwise_op::*;
 use crate::vector_op::cmp::*;
+use crate::vector_op::date_trunc::{date_trunc_timestamp, date_trunc_timestampz_at_time_zone};
 use crate::vector_op::extract::{
     extract_from_date, extract_from_timestamp, extract_from_timestampz,
 };
+use crate::vector_op::jsonb_access::jsonb_contains;
 use crate::vector_op::like::like_default;
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
+use crate::vector_op::timestampz::str_to_timestampz_at_time_zone;
 use crate::vector_op::tumble::{
     tumble_start_date, tumble_start_date_time, tumble_start_timestampz,
 };
@@ -168,6 +171,14 @@ macro_rules! gen_binary_expr_cmp {
                     gen_str_cmp($op),
                 )) as BoxedExpression
             }
+            (DataType::Bytea, DataType::Bytea) => Box::new(BinaryExpression::<
+                BytesArray,
+                BytesArray,
+                BoolArray,
+                _,
+            >::new(
+                $l, $r, $ret, gen_bytea_cmp($op)
+            )),
             (DataType::Struct { .. }, DataType::Struct { .. }) => {
                 Box::new(
                     BinaryExpression::<StructArray, StructArray, BoolArray, _>::new(
@@ -528,6 +539,14 @@ pub fn new_binary_expr(
         )),
         Type::TumbleStart => new_tumble_start(l, r, ret)?,
         Type::ConcatOp => new_concat_op(l, r, ret),
+        Type::JsonbContains => Box::new(
+            BinaryExpression::<JsonbArray, JsonbArray, BoolArray, _>::new(
+                l,
+                r,
+                ret,
+                jsonb_contains,
+            ),
+        ),
 
         tp => {
             return Err(ExprError::UnsupportedFunction(format!(
@@ -581,6 +600,51 @@ fn new_tumble_start(
     Ok(expr)
 }
 
+pub fn new_date_trunc_expr(
+    ret: DataType,
+    field: BoxedExpression,
+    source: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match source.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            _,
+        >::new(field, source, ret, date_trunc_timestamp)),
+        // The frontend always passes the session time zone for `timestamptz`, so this is only
+        // reached by plans that are built without a session.
+        DataType::Timestampz => {
+            Box::new(BinaryExpression::<Utf8Array, I64Array, I64Array, _>::new(
+                field,
+                source,
+                ret,
+                |field, usecs| date_trunc_timestampz_at_time_zone(field, usecs, "UTC"),
+            ))
+        }
+        _ => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "date_trunc is not supported for {:?}",
+                source.return_type()
+            )))
+        }
+    };
+    Ok(expr)
+}
+
+pub fn new_str_to_timestampz_at_time_zone(
+    expr_ia1: BoxedExpression,
+    time_zone: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryExpression::<Utf8Array, Utf8Array, I64Array, _>::new(
+        expr_ia1,
+        time_zone,
+        return_type,
+        str_to_timestampz_at_time_zone,
+    ))
+}
+
 pub fn new_like_default(
     expr_ia1: BoxedExpression,
     expr_ia2: BoxedExpression,
@@ -26,6 +26,9 @@ use crate::vector_op::cmp::{
     str_is_not_distinct_from,
 };
 use crate::vector_op::conjunction::{and, or};
+use crate::vector_op::jsonb_access::{
+    jsonb_array_element, jsonb_array_element_str, jsonb_object_field, jsonb_object_field_str,
+};
 use crate::{for_all_cmp_variants, ExprError, Result};
 
 macro_rules! gen_nullable_cmp_impl {
@@ -78,6 +81,36 @@ pub fn new_nullable_binary_expr(
         ),
         Type::IsDistinctFrom => new_distinct_from_expr(l, r, ret)?,
         Type::IsNotDistinctFrom => new_not_distinct_from_expr(l, r, ret)?,
+        Type::JsonbAccessInner => match r.return_type() {
+            DataType::Varchar => Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_object_field)),
+            DataType::Int32 => Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                I32Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_array_element)),
+            t => return Err(ExprError::UnsupportedFunction(format!("jsonb -> {:?}", t))),
+        },
+        Type::JsonbAccessStr => match r.return_type() {
+            DataType::Varchar => Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_object_field_str)),
+            DataType::Int32 => Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                I32Array,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_array_element_str)),
+            t => return Err(ExprError::UnsupportedFunction(format!("jsonb ->> {:?}", t))),
+        },
         tp => {
             return Err(ExprError::UnsupportedFunction(format!(
                 "{:?}({:?}, {:?})",
@@ -122,6 +155,8 @@ fn build_array_access_expr(
         DataType::Timestamp => array_access_expression!(NaiveDateTimeArray),
         DataType::Timestampz => array_access_expression!(PrimitiveArray::<i64>),
         DataType::Interval => array_access_expression!(IntervalArray),
+        DataType::Bytea => array_access_expression!(BytesArray),
+        DataType::Jsonb => array_access_expression!(JsonbArray),
         DataType::Struct { .. } => array_access_expression!(StructArray),
         DataType::List { .. } => array_access_expression!(ListArray),
     }
@@ -0,0 +1,78 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! For expression that accept 3 arguments as input.
+
+use risingwave_common::array::{DecimalArray, I64Array, Utf8Array};
+use risingwave_common::types::DataType;
+
+use crate::expr::template::TernaryExpression;
+use crate::expr::BoxedExpression;
+use crate::vector_op::date_trunc::date_trunc_timestampz_at_time_zone;
+use crate::vector_op::extract::extract_from_timestampz_at_time_zone;
+use crate::{ExprError, Result};
+
+pub fn new_extract_at_time_zone(
+    time_unit: BoxedExpression,
+    source: BoxedExpression,
+    time_zone: BoxedExpression,
+    return_type: DataType,
+) -> Result<BoxedExpression> {
+    if source.return_type() != DataType::Timestampz {
+        return Err(ExprError::UnsupportedFunction(format!(
+            "Extract ( {:?} ) with time zone is not supported",
+            source.return_type()
+        )));
+    }
+    Ok(Box::new(TernaryExpression::<
+        Utf8Array,
+        I64Array,
+        Utf8Array,
+        DecimalArray,
+        _,
+    >::new(
+        time_unit,
+        source,
+        time_zone,
+        return_type,
+        extract_from_timestampz_at_time_zone,
+    )))
+}
+
+pub fn new_date_trunc_at_time_zone(
+    field: BoxedExpression,
+    source: BoxedExpression,
+    time_zone: BoxedExpression,
+    return_type: DataType,
+) -> Result<BoxedExpression> {
+    if source.return_type() != DataType::Timestampz {
+        return Err(ExprError::UnsupportedFunction(format!(
+            "date_trunc ( {:?} ) with time zone is not supported",
+            source.return_type()
+        )));
+    }
+    Ok(Box::new(TernaryExpression::<
+        Utf8Array,
+        I64Array,
+        Utf8Array,
+        I64Array,
+        _,
+    >::new(
+        field,
+        source,
+        time_zone,
+        return_type,
+        date_trunc_timestampz_at_time_zone,
+    )))
+}
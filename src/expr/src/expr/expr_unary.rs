@@ -28,6 +28,7 @@ use crate::vector_op::bitwise_op::general_bitnot;
 use crate::vector_op::cast::*;
 use crate::vector_op::cmp::{is_false, is_not_false, is_not_true, is_true};
 use crate::vector_op::conjunction;
+use crate::vector_op::jsonb_access::{jsonb_array_length, jsonb_typeof};
 use crate::vector_op::length::{bit_length, length_default, octet_length};
 use crate::vector_op::lower::lower;
 use crate::vector_op::ltrim::ltrim;
@@ -272,6 +273,16 @@ pub fn new_unary_expr(
         (ProstType::Round, _, _) => {
             gen_round_expr! {"Ceil", child_expr, return_type, round_f64, round_decimal}
         }
+        (ProstType::JsonbTypeof, _, _) => Box::new(
+            UnaryExpression::<JsonbArray, Utf8Array, _>::new(child_expr, return_type, jsonb_typeof),
+        ),
+        (ProstType::JsonbArrayLength, _, _) => {
+            Box::new(UnaryExpression::<JsonbArray, I32Array, _>::new(
+                child_expr,
+                return_type,
+                jsonb_array_length,
+            ))
+        }
         (ProstType::ToTimestamp, DataType::Timestampz, DataType::Float64) => {
             Box::new(UnaryExpression::<F64Array, I64Array, _>::new(
                 child_expr,
@@ -30,6 +30,7 @@ mod expr_literal;
 mod expr_nested_construct;
 mod expr_quaternary_bytes;
 mod expr_regexp;
+mod expr_ternary;
 mod expr_ternary_bytes;
 mod expr_to_char_const_tmpl;
 pub mod expr_unary;
@@ -102,15 +103,17 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         // Fixed number of arguments and based on `Unary/Binary/Ternary/...Expression`
         Cast | Upper | Lower | Md5 | Not | IsTrue | IsNotTrue | IsFalse | IsNotFalse | IsNull
         | IsNotNull | Neg | Ascii | Abs | Ceil | Floor | Round | BitwiseNot | CharLength
-        | BoolOut | OctetLength | BitLength | ToTimestamp => build_unary_expr_prost(prost),
-        Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
-        | Subtract | Multiply | Divide | Modulus | Extract | RoundDigit | TumbleStart
-        | Position | BitwiseShiftLeft | BitwiseShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor
-        | ConcatOp => build_binary_expr_prost(prost),
-        And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess => {
-            build_nullable_binary_expr_prost(prost)
+        | BoolOut | OctetLength | BitLength | ToTimestamp | JsonbTypeof | JsonbArrayLength => {
+            build_unary_expr_prost(prost)
         }
+        Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
+        | Subtract | Multiply | Divide | Modulus | RoundDigit | TumbleStart | Position
+        | BitwiseShiftLeft | BitwiseShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor | ConcatOp
+        | JsonbContains => build_binary_expr_prost(prost),
+        And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess | JsonbAccessInner
+        | JsonbAccessStr => build_nullable_binary_expr_prost(prost),
         ToChar => build_to_char_expr(prost),
+        CastWithTimeZone => build_cast_with_time_zone_expr(prost),
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
         Like => build_like_expr(prost),
@@ -119,6 +122,8 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         Translate => build_translate_expr(prost),
 
         // Variable number of arguments and based on `Unary/Binary/Ternary/...Expression`
+        Extract => build_extract_expr(prost),
+        DateTrunc => build_date_trunc_expr(prost),
         Substr => build_substr_expr(prost),
         Overlay => build_overlay_expr(prost),
         Trim => build_trim_expr(prost),
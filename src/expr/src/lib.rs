@@ -31,6 +31,7 @@ pub mod error;
 pub mod expr;
 pub mod table_function;
 pub mod vector_op;
+pub mod window_function;
 
 pub use error::ExprError;
 pub use risingwave_common::{bail, ensure};
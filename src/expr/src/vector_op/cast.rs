@@ -16,16 +16,18 @@ use std::any::type_name;
 use std::str::FromStr;
 
 use bytes::{Bytes, BytesMut};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use num_traits::ToPrimitive;
 use postgres_types::ToSql;
 use risingwave_common::array::{Array, ListRef, ListValue};
 use risingwave_common::types::{
-    DataType, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper,
-    OrderedF32, OrderedF64, Scalar, ScalarImpl, ScalarRefImpl,
+    write_bytea, DataType, Decimal, IntervalUnit, JsonbRef, JsonbVal, NaiveDateTimeWrapper,
+    NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64, Scalar, ScalarImpl, ScalarRefImpl,
 };
 use speedate::{Date as SpeedDate, DateTime as SpeedDateTime, Time as SpeedTime};
 
+use crate::vector_op::timestampz::{parse_timestampz_in, timestampz_to_local_string};
 use crate::{ExprError, Result};
 
 /// String literals for bool type.
@@ -56,7 +58,7 @@ pub fn str_to_timestamp(elem: &str) -> Result<NaiveDateTimeWrapper> {
 }
 
 #[inline]
-fn parse_naive_datetime(s: &str) -> Result<NaiveDateTime> {
+pub(crate) fn parse_naive_datetime(s: &str) -> Result<NaiveDateTime> {
     if let Ok(res) = SpeedDateTime::parse_str(s) {
         let date = NaiveDate::from_ymd(
             res.date.year as i32,
@@ -102,24 +104,18 @@ fn parse_naive_time(s: &str) -> Result<NaiveTime> {
 
 #[inline(always)]
 pub fn str_to_timestampz(elem: &str) -> Result<i64> {
-    elem.parse::<DateTime<Utc>>()
-        .map(|ret| ret.timestamp_nanos() / 1000)
-        .map_err(|_| ExprError::Parse(PARSE_ERROR_STR_TO_TIMESTAMP))
+    // Strings without an explicit offset are interpreted in UTC. The frontend rewrites the cast
+    // into `CastWithTimeZone` when the session time zone is not UTC.
+    parse_timestampz_in(elem, &Utc)
 }
 
 #[inline(always)]
 pub fn timestampz_to_utc_string(elem: i64) -> String {
-    // Just a meaningful representation as placeholder. The real implementation depends on TimeZone
-    // from session. See #3552.
-    let instant = Utc.timestamp_nanos(elem * 1000);
-    // PostgreSQL uses a space rather than `T` to separate the date and time.
-    // https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-DATETIME-OUTPUT
-    instant.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string()
+    timestampz_to_local_string(elem, Tz::UTC)
 }
 
 pub fn timestampz_to_utc_binary(elem: i64) -> Bytes {
-    // Just a meaningful representation as placeholder. The real implementation depends on TimeZone
-    // from session. See #3552.
+    // The binary format of `timestamptz` is always in UTC, regardless of the session time zone.
     let instant = Utc.timestamp_nanos(elem * 1000);
     let mut out = BytesMut::new();
     // postgres_types::Type::ANY is only used as a placeholder.
@@ -261,6 +257,85 @@ pub fn bool_out(input: bool) -> Result<String> {
     Ok(if input { "t".into() } else { "f".into() })
 }
 
+/// Parses a `bytea` in either the hex format (`\x0aff`) or the escape format (`a\000b`) of
+/// `PostgreSQL`.
+pub fn str_to_bytea(elem: &str) -> Result<Box<[u8]>> {
+    if let Some(hex) = elem.strip_prefix("\\x") {
+        let digits: Vec<u8> = hex
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .map(|b| match b {
+                b'0'..=b'9' => Ok(b - b'0'),
+                b'a'..=b'f' => Ok(b - b'a' + 10),
+                b'A'..=b'F' => Ok(b - b'A' + 10),
+                _ => Err(ExprError::Parse("invalid hexadecimal digit in bytea")),
+            })
+            .try_collect()?;
+        if digits.len() % 2 != 0 {
+            return Err(ExprError::Parse(
+                "invalid hexadecimal data: odd number of digits",
+            ));
+        }
+        Ok(digits.chunks_exact(2).map(|d| d[0] << 4 | d[1]).collect())
+    } else {
+        let mut bytes = Vec::with_capacity(elem.len());
+        let mut rest = elem.as_bytes();
+        while let Some((&b, tail)) = rest.split_first() {
+            rest = tail;
+            if b != b'\\' {
+                bytes.push(b);
+                continue;
+            }
+            match rest {
+                [b'\\', tail @ ..] => {
+                    bytes.push(b'\\');
+                    rest = tail;
+                }
+                [d1 @ b'0'..=b'3', d2 @ b'0'..=b'7', d3 @ b'0'..=b'7', tail @ ..] => {
+                    bytes.push((d1 - b'0') << 6 | (d2 - b'0') << 3 | (d3 - b'0'));
+                    rest = tail;
+                }
+                _ => return Err(ExprError::Parse("invalid input syntax for type bytea")),
+            }
+        }
+        Ok(bytes.into())
+    }
+}
+
+/// Formats a `bytea` in the hex format, which is the default output format of `PostgreSQL`.
+pub fn bytea_to_str(elem: &[u8]) -> Result<String> {
+    let mut s = String::with_capacity(2 + elem.len() * 2);
+    write_bytea(elem, &mut s).unwrap();
+    Ok(s)
+}
+
+pub fn str_to_jsonb(elem: &str) -> Result<JsonbVal> {
+    elem.parse()
+        .map_err(|_| ExprError::Parse("invalid input syntax for type jsonb"))
+}
+
+pub fn jsonb_to_bool(elem: JsonbRef<'_>) -> Result<bool> {
+    elem.value()
+        .as_bool()
+        .ok_or(ExprError::Cast("jsonb", "boolean"))
+}
+
+pub fn jsonb_to_f64(elem: JsonbRef<'_>) -> Result<OrderedF64> {
+    elem.value()
+        .as_f64()
+        .map(Into::into)
+        .ok_or(ExprError::Cast("jsonb", "double precision"))
+}
+
+pub fn jsonb_to_dec(elem: JsonbRef<'_>) -> Result<Decimal> {
+    if !elem.value().is_number() {
+        return Err(ExprError::Cast("jsonb", "numeric"));
+    }
+    elem.to_string()
+        .parse()
+        .map_err(|_| ExprError::Cast("jsonb", "numeric"))
+}
+
 /// It accepts a macro whose input is `{ $input:ident, $cast:ident, $func:expr }` tuples
 ///
 /// * `$input`: input type
@@ -283,6 +358,8 @@ macro_rules! for_all_cast_variants {
             { varchar, float64, str_parse },
             { varchar, decimal, str_parse },
             { varchar, boolean, str_to_bool },
+            { varchar, bytea, str_to_bytea },
+            { varchar, jsonb, str_to_jsonb },
             // `str_to_list` requires `target_elem_type` and is handled elsewhere
 
             { boolean, varchar, general_to_string },
@@ -298,6 +375,12 @@ macro_rules! for_all_cast_variants {
             { timestamp, varchar, general_to_string },
             { timestampz, varchar, |x| Ok(timestampz_to_utc_string(x)) },
             { list, varchar, |x| general_to_string(x) },
+            { bytea, varchar, bytea_to_str },
+            { jsonb, varchar, |x| general_to_string(x) },
+
+            { jsonb, boolean, jsonb_to_bool },
+            { jsonb, float64, jsonb_to_f64 },
+            { jsonb, decimal, jsonb_to_dec },
 
             { boolean, int32, general_cast },
             { int32, boolean, int32_to_bool },
@@ -712,4 +795,28 @@ mod tests {
         assert!(str_to_list("{{1, 2, 3}, {4, 5, 6}", &DataType::Int32).is_err());
         assert!(str_to_list("{{1, 2, 3}, 4, 5, 6}}", &DataType::Int32).is_err());
     }
+
+    #[test]
+    fn test_bytea() {
+        assert_eq!(&*str_to_bytea("\\x0a FF").unwrap(), b"\x0a\xff");
+        assert_eq!(&*str_to_bytea("a\\000b\\\\").unwrap(), b"a\x00b\\");
+        assert_eq!(&*str_to_bytea("").unwrap(), b"");
+        assert!(str_to_bytea("\\x0").is_err());
+        assert!(str_to_bytea("\\xzz").is_err());
+        assert!(str_to_bytea("a\\9").is_err());
+        assert_eq!(bytea_to_str(b"\x00\x1f\xff").unwrap(), "\\x001fff");
+    }
+
+    #[test]
+    fn test_jsonb_cast() {
+        let v = str_to_jsonb(r#"{"a": 1.5, "b": true}"#).unwrap();
+        let v = v.as_scalar_ref();
+        assert_eq!(general_to_string(v).unwrap(), r#"{"a": 1.5, "b": true}"#);
+        let a = v.access_object_field("a").unwrap();
+        assert_eq!(jsonb_to_f64(a).unwrap(), 1.5.into());
+        assert_eq!(jsonb_to_dec(a).unwrap(), Decimal::from_str("1.5").unwrap());
+        assert!(jsonb_to_bool(a).is_err());
+        assert!(jsonb_to_bool(v.access_object_field("b").unwrap()).unwrap());
+        assert!(str_to_jsonb("{").is_err());
+    }
 }
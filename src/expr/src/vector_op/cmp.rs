@@ -156,6 +156,12 @@ pub fn gen_list_cmp(op: Comparison) -> fn(ListRef<'_>, ListRef<'_>) -> Result<bo
     gen_cmp!(op)
 }
 
+#[inline(always)]
+pub fn gen_bytea_cmp(op: Comparison) -> fn(&[u8], &[u8]) -> Result<bool> {
+    use crate::gen_cmp;
+    gen_cmp!(op)
+}
+
 #[inline(always)]
 pub fn gen_str_cmp(op: Comparison) -> fn(&str, &str) -> Result<bool> {
     use crate::gen_cmp;
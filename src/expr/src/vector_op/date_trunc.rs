@@ -0,0 +1,129 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+use crate::vector_op::timestampz::{local_to_timestampz, lookup_time_zone, timestampz_to_local};
+use crate::{ExprError, Result};
+
+fn truncate(field: &str, time: NaiveDateTime) -> Result<NaiveDateTime> {
+    let date = time.date();
+    let first_day_of_year = |year| NaiveDate::from_ymd(year, 1, 1).and_hms(0, 0, 0);
+    let res = match field.to_ascii_lowercase().as_str() {
+        "microseconds" => time,
+        "milliseconds" => time
+            .with_nanosecond(time.nanosecond() / 1_000_000 * 1_000_000)
+            .unwrap(),
+        "second" => time.with_nanosecond(0).unwrap(),
+        "minute" => date.and_hms(time.hour(), time.minute(), 0),
+        "hour" => date.and_hms(time.hour(), 0, 0),
+        "day" => date.and_hms(0, 0, 0),
+        // Weeks start on Monday.
+        "week" => {
+            (date - Duration::days(date.weekday().num_days_from_monday() as i64)).and_hms(0, 0, 0)
+        }
+        "month" => NaiveDate::from_ymd(date.year(), date.month(), 1).and_hms(0, 0, 0),
+        "quarter" => {
+            NaiveDate::from_ymd(date.year(), (date.month() - 1) / 3 * 3 + 1, 1).and_hms(0, 0, 0)
+        }
+        "year" => first_day_of_year(date.year()),
+        "decade" => first_day_of_year(date.year().div_euclid(10) * 10),
+        // Centuries and millenniums start with year 1, e.g. the 21st century starts at 2001.
+        "century" => first_day_of_year((date.year() - 1).div_euclid(100) * 100 + 1),
+        "millennium" => first_day_of_year((date.year() - 1).div_euclid(1000) * 1000 + 1),
+        _ => {
+            return Err(ExprError::InvalidParam {
+                name: "field",
+                reason: format!("timestamp units \"{}\" not supported", field),
+            })
+        }
+    };
+    Ok(res)
+}
+
+pub fn date_trunc_timestamp(
+    field: &str,
+    timestamp: NaiveDateTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    truncate(field, timestamp.0).map(NaiveDateTimeWrapper::new)
+}
+
+/// Truncates a `timestamptz` to the given precision as seen in the given time zone, e.g. `day`
+/// truncates to the local midnight rather than the midnight in UTC.
+pub fn date_trunc_timestampz_at_time_zone(field: &str, usecs: i64, time_zone: &str) -> Result<i64> {
+    let time_zone = lookup_time_zone(time_zone)?;
+    let local = truncate(field, timestampz_to_local(usecs, time_zone))?;
+    Ok(local_to_timestampz(&local, &time_zone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(s: &str) -> NaiveDateTimeWrapper {
+        NaiveDateTimeWrapper::new(NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").unwrap())
+    }
+
+    #[test]
+    fn test_date_trunc_timestamp() {
+        let time = timestamp("2022-11-16 12:34:56.789012");
+        let cases = [
+            ("microseconds", "2022-11-16 12:34:56.789012"),
+            ("milliseconds", "2022-11-16 12:34:56.789"),
+            ("second", "2022-11-16 12:34:56"),
+            ("MINUTE", "2022-11-16 12:34:00"),
+            ("hour", "2022-11-16 12:00:00"),
+            ("day", "2022-11-16 00:00:00"),
+            ("week", "2022-11-14 00:00:00"),
+            ("month", "2022-11-01 00:00:00"),
+            ("quarter", "2022-10-01 00:00:00"),
+            ("year", "2022-01-01 00:00:00"),
+            ("decade", "2020-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+            ("millennium", "2001-01-01 00:00:00"),
+        ];
+        for (field, expected) in cases {
+            assert_eq!(
+                date_trunc_timestamp(field, time).unwrap(),
+                timestamp(expected),
+                "{}",
+                field
+            );
+        }
+        assert!(date_trunc_timestamp("fortnight", time).is_err());
+    }
+
+    #[test]
+    fn test_date_trunc_timestampz_at_time_zone() {
+        // 2022-11-16 20:00:00 UTC, which is 2022-11-17 04:00:00 in Shanghai.
+        let usecs = 1_668_628_800_000_000;
+        assert_eq!(
+            date_trunc_timestampz_at_time_zone("day", usecs, "UTC").unwrap(),
+            1_668_556_800_000_000
+        );
+        // 2022-11-17 00:00:00+08:00
+        assert_eq!(
+            date_trunc_timestampz_at_time_zone("day", usecs, "Asia/Shanghai").unwrap(),
+            1_668_614_400_000_000
+        );
+        // 2018-11-04 12:00:00-02:00 in Sao Paulo, where the clocks skipped from 00:00 to 01:00 on
+        // that day. The local midnight is resolved forward to 2018-11-04 01:00:00-02:00.
+        assert_eq!(
+            date_trunc_timestampz_at_time_zone("day", 1_541_340_000_000_000, "America/Sao_Paulo")
+                .unwrap(),
+            1_541_300_400_000_000
+        );
+    }
+}
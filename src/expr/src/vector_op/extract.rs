@@ -15,6 +15,7 @@
 use chrono::{Datelike, Timelike};
 use risingwave_common::types::{Decimal, NaiveDateTimeWrapper, NaiveDateWrapper};
 
+use crate::vector_op::timestampz::{lookup_time_zone, timestampz_to_local};
 use crate::{bail, Result};
 
 fn extract_time<T>(time: T, time_unit: &str) -> Result<Decimal>
@@ -60,7 +61,8 @@ pub fn extract_from_timestamp(time_unit: &str, timestamp: NaiveDateTimeWrapper)
 pub fn extract_from_timestampz(time_unit: &str, usecs: i64) -> Result<Decimal> {
     match time_unit {
         "EPOCH" => Ok(Decimal::from(usecs) / 1_000_000.into()),
-        // TODO(#5826): all other units depend on implicit session TimeZone
+        // All other units depend on the session time zone, see
+        // `extract_from_timestampz_at_time_zone`.
         _ => bail!(
             "Unsupported timestamp with time zone unit {} in extract function",
             time_unit
@@ -68,6 +70,19 @@ pub fn extract_from_timestampz(time_unit: &str, usecs: i64) -> Result<Decimal> {
     }
 }
 
+/// Extracts a field from a `timestamptz` as seen in the given time zone.
+pub fn extract_from_timestampz_at_time_zone(
+    time_unit: &str,
+    usecs: i64,
+    time_zone: &str,
+) -> Result<Decimal> {
+    if time_unit == "EPOCH" {
+        return extract_from_timestampz(time_unit, usecs);
+    }
+    let local = timestampz_to_local(usecs, lookup_time_zone(time_zone)?);
+    extract_from_timestamp(time_unit, NaiveDateTimeWrapper::new(local))
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{NaiveDate, NaiveDateTime};
@@ -94,4 +109,16 @@ mod tests {
         assert_eq!(extract_from_timestamp("MINUTE", time).unwrap(), 4.into());
         assert_eq!(extract_from_timestamp("SECOND", time).unwrap(), 2.into());
     }
+
+    #[test]
+    fn test_timestampz_at_time_zone() {
+        // 2021-11-22 23:04:02 UTC
+        let usecs = 1_637_622_242_000_000;
+        let extract = |unit, zone| extract_from_timestampz_at_time_zone(unit, usecs, zone).unwrap();
+        assert_eq!(extract("DAY", "UTC"), 22.into());
+        assert_eq!(extract("HOUR", "UTC"), 23.into());
+        assert_eq!(extract("DAY", "Asia/Shanghai"), 23.into());
+        assert_eq!(extract("HOUR", "Asia/Shanghai"), 7.into());
+        assert_eq!(extract("EPOCH", "Asia/Shanghai"), 1_637_622_242.into());
+    }
 }
@@ -0,0 +1,115 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::{JsonbRef, JsonbVal, ScalarRef};
+
+use crate::{ExprError, Result};
+
+/// `jsonb -> text`, which returns `NULL` if the value is not an object or has no such field.
+#[inline(always)]
+pub fn jsonb_object_field(v: Option<JsonbRef<'_>>, key: Option<&str>) -> Result<Option<JsonbVal>> {
+    Ok(match (v, key) {
+        (Some(v), Some(key)) => v.access_object_field(key).map(|v| v.to_owned_scalar()),
+        _ => None,
+    })
+}
+
+/// `jsonb -> int`, which returns `NULL` if the value is not an array or the index is out of
+/// bounds.
+#[inline(always)]
+pub fn jsonb_array_element(
+    v: Option<JsonbRef<'_>>,
+    index: Option<i32>,
+) -> Result<Option<JsonbVal>> {
+    Ok(match (v, index) {
+        (Some(v), Some(index)) => v.access_array_element(index).map(|v| v.to_owned_scalar()),
+        _ => None,
+    })
+}
+
+/// `jsonb ->> text`
+#[inline(always)]
+pub fn jsonb_object_field_str(
+    v: Option<JsonbRef<'_>>,
+    key: Option<&str>,
+) -> Result<Option<String>> {
+    Ok(match (v, key) {
+        (Some(v), Some(key)) => v.access_object_field(key).and_then(|v| v.as_text()),
+        _ => None,
+    })
+}
+
+/// `jsonb ->> int`
+#[inline(always)]
+pub fn jsonb_array_element_str(
+    v: Option<JsonbRef<'_>>,
+    index: Option<i32>,
+) -> Result<Option<String>> {
+    Ok(match (v, index) {
+        (Some(v), Some(index)) => v.access_array_element(index).and_then(|v| v.as_text()),
+        _ => None,
+    })
+}
+
+/// `jsonb @> jsonb`
+#[inline(always)]
+pub fn jsonb_contains(left: JsonbRef<'_>, right: JsonbRef<'_>) -> Result<bool> {
+    Ok(left.contains(right))
+}
+
+#[inline(always)]
+pub fn jsonb_typeof(v: JsonbRef<'_>) -> Result<String> {
+    Ok(v.type_name().to_string())
+}
+
+#[inline(always)]
+pub fn jsonb_array_length(v: JsonbRef<'_>) -> Result<i32> {
+    match v.array_len() {
+        Some(len) => Ok(len as i32),
+        None => Err(ExprError::InvalidParam {
+            name: "jsonb_array_length",
+            reason: format!("cannot get array length of a {}", v.type_name()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::Scalar;
+
+    use super::*;
+
+    #[test]
+    fn test_jsonb_access() {
+        let v: JsonbVal = r#"{"a": [1, "x", null], "b": {"c": 2}}"#.parse().unwrap();
+        let v = Some(v.as_scalar_ref());
+
+        let a = jsonb_object_field(v, Some("a")).unwrap().unwrap();
+        assert_eq!(a.to_string(), r#"[1, "x", null]"#);
+        let a = Some(a.as_scalar_ref());
+        assert_eq!(jsonb_array_element_str(a, Some(1)).unwrap().unwrap(), "x");
+        assert_eq!(jsonb_array_element_str(a, Some(2)).unwrap(), None);
+        assert_eq!(jsonb_array_element(a, Some(3)).unwrap(), None);
+        assert_eq!(
+            jsonb_object_field_str(v, Some("b")).unwrap().unwrap(),
+            r#"{"c": 2}"#
+        );
+        assert_eq!(jsonb_object_field(v, None).unwrap(), None);
+        assert_eq!(jsonb_array_element(v, Some(0)).unwrap(), None);
+
+        assert_eq!(jsonb_typeof(a.unwrap()).unwrap(), "array");
+        assert_eq!(jsonb_array_length(a.unwrap()).unwrap(), 3);
+        assert!(jsonb_array_length(v.unwrap()).is_err());
+    }
+}
@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
 use num_traits::ToPrimitive;
+use risingwave_common::array::{BytesGuard, BytesWriter};
 use risingwave_common::types::OrderedF64;
 
+use crate::vector_op::cast::parse_naive_datetime;
 use crate::{ExprError, Result};
 
+const PARSE_ERROR_STR_TO_TIMESTAMPZ: &str = "Can't cast string to timestamp with time zone (expected format is YYYY-MM-DD HH:MM:SS[.D+{up to 6 digits}][+HH:MM] or ISO 8601 format)";
+
 #[inline(always)]
 pub fn f64_sec_to_timestampz(elem: OrderedF64) -> Result<i64> {
     // TODO(#4515): handle +/- infinity
@@ -25,3 +31,120 @@ pub fn f64_sec_to_timestampz(elem: OrderedF64) -> Result<i64> {
         .to_i64()
         .ok_or(ExprError::NumericOutOfRange)
 }
+
+/// Looks up a time zone by its IANA name, e.g. `Asia/Shanghai`.
+///
+/// The frontend always passes the canonical name of the session time zone, so no
+/// case-insensitive matching is done here.
+pub fn lookup_time_zone(time_zone: &str) -> Result<Tz> {
+    time_zone
+        .parse::<Tz>()
+        .map_err(|_| ExprError::InvalidParam {
+            name: "time_zone",
+            reason: format!("unknown time zone \"{}\"", time_zone),
+        })
+}
+
+/// Converts a UTC instant in microseconds to the wall-clock time in `time_zone`.
+pub fn timestampz_to_local(usecs: i64, time_zone: Tz) -> NaiveDateTime {
+    Utc.timestamp_nanos(usecs * 1000)
+        .with_timezone(&time_zone)
+        .naive_local()
+}
+
+/// Interprets a wall-clock time in `time_zone` and converts it to a UTC instant in microseconds.
+///
+/// For an ambiguous local time (e.g. at the end of daylight saving time), the earlier instant is
+/// chosen. A local time that is skipped over (e.g. at the start of daylight saving time) is
+/// interpreted with the UTC offset before the transition like PostgreSQL, so it's moved forward by
+/// the length of the gap, e.g. `02:30` becomes `03:30` when the clocks skip from `02:00` to
+/// `03:00`.
+pub fn local_to_timestampz<Z: TimeZone>(local: &NaiveDateTime, time_zone: &Z) -> i64 {
+    let instant = match time_zone.from_local_datetime(local).earliest() {
+        Some(instant) => instant.naive_utc(),
+        None => {
+            // Transitions are far more than a day apart, so the offset a day earlier is the one
+            // before the gap.
+            let before = time_zone
+                .offset_from_utc_datetime(&(*local - Duration::days(1)))
+                .fix();
+            *local - Duration::seconds(before.local_minus_utc() as i64)
+        }
+    };
+    instant.timestamp_nanos() / 1000
+}
+
+/// Parses a `timestamptz` string. An explicit UTC offset in the string takes precedence, otherwise
+/// the string is interpreted as a wall-clock time in `time_zone`.
+pub(crate) fn parse_timestampz_in<Z: TimeZone>(elem: &str, time_zone: &Z) -> Result<i64> {
+    if let Ok(instant) = elem.parse::<DateTime<FixedOffset>>() {
+        return Ok(instant.timestamp_nanos() / 1000);
+    }
+    let local =
+        parse_naive_datetime(elem).map_err(|_| ExprError::Parse(PARSE_ERROR_STR_TO_TIMESTAMPZ))?;
+    Ok(local_to_timestampz(&local, time_zone))
+}
+
+/// Casts a string to `timestamptz` in the given session time zone.
+#[inline(always)]
+pub fn str_to_timestampz_at_time_zone(elem: &str, time_zone: &str) -> Result<i64> {
+    parse_timestampz_in(elem, &lookup_time_zone(time_zone)?)
+}
+
+/// Formats a `timestamptz` as a string in the given time zone.
+pub fn timestampz_to_local_string(elem: i64, time_zone: Tz) -> String {
+    let instant = Utc.timestamp_nanos(elem * 1000).with_timezone(&time_zone);
+    // PostgreSQL uses a space rather than `T` to separate the date and time.
+    // https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-DATETIME-OUTPUT
+    instant.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string()
+}
+
+/// Casts a `timestamptz` to string in the given session time zone.
+pub fn timestampz_to_string_at_time_zone(
+    elem: i64,
+    time_zone: &str,
+    dst: BytesWriter,
+) -> Result<BytesGuard> {
+    let res = timestampz_to_local_string(elem, lookup_time_zone(time_zone)?);
+    dst.write_ref(&res).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_str_to_timestampz_at_time_zone() {
+        let utc = str_to_timestampz_at_time_zone("2022-10-01 00:00:00", "UTC").unwrap();
+        let shanghai =
+            str_to_timestampz_at_time_zone("2022-10-01 08:00:00", "Asia/Shanghai").unwrap();
+        assert_eq!(utc, shanghai);
+        // An explicit offset overrides the session time zone.
+        let with_offset =
+            str_to_timestampz_at_time_zone("2022-10-01 00:00:00+00:00", "Asia/Shanghai").unwrap();
+        assert_eq!(utc, with_offset);
+        // 02:30 is skipped when daylight saving time starts, and is moved forward to 03:30.
+        assert_eq!(
+            str_to_timestampz_at_time_zone("2022-03-13 02:30:00", "America/New_York").unwrap(),
+            str_to_timestampz_at_time_zone("2022-03-13 03:30:00-04:00", "UTC").unwrap()
+        );
+        assert!(
+            str_to_timestampz_at_time_zone("2022-10-01 00:00:00", "Mars/Olympus_Mons").is_err()
+        );
+    }
+
+    #[test]
+    fn test_timestampz_to_string_at_time_zone() {
+        let usecs = str_to_timestampz_at_time_zone("2022-10-01 00:00:00", "UTC").unwrap();
+        let writer = Utf8ArrayBuilder::new(1).writer();
+        let guard = timestampz_to_string_at_time_zone(usecs, "Asia/Shanghai", writer).unwrap();
+        let array = guard.into_inner().finish();
+        assert_eq!(array.value_at(0), Some("2022-10-01 08:00:00+08:00"));
+        assert_eq!(
+            timestampz_to_local_string(usecs, Tz::America__New_York),
+            "2022-09-30 20:00:00-04:00"
+        );
+    }
+}
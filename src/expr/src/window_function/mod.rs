@@ -0,0 +1,520 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Evaluation of window functions (`.. OVER (PARTITION BY .. ORDER BY .. <frame>)`), shared by
+//! the batch and the streaming over window executors.
+
+mod sliding_agg;
+mod test_utils;
+
+use std::cmp::Ordering;
+
+use risingwave_common::array::Row;
+use risingwave_common::bail;
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::sort_util::{compare_rows, OrderPair};
+use risingwave_pb::expr::agg_call::Type as AggTypeProst;
+use risingwave_pb::expr::window_frame::bound::Type as FrameBoundTypeProst;
+use risingwave_pb::expr::window_frame::{Bound as FrameBoundProst, Type as FrameTypeProst};
+use risingwave_pb::expr::window_function::Type as WindowFunctionTypeProst;
+use risingwave_pb::expr::{WindowFrame as WindowFrameProst, WindowFunction};
+pub use test_utils::*;
+
+use self::sliding_agg::SlidingAggState;
+use crate::expr::AggKind;
+use crate::Result;
+
+/// Kind of window function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowFuncKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    Aggregate(AggKind),
+}
+
+/// Unit of the window frame bounds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FrameUnits {
+    /// Offsets are counted in physical rows.
+    Rows,
+    /// Offsets are counted in peer groups, i.e. rows with equal `ORDER BY` keys. Only
+    /// `UNBOUNDED` and `CURRENT ROW` bounds are supported.
+    Range,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(usize),
+    CurrentRow,
+    Following(usize),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    fn from_protobuf(prost: &FrameBoundProst) -> Result<Self> {
+        let offset = prost.get_offset() as usize;
+        let bound = match prost.get_type()? {
+            FrameBoundTypeProst::UnboundedPreceding => Self::UnboundedPreceding,
+            FrameBoundTypeProst::Preceding => Self::Preceding(offset),
+            FrameBoundTypeProst::CurrentRow => Self::CurrentRow,
+            FrameBoundTypeProst::Following => Self::Following(offset),
+            FrameBoundTypeProst::UnboundedFollowing => Self::UnboundedFollowing,
+            FrameBoundTypeProst::TypeUnspecified => bail!("Unrecognized window frame bound."),
+        };
+        Ok(bound)
+    }
+}
+
+/// The window frame of a window function call.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl Default for Frame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, which covers the whole partition when
+    /// there's no `ORDER BY`.
+    fn default() -> Self {
+        Self {
+            units: FrameUnits::Range,
+            start: FrameBound::UnboundedPreceding,
+            end: FrameBound::CurrentRow,
+        }
+    }
+}
+
+impl Frame {
+    fn from_protobuf(prost: &WindowFrameProst) -> Result<Self> {
+        let units = match prost.get_type()? {
+            FrameTypeProst::Rows => FrameUnits::Rows,
+            FrameTypeProst::Range => FrameUnits::Range,
+            FrameTypeProst::TypeUnspecified => bail!("Unrecognized window frame type."),
+        };
+        let start = FrameBound::from_protobuf(prost.get_start()?)?;
+        let end = FrameBound::from_protobuf(prost.get_end()?)?;
+        let has_offset =
+            |bound| matches!(bound, FrameBound::Preceding(_) | FrameBound::Following(_));
+        if units == FrameUnits::Range && (has_offset(start) || has_offset(end)) {
+            bail!("RANGE frame with offset is not supported");
+        }
+        Ok(Self { units, start, end })
+    }
+}
+
+/// A partition of input rows, sorted by the `ORDER BY` of the window.
+pub struct Partition<'a> {
+    rows: &'a [Row],
+    /// The index of the first row of the peer group each row belongs to.
+    peer_start: Vec<usize>,
+    /// The index of the last row of the peer group each row belongs to.
+    peer_end: Vec<usize>,
+    /// The ordinal of the peer group each row belongs to, starting from 0.
+    peer_group: Vec<usize>,
+}
+
+impl<'a> Partition<'a> {
+    /// `rows` must be already sorted by `order_by`. Rows with equal `order_by` keys are peers.
+    pub fn new(rows: &'a [Row], order_by: &[OrderPair]) -> Result<Self> {
+        let len = rows.len();
+        let mut peer_start = Vec::with_capacity(len);
+        let mut peer_group = Vec::with_capacity(len);
+        for (i, row) in rows.iter().enumerate() {
+            let is_peer = i > 0
+                && compare_rows(&rows[i - 1], row, order_by)
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))?
+                    == Ordering::Equal;
+            if is_peer {
+                peer_start.push(peer_start[i - 1]);
+                peer_group.push(peer_group[i - 1]);
+            } else {
+                peer_start.push(i);
+                peer_group.push(peer_group.last().map_or(0, |g| g + 1));
+            }
+        }
+        let mut peer_end = vec![0; len];
+        for i in (0..len).rev() {
+            peer_end[i] = if i + 1 < len && peer_start[i + 1] == peer_start[i] {
+                peer_end[i + 1]
+            } else {
+                i
+            };
+        }
+        Ok(Self {
+            rows,
+            peer_start,
+            peer_end,
+            peer_group,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the inclusive range of rows in the frame of the `idx`-th row, or `None` if the
+    /// frame is empty.
+    fn frame_range(&self, frame: &Frame, idx: usize) -> Option<(usize, usize)> {
+        let len = self.len() as isize;
+        let idx_i = idx as isize;
+        let start = match frame.start {
+            FrameBound::UnboundedPreceding => 0,
+            FrameBound::Preceding(n) => idx_i - n as isize,
+            FrameBound::CurrentRow => match frame.units {
+                FrameUnits::Rows => idx_i,
+                FrameUnits::Range => self.peer_start[idx] as isize,
+            },
+            FrameBound::Following(n) => idx_i + n as isize,
+            FrameBound::UnboundedFollowing => len,
+        };
+        let end = match frame.end {
+            FrameBound::UnboundedPreceding => -1,
+            FrameBound::Preceding(n) => idx_i - n as isize,
+            FrameBound::CurrentRow => match frame.units {
+                FrameUnits::Rows => idx_i,
+                FrameUnits::Range => self.peer_end[idx] as isize,
+            },
+            FrameBound::Following(n) => idx_i + n as isize,
+            FrameBound::UnboundedFollowing => len - 1,
+        };
+        let start = start.max(0);
+        let end = end.min(len - 1);
+        if start > end {
+            None
+        } else {
+            Some((start as usize, end as usize))
+        }
+    }
+
+    /// Returns the half-open range of rows in the frame of the `idx`-th row. Unlike
+    /// [`Self::frame_range`], an empty frame is `(start, start)`, so that both bounds never move
+    /// backwards as `idx` grows.
+    fn frame_bounds(&self, frame: &Frame, idx: usize) -> (usize, usize) {
+        match self.frame_range(frame, idx) {
+            Some((start, end)) => (start, end + 1),
+            None => {
+                // Recompute the start bound alone, clamped into the partition.
+                let start = match frame.start {
+                    FrameBound::UnboundedPreceding => 0,
+                    FrameBound::Preceding(n) => idx.saturating_sub(n),
+                    FrameBound::CurrentRow => match frame.units {
+                        FrameUnits::Rows => idx,
+                        FrameUnits::Range => self.peer_start[idx],
+                    },
+                    FrameBound::Following(n) => idx + n,
+                    FrameBound::UnboundedFollowing => self.len(),
+                };
+                let start = start.min(self.len());
+                (start, start)
+            }
+        }
+    }
+}
+
+/// Instance of a window function call.
+pub struct WindowFuncCall {
+    pub kind: WindowFuncKind,
+    /// Column indices and types of the arguments.
+    pub args: Vec<(usize, DataType)>,
+    pub return_type: DataType,
+    pub frame: Frame,
+}
+
+impl WindowFuncCall {
+    pub fn from_protobuf(prost: &WindowFunction) -> Result<Self> {
+        let return_type = DataType::from(prost.get_return_type()?);
+        let args = prost
+            .get_args()
+            .iter()
+            .map(|arg| {
+                Ok((
+                    arg.get_input()?.get_column_idx() as usize,
+                    DataType::from(arg.get_type()?),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let frame = match &prost.frame {
+            Some(frame) => Frame::from_protobuf(frame)?,
+            None => Frame::default(),
+        };
+
+        let kind = match prost.get_type()? {
+            WindowFunctionTypeProst::RowNumber => WindowFuncKind::RowNumber,
+            WindowFunctionTypeProst::Rank => WindowFuncKind::Rank,
+            WindowFunctionTypeProst::DenseRank => WindowFuncKind::DenseRank,
+            WindowFunctionTypeProst::Lag => WindowFuncKind::Lag,
+            WindowFunctionTypeProst::Lead => WindowFuncKind::Lead,
+            WindowFunctionTypeProst::FirstValue => WindowFuncKind::FirstValue,
+            WindowFunctionTypeProst::LastValue => WindowFuncKind::LastValue,
+            WindowFunctionTypeProst::Aggregate => {
+                let agg_type = AggTypeProst::from_i32(prost.aggregate_type)
+                    .ok_or_else(|| anyhow::anyhow!("Unrecognized agg."))?;
+                let agg_kind = AggKind::try_from(agg_type)?;
+                // Validates the aggregate kind and arguments.
+                SlidingAggState::new(agg_kind, &args, &return_type)?;
+                WindowFuncKind::Aggregate(agg_kind)
+            }
+            WindowFunctionTypeProst::Unspecified => bail!("Unrecognized window function."),
+        };
+
+        match kind {
+            WindowFuncKind::Lag
+            | WindowFuncKind::Lead
+            | WindowFuncKind::FirstValue
+            | WindowFuncKind::LastValue
+                if args.len() != 1 =>
+            {
+                bail!("{:?} expects exactly one argument", kind)
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            kind,
+            args,
+            return_type,
+            frame,
+        })
+    }
+
+    /// Evaluates the window function for every row of the `partition`.
+    ///
+    /// Note that `LAG` and `LEAD` are planned with a single-row `ROWS` frame at the given offset,
+    /// so they are evaluated exactly like `FIRST_VALUE`.
+    pub fn evaluate_partition(&self, partition: &Partition<'_>) -> Result<Vec<Datum>> {
+        let len = partition.len();
+        let output = match self.kind {
+            WindowFuncKind::RowNumber => (0..len)
+                .map(|i| Some(ScalarImpl::Int64(i as i64 + 1)))
+                .collect(),
+            WindowFuncKind::Rank => (0..len)
+                .map(|i| Some(ScalarImpl::Int64(partition.peer_start[i] as i64 + 1)))
+                .collect(),
+            WindowFuncKind::DenseRank => (0..len)
+                .map(|i| Some(ScalarImpl::Int64(partition.peer_group[i] as i64 + 1)))
+                .collect(),
+            WindowFuncKind::Lag | WindowFuncKind::Lead | WindowFuncKind::FirstValue => {
+                let col_idx = self.args[0].0;
+                (0..len)
+                    .map(|i| {
+                        partition
+                            .frame_range(&self.frame, i)
+                            .and_then(|(start, _)| partition.rows[start][col_idx].clone())
+                    })
+                    .collect()
+            }
+            WindowFuncKind::LastValue => {
+                let col_idx = self.args[0].0;
+                (0..len)
+                    .map(|i| {
+                        partition
+                            .frame_range(&self.frame, i)
+                            .and_then(|(_, end)| partition.rows[end][col_idx].clone())
+                    })
+                    .collect()
+            }
+            WindowFuncKind::Aggregate(agg_kind) => {
+                // Frames only slide forward, so the state accumulates rows entering the frame
+                // and retracts rows leaving it, instead of aggregating every frame from scratch.
+                let mut state = SlidingAggState::new(agg_kind, &self.args, &self.return_type)?;
+                let (mut cur_start, mut cur_end) = (0, 0);
+                let mut output = Vec::with_capacity(len);
+                for i in 0..len {
+                    let (start, end) = partition.frame_bounds(&self.frame, i);
+                    if start >= cur_end {
+                        state.reset();
+                        (cur_start, cur_end) = (start, start);
+                    }
+                    for j in cur_start..start {
+                        state.retract(j, &partition.rows[j])?;
+                    }
+                    for j in cur_end..end {
+                        state.accumulate(j, &partition.rows[j])?;
+                    }
+                    (cur_start, cur_end) = (start, end);
+                    output.push(state.output());
+                }
+                output
+            }
+        };
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::util::sort_util::OrderType;
+
+    use super::*;
+
+    fn rows(values: &[(i32, i32)]) -> Vec<Row> {
+        values
+            .iter()
+            .map(|(k, v)| {
+                Row::new(vec![
+                    Some(ScalarImpl::Int32(*k)),
+                    Some(ScalarImpl::Int32(*v)),
+                ])
+            })
+            .collect()
+    }
+
+    fn bound(r#type: FrameBoundTypeProst, offset: u64) -> Option<FrameBoundProst> {
+        Some(FrameBoundProst {
+            r#type: r#type as i32,
+            offset,
+        })
+    }
+
+    fn eval(prost: WindowFunction, rows: &[Row]) -> Vec<Datum> {
+        let order_by = [OrderPair::new(0, OrderType::Ascending)];
+        let partition = Partition::new(rows, &order_by).unwrap();
+        WindowFuncCall::from_protobuf(&prost)
+            .unwrap()
+            .evaluate_partition(&partition)
+            .unwrap()
+    }
+
+    fn int64s(values: &[Option<i64>]) -> Vec<Datum> {
+        values.iter().map(|v| v.map(ScalarImpl::Int64)).collect()
+    }
+
+    #[test]
+    fn test_rank_functions() {
+        let rows = rows(&[(1, 10), (1, 20), (2, 30), (3, 40), (3, 50)]);
+        let call = |kind| {
+            window_function_prost(kind, AggTypeProst::Unspecified, None, DataType::Int64, None)
+        };
+        assert_eq!(
+            eval(call(WindowFunctionTypeProst::RowNumber), &rows),
+            int64s(&[Some(1), Some(2), Some(3), Some(4), Some(5)])
+        );
+        assert_eq!(
+            eval(call(WindowFunctionTypeProst::Rank), &rows),
+            int64s(&[Some(1), Some(1), Some(3), Some(4), Some(4)])
+        );
+        assert_eq!(
+            eval(call(WindowFunctionTypeProst::DenseRank), &rows),
+            int64s(&[Some(1), Some(1), Some(2), Some(3), Some(3)])
+        );
+    }
+
+    #[test]
+    fn test_lag() {
+        let rows = rows(&[(1, 10), (2, 20), (3, 30)]);
+        let frame = WindowFrameProst {
+            r#type: FrameTypeProst::Rows as i32,
+            start: bound(FrameBoundTypeProst::Preceding, 1),
+            end: bound(FrameBoundTypeProst::Preceding, 1),
+        };
+        let prost = window_function_prost(
+            WindowFunctionTypeProst::Lag,
+            AggTypeProst::Unspecified,
+            Some((1, DataType::Int32)),
+            DataType::Int32,
+            Some(frame),
+        );
+        assert_eq!(
+            eval(prost, &rows),
+            vec![
+                None,
+                Some(ScalarImpl::Int32(10)),
+                Some(ScalarImpl::Int32(20))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_framed_sum() {
+        let rows = rows(&[(1, 10), (2, 20), (2, 30), (3, 40)]);
+        let sum = |frame| {
+            window_function_prost(
+                WindowFunctionTypeProst::Aggregate,
+                AggTypeProst::Sum,
+                Some((1, DataType::Int32)),
+                DataType::Int64,
+                frame,
+            )
+        };
+
+        // Default frame: running sum including peers.
+        assert_eq!(
+            eval(sum(None), &rows),
+            int64s(&[Some(10), Some(60), Some(60), Some(100)])
+        );
+
+        // Sliding frame over physical rows.
+        let frame = WindowFrameProst {
+            r#type: FrameTypeProst::Rows as i32,
+            start: bound(FrameBoundTypeProst::Preceding, 1),
+            end: bound(FrameBoundTypeProst::Following, 1),
+        };
+        assert_eq!(
+            eval(sum(Some(frame)), &rows),
+            int64s(&[Some(30), Some(60), Some(90), Some(70)])
+        );
+
+        // Empty frame.
+        let frame = WindowFrameProst {
+            r#type: FrameTypeProst::Rows as i32,
+            start: bound(FrameBoundTypeProst::Following, 5),
+            end: bound(FrameBoundTypeProst::UnboundedFollowing, 0),
+        };
+        assert_eq!(
+            eval(sum(Some(frame)), &rows),
+            int64s(&[None, None, None, None])
+        );
+    }
+
+    #[test]
+    fn test_sliding_min_max() {
+        let rows = rows(&[(1, 30), (2, 10), (3, 20), (4, 50), (5, 40)]);
+        let frame = WindowFrameProst {
+            r#type: FrameTypeProst::Rows as i32,
+            start: bound(FrameBoundTypeProst::Preceding, 1),
+            end: bound(FrameBoundTypeProst::Following, 1),
+        };
+        let call = |agg_type| {
+            window_function_prost(
+                WindowFunctionTypeProst::Aggregate,
+                agg_type,
+                Some((1, DataType::Int32)),
+                DataType::Int32,
+                Some(frame.clone()),
+            )
+        };
+        let int32s = |values: &[i32]| -> Vec<Datum> {
+            values.iter().map(|v| Some(ScalarImpl::Int32(*v))).collect()
+        };
+        assert_eq!(
+            eval(call(AggTypeProst::Min), &rows),
+            int32s(&[10, 10, 10, 20, 40])
+        );
+        assert_eq!(
+            eval(call(AggTypeProst::Max), &rows),
+            int32s(&[30, 30, 50, 50, 50])
+        );
+    }
+}
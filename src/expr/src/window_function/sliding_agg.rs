@@ -0,0 +1,225 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use num_traits::{CheckedAdd, CheckedSub};
+use risingwave_common::array::Row;
+use risingwave_common::bail;
+use risingwave_common::types::{
+    DataType, Datum, Decimal, IntervalUnit, OrderedF32, OrderedF64, ScalarImpl,
+};
+
+use crate::expr::AggKind;
+use crate::{ExprError, Result};
+
+/// The state of an aggregate over a window frame that slides forward, i.e. rows only enter the
+/// frame at its end and leave at its start, both in the order of the partition. Each row is
+/// accumulated and retracted at most once, so evaluating all frames of a partition takes linear
+/// time.
+pub(super) enum SlidingAggState {
+    /// `COUNT(x)`, or `COUNT(*)` if there's no argument.
+    Count { arg: Option<usize>, count: i64 },
+    Sum {
+        arg: usize,
+        return_type: DataType,
+        sum: Option<ScalarImpl>,
+        /// The number of non-null values in the frame. The sum is null if it's 0.
+        non_null: usize,
+    },
+    /// `MIN(x)` or `MAX(x)`.
+    Extreme {
+        arg: usize,
+        is_min: bool,
+        /// The candidates of the result in the frame, as `(row index, value)` ordered by the
+        /// row index. Each value is strictly better than those after it, so the first one is the
+        /// result.
+        candidates: VecDeque<(usize, ScalarImpl)>,
+    },
+}
+
+impl SlidingAggState {
+    pub fn new(kind: AggKind, args: &[(usize, DataType)], return_type: &DataType) -> Result<Self> {
+        let arg = args.first().map(|(idx, _)| *idx);
+        let state = match (kind, arg) {
+            (AggKind::Count, _) => Self::Count { arg, count: 0 },
+            (AggKind::Sum, Some(arg)) => Self::Sum {
+                arg,
+                return_type: return_type.clone(),
+                sum: None,
+                non_null: 0,
+            },
+            (AggKind::Min | AggKind::Max, Some(arg)) => Self::Extreme {
+                arg,
+                is_min: kind == AggKind::Min,
+                candidates: VecDeque::new(),
+            },
+            _ => bail!("{:?} is not supported as window function", kind),
+        };
+        Ok(state)
+    }
+
+    /// Accumulates the `idx`-th row, which enters the frame.
+    pub fn accumulate(&mut self, idx: usize, row: &Row) -> Result<()> {
+        match self {
+            Self::Count { arg, count } => {
+                if arg.map_or(true, |arg| row[arg].is_some()) {
+                    *count += 1;
+                }
+            }
+            Self::Sum {
+                arg,
+                return_type,
+                sum,
+                non_null,
+            } => {
+                if let Some(value) = &row[*arg] {
+                    let base = match sum.take() {
+                        Some(sum) => sum,
+                        None => zero_of(return_type)?,
+                    };
+                    *sum = Some(sum_op(&base, value, false)?);
+                    *non_null += 1;
+                }
+            }
+            Self::Extreme {
+                arg,
+                is_min,
+                candidates,
+            } => {
+                if let Some(value) = &row[*arg] {
+                    while let Some((_, last)) = candidates.back() {
+                        let is_better = if *is_min {
+                            value <= last
+                        } else {
+                            value >= last
+                        };
+                        if !is_better {
+                            break;
+                        }
+                        candidates.pop_back();
+                    }
+                    candidates.push_back((idx, value.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Retracts the `idx`-th row, which leaves the frame. It must be the earliest row in the
+    /// frame.
+    pub fn retract(&mut self, idx: usize, row: &Row) -> Result<()> {
+        match self {
+            Self::Count { arg, count } => {
+                if arg.map_or(true, |arg| row[arg].is_some()) {
+                    *count -= 1;
+                }
+            }
+            Self::Sum {
+                arg, sum, non_null, ..
+            } => {
+                if let Some(value) = &row[*arg] {
+                    *non_null -= 1;
+                    *sum = match *non_null {
+                        0 => None,
+                        _ => Some(sum_op(sum.as_ref().unwrap(), value, true)?),
+                    };
+                }
+            }
+            Self::Extreme { candidates, .. } => {
+                if candidates.front().map_or(false, |(i, _)| *i == idx) {
+                    candidates.pop_front();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn output(&self) -> Datum {
+        match self {
+            Self::Count { count, .. } => Some(ScalarImpl::Int64(*count)),
+            Self::Sum { sum, .. } => sum.clone(),
+            Self::Extreme { candidates, .. } => candidates.front().map(|(_, v)| v.clone()),
+        }
+    }
+
+    /// Clears the state, as if all rows in the frame were retracted.
+    pub fn reset(&mut self) {
+        match self {
+            Self::Count { count, .. } => *count = 0,
+            Self::Sum { sum, non_null, .. } => {
+                *sum = None;
+                *non_null = 0;
+            }
+            Self::Extreme { candidates, .. } => candidates.clear(),
+        }
+    }
+}
+
+fn zero_of(return_type: &DataType) -> Result<ScalarImpl> {
+    let zero = match return_type {
+        DataType::Int16 => ScalarImpl::Int16(0),
+        DataType::Int32 => ScalarImpl::Int32(0),
+        DataType::Int64 => ScalarImpl::Int64(0),
+        DataType::Float32 => ScalarImpl::Float32(OrderedF32::from(0.0)),
+        DataType::Float64 => ScalarImpl::Float64(OrderedF64::from(0.0)),
+        DataType::Decimal => ScalarImpl::Decimal(Decimal::from(0)),
+        DataType::Interval => ScalarImpl::Interval(IntervalUnit::default()),
+        _ => bail!("unsupported return type of sum: {}", return_type),
+    };
+    Ok(zero)
+}
+
+/// Adds `value` to `sum`, or subtracts it if `retract`. The sum has the return type of `SUM`,
+/// which may be wider than that of the value.
+fn sum_op(sum: &ScalarImpl, value: &ScalarImpl, retract: bool) -> Result<ScalarImpl> {
+    fn op<T: CheckedAdd + CheckedSub>(sum: &T, value: &T, retract: bool) -> Result<T> {
+        let result = if retract {
+            sum.checked_sub(value)
+        } else {
+            sum.checked_add(value)
+        };
+        result.ok_or(ExprError::NumericOutOfRange)
+    }
+
+    let result = match (sum, value) {
+        (ScalarImpl::Int16(s), ScalarImpl::Int16(v)) => ScalarImpl::Int16(op(s, v, retract)?),
+        (ScalarImpl::Int32(s), ScalarImpl::Int32(v)) => ScalarImpl::Int32(op(s, v, retract)?),
+        (ScalarImpl::Int64(s), ScalarImpl::Int16(v)) => {
+            ScalarImpl::Int64(op(s, &(*v as i64), retract)?)
+        }
+        (ScalarImpl::Int64(s), ScalarImpl::Int32(v)) => {
+            ScalarImpl::Int64(op(s, &(*v as i64), retract)?)
+        }
+        (ScalarImpl::Int64(s), ScalarImpl::Int64(v)) => ScalarImpl::Int64(op(s, v, retract)?),
+        (ScalarImpl::Decimal(s), ScalarImpl::Int64(v)) => {
+            ScalarImpl::Decimal(op(s, &Decimal::from(*v), retract)?)
+        }
+        (ScalarImpl::Decimal(s), ScalarImpl::Decimal(v)) => ScalarImpl::Decimal(op(s, v, retract)?),
+        (ScalarImpl::Float32(s), ScalarImpl::Float32(v)) => ScalarImpl::Float32(op(s, v, retract)?),
+        (ScalarImpl::Float64(s), ScalarImpl::Float32(v)) => {
+            ScalarImpl::Float64(op(s, &OrderedF64::from(v.0 as f64), retract)?)
+        }
+        (ScalarImpl::Float64(s), ScalarImpl::Float64(v)) => ScalarImpl::Float64(op(s, v, retract)?),
+        (ScalarImpl::Interval(s), ScalarImpl::Interval(v)) => {
+            ScalarImpl::Interval(op(s, v, retract)?)
+        }
+        _ => bail!(
+            "unsupported sum of {} into {}",
+            value.get_ident(),
+            sum.get_ident()
+        ),
+    };
+    Ok(result)
+}
@@ -0,0 +1,43 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::agg_call::{Arg as AggArgProst, Type as AggTypeProst};
+use risingwave_pb::expr::window_function::Type as WindowFunctionTypeProst;
+use risingwave_pb::expr::{InputRefExpr, WindowFrame as WindowFrameProst, WindowFunction};
+
+/// Builds the protobuf of a window function call over one argument column.
+pub fn window_function_prost(
+    kind: WindowFunctionTypeProst,
+    aggregate_type: AggTypeProst,
+    arg: Option<(usize, DataType)>,
+    return_type: DataType,
+    frame: Option<WindowFrameProst>,
+) -> WindowFunction {
+    WindowFunction {
+        r#type: kind as i32,
+        aggregate_type: aggregate_type as i32,
+        args: arg
+            .into_iter()
+            .map(|(idx, data_type)| AggArgProst {
+                input: Some(InputRefExpr {
+                    column_idx: idx as i32,
+                }),
+                r#type: Some(data_type.to_protobuf()),
+            })
+            .collect(),
+        return_type: Some(return_type.to_protobuf()),
+        frame,
+    }
+}
@@ -351,9 +351,10 @@ impl TestCase {
                     or_replace: false,
                     name,
                     query,
+                    emit_mode,
                     ..
                 } => {
-                    create_mv::handle_create_mv(context, name, *query).await?;
+                    create_mv::handle_create_mv(context, name, *query, emit_mode).await?;
                 }
                 Statement::Drop(drop_statement) => {
                     drop_table::handle_drop_table(
@@ -499,6 +500,7 @@ impl TestCase {
                     context,
                     q,
                     ObjectName(vec!["test".into()]),
+                    None,
                 ) {
                     Ok((stream_plan, _)) => stream_plan,
                     Err(err) => {
@@ -22,13 +22,16 @@ use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::session_config::USER_NAME_WILD_CARD;
 use risingwave_common::types::{DataType, Scalar};
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, WindowSpec};
+use risingwave_sqlparser::ast::{
+    self, Function, FunctionArg, FunctionArgExpr, WindowFrameBound, WindowFrameUnits, WindowSpec,
+};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::{Binder, BoundQuery, BoundSetExpr};
 use crate::expr::{
-    AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, OrderBy, Subquery, SubqueryKind,
-    TableFunction, TableFunctionType, WindowFunction, WindowFunctionType,
+    AggCall, Expr, ExprImpl, ExprType, FrameBound, FrameUnits, FunctionCall, Literal, OrderBy,
+    Subquery, SubqueryKind, TableFunction, TableFunctionType, WindowFrame, WindowFunction,
+    WindowFunctionType,
 };
 use crate::utils::Condition;
 
@@ -57,15 +60,13 @@ impl Binder {
             }
         };
 
+        // window function
+        if f.over.is_some() {
+            return self.bind_window_function(f, function_name);
+        }
+
         // agg calls
         if let Ok(kind) = function_name.parse() {
-            if f.over.is_some() {
-                return Err(ErrorCode::NotImplemented(
-                    format!("aggregate function as over window function: {}", kind),
-                    4978.into(),
-                )
-                .into());
-            }
             return self.bind_agg(f, kind);
         }
 
@@ -84,11 +85,6 @@ impl Binder {
             .flatten_ok()
             .try_collect()?;
 
-        // window function
-        if let Some(window_spec) = f.over {
-            return self.bind_window_function(window_spec, function_name, inputs);
-        }
-
         // table function
         let table_function_type = TableFunctionType::from_str(function_name.as_str());
         if let Ok(function_type) = table_function_type {
@@ -127,6 +123,7 @@ impl Binder {
             "abs" => ExprType::Abs,
             // temporal/chrono
             "to_timestamp" => ExprType::ToTimestamp,
+            "date_trunc" => ExprType::DateTrunc,
             // string
             "substr" => ExprType::Substr,
             "length" => ExprType::Length,
@@ -157,6 +154,9 @@ impl Binder {
             "array_cat" => ExprType::ArrayCat,
             "array_append" => ExprType::ArrayAppend,
             "array_prepend" => ExprType::ArrayPrepend,
+            // jsonb
+            "jsonb_typeof" => ExprType::JsonbTypeof,
+            "jsonb_array_length" => ExprType::JsonbArrayLength,
             // System information operations.
             "pg_typeof" if inputs.len() == 1 => {
                 let input = &inputs[0];
@@ -368,23 +368,107 @@ impl Binder {
 
     pub(super) fn bind_window_function(
         &mut self,
-        WindowSpec {
-            partition_by,
-            order_by,
-            window_frame,
-        }: WindowSpec,
+        f: Function,
         function_name: String,
-        inputs: Vec<ExprImpl>,
     ) -> Result<ExprImpl> {
         self.ensure_window_function_allowed()?;
-        if let Some(window_frame) = window_frame {
+        let window_function_type = match WindowFunctionType::from_str(&function_name) {
+            Ok(function_type) => function_type,
+            Err(e) => match function_name.parse::<AggKind>() {
+                Ok(kind) => {
+                    if !matches!(
+                        kind,
+                        AggKind::Sum | AggKind::Count | AggKind::Min | AggKind::Max | AggKind::Avg
+                    ) {
+                        return Err(ErrorCode::NotImplemented(
+                            format!("aggregate function as over window function: {}", kind),
+                            4978.into(),
+                        )
+                        .into());
+                    }
+                    WindowFunctionType::Aggregate(kind)
+                }
+                Err(_) => return Err(e.into()),
+            },
+        };
+        if f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
             return Err(ErrorCode::NotImplemented(
-                format!("window frame: {}", window_frame),
+                format!(
+                    "DISTINCT, ORDER BY or FILTER in window function: {}",
+                    function_name
+                ),
                 None.into(),
             )
             .into());
         }
-        let window_function_type = WindowFunctionType::from_str(&function_name)?;
+
+        let mut inputs: Vec<ExprImpl> = f
+            .args
+            .into_iter()
+            .map(|arg| self.bind_function_arg(arg))
+            .flatten_ok()
+            .try_collect()?;
+
+        let WindowSpec {
+            partition_by,
+            order_by,
+            window_frame,
+        } = f.over.unwrap();
+        let mut frame = match window_frame {
+            Some(window_frame) => Self::bind_window_frame(window_frame)?,
+            None => WindowFrame::default(),
+        };
+
+        // `LAG(x, k)` and `LEAD(x, k)` are evaluated as the value of `x` in the single-row frame
+        // `k` rows before or after the current row. The frame in the window spec is ignored, as
+        // in PostgreSQL.
+        if matches!(
+            window_function_type,
+            WindowFunctionType::Lag | WindowFunctionType::Lead
+        ) {
+            if inputs.len() > 2 {
+                return Err(ErrorCode::NotImplemented(
+                    format!("default value of {}", function_name),
+                    None.into(),
+                )
+                .into());
+            }
+            let offset = match inputs.len() {
+                2 => {
+                    let offset = inputs.pop().unwrap();
+                    if !offset.is_const() {
+                        return Err(ErrorCode::NotImplemented(
+                            format!("non-constant offset of {}", function_name),
+                            None.into(),
+                        )
+                        .into());
+                    }
+                    match offset.cast_implicit(DataType::Int64)?.eval_row_const()? {
+                        Some(offset) => *offset.as_int64(),
+                        None => {
+                            return Err(ErrorCode::InvalidInputSyntax(format!(
+                                "offset of {} must not be null",
+                                function_name
+                            ))
+                            .into())
+                        }
+                    }
+                }
+                _ => 1,
+            };
+            if offset < 0 {
+                return Err(ErrorCode::NotImplemented(
+                    format!("negative offset of {}", function_name),
+                    None.into(),
+                )
+                .into());
+            }
+            frame = match window_function_type {
+                WindowFunctionType::Lag => WindowFrame::single_row(offset),
+                _ => WindowFrame::single_row(-offset),
+            };
+        }
+
         let partition_by = partition_by
             .into_iter()
             .map(|arg| self.bind_expr(arg))
@@ -396,7 +480,37 @@ impl Binder {
                 .map(|order_by_expr| self.bind_order_by_expr(order_by_expr))
                 .collect::<Result<_>>()?,
         );
-        Ok(WindowFunction::new(window_function_type, partition_by, order_by, inputs)?.into())
+        Ok(
+            WindowFunction::new(window_function_type, partition_by, order_by, inputs, frame)?
+                .into(),
+        )
+    }
+
+    fn bind_window_frame(window_frame: ast::WindowFrame) -> Result<WindowFrame> {
+        let units = match window_frame.units {
+            WindowFrameUnits::Rows => FrameUnits::Rows,
+            WindowFrameUnits::Range => FrameUnits::Range,
+            WindowFrameUnits::Groups => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("window frame: {}", window_frame),
+                    None.into(),
+                )
+                .into())
+            }
+        };
+        let bind_bound = |bound: &WindowFrameBound| match bound {
+            WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+            WindowFrameBound::Preceding(None) => FrameBound::UnboundedPreceding,
+            WindowFrameBound::Preceding(Some(n)) => FrameBound::Preceding(*n),
+            WindowFrameBound::Following(None) => FrameBound::UnboundedFollowing,
+            WindowFrameBound::Following(Some(n)) => FrameBound::Following(*n),
+        };
+        let start = bind_bound(&window_frame.start_bound);
+        let end = window_frame
+            .end_bound
+            .as_ref()
+            .map_or(FrameBound::CurrentRow, bind_bound);
+        WindowFrame::new(units, start, end)
     }
 
     fn rewrite_concat_to_concat_ws(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
@@ -13,16 +13,17 @@
 // limitations under the License.
 
 use itertools::{zip_eq, Itertools};
-use risingwave_common::catalog::{ColumnDesc, ColumnId};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Field};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, Datum};
 use risingwave_sqlparser::ast::{
-    BinaryOperator, DataType as AstDataType, Expr, Function, ObjectName, Query, StructField,
-    TrimWhereField, UnaryOperator,
+    BinaryOperator, DataType as AstDataType, Expr, Function, ObjectName, Query, SourceWatermark,
+    StructField, TrimWhereField, UnaryOperator,
 };
 
 use crate::binder::Binder;
-use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall, SubqueryKind};
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::expr::{Expr as _, ExprImpl, ExprRewriter, ExprType, FunctionCall, SubqueryKind};
 
 mod binary_op;
 mod column;
@@ -33,6 +34,73 @@ mod value;
 
 impl Binder {
     pub(super) fn bind_expr(&mut self, expr: Expr) -> Result<ExprImpl> {
+        let expr = self.bind_expr_inner(expr)?;
+        Ok(self.inline_session_timezone(expr))
+    }
+
+    /// Inline the session time zone into `expr`. Exprs returned by `bind_expr` have it inlined
+    /// already, but casts added afterwards (e.g. on insert) need to go through this again.
+    pub(super) fn inline_session_timezone(&mut self, expr: ExprImpl) -> ExprImpl {
+        self.session_timezone.rewrite_expr(expr)
+    }
+
+    /// Bind the `DEFAULT` expression of a column and evaluate it. It must be a constant that can be
+    /// assigned to a column of `data_type`.
+    pub fn bind_column_default(&mut self, expr: Expr, data_type: &DataType) -> Result<Datum> {
+        let expr = self.bind_expr(expr)?.cast_assign(data_type.clone())?;
+        if !expr.is_const() {
+            return Err(
+                ErrorCode::BindError("DEFAULT expression must be a constant".to_string()).into(),
+            );
+        }
+        self.inline_session_timezone(expr).eval_row_const()
+    }
+
+    /// Bind the watermark definition of a source against its columns. Returns the index of the
+    /// watermark column and the watermark expression, which must be of the same type as the column.
+    pub fn bind_source_watermark(
+        &mut self,
+        source_name: &str,
+        columns: &[ColumnCatalog],
+        watermark: SourceWatermark,
+    ) -> Result<(usize, ExprImpl)> {
+        self.bind_table_to_context(
+            columns
+                .iter()
+                .map(|c| (c.is_hidden, Field::from(&c.column_desc))),
+            source_name.to_string(),
+            None,
+        )?;
+
+        let column_name = watermark.column.real_value();
+        let watermark_idx = columns
+            .iter()
+            .position(|c| !c.is_hidden && c.name() == column_name)
+            .ok_or_else(|| ErrorCode::ItemNotFound(format!("Invalid column: {}", column_name)))?;
+
+        let expr = self.bind_expr(watermark.expr)?;
+        if expr.has_agg_call() || expr.has_subquery() || expr.has_table_function() {
+            return Err(ErrorCode::BindError(
+                "Aggregation, subquery and table function are not allowed in the watermark \
+                 expression"
+                    .to_string(),
+            )
+            .into());
+        }
+        let data_type = columns[watermark_idx].data_type();
+        if &expr.return_type() != data_type {
+            return Err(ErrorCode::BindError(format!(
+                "The watermark expression of column {} should be of type {:?}, but got {:?}",
+                column_name,
+                data_type,
+                expr.return_type()
+            ))
+            .into());
+        }
+        Ok((watermark_idx, expr))
+    }
+
+    fn bind_expr_inner(&mut self, expr: Expr) -> Result<ExprImpl> {
         match expr {
             // literal
             Expr::Value(v) => Ok(ExprImpl::Literal(Box::new(self.bind_value(v)?))),
@@ -444,6 +512,8 @@ pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
         AstDataType::Timestamp(false) => DataType::Timestamp,
         AstDataType::Timestamp(true) => DataType::Timestampz,
         AstDataType::Interval => DataType::Interval,
+        AstDataType::Bytea => DataType::Bytea,
+        AstDataType::Jsonb => DataType::Jsonb,
         AstDataType::Array(datatype) => DataType::List {
             datatype: Box::new(bind_data_type(datatype)?),
         },
@@ -19,7 +19,7 @@ use risingwave_sqlparser::ast::{Ident, ObjectName, Query, SetExpr};
 
 use super::{BoundQuery, BoundSetExpr};
 use crate::binder::{Binder, BoundTableSource};
-use crate::expr::{ExprImpl, InputRef};
+use crate::expr::{ExprImpl, InputRef, Literal};
 
 #[derive(Debug)]
 pub struct BoundInsert {
@@ -37,18 +37,54 @@ impl Binder {
     pub(super) fn bind_insert(
         &mut self,
         source_name: ObjectName,
-        _columns: Vec<Ident>,
+        columns: Vec<Ident>,
         source: Query,
     ) -> Result<BoundInsert> {
         let (schema_name, source_name) =
             Self::resolve_table_or_source_name(&self.db_name, source_name)?;
         let table_source = self.bind_table_source(schema_name.as_deref(), &source_name)?;
 
-        let expected_types = table_source
-            .columns
-            .iter()
-            .map(|c| c.data_type.clone())
-            .collect();
+        // Positions of the target columns in the table source if they're specified.
+        let target_indices = match columns.is_empty() {
+            true => None,
+            false => {
+                let mut indices = Vec::with_capacity(columns.len());
+                for column in columns {
+                    let column_name = column.real_value();
+                    let index = table_source
+                        .columns
+                        .iter()
+                        .position(|c| c.name == column_name)
+                        .ok_or_else(|| {
+                            ErrorCode::BindError(format!(
+                                "column \"{}\" of relation \"{}\" does not exist",
+                                column_name, table_source.name
+                            ))
+                        })?;
+                    if indices.contains(&index) {
+                        return Err(ErrorCode::BindError(format!(
+                            "column \"{}\" specified more than once",
+                            column_name
+                        ))
+                        .into());
+                    }
+                    indices.push(index);
+                }
+                Some(indices)
+            }
+        };
+
+        let expected_types: Vec<DataType> = match &target_indices {
+            Some(indices) => indices
+                .iter()
+                .map(|&i| table_source.columns[i].data_type.clone())
+                .collect(),
+            None => table_source
+                .columns
+                .iter()
+                .map(|c| c.data_type.clone())
+                .collect(),
+        };
 
         // When the column types of `source` query does not match `expected_types`, casting is
         // needed.
@@ -107,12 +143,63 @@ impl Binder {
                             .enumerate()
                             .map(|(i, t)| InputRef::new(i, t).into())
                             .collect(),
-                    )?,
+                    )?
+                    .into_iter()
+                    .map(|expr| self.inline_session_timezone(expr))
+                    .collect(),
                 };
                 (bound, cast_exprs)
             }
         };
 
+        // Fill the columns omitted in the column list with their defaults.
+        let cast_exprs = match target_indices {
+            None => cast_exprs,
+            Some(indices) => {
+                let exprs = match cast_exprs.is_empty() {
+                    true => source
+                        .data_types()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, t)| InputRef::new(i, t).into())
+                        .collect(),
+                    false => cast_exprs,
+                };
+                table_source
+                    .columns
+                    .iter()
+                    .zip_eq(&table_source.column_defaults)
+                    .enumerate()
+                    .map(|(i, (column, default))| {
+                        match indices.iter().position(|&index| index == i) {
+                            Some(pos) => exprs[pos].clone(),
+                            None => Literal::new(default.clone(), column.data_type.clone()).into(),
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        // Columns removed by `ALTER TABLE DROP COLUMN` are still stored physically. Fill them with
+        // `NULL` so that the inserted chunk matches the layout of the table source.
+        let cast_exprs = if table_source.dropped_columns.is_empty() {
+            cast_exprs
+        } else {
+            let mut exprs = match cast_exprs.is_empty() {
+                true => source
+                    .data_types()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, t)| InputRef::new(i, t).into())
+                    .collect(),
+                false => cast_exprs,
+            };
+            for (i, data_type) in &table_source.dropped_columns {
+                exprs.insert(*i, Literal::new(None, data_type.clone()).into());
+            }
+            exprs
+        };
+
         let insert = BoundInsert {
             table_source,
             source,
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use risingwave_common::error::Result;
@@ -31,6 +31,7 @@ mod statement;
 mod struct_field;
 mod update;
 mod values;
+mod view;
 
 pub use bind_context::{BindContext, LateralBindContext};
 pub use delete::BoundDelete;
@@ -43,12 +44,14 @@ pub use relation::{
 };
 use risingwave_common::error::ErrorCode;
 pub use select::{BoundDistinct, BoundSelect};
-pub use set_expr::BoundSetExpr;
+pub use set_expr::{BoundSetExpr, BoundSetOperation};
 pub use statement::BoundStatement;
 pub use update::BoundUpdate;
 pub use values::BoundValues;
 
 use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::catalog::TableId;
+use crate::expr::SessionTimezone;
 use crate::session::{AuthContext, SessionImpl};
 
 /// `Binder` binds the identifiers in AST to columns in relations
@@ -77,6 +80,12 @@ pub struct Binder {
     cte_to_relation: HashMap<String, (BoundQuery, TableAlias)>,
 
     search_path: SearchPath,
+
+    /// The session time zone, inlined into exprs that depend on it.
+    session_timezone: SessionTimezone,
+
+    /// Ids of the tables, sources and views referenced by the bound statement.
+    included_relations: HashSet<TableId>,
 }
 
 impl Binder {
@@ -92,6 +101,8 @@ impl Binder {
             next_values_id: 0,
             cte_to_relation: HashMap::new(),
             search_path: session.config().get_search_path(),
+            session_timezone: SessionTimezone::new(session.config().get_timezone().name()),
+            included_relations: HashSet::new(),
         }
     }
 
@@ -100,6 +111,11 @@ impl Binder {
         self.bind_statement(stmt)
     }
 
+    /// Returns the ids of all the relations referenced by the statements bound so far.
+    pub fn included_relations(&self) -> HashSet<TableId> {
+        self.included_relations.clone()
+    }
+
     fn push_context(&mut self) {
         let new_context = std::mem::take(&mut self.context);
         let new_lateral_contexts = std::mem::take(&mut self.lateral_contexts);
@@ -165,9 +165,19 @@ impl Binder {
             });
         let mut extra_order_exprs = vec![];
         let visible_output_num = body.schema().len();
+        let is_set_operation = matches!(body, BoundSetExpr::SetOperation { .. });
         let order = order_by
             .into_iter()
             .map(|order_by_expr| {
+                // The inputs of a set operation are invisible here, so only its output columns can
+                // be referenced.
+                if is_set_operation && !is_output_column_ref(&order_by_expr.expr, &name_to_index) {
+                    return Err(ErrorCode::BindError(format!(
+                        "invalid UNION/INTERSECT/EXCEPT ORDER BY clause: {}",
+                        order_by_expr.expr
+                    ))
+                    .into());
+                }
                 self.bind_order_by_expr_in_query(
                     order_by_expr,
                     &name_to_index,
@@ -241,7 +251,7 @@ impl Binder {
         Ok(FieldOrder { index, direct })
     }
 
-    fn bind_with(&mut self, with: With) -> Result<()> {
+    pub(super) fn bind_with(&mut self, with: With) -> Result<()> {
         if with.recursive {
             Err(ErrorCode::NotImplemented("recursive cte".into(), None.into()).into())
         } else {
@@ -257,6 +267,15 @@ impl Binder {
     }
 }
 
+/// Whether `expr` refers to an output column of a [`Query`] by its name or index.
+fn is_output_column_ref(expr: &Expr, name_to_index: &HashMap<String, usize>) -> bool {
+    match expr {
+        Expr::Identifier(name) => name_to_index.contains_key(&name.real_value()),
+        Expr::Value(Value::Number(_)) => true,
+        _ => false,
+    }
+}
+
 // TODO: Make clause a const generic param after <https://github.com/rust-lang/rust/issues/95174>.
 fn parse_non_negative_i64(clause: &str, s: &str) -> Result<i64> {
     match s.parse::<i64>() {
@@ -18,14 +18,20 @@ use std::sync::Arc;
 use risingwave_common::catalog::{ColumnDesc, PG_CATALOG_SCHEMA_NAME};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::session_config::USER_NAME_WILD_CARD;
-use risingwave_sqlparser::ast::TableAlias;
+use risingwave_common::types::{DataType, Datum};
+use risingwave_sqlparser::ast::{Statement, TableAlias};
+use risingwave_sqlparser::parser::Parser;
 
+use super::BoundSubquery;
 use crate::binder::{Binder, Relation};
 use crate::catalog::root_catalog::SchemaPath;
 use crate::catalog::source_catalog::SourceCatalog;
 use crate::catalog::system_catalog::SystemCatalog;
 use crate::catalog::table_catalog::TableCatalog;
-use crate::catalog::{CatalogError, IndexCatalog, TableId};
+use crate::catalog::view_catalog::ViewCatalog;
+use crate::catalog::{
+    is_dropped_column_name, is_row_id_column_name, CatalogError, IndexCatalog, TableId,
+};
 use crate::user::UserId;
 
 #[derive(Debug, Clone)]
@@ -43,6 +49,12 @@ pub struct BoundTableSource {
     pub source_id: TableId, // TODO: refactor to source id
     pub associated_mview_id: TableId,
     pub columns: Vec<ColumnDesc>,
+    /// Defaults of the `columns`, used for the columns omitted in `INSERT`.
+    pub column_defaults: Vec<Datum>,
+    /// Columns removed by `ALTER TABLE DROP COLUMN`, with their positions in the chunks written
+    /// to the source (i.e. excluding the row id column). They are still stored physically and
+    /// must be filled with `NULL` on insertion.
+    pub dropped_columns: Vec<(usize, DataType)>,
     pub append_only: bool,
     pub owner: UserId,
 }
@@ -72,6 +84,12 @@ impl Binder {
         table_name: &str,
         alias: Option<TableAlias>,
     ) -> Result<Relation> {
+        if schema_name != Some(PG_CATALOG_SCHEMA_NAME) {
+            if let Some(view_catalog) = self.resolve_view(schema_name, table_name) {
+                return self.bind_view(&view_catalog, alias);
+            }
+        }
+
         let (ret, columns) = {
             let catalog = &self.catalog;
             let db_name = &self.db_name;
@@ -186,6 +204,17 @@ impl Binder {
             }
         };
 
+        match &ret {
+            Relation::BaseTable(table) => {
+                self.included_relations.insert(table.table_id);
+            }
+            Relation::Source(source) => {
+                self.included_relations
+                    .insert(TableId::new(source.catalog.id));
+            }
+            _ => {}
+        }
+
         self.bind_table_to_context(
             columns
                 .iter()
@@ -196,6 +225,62 @@ impl Binder {
         Ok(ret)
     }
 
+    fn resolve_view(&self, schema_name: Option<&str>, view_name: &str) -> Option<Arc<ViewCatalog>> {
+        let schema_path = match schema_name {
+            Some(schema_name) => SchemaPath::Name(schema_name),
+            None => SchemaPath::Path(&self.search_path, &self.auth_context.user_name),
+        };
+        self.catalog
+            .get_view_by_name(&self.db_name, schema_path, view_name)
+            .ok()
+            .map(|(view_catalog, _)| view_catalog.clone())
+    }
+
+    /// Binds a non-materialized view by inlining its definition as a subquery.
+    fn bind_view(
+        &mut self,
+        view_catalog: &ViewCatalog,
+        alias: Option<TableAlias>,
+    ) -> Result<Relation> {
+        let query = match Parser::parse_sql(&view_catalog.sql)
+            .map_err(|e| ErrorCode::InternalError(format!("failed to parse view: {}", e)))?
+            .pop()
+        {
+            Some(Statement::Query(query)) => *query,
+            _ => {
+                return Err(ErrorCode::InternalError(format!(
+                    "invalid definition of view \"{}\": {}",
+                    view_catalog.name, view_catalog.sql
+                ))
+                .into())
+            }
+        };
+
+        // CTEs of the outer query are not visible inside the view.
+        let cte_to_relation = std::mem::take(&mut self.cte_to_relation);
+        let query = self.bind_query(query);
+        self.cte_to_relation = cte_to_relation;
+        let query = query?;
+        if query.schema().len() != view_catalog.columns.len() {
+            return Err(ErrorCode::BindError(format!(
+                "definition of view \"{}\" no longer matches its {} columns",
+                view_catalog.name,
+                view_catalog.columns.len()
+            ))
+            .into());
+        }
+
+        self.included_relations
+            .insert(TableId::new(view_catalog.id));
+
+        self.bind_table_to_context(
+            view_catalog.columns.iter().map(|f| (false, f.clone())),
+            view_catalog.name.clone(),
+            alias,
+        )?;
+        Ok(Relation::Subquery(Box::new(BoundSubquery { query })))
+    }
+
     fn resolve_table_indexes(
         &self,
         schema_name: &str,
@@ -227,6 +312,7 @@ impl Binder {
         let table_indexes = self.resolve_table_indexes(schema_name, table_id)?;
 
         let columns = table_catalog.columns.clone();
+        self.included_relations.insert(table_id);
 
         self.bind_table_to_context(
             columns
@@ -268,11 +354,19 @@ impl Binder {
         let source_id = TableId::new(source.id);
 
         let append_only = source.append_only;
-        let columns = source
+        let (columns, column_defaults) = source
             .columns
             .iter()
             .filter(|c| !c.is_hidden)
-            .map(|c| c.column_desc.clone())
+            .map(|c| (c.column_desc.clone(), c.default_value.clone().flatten()))
+            .unzip();
+        let dropped_columns = source
+            .columns
+            .iter()
+            .filter(|c| !is_row_id_column_name(c.name()))
+            .enumerate()
+            .filter(|(_, c)| is_dropped_column_name(c.name()))
+            .map(|(i, c)| (i, c.data_type().clone()))
             .collect();
 
         let owner = source.owner;
@@ -284,6 +378,8 @@ impl Binder {
             source_id,
             associated_mview_id: associate_table_id,
             columns,
+            column_defaults,
+            dropped_columns,
             append_only,
             owner,
         })
@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_common::catalog::Schema;
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::SetExpr;
+use risingwave_sqlparser::ast::{SetExpr, SetOperator};
 
 use crate::binder::{Binder, BoundSelect, BoundValues};
-use crate::expr::{CorrelatedId, Depth};
+use crate::expr::{least_restrictive, CorrelatedId, Depth, ExprImpl};
 
 /// Part of a validated query, without order or limit clause. It may be composed of smaller
 /// `BoundSetExpr`s via set operators (e.g. union).
@@ -25,6 +26,42 @@ use crate::expr::{CorrelatedId, Depth};
 pub enum BoundSetExpr {
     Select(Box<BoundSelect>),
     Values(Box<BoundValues>),
+    /// `UNION`, `EXCEPT` or `INTERSECT` of two [`BoundSetExpr`]s. The output columns are named
+    /// after the left side and typed with the common types of both sides.
+    SetOperation {
+        op: BoundSetOperation,
+        all: bool,
+        left: Box<BoundSetExpr>,
+        right: Box<BoundSetExpr>,
+        schema: Schema,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundSetOperation {
+    Union,
+    Except,
+    Intersect,
+}
+
+impl From<SetOperator> for BoundSetOperation {
+    fn from(value: SetOperator) -> Self {
+        match value {
+            SetOperator::Union => BoundSetOperation::Union,
+            SetOperator::Except => BoundSetOperation::Except,
+            SetOperator::Intersect => BoundSetOperation::Intersect,
+        }
+    }
+}
+
+impl std::fmt::Display for BoundSetOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BoundSetOperation::Union => "UNION",
+            BoundSetOperation::Except => "EXCEPT",
+            BoundSetOperation::Intersect => "INTERSECT",
+        })
+    }
 }
 
 impl BoundSetExpr {
@@ -34,6 +71,29 @@ impl BoundSetExpr {
         match self {
             BoundSetExpr::Select(s) => s.schema(),
             BoundSetExpr::Values(v) => v.schema(),
+            BoundSetExpr::SetOperation { schema, .. } => schema,
+        }
+    }
+
+    /// All expressions in this [`BoundSetExpr`], including those of both sides of a set
+    /// operation.
+    pub fn exprs(&self) -> Box<dyn Iterator<Item = &ExprImpl> + '_> {
+        match self {
+            BoundSetExpr::Select(s) => Box::new(s.exprs()),
+            BoundSetExpr::Values(v) => Box::new(v.exprs()),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                Box::new(left.exprs().chain(right.exprs()))
+            }
+        }
+    }
+
+    pub fn exprs_mut(&mut self) -> Box<dyn Iterator<Item = &mut ExprImpl> + '_> {
+        match self {
+            BoundSetExpr::Select(s) => Box::new(s.exprs_mut()),
+            BoundSetExpr::Values(v) => Box::new(v.exprs_mut()),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                Box::new(left.exprs_mut().chain(right.exprs_mut()))
+            }
         }
     }
 
@@ -41,6 +101,9 @@ impl BoundSetExpr {
         match self {
             BoundSetExpr::Select(s) => s.is_correlated(),
             BoundSetExpr::Values(v) => v.is_correlated(),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                left.is_correlated() || right.is_correlated()
+            }
         }
     }
 
@@ -56,6 +119,14 @@ impl BoundSetExpr {
             BoundSetExpr::Values(v) => {
                 v.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id)
             }
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                let mut correlated_indices =
+                    left.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id);
+                correlated_indices.extend(
+                    right.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id),
+                );
+                correlated_indices
+            }
         }
     }
 }
@@ -70,11 +141,69 @@ impl Binder {
                 3584.into(),
             )
             .into()),
-            SetExpr::SetOperation { .. } => Err(ErrorCode::NotImplemented(
-                format!("set expr: {:}", set_expr),
-                None.into(),
-            )
-            .into()),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => self.bind_set_operation(op.into(), all, *left, *right),
+        }
+    }
+
+    /// Bind both sides of a set operation, each with its own [`BindContext`](super::BindContext),
+    /// and derive the output schema from them.
+    fn bind_set_operation(
+        &mut self,
+        op: BoundSetOperation,
+        all: bool,
+        left: SetExpr,
+        right: SetExpr,
+    ) -> Result<BoundSetExpr> {
+        let left = self.bind_set_expr_in_new_context(left)?;
+        let right = self.bind_set_expr_in_new_context(right)?;
+
+        let left_fields = left.schema().fields();
+        let right_fields = right.schema().fields();
+        if left_fields.len() != right_fields.len() {
+            return Err(ErrorCode::BindError(format!(
+                "each {} query must have the same number of columns",
+                op
+            ))
+            .into());
         }
+
+        let fields = left_fields
+            .iter()
+            .zip_eq(right_fields.iter())
+            .map(|(l, r)| {
+                let data_type = least_restrictive(l.data_type(), r.data_type()).map_err(|_| {
+                    ErrorCode::BindError(format!(
+                        "{} types {} and {} cannot be matched",
+                        op,
+                        l.data_type(),
+                        r.data_type()
+                    ))
+                })?;
+                Ok(Field::with_name(data_type, l.name.clone()))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(BoundSetExpr::SetOperation {
+            op,
+            all,
+            left: Box::new(left),
+            right: Box::new(right),
+            schema: Schema { fields },
+        })
+    }
+
+    /// Bind a [`SetExpr`] with an empty [`BindContext`](super::BindContext), so that the relations
+    /// of one side of a set operation are invisible to the other side. The depth of correlated
+    /// input refs is not affected, as the outer contexts stay on the stack.
+    fn bind_set_expr_in_new_context(&mut self, set_expr: SetExpr) -> Result<BoundSetExpr> {
+        let old_context = std::mem::take(&mut self.context);
+        let result = self.bind_set_expr(set_expr);
+        self.context = old_context;
+        result
     }
 }
@@ -99,6 +99,7 @@ impl Binder {
             for (id, value) in assignments {
                 let id_expr = self.bind_expr(Expr::Identifier(id.clone()))?;
                 let value_expr = self.bind_expr(value)?.cast_assign(id_expr.return_type())?;
+                let value_expr = self.inline_session_timezone(value_expr);
 
                 match assignment_exprs.entry(id_expr) {
                     Entry::Occupied(_) => {
@@ -104,6 +104,16 @@ impl Binder {
                 .try_collect()?,
         };
 
+        // The casts added above may depend on the session time zone.
+        let bound = bound
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|expr| self.inline_session_timezone(expr))
+                    .collect_vec()
+            })
+            .collect_vec();
+
         let values_id = self.next_values_id();
         let schema = Schema::new(
             types
@@ -0,0 +1,396 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use risingwave_common::catalog::PG_CATALOG_SCHEMA_NAME;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::session_config::USER_NAME_WILD_CARD;
+use risingwave_sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, Ident, JoinConstraint, JoinOperator, ObjectName, Query,
+    Select, SelectItem, SetExpr, TableFactor, TableWithJoins,
+};
+
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
+
+impl Binder {
+    /// Rewrites the definition of a view so that binding it later yields the same columns from the
+    /// same relations, no matter how the referenced tables or the search path change:
+    /// * `*` and `t.*` in the output of the view are expanded to the columns they refer to now.
+    /// * Unqualified relation names are qualified with the schema they are resolved to now.
+    pub fn rewrite_view_query(&mut self, query: &mut Query) -> Result<()> {
+        self.push_context();
+        let result = self.expand_query_wildcards(query);
+        self.pop_context()?;
+        result?;
+
+        let mut ctes = HashSet::new();
+        self.qualify_query(query, &mut ctes);
+        Ok(())
+    }
+
+    fn expand_query_wildcards(&mut self, query: &mut Query) -> Result<()> {
+        if let Some(with) = query.with.clone() {
+            self.bind_with(with)?;
+        }
+        self.expand_set_expr_wildcards(&mut query.body)
+    }
+
+    fn expand_set_expr_wildcards(&mut self, set_expr: &mut SetExpr) -> Result<()> {
+        match set_expr {
+            SetExpr::Select(select) => self.expand_select_wildcards(select),
+            SetExpr::Query(query) => {
+                self.push_context();
+                let result = self.expand_query_wildcards(query);
+                self.pop_context()?;
+                result
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                self.expand_set_expr_wildcards(left)?;
+                self.expand_set_expr_wildcards(right)
+            }
+            SetExpr::Values(_) => Ok(()),
+        }
+    }
+
+    fn expand_select_wildcards(&mut self, select: &mut Select) -> Result<()> {
+        if !select.projection.iter().any(|item| {
+            matches!(
+                item,
+                SelectItem::Wildcard | SelectItem::QualifiedWildcard(_)
+            )
+        }) {
+            return Ok(());
+        }
+
+        self.push_context();
+        let result = self
+            .bind_vec_table_with_joins(select.from.clone())
+            .and_then(|_| self.expand_select_items(std::mem::take(&mut select.projection)));
+        self.pop_context()?;
+        select.projection = result?;
+        Ok(())
+    }
+
+    /// Expands the wildcards in `items` against the relations in the current context, in the same
+    /// order as [`Binder::bind_select_list`] does.
+    fn expand_select_items(&self, items: Vec<SelectItem>) -> Result<Vec<SelectItem>> {
+        let column_ref = |index: usize| {
+            let column = &self.context.columns[index];
+            SelectItem::UnnamedExpr(Expr::CompoundIdentifier(vec![
+                Ident::with_quote('"', &column.table_name),
+                Ident::with_quote('"', &column.field.name),
+            ]))
+        };
+
+        let mut expanded = vec![];
+        for item in items {
+            match item {
+                SelectItem::QualifiedWildcard(obj_name) => {
+                    let table_name = &obj_name.0.last().unwrap().real_value();
+                    let (begin, end) = self.context.range_of.get(table_name).ok_or_else(|| {
+                        ErrorCode::ItemNotFound(format!("relation \"{}\"", table_name))
+                    })?;
+                    expanded.extend(
+                        (*begin..*end)
+                            .filter(|index| !self.context.columns[*index].is_hidden)
+                            .map(&column_ref),
+                    );
+                }
+                SelectItem::Wildcard => {
+                    // The USING and NATURAL columns come first. They are referred to by their
+                    // unqualified names, which are bound to the merged column of each group.
+                    let groups = &self.context.column_group_context;
+                    expanded.extend(groups.groups.values().rev().map(|g| {
+                        let index = g
+                            .non_nullable_column
+                            .unwrap_or_else(|| *g.indices.iter().next().unwrap());
+                        let name = &self.context.columns[index].field.name;
+                        SelectItem::UnnamedExpr(Expr::Identifier(Ident::with_quote('"', name)))
+                    }));
+                    expanded.extend(
+                        self.context
+                            .columns
+                            .iter()
+                            .filter(|c| !c.is_hidden && !groups.mapping.contains_key(&c.index))
+                            .map(|c| column_ref(c.index)),
+                    );
+                }
+                item => expanded.push(item),
+            }
+        }
+        Ok(expanded)
+    }
+
+    /// Returns the schema that the unqualified relation `name` is resolved to, following the same
+    /// order as [`Binder::bind_table_or_source`].
+    fn resolve_relation_schema(&self, name: &str) -> Option<String> {
+        let user_name = &self.auth_context.user_name;
+        if let Ok((_, schema_name)) = self.catalog.get_view_by_name(
+            &self.db_name,
+            SchemaPath::Path(&self.search_path, user_name),
+            name,
+        ) {
+            return Some(schema_name.to_string());
+        }
+
+        for path in self.search_path.path() {
+            if path == PG_CATALOG_SCHEMA_NAME {
+                if self
+                    .catalog
+                    .get_sys_table_by_name(&self.db_name, name)
+                    .is_ok()
+                {
+                    return Some(PG_CATALOG_SCHEMA_NAME.to_string());
+                }
+            } else {
+                let schema_name = if path == USER_NAME_WILD_CARD {
+                    user_name
+                } else {
+                    path
+                };
+                if let Ok(schema) = self.catalog.get_schema_by_name(&self.db_name, schema_name) {
+                    if schema.get_table_by_name(name).is_some()
+                        || schema.get_source_by_name(name).is_some()
+                    {
+                        return Some(schema_name.to_string());
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn qualify_query(&self, query: &mut Query, ctes: &mut HashSet<String>) {
+        if let Some(with) = &mut query.with {
+            for cte in &mut with.cte_tables {
+                self.qualify_query(&mut cte.query, ctes);
+                ctes.insert(cte.alias.name.real_value());
+            }
+        }
+        self.qualify_set_expr(&mut query.body, ctes);
+        for order_by in &mut query.order_by {
+            self.qualify_expr(&mut order_by.expr, ctes);
+        }
+    }
+
+    fn qualify_set_expr(&self, set_expr: &mut SetExpr, ctes: &mut HashSet<String>) {
+        match set_expr {
+            SetExpr::Select(select) => {
+                for from in &mut select.from {
+                    self.qualify_table_with_joins(from, ctes);
+                }
+                for item in &mut select.projection {
+                    match item {
+                        SelectItem::UnnamedExpr(expr)
+                        | SelectItem::ExprWithAlias { expr, .. }
+                        | SelectItem::ExprQualifiedWildcard(expr, _) => {
+                            self.qualify_expr(expr, ctes)
+                        }
+                        SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => {}
+                    }
+                }
+                let exprs = select
+                    .selection
+                    .iter_mut()
+                    .chain(select.group_by.iter_mut())
+                    .chain(select.having.iter_mut());
+                for expr in exprs {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            SetExpr::Query(query) => self.qualify_query(query, ctes),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.qualify_set_expr(left, ctes);
+                self.qualify_set_expr(right, ctes);
+            }
+            SetExpr::Values(values) => {
+                for expr in values.0.iter_mut().flatten() {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+        }
+    }
+
+    fn qualify_table_with_joins(&self, table: &mut TableWithJoins, ctes: &mut HashSet<String>) {
+        self.qualify_table_factor(&mut table.relation, ctes);
+        for join in &mut table.joins {
+            self.qualify_table_factor(&mut join.relation, ctes);
+            match &mut join.join_operator {
+                JoinOperator::Inner(JoinConstraint::On(expr))
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) => {
+                    self.qualify_expr(expr, ctes)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn qualify_table_factor(&self, table_factor: &mut TableFactor, ctes: &mut HashSet<String>) {
+        match table_factor {
+            TableFactor::Table { name, .. } => {
+                if let [ident] = name.0.as_slice() {
+                    let table_name = ident.real_value();
+                    if ctes.contains(&table_name) {
+                        return;
+                    }
+                    if let Some(schema_name) = self.resolve_relation_schema(&table_name) {
+                        *name =
+                            ObjectName(vec![Ident::with_quote('"', schema_name), ident.clone()]);
+                    }
+                }
+            }
+            TableFactor::Derived { subquery, .. } => self.qualify_query(subquery, ctes),
+            TableFactor::TableFunction { args, .. } => self.qualify_function_args(args, ctes),
+            TableFactor::NestedJoin(table) => self.qualify_table_with_joins(table, ctes),
+        }
+    }
+
+    fn qualify_function_args(&self, args: &mut [FunctionArg], ctes: &mut HashSet<String>) {
+        for arg in args {
+            let (FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg)) = arg;
+            if let FunctionArgExpr::Expr(expr) | FunctionArgExpr::ExprQualifiedWildcard(expr, _) =
+                arg
+            {
+                self.qualify_expr(expr, ctes);
+            }
+        }
+    }
+
+    /// Qualifies the relations referenced by the subqueries in `expr`.
+    fn qualify_expr(&self, expr: &mut Expr, ctes: &mut HashSet<String>) {
+        match expr {
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.qualify_expr(expr, ctes);
+                self.qualify_query(subquery, ctes);
+            }
+            Expr::Exists(query) | Expr::Subquery(query) => self.qualify_query(query, ctes),
+            Expr::FieldIdentifier(expr, _)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsNotTrue(expr)
+            | Expr::IsFalse(expr)
+            | Expr::IsNotFalse(expr)
+            | Expr::UnaryOp { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. }
+            | Expr::Extract { expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::Nested(expr) => self.qualify_expr(expr, ctes),
+            Expr::IsDistinctFrom(left, right)
+            | Expr::IsNotDistinctFrom(left, right)
+            | Expr::BinaryOp { left, right, .. }
+            | Expr::ArrayIndex {
+                obj: left,
+                index: right,
+            } => {
+                self.qualify_expr(left, ctes);
+                self.qualify_expr(right, ctes);
+            }
+            Expr::InList { expr, list, .. } => {
+                self.qualify_expr(expr, ctes);
+                for expr in list {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.qualify_expr(expr, ctes);
+                self.qualify_expr(low, ctes);
+                self.qualify_expr(high, ctes);
+            }
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => {
+                self.qualify_expr(expr, ctes);
+                for expr in substring_from.iter_mut().chain(substring_for.iter_mut()) {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            Expr::Overlay {
+                expr,
+                new_substring,
+                start,
+                count,
+            } => {
+                self.qualify_expr(expr, ctes);
+                self.qualify_expr(new_substring, ctes);
+                self.qualify_expr(start, ctes);
+                if let Some(count) = count {
+                    self.qualify_expr(count, ctes);
+                }
+            }
+            Expr::Trim { expr, trim_where } => {
+                self.qualify_expr(expr, ctes);
+                if let Some((_, expr)) = trim_where {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            Expr::Function(function) => {
+                self.qualify_function_args(&mut function.args, ctes);
+                if let Some(over) = &mut function.over {
+                    for expr in &mut over.partition_by {
+                        self.qualify_expr(expr, ctes);
+                    }
+                    for order_by in &mut over.order_by {
+                        self.qualify_expr(&mut order_by.expr, ctes);
+                    }
+                }
+                for order_by in &mut function.order_by {
+                    self.qualify_expr(&mut order_by.expr, ctes);
+                }
+                if let Some(filter) = &mut function.filter {
+                    self.qualify_expr(filter, ctes);
+                }
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                let exprs = operand
+                    .iter_mut()
+                    .chain(else_result.iter_mut())
+                    .map(|expr| expr.as_mut())
+                    .chain(conditions.iter_mut())
+                    .chain(results.iter_mut());
+                for expr in exprs {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            Expr::GroupingSets(sets) | Expr::Cube(sets) | Expr::Rollup(sets) => {
+                for expr in sets.iter_mut().flatten() {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            Expr::Row(exprs) | Expr::Array(exprs) => {
+                for expr in exprs {
+                    self.qualify_expr(expr, ctes);
+                }
+            }
+            Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::Value(_)
+            | Expr::TypedString { .. } => {}
+        }
+    }
+}
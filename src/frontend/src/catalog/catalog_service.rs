@@ -21,7 +21,7 @@ use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable,
+    Source as ProstSource, Table as ProstTable, View as ProstView,
 };
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_rpc_client::MetaClient;
@@ -60,6 +60,8 @@ pub trait CatalogWriter: Send + Sync {
         owner: UserId,
     ) -> Result<()>;
 
+    async fn create_view(&self, view: ProstView) -> Result<()>;
+
     async fn create_materialized_view(
         &self,
         table: ProstTable,
@@ -97,6 +99,10 @@ pub trait CatalogWriter: Send + Sync {
         indexes_id: Vec<IndexId>,
     ) -> Result<()>;
 
+    async fn alter_table(&self, source: ProstSource, table: ProstTable) -> Result<()>;
+
+    async fn truncate_table(&self, table_id: TableId) -> Result<bool>;
+
     async fn drop_source(&self, source_id: u32) -> Result<()>;
 
     async fn drop_sink(&self, sink_id: u32) -> Result<()>;
@@ -106,6 +112,8 @@ pub trait CatalogWriter: Send + Sync {
     async fn drop_schema(&self, schema_id: u32) -> Result<()>;
 
     async fn drop_index(&self, index_id: IndexId) -> Result<()>;
+
+    async fn drop_view(&self, view_id: u32) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -146,6 +154,11 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn create_view(&self, view: ProstView) -> Result<()> {
+        let (_, version) = self.meta_client.create_view(view).await?;
+        self.wait_version(version).await
+    }
+
     // TODO: maybe here to pass a materialize plan node
     async fn create_materialized_view(
         &self,
@@ -217,6 +230,16 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn alter_table(&self, source: ProstSource, table: ProstTable) -> Result<()> {
+        let version = self.meta_client.alter_table(source, table).await?;
+        self.wait_version(version).await
+    }
+
+    async fn truncate_table(&self, table_id: TableId) -> Result<bool> {
+        let truncated = self.meta_client.truncate_table(table_id.table_id).await?;
+        Ok(truncated)
+    }
+
     async fn drop_source(&self, source_id: u32) -> Result<()> {
         let version = self.meta_client.drop_source(source_id).await?;
         self.wait_version(version).await
@@ -232,6 +255,11 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn drop_view(&self, view_id: u32) -> Result<()> {
+        let version = self.meta_client.drop_view(view_id).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_schema(&self, schema_id: u32) -> Result<()> {
         let version = self.meta_client.drop_schema(schema_id).await?;
         self.wait_version(version).await
@@ -14,8 +14,9 @@
 
 use std::borrow::Cow;
 
-use risingwave_common::catalog::{ColumnDesc, ColumnId};
-use risingwave_common::types::DataType;
+use risingwave_common::catalog::{column_default_from_protobuf, ColumnDesc, ColumnId};
+use risingwave_common::types::{DataType, Datum};
+use risingwave_common::util::value_encoding::serialize_datum_to_bytes;
 use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 
 use super::row_id_column_desc;
@@ -24,6 +25,9 @@ use super::row_id_column_desc;
 pub struct ColumnCatalog {
     pub column_desc: ColumnDesc,
     pub is_hidden: bool,
+    /// The default of a column appended by `ALTER TABLE ADD COLUMN`, which is also used for the
+    /// rows written before the column is appended. `None` for the columns created with the table.
+    pub default_value: Option<Datum>,
 }
 
 impl ColumnCatalog {
@@ -52,6 +56,11 @@ impl ColumnCatalog {
         ProstColumnCatalog {
             column_desc: Some(self.column_desc.to_protobuf()),
             is_hidden: self.is_hidden,
+            default_value: self
+                .default_value
+                .as_ref()
+                .map(|d| serialize_datum_to_bytes(d.as_ref()))
+                .unwrap_or_default(),
         }
     }
 
@@ -60,6 +69,7 @@ impl ColumnCatalog {
         Self {
             column_desc: row_id_column_desc(column_id),
             is_hidden: true,
+            default_value: None,
         }
     }
 }
@@ -67,6 +77,7 @@ impl ColumnCatalog {
 impl From<ProstColumnCatalog> for ColumnCatalog {
     fn from(prost: ProstColumnCatalog) -> Self {
         Self {
+            default_value: column_default_from_protobuf(&prost),
             column_desc: prost.column_desc.unwrap().into(),
             is_hidden: prost.is_hidden,
         }
@@ -28,12 +28,14 @@ pub(crate) mod sink_catalog;
 pub(crate) mod source_catalog;
 pub(crate) mod system_catalog;
 pub(crate) mod table_catalog;
+pub(crate) mod view_catalog;
 
 pub use index_catalog::IndexCatalog;
 pub use table_catalog::TableCatalog;
 
 pub(crate) type SourceId = u32;
 pub(crate) type SinkId = u32;
+pub(crate) type ViewId = u32;
 
 pub(crate) type DatabaseId = u32;
 pub(crate) type SchemaId = u32;
@@ -49,6 +51,12 @@ pub fn check_valid_column_name(column_name: &str) -> Result<()> {
             ROWID_PREFIX
         ))
         .into())
+    } else if is_dropped_column_name(column_name) {
+        Err(ErrorCode::InternalError(format!(
+            "column name prefixed with {:?} are reserved word.",
+            DROPPED_COLUMN_PREFIX
+        ))
+        .into())
     } else {
         Ok(())
     }
@@ -76,6 +84,18 @@ pub fn is_row_id_column_name(name: &str) -> bool {
     name.starts_with(ROWID_PREFIX)
 }
 
+const DROPPED_COLUMN_PREFIX: &str = "_dropped_column_";
+
+/// Name of a column removed by `ALTER TABLE DROP COLUMN`. The column is kept physically and only
+/// hidden, so it gets a reserved name to make room for a new column with the same name.
+pub fn dropped_column_name(column_id: ColumnId) -> String {
+    format!("{}{}", DROPPED_COLUMN_PREFIX, column_id.get_id())
+}
+
+pub fn is_dropped_column_name(name: &str) -> bool {
+    name.starts_with(DROPPED_COLUMN_PREFIX)
+}
+
 /// Creates a row ID column (for implicit primary key).
 pub fn row_id_column_desc(column_id: ColumnId) -> ColumnDesc {
     ColumnDesc {
@@ -19,6 +19,7 @@ pub mod pg_matviews_info;
 pub mod pg_namespace;
 pub mod pg_type;
 pub mod pg_user;
+pub mod pg_views;
 
 use std::collections::HashMap;
 use std::sync::{Arc, LazyLock};
@@ -42,6 +43,7 @@ use crate::catalog::pg_catalog::pg_matviews_info::*;
 use crate::catalog::pg_catalog::pg_namespace::*;
 use crate::catalog::pg_catalog::pg_type::*;
 use crate::catalog::pg_catalog::pg_user::*;
+use crate::catalog::pg_catalog::pg_views::*;
 use crate::catalog::system_catalog::SystemCatalog;
 use crate::meta_client::FrontendMetaClient;
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
@@ -92,6 +94,7 @@ impl SysCatalogReader for SysCatalogReaderImpl {
             PG_USER_TABLE_NAME => self.read_user_info(),
             PG_CLASS_TABLE_NAME => self.read_class_info(),
             PG_INDEX_TABLE_NAME => self.read_index_info(),
+            PG_VIEWS_TABLE_NAME => self.read_views_info(),
             _ => {
                 Err(ErrorCode::ItemNotFound(format!("Invalid system table: {}", table_name)).into())
             }
@@ -269,6 +272,19 @@ impl SysCatalogReaderImpl {
                     })
                     .collect_vec();
 
+                let views = schema
+                    .iter_view()
+                    .map(|view| {
+                        Row::new(vec![
+                            Some(ScalarImpl::Int32(view.id as i32)),
+                            Some(ScalarImpl::Utf8(view.name.clone())),
+                            Some(ScalarImpl::Int32(schema_info.id as i32)),
+                            Some(ScalarImpl::Int32(view.owner as i32)),
+                            Some(ScalarImpl::Utf8("v".to_string())),
+                        ])
+                    })
+                    .collect_vec();
+
                 let sys_tables = schema
                     .iter_system_tables()
                     .map(|table| {
@@ -286,6 +302,7 @@ impl SysCatalogReaderImpl {
                     .chain(mvs.into_iter())
                     .chain(indexes.into_iter())
                     .chain(sources.into_iter())
+                    .chain(views.into_iter())
                     .chain(sys_tables.into_iter())
                     .collect_vec()
             })
@@ -309,6 +326,28 @@ impl SysCatalogReaderImpl {
             .collect_vec())
     }
 
+    fn read_views_info(&self) -> Result<Vec<Row>> {
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.iter_schemas(&self.auth_context.database)?;
+        let user_reader = self.user_info_reader.read_guard();
+        let username_map = user_reader.get_user_name_map();
+
+        Ok(schemas
+            .flat_map(|schema| {
+                schema.iter_view().map(|view| {
+                    Row::new(vec![
+                        Some(ScalarImpl::Utf8(schema.name())),
+                        Some(ScalarImpl::Utf8(view.name.clone())),
+                        Some(ScalarImpl::Utf8(
+                            username_map.get(&view.owner).cloned().unwrap_or_default(),
+                        )),
+                        Some(ScalarImpl::Utf8(view.sql.clone())),
+                    ])
+                })
+            })
+            .collect_vec())
+    }
+
     async fn read_mviews_info(&self) -> Result<Vec<Row>> {
         let mut table_ids = Vec::new();
         {
@@ -371,6 +410,7 @@ macro_rules! def_sys_catalog {
                         type_name: "".to_string(),
                     },
                     is_hidden: false,
+                    default_value: None,
                 })
                 .collect::<Vec<_>>(),
             pk: vec![0], // change this when multi-column pk is needed in some system table.
@@ -390,6 +430,7 @@ pub(crate) static PG_CATALOG_MAP: LazyLock<HashMap<String, SystemCatalog>> = Laz
         PG_USER_TABLE_NAME.to_string() => def_sys_catalog!(5, PG_USER_TABLE_NAME, PG_USER_COLUMNS),
         PG_CLASS_TABLE_NAME.to_string() => def_sys_catalog!(6, PG_CLASS_TABLE_NAME, PG_CLASS_COLUMNS),
         PG_INDEX_TABLE_NAME.to_string() => def_sys_catalog!(7, PG_INDEX_TABLE_NAME, PG_INDEX_COLUMNS),
+        PG_VIEWS_TABLE_NAME.to_string() => def_sys_catalog!(8, PG_VIEWS_TABLE_NAME, PG_VIEWS_COLUMNS),
     }
 });
 
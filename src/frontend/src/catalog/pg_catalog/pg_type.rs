@@ -29,6 +29,7 @@ pub const PG_TYPE_COLUMNS: &[PgCatalogColumnsDef<'_>] =
 // TODO: uniform the default data with `TypeOid` under `pg_field_descriptor`.
 pub const PG_TYPE_DATA: &[(i32, &str)] = &[
     (16, "bool"),
+    (17, "bytea"),
     (20, "int8"),
     (21, "int2"),
     (23, "int4"),
@@ -41,6 +42,7 @@ pub const PG_TYPE_DATA: &[(i32, &str)] = &[
     (1184, "timestamptz"),
     (1186, "interval"),
     (1700, "numeric"),
+    (3802, "jsonb"),
 ];
 
 pub static PG_TYPE_DATA_ROWS: LazyLock<Vec<Row>> = LazyLock::new(|| {
@@ -22,11 +22,12 @@ use risingwave_common::error::Result;
 use risingwave_common::session_config::{SearchPath, USER_NAME_WILD_CARD};
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable,
+    Source as ProstSource, Table as ProstTable, View as ProstView,
 };
 
 use super::source_catalog::SourceCatalog;
-use super::{CatalogError, SinkId, SourceId};
+use super::view_catalog::ViewCatalog;
+use super::{CatalogError, SinkId, SourceId, ViewId};
 use crate::catalog::database_catalog::DatabaseCatalog;
 use crate::catalog::schema_catalog::SchemaCatalog;
 use crate::catalog::sink_catalog::SinkCatalog;
@@ -144,6 +145,14 @@ impl Catalog {
             .create_sink(proto);
     }
 
+    pub fn create_view(&mut self, proto: ProstView) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .create_view(proto);
+    }
+
     pub fn drop_database(&mut self, db_id: DatabaseId) {
         let name = self.db_name_by_id.remove(&db_id).unwrap();
         let _database = self.database_by_name.remove(&name).unwrap();
@@ -171,6 +180,14 @@ impl Catalog {
             .update_table(proto);
     }
 
+    pub fn update_source(&mut self, proto: &ProstSource) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .update_source(proto);
+    }
+
     pub fn drop_source(&mut self, db_id: DatabaseId, schema_id: SchemaId, source_id: SourceId) {
         self.get_database_mut(db_id)
             .unwrap()
@@ -187,6 +204,14 @@ impl Catalog {
             .drop_sink(sink_id);
     }
 
+    pub fn drop_view(&mut self, db_id: DatabaseId, schema_id: SchemaId, view_id: ViewId) {
+        self.get_database_mut(db_id)
+            .unwrap()
+            .get_schema_mut(schema_id)
+            .unwrap()
+            .drop_view(view_id);
+    }
+
     pub fn drop_index(&mut self, db_id: DatabaseId, schema_id: SchemaId, index_id: IndexId) {
         self.get_database_mut(db_id)
             .unwrap()
@@ -451,6 +476,46 @@ impl Catalog {
         }
     }
 
+    #[inline(always)]
+    fn get_view_by_name_with_schema_name(
+        &self,
+        db_name: &str,
+        schema_name: &str,
+        view_name: &str,
+    ) -> Result<&Arc<ViewCatalog>> {
+        self.get_schema_by_name(db_name, schema_name)?
+            .get_view_by_name(view_name)
+            .ok_or_else(|| CatalogError::NotFound("view", view_name.to_string()).into())
+    }
+
+    pub fn get_view_by_name<'a>(
+        &self,
+        db_name: &str,
+        schema_path: SchemaPath<'a>,
+        view_name: &str,
+    ) -> Result<(&Arc<ViewCatalog>, &'a str)> {
+        match schema_path {
+            SchemaPath::Name(schema_name) => self
+                .get_view_by_name_with_schema_name(db_name, schema_name, view_name)
+                .map(|view_catalog| (view_catalog, schema_name)),
+            SchemaPath::Path(search_path, user_name) => {
+                for path in search_path.path() {
+                    let mut schema_name: &str = path;
+                    if schema_name == USER_NAME_WILD_CARD {
+                        schema_name = user_name;
+                    }
+
+                    if let Ok(view_catalog) =
+                        self.get_view_by_name_with_schema_name(db_name, schema_name, view_name)
+                    {
+                        return Ok((view_catalog, schema_name));
+                    }
+                }
+                Err(CatalogError::NotFound("view", view_name.to_string()).into())
+            }
+        }
+    }
+
     /// Check the name if duplicated with existing table, materialized view or source.
     pub fn check_relation_name_duplicated(
         &self,
@@ -476,6 +541,8 @@ impl Catalog {
             }
         } else if schema.get_sink_by_name(relation_name).is_some() {
             Err(CatalogError::Duplicated("sink", relation_name.to_string()).into())
+        } else if schema.get_view_by_name(relation_name).is_some() {
+            Err(CatalogError::Duplicated("view", relation_name.to_string()).into())
         } else {
             Ok(())
         }
@@ -19,7 +19,7 @@ use std::sync::Arc;
 use risingwave_common::catalog::{valid_table_name, IndexId, TableId, PG_CATALOG_SCHEMA_NAME};
 use risingwave_pb::catalog::{
     Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
-    Table as ProstTable,
+    Table as ProstTable, View as ProstView,
 };
 
 use super::source_catalog::SourceCatalog;
@@ -27,7 +27,8 @@ use crate::catalog::index_catalog::IndexCatalog;
 use crate::catalog::sink_catalog::SinkCatalog;
 use crate::catalog::system_catalog::SystemCatalog;
 use crate::catalog::table_catalog::TableCatalog;
-use crate::catalog::SchemaId;
+use crate::catalog::view_catalog::ViewCatalog;
+use crate::catalog::{SchemaId, ViewId};
 
 pub type SourceId = u32;
 pub type SinkId = u32;
@@ -45,6 +46,8 @@ pub struct SchemaCatalog {
     index_by_name: HashMap<String, Arc<IndexCatalog>>,
     index_by_id: HashMap<IndexId, Arc<IndexCatalog>>,
     indexes_by_table_id: HashMap<TableId, Vec<Arc<IndexCatalog>>>,
+    view_by_name: HashMap<String, Arc<ViewCatalog>>,
+    view_by_id: HashMap<ViewId, Arc<ViewCatalog>>,
 
     // This field only available when schema is "pg_catalog". Meanwhile, others will be empty.
     system_table_by_name: HashMap<String, SystemCatalog>,
@@ -140,6 +143,16 @@ impl SchemaCatalog {
         self.source_by_id.try_insert(id, source_ref).unwrap();
     }
 
+    pub fn update_source(&mut self, prost: &ProstSource) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let source = SourceCatalog::from(prost);
+        let source_ref = Arc::new(source);
+
+        self.source_by_name.insert(name, source_ref.clone());
+        self.source_by_id.insert(id, source_ref);
+    }
+
     pub fn drop_source(&mut self, id: SourceId) {
         let source_ref = self.source_by_id.remove(&id).unwrap();
         self.source_by_name.remove(&source_ref.name).unwrap();
@@ -162,6 +175,23 @@ impl SchemaCatalog {
         self.sink_by_name.remove(&sink_ref.name).unwrap();
     }
 
+    pub fn create_view(&mut self, prost: ProstView) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let view = ViewCatalog::from(&prost);
+        let view_ref = Arc::new(view);
+
+        self.view_by_name
+            .try_insert(name, view_ref.clone())
+            .unwrap();
+        self.view_by_id.try_insert(id, view_ref).unwrap();
+    }
+
+    pub fn drop_view(&mut self, id: ViewId) {
+        let view_ref = self.view_by_id.remove(&id).unwrap();
+        self.view_by_name.remove(&view_ref.name).unwrap();
+    }
+
     pub fn iter_table(&self) -> impl Iterator<Item = &Arc<TableCatalog>> {
         self.table_by_name
             .iter()
@@ -209,6 +239,10 @@ impl SchemaCatalog {
         self.sink_by_name.values()
     }
 
+    pub fn iter_view(&self) -> impl Iterator<Item = &Arc<ViewCatalog>> {
+        self.view_by_name.values()
+    }
+
     pub fn iter_system_tables(&self) -> impl Iterator<Item = &SystemCatalog> {
         self.system_table_by_name.values()
     }
@@ -229,6 +263,10 @@ impl SchemaCatalog {
         self.sink_by_name.get(sink_name)
     }
 
+    pub fn get_view_by_name(&self, view_name: &str) -> Option<&Arc<ViewCatalog>> {
+        self.view_by_name.get(view_name)
+    }
+
     pub fn get_index_by_name(&self, index_name: &str) -> Option<&Arc<IndexCatalog>> {
         self.index_by_name.get(index_name)
     }
@@ -281,6 +319,8 @@ impl From<&ProstSchema> for SchemaCatalog {
             index_by_name: HashMap::new(),
             index_by_id: HashMap::new(),
             indexes_by_table_id: HashMap::new(),
+            view_by_name: HashMap::new(),
+            view_by_id: HashMap::new(),
             system_table_by_name: HashMap::new(),
             owner: schema.owner,
         }
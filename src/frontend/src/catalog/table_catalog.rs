@@ -17,6 +17,7 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 use risingwave_common::catalog::{TableDesc, TableId};
 use risingwave_common::config::constant::hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND;
+use risingwave_common::types::Datum;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{ColumnIndex as ProstColumnIndex, Table as ProstTable};
 
@@ -96,6 +97,9 @@ pub struct TableCatalog {
 
     /// Definition of the materialized view.
     pub definition: String,
+
+    /// Version of the table schema, bumped on every `ALTER TABLE`.
+    pub version: u64,
 }
 
 impl TableCatalog {
@@ -142,9 +146,22 @@ impl TableCatalog {
                 .retention_seconds
                 .unwrap_or(TABLE_OPTION_DUMMY_RETENTION_SECOND),
             value_indices: self.value_indices.clone(),
+            appended_value_defaults: self.appended_value_defaults(),
         }
     }
 
+    /// Returns the defaults of the trailing value columns appended by `ALTER TABLE ADD COLUMN`.
+    pub fn appended_value_defaults(&self) -> Vec<Datum> {
+        let mut defaults = self
+            .value_indices
+            .iter()
+            .rev()
+            .map_while(|&i| self.columns[i].default_value.clone())
+            .collect_vec();
+        defaults.reverse();
+        defaults
+    }
+
     /// Get a reference to the table catalog's name.
     pub fn name(&self) -> &str {
         self.name.as_ref()
@@ -190,6 +207,7 @@ impl TableCatalog {
                 .map(|i| ProstColumnIndex { index: i as _ }),
             value_indices: self.value_indices.iter().map(|x| *x as _).collect(),
             definition: self.definition.clone(),
+            version: self.version,
         }
     }
 }
@@ -236,6 +254,7 @@ impl From<ProstTable> for TableCatalog {
             vnode_col_idx: tb.vnode_col_idx.map(|x| x.index as usize),
             value_indices: tb.value_indices.iter().map(|x| *x as _).collect(),
             definition: tb.definition.clone(),
+            version: tb.version,
         }
     }
 }
@@ -278,6 +297,7 @@ mod tests {
                 ProstColumnCatalog {
                     column_desc: Some((&row_id_column_desc(ColumnId::new(0))).into()),
                     is_hidden: true,
+                    default_value: vec![],
                 },
                 ProstColumnCatalog {
                     column_desc: Some(ProstColumnDesc::new_struct(
@@ -298,6 +318,7 @@ mod tests {
                         ],
                     )),
                     is_hidden: false,
+                    default_value: vec![],
                 },
             ],
             pk: vec![FieldOrder {
@@ -320,6 +341,7 @@ mod tests {
             vnode_col_idx: None,
             value_indices: vec![0],
             definition: "".into(),
+            version: 0,
         }
         .into();
 
@@ -358,7 +380,8 @@ mod tests {
                             ],
                             type_name: ".test.Country".to_string()
                         },
-                        is_hidden: false
+                        is_hidden: false,
+                        default_value: None
                     }
                 ],
                 stream_key: vec![0],
@@ -377,6 +400,7 @@ mod tests {
                 vnode_col_idx: None,
                 value_indices: vec![0],
                 definition: "".into(),
+                version: 0,
             }
         );
         assert_eq!(table, TableCatalog::from(table.to_prost(0, 0)));
@@ -89,6 +89,7 @@ pub trait ExprRewriter {
             function_type,
             partition_by,
             order_by,
+            frame,
         } = window_func;
         let args = args
             .into_iter()
@@ -100,6 +101,7 @@ pub trait ExprRewriter {
             function_type,
             partition_by,
             order_by,
+            frame,
         }
         .into()
     }
@@ -17,7 +17,7 @@ use fixedbitset::FixedBitSet;
 use paste::paste;
 use risingwave_common::array::{ListValue, Row};
 use risingwave_common::error::Result;
-use risingwave_common::types::{DataType, Datum, Scalar};
+use risingwave_common::types::{DataType, Datum, IntervalUnit, Scalar, ScalarImpl};
 use risingwave_expr::expr::{build_from_prost, AggKind};
 use risingwave_pb::expr::{ExprNode, ProjectSetSelectItem};
 
@@ -36,6 +36,7 @@ pub use order_by_expr::{OrderBy, OrderByExpr};
 mod expr_mutator;
 mod expr_rewriter;
 mod expr_visitor;
+mod session_timezone;
 mod type_inference;
 mod utils;
 
@@ -46,13 +47,16 @@ pub use input_ref::{input_ref_to_column_indices, InputRef, InputRefDisplay};
 pub use literal::Literal;
 pub use subquery::{Subquery, SubqueryKind};
 pub use table_function::{TableFunction, TableFunctionType};
-pub use window_function::{WindowFunction, WindowFunctionType};
+pub use window_function::{
+    FrameBound, FrameUnits, WindowFrame, WindowFunction, WindowFunctionType,
+};
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
 
 pub use expr_mutator::ExprMutator;
 pub use expr_rewriter::ExprRewriter;
 pub use expr_visitor::ExprVisitor;
+pub use session_timezone::SessionTimezone;
 pub use type_inference::{
     agg_func_sigs, align_types, cast_map_array, cast_ok, cast_sigs, func_sigs, infer_type,
     least_restrictive, AggFuncSig, CastContext, CastSig, FuncSign,
@@ -288,18 +292,13 @@ impl ExprImpl {
             }
 
             fn visit_subquery(&mut self, subquery: &Subquery) -> bool {
-                use crate::binder::BoundSetExpr;
-
                 let mut has = false;
                 self.depth += 1;
-                match &subquery.query.body {
-                    BoundSetExpr::Select(select) => {
-                        select.exprs().for_each(|expr| has |= self.visit_expr(expr))
-                    }
-                    BoundSetExpr::Values(values) => {
-                        values.exprs().for_each(|expr| has |= self.visit_expr(expr))
-                    }
-                }
+                subquery
+                    .query
+                    .body
+                    .exprs()
+                    .for_each(|expr| has |= self.visit_expr(expr));
                 self.depth -= 1;
 
                 has
@@ -328,19 +327,13 @@ impl ExprImpl {
             }
 
             fn visit_subquery(&mut self, subquery: &Subquery) -> bool {
-                use crate::binder::BoundSetExpr;
-                match &subquery.query.body {
-                    BoundSetExpr::Select(select) => select
-                        .exprs()
-                        .map(|expr| self.visit_expr(expr))
-                        .reduce(Self::merge)
-                        .unwrap_or_default(),
-                    BoundSetExpr::Values(values) => values
-                        .exprs()
-                        .map(|expr| self.visit_expr(expr))
-                        .reduce(Self::merge)
-                        .unwrap_or_default(),
-                }
+                subquery
+                    .query
+                    .body
+                    .exprs()
+                    .map(|expr| self.visit_expr(expr))
+                    .reduce(Self::merge)
+                    .unwrap_or_default()
             }
         }
 
@@ -373,17 +366,12 @@ impl ExprImpl {
             }
 
             fn visit_subquery(&mut self, subquery: &mut Subquery) {
-                use crate::binder::BoundSetExpr;
-
                 self.depth += 1;
-                match &mut subquery.query.body {
-                    BoundSetExpr::Select(select) => {
-                        select.exprs_mut().for_each(|expr| self.visit_expr(expr))
-                    }
-                    BoundSetExpr::Values(values) => {
-                        values.exprs_mut().for_each(|expr| self.visit_expr(expr))
-                    }
-                }
+                subquery
+                    .query
+                    .body
+                    .exprs_mut()
+                    .for_each(|expr| self.visit_expr(expr));
                 self.depth -= 1;
             }
         }
@@ -487,6 +475,98 @@ impl ExprImpl {
         }
     }
 
+    /// Returns `(x, cmp, y, offset)` if the expression compares two columns with at most one side
+    /// shifted by a constant interval, i.e. `x cmp y + offset` after normalization, with
+    /// `x.index < y.index`.
+    pub fn as_interval_comparison_cond(
+        &self,
+    ) -> Option<(InputRef, ExprType, InputRef, IntervalUnit)> {
+        fn reverse_comparison(comparison: ExprType) -> ExprType {
+            match comparison {
+                ExprType::LessThan => ExprType::GreaterThan,
+                ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+                ExprType::GreaterThan => ExprType::LessThan,
+                ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+                _ => unreachable!(),
+            }
+        }
+
+        /// Matches `input_ref`, `input_ref + interval` or `input_ref - interval`.
+        fn as_shifted_input_ref(expr: ExprImpl) -> Option<(InputRef, IntervalUnit)> {
+            let as_interval = |expr: &ExprImpl| match expr {
+                ExprImpl::Literal(literal) => match literal.get_data() {
+                    Some(ScalarImpl::Interval(interval)) => Some(*interval),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match expr {
+                ExprImpl::InputRef(x) => Some((*x, IntervalUnit::default())),
+                ExprImpl::FunctionCall(function_call)
+                    if matches!(
+                        function_call.get_expr_type(),
+                        ExprType::Add | ExprType::Subtract
+                    ) =>
+                {
+                    match function_call.decompose_as_binary() {
+                        (ExprType::Add, ExprImpl::InputRef(x), offset)
+                        | (ExprType::Add, offset, ExprImpl::InputRef(x)) => {
+                            Some((*x, as_interval(&offset)?))
+                        }
+                        (ExprType::Subtract, ExprImpl::InputRef(x), offset) => {
+                            Some((*x, as_interval(&offset)?.negative()))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+
+        if let ExprImpl::FunctionCall(function_call) = self {
+            match function_call.get_expr_type() {
+                ty @ (ExprType::LessThan
+                | ExprType::LessThanOrEqual
+                | ExprType::GreaterThan
+                | ExprType::GreaterThanOrEqual) => {
+                    let (_, op1, op2) = function_call.clone().decompose_as_binary();
+                    let (x, x_offset) = as_shifted_input_ref(op1)?;
+                    let (y, y_offset) = as_shifted_input_ref(op2)?;
+                    // `x + x_offset cmp y + y_offset`
+                    if x.index < y.index {
+                        Some((x, ty, y, y_offset - x_offset))
+                    } else {
+                        Some((y, reverse_comparison(ty), x, x_offset - y_offset))
+                    }
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the only input column if the expression is monotonically
+    /// non-decreasing on it, e.g. `col + const` or `tumble_start(col, const)`, so that the
+    /// watermark of the column can be derived to the one of the expression.
+    pub fn as_monotonic_input_ref(&self) -> Option<usize> {
+        match self {
+            ExprImpl::InputRef(input_ref) => Some(input_ref.index),
+            ExprImpl::FunctionCall(function_call) => {
+                match (function_call.get_expr_type(), function_call.inputs()) {
+                    (ExprType::Add | ExprType::Subtract | ExprType::TumbleStart, [x, c])
+                        if c.is_const() =>
+                    {
+                        x.as_monotonic_input_ref()
+                    }
+                    (ExprType::Add, [c, x]) if c.is_const() => x.as_monotonic_input_ref(),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn as_eq_const(&self) -> Option<(InputRef, ExprImpl)> {
         if let ExprImpl::FunctionCall(function_call) = self &&
         function_call.get_expr_type() == ExprType::Equal{
@@ -0,0 +1,115 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use super::{Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall};
+
+/// Inlines the session time zone into expressions whose results depend on it, i.e. casts between
+/// `varchar` and `timestamptz`, and `extract`/`date_trunc` on `timestamptz`. The time zone is
+/// appended as a constant argument, so that batch and streaming executors, which have no access to
+/// the session, evaluate them the same way as the frontend.
+pub struct SessionTimezone {
+    timezone: &'static str,
+}
+
+impl SessionTimezone {
+    pub fn new(timezone: &'static str) -> Self {
+        Self { timezone }
+    }
+
+    fn timezone_literal(&self) -> ExprImpl {
+        ExprImpl::literal_varchar(self.timezone.to_string())
+    }
+}
+
+impl ExprRewriter for SessionTimezone {
+    fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
+        let (func_type, inputs, ret) = func_call.decompose();
+        let mut inputs: Vec<ExprImpl> = inputs
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        match func_type {
+            // A plain cast already works in UTC, so it is left as is to keep plans unchanged.
+            ExprType::Cast if self.timezone != "UTC" => {
+                if let (DataType::Varchar, DataType::Timestampz)
+                | (DataType::Timestampz, DataType::Varchar) = (inputs[0].return_type(), &ret)
+                {
+                    inputs.push(self.timezone_literal());
+                    return FunctionCall::new_unchecked(ExprType::CastWithTimeZone, inputs, ret)
+                        .into();
+                }
+            }
+            ExprType::Extract | ExprType::DateTrunc
+                if inputs.len() == 2 && inputs[1].return_type() == DataType::Timestampz =>
+            {
+                inputs.push(self.timezone_literal());
+            }
+            _ => {}
+        }
+        FunctionCall::new_unchecked(func_type, inputs, ret).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cast(input: ExprImpl, target: DataType) -> ExprImpl {
+        input.cast_explicit(target).unwrap()
+    }
+
+    fn func_type(expr: &ExprImpl) -> ExprType {
+        expr.as_function_call().unwrap().get_expr_type()
+    }
+
+    #[test]
+    fn test_rewrite_cast() {
+        let literal = ExprImpl::literal_varchar("2022-10-01 12:00:00".to_string());
+
+        let mut utc = SessionTimezone::new("UTC");
+        let expr = utc.rewrite_expr(cast(literal.clone(), DataType::Timestampz));
+        assert_eq!(func_type(&expr), ExprType::Cast);
+
+        let mut shanghai = SessionTimezone::new("Asia/Shanghai");
+        let expr = shanghai.rewrite_expr(cast(literal, DataType::Timestampz));
+        assert_eq!(func_type(&expr), ExprType::CastWithTimeZone);
+        let expr = shanghai.rewrite_expr(cast(expr, DataType::Varchar));
+        assert_eq!(func_type(&expr), ExprType::CastWithTimeZone);
+        // Rewriting again is a no-op.
+        let expr = shanghai.rewrite_expr(expr);
+        assert_eq!(expr.as_function_call().unwrap().inputs().len(), 2);
+    }
+
+    #[test]
+    fn test_rewrite_extract() {
+        let time = cast(
+            ExprImpl::literal_varchar("2022-10-01 12:00:00+08:00".to_string()),
+            DataType::Timestampz,
+        );
+        let extract: ExprImpl = FunctionCall::new(
+            ExprType::Extract,
+            vec![ExprImpl::literal_varchar("HOUR".to_string()), time],
+        )
+        .unwrap()
+        .into();
+
+        let mut utc = SessionTimezone::new("UTC");
+        let expr = utc.rewrite_expr(extract.clone());
+        assert_eq!(expr.as_function_call().unwrap().inputs().len(), 3);
+        let expr = utc.rewrite_expr(expr);
+        assert_eq!(expr.as_function_call().unwrap().inputs().len(), 3);
+    }
+}
@@ -210,6 +210,8 @@ pub static CAST_MAP: LazyLock<CastMap> = LazyLock::new(|| {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Bytea,
+        T::Jsonb,
     ] {
         m.insert((t, T::Varchar), CastContext::Assign);
         m.insert((T::Varchar, t), CastContext::Explicit);
@@ -220,6 +222,9 @@ pub static CAST_MAP: LazyLock<CastMap> = LazyLock::new(|| {
     m.insert((T::Timestampz, T::Time), CastContext::Assign);
     m.insert((T::Boolean, T::Int32), CastContext::Explicit);
     m.insert((T::Int32, T::Boolean), CastContext::Explicit);
+    for t in [T::Boolean, T::Decimal, T::Float64] {
+        m.insert((T::Jsonb, t), CastContext::Explicit);
+    }
     m
 });
 
@@ -288,6 +293,8 @@ mod tests {
             T::Timestampz,
             T::Time,
             T::Interval,
+            T::Bytea,
+            T::Jsonb,
         ];
         all_types
             .iter()
@@ -313,57 +320,63 @@ mod tests {
         assert_eq!(
             actual,
             vec![
-                "             ", // bool
-                "  TTTTT      ",
-                "   TTTT      ",
-                "    TTT      ",
-                "     TT      ",
-                "      T      ",
-                "             ",
-                "             ", // varchar
-                "         TT  ",
-                "          T  ",
-                "             ",
-                "            T",
-                "             ",
+                "               ", // bool
+                "  TTTTT        ",
+                "   TTTT        ",
+                "    TTT        ",
+                "     TT        ",
+                "      T        ",
+                "               ",
+                "               ", // varchar
+                "         TT    ",
+                "          T    ",
+                "               ",
+                "            T  ",
+                "               ",
+                "               ", // bytea
+                "               ", // jsonb
             ]
         );
         let actual = gen_cast_table(CastContext::Assign);
         assert_eq!(
             actual,
             vec![
-                "       T     ", // bool
-                "  TTTTTT     ",
-                " T TTTTT     ",
-                " TT TTTT     ",
-                " TTT TTT     ",
-                " TTTT TT     ",
-                " TTTTT T     ",
-                "             ", // varchar
-                "       T TT  ",
-                "       TT TT ",
-                "       TTT T ",
-                "       T    T",
-                "       T   T ",
+                "       T       ", // bool
+                "  TTTTTT       ",
+                " T TTTTT       ",
+                " TT TTTT       ",
+                " TTT TTT       ",
+                " TTTT TT       ",
+                " TTTTT T       ",
+                "               ", // varchar
+                "       T TT    ",
+                "       TT TT   ",
+                "       TTT T   ",
+                "       T    T  ",
+                "       T   T   ",
+                "       T       ", // bytea
+                "       T       ", // jsonb
             ]
         );
         let actual = gen_cast_table(CastContext::Explicit);
         assert_eq!(
             actual,
             vec![
-                "  T    T     ", // bool
-                "  TTTTTT     ",
-                "TT TTTTT     ",
-                " TT TTTT     ",
-                " TTT TTT     ",
-                " TTTT TT     ",
-                " TTTTT T     ",
-                "TTTTTTT TTTTT", // varchar
-                "       T TT  ",
-                "       TT TT ",
-                "       TTT T ",
-                "       T    T",
-                "       T   T ",
+                "  T    T       ", // bool
+                "  TTTTTT       ",
+                "TT TTTTT       ",
+                " TT TTTT       ",
+                " TTT TTT       ",
+                " TTTT TT       ",
+                " TTTTT T       ",
+                "TTTTTTT TTTTTTT", // varchar
+                "       T TT    ",
+                "       TT TT   ",
+                "       TTT T   ",
+                "       T    T  ",
+                "       T   T   ",
+                "       T       ", // bytea
+                "T   T TT       ", // jsonb
             ]
         );
     }
@@ -684,6 +684,8 @@ fn build_type_derive_map() -> FuncSigMap {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Bytea,
+        T::Jsonb,
     ];
     let num_types = [
         T::Int16,
@@ -724,7 +726,7 @@ fn build_type_derive_map() -> FuncSigMap {
     build_binary_cmp_funcs(&mut map, cmp_exprs, &[T::Date, T::Timestamp, T::Timestampz]);
     build_binary_cmp_funcs(&mut map, cmp_exprs, &[T::Time, T::Interval]);
     for e in cmp_exprs {
-        for t in [T::Boolean, T::Varchar] {
+        for t in [T::Boolean, T::Varchar, T::Bytea] {
             map.insert(*e, vec![t, t], T::Boolean);
         }
     }
@@ -804,6 +806,19 @@ fn build_type_derive_map() -> FuncSigMap {
     for t in [T::Timestampz, T::Timestamp, T::Time, T::Date] {
         map.insert(E::Extract, vec![T::Varchar, t], T::Decimal);
     }
+    // The time zone argument is appended when binding, see `SessionTimezone`.
+    map.insert(
+        E::Extract,
+        vec![T::Varchar, T::Timestampz, T::Varchar],
+        T::Decimal,
+    );
+    map.insert(E::DateTrunc, vec![T::Varchar, T::Timestamp], T::Timestamp);
+    map.insert(E::DateTrunc, vec![T::Varchar, T::Timestampz], T::Timestampz);
+    map.insert(
+        E::DateTrunc,
+        vec![T::Varchar, T::Timestampz, T::Varchar],
+        T::Timestampz,
+    );
     for t in [T::Timestamp, T::Date] {
         map.insert(E::TumbleStart, vec![t, T::Interval], T::Timestamp);
     }
@@ -857,6 +872,15 @@ fn build_type_derive_map() -> FuncSigMap {
     // TODO: Support more `to_char` types.
     map.insert(E::ToChar, vec![T::Timestamp, T::Varchar], T::Varchar);
 
+    // jsonb functions
+    for t in [T::Varchar, T::Int32] {
+        map.insert(E::JsonbAccessInner, vec![T::Jsonb, t], T::Jsonb);
+        map.insert(E::JsonbAccessStr, vec![T::Jsonb, t], T::Varchar);
+    }
+    map.insert(E::JsonbContains, vec![T::Jsonb, T::Jsonb], T::Boolean);
+    map.insert(E::JsonbTypeof, vec![T::Jsonb], T::Varchar);
+    map.insert(E::JsonbArrayLength, vec![T::Jsonb], T::Int32);
+
     map
 }
 
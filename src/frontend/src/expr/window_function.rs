@@ -18,8 +18,13 @@ use itertools::Itertools;
 use parse_display::Display;
 use risingwave_common::error::ErrorCode;
 use risingwave_common::types::DataType;
+use risingwave_expr::expr::AggKind;
+use risingwave_pb::expr::window_frame::bound::Type as ProstFrameBoundType;
+use risingwave_pb::expr::window_frame::{Bound as ProstFrameBound, Type as ProstFrameType};
+use risingwave_pb::expr::window_function::Type as ProstWindowFunctionType;
+use risingwave_pb::expr::WindowFrame as ProstWindowFrame;
 
-use super::{Expr, ExprImpl, OrderBy, Result};
+use super::{AggCall, Expr, ExprImpl, OrderBy, Result};
 
 /// A window function performs a calculation across a set of table rows that are somehow related to
 /// the current row, according to the window spec `OVER (PARTITION BY .. ORDER BY ..)`.
@@ -34,6 +39,7 @@ pub struct WindowFunction {
     pub function_type: WindowFunctionType,
     pub partition_by: Vec<ExprImpl>,
     pub order_by: OrderBy,
+    pub frame: WindowFrame,
 }
 
 #[derive(Debug, Display, Copy, Clone, PartialEq, Eq, Hash)]
@@ -42,6 +48,13 @@ pub enum WindowFunctionType {
     RowNumber,
     Rank,
     DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    /// An aggregate function used as a window function, e.g. `SUM(..) OVER (..)`.
+    #[display("{0}")]
+    Aggregate(AggKind),
 }
 
 impl WindowFunctionType {
@@ -53,6 +66,19 @@ impl WindowFunctionType {
                 | WindowFunctionType::DenseRank
         )
     }
+
+    pub fn to_protobuf(self) -> ProstWindowFunctionType {
+        match self {
+            WindowFunctionType::RowNumber => ProstWindowFunctionType::RowNumber,
+            WindowFunctionType::Rank => ProstWindowFunctionType::Rank,
+            WindowFunctionType::DenseRank => ProstWindowFunctionType::DenseRank,
+            WindowFunctionType::Lag => ProstWindowFunctionType::Lag,
+            WindowFunctionType::Lead => ProstWindowFunctionType::Lead,
+            WindowFunctionType::FirstValue => ProstWindowFunctionType::FirstValue,
+            WindowFunctionType::LastValue => ProstWindowFunctionType::LastValue,
+            WindowFunctionType::Aggregate(_) => ProstWindowFunctionType::Aggregate,
+        }
+    }
 }
 
 impl FromStr for WindowFunctionType {
@@ -63,14 +89,151 @@ impl FromStr for WindowFunctionType {
             "row_number" => Ok(WindowFunctionType::RowNumber),
             "rank" => Ok(WindowFunctionType::Rank),
             "dense_rank" => Ok(WindowFunctionType::DenseRank),
+            "lag" => Ok(WindowFunctionType::Lag),
+            "lead" => Ok(WindowFunctionType::Lead),
+            "first_value" => Ok(WindowFunctionType::FirstValue),
+            "last_value" => Ok(WindowFunctionType::LastValue),
             _ => Err(ErrorCode::NotImplemented(
-                format!("unknown table function kind: {s}"),
+                format!("unknown window function kind: {s}"),
                 None.into(),
             )),
         }
     }
 }
 
+/// Unit of the bounds of a [`WindowFrame`].
+#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, Hash)]
+#[display(style = "UPPERCASE")]
+pub enum FrameUnits {
+    Rows,
+    /// Only `UNBOUNDED` and `CURRENT ROW` bounds are supported in `RANGE` mode.
+    Range,
+}
+
+#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FrameBound {
+    #[display("UNBOUNDED PRECEDING")]
+    UnboundedPreceding,
+    #[display("{0} PRECEDING")]
+    Preceding(u64),
+    #[display("CURRENT ROW")]
+    CurrentRow,
+    #[display("{0} FOLLOWING")]
+    Following(u64),
+    #[display("UNBOUNDED FOLLOWING")]
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    /// Position of the bound relative to the current row, used to validate the frame.
+    fn position(&self) -> i8 {
+        match self {
+            FrameBound::UnboundedPreceding => -2,
+            FrameBound::Preceding(_) => -1,
+            FrameBound::CurrentRow => 0,
+            FrameBound::Following(_) => 1,
+            FrameBound::UnboundedFollowing => 2,
+        }
+    }
+
+    fn to_protobuf(self) -> ProstFrameBound {
+        let (r#type, offset) = match self {
+            FrameBound::UnboundedPreceding => (ProstFrameBoundType::UnboundedPreceding, 0),
+            FrameBound::Preceding(n) => (ProstFrameBoundType::Preceding, n),
+            FrameBound::CurrentRow => (ProstFrameBoundType::CurrentRow, 0),
+            FrameBound::Following(n) => (ProstFrameBoundType::Following, n),
+            FrameBound::UnboundedFollowing => (ProstFrameBoundType::UnboundedFollowing, 0),
+        };
+        ProstFrameBound {
+            r#type: r#type as i32,
+            offset,
+        }
+    }
+}
+
+/// The frame of a window function, i.e. `{ ROWS | RANGE } BETWEEN <start> AND <end>`.
+#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, Hash)]
+#[display("{units} BETWEEN {start} AND {end}")]
+pub struct WindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl Default for WindowFrame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, which is the whole partition when
+    /// there's no `ORDER BY`, or all rows up to the current row's last peer otherwise.
+    fn default() -> Self {
+        Self {
+            units: FrameUnits::Range,
+            start: FrameBound::UnboundedPreceding,
+            end: FrameBound::CurrentRow,
+        }
+    }
+}
+
+impl WindowFrame {
+    pub fn new(units: FrameUnits, start: FrameBound, end: FrameBound) -> Result<Self> {
+        if start == FrameBound::UnboundedFollowing {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "frame start cannot be UNBOUNDED FOLLOWING".to_string(),
+            )
+            .into());
+        }
+        if end == FrameBound::UnboundedPreceding {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "frame end cannot be UNBOUNDED PRECEDING".to_string(),
+            )
+            .into());
+        }
+        if start.position() > end.position() {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "frame starting from {start} cannot end with {end}"
+            ))
+            .into());
+        }
+        if units == FrameUnits::Range
+            && [start, end]
+                .iter()
+                .any(|b| matches!(b, FrameBound::Preceding(_) | FrameBound::Following(_)))
+        {
+            return Err(ErrorCode::NotImplemented(
+                "RANGE frame with offset PRECEDING or FOLLOWING".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+        Ok(Self { units, start, end })
+    }
+
+    /// The single-row frame at `offset` rows before (or after, if negative) the current row,
+    /// which is how `LAG` and `LEAD` are evaluated.
+    pub fn single_row(offset: i64) -> Self {
+        let bound = match offset {
+            0 => FrameBound::CurrentRow,
+            n if n > 0 => FrameBound::Preceding(n as u64),
+            n => FrameBound::Following(n.unsigned_abs()),
+        };
+        Self {
+            units: FrameUnits::Rows,
+            start: bound,
+            end: bound,
+        }
+    }
+
+    pub fn to_protobuf(&self) -> ProstWindowFrame {
+        let r#type = match self.units {
+            FrameUnits::Rows => ProstFrameType::Rows,
+            FrameUnits::Range => ProstFrameType::Range,
+        };
+        ProstWindowFrame {
+            r#type: r#type as i32,
+            start: Some(self.start.to_protobuf()),
+            end: Some(self.end.to_protobuf()),
+        }
+    }
+}
+
 impl WindowFunction {
     /// Create a `WindowFunction` expr with the return type inferred from `func_type` and types of
     /// `inputs`.
@@ -79,20 +242,45 @@ impl WindowFunction {
         partition_by: Vec<ExprImpl>,
         order_by: OrderBy,
         args: Vec<ExprImpl>,
+        frame: WindowFrame,
     ) -> Result<Self> {
-        if !args.is_empty() {
-            return Err(ErrorCode::BindError(format!(
-                "the length of args of {function_type} function should be 0"
-            ))
-            .into());
-        }
+        let return_type = match function_type {
+            WindowFunctionType::RowNumber
+            | WindowFunctionType::Rank
+            | WindowFunctionType::DenseRank => {
+                if !args.is_empty() {
+                    return Err(ErrorCode::BindError(format!(
+                        "the length of args of {function_type} function should be 0"
+                    ))
+                    .into());
+                }
+                DataType::Int64
+            }
+            WindowFunctionType::Lag
+            | WindowFunctionType::Lead
+            | WindowFunctionType::FirstValue
+            | WindowFunctionType::LastValue => {
+                if args.len() != 1 {
+                    return Err(ErrorCode::BindError(format!(
+                        "the length of args of {function_type} function should be 1"
+                    ))
+                    .into());
+                }
+                args[0].return_type()
+            }
+            WindowFunctionType::Aggregate(agg_kind) => {
+                let data_types = args.iter().map(ExprImpl::return_type).collect_vec();
+                AggCall::infer_return_type(&agg_kind, &data_types)?
+            }
+        };
 
         Ok(Self {
             args,
-            return_type: DataType::Int64,
+            return_type,
             function_type,
             partition_by,
             order_by,
+            frame,
         })
     }
 }
@@ -106,9 +294,15 @@ impl std::fmt::Debug for WindowFunction {
                 .field("args", &self.args)
                 .field("partition_by", &self.partition_by)
                 .field("order_by", &format_args!("{}", self.order_by))
+                .field("frame", &format_args!("{}", self.frame))
                 .finish()
         } else {
-            write!(f, "{}() OVER(", self.function_type)?;
+            write!(
+                f,
+                "{}({:?}) OVER(",
+                self.function_type,
+                self.args.iter().format(", ")
+            )?;
 
             let mut delim = "";
             if !self.partition_by.is_empty() {
@@ -120,8 +314,12 @@ impl std::fmt::Debug for WindowFunction {
                 )?;
             }
             if !self.order_by.sort_exprs.is_empty() {
+                delim = " ";
                 write!(f, "{delim}{}", self.order_by)?;
             }
+            if self.frame != WindowFrame::default() {
+                write!(f, "{delim}{}", self.frame)?;
+            }
             f.write_str(")")?;
 
             Ok(())
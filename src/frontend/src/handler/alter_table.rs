@@ -0,0 +1,383 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_pb::catalog::source::Info;
+use risingwave_pb::catalog::{Source as ProstSource, StreamSourceInfo, TableSourceInfo};
+use risingwave_sqlparser::ast::{AlterTableOperation, ColumnDef, ColumnOption, Ident, ObjectName};
+
+use super::create_table::bind_sql_columns;
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::source_catalog::{SourceCatalog, SourceCatalogInfo};
+use crate::catalog::table_catalog::TableCatalog;
+use crate::catalog::{dropped_column_name, ColumnId};
+use crate::session::{OptimizerContext, SessionImpl};
+
+pub async fn handle_alter_table(
+    context: OptimizerContext,
+    table_name: ObjectName,
+    operation: AlterTableOperation,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let (mut source, mut table) = resolve_table_with_source(&session, table_name)?;
+
+    match operation {
+        AlterTableOperation::AddColumn { column_def } => {
+            add_column(&session, &mut source, &mut table, column_def)?;
+            alter_table(&session, &source, &table).await?;
+        }
+        AlterTableOperation::DropColumn {
+            column_name,
+            if_exists,
+            cascade: _,
+        } => {
+            if !drop_column(&mut source, &mut table, &column_name)? {
+                return if if_exists {
+                    Ok(RwPgResponse::empty_result_with_notice(
+                        StatementType::ALTER_TABLE,
+                        format!(
+                            "column \"{}\" does not exist, skipping",
+                            column_name.real_value()
+                        ),
+                    ))
+                } else {
+                    Err(ErrorCode::ItemNotFound(format!(
+                        "column \"{}\" does not exist",
+                        column_name.real_value()
+                    ))
+                    .into())
+                };
+            }
+            alter_table(&session, &source, &table).await?;
+        }
+        _ => {
+            return Err(ErrorCode::NotImplemented(
+                format!("ALTER TABLE {}", operation),
+                None.into(),
+            )
+            .into())
+        }
+    }
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_TABLE))
+}
+
+/// Resolves the table or materialized source to alter and its associated source, checking the
+/// privilege.
+fn resolve_table_with_source(
+    session: &SessionImpl,
+    table_name: ObjectName,
+) -> Result<(SourceCatalog, TableCatalog)> {
+    let db_name = session.database();
+    let (schema_name, table_name) = Binder::resolve_table_or_source_name(db_name, table_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let schema_path = match schema_name.as_deref() {
+        Some(schema_name) => SchemaPath::Name(schema_name),
+        None => SchemaPath::Path(&search_path, user_name),
+    };
+
+    let reader = session.env().catalog_reader().read_guard();
+    let (table, schema_name) = reader.get_table_by_name(db_name, schema_path, &table_name)?;
+
+    let schema_owner = reader.get_schema_by_name(db_name, schema_name)?.owner();
+    if session.user_id() != table.owner
+        && session.user_id() != schema_owner
+        && !check_super_user(session)
+    {
+        return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+    }
+
+    if table.is_index || table.associated_source_id().is_none() {
+        return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+            "\"{}\" is not a table",
+            table_name
+        ))));
+    }
+
+    let (source, _) =
+        reader.get_source_by_name(db_name, SchemaPath::Name(schema_name), &table_name)?;
+
+    Ok((source.as_ref().clone(), table.as_ref().clone()))
+}
+
+/// Appends the column to both the source and the table. The `DEFAULT` of the column is stored in
+/// the catalog, which is used for both the rows written before and the insertions omitting it.
+fn add_column(
+    session: &SessionImpl,
+    source: &mut SourceCatalog,
+    table: &mut TableCatalog,
+    mut column_def: ColumnDef,
+) -> Result<()> {
+    if let SourceCatalogInfo::StreamSource(info) = &source.info {
+        // The columns are derived from the schema, which can't be changed.
+        if !info.row_schema_location.is_empty() {
+            return Err(ErrorCode::NotImplemented(
+                format!(
+                    "add a column to a materialized source with row format {:?}",
+                    info.get_row_format().unwrap()
+                ),
+                None.into(),
+            )
+            .into());
+        }
+    }
+
+    let mut default = None;
+    column_def
+        .options
+        .retain(|option_def| match &option_def.option {
+            ColumnOption::Default(expr) => {
+                default = Some(expr.clone());
+                false
+            }
+            _ => true,
+        });
+
+    let (mut column_descs, pk_column_id) = bind_sql_columns(vec![column_def])?;
+    if pk_column_id.is_some() {
+        return Err(
+            ErrorCode::NotImplemented("add a primary key column".to_owned(), None.into()).into(),
+        );
+    }
+    let mut column_desc = column_descs.pop().unwrap();
+
+    if table
+        .columns()
+        .iter()
+        .any(|c| !c.is_hidden && c.name() == column_desc.name)
+    {
+        return Err(ErrorCode::BindError(format!(
+            "column \"{}\" of relation \"{}\" already exists",
+            column_desc.name, table.name
+        ))
+        .into());
+    }
+
+    let default_value = match default {
+        // Rows of a materialized source come from the connector, which won't fill the default.
+        Some(expr) if source.is_stream() => {
+            return Err(ErrorCode::NotImplemented(
+                format!("DEFAULT {} on materialized source", expr),
+                None.into(),
+            )
+            .into())
+        }
+        Some(expr) => Binder::new(session).bind_column_default(expr, &column_desc.data_type)?,
+        None => None,
+    };
+
+    column_desc.column_id = table
+        .columns()
+        .iter()
+        .chain(source.columns.iter())
+        .map(|c| c.column_id())
+        .max()
+        .map_or(ColumnId::new(0), |id| ColumnId::new(id.get_id() + 1));
+
+    let column = ColumnCatalog {
+        column_desc,
+        is_hidden: false,
+        default_value: Some(default_value),
+    };
+    table.value_indices.push(table.columns.len());
+    table.columns.push(column.clone());
+    source.columns.push(column);
+
+    Ok(())
+}
+
+/// Hides the column in both the table source and the table. The column is still stored
+/// physically, so it's renamed to a reserved name. Returns `false` if the column is not found.
+fn drop_column(
+    source: &mut SourceCatalog,
+    table: &mut TableCatalog,
+    column_name: &Ident,
+) -> Result<bool> {
+    let column_name = column_name.real_value();
+    let Some(index) = table
+        .columns()
+        .iter()
+        .position(|c| !c.is_hidden && c.name() == column_name) else {
+        return Ok(false);
+    };
+
+    if table.pk().iter().any(|order| order.index == index) {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "cannot drop primary key column \"{}\"",
+            column_name
+        ))
+        .into());
+    }
+    if table.distribution_key().contains(&index) {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "cannot drop distribution key column \"{}\"",
+            column_name
+        ))
+        .into());
+    }
+
+    let column_id = table.columns[index].column_id();
+    for column in table.columns.iter_mut().chain(source.columns.iter_mut()) {
+        if column.column_id() == column_id {
+            column.is_hidden = true;
+            column.column_desc.name = dropped_column_name(column_id);
+        }
+    }
+
+    Ok(true)
+}
+
+async fn alter_table(
+    session: &SessionImpl,
+    source: &SourceCatalog,
+    table: &TableCatalog,
+) -> Result<()> {
+    let (schema_id, database_id) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let db = reader.get_database_by_name(session.database())?;
+        let schema = db
+            .iter_schemas()
+            .find(|schema| schema.get_table_by_id(&table.id()).is_some())
+            .ok_or_else(|| ErrorCode::ItemNotFound(format!("table \"{}\"", table.name)))?;
+        (schema.id(), db.id())
+    };
+
+    let columns = source.columns.iter().map(|c| c.to_protobuf()).collect();
+    let info = match &source.info {
+        SourceCatalogInfo::TableSource(info) => Info::TableSource(TableSourceInfo {
+            columns,
+            ..info.clone()
+        }),
+        SourceCatalogInfo::StreamSource(info) => Info::StreamSource(StreamSourceInfo {
+            columns,
+            ..info.clone()
+        }),
+    };
+    let source = ProstSource {
+        id: source.id,
+        schema_id,
+        database_id,
+        name: source.name.clone(),
+        info: Some(info),
+        owner: source.owner,
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer
+        .alter_table(source, table.to_prost(schema_id, database_id))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::catalog::{dropped_column_name, ColumnId};
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_alter_table_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t add column v3 bigint;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t add column v4 int default 1 + 1;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t drop column v2;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t drop column if exists v2;")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("alter table t drop column v2;")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("alter table t add column v1 int;")
+            .await
+            .is_err());
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+        let (table, _) = catalog_reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, schema_path, "t")
+            .unwrap();
+        assert_eq!(table.version, 3);
+
+        let columns = table
+            .columns()
+            .iter()
+            .map(|c| {
+                (
+                    c.name().to_string(),
+                    c.is_hidden,
+                    c.data_type().clone(),
+                    c.default_value.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec![
+                ("v1".to_string(), false, DataType::Int32, None),
+                (
+                    dropped_column_name(ColumnId::new(1)),
+                    true,
+                    DataType::Varchar,
+                    None
+                ),
+                ("_row_id".to_string(), true, DataType::Int64, None),
+                ("v3".to_string(), false, DataType::Int64, Some(None)),
+                (
+                    "v4".to_string(),
+                    false,
+                    DataType::Int32,
+                    Some(Some(ScalarImpl::Int32(2)))
+                ),
+            ]
+        );
+        assert_eq!(
+            table.table_desc().appended_value_defaults,
+            vec![None, Some(ScalarImpl::Int32(2))]
+        );
+
+        let (source, _) = catalog_reader
+            .get_source_by_name(DEFAULT_DATABASE_NAME, schema_path, "t")
+            .unwrap();
+        assert_eq!(source.columns.len(), 5);
+        assert_eq!(source.columns[3].column_id(), ColumnId::new(3));
+        assert_eq!(source.columns[4].column_id(), ColumnId::new(4));
+    }
+}
@@ -0,0 +1,696 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use futures::stream::{self, StreamExt};
+use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::pg_server::CopyDataStream;
+use pgwire::types::Row;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_sqlparser::ast::{
+    CopyOption, CopySource, Distinct, Expr, Ident, ObjectName, Query, Select, SelectItem, SetExpr,
+    Statement, TableFactor, TableWithJoins, Value, Values,
+};
+
+use super::{query, PgResponseStream, RwPgResponse};
+use crate::binder::Binder;
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::utils::WithOptions;
+
+/// The maximum number of rows inserted at once by `COPY FROM STDIN`.
+const COPY_BATCH_SIZE: usize = 1024;
+
+/// The format of the data of a `COPY` statement, i.e. PostgreSQL's `text` or `csv` format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CopyFormat {
+    csv: bool,
+    delimiter: char,
+    null: String,
+    header: bool,
+}
+
+impl CopyFormat {
+    fn from_options(options: &[CopyOption]) -> Result<Self> {
+        let mut csv = false;
+        let mut delimiter = None;
+        let mut null = None;
+        let mut header = false;
+        for option in options {
+            match option {
+                CopyOption::Format(format) => match format.value.to_lowercase().as_str() {
+                    "text" => csv = false,
+                    "csv" => csv = true,
+                    "binary" => {
+                        return Err(ErrorCode::NotImplemented(
+                            "COPY with binary format".into(),
+                            None.into(),
+                        )
+                        .into())
+                    }
+                    _ => {
+                        return Err(ErrorCode::InvalidInputSyntax(format!(
+                            "COPY format \"{}\" not recognized",
+                            format.value
+                        ))
+                        .into())
+                    }
+                },
+                CopyOption::Delimiter(c) => delimiter = Some(*c),
+                CopyOption::Null(s) => null = Some(s.clone()),
+                CopyOption::Header(h) => header = *h,
+            }
+        }
+        let delimiter = delimiter.unwrap_or(if csv { ',' } else { '\t' });
+        if delimiter == '\n' || delimiter == '\r' || (!csv && delimiter == '\\') {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "COPY delimiter cannot be newline, carriage return or backslash".into(),
+            )
+            .into());
+        }
+        if csv && delimiter == '"' {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "COPY delimiter and quote must be different".into(),
+            )
+            .into());
+        }
+        let null = null.unwrap_or_else(|| if csv { "" } else { "\\N" }.to_string());
+        Ok(Self {
+            csv,
+            delimiter,
+            null,
+            header,
+        })
+    }
+
+    /// Encodes a row of values in text representation into a line, including the trailing
+    /// newline.
+    fn encode_row<'a>(&self, values: impl IntoIterator<Item = Option<&'a [u8]>>) -> Vec<u8> {
+        let mut line = String::new();
+        for (i, value) in values.into_iter().enumerate() {
+            if i > 0 {
+                line.push(self.delimiter);
+            }
+            match value {
+                None => line.push_str(&self.null),
+                Some(value) => self.encode_value(&String::from_utf8_lossy(value), &mut line),
+            }
+        }
+        line.push('\n');
+        line.into_bytes()
+    }
+
+    fn encode_value(&self, value: &str, line: &mut String) {
+        if self.csv {
+            let need_quote = value == self.null
+                || value.starts_with("\\.")
+                || value
+                    .chars()
+                    .any(|c| c == self.delimiter || c == '"' || c == '\n' || c == '\r');
+            if need_quote {
+                line.push('"');
+                line.push_str(&value.replace('"', "\"\""));
+                line.push('"');
+            } else {
+                line.push_str(value);
+            }
+        } else {
+            for c in value.chars() {
+                match c {
+                    '\\' => line.push_str("\\\\"),
+                    '\n' => line.push_str("\\n"),
+                    '\r' => line.push_str("\\r"),
+                    '\t' => line.push_str("\\t"),
+                    c if c == self.delimiter => {
+                        line.push('\\');
+                        line.push(c);
+                    }
+                    c => line.push(c),
+                }
+            }
+        }
+    }
+
+    /// Decodes the rows in `data`, which ends with a newline unless it is the end of the input.
+    /// Returns the rows, the number of bytes consumed and whether the end-of-data marker `\.` is
+    /// reached.
+    fn decode_text(&self, data: &str) -> Result<(Vec<Vec<Option<String>>>, usize, bool)> {
+        let mut rows = vec![];
+        for line in data.lines() {
+            if line == "\\." {
+                return Ok((rows, data.len(), true));
+            }
+            let mut row = vec![];
+            // The raw field is kept to be compared with the null string, which is not escaped.
+            let mut raw = String::new();
+            let mut field = String::new();
+            let mut chars = line.chars();
+            while let Some(c) = chars.next() {
+                if c == self.delimiter {
+                    row.push(self.text_field(&raw, std::mem::take(&mut field)));
+                    raw.clear();
+                    continue;
+                }
+                raw.push(c);
+                if c != '\\' {
+                    field.push(c);
+                    continue;
+                }
+                let Some(escaped) = chars.next() else {
+                    return Err(ErrorCode::InvalidInputSyntax(
+                        "unterminated escape sequence in COPY data".into(),
+                    )
+                    .into());
+                };
+                raw.push(escaped);
+                field.push(match escaped {
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'v' => '\u{b}',
+                    c => c,
+                });
+            }
+            row.push(self.text_field(&raw, field));
+            rows.push(row);
+        }
+        Ok((rows, data.len(), false))
+    }
+
+    fn text_field(&self, raw: &str, field: String) -> Option<String> {
+        if raw == self.null {
+            None
+        } else {
+            Some(field)
+        }
+    }
+
+    /// Like [`Self::decode_text`], but a quoted field may span lines. Unless `eof`, the last row is
+    /// left unconsumed if it is incomplete.
+    fn decode_csv(&self, data: &str, eof: bool) -> Result<(Vec<Vec<Option<String>>>, usize, bool)> {
+        let mut rows = vec![];
+        let mut row = vec![];
+        let mut field = String::new();
+        // Whether any part of the current field is quoted. A quoted field never equals null.
+        let mut quoted = false;
+        let mut in_quote = false;
+        let mut at_line_start = true;
+        // The position where the current row starts.
+        let mut row_start = 0;
+        let mut chars = data.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let next = chars.peek().map(|&(_, c)| c);
+            if in_quote {
+                if c == '"' {
+                    if next == Some('"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        in_quote = false;
+                    }
+                } else {
+                    field.push(c);
+                }
+                continue;
+            }
+            if at_line_start && c == '\\' && next == Some('.') {
+                let mut rest = chars.clone();
+                rest.next();
+                if matches!(rest.peek(), None | Some((_, '\n')) | Some((_, '\r'))) {
+                    return Ok((rows, data.len(), true));
+                }
+            }
+            at_line_start = false;
+            match c {
+                '"' => {
+                    quoted = true;
+                    in_quote = true;
+                }
+                '\r' | '\n' => {
+                    row_start = i + 1;
+                    if c == '\r' && next == Some('\n') {
+                        chars.next();
+                        row_start += 1;
+                    }
+                    row.push(self.csv_field(std::mem::take(&mut field), quoted));
+                    rows.push(std::mem::take(&mut row));
+                    quoted = false;
+                    at_line_start = true;
+                }
+                c if c == self.delimiter => {
+                    row.push(self.csv_field(std::mem::take(&mut field), quoted));
+                    quoted = false;
+                }
+                c => field.push(c),
+            }
+        }
+        if !eof {
+            return Ok((rows, row_start, false));
+        }
+        if in_quote {
+            return Err(
+                ErrorCode::InvalidInputSyntax("unterminated CSV quoted field".into()).into(),
+            );
+        }
+        if !at_line_start {
+            row.push(self.csv_field(field, quoted));
+            rows.push(row);
+        }
+        Ok((rows, data.len(), false))
+    }
+
+    fn csv_field(&self, field: String, quoted: bool) -> Option<String> {
+        if !quoted && field == self.null {
+            None
+        } else {
+            Some(field)
+        }
+    }
+}
+
+/// Decodes the data of `COPY FROM STDIN` into rows as it arrives from the client, in pieces that
+/// don't necessarily end at row boundaries.
+struct CopyDecoder {
+    format: CopyFormat,
+    /// The data received but not decoded yet.
+    buffer: Vec<u8>,
+    /// Whether the header line is yet to be skipped.
+    skip_header: bool,
+    /// Whether the end-of-data marker is reached, after which the data is ignored.
+    finished: bool,
+}
+
+impl CopyDecoder {
+    fn new(format: CopyFormat) -> Self {
+        Self {
+            skip_header: format.header,
+            format,
+            buffer: vec![],
+            finished: false,
+        }
+    }
+
+    /// Appends `data` and decodes the complete rows received so far. If `eof`, the rest of the data
+    /// is decoded as well.
+    fn decode(&mut self, data: &[u8], eof: bool) -> Result<Vec<Vec<Option<String>>>> {
+        if self.finished {
+            return Ok(vec![]);
+        }
+        self.buffer.extend_from_slice(data);
+        // A row never ends in the middle of a line, so only complete lines are decoded. This also
+        // keeps multi-byte characters that are split across pieces in the buffer.
+        let end = match eof {
+            true => self.buffer.len(),
+            false => match self.buffer.iter().rposition(|&b| b == b'\n') {
+                Some(pos) => pos + 1,
+                None => return Ok(vec![]),
+            },
+        };
+        let data = std::str::from_utf8(&self.buffer[..end]).map_err(|e| {
+            ErrorCode::InvalidInputSyntax(format!("invalid UTF-8 in COPY data: {}", e))
+        })?;
+        let (mut rows, consumed, finished) = match self.format.csv {
+            true => self.format.decode_csv(data, eof)?,
+            false => self.format.decode_text(data)?,
+        };
+        self.buffer.drain(..consumed);
+        self.finished = finished;
+        if self.skip_header && !rows.is_empty() {
+            rows.remove(0);
+            self.skip_header = false;
+        }
+        Ok(rows)
+    }
+}
+
+pub async fn handle_copy(context: OptimizerContext, stmt: Statement) -> Result<RwPgResponse> {
+    let Statement::Copy {
+        source,
+        to,
+        options,
+        values,
+        ..
+    } = stmt else {
+        unreachable!("handle_copy called with a non-COPY statement");
+    };
+    let format = CopyFormat::from_options(&options)?;
+
+    if to {
+        return handle_copy_to(context, source, format).await;
+    }
+
+    let CopySource::Table {
+        table_name,
+        columns,
+    } = source else {
+        unreachable!("COPY FROM a query is rejected by the parser");
+    };
+    let session = context.session_ctx.clone();
+    let num_columns = bind_copy_columns(&session, table_name.clone(), &columns)?;
+
+    if values.is_empty() {
+        // Ask the client for the data, which is then passed to `handle_copy_in`.
+        return Ok(PgResponse::new_for_copy_in(num_columns));
+    }
+
+    // Data inlined after the statement, as dumped by `pg_dump`.
+    let rows = values.chunks(num_columns).map(|row| row.to_vec()).collect();
+    let rows_cnt = insert_rows(context, table_name, columns, num_columns, rows).await?;
+    Ok(PgResponse::new_for_stream(
+        StatementType::COPY,
+        Some(rows_cnt),
+        PgResponseStream::Rows(stream::empty().boxed()),
+        vec![],
+    ))
+}
+
+/// Inserts the `data` of a `COPY FROM STDIN` statement sent by the client. The data is decoded as
+/// it arrives, and the rows are inserted in batches of [`COPY_BATCH_SIZE`], so that the memory
+/// usage doesn't grow with the size of the data. Note that the batches inserted before an error
+/// are not rolled back.
+pub async fn handle_copy_in(
+    session: Arc<SessionImpl>,
+    stmt: Statement,
+    sql: &str,
+    mut data: CopyDataStream,
+) -> Result<RwPgResponse> {
+    let Statement::Copy {
+        source: CopySource::Table {
+            table_name,
+            columns,
+        },
+        to: false,
+        options,
+        ..
+    } = stmt else {
+        return Err(ErrorCode::InternalError(format!(
+            "unexpected statement for COPY FROM STDIN: {}",
+            sql
+        ))
+        .into());
+    };
+    let format = CopyFormat::from_options(&options)?;
+    let num_columns = bind_copy_columns(&session, table_name.clone(), &columns)?;
+
+    let sql: Arc<str> = Arc::from(sql);
+    let mut decoder = CopyDecoder::new(format);
+    let mut rows = vec![];
+    let mut rows_cnt = 0;
+    loop {
+        let piece =
+            data.next().await.transpose().map_err(|e| {
+                ErrorCode::InternalError(format!("failed to receive COPY data: {}", e))
+            })?;
+        match &piece {
+            Some(piece) => rows.extend(decoder.decode(piece, false)?),
+            None => rows.extend(decoder.decode(&[], true)?),
+        }
+        if rows.len() >= COPY_BATCH_SIZE || piece.is_none() {
+            let context =
+                OptimizerContext::new(session.clone(), sql.clone(), WithOptions::default());
+            let rows = std::mem::take(&mut rows);
+            rows_cnt += insert_rows(
+                context,
+                table_name.clone(),
+                columns.clone(),
+                num_columns,
+                rows,
+            )
+            .await?;
+        }
+        if piece.is_none() {
+            break;
+        }
+    }
+    Ok(PgResponse::new_for_stream(
+        StatementType::COPY,
+        Some(rows_cnt),
+        PgResponseStream::Rows(stream::empty().boxed()),
+        vec![],
+    ))
+}
+
+/// Checks that the columns to copy exist in the table, and returns the number of them.
+fn bind_copy_columns(
+    session: &SessionImpl,
+    table_name: ObjectName,
+    columns: &[Ident],
+) -> Result<usize> {
+    let (schema_name, table_name) =
+        Binder::resolve_table_or_source_name(session.database(), table_name)?;
+    let table_source =
+        Binder::new(session).bind_table_source(schema_name.as_deref(), &table_name)?;
+    if columns.is_empty() {
+        return Ok(table_source.columns.len());
+    }
+    for column in columns {
+        let name = column.real_value();
+        if !table_source.columns.iter().any(|c| c.name == name) {
+            return Err(ErrorCode::BindError(format!(
+                "column \"{}\" of relation \"{}\" does not exist",
+                name, table_name
+            ))
+            .into());
+        }
+    }
+    Ok(columns.len())
+}
+
+/// Inserts `rows` of the copied `columns` into the table, by executing an `INSERT` statement with
+/// the same column list. Columns that are not copied are filled with their defaults.
+async fn insert_rows(
+    context: OptimizerContext,
+    table_name: ObjectName,
+    columns: Vec<Ident>,
+    num_columns: usize,
+    rows: Vec<Vec<Option<String>>>,
+) -> Result<i32> {
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            if row.len() < num_columns {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "missing data for column in COPY data".into(),
+                )
+                .into());
+            }
+            if row.len() > num_columns {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "extra data after last expected column in COPY data".into(),
+                )
+                .into());
+            }
+            Ok(row
+                .into_iter()
+                .map(|value| match value {
+                    Some(value) => Expr::Value(Value::SingleQuotedString(value)),
+                    None => Expr::Value(Value::Null),
+                })
+                .collect())
+        })
+        .collect::<Result<_>>()?;
+    let insert = Statement::Insert {
+        table_name,
+        columns,
+        source: Box::new(Query {
+            with: None,
+            body: SetExpr::Values(Values(rows)),
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            fetch: None,
+        }),
+    };
+    let res = query::handle_query(context, insert, false).await?;
+    Ok(res.get_effected_rows_cnt().unwrap_or_default())
+}
+
+async fn handle_copy_to(
+    context: OptimizerContext,
+    source: CopySource,
+    format: CopyFormat,
+) -> Result<RwPgResponse> {
+    let query = match source {
+        CopySource::Table {
+            table_name,
+            columns,
+        } => {
+            let projection = if columns.is_empty() {
+                vec![SelectItem::Wildcard]
+            } else {
+                columns
+                    .into_iter()
+                    .map(|c| SelectItem::UnnamedExpr(Expr::Identifier(c)))
+                    .collect()
+            };
+            Box::new(Query {
+                with: None,
+                body: SetExpr::Select(Box::new(Select {
+                    distinct: Distinct::All,
+                    projection,
+                    from: vec![TableWithJoins {
+                        relation: TableFactor::Table {
+                            name: table_name,
+                            alias: None,
+                        },
+                        joins: vec![],
+                    }],
+                    lateral_views: vec![],
+                    selection: None,
+                    group_by: vec![],
+                    having: None,
+                })),
+                order_by: vec![],
+                limit: None,
+                offset: None,
+                fetch: None,
+            })
+        }
+        CopySource::Query(query) => query,
+    };
+
+    let res = query::handle_query(context, Statement::Query(query), false).await?;
+    let row_desc = res.get_row_desc();
+    let header = format
+        .header
+        .then(|| format.encode_row(row_desc.iter().map(|desc| Some(desc.get_name().as_bytes()))));
+
+    let lines = stream::unfold(res, |mut res| async move {
+        let row_set = res.values_stream().next().await?;
+        Some((row_set, res))
+    })
+    .map(move |row_set| {
+        row_set.map(|rows| {
+            rows.iter()
+                .map(|row| {
+                    let line = format.encode_row(row.values().iter().map(|v| v.as_deref()));
+                    Row::new(vec![Some(line.into())])
+                })
+                .collect()
+        })
+    });
+
+    Ok(PgResponse::new_for_copy_out(
+        PgResponseStream::Rows(lines.boxed()),
+        row_desc,
+        header,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(options: Vec<CopyOption>) -> CopyFormat {
+        CopyFormat::from_options(&options).unwrap()
+    }
+
+    fn decode(format: &CopyFormat, data: &str) -> Result<Vec<Vec<Option<String>>>> {
+        CopyDecoder::new(format.clone()).decode(data.as_bytes(), true)
+    }
+
+    fn encode(format: &CopyFormat, values: &[Option<&str>]) -> String {
+        String::from_utf8(format.encode_row(values.iter().map(|v| v.map(str::as_bytes)))).unwrap()
+    }
+
+    #[test]
+    fn test_copy_text_format() {
+        let text = format(vec![]);
+        assert_eq!(
+            encode(&text, &[Some("a\tb\\c\nd"), None, Some("")]),
+            "a\\tb\\\\c\\nd\t\\N\t\n"
+        );
+        assert_eq!(
+            decode(&text, "a\\tb\\\\c\\nd\t\\N\t\n1\t2\t3\n\\.\nignored\n").unwrap(),
+            vec![
+                vec![Some("a\tb\\c\nd".to_string()), None, Some("".to_string())],
+                vec![
+                    Some("1".to_string()),
+                    Some("2".to_string()),
+                    Some("3".to_string())
+                ],
+            ]
+        );
+
+        let text = format(vec![
+            CopyOption::Delimiter('|'),
+            CopyOption::Null("NULL".to_string()),
+            CopyOption::Header(true),
+        ]);
+        assert_eq!(encode(&text, &[Some("a|b"), None]), "a\\|b|NULL\n");
+        assert_eq!(
+            decode(&text, "x|y\na\\|b|NULL\n").unwrap(),
+            vec![vec![Some("a|b".to_string()), None]]
+        );
+    }
+
+    #[test]
+    fn test_copy_csv_format() {
+        let csv = format(vec![CopyOption::Format(Ident::new("csv"))]);
+        assert_eq!(
+            encode(&csv, &[Some("a,b"), Some("say \"hi\""), None, Some("")]),
+            "\"a,b\",\"say \"\"hi\"\"\",,\"\"\n"
+        );
+        assert_eq!(
+            decode(
+                &csv,
+                "\"a,b\",\"say \"\"hi\"\"\",,\"\"\r\n\"multi\nline\",1\n\\.\n"
+            )
+            .unwrap(),
+            vec![
+                vec![
+                    Some("a,b".to_string()),
+                    Some("say \"hi\"".to_string()),
+                    None,
+                    Some("".to_string())
+                ],
+                vec![Some("multi\nline".to_string()), Some("1".to_string())],
+            ]
+        );
+        assert!(decode(&csv, "\"unterminated\n").is_err());
+    }
+
+    #[test]
+    fn test_copy_decode_in_pieces() {
+        let csv = format(vec![
+            CopyOption::Format(Ident::new("csv")),
+            CopyOption::Header(true),
+        ]);
+        let data = "v1,v2\n\"multi\nline\",\u{4f60}\u{597d}\r\n2,\"\"\n3,\n\\.\nignored\n";
+        let expected = decode(&csv, data).unwrap();
+        assert_eq!(expected.len(), 3);
+        // The data may be split anywhere, even in the middle of a quoted field or a character.
+        for piece_size in 1..data.len() {
+            let mut decoder = CopyDecoder::new(csv.clone());
+            let mut rows = vec![];
+            for piece in data.as_bytes().chunks(piece_size) {
+                rows.extend(decoder.decode(piece, false).unwrap());
+            }
+            rows.extend(decoder.decode(&[], true).unwrap());
+            assert_eq!(rows, expected);
+        }
+    }
+
+    #[test]
+    fn test_copy_options() {
+        assert!(CopyFormat::from_options(&[CopyOption::Format(Ident::new("binary"))]).is_err());
+        assert!(CopyFormat::from_options(&[CopyOption::Format(Ident::new("json"))]).is_err());
+        assert!(CopyFormat::from_options(&[CopyOption::Delimiter('\\')]).is_err());
+    }
+}
@@ -12,14 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::Ordering;
+
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_pb::catalog::Table as ProstTable;
 use risingwave_pb::user::grant_privilege::{Action, Object};
-use risingwave_sqlparser::ast::{ObjectName, Query};
+use risingwave_sqlparser::ast::{EmitMode, ObjectName, Query};
 
-use super::privilege::{check_privileges, resolve_relation_privileges};
+use super::privilege::{check_privileges, resolve_set_expr_privileges};
 use super::RwPgResponse;
 use crate::binder::{Binder, BoundSetExpr};
 use crate::catalog::check_schema_writable;
@@ -35,6 +37,7 @@ pub fn gen_create_mv_plan(
     context: OptimizerContextRef,
     query: Query,
     name: ObjectName,
+    emit_mode: Option<EmitMode>,
 ) -> Result<(PlanRef, ProstTable)> {
     let db_name = session.database();
     let (schema_name, table_name) = Binder::resolve_table_or_source_name(db_name, name)?;
@@ -71,7 +74,12 @@ pub fn gen_create_mv_plan(
         binder.bind_query(query)?
     };
 
-    if let BoundSetExpr::Select(select) = &bound.body {
+    // The output columns of a set operation are named after its leftmost side.
+    let mut output_body = &bound.body;
+    while let BoundSetExpr::SetOperation { left, .. } = output_body {
+        output_body = left;
+    }
+    if let BoundSetExpr::Select(select) = output_body {
         // `InputRef`'s alias will be implicitly assigned in `bind_project`.
         // For other expressions, we require the user to explicitly assign an alias.
         if select.aliases.iter().any(Option::is_none) {
@@ -80,13 +88,17 @@ pub fn gen_create_mv_plan(
             )
             .into());
         }
-        if let Some(relation) = &select.from {
-            let mut check_items = Vec::new();
-            resolve_relation_privileges(relation, Action::Select, &mut check_items);
-            check_privileges(session, &check_items)?;
-        }
+    }
+    let mut check_items = Vec::new();
+    resolve_set_expr_privileges(&bound.body, Action::Select, &mut check_items);
+    if !check_items.is_empty() {
+        check_privileges(session, &check_items)?;
     }
 
+    context.inner().emit_on_window_close.store(
+        emit_mode == Some(EmitMode::OnWindowClose),
+        Ordering::Release,
+    );
     let mut plan_root = Planner::new(context).plan_query(bound)?;
     let materialize = plan_root.gen_create_mv_plan(table_name, definition)?;
     let mut table = materialize.table().to_prost(schema_id, database_id);
@@ -113,6 +125,7 @@ pub async fn handle_create_mv(
     context: OptimizerContext,
     name: ObjectName,
     query: Query,
+    emit_mode: Option<EmitMode>,
 ) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
 
@@ -138,7 +151,7 @@ pub async fn handle_create_mv(
             catalog_reader.check_relation_name_duplicated(db_name, &schema_name, &table_name)?;
         }
 
-        let (plan, table) = gen_create_mv_plan(&session, context.into(), query, name)?;
+        let (plan, table) = gen_create_mv_plan(&session, context.into(), query, name, emit_mode)?;
         let graph = build_graph(plan);
 
         (table, graph)
@@ -217,6 +230,29 @@ pub mod tests {
         assert_eq!(columns, expected_columns);
     }
 
+    #[tokio::test]
+    async fn test_create_mv_with_ttl() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t(v int)").await.unwrap();
+
+        let sql = "create materialized view mv1 with (ttl = 300) as select v from t";
+        frontend.run_sql(sql).await.unwrap();
+        let sql = "create materialized view mv2 with (ttl = '1 hour') as select v from t";
+        frontend.run_sql(sql).await.unwrap();
+        let sql = "create materialized view mv3 with (ttl = '0 seconds') as select v from t";
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+        for (name, retention_seconds) in [("mv1", 300), ("mv2", 3600)] {
+            let (table, _) = catalog_reader
+                .get_table_by_name(DEFAULT_DATABASE_NAME, schema_path, name)
+                .unwrap();
+            assert_eq!(table.table_desc().retention_seconds, retention_seconds);
+        }
+    }
+
     /// When creating MV, The only thing to allow without explicit alias is `InputRef`.
     #[tokio::test]
     async fn test_no_alias() {
@@ -249,4 +285,62 @@ pub mod tests {
             "Bind error: An alias must be specified for an expression"
         );
     }
+
+    #[tokio::test]
+    async fn test_emit_on_window_close() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql =
+            "create source t(ts timestamp, v int, watermark for ts as ts - interval '1' minute) \
+                   with (kafka.topic = 'abc', kafka.servers = 'localhost:1001') row format json";
+        frontend.run_sql(sql).await.unwrap();
+
+        let sql = "create materialized view mv1 as select window_start, v, count(*) as cnt \
+                   from tumble(t, ts, interval '1' minute) group by v, window_start \
+                   emit on window close";
+        frontend.run_sql(sql).await.unwrap();
+
+        let sql = "create materialized view mv2 as select window_end, count(*) as cnt \
+                   from hop(t, ts, interval '1' minute, interval '2' minute) group by window_end \
+                   emit on window close";
+        frontend.run_sql(sql).await.unwrap();
+
+        // The group key must contain a window column.
+        let sql = "create materialized view mv3 as select v, count(*) as cnt from t group by v \
+                   emit on window close";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input syntax: EMIT ON WINDOW CLOSE requires the GROUP BY to contain a window \
+             column, i.e. `window_start` or `window_end` of TUMBLE or HOP"
+        );
+
+        // The window must be built on a column with watermark.
+        let sql = "create table t2(ts timestamp, v int)";
+        frontend.run_sql(sql).await.unwrap();
+        let sql = "create materialized view mv4 as select window_start, count(*) as cnt \
+                   from tumble(t2, ts, interval '1' minute) group by window_start \
+                   emit on window close";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input syntax: EMIT ON WINDOW CLOSE requires the time column of the window to \
+             have a watermark defined on the source"
+        );
+
+        // Only the top-level aggregation can emit on window close.
+        let sql = "create materialized view mv5 as select a.window_start, a.cnt, b.cnt \
+                   from (select window_start, count(*) as cnt \
+                         from tumble(t, ts, interval '1' minute) group by window_start) a \
+                   join (select window_start, count(*) as cnt \
+                         from tumble(t, ts, interval '1' minute) group by window_start) b \
+                   on a.window_start = b.window_start \
+                   emit on window close";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input syntax: EMIT ON WINDOW CLOSE requires an aggregation at the top level \
+             of the query"
+        );
+    }
 }
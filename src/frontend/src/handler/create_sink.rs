@@ -14,9 +14,10 @@
 
 use std::rc::Rc;
 
+use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
-use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
-use risingwave_common::error::Result;
+use risingwave_common::catalog::{SinkType, DEFAULT_SCHEMA_NAME, SINK_CONNECTOR_OPTION};
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_pb::catalog::Sink as ProstSink;
 use risingwave_pb::user::grant_privilege::{Action, Object};
 use risingwave_sqlparser::ast::CreateSinkStatement;
@@ -108,8 +109,8 @@ pub fn gen_sink_plan(
         session.user_id(),
     )?;
 
-    let scan_node = StreamTableScan::new(LogicalScan::create(
-        associated_table_name,
+    let scan_node: PlanRef = StreamTableScan::new(LogicalScan::create(
+        associated_table_name.clone(),
         false,
         Rc::new(associated_table_desc),
         vec![],
@@ -117,6 +118,59 @@ pub fn gen_sink_plan(
     ))
     .into();
 
+    let supported_sink_types = properties
+        .get(SINK_CONNECTOR_OPTION)
+        .and_then(|connector| SinkType::supported_by(connector));
+    let sink_type = match (
+        SinkType::from_properties(&properties)?,
+        supported_sink_types,
+    ) {
+        (Some(sink_type), Some(supported)) if !supported.contains(&sink_type) => {
+            return Err(ErrorCode::InvalidParameterValue(format!(
+                "{} sink does not support type = '{}', expected one of {}",
+                properties[SINK_CONNECTOR_OPTION],
+                sink_type.as_str(),
+                supported
+                    .iter()
+                    .map(|t| format!("'{}'", t.as_str()))
+                    .join(", ")
+            ))
+            .into());
+        }
+        // Connectors that only write inserts, e.g. the file sink, are append-only even if `type`
+        // is not set.
+        (None, Some([SinkType::AppendOnly])) => Some(SinkType::AppendOnly),
+        (sink_type, _) => sink_type,
+    };
+    match sink_type {
+        Some(SinkType::AppendOnly) if !scan_node.append_only() => {
+            let alternatives = supported_sink_types
+                .unwrap_or(&[SinkType::Upsert, SinkType::Debezium])
+                .iter()
+                .filter(|t| **t != SinkType::AppendOnly)
+                .map(|t| format!("type = '{}'", t.as_str()))
+                .join(" or ");
+            let hint = if alternatives.is_empty() {
+                "Please sink from an append-only table or materialized view instead.".to_string()
+            } else {
+                format!("Please use {} instead.", alternatives)
+            };
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "The sink cannot be append-only because {} is not append-only. {}",
+                associated_table_name, hint
+            ))
+            .into());
+        }
+        Some(SinkType::Upsert) if scan_node.logical_pk().is_empty() => {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "The sink cannot be upsert because {} has no primary key.",
+                associated_table_name
+            ))
+            .into());
+        }
+        _ => {}
+    }
+
     let plan: PlanRef = StreamSink::new(scan_node, properties).into();
 
     let ctx = plan.ctx();
@@ -217,4 +271,78 @@ pub mod tests {
             .unwrap();
         assert_eq!(sink.name, "snk1");
     }
+
+    #[tokio::test]
+    async fn test_create_sink_type() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1, sum(v2) as s from t group by v1;")
+            .await
+            .unwrap();
+
+        let create_sink = |sink_type: &str| {
+            format!(
+                "CREATE SINK snk_{} FROM mv WITH (connector = 'kafka', kafka.brokers = '127.0.0.1:9092', kafka.topic = 't', type = '{}');",
+                sink_type.replace('-', "_"),
+                sink_type
+            )
+        };
+        let err = frontend
+            .run_sql(create_sink("append-only"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not append-only"), "{}", err);
+        assert!(frontend.run_sql(create_sink("retract")).await.is_err());
+        frontend.run_sql(create_sink("upsert")).await.unwrap();
+        frontend.run_sql(create_sink("debezium")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_sink_type_unsupported_by_connector() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int primary key, v2 int);")
+            .await
+            .unwrap();
+
+        let err = frontend
+            .run_sql("CREATE SINK snk FROM t WITH (connector = 'redis', type = 'debezium');")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not support"), "{}", err);
+        frontend
+            .run_sql("CREATE SINK snk FROM t WITH (connector = 'redis', type = 'upsert');")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_file_sink_from_retracting_upstream() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table t_append_only (v1 int, v2 int) with (appendonly = true);")
+            .await
+            .unwrap();
+
+        let create_sink = |table: &str| {
+            format!(
+                "CREATE SINK snk_{} FROM {} WITH (connector = 'file', file.url = 'memory://');",
+                table, table
+            )
+        };
+        let err = frontend.run_sql(create_sink("t")).await.unwrap_err();
+        assert!(err.to_string().contains("not append-only"), "{}", err);
+        frontend
+            .run_sql(create_sink("t_append_only"))
+            .await
+            .unwrap();
+    }
 }
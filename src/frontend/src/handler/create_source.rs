@@ -17,17 +17,17 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
-use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::ErrorCode::{self, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::{
-    ColumnIndex as ProstColumnIndex, Source as ProstSource, StreamSourceInfo,
+    ColumnIndex as ProstColumnIndex, Source as ProstSource, StreamSourceInfo, WatermarkDesc,
 };
 use risingwave_pb::plan_common::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
 use risingwave_pb::user::grant_privilege::{Action, Object};
 use risingwave_source::{AvroParser, ProtobufParser};
 use risingwave_sqlparser::ast::{
-    AvroSchema, CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema,
+    AvroSchema, CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema, SourceWatermark,
 };
 
 use super::create_table::{
@@ -37,6 +37,8 @@ use super::privilege::check_privileges;
 use super::RwPgResponse;
 use crate::binder::Binder;
 use crate::catalog::check_schema_writable;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::expr::Expr;
 use crate::handler::privilege::ObjectCheckItem;
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::stream_fragmenter::build_graph;
@@ -89,20 +91,35 @@ async fn extract_avro_table_schema(
     schema: &AvroSchema,
     with_properties: HashMap<String, String>,
 ) -> Result<Vec<ProstColumnCatalog>> {
-    let parser = AvroParser::new(schema.row_schema_location.0.as_str(), with_properties).await?;
+    let parser = AvroParser::new(
+        schema.row_schema_location.0.as_str(),
+        with_properties,
+        schema.use_schema_registry,
+    )
+    .await?;
     let vec_column_desc = parser.map_to_columns()?;
     Ok(vec_column_desc
         .into_iter()
         .map(|c| ProstColumnCatalog {
             column_desc: Some(c),
             is_hidden: false,
+            default_value: vec![],
         })
         .collect_vec())
 }
 
 /// Map a protobuf schema to a relational schema.
-fn extract_protobuf_table_schema(schema: &ProtobufSchema) -> Result<Vec<ProstColumnCatalog>> {
-    let parser = ProtobufParser::new(&schema.row_schema_location.0, &schema.message_name.0)?;
+async fn extract_protobuf_table_schema(
+    schema: &ProtobufSchema,
+    with_properties: &HashMap<String, String>,
+) -> Result<Vec<ProstColumnCatalog>> {
+    let parser = ProtobufParser::new(
+        &schema.row_schema_location.0,
+        &schema.message_name.0,
+        with_properties,
+        schema.use_schema_registry,
+    )
+    .await?;
     let column_descs = parser.map_to_columns()?;
 
     Ok(column_descs
@@ -110,10 +127,48 @@ fn extract_protobuf_table_schema(schema: &ProtobufSchema) -> Result<Vec<ProstCol
         .map(|col| ProstColumnCatalog {
             column_desc: Some(col),
             is_hidden: false,
+            default_value: vec![],
         })
         .collect_vec())
 }
 
+/// Bind the watermark definitions of a source. At most one watermark is allowed for each column.
+fn bind_source_watermarks(
+    session: &SessionImpl,
+    source_name: &ObjectName,
+    source_watermarks: Vec<SourceWatermark>,
+    source: &StreamSourceInfo,
+) -> Result<Vec<WatermarkDesc>> {
+    let columns = source
+        .columns
+        .iter()
+        .cloned()
+        .map(ColumnCatalog::from)
+        .collect_vec();
+    let source_name = source_name.0.last().unwrap().real_value();
+
+    let mut watermark_descs: Vec<WatermarkDesc> = vec![];
+    for source_watermark in source_watermarks {
+        let (watermark_idx, expr) =
+            Binder::new(session).bind_source_watermark(&source_name, &columns, source_watermark)?;
+        if watermark_descs
+            .iter()
+            .any(|desc| desc.watermark_idx as usize == watermark_idx)
+        {
+            return Err(ErrorCode::BindError(format!(
+                "Multiple watermarks are defined on column {}",
+                columns[watermark_idx].name()
+            ))
+            .into());
+        }
+        watermark_descs.push(WatermarkDesc {
+            watermark_idx: watermark_idx as u32,
+            expr: Some(expr.to_expr_proto()),
+        });
+    }
+    Ok(watermark_descs)
+}
+
 pub async fn handle_create_source(
     context: OptimizerContext,
     is_materialized: bool,
@@ -125,19 +180,21 @@ pub async fn handle_create_source(
 
     let with_properties = context.with_options.inner().clone();
 
-    let source = match &stmt.source_schema {
+    let mut source = match &stmt.source_schema {
         SourceSchema::Protobuf(protobuf_schema) => {
             assert_eq!(columns.len(), 1);
             assert_eq!(pk_column_ids, vec![0.into()]);
             assert_eq!(row_id_index, Some(0));
-            columns.extend(extract_protobuf_table_schema(protobuf_schema)?);
+            columns.extend(extract_protobuf_table_schema(protobuf_schema, &with_properties).await?);
             StreamSourceInfo {
                 properties: with_properties.clone(),
                 row_format: RowFormatType::Protobuf as i32,
                 row_schema_location: protobuf_schema.row_schema_location.0.clone(),
+                use_schema_registry: protobuf_schema.use_schema_registry,
                 row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
                 columns,
                 pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+                ..Default::default()
             }
         }
         SourceSchema::Avro(avro_schema) => {
@@ -149,9 +206,11 @@ pub async fn handle_create_source(
                 properties: with_properties.clone(),
                 row_format: RowFormatType::Avro as i32,
                 row_schema_location: avro_schema.row_schema_location.0.clone(),
+                use_schema_registry: avro_schema.use_schema_registry,
                 row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
                 columns,
                 pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+                ..Default::default()
             }
         }
         SourceSchema::Json => StreamSourceInfo {
@@ -161,6 +220,16 @@ pub async fn handle_create_source(
             row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
             columns,
             pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        },
+        SourceSchema::Csv => StreamSourceInfo {
+            properties: with_properties.clone(),
+            row_format: RowFormatType::Csv as i32,
+            row_schema_location: "".to_string(),
+            row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
+            columns,
+            pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+            ..Default::default()
         },
         SourceSchema::DebeziumJson => {
             // return err if user has not specified a pk
@@ -177,11 +246,50 @@ pub async fn handle_create_source(
                 row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
                 columns,
                 pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+                ..Default::default()
+            }
+        }
+        SourceSchema::CanalJson => {
+            // return err if user has not specified a pk
+            if row_id_index.is_some() {
+                return Err(RwError::from(ProtocolError(
+                    "Primary key must be specified when creating source with row format canal json."
+                        .to_string(),
+                )));
+            }
+            StreamSourceInfo {
+                properties: with_properties.clone(),
+                row_format: RowFormatType::CanalJson as i32,
+                row_schema_location: "".to_string(),
+                row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
+                columns,
+                pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+                ..Default::default()
+            }
+        }
+        SourceSchema::Maxwell => {
+            // return err if user has not specified a pk
+            if row_id_index.is_some() {
+                return Err(RwError::from(ProtocolError(
+                    "Primary key must be specified when creating source with row format maxwell."
+                        .to_string(),
+                )));
+            }
+            StreamSourceInfo {
+                properties: with_properties.clone(),
+                row_format: RowFormatType::Maxwell as i32,
+                row_schema_location: "".to_string(),
+                row_id_index: row_id_index.map(|index| ProstColumnIndex { index: index as _ }),
+                columns,
+                pk_column_ids: pk_column_ids.into_iter().map(Into::into).collect(),
+                ..Default::default()
             }
         }
     };
 
     let session = context.session_ctx.clone();
+    source.watermark_descs =
+        bind_source_watermarks(&session, &stmt.source_name, stmt.source_watermarks, &source)?;
     {
         let db_name = session.database();
         let catalog_reader = session.env().catalog_reader().read_guard();
@@ -181,6 +181,7 @@ pub fn bind_sql_table_constraints(
                 column_desc: c,
                 // All columns except `_row_id` should be visible.
                 is_hidden: false,
+                default_value: None,
             }
             .to_protobuf()
         })
@@ -368,6 +369,34 @@ mod tests {
         assert_eq!(columns, expected_columns);
     }
 
+    #[tokio::test]
+    async fn test_create_table_with_ttl() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int) with (ttl = '7 days');")
+            .await
+            .unwrap();
+        for sql in [
+            "create table t1 (v1 int) with (ttl = '0 seconds');",
+            "create table t1 (v1 int) with (ttl = '-1 day');",
+            "create table t1 (v1 int) with (ttl = 'forever');",
+            "create table t1 (v1 int) with (ttl = 60, retention_seconds = 60);",
+        ] {
+            assert!(frontend.run_sql(sql).await.is_err(), "{}", sql);
+        }
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let (table, _) = catalog_reader
+            .get_table_by_name(
+                DEFAULT_DATABASE_NAME,
+                SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                "t",
+            )
+            .unwrap();
+        assert_eq!(table.table_desc().retention_seconds, 7 * 24 * 3600);
+    }
+
     #[test]
     fn test_bind_primary_key() {
         for (sql, expected) in [
@@ -0,0 +1,213 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::catalog::View as ProstView;
+use risingwave_pb::user::grant_privilege::{Action, Object};
+use risingwave_sqlparser::ast::{Ident, ObjectName, Query};
+
+use super::privilege::{check_privileges, resolve_set_expr_privileges};
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::check_schema_writable;
+use crate::handler::privilege::ObjectCheckItem;
+use crate::session::OptimizerContext;
+
+pub async fn handle_create_view(
+    context: OptimizerContext,
+    name: ObjectName,
+    columns: Vec<Ident>,
+    mut query: Query,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx.clone();
+    let db_name = session.database();
+    let (schema_name, view_name) = Binder::resolve_table_or_source_name(db_name, name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let (database_id, schema_id) = {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema = match schema_name {
+            Some(schema_name) => catalog_reader.get_schema_by_name(db_name, &schema_name)?,
+            None => catalog_reader.first_valid_schema(db_name, &search_path, user_name)?,
+        };
+
+        check_schema_writable(&schema.name())?;
+        if schema.name() != DEFAULT_SCHEMA_NAME {
+            check_privileges(
+                &session,
+                &vec![ObjectCheckItem::new(
+                    schema.owner(),
+                    Action::Create,
+                    Object::SchemaId(schema.id()),
+                )],
+            )?;
+        }
+        catalog_reader.check_relation_name_duplicated(db_name, &schema.name(), &view_name)?;
+
+        let db_id = catalog_reader.get_database_by_name(db_name)?.id();
+        (db_id, schema.id())
+    };
+
+    // Pin the columns and relations the view refers to, so that the definition binds to the same
+    // ones later.
+    Binder::new(&session).rewrite_view_query(&mut query)?;
+    let definition = query.to_string();
+
+    let (schema, dependent_relations) = {
+        let mut binder = Binder::new(&session);
+        let bound = binder.bind_query(query)?;
+
+        let mut check_items = Vec::new();
+        resolve_set_expr_privileges(&bound.body, Action::Select, &mut check_items);
+        if !check_items.is_empty() {
+            check_privileges(&session, &check_items)?;
+        }
+
+        (bound.schema().clone(), binder.included_relations())
+    };
+
+    let mut fields = schema.fields;
+    if columns.len() > fields.len() {
+        return Err(ErrorCode::BindError(
+            "CREATE VIEW specifies more column names than columns".to_string(),
+        )
+        .into());
+    }
+    for (field, column) in fields.iter_mut().zip(columns.into_iter()) {
+        field.name = column.real_value();
+    }
+
+    let view = ProstView {
+        id: 0,
+        schema_id,
+        database_id,
+        name: view_name,
+        owner: session.user_id(),
+        properties: context.with_options.inner().clone(),
+        sql: definition,
+        dependent_relations: dependent_relations
+            .into_iter()
+            .map(|t| t.table_id)
+            .collect_vec(),
+        columns: fields.iter().map(|f| f.to_prost()).collect(),
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.create_view(view).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_VIEW))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use risingwave_common::types::DataType;
+    use risingwave_sqlparser::ast::Statement;
+    use risingwave_sqlparser::parser::Parser;
+
+    use crate::binder::Binder;
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_view_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create view v (a) as select v1, v2 from t;")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+
+        let (view, _) = catalog_reader
+            .get_view_by_name(DEFAULT_DATABASE_NAME, schema_path, "v")
+            .unwrap();
+        assert_eq!(view.name, "v");
+        assert_eq!(
+            view.columns
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "v2"]
+        );
+        assert_eq!(view.sql, "SELECT v1, v2 FROM \"public\".t");
+    }
+
+    #[tokio::test]
+    async fn test_create_view_pins_columns_and_relations() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create schema s;").await.unwrap();
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create view v as select * from t;")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let (view, _) = catalog_reader
+                .get_view_by_name(
+                    DEFAULT_DATABASE_NAME,
+                    SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                    "v",
+                )
+                .unwrap();
+            assert_eq!(
+                view.sql,
+                "SELECT \"t\".\"v1\", \"t\".\"v2\" FROM \"public\".t"
+            );
+        }
+
+        // Neither a new column of `t` nor another `t` earlier in the search path is picked up.
+        frontend
+            .run_sql("alter table t add column v3 int;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table s.t (v1 varchar);")
+            .await
+            .unwrap();
+        session
+            .set_config("search_path", vec!["s".to_string(), "public".to_string()])
+            .unwrap();
+        let query = match Parser::parse_sql("select * from v").unwrap().pop() {
+            Some(Statement::Query(query)) => *query,
+            _ => unreachable!(),
+        };
+        let bound = Binder::new(&session).bind_query(query).unwrap();
+        assert_eq!(
+            bound
+                .schema()
+                .fields
+                .iter()
+                .map(|f| (f.name.as_str(), &f.data_type))
+                .collect::<Vec<_>>(),
+            vec![("v1", &DataType::Int32), ("v2", &DataType::Int32)]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::ObjectName;
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::session::OptimizerContext;
+
+pub async fn handle_drop_view(
+    context: OptimizerContext,
+    view_name: ObjectName,
+    if_exists: bool,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, view_name) = Binder::resolve_table_or_source_name(db_name, view_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+    let schema_path = match schema_name.as_deref() {
+        Some(schema_name) => SchemaPath::Name(schema_name),
+        None => SchemaPath::Path(&search_path, user_name),
+    };
+
+    let view_id = {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let (view, schema_name) =
+            match catalog_reader.get_view_by_name(db_name, schema_path, &view_name) {
+                Ok((view, schema)) => (view.clone(), schema),
+                Err(e) => {
+                    return if if_exists {
+                        Ok(RwPgResponse::empty_result_with_notice(
+                            StatementType::DROP_VIEW,
+                            format!("view \"{}\" does not exist, skipping", view_name),
+                        ))
+                    } else {
+                        Err(e)
+                    }
+                }
+            };
+
+        let schema_owner = catalog_reader
+            .get_schema_by_name(db_name, schema_name)
+            .unwrap()
+            .owner();
+        if view.owner != session.user_id()
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        view.id
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_view(view_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_VIEW))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_view_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int);").await.unwrap();
+        frontend
+            .run_sql("create view v as select v1 from t;")
+            .await
+            .unwrap();
+        frontend.run_sql("drop view v;").await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+
+        let view = catalog_reader.get_view_by_name(DEFAULT_DATABASE_NAME, schema_path, "v");
+        assert!(view.is_err());
+    }
+}
@@ -30,7 +30,9 @@ use crate::scheduler::{DistributedQueryStream, LocalQueryStream};
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::utils::WithOptions;
 
+pub mod alter_table;
 pub mod alter_user;
+pub mod copy;
 mod create_database;
 pub mod create_index;
 pub mod create_mv;
@@ -39,6 +41,7 @@ pub mod create_sink;
 pub mod create_source;
 pub mod create_table;
 pub mod create_user;
+pub mod create_view;
 mod describe;
 mod drop_database;
 mod drop_index;
@@ -48,12 +51,14 @@ pub mod drop_sink;
 pub mod drop_source;
 pub mod drop_table;
 pub mod drop_user;
+mod drop_view;
 mod explain;
 mod flush;
 pub mod handle_privilege;
 pub mod privilege;
 pub mod query;
 mod show;
+mod truncate;
 pub mod util;
 pub mod variable;
 
@@ -154,6 +159,9 @@ pub async fn handle(
         } => create_schema::handle_create_schema(context, schema_name, if_not_exists).await,
         Statement::CreateUser(stmt) => create_user::handle_create_user(context, stmt).await,
         Statement::AlterUser(stmt) => alter_user::handle_alter_user(context, stmt).await,
+        Statement::AlterTable { name, operation } => {
+            alter_table::handle_alter_table(context, name, operation).await
+        }
         Statement::Grant { .. } => handle_privilege::handle_grant_privilege(context, stmt).await,
         Statement::Revoke { .. } => handle_privilege::handle_revoke_privilege(context, stmt).await,
         Statement::Describe { name } => describe::handle_describe(context, name),
@@ -170,6 +178,7 @@ pub async fn handle(
             ObjectType::MaterializedView => {
                 drop_mv::handle_drop_mv(context, object_name, if_exists).await
             }
+            ObjectType::View => drop_view::handle_drop_view(context, object_name, if_exists).await,
             ObjectType::Index => {
                 drop_index::handle_drop_index(context, object_name, if_exists).await
             }
@@ -202,14 +211,25 @@ pub async fn handle(
         | Statement::Insert { .. }
         | Statement::Delete { .. }
         | Statement::Update { .. } => query::handle_query(context, stmt, format).await,
+        Statement::Copy { .. } => copy::handle_copy(context, stmt).await,
         Statement::CreateView {
             materialized: true,
             or_replace: false,
             name,
             query,
+            emit_mode,
+            ..
+        } => create_mv::handle_create_mv(context, name, *query, emit_mode).await,
+        Statement::CreateView {
+            materialized: false,
+            or_replace: false,
+            name,
+            columns,
+            query,
             ..
-        } => create_mv::handle_create_mv(context, name, *query).await,
+        } => create_view::handle_create_view(context, name, columns, *query).await,
         Statement::Flush => flush::handle_flush(context).await,
+        Statement::Truncate { table_name } => truncate::handle_truncate(context, table_name).await,
         Statement::SetVariable {
             local: _,
             variable,
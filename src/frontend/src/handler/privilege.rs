@@ -16,7 +16,7 @@ use risingwave_common::error::ErrorCode::PermissionDenied;
 use risingwave_common::error::Result;
 use risingwave_pb::user::grant_privilege::{Action as ProstAction, Object as ProstObject};
 
-use crate::binder::{BoundStatement, Relation};
+use crate::binder::{BoundSetExpr, BoundStatement, Relation};
 use crate::session::SessionImpl;
 use crate::user::UserId;
 
@@ -60,11 +60,7 @@ pub(crate) fn resolve_relation_privileges(
             objects.push(item);
         }
         Relation::Subquery(query) => {
-            if let crate::binder::BoundSetExpr::Select(select) = &query.query.body {
-                if let Some(sub_relation) = &select.from {
-                    resolve_relation_privileges(sub_relation, action, objects);
-                }
-            }
+            resolve_set_expr_privileges(&query.query.body, action, objects)
         }
         Relation::Join(join) => {
             resolve_relation_privileges(&join.left, action, objects);
@@ -77,6 +73,26 @@ pub(crate) fn resolve_relation_privileges(
     };
 }
 
+/// resolve privileges in the relations of `set_expr`, including both sides of set operations
+pub(crate) fn resolve_set_expr_privileges(
+    set_expr: &BoundSetExpr,
+    action: ProstAction,
+    objects: &mut Vec<ObjectCheckItem>,
+) {
+    match set_expr {
+        BoundSetExpr::Select(select) => {
+            if let Some(relation) = &select.from {
+                resolve_relation_privileges(relation, action, objects);
+            }
+        }
+        BoundSetExpr::Values(_) => {}
+        BoundSetExpr::SetOperation { left, right, .. } => {
+            resolve_set_expr_privileges(left, action, objects);
+            resolve_set_expr_privileges(right, action, objects);
+        }
+    }
+}
+
 /// resolve privileges in `stmt`
 pub(crate) fn resolve_privileges(stmt: &BoundStatement) -> Vec<ObjectCheckItem> {
     let mut objects = Vec::new();
@@ -88,11 +104,7 @@ pub(crate) fn resolve_privileges(stmt: &BoundStatement) -> Vec<ObjectCheckItem>
                 object: ProstObject::TableId(insert.table_source.source_id.table_id),
             };
             objects.push(object);
-            if let crate::binder::BoundSetExpr::Select(select) = &insert.source.body {
-                if let Some(sub_relation) = &select.from {
-                    resolve_relation_privileges(sub_relation, ProstAction::Select, &mut objects);
-                }
-            }
+            resolve_set_expr_privileges(&insert.source.body, ProstAction::Select, &mut objects);
         }
         BoundStatement::Delete(ref delete) => {
             let object = ObjectCheckItem {
@@ -111,11 +123,7 @@ pub(crate) fn resolve_privileges(stmt: &BoundStatement) -> Vec<ObjectCheckItem>
             objects.push(object);
         }
         BoundStatement::Query(ref query) => {
-            if let crate::binder::BoundSetExpr::Select(select) = &query.body {
-                if let Some(sub_relation) = &select.from {
-                    resolve_relation_privileges(sub_relation, ProstAction::Select, &mut objects);
-                }
-            }
+            resolve_set_expr_privileges(&query.body, ProstAction::Select, &mut objects)
         }
     };
     objects
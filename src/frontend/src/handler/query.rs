@@ -122,12 +122,14 @@ pub async fn handle_query(
         .iter()
         .map(|f| f.data_type())
         .collect_vec();
+    let time_zone = session.config().get_timezone();
 
     let mut row_stream = match query_mode {
         QueryMode::Local => PgResponseStream::LocalQuery(DataChunkToRowSetAdapter::new(
             local_execute(session.clone(), query).await?,
             column_types,
             format,
+            time_zone,
         )),
         // Local mode do not support cancel tasks.
         QueryMode::Distributed => {
@@ -135,6 +137,7 @@ pub async fn handle_query(
                 distribute_execute(session.clone(), query).await?,
                 column_types,
                 format,
+                time_zone,
             ))
         }
     };
@@ -227,6 +230,7 @@ async fn local_execute(session: Arc<SessionImpl>, query: Query) -> Result<LocalQ
         "",
         pinned_snapshot.snapshot.committed_epoch,
         session.auth_context(),
+        session.config().get_statement_timeout(),
     );
 
     Ok(execution.stream_rows())
@@ -85,6 +85,11 @@ pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Res
             .iter_mv()
             .map(|t| t.name.clone())
             .collect(),
+        ShowObject::View { schema } => catalog_reader
+            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
+            .iter_view()
+            .map(|t| t.name.clone())
+            .collect(),
         ShowObject::Source { schema } => catalog_reader
             .get_schema_by_name(session.database(), &schema_or_default(&schema))?
             .iter_source()
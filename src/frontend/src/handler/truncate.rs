@@ -0,0 +1,108 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_sqlparser::ast::{ObjectName, Statement};
+
+use super::{query, RwPgResponse};
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::session::OptimizerContext;
+
+/// `TRUNCATE` deletes all the rows of a table by range tombstones if no other relations depend on
+/// it. Otherwise, it's executed as a `DELETE` without predicates through the DML path of the table
+/// source, so that the deletions are propagated to all downstream materialized views.
+pub(super) async fn handle_truncate(
+    context: OptimizerContext,
+    table_name: ObjectName,
+) -> Result<RwPgResponse> {
+    let table_id = {
+        let session = context.session_ctx.clone();
+        let db_name = session.database();
+        let (schema_name, real_table_name) =
+            Binder::resolve_table_or_source_name(db_name, table_name.clone())?;
+        let search_path = session.config().get_search_path();
+        let user_name = &session.auth_context().user_name;
+
+        let schema_path = match schema_name.as_deref() {
+            Some(schema_name) => SchemaPath::Name(schema_name),
+            None => SchemaPath::Path(&search_path, user_name),
+        };
+
+        let reader = session.env().catalog_reader().read_guard();
+        let (table, _) = reader.get_table_by_name(db_name, schema_path, &real_table_name)?;
+        if table.is_index || table.associated_source_id().is_none() {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is not a table",
+                real_table_name
+            ))));
+        }
+        if table.appendonly {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "cannot truncate append-only table \"{}\"",
+                real_table_name
+            ))));
+        }
+        table.id()
+    };
+
+    let catalog_writer = context.session_ctx.env().catalog_writer();
+    if catalog_writer.truncate_table(table_id).await? {
+        return Ok(PgResponse::empty_result(StatementType::TRUNCATE_TABLE));
+    }
+
+    let stmt = Statement::Delete {
+        table_name,
+        selection: None,
+    };
+    query::handle_query(context, stmt, false).await?;
+
+    Ok(PgResponse::empty_result(StatementType::TRUNCATE_TABLE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_truncate_non_table() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int);").await.unwrap();
+        frontend
+            .run_sql("create materialized view mv as select * from t;")
+            .await
+            .unwrap();
+
+        let err = frontend.run_sql("truncate mv;").await.unwrap_err();
+        assert!(err.to_string().contains("is not a table"));
+        assert!(frontend.run_sql("truncate not_exist;").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_truncate_table_without_downstream() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int);").await.unwrap();
+
+        // The table is truncated by meta without running a `DELETE`.
+        frontend.run_sql("truncate t;").await.unwrap();
+
+        frontend
+            .run_sql("create table t2 (v1 int) with (appendonly = true);")
+            .await
+            .unwrap();
+        let err = frontend.run_sql("truncate t2;").await.unwrap_err();
+        assert!(err.to_string().contains("append-only"));
+    }
+}
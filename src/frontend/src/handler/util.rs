@@ -25,8 +25,10 @@ use pgwire::types::Row;
 use pin_project_lite::pin_project;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{ColumnDesc, Field};
+use risingwave_common::session_config::TimeZone;
 use risingwave_common::types::{DataType, ScalarRefImpl};
-use risingwave_expr::vector_op::cast::{timestampz_to_utc_binary, timestampz_to_utc_string};
+use risingwave_expr::vector_op::cast::timestampz_to_utc_binary;
+use risingwave_expr::vector_op::timestampz::timestampz_to_local_string;
 
 pin_project! {
     /// Wrapper struct that converts a stream of DataChunk to a stream of RowSet based on formatting
@@ -43,17 +45,24 @@ pin_project! {
         chunk_stream: VS,
         column_types: Vec<DataType>,
         format: bool,
+        time_zone: TimeZone,
     }
 }
 impl<VS> DataChunkToRowSetAdapter<VS>
 where
     VS: Stream<Item = Result<DataChunk, BoxedError>>,
 {
-    pub fn new(chunk_stream: VS, column_types: Vec<DataType>, format: bool) -> Self {
+    pub fn new(
+        chunk_stream: VS,
+        column_types: Vec<DataType>,
+        format: bool,
+        time_zone: TimeZone,
+    ) -> Self {
         Self {
             chunk_stream,
             column_types,
             format,
+            time_zone,
         }
     }
 }
@@ -70,9 +79,12 @@ where
             Poll::Pending => Poll::Pending,
             Poll::Ready(chunk) => match chunk {
                 Some(chunk_result) => match chunk_result {
-                    Ok(chunk) => {
-                        Poll::Ready(Some(Ok(to_pg_rows(this.column_types, chunk, *this.format))))
-                    }
+                    Ok(chunk) => Poll::Ready(Some(Ok(to_pg_rows(
+                        this.column_types,
+                        chunk,
+                        *this.format,
+                        *this.time_zone,
+                    )))),
                     Err(err) => Poll::Ready(Some(Err(err))),
                 },
                 None => Poll::Ready(None),
@@ -81,14 +93,22 @@ where
     }
 }
 
-/// Format scalars according to postgres convention.
-fn pg_value_format(data_type: &DataType, d: ScalarRefImpl<'_>, format: bool) -> Bytes {
+/// Format scalars according to postgres convention. `timestamptz` values are printed in the
+/// session time zone.
+fn pg_value_format(
+    data_type: &DataType,
+    d: ScalarRefImpl<'_>,
+    format: bool,
+    time_zone: TimeZone,
+) -> Bytes {
     // format == false means TEXT format
     // format == true means BINARY format
     if !format {
         match (data_type, d) {
             (DataType::Boolean, ScalarRefImpl::Bool(b)) => if b { "t" } else { "f" }.into(),
-            (DataType::Timestampz, ScalarRefImpl::Int64(us)) => timestampz_to_utc_string(us).into(),
+            (DataType::Timestampz, ScalarRefImpl::Int64(us)) => {
+                timestampz_to_local_string(us, time_zone.tz()).into()
+            }
             _ => d.to_string().into(),
         }
     } else {
@@ -99,14 +119,19 @@ fn pg_value_format(data_type: &DataType, d: ScalarRefImpl<'_>, format: bool) ->
     }
 }
 
-fn to_pg_rows(column_types: &[DataType], chunk: DataChunk, format: bool) -> Vec<Row> {
+fn to_pg_rows(
+    column_types: &[DataType],
+    chunk: DataChunk,
+    format: bool,
+    time_zone: TimeZone,
+) -> Vec<Row> {
     chunk
         .rows()
         .map(|r| {
             Row::new(
                 r.values()
                     .zip_eq(column_types)
-                    .map(|(data, t)| data.map(|data| pg_value_format(t, data, format)))
+                    .map(|(data, t)| data.map(|data| pg_value_format(t, data, format, time_zone)))
                     .collect_vec(),
             )
         })
@@ -153,6 +178,8 @@ pub fn data_type_to_type_oid(data_type: DataType) -> TypeOid {
         DataType::Timestampz => TypeOid::Timestamptz,
         DataType::Decimal => TypeOid::Decimal,
         DataType::Interval => TypeOid::Interval,
+        DataType::Bytea => TypeOid::Bytea,
+        DataType::Jsonb => TypeOid::Jsonb,
         DataType::Struct { .. } => TypeOid::Varchar,
         DataType::List { .. } => TypeOid::Varchar,
     }
@@ -193,6 +220,7 @@ mod tests {
             ],
             chunk,
             false,
+            TimeZone::default(),
         );
         let expected: Vec<Vec<Option<Bytes>>> = vec![
             vec![
@@ -222,7 +250,9 @@ mod tests {
     fn test_value_format() {
         use {DataType as T, ScalarRefImpl as S};
 
-        let f = pg_value_format;
+        fn f(data_type: &DataType, d: ScalarRefImpl<'_>, format: bool) -> Bytes {
+            pg_value_format(data_type, d, format, TimeZone::default())
+        }
         assert_eq!(&f(&T::Float32, S::Float32(1_f32.into()), false), "1");
         assert_eq!(&f(&T::Float32, S::Float32(f32::NAN.into()), false), "NaN");
         assert_eq!(&f(&T::Float64, S::Float64(f64::NAN.into()), false), "NaN");
@@ -244,5 +274,15 @@ mod tests {
         );
         assert_eq!(&f(&T::Boolean, S::Bool(true), false), "t");
         assert_eq!(&f(&T::Boolean, S::Bool(false), false), "f");
+        assert_eq!(
+            &f(&T::Timestampz, S::Int64(0), false),
+            "1970-01-01 00:00:00+00:00"
+        );
+
+        let shanghai = TimeZone::try_from(["Asia/Shanghai"].as_slice()).unwrap();
+        assert_eq!(
+            &pg_value_format(&T::Timestampz, S::Int64(0), false, shanghai),
+            "1970-01-01 08:00:00+08:00"
+        );
     }
 }
@@ -53,7 +53,8 @@ impl ObserverState for FrontendObserverNode {
             | Info::Table(_)
             | Info::Source(_)
             | Info::Index(_)
-            | Info::Sink(_) => {
+            | Info::Sink(_)
+            | Info::View(_) => {
                 self.handle_catalog_notification(resp);
             }
             Info::Node(node) => {
@@ -103,6 +104,9 @@ impl ObserverState for FrontendObserverNode {
                 for index in snapshot.indexes {
                     catalog_guard.create_index(&index)
                 }
+                for view in snapshot.views {
+                    catalog_guard.create_view(view)
+                }
                 self.worker_node_manager.refresh(
                     snapshot.nodes,
                     snapshot
@@ -184,6 +188,7 @@ impl FrontendObserverNode {
                 Operation::Delete => {
                     catalog_guard.drop_source(source.database_id, source.schema_id, source.id)
                 }
+                Operation::Update => catalog_guard.update_source(source),
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
             Info::Sink(sink) => match resp.operation() {
@@ -193,6 +198,13 @@ impl FrontendObserverNode {
                 }
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
+            Info::View(view) => match resp.operation() {
+                Operation::Add => catalog_guard.create_view(view.clone()),
+                Operation::Delete => {
+                    catalog_guard.drop_view(view.database_id, view.schema_id, view.id)
+                }
+                _ => panic!("receive an unsupported notify {:?}", resp),
+            },
             Info::Index(index) => match resp.operation() {
                 Operation::Add => catalog_guard.create_index(index),
                 Operation::Delete => {
@@ -32,8 +32,10 @@ use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
 
 use self::heuristic::{ApplyOrder, HeuristicOptimizer};
-use self::plan_node::{BatchProject, Convention, LogicalProject, StreamMaterialize};
-use self::plan_visitor::{has_batch_seq_scan, has_batch_seq_scan_where, has_logical_over_agg};
+use self::plan_node::{
+    BatchProject, Convention, LogicalFilter, LogicalProject, PlanTreeNodeUnary, StreamMaterialize,
+};
+use self::plan_visitor::{has_batch_seq_scan, has_batch_seq_scan_where};
 use self::property::RequiredDist;
 use self::rule::*;
 use crate::optimizer::max_one_row_visitor::HasMaxOneRowApply;
@@ -315,14 +317,6 @@ impl PlanRoot {
             ],
             ApplyOrder::TopDown,
         );
-        if has_logical_over_agg(plan.clone()) {
-            return Err(ErrorCode::InternalError(format!(
-                "OverAgg can not be transformed. Plan:\n{}",
-                plan.explain_to_string().unwrap()
-            ))
-            .into());
-        }
-
         Ok(plan)
     }
 
@@ -433,7 +427,10 @@ impl PlanRoot {
 
         let mut plan = match self.plan.convention() {
             Convention::Logical => {
-                let plan = self.gen_optimized_logical_plan()?;
+                let mut plan = self.gen_optimized_logical_plan()?;
+                if ctx.is_emit_on_window_close() {
+                    plan = Self::apply_emit_on_window_close(plan)?;
+                }
                 let (plan, out_col_change) = plan.logical_rewrite_for_stream()?;
 
                 if explain_trace {
@@ -472,6 +469,27 @@ impl PlanRoot {
         Ok(plan)
     }
 
+    /// Applies `EMIT ON WINDOW CLOSE` to the top-level aggregation of the plan, i.e. the one whose
+    /// output reaches the root only through projections and filters. Other aggregations still
+    /// emit their results eagerly.
+    fn apply_emit_on_window_close(plan: PlanRef) -> Result<PlanRef> {
+        if let Some(agg) = plan.as_logical_agg() {
+            Ok(agg.clone().with_emit_on_window_close(true).into())
+        } else if let Some(project) = plan.as_logical_project() {
+            let input = Self::apply_emit_on_window_close(project.input())?;
+            Ok(LogicalProject::clone_with_input(project, input).into())
+        } else if let Some(filter) = plan.as_logical_filter() {
+            let input = Self::apply_emit_on_window_close(filter.input())?;
+            Ok(LogicalFilter::clone_with_input(filter, input).into())
+        } else {
+            Err(ErrorCode::InvalidInputSyntax(
+                "EMIT ON WINDOW CLOSE requires an aggregation at the top level of the query"
+                    .to_string(),
+            )
+            .into())
+        }
+    }
+
     /// Optimize and generate a create materialize view plan.
     pub fn gen_create_mv_plan(
         &mut self,
@@ -0,0 +1,98 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::OverWindowNode;
+
+use super::{
+    LogicalOverAgg, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch,
+};
+use crate::optimizer::plan_node::ToLocalBatch;
+use crate::optimizer::property::{Order, RequiredDist};
+
+/// `BatchOverWindow` implements [`super::LogicalOverAgg`] by sorting the input by the partition key
+/// and evaluating the window function partition by partition.
+#[derive(Debug, Clone)]
+pub struct BatchOverWindow {
+    pub base: PlanBase,
+    logical: LogicalOverAgg,
+}
+
+impl BatchOverWindow {
+    pub fn new(logical: LogicalOverAgg) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            logical.input().distribution().clone(),
+            Order::any(),
+        );
+        BatchOverWindow { base, logical }
+    }
+}
+
+impl fmt::Display for BatchOverWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchOverWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for BatchOverWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! {BatchOverWindow}
+
+impl ToDistributedBatch for BatchOverWindow {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        let input = self.input().to_distributed()?;
+        let input = self
+            .logical
+            .required_input_dist()
+            .enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(self.clone_with_input(input).into())
+    }
+}
+
+impl ToBatchProst for BatchOverWindow {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        let window_function = &self.logical.window_function;
+        NodeBody::OverWindow(OverWindowNode {
+            calls: vec![window_function.to_protobuf()],
+            partition_by: window_function
+                .partition_key_indices()
+                .into_iter()
+                .map(|idx| idx as u32)
+                .collect(),
+            order_by: window_function.order_by_to_protobuf(),
+        })
+    }
+}
+
+impl ToLocalBatch for BatchOverWindow {
+    fn to_local(&self) -> Result<PlanRef> {
+        let input = self.input().to_local()?;
+        let input = RequiredDist::single().enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(self.clone_with_input(input).into())
+    }
+}
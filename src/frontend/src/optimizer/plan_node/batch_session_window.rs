@@ -0,0 +1,92 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::SessionWindowNode;
+
+use super::{
+    LogicalSessionWindow, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch,
+};
+use crate::optimizer::plan_node::ToLocalBatch;
+use crate::optimizer::property::{Order, RequiredDist};
+
+/// `BatchSessionWindow` implements [`super::LogicalSessionWindow`] by sorting all input rows by
+/// time on a single node.
+#[derive(Debug, Clone)]
+pub struct BatchSessionWindow {
+    pub base: PlanBase,
+    logical: LogicalSessionWindow,
+}
+
+impl BatchSessionWindow {
+    pub fn new(logical: LogicalSessionWindow) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            logical.input().distribution().clone(),
+            Order::any(),
+        );
+        BatchSessionWindow { base, logical }
+    }
+}
+
+impl fmt::Display for BatchSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchSessionWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for BatchSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! {BatchSessionWindow}
+
+impl ToDistributedBatch for BatchSessionWindow {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        let input = self.input().to_distributed()?;
+        let input = self
+            .logical
+            .required_input_dist()
+            .enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(self.clone_with_input(input).into())
+    }
+}
+
+impl ToBatchProst for BatchSessionWindow {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::SessionWindow(SessionWindowNode {
+            time_col: Some(self.logical.time_col.to_proto()),
+            gap: Some(self.logical.gap.into()),
+        })
+    }
+}
+
+impl ToLocalBatch for BatchSessionWindow {
+    fn to_local(&self) -> Result<PlanRef> {
+        let input = self.input().to_local()?;
+        let input = RequiredDist::single().enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(self.clone_with_input(input).into())
+    }
+}
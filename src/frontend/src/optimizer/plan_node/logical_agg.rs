@@ -30,6 +30,7 @@ use super::{
     PlanTreeNodeUnary, PredicatePushdown, StreamGlobalSimpleAgg, StreamHashAgg,
     StreamLocalSimpleAgg, StreamProject, ToBatch, ToStream,
 };
+use crate::catalog::source_catalog::SourceCatalogInfo;
 use crate::catalog::table_catalog::TableCatalog;
 use crate::expr::{
     AggCall, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Literal, OrderBy,
@@ -53,6 +54,9 @@ use crate::utils::{ColIndexMapping, Condition, Substitute};
 pub struct LogicalAgg {
     pub base: PlanBase,
     core: generic::Agg<PlanRef>,
+    /// Whether the result of a window is only emitted once it's closed, which is only set for the
+    /// top-level aggregation of a materialized view with `EMIT ON WINDOW CLOSE`.
+    emit_on_window_close: bool,
 }
 
 pub enum AggCallState {
@@ -279,6 +283,7 @@ impl LogicalAgg {
         let local_agg = StreamHashAgg::new(
             LogicalAgg::new(self.agg_calls().to_vec(), local_group_key, project.into()),
             Some(vnode_col_idx),
+            false,
         );
 
         if self.group_key().is_empty() {
@@ -313,6 +318,7 @@ impl LogicalAgg {
                     exchange,
                 ),
                 None,
+                false,
             );
             Ok(global_agg.into())
         }
@@ -329,6 +335,7 @@ impl LogicalAgg {
                         .enforce_if_not_satisfies(stream_input, &Order::any())?,
                 ),
                 None,
+                self.emit_on_window_close,
             )
             .into());
         }
@@ -755,7 +762,17 @@ impl LogicalAgg {
             group_key,
             input,
         };
-        Self { base, core }
+        Self {
+            base,
+            core,
+            emit_on_window_close: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_emit_on_window_close(mut self, emit_on_window_close: bool) -> Self {
+        self.emit_on_window_close = emit_on_window_close;
+        self
     }
 
     /// get the Mapping of columnIndex from input column index to output column index,if a input
@@ -898,7 +915,7 @@ impl LogicalAgg {
             .cloned()
             .map(|key| input_col_change.map(key))
             .collect();
-        Self::new(agg_calls, group_key, input)
+        Self::new(agg_calls, group_key, input).with_emit_on_window_close(self.emit_on_window_close)
     }
 
     pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
@@ -918,6 +935,7 @@ impl PlanTreeNodeUnary for LogicalAgg {
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
         Self::new(self.agg_calls().to_vec(), self.group_key().to_vec(), input)
+            .with_emit_on_window_close(self.emit_on_window_close)
     }
 
     #[must_use]
@@ -1099,7 +1117,36 @@ impl ToStream for LogicalAgg {
             .chain(self.agg_calls().iter().cloned())
             .collect_vec();
 
-        let logical_agg = LogicalAgg::new(agg_calls, self.group_key().to_vec(), self.input());
+        let mut group_key = self.group_key().to_vec();
+        if self.emit_on_window_close {
+            // The windows are closed by the watermark on the first group key, so a window column
+            // with watermarks is moved to the front.
+            let window_columns = derive_window_columns(&self.input());
+            if !group_key.iter().any(|idx| window_columns.contains(*idx)) {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "EMIT ON WINDOW CLOSE requires the GROUP BY to contain a window column, i.e. \
+                     `window_start` or `window_end` of TUMBLE or HOP"
+                        .to_string(),
+                )
+                .into());
+            }
+            let watermark_columns = derive_watermark_columns(&self.input());
+            let Some(pos) = group_key.iter().position(|idx| {
+                window_columns.contains(*idx) && watermark_columns.contains(*idx)
+            }) else {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "EMIT ON WINDOW CLOSE requires the time column of the window to have a \
+                     watermark defined on the source"
+                        .to_string(),
+                )
+                .into());
+            };
+            group_key[..=pos].rotate_right(1);
+            output_indices[..=pos].rotate_left(1);
+        }
+
+        let logical_agg = LogicalAgg::new(agg_calls, group_key, self.input())
+            .with_emit_on_window_close(self.emit_on_window_close);
         let stream_agg = logical_agg.gen_dist_stream_agg_plan(self.input().to_stream()?)?;
 
         let stream_project = StreamProject::new(LogicalProject::with_out_col_idx(
@@ -1118,6 +1165,83 @@ impl ToStream for LogicalAgg {
     }
 }
 
+/// Returns the columns of `plan` that are the start or end of the time windows generated by
+/// `TUMBLE` or `HOP`, which may be shifted by constants.
+fn derive_window_columns(plan: &PlanRef) -> FixedBitSet {
+    let mut window_columns = FixedBitSet::with_capacity(plan.schema().len());
+    if let Some(project) = plan.as_logical_project() {
+        let input_window_columns = derive_window_columns(&project.input());
+        for (idx, expr) in project.exprs().iter().enumerate() {
+            window_columns.set(idx, is_window_expr(expr, &input_window_columns));
+        }
+    } else if let Some(hop_window) = plan.as_logical_hop_window() {
+        let input_window_columns = derive_window_columns(&hop_window.input());
+        let o2i = hop_window.o2i_col_mapping();
+        for idx in 0..plan.schema().len() {
+            if let Some(input_idx) = o2i.try_map(idx) {
+                window_columns.set(idx, input_window_columns.contains(input_idx));
+            }
+        }
+        window_columns.extend(hop_window.window_output_indices());
+    } else if let Some(filter) = plan.as_logical_filter() {
+        window_columns = derive_window_columns(&filter.input());
+    }
+    window_columns
+}
+
+/// Returns the columns of `plan` with watermarks, which are generated by the watermark definitions
+/// of sources and derived through monotonic expressions and time windows.
+fn derive_watermark_columns(plan: &PlanRef) -> FixedBitSet {
+    let mut watermark_columns = FixedBitSet::with_capacity(plan.schema().len());
+    if let Some(source) = plan.as_logical_source() {
+        if let SourceCatalogInfo::StreamSource(info) = &source.source_catalog().info {
+            watermark_columns.extend(
+                info.watermark_descs
+                    .iter()
+                    .map(|desc| desc.watermark_idx as usize),
+            );
+        }
+    } else if let Some(project) = plan.as_logical_project() {
+        let input_watermark_columns = derive_watermark_columns(&project.input());
+        for (idx, expr) in project.exprs().iter().enumerate() {
+            if let Some(input_idx) = expr.as_monotonic_input_ref() {
+                watermark_columns.set(idx, input_watermark_columns.contains(input_idx));
+            }
+        }
+    } else if let Some(hop_window) = plan.as_logical_hop_window() {
+        let input_watermark_columns = derive_watermark_columns(&hop_window.input());
+        let o2i = hop_window.o2i_col_mapping();
+        for idx in 0..plan.schema().len() {
+            if let Some(input_idx) = o2i.try_map(idx) {
+                watermark_columns.set(idx, input_watermark_columns.contains(input_idx));
+            }
+        }
+        if input_watermark_columns.contains(hop_window.core.time_col.index) {
+            watermark_columns.extend(hop_window.window_output_indices());
+        }
+    } else if let Some(filter) = plan.as_logical_filter() {
+        watermark_columns = derive_watermark_columns(&filter.input());
+    }
+    watermark_columns
+}
+
+fn is_window_expr(expr: &ExprImpl, input_window_columns: &FixedBitSet) -> bool {
+    match expr {
+        ExprImpl::InputRef(input_ref) => input_window_columns.contains(input_ref.index),
+        ExprImpl::FunctionCall(function_call) => {
+            match (function_call.get_expr_type(), function_call.inputs()) {
+                (ExprType::TumbleStart, _) => true,
+                (ExprType::Add | ExprType::Subtract, [x, c]) if c.is_const() => {
+                    is_window_expr(x, input_window_columns)
+                }
+                (ExprType::Add, [c, x]) if c.is_const() => is_window_expr(x, input_window_columns),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::rc::Rc;
@@ -146,6 +146,17 @@ impl LogicalHopWindow {
         self.window_start_col_idx() + 1
     }
 
+    /// Returns the output indices of `window_start` and `window_end`.
+    pub fn window_output_indices(&self) -> Vec<usize> {
+        self.core
+            .output_indices
+            .iter()
+            .positions(|&idx| {
+                idx == self.window_start_col_idx() || idx == self.window_end_col_idx()
+            })
+            .collect()
+    }
+
     pub fn o2i_col_mapping(&self) -> ColIndexMapping {
         self.output2internal_col_mapping()
             .composite(&self.internal2input_col_mapping())
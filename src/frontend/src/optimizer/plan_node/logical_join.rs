@@ -30,7 +30,7 @@ use crate::optimizer::max_one_row_visitor::MaxOneRowVisitor;
 use crate::optimizer::plan_node::utils::IndicesDisplay;
 use crate::optimizer::plan_node::{
     BatchFilter, BatchHashJoin, BatchLookupJoin, BatchNestedLoopJoin, EqJoinPredicate,
-    LogicalFilter, StreamDynamicFilter, StreamFilter,
+    LogicalFilter, StreamDynamicFilter, StreamFilter, StreamIntervalJoin,
 };
 use crate::optimizer::plan_visitor::PlanVisitor;
 use crate::optimizer::property::{Distribution, FunctionalDependencySet, Order, RequiredDist};
@@ -997,6 +997,19 @@ impl ToStream for LogicalJoin {
             // Convert to Hash Join for equal joins
             // For inner joins, pull non-equal conditions to a filter operator on top of it
             let pull_filter = self.join_type() == JoinType::Inner && predicate.has_non_eq();
+
+            // Convert to Interval Join for inner joins of append-only streams with a time band,
+            // whose states can be evicted as the time advances.
+            if pull_filter
+                && logical_join.left().append_only()
+                && logical_join.right().append_only()
+                && predicate.null_safes().iter().all(|null_safe| !null_safe)
+            {
+                if let Some(band) = StreamIntervalJoin::derive_band(&logical_join, &predicate) {
+                    return Ok(StreamIntervalJoin::new(logical_join, predicate, band).into());
+                }
+            }
+
             if pull_filter {
                 let default_indices = (0..self.internal_column_num()).collect::<Vec<_>>();
 
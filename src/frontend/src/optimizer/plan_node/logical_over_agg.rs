@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 use fixedbitset::FixedBitSet;
@@ -19,24 +20,86 @@ use itertools::Itertools;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_expr::expr::AggKind;
+use risingwave_pb::expr::agg_call::Type as ProstAggType;
+use risingwave_pb::expr::WindowFunction as ProstWindowFunction;
+use risingwave_pb::plan_common::ColumnOrder;
 
 use super::generic::{PlanAggOrderByField, PlanAggOrderByFieldDisplay};
+use super::utils::TableCatalogBuilder;
 use super::{
-    gen_filter_and_pushdown, ColPrunable, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchOverWindow, ColPrunable, LogicalProject, LogicalProjectBuilder,
+    PlanBase, PlanRef, PlanTreeNodeUnary, PredicatePushdown, StreamOverWindow, ToBatch, ToStream,
 };
-use crate::expr::{Expr, ExprImpl, InputRef, InputRefDisplay, WindowFunction, WindowFunctionType};
+use crate::expr::{
+    AggCall, Expr, ExprImpl, ExprRewriter, ExprType, ExprVisitor, FunctionCall, InputRef,
+    InputRefDisplay, WindowFrame, WindowFunction, WindowFunctionType,
+};
+use crate::optimizer::property::{Order, RequiredDist};
 use crate::utils::{ColIndexMapping, Condition};
+use crate::TableCatalog;
 
 /// Rewritten version of [`WindowFunction`] which uses `InputRef` instead of `ExprImpl`.
 #[derive(Debug, Clone)]
 pub struct PlanWindowFunction {
     pub function_type: WindowFunctionType,
     pub return_type: DataType,
+    pub args: Vec<InputRef>,
     pub partition_by: Vec<InputRef>,
     /// TODO: rename & move `PlanAggOrderByField` so that it can be better shared like
     /// [`crate::expr::OrderByExpr`]
     pub order_by: Vec<PlanAggOrderByField>,
+    pub frame: WindowFrame,
+}
+
+impl PlanWindowFunction {
+    pub fn to_protobuf(&self) -> ProstWindowFunction {
+        let aggregate_type = match self.function_type {
+            WindowFunctionType::Aggregate(agg_kind) => agg_kind.to_prost(),
+            _ => ProstAggType::Unspecified,
+        };
+        ProstWindowFunction {
+            r#type: self.function_type.to_protobuf() as i32,
+            aggregate_type: aggregate_type as i32,
+            args: self.args.iter().map(InputRef::to_agg_arg_proto).collect(),
+            return_type: Some(self.return_type.to_protobuf()),
+            frame: Some(self.frame.to_protobuf()),
+        }
+    }
+
+    pub fn partition_key_indices(&self) -> Vec<usize> {
+        self.partition_by.iter().map(|i| i.index).collect()
+    }
+
+    pub fn order_by_to_protobuf(&self) -> Vec<ColumnOrder> {
+        self.order_by
+            .iter()
+            .map(|f| ColumnOrder {
+                order_type: f.direction.to_protobuf() as i32,
+                index: f.input.index as u32,
+            })
+            .collect()
+    }
+
+    fn rewrite_with_col_index_mapping(&self, mapping: &ColIndexMapping) -> Self {
+        let rewrite_input_ref = |input_ref: &InputRef| {
+            InputRef::new(mapping.map(input_ref.index), input_ref.data_type.clone())
+        };
+        Self {
+            args: self.args.iter().map(rewrite_input_ref).collect(),
+            partition_by: self.partition_by.iter().map(rewrite_input_ref).collect(),
+            order_by: self
+                .order_by
+                .iter()
+                .map(|f| PlanAggOrderByField {
+                    input: rewrite_input_ref(&f.input),
+                    ..f.clone()
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
 }
 
 struct PlanWindowFunctionDisplay<'a> {
@@ -51,11 +114,26 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
             f.debug_struct("WindowFunction")
                 .field("function_type", &window_function.function_type)
                 .field("return_type", &window_function.return_type)
+                .field("args", &window_function.args)
                 .field("partition_by", &window_function.partition_by)
                 .field("order_by", &window_function.order_by)
+                .field("frame", &format_args!("{}", window_function.frame))
                 .finish()
         } else {
-            write!(f, "{}() OVER(", window_function.function_type)?;
+            write!(
+                f,
+                "{}({}) OVER(",
+                window_function.function_type,
+                window_function
+                    .args
+                    .iter()
+                    .format_with(", ", |input_ref, f| {
+                        f(&InputRefDisplay {
+                            input_ref,
+                            input_schema: self.input_schema,
+                        })
+                    })
+            )?;
 
             let mut delim = "";
             if !window_function.partition_by.is_empty() {
@@ -75,6 +153,7 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
                 )?;
             }
             if !window_function.order_by.is_empty() {
+                delim = " ";
                 write!(
                     f,
                     "{delim}ORDER BY {}",
@@ -89,6 +168,9 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
                     })
                 )?;
             }
+            if window_function.frame != WindowFrame::default() {
+                write!(f, "{delim}{}", window_function.frame)?;
+            }
             f.write_str(")")?;
 
             Ok(())
@@ -98,7 +180,8 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
 
 /// `LogicalOverAgg` performs `OVER` window aggregates ([`WindowFunction`]) to its input.
 ///
-/// The output schema is the input schema plus the window functions.
+/// The output schema is the input schema plus the window function. Multiple window functions in
+/// a query are planned as a chain of `LogicalOverAgg`s, each appending one column.
 #[derive(Debug, Clone)]
 pub struct LogicalOverAgg {
     pub base: PlanBase,
@@ -106,6 +189,33 @@ pub struct LogicalOverAgg {
     input: PlanRef,
 }
 
+/// Collects the distinct window functions in expressions.
+#[derive(Default)]
+struct WindowFunctionCollector {
+    window_funcs: Vec<WindowFunction>,
+}
+
+impl ExprVisitor<()> for WindowFunctionCollector {
+    fn merge(_: (), _: ()) {}
+
+    fn visit_window_function(&mut self, window_func: &WindowFunction) {
+        if !self.window_funcs.contains(window_func) {
+            self.window_funcs.push(window_func.clone());
+        }
+    }
+}
+
+/// Replaces window functions in expressions with the output columns of `LogicalOverAgg`s.
+struct WindowFunctionReplacer {
+    outputs: HashMap<WindowFunction, ExprImpl>,
+}
+
+impl ExprRewriter for WindowFunctionReplacer {
+    fn rewrite_window_function(&mut self, window_func: WindowFunction) -> ExprImpl {
+        self.outputs[&window_func].clone()
+    }
+}
+
 impl LogicalOverAgg {
     fn new(window_function: PlanWindowFunction, input: PlanRef) -> Self {
         let ctx = input.ctx();
@@ -131,103 +241,216 @@ impl LogicalOverAgg {
         }
     }
 
-    pub fn create(
-        input: PlanRef,
-        mut select_exprs: Vec<ExprImpl>,
-    ) -> Result<(PlanRef, Vec<ExprImpl>)> {
-        let input_len = input.schema().len();
-        let mut window_funcs = vec![];
-        for expr in &mut select_exprs {
-            if let ExprImpl::WindowFunction(f) = expr {
-                window_funcs.push(*(f.clone()));
-                *expr = InputRef::new(
-                    input_len + window_funcs.len() - 1,
-                    expr.return_type().clone(),
-                )
-                .into();
-            }
-            if expr.has_window_function() {
-                return Err(ErrorCode::NotImplemented(
-                    format!("window function in expression: {:?}", expr),
+    pub fn create(input: PlanRef, select_exprs: Vec<ExprImpl>) -> Result<(PlanRef, Vec<ExprImpl>)> {
+        let mut collector = WindowFunctionCollector::default();
+        select_exprs
+            .iter()
+            .for_each(|expr| collector.visit_expr(expr));
+
+        // Arguments, `PARTITION BY` and `ORDER BY` expressions which are not columns of the input
+        // are computed by a project below the window functions.
+        let mut input_proj_builder = LogicalProjectBuilder::default();
+        for (idx, field) in input.schema().fields().iter().enumerate() {
+            input_proj_builder
+                .add_expr(&InputRef::new(idx, field.data_type()).into())
+                .unwrap();
+        }
+        let mut add_input_ref = |expr: &ExprImpl, clause: &str| -> Result<InputRef> {
+            let index = input_proj_builder.add_expr(expr).map_err(|err| {
+                ErrorCode::NotImplemented(
+                    format!("{err} inside window function {clause}"),
                     None.into(),
                 )
+            })?;
+            Ok(InputRef::new(index, expr.return_type()))
+        };
+
+        let mut plan_window_funcs: Vec<PlanWindowFunction> = vec![];
+        // The positions of the plan window functions computing each window function. `AVG` is
+        // computed as `SUM / COUNT`.
+        let mut window_func_positions: Vec<(WindowFunction, Vec<usize>)> = vec![];
+        for f in collector.window_funcs {
+            if f.function_type.is_rank_function() && f.order_by.sort_exprs.is_empty() {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "window rank function without order by: {:?}",
+                    f
+                ))
                 .into());
             }
-        }
-        for f in &window_funcs {
-            if f.function_type.is_rank_function() {
-                if f.order_by.sort_exprs.is_empty() {
-                    return Err(ErrorCode::InvalidInputSyntax(format!(
-                        "window rank function without order by: {:?}",
-                        f
-                    ))
-                    .into());
-                }
-                if f.function_type == WindowFunctionType::DenseRank {
-                    return Err(ErrorCode::NotImplemented(
-                        format!("window rank function: {}", f.function_type),
-                        4847.into(),
-                    )
-                    .into());
-                }
+            let args: Vec<_> = f
+                .args
+                .iter()
+                .map(|e| add_input_ref(e, "arguments"))
+                .try_collect()?;
+            let partition_by: Vec<_> = f
+                .partition_by
+                .iter()
+                .map(|e| add_input_ref(e, "PARTITION BY"))
+                .try_collect()?;
+            let order_by: Vec<_> = f
+                .order_by
+                .sort_exprs
+                .iter()
+                .map(|e| -> Result<_> {
+                    Ok(PlanAggOrderByField {
+                        input: add_input_ref(&e.expr, "ORDER BY")?,
+                        direction: e.direction,
+                        nulls_first: e.nulls_first,
+                    })
+                })
+                .try_collect()?;
+
+            let plan_window_func =
+                |function_type: WindowFunctionType, return_type| PlanWindowFunction {
+                    function_type,
+                    return_type,
+                    args: args.clone(),
+                    partition_by: partition_by.clone(),
+                    order_by: order_by.clone(),
+                    frame: f.frame,
+                };
+            if f.function_type == WindowFunctionType::Aggregate(AggKind::Avg) {
+                let arg_types = args.iter().map(|i| i.data_type.clone()).collect_vec();
+                let sum_type = AggCall::infer_return_type(&AggKind::Sum, &arg_types)?;
+                let count_type = AggCall::infer_return_type(&AggKind::Count, &arg_types)?;
+                plan_window_funcs.push(plan_window_func(
+                    WindowFunctionType::Aggregate(AggKind::Sum),
+                    sum_type,
+                ));
+                plan_window_funcs.push(plan_window_func(
+                    WindowFunctionType::Aggregate(AggKind::Count),
+                    count_type,
+                ));
+                let positions = vec![plan_window_funcs.len() - 2, plan_window_funcs.len() - 1];
+                window_func_positions.push((f, positions));
+            } else {
+                plan_window_funcs.push(plan_window_func(f.function_type, f.return_type.clone()));
+                window_func_positions.push((f, vec![plan_window_funcs.len() - 1]));
             }
         }
-        if window_funcs.len() > 1 {
-            return Err(ErrorCode::NotImplemented(
-                "Multiple window functions".to_string(),
-                None.into(),
+
+        let input_len = input.schema().len();
+        let output_offset = input_proj_builder.exprs_len();
+        let input_ref_of = |pos: usize| {
+            InputRef::new(
+                output_offset + pos,
+                plan_window_funcs[pos].return_type.clone(),
             )
-            .into());
-        }
-        let WindowFunction {
-            args,
-            return_type,
-            function_type,
-            partition_by,
-            order_by,
-        } = window_funcs.into_iter().next().unwrap();
-        assert!(args.is_empty());
-        assert!(return_type == DataType::Int64);
-
-        // TODO: rewrite ORDER BY & PARTITION BY expr to InputRef like `LogicalAgg`
-        let order_by = order_by
-            .sort_exprs
+        };
+        let outputs = window_func_positions
             .into_iter()
-            .map(|e| match e.expr.as_input_ref() {
-                Some(i) => Ok(PlanAggOrderByField {
-                    input: *i.clone(),
-                    direction: e.direction,
-                    nulls_first: e.nulls_first,
-                }),
-                None => Err(ErrorCode::NotImplemented(
-                    "ORDER BY expression in window function".to_string(),
-                    None.into(),
-                )
-                .into()),
+            .map(|(f, positions)| {
+                let output = match positions[..] {
+                    [pos] => input_ref_of(pos).into(),
+                    [sum_pos, count_pos] => {
+                        // Rewrite avg to cast(sum as avg_return_type) / count.
+                        let sum = ExprImpl::from(input_ref_of(sum_pos))
+                            .cast_implicit(f.return_type.clone())
+                            .unwrap();
+                        FunctionCall::new(
+                            ExprType::Divide,
+                            vec![sum, input_ref_of(count_pos).into()],
+                        )
+                        .unwrap()
+                        .into()
+                    }
+                    _ => unreachable!(),
+                };
+                (f, output)
             })
-            .collect::<Result<Vec<_>>>()?;
-        let partition_by = partition_by
+            .collect();
+
+        let mut plan = if output_offset > input_len {
+            input_proj_builder.build(input).into()
+        } else {
+            input
+        };
+        for window_function in plan_window_funcs {
+            plan = Self::new(window_function, plan).into();
+        }
+
+        let mut replacer = WindowFunctionReplacer { outputs };
+        let select_exprs = select_exprs
             .into_iter()
-            .map(|e| match e.as_input_ref() {
-                Some(i) => Ok(*i.clone()),
-                None => Err(ErrorCode::NotImplemented(
-                    "PARTITION BY expression in window function".to_string(),
-                    None.into(),
-                )
-                .into()),
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        let over_agg = Self::new(
-            PlanWindowFunction {
-                function_type,
-                return_type,
-                partition_by,
-                order_by,
+            .map(|expr| replacer.rewrite_expr(expr))
+            .collect();
+        Ok((plan, select_exprs))
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let mut builder = f.debug_struct(name);
+        builder.field(
+            "window_function",
+            &PlanWindowFunctionDisplay {
+                window_function: &self.window_function,
+                input_schema: self.input.schema(),
             },
-            input,
         );
-        Ok((over_agg.into(), select_exprs))
+        builder.finish()
+    }
+
+    /// The state table of the streaming over window keeps all input rows, ordered by the
+    /// partition key, then the `ORDER BY` of the window and finally the stream key, so that each
+    /// partition can be scanned in order by prefix.
+    pub fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let mut internal_table_catalog_builder =
+            TableCatalogBuilder::new(self.ctx().inner().with_options.internal_table_subset());
+
+        self.input.schema().fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        let mut order_cols = HashSet::new();
+        self.window_function
+            .partition_by
+            .iter()
+            .for_each(|input_ref| {
+                if order_cols.insert(input_ref.index) {
+                    internal_table_catalog_builder
+                        .add_order_column(input_ref.index, OrderType::Ascending);
+                }
+            });
+        self.window_function.order_by.iter().for_each(|field| {
+            if order_cols.insert(field.input.index) {
+                internal_table_catalog_builder
+                    .add_order_column(field.input.index, OrderType::from(field.direction));
+            }
+        });
+        self.input.logical_pk().iter().for_each(|idx| {
+            if order_cols.insert(*idx) {
+                internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending);
+            }
+        });
+
+        internal_table_catalog_builder
+            .build(self.input.distribution().dist_column_indices().to_vec())
+    }
+
+    /// The input is sharded by the partition key, or gathered to a single node if there's none.
+    pub fn required_input_dist(&self) -> RequiredDist {
+        let partition_key = self.window_function.partition_key_indices();
+        if partition_key.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::hash_shard(&partition_key)
+        }
+    }
+
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let window_function = self
+            .window_function
+            .rewrite_with_col_index_mapping(&input_col_change);
+        let new_input_len = input.schema().len();
+        let (mut map, _) = input_col_change.into_parts();
+        map.push(Some(new_input_len));
+        (
+            Self::new(window_function, input),
+            ColIndexMapping::with_target_size(map, new_input_len + 1),
+        )
     }
 }
 
@@ -245,15 +468,7 @@ impl_plan_tree_node_for_unary! { LogicalOverAgg }
 
 impl fmt::Display for LogicalOverAgg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut builder = f.debug_struct("LogicalOverAgg");
-        builder.field(
-            "window_function",
-            &PlanWindowFunctionDisplay {
-                window_function: &self.window_function,
-                input_schema: self.input.schema(),
-            },
-        );
-        builder.finish()
+        self.fmt_with_name(f, "LogicalOverAgg")
     }
 }
 
@@ -266,25 +481,38 @@ impl ColPrunable for LogicalOverAgg {
 
 impl PredicatePushdown for LogicalOverAgg {
     fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
-        let mut window_col = FixedBitSet::with_capacity(self.schema().len());
-        window_col.insert(self.schema().len() - 1);
-        let (window_pred, other_pred) = predicate.split_disjoint(&window_col);
+        // Only the predicates on the partition key can be pushed down, as filtering other columns
+        // changes the rows in the window of the remaining ones.
+        let mut non_partition_cols = FixedBitSet::with_capacity(self.schema().len());
+        non_partition_cols.insert_range(..);
+        for input_ref in &self.window_function.partition_by {
+            non_partition_cols.set(input_ref.index, false);
+        }
+        let (window_pred, other_pred) = predicate.split_disjoint(&non_partition_cols);
         gen_filter_and_pushdown(self, window_pred, other_pred)
     }
 }
 
 impl ToBatch for LogicalOverAgg {
     fn to_batch(&self) -> Result<PlanRef> {
-        Err(ErrorCode::NotImplemented("OverAgg to batch".to_string(), 4847.into()).into())
+        let new_input = self.input().to_batch()?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(BatchOverWindow::new(new_logical).into())
     }
 }
 
 impl ToStream for LogicalOverAgg {
     fn to_stream(&self) -> Result<PlanRef> {
-        Err(ErrorCode::NotImplemented("OverAgg to stream".to_string(), 4847.into()).into())
+        let input = self.input().to_stream()?;
+        let input = self
+            .required_input_dist()
+            .enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(StreamOverWindow::new(self.clone_with_input(input)).into())
     }
 
-    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, crate::utils::ColIndexMapping)> {
-        Err(ErrorCode::NotImplemented("OverAgg to stream".to_string(), 4847.into()).into())
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (over_agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((over_agg.into(), out_col_change))
     }
 }
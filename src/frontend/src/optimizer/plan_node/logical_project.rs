@@ -73,6 +73,11 @@ impl LogicalProjectBuilder {
         self.exprs_index.get(expr).copied()
     }
 
+    /// number of expressions added to the builder so far
+    pub fn exprs_len(&self) -> usize {
+        self.exprs.len()
+    }
+
     /// build the `LogicalProject` from `LogicalProjectBuilder`
     pub fn build(self, input: PlanRef) -> LogicalProject {
         LogicalProject::new(input, self.exprs)
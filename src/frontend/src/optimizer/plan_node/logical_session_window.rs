@@ -0,0 +1,191 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::catalog::Field;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, IntervalUnit};
+use risingwave_common::util::sort_util::OrderType;
+
+use super::utils::TableCatalogBuilder;
+use super::{
+    gen_filter_and_pushdown, BatchSessionWindow, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, StreamSessionWindow, ToBatch, ToStream,
+};
+use crate::expr::{InputRef, InputRefDisplay};
+use crate::optimizer::property::{Order, RequiredDist};
+use crate::utils::{ColIndexMapping, Condition};
+use crate::TableCatalog;
+
+/// `LogicalSessionWindow` implements the `SESSION` window table function. Rows whose `time_col`
+/// are less than `gap` apart are grouped into the same session, and the `window_start` and
+/// `window_end` of the session are appended to each row.
+///
+/// `window_start` is the earliest time of the session, and `window_end` is the latest time plus
+/// `gap`. Rows with null `time_col` have null windows.
+#[derive(Debug, Clone)]
+pub struct LogicalSessionWindow {
+    pub base: PlanBase,
+    input: PlanRef,
+    pub(super) time_col: InputRef,
+    pub(super) gap: IntervalUnit,
+}
+
+impl LogicalSessionWindow {
+    fn new(input: PlanRef, time_col: InputRef, gap: IntervalUnit) -> Self {
+        let ctx = input.ctx();
+        let output_type = DataType::window_of(&time_col.data_type).unwrap();
+        let mut schema = input.schema().clone();
+        schema.fields.extend([
+            Field::with_name(output_type.clone(), "window_start"),
+            Field::with_name(output_type, "window_end"),
+        ]);
+
+        let logical_pk = input.logical_pk().to_vec();
+
+        let mapping =
+            ColIndexMapping::identity_or_none(input.schema().len(), input.schema().len() + 2);
+        let fd_set = input.functional_dependency().clone();
+        let fd_set = mapping.rewrite_functional_dependency_set(fd_set);
+
+        let base = PlanBase::new_logical(ctx, schema, logical_pk, fd_set);
+
+        Self {
+            base,
+            input,
+            time_col,
+            gap,
+        }
+    }
+
+    pub fn create(input: PlanRef, time_col: InputRef, gap: IntervalUnit) -> PlanRef {
+        Self::new(input, time_col, gap).into()
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ time_col: {}, gap: {} }}",
+            name,
+            InputRefDisplay {
+                input_ref: &self.time_col,
+                input_schema: self.input.schema()
+            },
+            self.gap,
+        )
+    }
+
+    /// The state table of the streaming session window keeps all input rows with non-null time,
+    /// ordered by the time and then the stream key, so that the rows of a session can be scanned
+    /// by a range of time.
+    pub fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let mut internal_table_catalog_builder =
+            TableCatalogBuilder::new(self.ctx().inner().with_options.internal_table_subset());
+
+        self.input.schema().fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        internal_table_catalog_builder.add_order_column(self.time_col.index, OrderType::Ascending);
+        self.input.logical_pk().iter().for_each(|idx| {
+            if *idx != self.time_col.index {
+                internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending);
+            }
+        });
+
+        internal_table_catalog_builder.build(vec![])
+    }
+
+    /// Sessions can't be partitioned, so the input is always gathered to a single node.
+    pub fn required_input_dist(&self) -> RequiredDist {
+        RequiredDist::single()
+    }
+
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let time_col = InputRef::new(
+            input_col_change.map(self.time_col.index),
+            self.time_col.data_type.clone(),
+        );
+        let new_input_len = input.schema().len();
+        let (mut map, _) = input_col_change.into_parts();
+        map.push(Some(new_input_len));
+        map.push(Some(new_input_len + 1));
+        (
+            Self::new(input, time_col, self.gap),
+            ColIndexMapping::with_target_size(map, new_input_len + 2),
+        )
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.time_col.clone(), self.gap)
+    }
+}
+
+impl_plan_tree_node_for_unary! { LogicalSessionWindow }
+
+impl fmt::Display for LogicalSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalSessionWindow")
+    }
+}
+
+impl ColPrunable for LogicalSessionWindow {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let mapping = ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
+        LogicalProject::with_mapping(self.clone().into(), mapping).into()
+    }
+}
+
+impl PredicatePushdown for LogicalSessionWindow {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // Filtering any row below may split or shrink the sessions of the others, so nothing can
+        // be pushed down.
+        gen_filter_and_pushdown(self, predicate, Condition::true_cond())
+    }
+}
+
+impl ToBatch for LogicalSessionWindow {
+    fn to_batch(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_batch()?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(BatchSessionWindow::new(new_logical).into())
+    }
+}
+
+impl ToStream for LogicalSessionWindow {
+    fn to_stream(&self) -> Result<PlanRef> {
+        let input = self.input().to_stream()?;
+        let input = self
+            .required_input_dist()
+            .enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(StreamSessionWindow::new(self.clone_with_input(input)).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (session_window, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((session_window.into(), out_col_change))
+    }
+}
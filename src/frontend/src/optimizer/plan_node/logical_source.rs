@@ -23,9 +23,9 @@ use risingwave_common::util::sort_util::OrderType;
 
 use super::{
     generic, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef, PredicatePushdown,
-    StreamSource, ToBatch, ToStream,
+    StreamSource, StreamWatermarkFilter, ToBatch, ToStream,
 };
-use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::source_catalog::{SourceCatalog, SourceCatalogInfo};
 use crate::optimizer::plan_node::utils::TableCatalogBuilder;
 use crate::optimizer::property::FunctionalDependencySet;
 use crate::session::OptimizerContextRef;
@@ -148,7 +148,13 @@ impl ToBatch for LogicalSource {
 
 impl ToStream for LogicalSource {
     fn to_stream(&self) -> Result<PlanRef> {
-        Ok(StreamSource::new(self.clone()).into())
+        let source: PlanRef = StreamSource::new(self.clone()).into();
+        match &self.source_catalog().info {
+            SourceCatalogInfo::StreamSource(info) if !info.watermark_descs.is_empty() => {
+                Ok(StreamWatermarkFilter::new(source, info.watermark_descs.clone()).into())
+            }
+            _ => Ok(source),
+        }
     }
 
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
@@ -251,9 +251,11 @@ mod batch_insert;
 mod batch_limit;
 mod batch_lookup_join;
 mod batch_nested_loop_join;
+mod batch_over_window;
 mod batch_project;
 mod batch_project_set;
 mod batch_seq_scan;
+mod batch_session_window;
 mod batch_simple_agg;
 mod batch_sort;
 mod batch_sort_agg;
@@ -276,6 +278,7 @@ mod logical_over_agg;
 mod logical_project;
 mod logical_project_set;
 mod logical_scan;
+mod logical_session_window;
 mod logical_source;
 mod logical_table_function;
 mod logical_topn;
@@ -293,14 +296,18 @@ mod stream_hash_agg;
 mod stream_hash_join;
 mod stream_hop_window;
 mod stream_index_scan;
+mod stream_interval_join;
 mod stream_local_simple_agg;
 mod stream_materialize;
+mod stream_over_window;
 mod stream_project;
 mod stream_project_set;
+mod stream_session_window;
 mod stream_sink;
 mod stream_source;
 mod stream_table_scan;
 mod stream_topn;
+mod stream_watermark_filter;
 
 pub mod utils;
 
@@ -316,9 +323,11 @@ pub use batch_insert::BatchInsert;
 pub use batch_limit::BatchLimit;
 pub use batch_lookup_join::BatchLookupJoin;
 pub use batch_nested_loop_join::BatchNestedLoopJoin;
+pub use batch_over_window::BatchOverWindow;
 pub use batch_project::BatchProject;
 pub use batch_project_set::BatchProjectSet;
 pub use batch_seq_scan::BatchSeqScan;
+pub use batch_session_window::BatchSessionWindow;
 pub use batch_simple_agg::BatchSimpleAgg;
 pub use batch_sort::BatchSort;
 pub use batch_sort_agg::BatchSortAgg;
@@ -341,6 +350,7 @@ pub use logical_over_agg::{LogicalOverAgg, PlanWindowFunction};
 pub use logical_project::{LogicalProject, LogicalProjectBuilder};
 pub use logical_project_set::LogicalProjectSet;
 pub use logical_scan::LogicalScan;
+pub use logical_session_window::LogicalSessionWindow;
 pub use logical_source::LogicalSource;
 pub use logical_table_function::LogicalTableFunction;
 pub use logical_topn::LogicalTopN;
@@ -358,14 +368,18 @@ pub use stream_hash_agg::StreamHashAgg;
 pub use stream_hash_join::StreamHashJoin;
 pub use stream_hop_window::StreamHopWindow;
 pub use stream_index_scan::StreamIndexScan;
+pub use stream_interval_join::StreamIntervalJoin;
 pub use stream_local_simple_agg::StreamLocalSimpleAgg;
 pub use stream_materialize::StreamMaterialize;
+pub use stream_over_window::StreamOverWindow;
 pub use stream_project::StreamProject;
 pub use stream_project_set::StreamProjectSet;
+pub use stream_session_window::StreamSessionWindow;
 pub use stream_sink::StreamSink;
 pub use stream_source::StreamSource;
 pub use stream_table_scan::StreamTableScan;
 pub use stream_topn::StreamTopN;
+pub use stream_watermark_filter::StreamWatermarkFilter;
 
 use crate::session::OptimizerContextRef;
 use crate::stream_fragmenter::BuildFragmentGraphState;
@@ -406,6 +420,7 @@ macro_rules! for_all_plan_nodes {
             , { Logical, ProjectSet }
             , { Logical, Union }
             , { Logical, OverAgg }
+            , { Logical, SessionWindow }
             // , { Logical, Sort } we don't need a LogicalSort, just require the Order
             , { Batch, SimpleAgg }
             , { Batch, HashAgg }
@@ -430,12 +445,15 @@ macro_rules! for_all_plan_nodes {
             , { Batch, ProjectSet }
             , { Batch, Union }
             , { Batch, GroupTopN }
+            , { Batch, OverWindow }
+            , { Batch, SessionWindow }
             , { Stream, Project }
             , { Stream, Filter }
             , { Stream, TableScan }
             , { Stream, Sink }
             , { Stream, Source }
             , { Stream, HashJoin }
+            , { Stream, IntervalJoin }
             , { Stream, Exchange }
             , { Stream, HashAgg }
             , { Stream, LocalSimpleAgg }
@@ -449,6 +467,9 @@ macro_rules! for_all_plan_nodes {
             , { Stream, DynamicFilter }
             , { Stream, ProjectSet }
             , { Stream, GroupTopN }
+            , { Stream, OverWindow }
+            , { Stream, WatermarkFilter }
+            , { Stream, SessionWindow }
         }
     };
 }
@@ -478,6 +499,7 @@ macro_rules! for_logical_plan_nodes {
             , { Logical, ProjectSet }
             , { Logical, Union }
             , { Logical, OverAgg }
+            , { Logical, SessionWindow }
             // , { Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
             // if we don't support that, we don't need LogicalSort, just require the Order at the top of query
         }
@@ -512,6 +534,8 @@ macro_rules! for_batch_plan_nodes {
             , { Batch, ProjectSet }
             , { Batch, Union }
             , { Batch, GroupTopN }
+            , { Batch, OverWindow }
+            , { Batch, SessionWindow }
         }
     };
 }
@@ -524,6 +548,7 @@ macro_rules! for_stream_plan_nodes {
               { Stream, Project }
             , { Stream, Filter }
             , { Stream, HashJoin }
+            , { Stream, IntervalJoin }
             , { Stream, Exchange }
             , { Stream, TableScan }
             , { Stream, Sink }
@@ -540,6 +565,9 @@ macro_rules! for_stream_plan_nodes {
             , { Stream, DynamicFilter }
             , { Stream, ProjectSet }
             , { Stream, GroupTopN }
+            , { Stream, OverWindow }
+            , { Stream, WatermarkFilter }
+            , { Stream, SessionWindow }
         }
     };
 }
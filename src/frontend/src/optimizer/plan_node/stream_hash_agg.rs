@@ -27,11 +27,18 @@ pub struct StreamHashAgg {
     /// an optional column index which is the vnode of each row computed by the input's consistent
     /// hash distribution
     vnode_col_idx: Option<usize>,
+    /// Whether the result of a window is only emitted once it's closed by the watermark on the
+    /// first group key.
+    emit_on_window_close: bool,
     logical: LogicalAgg,
 }
 
 impl StreamHashAgg {
-    pub fn new(logical: LogicalAgg, vnode_col_idx: Option<usize>) -> Self {
+    pub fn new(
+        logical: LogicalAgg,
+        vnode_col_idx: Option<usize>,
+        emit_on_window_close: bool,
+    ) -> Self {
         let ctx = logical.base.ctx.clone();
         let pk_indices = logical.base.logical_pk.to_vec();
         let input = logical.input();
@@ -42,18 +49,20 @@ impl StreamHashAgg {
                 .rewrite_provided_distribution(input_dist),
             d => d.clone(),
         };
-        // Hash agg executor might change the append-only behavior of the stream.
+        // Hash agg executor might change the append-only behavior of the stream, unless the
+        // result of each window is emitted only once.
         let base = PlanBase::new_stream(
             ctx,
             logical.schema().clone(),
             pk_indices,
             logical.functional_dependency().clone(),
             dist,
-            false,
+            emit_on_window_close,
         );
         StreamHashAgg {
             base,
             vnode_col_idx,
+            emit_on_window_close,
             logical,
         }
     }
@@ -69,7 +78,10 @@ impl StreamHashAgg {
 
 impl fmt::Display for StreamHashAgg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.input().append_only() {
+        if self.emit_on_window_close {
+            self.logical
+                .fmt_with_name(f, "StreamEmitOnWindowCloseHashAgg")
+        } else if self.input().append_only() {
             self.logical.fmt_with_name(f, "StreamAppendOnlyHashAgg")
         } else {
             self.logical.fmt_with_name(f, "StreamHashAgg")
@@ -83,7 +95,11 @@ impl PlanTreeNodeUnary for StreamHashAgg {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(self.logical.clone_with_input(input), self.vnode_col_idx)
+        Self::new(
+            self.logical.clone_with_input(input),
+            self.vnode_col_idx,
+            self.emit_on_window_close,
+        )
     }
 }
 impl_plan_tree_node_for_unary! { StreamHashAgg }
@@ -112,6 +128,7 @@ impl StreamNode for StreamHashAgg {
                     .with_id(state.gen_table_id_wrapped())
                     .to_internal_table_prost(),
             ),
+            emit_on_window_close: self.emit_on_window_close,
         })
     }
 }
@@ -0,0 +1,314 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, IntervalUnit};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::expr::expr_node::Type as ExprType;
+use risingwave_pb::plan_common::JoinType;
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::IntervalJoinNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, StreamHashJoin, StreamNode};
+use crate::catalog::table_catalog::TableCatalog;
+use crate::expr::Expr;
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// The time band of an interval join, i.e.
+/// `left.left_time_key BETWEEN right.right_time_key + lower_bound AND right.right_time_key +
+/// upper_bound`.
+#[derive(Debug, Clone)]
+pub struct IntervalJoinBand {
+    pub left_time_key: usize,
+    /// The index in the right input.
+    pub right_time_key: usize,
+    pub lower_bound: IntervalUnit,
+    pub upper_bound: IntervalUnit,
+}
+
+/// [`StreamIntervalJoin`] implements [`super::LogicalJoin`] for inner joins of two append-only
+/// streams with both equal join keys and a time band. Unlike [`StreamHashJoin`], the states of
+/// both sides are evicted as the watermarks of the time keys advance, so they won't grow without
+/// bound if the inputs have watermarks.
+#[derive(Debug, Clone)]
+pub struct StreamIntervalJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The join condition must be equivalent to `logical.on`, but separated into equal and
+    /// non-equal parts to facilitate execution later. The time band is still kept in the non-equal
+    /// part.
+    eq_join_predicate: EqJoinPredicate,
+
+    band: IntervalJoinBand,
+}
+
+impl StreamIntervalJoin {
+    pub fn new(
+        logical: LogicalJoin,
+        eq_join_predicate: EqJoinPredicate,
+        band: IntervalJoinBand,
+    ) -> Self {
+        assert_eq!(logical.join_type(), JoinType::Inner);
+        let ctx = logical.base.ctx.clone();
+
+        let dist = StreamHashJoin::derive_dist(
+            logical.left().distribution(),
+            logical.right().distribution(),
+            &logical,
+        );
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.base.logical_pk.to_vec(),
+            logical.functional_dependency().clone(),
+            dist,
+            true,
+        );
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+            band,
+        }
+    }
+
+    /// Finds the time band in the non-equal join condition, which is a pair of comparisons
+    /// between the same time columns of both sides, shifted by constant intervals.
+    pub fn derive_band(
+        logical: &LogicalJoin,
+        eq_join_predicate: &EqJoinPredicate,
+    ) -> Option<IntervalJoinBand> {
+        let left_len = logical.left().schema().len();
+        let mut bands: Vec<(usize, usize, Option<IntervalUnit>, Option<IntervalUnit>)> = vec![];
+
+        for expr in &eq_join_predicate.other_cond().conjunctions {
+            let Some((left, cmp, right, offset)) = expr.as_interval_comparison_cond() else {
+                continue;
+            };
+            if left.index >= left_len || right.index < left_len {
+                continue;
+            }
+            match left.data_type {
+                DataType::Timestamp => {}
+                // Months can't be added to `timestamp with time zone` without a time zone.
+                DataType::Timestampz if offset.get_months() == 0 => {}
+                _ => continue,
+            }
+            if left.data_type != right.data_type {
+                continue;
+            }
+
+            let right_index = right.index - left_len;
+            let band = match bands
+                .iter_mut()
+                .find(|(l, r, _, _)| *l == left.index && *r == right_index)
+            {
+                Some(band) => band,
+                None => {
+                    bands.push((left.index, right_index, None, None));
+                    bands.last_mut().unwrap()
+                }
+            };
+            // `left cmp right + offset`
+            match cmp {
+                ExprType::GreaterThan | ExprType::GreaterThanOrEqual => {
+                    band.2 = Some(band.2.map_or(offset, |lower| lower.max(offset)));
+                }
+                ExprType::LessThan | ExprType::LessThanOrEqual => {
+                    band.3 = Some(band.3.map_or(offset, |upper| upper.min(offset)));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        bands.into_iter().find_map(|band| match band {
+            (left_time_key, right_time_key, Some(lower_bound), Some(upper_bound))
+                if lower_bound <= upper_bound =>
+            {
+                Some(IntervalJoinBand {
+                    left_time_key,
+                    right_time_key,
+                    lower_bound,
+                    upper_bound,
+                })
+            }
+            _ => None,
+        })
+    }
+
+    /// Get a reference to the interval join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+}
+
+impl fmt::Display for StreamIntervalJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut builder = f.debug_struct("StreamIntervalJoin");
+
+        let verbose = self.base.ctx.is_explain_verbose();
+        builder.field("type", &format_args!("{:?}", self.logical.join_type()));
+
+        let mut concat_schema = self.left().schema().fields.clone();
+        concat_schema.extend(self.right().schema().fields.clone());
+        let concat_schema = Schema::new(concat_schema);
+        builder.field(
+            "predicate",
+            &format_args!(
+                "{}",
+                EqJoinPredicateDisplay {
+                    eq_join_predicate: self.eq_join_predicate(),
+                    input_schema: &concat_schema
+                }
+            ),
+        );
+
+        let left_time = &self.left().schema().fields[self.band.left_time_key].name;
+        let right_time = &self.right().schema().fields[self.band.right_time_key].name;
+        builder.field(
+            "time_band",
+            &format_args!(
+                "{} BETWEEN {} + '{}' AND {} + '{}'",
+                left_time, right_time, self.band.lower_bound, right_time, self.band.upper_bound
+            ),
+        );
+
+        if verbose {
+            if self
+                .logical
+                .output_indices()
+                .iter()
+                .copied()
+                .eq(0..self.logical.internal_column_num())
+            {
+                builder.field("output", &format_args!("all"));
+            } else {
+                builder.field(
+                    "output",
+                    &format_args!(
+                        "{:?}",
+                        &IndicesDisplay {
+                            indices: self.logical.output_indices(),
+                            input_schema: &concat_schema,
+                        }
+                    ),
+                );
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl PlanTreeNodeBinary for StreamIntervalJoin {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.logical.clone_with_left_right(left, right),
+            self.eq_join_predicate.clone(),
+            self.band.clone(),
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { StreamIntervalJoin }
+
+impl StreamNode for StreamIntervalJoin {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> NodeBody {
+        let left_key_indices = self.eq_join_predicate.left_eq_indexes();
+        let right_key_indices = self.eq_join_predicate.right_eq_indexes();
+        let left_key_indices_prost = left_key_indices.iter().map(|idx| *idx as i32).collect_vec();
+        let right_key_indices_prost = right_key_indices
+            .iter()
+            .map(|idx| *idx as i32)
+            .collect_vec();
+
+        let left_table =
+            infer_internal_table_catalog(self.left(), left_key_indices, self.band.left_time_key)
+                .with_id(state.gen_table_id_wrapped());
+        let right_table =
+            infer_internal_table_catalog(self.right(), right_key_indices, self.band.right_time_key)
+                .with_id(state.gen_table_id_wrapped());
+
+        NodeBody::IntervalJoin(IntervalJoinNode {
+            join_type: self.logical.join_type() as i32,
+            left_key: left_key_indices_prost,
+            right_key: right_key_indices_prost,
+            condition: self
+                .eq_join_predicate
+                .other_cond()
+                .as_expr_unless_true()
+                .map(|x| x.to_expr_proto()),
+            left_time_key: self.band.left_time_key as u32,
+            right_time_key: self.band.right_time_key as u32,
+            lower_bound: Some(self.band.lower_bound.into()),
+            upper_bound: Some(self.band.upper_bound.into()),
+            left_table: Some(left_table.to_internal_table_prost()),
+            right_table: Some(right_table.to_internal_table_prost()),
+            output_indices: self
+                .logical
+                .output_indices()
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+        })
+    }
+}
+
+/// Return interval join internal table catalog, which is ordered by the time key first, so that
+/// the expired rows can be evicted with a range deletion.
+fn infer_internal_table_catalog(
+    input: PlanRef,
+    join_key_indices: Vec<usize>,
+    time_key_index: usize,
+) -> TableCatalog {
+    let base = input.plan_base();
+    let schema = &base.schema;
+
+    let internal_table_dist_keys = base.dist.dist_column_indices().to_vec();
+
+    // The pk of interval join internal table should be time_key + join_key + input_pk.
+    let mut pk_indices = vec![time_key_index];
+    pk_indices.extend(join_key_indices);
+    pk_indices.extend(&base.logical_pk);
+
+    let mut internal_table_catalog_builder =
+        TableCatalogBuilder::new(base.ctx.inner().with_options.internal_table_subset());
+
+    schema.fields().iter().for_each(|field| {
+        internal_table_catalog_builder.add_column(field);
+    });
+
+    pk_indices.iter().for_each(|idx| {
+        internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
+    });
+
+    internal_table_catalog_builder.build(internal_table_dist_keys)
+}
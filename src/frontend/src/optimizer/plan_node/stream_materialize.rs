@@ -127,6 +127,7 @@ impl StreamMaterialize {
                         i as i32 + COLUMN_ID_BASE,
                     ),
                     is_hidden: !user_cols.contains(i),
+                    default_value: None,
                 };
                 c.column_desc.name = if !c.is_hidden {
                     out_name_iter.next().unwrap()
@@ -185,6 +186,7 @@ impl StreamMaterialize {
             vnode_col_idx: None,
             value_indices,
             definition,
+            version: 0,
         };
 
         Ok(Self { base, input, table })
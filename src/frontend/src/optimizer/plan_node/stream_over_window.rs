@@ -0,0 +1,84 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+
+use super::{LogicalOverAgg, PlanBase, PlanTreeNodeUnary, StreamNode};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+use crate::PlanRef;
+
+/// `StreamOverWindow` implements [`super::LogicalOverAgg`] by keeping all input rows in a state
+/// table and re-evaluating the window function over each partition touched by a chunk.
+#[derive(Debug, Clone)]
+pub struct StreamOverWindow {
+    pub base: PlanBase,
+    logical: LogicalOverAgg,
+}
+
+impl StreamOverWindow {
+    pub fn new(logical: LogicalOverAgg) -> Self {
+        let input = logical.input();
+        let base = PlanBase::new_stream(
+            logical.base.ctx.clone(),
+            logical.schema().clone(),
+            logical.logical_pk().to_vec(),
+            logical.functional_dependency().clone(),
+            input.distribution().clone(),
+            false,
+        );
+        StreamOverWindow { base, logical }
+    }
+}
+
+impl StreamNode for StreamOverWindow {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+        let window_function = &self.logical.window_function;
+        let state_table = self
+            .logical
+            .infer_internal_table_catalog()
+            .with_id(state.gen_table_id_wrapped());
+
+        ProstStreamNode::OverWindow(OverWindowNode {
+            calls: vec![window_function.to_protobuf()],
+            partition_by: window_function
+                .partition_key_indices()
+                .into_iter()
+                .map(|idx| idx as u32)
+                .collect(),
+            order_by: window_function.order_by_to_protobuf(),
+            state_table: Some(state_table.to_internal_table_prost()),
+        })
+    }
+}
+
+impl fmt::Display for StreamOverWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamOverWindow")
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamOverWindow }
+
+impl PlanTreeNodeUnary for StreamOverWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
@@ -74,6 +74,16 @@ impl_plan_tree_node_for_unary! {StreamProject}
 
 impl StreamNode for StreamProject {
     fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        let (watermark_input_key, watermark_output_key) = self
+            .logical
+            .exprs()
+            .iter()
+            .enumerate()
+            .filter_map(|(output_idx, expr)| {
+                expr.as_monotonic_input_ref()
+                    .map(|input_idx| (input_idx as u32, output_idx as u32))
+            })
+            .unzip();
         ProstStreamNode::Project(ProjectNode {
             select_list: self
                 .logical
@@ -81,6 +91,8 @@ impl StreamNode for StreamProject {
                 .iter()
                 .map(Expr::to_expr_proto)
                 .collect(),
+            watermark_input_key,
+            watermark_output_key,
         })
     }
 }
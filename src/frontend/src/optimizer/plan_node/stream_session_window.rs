@@ -0,0 +1,79 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::SessionWindowNode;
+
+use super::{LogicalSessionWindow, PlanBase, PlanTreeNodeUnary, StreamNode};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+use crate::PlanRef;
+
+/// `StreamSessionWindow` implements [`super::LogicalSessionWindow`]. As a late row may bridge
+/// two sessions, the rows of previously emitted sessions can be retracted, so the output is never
+/// append-only.
+#[derive(Debug, Clone)]
+pub struct StreamSessionWindow {
+    pub base: PlanBase,
+    logical: LogicalSessionWindow,
+}
+
+impl StreamSessionWindow {
+    pub fn new(logical: LogicalSessionWindow) -> Self {
+        let input = logical.input();
+        let base = PlanBase::new_stream(
+            logical.base.ctx.clone(),
+            logical.schema().clone(),
+            logical.logical_pk().to_vec(),
+            logical.functional_dependency().clone(),
+            input.distribution().clone(),
+            false,
+        );
+        StreamSessionWindow { base, logical }
+    }
+}
+
+impl StreamNode for StreamSessionWindow {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        let state_table = self
+            .logical
+            .infer_internal_table_catalog()
+            .with_id(state.gen_table_id_wrapped());
+
+        ProstStreamNode::SessionWindow(SessionWindowNode {
+            time_col: Some(self.logical.time_col.to_proto()),
+            gap: Some(self.logical.gap.into()),
+            state_table: Some(state_table.to_internal_table_prost()),
+        })
+    }
+}
+
+impl fmt::Display for StreamSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamSessionWindow")
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamSessionWindow }
+
+impl PlanTreeNodeUnary for StreamSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::Field;
+use risingwave_common::types::DataType;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::catalog::WatermarkDesc;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::WatermarkFilterNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+use crate::TableCatalog;
+
+/// [`StreamWatermarkFilter`] generates the watermarks of a source with the watermark definitions,
+/// and filters out the late rows.
+#[derive(Debug, Clone)]
+pub struct StreamWatermarkFilter {
+    pub base: PlanBase,
+    input: PlanRef,
+    watermark_descs: Vec<WatermarkDesc>,
+}
+
+impl StreamWatermarkFilter {
+    pub fn new(input: PlanRef, watermark_descs: Vec<WatermarkDesc>) -> Self {
+        let base = PlanBase::new_stream(
+            input.ctx(),
+            input.schema().clone(),
+            input.logical_pk().to_vec(),
+            input.functional_dependency().clone(),
+            input.distribution().clone(),
+            input.append_only(),
+        );
+        Self {
+            base,
+            input,
+            watermark_descs,
+        }
+    }
+
+    /// The state table persists the watermarks of each vnode, in the order of `watermark_descs`,
+    /// so that they can be restored on recovery before any rows are admitted.
+    pub fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let mut internal_table_catalog_builder =
+            TableCatalogBuilder::new(self.ctx().inner().with_options.internal_table_subset());
+
+        let vnode_col_idx =
+            internal_table_catalog_builder.add_column(&Field::with_name(DataType::Int16, "vnode"));
+        let fields = self.input.schema().fields();
+        for desc in &self.watermark_descs {
+            internal_table_catalog_builder.add_column(&fields[desc.watermark_idx as usize]);
+        }
+        internal_table_catalog_builder.add_order_column(vnode_col_idx, OrderType::Ascending);
+        internal_table_catalog_builder.set_vnode_col_idx(vnode_col_idx);
+
+        internal_table_catalog_builder.build(vec![vnode_col_idx])
+    }
+}
+
+impl fmt::Display for StreamWatermarkFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self.schema().fields();
+        let watermark_columns = self
+            .watermark_descs
+            .iter()
+            .map(|desc| fields[desc.watermark_idx as usize].name.as_str())
+            .collect_vec();
+        f.debug_struct("StreamWatermarkFilter")
+            .field(
+                "watermark_columns",
+                &format_args!("[{}]", watermark_columns.join(", ")),
+            )
+            .finish()
+    }
+}
+
+impl PlanTreeNodeUnary for StreamWatermarkFilter {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.watermark_descs.clone())
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamWatermarkFilter }
+
+impl StreamNode for StreamWatermarkFilter {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        let table = self
+            .infer_internal_table_catalog()
+            .with_id(state.gen_table_id_wrapped());
+        ProstStreamNode::WatermarkFilter(WatermarkFilterNode {
+            watermark_descs: self.watermark_descs.clone(),
+            table: Some(table.to_internal_table_prost()),
+        })
+    }
+}
@@ -60,6 +60,7 @@ impl TableCatalogBuilder {
             column_desc: column_desc.clone(),
             // All columns in internal table are invisible to batch query.
             is_hidden: false,
+            default_value: None,
         });
         column_idx
     }
@@ -117,6 +118,7 @@ impl TableCatalogBuilder {
                 .value_indices
                 .unwrap_or_else(|| (0..self.columns.len()).collect_vec()),
             definition: "".into(),
+            version: 0,
         }
     }
 
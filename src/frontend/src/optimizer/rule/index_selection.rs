@@ -698,7 +698,7 @@ impl<'a> TableScanIoEstimator<'a> {
             DataType::Timestamp => size_of::<NaiveDateTimeWrapper>(),
             DataType::Timestampz => size_of::<i64>(),
             DataType::Interval => size_of::<IntervalUnit>(),
-            DataType::Varchar => 20,
+            DataType::Varchar | DataType::Bytea | DataType::Jsonb => 20,
             DataType::Struct { .. } => 20,
             DataType::List { .. } => 20,
         }
@@ -63,14 +63,15 @@ impl Rule for OverAggToTopNRule {
 
         let PlanWindowFunction {
             function_type,
-            return_type: _,
             partition_by,
             order_by,
+            ..
         } = &over_agg.window_function;
         let with_ties = match function_type {
             WindowFunctionType::RowNumber => false,
             WindowFunctionType::Rank => true,
-            WindowFunctionType::DenseRank => unreachable!("Not implemented. Banned in planner."),
+            // Other window functions are evaluated by the over window executors.
+            _ => return None,
         };
 
         let (rank_pred, other_pred) = {
@@ -16,7 +16,7 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::ScalarImpl;
+use risingwave_common::types::{DataType, ScalarImpl};
 
 use crate::binder::{
     BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundWindowTableFunction, Relation,
@@ -24,8 +24,8 @@ use crate::binder::{
 };
 use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef, TableFunction};
 use crate::optimizer::plan_node::{
-    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSource,
-    LogicalTableFunction, PlanRef,
+    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSessionWindow,
+    LogicalSource, LogicalTableFunction, PlanRef,
 };
 use crate::planner::Planner;
 
@@ -97,6 +97,11 @@ impl Planner {
                 table_function.time_col,
                 table_function.args,
             ),
+            Session => self.plan_session_window(
+                table_function.input,
+                table_function.time_col,
+                table_function.args,
+            ),
         }
     }
 
@@ -206,4 +211,37 @@ impl Planner {
             window_size,
         ))
     }
+
+    fn plan_session_window(
+        &mut self,
+        input: Relation,
+        time_col: InputRef,
+        args: Vec<ExprImpl>,
+    ) -> Result<PlanRef> {
+        if time_col.data_type != DataType::Timestamp {
+            return Err(ErrorCode::BindError(
+                "the time column of SESSION window function should be a timestamp column"
+                    .to_string(),
+            )
+            .into());
+        }
+        let input = self.plan_relation(input)?;
+        let mut args = args.into_iter();
+        let (Some(ExprImpl::Literal(gap)), None) = (args.next(), args.next()) else {
+            return Err(ErrorCode::BindError(
+                "Invalid arguments for SESSION window function".to_string(),
+            )
+            .into());
+        };
+        let Some(ScalarImpl::Interval(gap)) = *gap.get_data() else {
+            return Err(ErrorCode::BindError(
+                "Invalid arguments for SESSION window function".to_string(),
+            )
+            .into());
+        };
+        if !gap.is_positive() {
+            return Err(ErrorCode::BindError(format!("gap {} must be positive", gap)).into());
+        }
+        Ok(LogicalSessionWindow::create(input, time_col, gap))
+    }
 }
@@ -12,12 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
 use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_pb::plan_common::JoinType;
 
-use crate::binder::BoundSetExpr;
-use crate::expr::ExprImpl;
-use crate::optimizer::plan_node::PlanRef;
+use crate::binder::{BoundSetExpr, BoundSetOperation};
+use crate::expr::{
+    ExprImpl, ExprType, FunctionCall, InputRef, Literal, TableFunction, TableFunctionType,
+};
+use crate::optimizer::plan_node::{
+    LogicalAgg, LogicalFilter, LogicalJoin, LogicalProject, LogicalProjectSet, LogicalUnion,
+    PlanAggCall, PlanRef,
+};
 use crate::planner::Planner;
+use crate::utils::Condition;
 
 impl Planner {
     pub(super) fn plan_set_expr(
@@ -28,6 +38,202 @@ impl Planner {
         match set_expr {
             BoundSetExpr::Select(s) => self.plan_select(*s, extra_order_exprs),
             BoundSetExpr::Values(v) => self.plan_values(*v),
+            BoundSetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+                schema,
+            } => self.plan_set_operation(op, all, *left, *right, &schema),
         }
     }
+
+    /// Plan a set operation. `UNION` is planned as a [`LogicalUnion`], while `INTERSECT` and
+    /// `EXCEPT` are rewritten into joins:
+    ///
+    /// * `INTERSECT` / `EXCEPT`: the distinct rows of the left side, semi / anti joined with the
+    ///   right side.
+    /// * `INTERSECT ALL` / `EXCEPT ALL`: both sides are grouped by all columns with their number of
+    ///   occurrences, and joined to compute the number of occurrences in the result, i.e. `least(l,
+    ///   r)` or `l - r`. Each row is then repeated that many times.
+    ///
+    /// All joins compare the columns with `IS NOT DISTINCT FROM`, as set operations treat nulls as
+    /// equal.
+    fn plan_set_operation(
+        &mut self,
+        op: BoundSetOperation,
+        all: bool,
+        left: BoundSetExpr,
+        right: BoundSetExpr,
+        schema: &Schema,
+    ) -> Result<PlanRef> {
+        let left = self.plan_set_operation_input(left, schema)?;
+        let right = self.plan_set_operation_input(right, schema)?;
+        let column_num = schema.len();
+
+        let plan = match (op, all) {
+            (BoundSetOperation::Union, all) => LogicalUnion::create(all, vec![left, right]),
+            (BoundSetOperation::Intersect, false) | (BoundSetOperation::Except, false) => {
+                let join_type = match op {
+                    BoundSetOperation::Intersect => JoinType::LeftSemi,
+                    _ => JoinType::LeftAnti,
+                };
+                let left = LogicalAgg::new(vec![], (0..column_num).collect_vec(), left).into();
+                let on = null_safe_equal_condition(schema, 0, column_num);
+                LogicalJoin::new(left, right, join_type, on).into()
+            }
+            (BoundSetOperation::Intersect, true) | (BoundSetOperation::Except, true) => {
+                let count_rows = |input: PlanRef| -> PlanRef {
+                    LogicalAgg::new(
+                        vec![PlanAggCall::count_star()],
+                        (0..column_num).collect_vec(),
+                        input,
+                    )
+                    .into()
+                };
+                let join_type = match op {
+                    BoundSetOperation::Intersect => JoinType::Inner,
+                    _ => JoinType::LeftOuter,
+                };
+                // The join outputs the columns and count of the left side, followed by those of
+                // the right side.
+                let on = null_safe_equal_condition(schema, 0, column_num + 1);
+                let join: PlanRef =
+                    LogicalJoin::new(count_rows(left), count_rows(right), join_type, on).into();
+
+                let left_count: ExprImpl = InputRef::new(column_num, DataType::Int64).into();
+                let right_count: ExprImpl =
+                    InputRef::new(column_num * 2 + 1, DataType::Int64).into();
+                let count = match op {
+                    BoundSetOperation::Intersect => FunctionCall::new(
+                        ExprType::Case,
+                        vec![
+                            FunctionCall::new(
+                                ExprType::LessThan,
+                                vec![left_count.clone(), right_count.clone()],
+                            )?
+                            .into(),
+                            left_count,
+                            right_count,
+                        ],
+                    )?,
+                    _ => FunctionCall::new(
+                        ExprType::Subtract,
+                        vec![
+                            left_count,
+                            FunctionCall::new(
+                                ExprType::Coalesce,
+                                vec![
+                                    right_count,
+                                    Literal::new(Some(ScalarImpl::Int64(0)), DataType::Int64)
+                                        .into(),
+                                ],
+                            )?
+                            .into(),
+                        ],
+                    )?,
+                };
+                let exprs: Vec<ExprImpl> = schema
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+                    .chain(std::iter::once(count.into()))
+                    .collect();
+                let mut plan = LogicalProject::create(join, exprs);
+                if op == BoundSetOperation::Except {
+                    plan = LogicalFilter::create_with_expr(
+                        plan,
+                        FunctionCall::new(
+                            ExprType::GreaterThan,
+                            vec![
+                                InputRef::new(column_num, DataType::Int64).into(),
+                                Literal::new(Some(ScalarImpl::Int64(0)), DataType::Int64).into(),
+                            ],
+                        )?
+                        .into(),
+                    );
+                }
+                repeat_rows(plan, schema)?
+            }
+        };
+        Ok(plan)
+    }
+
+    /// Plan one side of a set operation, and cast its columns to the types of the output
+    /// `schema` if necessary.
+    fn plan_set_operation_input(
+        &mut self,
+        set_expr: BoundSetExpr,
+        schema: &Schema,
+    ) -> Result<PlanRef> {
+        let mut plan = self.plan_set_expr(set_expr, vec![])?;
+        if plan.as_logical_project_set().is_some() {
+            // Do not output projected_row_id hidden column.
+            plan = LogicalProject::with_out_col_idx(plan.clone(), 1..plan.schema().len()).into();
+        }
+        if plan.schema().data_types() == schema.data_types() {
+            return Ok(plan);
+        }
+        let exprs = plan
+            .schema()
+            .fields()
+            .iter()
+            .zip_eq(schema.fields())
+            .enumerate()
+            .map(|(i, (input_field, output_field))| {
+                ExprImpl::from(InputRef::new(i, input_field.data_type()))
+                    .cast_implicit(output_field.data_type())
+            })
+            .collect::<Result<_>>()?;
+        Ok(LogicalProject::create(plan, exprs))
+    }
+}
+
+/// `IS NOT DISTINCT FROM` between each column of `schema` in the left and right input of a join,
+/// which start at `left_offset` and `right_offset` respectively.
+fn null_safe_equal_condition(
+    schema: &Schema,
+    left_offset: usize,
+    right_offset: usize,
+) -> Condition {
+    let conjunctions = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            FunctionCall::new_unchecked(
+                ExprType::IsNotDistinctFrom,
+                vec![
+                    InputRef::new(left_offset + i, field.data_type()).into(),
+                    InputRef::new(right_offset + i, field.data_type()).into(),
+                ],
+                DataType::Boolean,
+            )
+            .into()
+        })
+        .collect();
+    Condition { conjunctions }
+}
+
+/// Repeat each row of `input`, which consists of the columns of `schema` followed by a count, as
+/// many times as its count.
+fn repeat_rows(input: PlanRef, schema: &Schema) -> Result<PlanRef> {
+    let column_num = schema.len();
+    let count = ExprImpl::from(InputRef::new(column_num, DataType::Int64))
+        .cast_explicit(DataType::Int32)?;
+    let series = TableFunction::new(
+        TableFunctionType::Generate,
+        vec![ExprImpl::literal_int(1), count, ExprImpl::literal_int(1)],
+    )?;
+    let select_list: Vec<ExprImpl> = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+        .chain(std::iter::once(series.into()))
+        .collect();
+    let project_set = LogicalProjectSet::create(input, select_list);
+    // Skip the projected_row_id hidden column and the series.
+    Ok(LogicalProject::with_out_col_idx(project_set, 1..=column_num).into())
 }
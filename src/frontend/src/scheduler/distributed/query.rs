@@ -18,6 +18,7 @@ use std::mem;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use pgwire::error::QueryCanceled;
 use pgwire::pg_server::SessionId;
 use risingwave_common::array::DataChunk;
 use risingwave_pb::batch_plan::{TaskId as TaskIdProst, TaskOutputId as TaskOutputIdProst};
@@ -45,7 +46,7 @@ use crate::scheduler::{
 pub enum QueryMessage {
     /// Events passed running execution.
     Stage(StageEvent),
-    CancelQuery,
+    CancelQuery(QueryCanceled),
 }
 
 enum QueryState {
@@ -179,10 +180,10 @@ impl QueryExecution {
     }
 
     /// Cancel execution of this query.
-    pub async fn abort(self: Arc<Self>) {
+    pub async fn abort(self: Arc<Self>, reason: QueryCanceled) {
         if self
             .shutdown_tx
-            .send(QueryMessage::CancelQuery)
+            .send(QueryMessage::CancelQuery(reason))
             .await
             .is_err()
         {
@@ -290,8 +291,8 @@ impl QueryRunner {
                     // One stage failed, not necessary to execute schedule stages.
                     break;
                 }
-                QueryMessage::CancelQuery => {
-                    self.handle_cancel_or_failed_stage(SchedulerError::QueryCancelError)
+                QueryMessage::CancelQuery(reason) => {
+                    self.handle_cancel_or_failed_stage(SchedulerError::QueryCancelError(reason))
                         .await;
                     // One stage failed, not necessary to execute schedule stages.
                     break;
@@ -353,7 +354,7 @@ impl QueryRunner {
     /// Handle ctrl-c query or failed execution. Should stop all executions and send error to query
     /// result fetcher.
     async fn handle_cancel_or_failed_stage(mut self, reason: SchedulerError) {
-        let err_str = reason.to_string();
+        let stop_reason = reason.to_stop_reason();
         // Consume sender here and send error to root stage.
         let root_stage_sender = mem::take(&mut self.root_stage_sender);
         // It's possible we receive stage failed event message multi times and the
@@ -375,7 +376,7 @@ impl QueryRunner {
         // Stop all running stages.
         for stage_execution in self.stage_executions.values() {
             // The stop is return immediately so no need to spawn tasks.
-            stage_execution.stop(err_str.clone()).await;
+            stage_execution.stop(stop_reason.to_stop_reason()).await;
         }
     }
 }
@@ -482,6 +483,7 @@ pub(crate) mod tests {
                 appendonly: false,
                 retention_seconds: TABLE_OPTION_DUMMY_RETENTION_SECOND,
                 value_indices: vec![0, 1, 2],
+                appended_value_defaults: vec![],
             }),
             vec![],
             ctx,
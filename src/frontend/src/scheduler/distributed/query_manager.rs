@@ -20,6 +20,7 @@ use std::task::{Context, Poll};
 
 use futures::{Stream, StreamExt};
 use futures_async_stream::try_stream;
+use pgwire::error::QueryCanceled;
 use pgwire::pg_server::{BoxedError, Session, SessionId};
 use risingwave_batch::executor::BoxedDataChunkStream;
 use risingwave_common::array::DataChunk;
@@ -120,7 +121,7 @@ impl QueryExecutionInfo {
             if query.session_id == session_id {
                 let query = query.clone();
                 // Spawn a task to abort. Avoid await point in this function.
-                tokio::spawn(async move { query.abort().await });
+                tokio::spawn(async move { query.abort(QueryCanceled::UserRequest).await });
             }
         }
     }
@@ -161,6 +162,7 @@ impl QueryManager {
     ) -> SchedulerResult<DistributedQueryStream> {
         let query_id = query.query_id.clone();
         let query_execution = Arc::new(QueryExecution::new(query, context.session().id()));
+        let statement_timeout = context.session().config().get_statement_timeout();
 
         // Add queries status when begin.
         context
@@ -169,6 +171,25 @@ impl QueryManager {
             .query_manager()
             .add_query(query_id.clone(), query_execution.clone());
 
+        // Cancel the query once it runs over `statement_timeout`. It's a no-op if the query has
+        // already finished.
+        if let Some(timeout) = statement_timeout {
+            let query_execution_info = self.query_execution_info.clone();
+            let query_id = query_id.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                let query_execution = query_execution_info
+                    .read()
+                    .unwrap()
+                    .query_execution_map
+                    .get(&query_id)
+                    .cloned();
+                if let Some(query_execution) = query_execution {
+                    query_execution.abort(QueryCanceled::StatementTimeout).await;
+                }
+            });
+        }
+
         // Starts the execution of the query.
         let query_result_fetcher = query_execution
             .start(
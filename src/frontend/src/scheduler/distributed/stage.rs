@@ -55,7 +55,6 @@ use crate::scheduler::plan_fragmenter::{
     ExecutionPlanNode, PartitionInfo, QueryStageRef, StageId, TaskId, ROOT_TASK_ID,
 };
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
-use crate::scheduler::SchedulerError::TaskExecutionError;
 use crate::scheduler::{ExecutionContextRef, SchedulerError, SchedulerResult};
 
 const TASK_SCHEDULING_PARALLELISM: usize = 10;
@@ -76,8 +75,8 @@ enum StageState {
 }
 
 enum StageMessage {
-    /// Contains the reason why need to stop (e.g. Execution failure).
-    Stop(String),
+    /// Contains the reason why need to stop (e.g. Execution failure or query canceled).
+    Stop(SchedulerError),
 }
 
 #[derive(Debug)]
@@ -220,12 +219,12 @@ impl StageExecution {
         }
     }
 
-    pub async fn stop(&self, err_str: String) {
+    pub async fn stop(&self, reason: SchedulerError) {
         // Send message to tell Stage Runner stop.
         if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
             // It's possible that the stage has not been scheduled, so the channel sender is
             // None.
-            if shutdown_tx.send(StageMessage::Stop(err_str)).is_err() {
+            if shutdown_tx.send(StageMessage::Stop(reason)).is_err() {
                 // The stage runner handle has already closed. so do no-op.
             }
         }
@@ -481,9 +480,9 @@ impl StageRunner {
 
             // Terminated by other tasks execution error, so no need to return error here.
             match stage_message {
-                StageMessage::Stop(err_str) => {
-                    // Tell Query Result Fetcher to stop polling and attach failure reason as str.
-                    if let Err(_e) = result_tx.send(Err(TaskExecutionError(err_str))).await {
+                StageMessage::Stop(reason) => {
+                    // Tell Query Result Fetcher to stop polling and attach failure reason.
+                    if let Err(_e) = result_tx.send(Err(reason)).await {
                         warn!("Send task execution failed");
                     }
                 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use pgwire::error::QueryCanceled;
 use risingwave_common::error::{ErrorCode, RwError};
 use risingwave_rpc_client::error::RpcError;
 use thiserror::Error;
@@ -33,9 +34,10 @@ pub enum SchedulerError {
     #[error("{0}")]
     TaskExecutionError(String),
 
-    /// Used when receive cancel request (ctrl-c) from user.
-    #[error("Canceled by user")]
-    QueryCancelError,
+    /// Used when receive cancel request (ctrl-c) from user, or the query exceeds
+    /// `statement_timeout`.
+    #[error("{0}")]
+    QueryCancelError(#[source] QueryCanceled),
 
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
@@ -51,6 +53,17 @@ impl From<tonic::Status> for SchedulerError {
     }
 }
 
+impl SchedulerError {
+    /// Duplicates the error to notify every stage of a query to stop. The reason of cancellation
+    /// is kept so that the client receives `query_canceled`, other errors are turned into strings.
+    pub(super) fn to_stop_reason(&self) -> Self {
+        match self {
+            Self::QueryCancelError(reason) => Self::QueryCancelError(*reason),
+            e => Self::TaskExecutionError(e.to_string()),
+        }
+    }
+}
+
 impl From<SchedulerError> for RwError {
     fn from(s: SchedulerError) -> Self {
         ErrorCode::SchedulerError(Box::new(s)).into()
@@ -15,12 +15,15 @@
 //! Local execution for batch query.
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
+use pgwire::error::QueryCanceled;
 use pgwire::pg_server::BoxedError;
 use risingwave_batch::executor::{BoxedDataChunkStream, ExecutorBuilder};
 use risingwave_batch::task::TaskId;
@@ -34,6 +37,8 @@ use risingwave_pb::batch_plan::{
     ExchangeInfo, ExchangeSource, LocalExecutePlan, PlanFragment, PlanNode as PlanNodeProst,
     TaskId as ProstTaskId, TaskOutputId,
 };
+use tokio::sync::mpsc;
+use tokio::time::Instant;
 use tracing::debug;
 use uuid::Uuid;
 
@@ -41,9 +46,13 @@ use super::plan_fragmenter::{PartitionInfo, QueryStageRef};
 use crate::optimizer::plan_node::PlanNodeType;
 use crate::scheduler::plan_fragmenter::{ExecutionPlanNode, Query, StageId};
 use crate::scheduler::task_context::FrontendBatchTaskContext;
-use crate::scheduler::SchedulerResult;
+use crate::scheduler::{SchedulerError, SchedulerResult};
 use crate::session::{AuthContext, FrontendEnv};
 
+/// Number of chunks buffered between the executors and the client when the query runs with a
+/// timeout.
+const LOCAL_EXECUTION_CHANNEL_SIZE: usize = 16;
+
 pub struct LocalQueryStream {
     data_stream: BoxedDataChunkStream,
 }
@@ -72,6 +81,8 @@ pub struct LocalQueryExecution {
     epoch: u64,
 
     auth_context: Arc<AuthContext>,
+    /// The query is aborted once it runs over this duration.
+    timeout: Option<Duration>,
 }
 
 impl LocalQueryExecution {
@@ -81,6 +92,7 @@ impl LocalQueryExecution {
         sql: S,
         epoch: u64,
         auth_context: Arc<AuthContext>,
+        timeout: Option<Duration>,
     ) -> Self {
         Self {
             sql: sql.into(),
@@ -88,11 +100,12 @@ impl LocalQueryExecution {
             front_env,
             epoch,
             auth_context,
+            timeout,
         }
     }
 
     #[try_stream(ok = DataChunk, error = RwError)]
-    pub async fn run_inner(self) {
+    pub async fn run_inner(self, cancel_flag: Option<Arc<AtomicBool>>) {
         debug!(
             "Starting to run query: {:?}, sql: '{}'",
             self.query.query_id, self.sql
@@ -109,7 +122,10 @@ impl LocalQueryExecution {
 
         let plan_fragment = self.create_plan_fragment()?;
         let plan_node = plan_fragment.root.unwrap();
-        let executor = ExecutorBuilder::new(&plan_node, &task_id, context, self.epoch);
+        let mut executor = ExecutorBuilder::new(&plan_node, &task_id, context, self.epoch);
+        if let Some(cancel_flag) = cancel_flag {
+            executor = executor.with_cancel_flag(cancel_flag);
+        }
         let executor = executor.build().await?;
 
         #[for_await]
@@ -118,8 +134,52 @@ impl LocalQueryExecution {
         }
     }
 
+    /// Runs the query until `timeout` is reached.
+    ///
+    /// The executors run in a spawned task and send their output over a channel, so that the
+    /// deadline fires even if a CPU-bound stage never yields. On the deadline, the task is aborted
+    /// and the cancel flag stops the executors at their next chunk. Dropping the executors closes
+    /// the exchange streams, which in turn aborts the tasks on compute nodes.
+    #[try_stream(ok = DataChunk, error = RwError)]
+    async fn run_with_timeout(self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let mut data_stream = Box::pin(self.run_inner(Some(cancel_flag.clone())));
+        let (sender, mut receiver) = mpsc::channel(LOCAL_EXECUTION_CHANNEL_SIZE);
+        let handle = tokio::spawn(async move {
+            while let Some(chunk) = data_stream.next().await {
+                if sender.send(chunk).await.is_err() {
+                    // The receiver is dropped, e.g. the client has gone.
+                    break;
+                }
+            }
+        });
+
+        loop {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(chunk)) => yield chunk?,
+                Ok(None) => {
+                    if let Err(e) = handle.await {
+                        bail!("local execution task failed: {}", e);
+                    }
+                    break;
+                }
+                Err(_) => {
+                    cancel_flag.store(true, Ordering::Relaxed);
+                    handle.abort();
+                    return Err(
+                        SchedulerError::QueryCancelError(QueryCanceled::StatementTimeout).into(),
+                    );
+                }
+            }
+        }
+    }
+
     pub fn run(self) -> BoxedDataChunkStream {
-        Box::pin(self.run_inner())
+        match self.timeout {
+            Some(timeout) => Box::pin(self.run_with_timeout(timeout)),
+            None => Box::pin(self.run_inner(None)),
+        }
     }
 
     pub fn stream_rows(self) -> LocalQueryStream {
@@ -380,3 +440,39 @@ impl LocalQueryExecution {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use tokio::time::Instant;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg_attr(madsim, ignore)]
+    async fn test_statement_timeout_on_cpu_bound_query() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        session
+            .run_statement("SET statement_timeout = 100", false)
+            .await
+            .unwrap();
+
+        // The aggregation never yields to the runtime, so the timeout must not depend on it.
+        let start = Instant::now();
+        let mut rsp = session
+            .run_statement("select count(*) from generate_series(1, 2000000000)", false)
+            .await
+            .unwrap();
+        let err = rsp.values_stream().next().await.unwrap().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("canceling statement due to statement timeout"),
+            "{}",
+            err
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -24,7 +24,10 @@ use std::time::Duration;
 use parking_lot::{RwLock, RwLockReadGuard};
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::PgResponse;
-use pgwire::pg_server::{BoxedError, Session, SessionId, SessionManager, UserAuthenticator};
+use pgwire::pg_server::{
+    BoxedError, CopyDataStream, Session, SessionId, SessionManager, UserAuthenticator,
+};
+use pgwire::scram::ScramSecret;
 use rand::RngCore;
 #[cfg(test)]
 use risingwave_common::catalog::{
@@ -51,6 +54,7 @@ use crate::binder::Binder;
 use crate::catalog::catalog_service::{CatalogReader, CatalogWriter, CatalogWriterImpl};
 use crate::catalog::root_catalog::Catalog;
 use crate::expr::CorrelatedId;
+use crate::handler::copy::handle_copy_in;
 use crate::handler::handle;
 use crate::handler::util::to_pg_field;
 use crate::meta_client::{FrontendMetaClient, FrontendMetaClientImpl};
@@ -84,6 +88,9 @@ pub struct OptimizerContext {
     pub next_correlated_id: AtomicU32,
     /// Store options or properties from the `with` clause
     pub with_options: WithOptions,
+    /// it indicates whether the results of the top-level windowed aggregation are only emitted
+    /// once the windows are closed, i.e. `EMIT ON WINDOW CLOSE`
+    pub emit_on_window_close: AtomicBool,
 }
 
 #[derive(Clone, Debug)]
@@ -127,6 +134,10 @@ impl OptimizerContextRef {
         self.inner.explain_trace.load(Ordering::Acquire)
     }
 
+    pub fn is_emit_on_window_close(&self) -> bool {
+        self.inner.emit_on_window_close.load(Ordering::Acquire)
+    }
+
     pub fn trace(&self, str: impl Into<String>) {
         let mut guard = self.inner.optimizer_trace.lock().unwrap();
         guard.push(str.into());
@@ -150,6 +161,7 @@ impl OptimizerContext {
             optimizer_trace: Arc::new(Mutex::new(vec![])),
             next_correlated_id: AtomicU32::new(1),
             with_options,
+            emit_on_window_close: AtomicBool::new(false),
         }
     }
 
@@ -166,6 +178,7 @@ impl OptimizerContext {
             optimizer_trace: Arc::new(Mutex::new(vec![])),
             next_correlated_id: AtomicU32::new(1),
             with_options: Default::default(),
+            emit_on_window_close: AtomicBool::new(false),
         }
         .into()
     }
@@ -583,6 +596,14 @@ impl SessionManager<PgResponseStream> for SessionManagerImpl {
                             ),
                             salt,
                         }
+                    } else if auth_info.encryption_type == EncryptionType::ScramSha256 as i32 {
+                        let secret = std::str::from_utf8(&auth_info.encrypted_value)
+                            .ok()
+                            .and_then(ScramSecret::parse)
+                            .ok_or_else(|| {
+                                Error::new(ErrorKind::InvalidData, "Invalid SCRAM-SHA-256 secret")
+                            })?;
+                        UserAuthenticator::ScramSha256(secret)
                     } else {
                         return Err(Box::new(Error::new(
                             ErrorKind::Unsupported,
@@ -687,6 +708,20 @@ impl Session<PgResponseStream> for SessionImpl {
         Ok(rsp)
     }
 
+    async fn run_copy_in(
+        self: Arc<Self>,
+        sql: &str,
+        data: CopyDataStream,
+    ) -> std::result::Result<PgResponse<PgResponseStream>, BoxedError> {
+        let mut stmts = Parser::parse_sql(sql)?;
+        let stmt = stmts.swap_remove(0);
+        let rsp = handle_copy_in(self, stmt, sql, data).await.map_err(|e| {
+            tracing::error!("failed to copy in sql:\n{}:\n{}", sql, e);
+            e
+        })?;
+        Ok(rsp)
+    }
+
     async fn infer_return_type(
         self: Arc<Self>,
         sql: &str,
@@ -30,7 +30,7 @@ use risingwave_common::error::Result;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable,
+    Source as ProstSource, Table as ProstTable, View as ProstView,
 };
 use risingwave_pb::hummock::HummockSnapshot;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
@@ -224,6 +224,13 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn create_view(&self, mut view: ProstView) -> Result<()> {
+        view.id = self.gen_id();
+        self.catalog.write().create_view(view.clone());
+        self.add_table_or_source_id(view.id, view.schema_id, view.database_id);
+        Ok(())
+    }
+
     async fn create_materialized_source(
         &self,
         source: ProstSource,
@@ -300,6 +307,17 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn alter_table(&self, source: ProstSource, mut table: ProstTable) -> Result<()> {
+        table.version += 1;
+        self.catalog.write().update_table(&table);
+        self.catalog.write().update_source(&source);
+        Ok(())
+    }
+
+    async fn truncate_table(&self, _table_id: TableId) -> Result<bool> {
+        Ok(true)
+    }
+
     async fn drop_source(&self, source_id: u32) -> Result<()> {
         let (database_id, schema_id) = self.drop_table_or_source_id(source_id);
         self.catalog
@@ -343,6 +361,14 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn drop_view(&self, view_id: u32) -> Result<()> {
+        let (database_id, schema_id) = self.drop_table_or_source_id(view_id);
+        self.catalog
+            .write()
+            .drop_view(database_id, schema_id, view_id);
+        Ok(())
+    }
+
     async fn drop_database(&self, database_id: u32) -> Result<()> {
         self.catalog.write().drop_database(database_id);
         Ok(())
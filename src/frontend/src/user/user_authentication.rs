@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use pgwire::scram::ScramSecret;
 use risingwave_pb::user::auth_info::EncryptionType;
 use risingwave_pb::user::AuthInfo;
 use sha2::{Digest, Sha256};
 
-// SHA-256 is not supported in PostgreSQL protocol. We need to implement SCRAM-SHA-256 instead
-// if necessary.
+// SHA-256 is not supported in PostgreSQL protocol, users with such passwords cannot login.
+// SCRAM-SHA-256 is used instead by default.
 const SHA256_ENCRYPTED_PREFIX: &str = "SHA-256:";
 const MD5_ENCRYPTED_PREFIX: &str = "md5";
 
@@ -26,10 +27,15 @@ const VALID_MD5_ENCRYPTED_LEN: usize = MD5_ENCRYPTED_PREFIX.len() + 32;
 
 /// Try to extract the encryption password from given password. The password is always stored
 /// encrypted in the system catalogs. The ENCRYPTED keyword has no effect, but is accepted for
-/// backwards compatibility. The method of encryption is by default SHA-256-encrypted. If the
-/// presented password string is already in MD5-encrypted or SHA-256-encrypted format, then it is
-/// stored as-is regardless of `password_encryption` (since the system cannot decrypt the specified
-/// encrypted password string, to encrypt it in a different format).
+/// backwards compatibility. The method of encryption is by default SCRAM-SHA-256. If the
+/// presented password string is already in SCRAM-SHA-256, MD5-encrypted or SHA-256-encrypted
+/// format, then it is stored as-is regardless of `password_encryption` (since the system cannot
+/// decrypt the specified encrypted password string, to encrypt it in a different format).
+///
+/// For a SCRAM-SHA-256 encrypted password, rolpassword column will be of the format
+/// `SCRAM-SHA-256$<iteration count>:<salt>$<StoredKey>:<ServerKey>`, where salt, StoredKey and
+/// ServerKey are in Base64 encoded format, as in PostgreSQL. A new random salt is generated each
+/// time the password is set.
 ///
 /// For an MD5 encrypted password, rolpassword column will begin with the string md5 followed by a
 /// 32-character hexadecimal MD5 hash. The MD5 hash will be of the user's password concatenated to
@@ -38,8 +44,7 @@ const VALID_MD5_ENCRYPTED_LEN: usize = MD5_ENCRYPTED_PREFIX.len() + 32;
 ///
 /// For an SHA-256 encrypted password, rolpassword column will begin with the string SHA-256:
 /// followed by a 64-character hexadecimal SHA-256 hash, which is the SHA-256 hash of the user's
-/// password concatenated to their user name. The SHA-256 will be the default hash algorithm for
-/// Risingwave.
+/// password concatenated to their user name.
 ///
 /// A password that does not follow either of those formats is assumed to be unencrypted.
 #[inline(always)]
@@ -49,7 +54,12 @@ pub fn encrypted_password(name: &str, password: &str) -> Option<AuthInfo> {
         return None;
     }
 
-    if valid_sha256_password(password) {
+    if ScramSecret::parse(password).is_some() {
+        Some(AuthInfo {
+            encryption_type: EncryptionType::ScramSha256 as i32,
+            encrypted_value: password.into(),
+        })
+    } else if valid_sha256_password(password) {
         Some(AuthInfo {
             encryption_type: EncryptionType::Sha256 as i32,
             encrypted_value: password.trim_start_matches(SHA256_ENCRYPTED_PREFIX).into(),
@@ -64,12 +74,12 @@ pub fn encrypted_password(name: &str, password: &str) -> Option<AuthInfo> {
     }
 }
 
-/// Encrypt the password with MD5 as default.
+/// Encrypt the password with SCRAM-SHA-256 as default.
 #[inline(always)]
-fn encrypt_default(name: &str, password: &str) -> AuthInfo {
+fn encrypt_default(_name: &str, password: &str) -> AuthInfo {
     AuthInfo {
-        encryption_type: EncryptionType::Md5 as i32,
-        encrypted_value: md5_hash(name, password),
+        encryption_type: EncryptionType::ScramSha256 as i32,
+        encrypted_value: ScramSecret::new(password).to_string().into_bytes(),
     }
 }
 
@@ -103,6 +113,7 @@ pub fn sha256_hash(name: &str, password: &str) -> Vec<u8> {
 }
 
 /// Encrypt "`password`+`name`" with MD5.
+#[cfg_attr(not(test), expect(dead_code))]
 #[inline(always)]
 pub fn md5_hash(name: &str, password: &str) -> Vec<u8> {
     let mut ctx = md5::Context::new();
@@ -135,16 +146,11 @@ mod tests {
         );
 
         let input_passwords = vec![
-            "bar",
             "",
             "md596948aad3fcae80c08a35c9b5958cd89",
             "SHA-256:88ecde925da3c6f8ec3d140683da9d2a422f26c1ae1d9212da1e5a53416dcc88",
         ];
         let expected_output_passwords = vec![
-            Some(AuthInfo {
-                encryption_type: EncryptionType::Md5 as i32,
-                encrypted_value: md5_hash(user_name, password),
-            }),
             None,
             Some(AuthInfo {
                 encryption_type: EncryptionType::Md5 as i32,
@@ -160,5 +166,25 @@ mod tests {
             .map(|&p| encrypted_password(user_name, p))
             .collect::<Vec<_>>();
         assert_eq!(output_passwords, expected_output_passwords);
+
+        // Plain passwords are stored as SCRAM-SHA-256 secrets, with a random salt each time.
+        let auth_info = encrypted_password(user_name, password).unwrap();
+        assert_eq!(
+            auth_info.encryption_type,
+            EncryptionType::ScramSha256 as i32
+        );
+        let secret = String::from_utf8(auth_info.encrypted_value).unwrap();
+        assert!(ScramSecret::parse(&secret).is_some());
+        assert_ne!(
+            encrypted_password(user_name, password),
+            encrypted_password(user_name, password)
+        );
+        assert_eq!(
+            encrypted_password(user_name, &secret),
+            Some(AuthInfo {
+                encryption_type: EncryptionType::ScramSha256 as i32,
+                encrypted_value: secret.into_bytes(),
+            })
+        );
     }
 }
@@ -189,6 +189,11 @@ impl StreamGraphFormatter {
                     None => "".to_string(),
                 },
             )),
+            stream_node::NodeBody::IntervalJoin(node) => Some(format!(
+                "left table: {}, right table {}",
+                self.add_table(node.get_left_table().unwrap()),
+                self.add_table(node.get_right_table().unwrap()),
+            )),
             stream_node::NodeBody::TopN(node) => Some(format!(
                 "state table: {}",
                 self.add_table(node.get_table().unwrap())
@@ -210,6 +215,18 @@ impl StreamGraphFormatter {
                 "state table: {}",
                 self.add_table(node.get_table().unwrap())
             )),
+            stream_node::NodeBody::OverWindow(node) => Some(format!(
+                "state table: {}",
+                self.add_table(node.get_state_table().unwrap())
+            )),
+            stream_node::NodeBody::SessionWindow(node) => Some(format!(
+                "state table: {}",
+                self.add_table(node.get_state_table().unwrap())
+            )),
+            stream_node::NodeBody::WatermarkFilter(node) => Some(format!(
+                "state table: {}",
+                self.add_table(node.get_table().unwrap())
+            )),
             _ => None,
         };
         if let Some(explain_table_oneline) = explain_table_oneline {
@@ -15,9 +15,11 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::num::NonZeroU32;
+use std::str::FromStr;
 
 use itertools::Itertools;
-use risingwave_common::error::{ErrorCode, RwError};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::IntervalUnit;
 use risingwave_sqlparser::ast::{
     CreateSinkStatement, CreateSourceStatement, SqlOption, Statement, Value,
 };
@@ -30,6 +32,7 @@ mod options {
     pub const APPEND_ONLY: &str = "appendonly";
     pub const CONNECTOR: &str = "connector";
     pub const RETENTION_SECONDS: &str = PROPERTIES_RETENTION_SECOND_KEY;
+    pub const TTL: &str = "ttl";
 }
 
 /// Options or properties extracted from the `WITH` clause of DDLs.
@@ -105,12 +108,52 @@ impl WithOptions {
     pub fn internal_table_subset(&self) -> Self {
         self.subset([options::RETENTION_SECONDS])
     }
+
+    /// Converts the user-facing `ttl` option, which is either an interval like `'7 days'` or a
+    /// number of seconds, to `retention_seconds` understood by the storage.
+    fn resolve_ttl(mut self) -> Result<Self> {
+        let Some(ttl) = self.inner.remove(options::TTL) else {
+            return Ok(self);
+        };
+        if self.inner.contains_key(options::RETENTION_SECONDS) {
+            return Err(ErrorCode::InvalidParameterValue(format!(
+                "cannot specify both `{}` and `{}`",
+                options::TTL,
+                options::RETENTION_SECONDS
+            ))
+            .into());
+        }
+
+        let invalid_ttl = || {
+            ErrorCode::InvalidParameterValue(format!(
+                "invalid ttl \"{}\": expect a positive interval of at least 1 second",
+                ttl
+            ))
+        };
+        let seconds = match ttl.trim().parse::<i64>() {
+            Ok(seconds) => seconds,
+            Err(_) => {
+                IntervalUnit::from_str(&ttl)
+                    .map_err(|_| invalid_ttl())?
+                    .total_ms()
+                    / 1000
+            }
+        };
+        if seconds <= 0 {
+            return Err(invalid_ttl().into());
+        }
+        let seconds = u32::try_from(seconds).map_err(|_| invalid_ttl())?;
+
+        self.inner
+            .insert(options::RETENTION_SECONDS.to_owned(), seconds.to_string());
+        Ok(self)
+    }
 }
 
 impl TryFrom<&[SqlOption]> for WithOptions {
     type Error = RwError;
 
-    fn try_from(options: &[SqlOption]) -> Result<Self, Self::Error> {
+    fn try_from(options: &[SqlOption]) -> Result<Self> {
         let inner = options
             .iter()
             .cloned()
@@ -133,14 +176,16 @@ impl TryFrom<&Statement> for WithOptions {
     type Error = RwError;
 
     /// Extract options from the `WITH` clause from the given statement.
-    fn try_from(statement: &Statement) -> Result<Self, Self::Error> {
+    fn try_from(statement: &Statement) -> Result<Self> {
         match statement {
             // Explain: forward to the inner statement.
             Statement::Explain { statement, .. } => Self::try_from(statement.as_ref()),
 
             // Table & View
             Statement::CreateTable { with_options, .. }
-            | Statement::CreateView { with_options, .. } => Self::try_from(with_options.as_slice()),
+            | Statement::CreateView { with_options, .. } => {
+                Self::try_from(with_options.as_slice())?.resolve_ttl()
+            }
 
             // Source & Sink
             Statement::CreateSource {
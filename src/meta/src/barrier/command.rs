@@ -20,13 +20,14 @@ use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::TableId;
 use risingwave_common::util::epoch::Epoch;
 use risingwave_connector::source::SplitImpl;
+use risingwave_pb::catalog::{Source, Table};
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
 use risingwave_pb::stream_plan::add_mutation::Dispatchers;
 use risingwave_pb::stream_plan::barrier::Mutation;
 use risingwave_pb::stream_plan::update_mutation::*;
 use risingwave_pb::stream_plan::{
-    ActorMapping, AddMutation, Dispatcher, PauseMutation, ResumeMutation,
-    SourceChangeSplitMutation, StopMutation, UpdateMutation,
+    ActorMapping, AddMutation, AlterTableMutation, Dispatcher, PauseMutation, ResumeMutation,
+    SourceChangeSplitMutation, StopMutation, TruncateTableMutation, UpdateMutation,
 };
 use risingwave_pb::stream_service::{DropActorsRequest, WaitEpochCommitRequest};
 use risingwave_rpc_client::StreamClientPoolRef;
@@ -111,6 +112,19 @@ pub enum Command {
     /// `SourceSplitAssignment` generates Plain(Mutation::Splits) for pushing initialized splits or
     /// newly added splits.
     SourceSplitAssignment(SplitAssignment),
+
+    /// `AlterTable` command generates an `AlterTable` barrier with the new catalogs of a table and
+    /// its associated source. The source and materialize executors of the table will apply the new
+    /// columns on this barrier.
+    ///
+    /// After the barrier is collected, it updates the stream nodes of the table fragments in meta
+    /// store, so that the actors will be built with the new columns on recovery.
+    AlterTable { source: Source, table: Table },
+
+    /// `TruncateTable` command generates a `TruncateTable` barrier, on which the materialize
+    /// executors of the table delete all the rows with range tombstones. It's only used for tables
+    /// without any downstream, since the deletions are not propagated.
+    TruncateTable(TableId),
 }
 
 impl Command {
@@ -148,6 +162,8 @@ impl Command {
                 CommandChanges::Actor { to_add, to_remove }
             }
             Command::SourceSplitAssignment(_) => CommandChanges::None,
+            Command::AlterTable { .. } => CommandChanges::None,
+            Command::TruncateTable(_) => CommandChanges::None,
         }
     }
 
@@ -243,6 +259,19 @@ where
                 Some(Mutation::Stop(StopMutation { actors }))
             }
 
+            Command::AlterTable { source, table } => {
+                Some(Mutation::AlterTable(AlterTableMutation {
+                    source: Some(source.clone()),
+                    table: Some(table.clone()),
+                }))
+            }
+
+            Command::TruncateTable(table_id) => {
+                Some(Mutation::TruncateTable(TruncateTableMutation {
+                    table_id: table_id.table_id,
+                }))
+            }
+
             Command::CreateMaterializedView {
                 dispatchers,
                 init_split_assignment: split_assignment,
@@ -445,6 +474,12 @@ where
                     .await;
             }
 
+            Command::AlterTable { source, table } => {
+                self.fragment_manager
+                    .update_table_fragments_schema(source, table)
+                    .await?;
+            }
+
             Command::DropMaterializedViews(table_ids) => {
                 // Tell compute nodes to drop actors.
                 let node_actors = self.fragment_manager.table_node_actors(table_ids).await?;
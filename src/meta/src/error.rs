@@ -52,6 +52,9 @@ enum MetaErrorInner {
     #[error("{0} with name {1} exists")]
     Duplicated(&'static str, String),
 
+    #[error("cannot drop {0} because other objects depend on it: {1}")]
+    Dependent(String, String),
+
     #[error(transparent)]
     Internal(anyhow::Error),
 }
@@ -109,6 +112,10 @@ impl MetaError {
     pub fn catalog_duplicated<T: Into<String>>(relation: &'static str, name: T) -> Self {
         MetaErrorInner::Duplicated(relation, name.into()).into()
     }
+
+    pub fn catalog_dependent<T: Into<String>>(object: T, dependents: T) -> Self {
+        MetaErrorInner::Dependent(object.into(), dependents.into()).into()
+    }
 }
 
 impl From<MetadataModelError> for MetaError {
@@ -143,6 +150,7 @@ impl From<MetaError> for tonic::Status {
             }
             MetaErrorInner::NotFound(_, _) => tonic::Status::not_found(err.to_string()),
             MetaErrorInner::Duplicated(_, _) => tonic::Status::already_exists(err.to_string()),
+            MetaErrorInner::Dependent(_, _) => tonic::Status::failed_precondition(err.to_string()),
             _ => tonic::Status::internal(err.to_string()),
         }
     }
@@ -421,6 +421,7 @@ mod tests {
                     stale_key_count: 0,
                     total_key_count: 0,
                     divide_version: 0,
+                    range_tombstone_count: 0,
                 }],
             }],
             splits: vec![],
@@ -16,9 +16,10 @@ use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table};
+use risingwave_common::bail;
+use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
 
-use super::{DatabaseId, RelationId, SchemaId, SinkId, SourceId};
+use super::{DatabaseId, RelationId, SchemaId, SinkId, SourceId, ViewId};
 use crate::manager::{IndexId, MetaSrvEnv, TableId};
 use crate::model::MetadataModel;
 use crate::storage::MetaStore;
@@ -31,6 +32,7 @@ pub type Catalog = (
     Vec<Source>,
     Vec<Sink>,
     Vec<Index>,
+    Vec<View>,
 );
 
 type DatabaseKey = String;
@@ -53,6 +55,8 @@ pub struct DatabaseManager<S: MetaStore> {
     pub(super) indexes: BTreeMap<IndexId, Index>,
     /// Cached table information.
     pub(super) tables: BTreeMap<TableId, Table>,
+    /// Cached view information.
+    pub(super) views: BTreeMap<ViewId, View>,
 
     /// Relation refer count mapping.
     // TODO(zehua): avoid key conflicts after distinguishing table's and source's id generator.
@@ -65,6 +69,10 @@ pub struct DatabaseManager<S: MetaStore> {
     pub(super) in_progress_creation_streaming_job: HashSet<TableId>,
     // In-progress creating tables, including internal tables.
     pub(super) in_progress_creating_tables: HashMap<TableId, Table>,
+    // In-progress altering tables.
+    pub(super) in_progress_alteration_tracker: HashSet<TableId>,
+    // In-progress truncating tables.
+    pub(super) in_progress_truncation_tracker: HashSet<TableId>,
 }
 
 impl<S> DatabaseManager<S>
@@ -78,6 +86,7 @@ where
         let sinks = Sink::list(env.meta_store()).await?;
         let tables = Table::list(env.meta_store()).await?;
         let indexes = Index::list(env.meta_store()).await?;
+        let views = View::list(env.meta_store()).await?;
 
         let mut relation_ref_count = HashMap::new();
 
@@ -95,6 +104,16 @@ where
 
         let indexes = BTreeMap::from_iter(indexes.into_iter().map(|index| (index.id, index)));
 
+        let views = BTreeMap::from_iter(views.into_iter().map(|view| {
+            for depend_relation_id in &view.dependent_relations {
+                relation_ref_count
+                    .entry(*depend_relation_id)
+                    .and_modify(|e| *e += 1)
+                    .or_insert(1);
+            }
+            (view.id, view)
+        }));
+
         let tables = BTreeMap::from_iter(tables.into_iter().map(|table| {
             for depend_relation_id in &table.dependent_relations {
                 relation_ref_count
@@ -113,10 +132,13 @@ where
             sinks,
             tables,
             indexes,
+            views,
             relation_ref_count,
             in_progress_creation_tracker: HashSet::default(),
             in_progress_creation_streaming_job: HashSet::default(),
             in_progress_creating_tables: HashMap::default(),
+            in_progress_alteration_tracker: HashSet::default(),
+            in_progress_truncation_tracker: HashSet::default(),
         })
     }
 
@@ -128,6 +150,7 @@ where
             Source::list(self.env.meta_store()).await?,
             Sink::list(self.env.meta_store()).await?,
             Index::list(self.env.meta_store()).await?,
+            View::list(self.env.meta_store()).await?,
         ))
     }
 
@@ -156,6 +179,12 @@ where
                 && x.name.eq(&relation_key.2)
         }) {
             Err(MetaError::catalog_duplicated("sink", &relation_key.2))
+        } else if self.views.values().any(|x| {
+            x.database_id == relation_key.0
+                && x.schema_id == relation_key.1
+                && x.name.eq(&relation_key.2)
+        }) {
+            Err(MetaError::catalog_duplicated("view", &relation_key.2))
         } else {
             Ok(())
         }
@@ -168,6 +197,14 @@ where
             .collect_vec()
     }
 
+    pub fn list_dependent_views(&self, relation_id: RelationId) -> Vec<View> {
+        self.views
+            .values()
+            .filter(|view| view.dependent_relations.contains(&relation_id))
+            .cloned()
+            .collect()
+    }
+
     pub fn list_sources(&self) -> Vec<Source> {
         self.sources.values().cloned().collect_vec()
     }
@@ -218,6 +255,18 @@ where
         }
     }
 
+    /// Fails if any of the relations is being truncated, since a table is only truncated when no
+    /// other relations depend on it.
+    pub fn ensure_not_in_truncation(&self, relation_ids: &[RelationId]) -> MetaResult<()> {
+        if let Some(id) = relation_ids
+            .iter()
+            .find(|id| self.in_progress_truncation_tracker.contains(id))
+        {
+            bail!("table {} is being truncated, please retry", id);
+        }
+        Ok(())
+    }
+
     pub fn has_in_progress_creation(&self, relation: &RelationKey) -> bool {
         self.in_progress_creation_tracker
             .contains(&relation.clone())
@@ -21,6 +21,7 @@ use itertools::Itertools;
 use risingwave_common::catalog::TableId;
 use risingwave_common::types::ParallelUnitId;
 use risingwave_common::{bail, try_match_expand};
+use risingwave_pb::catalog::{Source, Table};
 use risingwave_pb::common::{Buffer, ParallelUnit, ParallelUnitMapping, WorkerNode};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use risingwave_pb::meta::table_fragments::actor_status::ActorState;
@@ -183,6 +184,37 @@ where
         }
     }
 
+    /// Returns the column indices of the table `table_id` used by the other streaming jobs.
+    pub async fn get_table_used_columns(&self, table_id: &TableId) -> HashSet<u32> {
+        let map = &self.core.read().await.table_fragments;
+        map.values()
+            .filter(|table_fragments| table_fragments.table_id() != *table_id)
+            .flat_map(|table_fragments| table_fragments.used_upstream_columns(*table_id))
+            .collect()
+    }
+
+    /// Update the stream nodes of the table fragments with the altered schema of the table.
+    pub async fn update_table_fragments_schema(
+        &self,
+        source: &Source,
+        table: &Table,
+    ) -> MetaResult<()> {
+        let map = &mut self.core.write().await.table_fragments;
+        let table_id = TableId::new(table.id);
+        let mut table_fragments = match map.get(&table_id) {
+            Some(table_fragments) => table_fragments.clone(),
+            None => bail!("table_fragment not exist: id={}", table_id),
+        };
+        table_fragments.update_table_schema(source, table);
+
+        let mut transaction = Transaction::default();
+        table_fragments.upsert_in_transaction(&mut transaction)?;
+        self.env.meta_store().txn(transaction).await?;
+        map.insert(table_id, table_fragments);
+
+        Ok(())
+    }
+
     /// Start create a new `TableFragments` and insert it into meta store, currently the actors'
     /// state is `ActorState::Inactive` and the table fragments' state is `State::Creating`.
     pub async fn start_create_table_fragments(
@@ -31,7 +31,7 @@ use risingwave_common::catalog::{
 };
 use risingwave_common::{bail, ensure};
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table};
+use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use risingwave_pb::user::grant_privilege::{ActionWithGrantOption, Object};
 use risingwave_pb::user::update_user_request::UpdateField;
@@ -51,6 +51,7 @@ pub type SourceId = u32;
 pub type SinkId = u32;
 pub type RelationId = u32;
 pub type IndexId = u32;
+pub type ViewId = u32;
 
 pub type UserId = u32;
 
@@ -207,6 +208,7 @@ where
         let mut sinks = BTreeMapTransaction::new(&mut database_core.sinks);
         let mut tables = BTreeMapTransaction::new(&mut database_core.tables);
         let mut indexes = BTreeMapTransaction::new(&mut database_core.indexes);
+        let mut views = BTreeMapTransaction::new(&mut database_core.views);
         let mut users = BTreeMapTransaction::new(&mut user_core.user_info);
 
         let database = databases.remove(database_id);
@@ -250,6 +252,13 @@ where
                 }
             }
 
+            let view_ids = views.tree_ref().keys().copied().collect_vec();
+            for view_id in &view_ids {
+                if database_id == views.get(view_id).unwrap().database_id {
+                    views.remove(*view_id).unwrap();
+                }
+            }
+
             let mut objects = Vec::with_capacity(
                 1 + schemas_to_drop.len() + tables_to_drop.len() + sources_to_drop.len(),
             );
@@ -268,11 +277,11 @@ where
 
             let users_need_update = Self::update_user_privileges(&mut users, &objects);
 
-            commit_meta!(self, databases, schemas, sources, sinks, tables, indexes, users)?;
+            commit_meta!(self, databases, schemas, sources, sinks, tables, indexes, views, users)?;
 
-            database_core
-                .relation_ref_count
-                .retain(|k, _| (!table_ids.contains(k)) && (!source_ids.contains(k)));
+            database_core.relation_ref_count.retain(|k, _| {
+                (!table_ids.contains(k)) && (!source_ids.contains(k)) && (!view_ids.contains(k))
+            });
 
             for user in users_need_update {
                 self.notify_frontend(Operation::Update, Info::User(user))
@@ -338,6 +347,10 @@ where
                 .tables
                 .values()
                 .any(|t| t.database_id == schema.database_id && t.schema_id == schema_id)
+                || database_core
+                    .views
+                    .values()
+                    .any(|v| v.database_id == schema.database_id && v.schema_id == schema_id)
             {
                 bail!("schema is not empty!");
             }
@@ -425,6 +438,7 @@ where
             table.schema_id,
             table.name.clone(),
         ))?;
+        core.ensure_not_in_truncation(&table.dependent_relations)?;
         if core.has_in_progress_creation(&key) {
             bail!("table is in creating procedure");
         } else {
@@ -849,6 +863,92 @@ where
         }
     }
 
+    /// Start the alteration of a table with associated source. The `version` of the `table` should
+    /// match the current one. Returns the current catalog of the table.
+    pub async fn start_alter_table_procedure(
+        &self,
+        source: &Source,
+        table: &Table,
+    ) -> MetaResult<Table> {
+        let core = &mut self.core.lock().await.database;
+        let original_table = core
+            .tables
+            .get(&table.id)
+            .cloned()
+            .ok_or_else(|| MetaError::catalog_not_found("table", table.id.to_string()))?;
+        if !core.sources.contains_key(&source.id)
+            || original_table.optional_associated_source_id
+                != Some(OptionalAssociatedSourceId::AssociatedSourceId(source.id))
+        {
+            bail!(
+                "table {} is not associated with source {}",
+                table.id,
+                source.id
+            );
+        }
+        if original_table.version != table.version {
+            bail!(
+                "table {} has been altered concurrently, please retry",
+                original_table.name
+            );
+        }
+        if !core.in_progress_alteration_tracker.insert(table.id) {
+            bail!("table {} is in altering procedure", original_table.name);
+        }
+        Ok(original_table)
+    }
+
+    /// Finish the alteration of a table with associated source, the version of the table is bumped.
+    pub async fn finish_alter_table_procedure(
+        &self,
+        source: &Source,
+        table: &Table,
+    ) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        if !core.in_progress_alteration_tracker.remove(&table.id) {
+            bail!("table {} is not in altering procedure", table.name);
+        }
+        let mut tables = BTreeMapTransaction::new(&mut core.tables);
+        let mut sources = BTreeMapTransaction::new(&mut core.sources);
+        let table = Table {
+            version: table.version + 1,
+            ..table.clone()
+        };
+        sources.insert(source.id, source.clone());
+        tables.insert(table.id, table.clone());
+        commit_meta!(self, sources, tables)?;
+
+        self.notify_frontend(Operation::Update, Info::Table(table))
+            .await;
+        let version = self
+            .notify_frontend(Operation::Update, Info::Source(source.to_owned()))
+            .await;
+        Ok(version)
+    }
+
+    pub async fn cancel_alter_table_procedure(&self, table: &Table) {
+        let core = &mut self.core.lock().await.database;
+        core.in_progress_alteration_tracker.remove(&table.id);
+    }
+
+    /// Starts to truncate a table, returns false if other relations depend on it. Until
+    /// [`Self::finish_truncate_table_procedure`], no streaming job can be created on the table.
+    pub async fn start_truncate_table_procedure(&self, table_id: TableId) -> MetaResult<bool> {
+        let core = &mut self.core.lock().await.database;
+        if core.get_ref_count(table_id).unwrap_or(0) > 0 {
+            return Ok(false);
+        }
+        if !core.in_progress_truncation_tracker.insert(table_id) {
+            bail!("table {} is in truncating procedure", table_id);
+        }
+        Ok(true)
+    }
+
+    pub async fn finish_truncate_table_procedure(&self, table_id: TableId) {
+        let core = &mut self.core.lock().await.database;
+        core.in_progress_truncation_tracker.remove(&table_id);
+    }
+
     pub async fn drop_materialized_source(
         &self,
         source_id: SourceId,
@@ -1003,6 +1103,7 @@ where
             index.schema_id,
             index.name.clone(),
         ))?;
+        core.ensure_not_in_truncation(&index_table.dependent_relations)?;
         if core.has_in_progress_creation(&key) {
             bail!("index already in creating procedure");
         } else {
@@ -1077,6 +1178,7 @@ where
             sink.schema_id,
             sink.name.clone(),
         ))?;
+        core.ensure_not_in_truncation(&sink.dependent_relations)?;
         if core.has_in_progress_creation(&key) {
             bail!("sink already in creating procedure");
         } else {
@@ -1146,6 +1248,56 @@ where
         }
     }
 
+    pub async fn create_view(&self, view: &View) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        let key = (view.database_id, view.schema_id, view.name.clone());
+        core.check_relation_name_duplicated(&key)?;
+        if core.has_in_progress_creation(&key) {
+            bail!("view is in creating procedure");
+        }
+
+        let mut views = BTreeMapTransaction::new(&mut core.views);
+        views.insert(view.id, view.clone());
+        commit_meta!(self, views)?;
+
+        for &dependent_relation_id in &view.dependent_relations {
+            core.increase_ref_count(dependent_relation_id);
+        }
+
+        let version = self
+            .notify_frontend(Operation::Add, Info::View(view.to_owned()))
+            .await;
+
+        Ok(version)
+    }
+
+    pub async fn drop_view(&self, view_id: ViewId) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        let mut views = BTreeMapTransaction::new(&mut core.views);
+        let view = views.remove(view_id);
+        if let Some(view) = view {
+            if let Some(ref_count) = core.relation_ref_count.get(&view_id) {
+                return Err(MetaError::permission_denied(format!(
+                    "Fail to delete view `{}` because {} other relation(s) depend on it",
+                    view.name, ref_count
+                )));
+            }
+            commit_meta!(self, views)?;
+
+            for &dependent_relation_id in &view.dependent_relations {
+                core.decrease_ref_count(dependent_relation_id);
+            }
+
+            let version = self
+                .notify_frontend(Operation::Delete, Info::View(view))
+                .await;
+
+            Ok(version)
+        } else {
+            Err(MetaError::catalog_not_found("view", view_id.to_string()))
+        }
+    }
+
     pub async fn list_tables(&self, schema_id: SchemaId) -> MetaResult<Vec<TableId>> {
         let _core = &self.core.lock().await.user;
         let tables = Table::list(self.env.meta_store()).await?;
@@ -1160,6 +1312,15 @@ where
         self.core.lock().await.database.list_sources()
     }
 
+    /// Lists the views whose definitions refer to the relation.
+    pub async fn list_dependent_views(&self, relation_id: RelationId) -> Vec<View> {
+        self.core
+            .lock()
+            .await
+            .database
+            .list_dependent_views(relation_id)
+    }
+
     pub async fn list_source_ids(&self, schema_id: SchemaId) -> Vec<SourceId> {
         self.core.lock().await.database.list_source_ids(schema_id)
     }
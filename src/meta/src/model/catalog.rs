@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table};
+use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
 
 use crate::model::{MetadataModel, MetadataModelResult};
 
@@ -20,6 +20,8 @@ use crate::model::{MetadataModel, MetadataModelResult};
 const CATALOG_SOURCE_CF_NAME: &str = "cf/catalog_source";
 /// Column family name for sink catalog.
 const CATALOG_SINK_CF_NAME: &str = "cf/catalog_sink";
+/// Column family name for view catalog.
+const CATALOG_VIEW_CF_NAME: &str = "cf/catalog_view";
 /// Column family name for index catalog.
 const CATALOG_INDEX_CF_NAME: &str = "cf/catalog_index";
 /// Column family name for table catalog.
@@ -56,6 +58,7 @@ macro_rules! impl_model_for_catalog {
 
 impl_model_for_catalog!(Source, CATALOG_SOURCE_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Sink, CATALOG_SINK_CF_NAME, u32, get_id);
+impl_model_for_catalog!(View, CATALOG_VIEW_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Index, CATALOG_INDEX_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Table, CATALOG_TABLE_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Schema, CATALOG_SCHEMA_CF_NAME, u32, get_id);
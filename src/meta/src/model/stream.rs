@@ -19,10 +19,14 @@ use risingwave_common::catalog::TableId;
 use risingwave_common::types::ParallelUnitId;
 use risingwave_common::util::is_stream_source;
 use risingwave_connector::source::SplitImpl;
+use risingwave_pb::catalog::source::Info as SourceInfo;
+use risingwave_pb::catalog::{Source, Table};
 use risingwave_pb::common::{Buffer, ParallelUnit, ParallelUnitMapping};
 use risingwave_pb::meta::table_fragments::actor_status::ActorState;
 use risingwave_pb::meta::table_fragments::{ActorStatus, Fragment, State};
 use risingwave_pb::meta::TableFragments as ProstTableFragments;
+use risingwave_pb::plan_common::Field;
+use risingwave_pb::stream_plan::source_node::Info as SourceNodeInfo;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
 use risingwave_pb::stream_plan::{FragmentType, SourceNode, StreamActor, StreamNode};
 
@@ -282,6 +286,94 @@ impl TableFragments {
         }
     }
 
+    /// Resolve the upstream column indices of `table_id` used by the chain nodes.
+    fn resolve_used_upstream_columns(
+        stream_node: &StreamNode,
+        table_id: TableId,
+        column_indices: &mut HashSet<u32>,
+    ) {
+        if let Some(NodeBody::Chain(chain)) = stream_node.node_body.as_ref() {
+            if chain.table_id == table_id.table_id {
+                column_indices.extend(chain.upstream_column_indices.iter().copied());
+            }
+        }
+
+        for child in &stream_node.input {
+            Self::resolve_used_upstream_columns(child, table_id, column_indices);
+        }
+    }
+
+    /// Returns the column indices of the upstream table `table_id` used by these fragments.
+    pub fn used_upstream_columns(&self, table_id: TableId) -> HashSet<u32> {
+        let mut column_indices = HashSet::new();
+        self.fragments.values().for_each(|fragment| {
+            let actor = &fragment.actors[0];
+            Self::resolve_used_upstream_columns(
+                actor.nodes.as_ref().unwrap(),
+                table_id,
+                &mut column_indices,
+            );
+        });
+
+        column_indices
+    }
+
+    /// Apply the altered schema to the stream nodes of the table, i.e. the table source and the
+    /// materialize nodes, and all nodes in between.
+    pub fn update_table_schema(&mut self, source: &Source, table: &Table) {
+        fn update_stream_node(
+            stream_node: &mut StreamNode,
+            source: &Source,
+            table: &Table,
+            fields: &[Field],
+        ) {
+            stream_node.fields = fields.to_vec();
+            match stream_node.node_body.as_mut() {
+                Some(NodeBody::Source(source_node)) if source_node.source_id == source.id => {
+                    let (info, columns) = match source.info.as_ref().unwrap() {
+                        SourceInfo::TableSource(info) => {
+                            (SourceNodeInfo::TableSource(info.clone()), &info.columns)
+                        }
+                        SourceInfo::StreamSource(info) => {
+                            (SourceNodeInfo::StreamSource(info.clone()), &info.columns)
+                        }
+                    };
+                    source_node.column_ids = columns
+                        .iter()
+                        .map(|c| c.column_desc.as_ref().unwrap().column_id)
+                        .collect();
+                    source_node.info = Some(info);
+                }
+                Some(NodeBody::Materialize(materialize)) if materialize.table_id == table.id => {
+                    materialize.table = Some(table.clone());
+                }
+                Some(NodeBody::Merge(merge)) => merge.fields = fields.to_vec(),
+                _ => {}
+            }
+
+            for child in &mut stream_node.input {
+                update_stream_node(child, source, table, fields);
+            }
+        }
+
+        let fields = table
+            .columns
+            .iter()
+            .map(|c| {
+                let desc = c.column_desc.as_ref().unwrap();
+                Field {
+                    data_type: desc.column_type.clone(),
+                    name: desc.name.clone(),
+                }
+            })
+            .collect_vec();
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                update_stream_node(actor.nodes.as_mut().unwrap(), source, table, &fields);
+            }
+        }
+    }
+
     /// Returns dependent table ids.
     pub fn dependent_table_ids(&self) -> HashSet<TableId> {
         let mut table_ids = HashSet::new();
@@ -15,6 +15,7 @@
 use std::collections::HashSet;
 
 use itertools::Itertools;
+use risingwave_common::bail;
 use risingwave_common::catalog::CatalogVersion;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::*;
@@ -36,7 +37,7 @@ use crate::storage::MetaStore;
 use crate::stream::{
     ActorGraphBuilder, CreateMaterializedViewContext, GlobalStreamManagerRef, SourceManagerRef,
 };
-use crate::MetaResult;
+use crate::{MetaError, MetaResult};
 
 #[derive(Clone)]
 pub struct DdlServiceImpl<S: MetaStore> {
@@ -395,6 +396,68 @@ where
         }))
     }
 
+    async fn alter_table(
+        &self,
+        request: Request<AlterTableRequest>,
+    ) -> Result<Response<AlterTableResponse>, Status> {
+        let request = request.into_inner();
+        let source = request.source.unwrap();
+        let table = request.table.unwrap();
+
+        let version = self.alter_table_inner(source, table).await?;
+
+        Ok(Response::new(AlterTableResponse {
+            status: None,
+            version,
+        }))
+    }
+
+    async fn truncate_table(
+        &self,
+        request: Request<TruncateTableRequest>,
+    ) -> Result<Response<TruncateTableResponse>, Status> {
+        let table_id = request.into_inner().table_id;
+
+        let truncated = self.truncate_table_inner(table_id).await?;
+
+        Ok(Response::new(TruncateTableResponse {
+            status: None,
+            truncated,
+        }))
+    }
+
+    async fn create_view(
+        &self,
+        request: Request<CreateViewRequest>,
+    ) -> Result<Response<CreateViewResponse>, Status> {
+        let req = request.into_inner();
+        let mut view = req.get_view()?.clone();
+        // Views share the id space with other relations, so that they can be tracked in the
+        // relation ref count.
+        let id = self.gen_unique_id::<{ IdCategory::Table }>().await?;
+        view.id = id;
+        let version = self.catalog_manager.create_view(&view).await?;
+
+        Ok(Response::new(CreateViewResponse {
+            status: None,
+            view_id: id,
+            version,
+        }))
+    }
+
+    async fn drop_view(
+        &self,
+        request: Request<DropViewRequest>,
+    ) -> Result<Response<DropViewResponse>, Status> {
+        let req = request.into_inner();
+        let view_id = req.get_view_id();
+        let version = self.catalog_manager.drop_view(view_id).await?;
+        Ok(Response::new(DropViewResponse {
+            status: None,
+            version,
+        }))
+    }
+
     async fn risectl_list_state_tables(
         &self,
         _request: Request<RisectlListStateTablesRequest>,
@@ -732,6 +795,107 @@ where
         Ok(version)
     }
 
+    async fn alter_table_inner(&self, source: Source, table: Table) -> MetaResult<CatalogVersion> {
+        // 1. Check the version of the table and mark it as altering.
+        let original_table = self
+            .catalog_manager
+            .start_alter_table_procedure(&source, &table)
+            .await?;
+
+        // 2. Apply the new columns to the streaming job of the table.
+        match self
+            .alter_table_stream_job(&original_table, &source, &table)
+            .await
+        {
+            Ok(_) => {
+                self.catalog_manager
+                    .finish_alter_table_procedure(&source, &table)
+                    .await
+            }
+            Err(err) => {
+                self.catalog_manager
+                    .cancel_alter_table_procedure(&table)
+                    .await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Truncates the table by range tombstones, returns false if other relations depend on it, in
+    /// which case the frontend should delete the rows through the DML path instead.
+    async fn truncate_table_inner(&self, table_id: u32) -> MetaResult<bool> {
+        if !self
+            .catalog_manager
+            .start_truncate_table_procedure(table_id)
+            .await?
+        {
+            return Ok(false);
+        }
+
+        // Creating streaming jobs on the table is rejected until the truncation is done, as they
+        // would otherwise backfill from a partially truncated table.
+        let result = self.stream_manager.truncate_table(table_id.into()).await;
+        self.catalog_manager
+            .finish_truncate_table_procedure(table_id)
+            .await;
+        result?;
+        Ok(true)
+    }
+
+    async fn alter_table_stream_job(
+        &self,
+        original_table: &Table,
+        source: &Source,
+        table: &Table,
+    ) -> MetaResult<()> {
+        if table.columns.len() < original_table.columns.len() {
+            bail!("columns of a table can not be removed physically");
+        }
+
+        // Columns to be dropped are still kept physically but hidden, they must not be used by
+        // any downstream streaming jobs or views.
+        let used_columns = self
+            .fragment_manager
+            .get_table_used_columns(&table.id.into())
+            .await;
+        // Views only record the relations they depend on, so any of them blocks dropping columns.
+        let dependent_views = self
+            .catalog_manager
+            .list_dependent_views(table.id)
+            .await
+            .into_iter()
+            .map(|view| format!("view \"{}\"", view.name))
+            .collect_vec();
+        for (idx, (original, altered)) in original_table
+            .columns
+            .iter()
+            .zip_eq(&table.columns[..original_table.columns.len()])
+            .enumerate()
+        {
+            if original.is_hidden || !altered.is_hidden {
+                continue;
+            }
+            let mut dependents = dependent_views.clone();
+            if used_columns.contains(&(idx as u32)) {
+                dependents.push("streaming jobs".to_string());
+            }
+            if !dependents.is_empty() {
+                return Err(MetaError::catalog_dependent(
+                    format!(
+                        "column \"{}\" of table \"{}\"",
+                        original.column_desc.as_ref().unwrap().name,
+                        original_table.name
+                    ),
+                    dependents.join(", "),
+                ));
+            }
+        }
+
+        self.stream_manager
+            .alter_table(source.clone(), table.clone())
+            .await
+    }
+
     async fn gen_unique_id<const C: IdCategoryType>(&self) -> MetaResult<u32> {
         let id = self.env.id_gen_manager().generate::<C>().await? as u32;
         Ok(id)
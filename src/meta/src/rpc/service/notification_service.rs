@@ -83,7 +83,7 @@ where
         let (tx, rx) = mpsc::unbounded_channel();
 
         let catalog_guard = self.catalog_manager.get_catalog_core_guard().await;
-        let (databases, schemas, mut tables, sources, sinks, indexes) =
+        let (databases, schemas, mut tables, sources, sinks, indexes, views) =
             catalog_guard.database.get_catalog().await?;
         let creating_tables = catalog_guard.database.list_creating_tables();
         let users = catalog_guard.user.list_users();
@@ -135,6 +135,7 @@ where
                 sinks,
                 tables,
                 indexes,
+                views,
                 users,
                 parallel_unit_mappings,
                 hummock_version: None,
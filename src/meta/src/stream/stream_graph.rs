@@ -565,6 +565,15 @@ impl StreamGraphBuilder {
                         }
                     }
 
+                    NodeBody::IntervalJoin(node) => {
+                        if let Some(table) = &mut node.left_table {
+                            update_table(table, "IntervalJoinLeft");
+                        }
+                        if let Some(table) = &mut node.right_table {
+                            update_table(table, "IntervalJoinRight");
+                        }
+                    }
+
                     NodeBody::Source(node) => {
                         if let Some(table) = &mut node.state_table {
                             update_table(table, "SourceInternalTable");
@@ -618,6 +627,24 @@ impl StreamGraphBuilder {
                         }
                     }
 
+                    NodeBody::OverWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "OverWindowNode");
+                        }
+                    }
+
+                    NodeBody::SessionWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "SessionWindowNode");
+                        }
+                    }
+
+                    NodeBody::WatermarkFilter(node) => {
+                        if let Some(table) = &mut node.table {
+                            update_table(table, "WatermarkFilterNode");
+                        }
+                    }
+
                     NodeBody::GlobalSimpleAgg(node) => {
                         assert_eq!(node.agg_call_states.len(), node.agg_calls.len());
                         // In-place update the table id. Convert from local to global.
@@ -1098,6 +1125,12 @@ impl ActorGraphBuilder {
                     node.right_degree_table.as_ref().unwrap().id,
                 ]
             }
+            NodeBody::IntervalJoin(node) => {
+                vec![
+                    node.left_table.as_ref().unwrap().id,
+                    node.right_table.as_ref().unwrap().id,
+                ]
+            }
             NodeBody::DynamicFilter(node) => {
                 vec![
                     node.left_table.as_ref().unwrap().id,
@@ -1110,6 +1143,15 @@ impl ActorGraphBuilder {
             NodeBody::GroupTopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
+            NodeBody::OverWindow(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::SessionWindow(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::WatermarkFilter(node) => {
+                vec![node.table.as_ref().unwrap().id]
+            }
             NodeBody::TopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
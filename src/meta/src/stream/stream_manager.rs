@@ -20,7 +20,7 @@ use itertools::Itertools;
 use risingwave_common::bail;
 use risingwave_common::catalog::TableId;
 use risingwave_common::types::VIRTUAL_NODE_COUNT;
-use risingwave_pb::catalog::Table;
+use risingwave_pb::catalog::{Source, Table};
 use risingwave_pb::common::{ActorInfo, Buffer, WorkerType};
 use risingwave_pb::meta::table_fragments::actor_status::ActorState;
 use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
@@ -695,6 +695,29 @@ where
         Ok(())
     }
 
+    /// Altering the schema of a table with associated source is done by barrier manager. Check
+    /// [`Command::AlterTable`] for details.
+    pub async fn alter_table(&self, source: Source, table: Table) -> MetaResult<()> {
+        self.barrier_scheduler
+            .run_command(Command::AlterTable { source, table })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Truncating a table without downstream is done by barrier manager. Check
+    /// [`Command::TruncateTable`] for details.
+    pub async fn truncate_table(&self, table_id: TableId) -> MetaResult<()> {
+        self.barrier_scheduler
+            .run_command(Command::TruncateTable(table_id))
+            .await?;
+        // The range tombstones are written in the epoch after the truncation barrier, flush a
+        // checkpoint so that they are visible to batch queries once the statement returns.
+        self.barrier_scheduler.flush(true).await?;
+
+        Ok(())
+    }
+
     /// Dropping materialized view is done by barrier manager. Check
     /// [`Command::DropMaterializedViews`] for details.
     pub async fn drop_materialized_views(&self, table_ids: Vec<TableId>) -> MetaResult<()> {
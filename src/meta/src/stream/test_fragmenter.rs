@@ -105,6 +105,7 @@ fn make_column(column_type: TypeName, column_id: i32) -> ColumnCatalog {
             ..Default::default()
         }),
         is_hidden: false,
+        default_value: vec![],
     }
 }
 
@@ -307,6 +308,7 @@ fn make_stream_fragments() -> Vec<StreamFragment> {
                 make_inputref(0),
                 make_inputref(1),
             ],
+            ..Default::default()
         })),
         fields: vec![], // TODO: fill this later
         input: vec![simple_agg_node_1],
@@ -26,7 +26,7 @@ use risingwave_hummock_sdk::{
 };
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable,
+    Source as ProstSource, Table as ProstTable, View as ProstView,
 };
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
@@ -213,6 +213,13 @@ impl MetaClient {
         Ok((resp.sink_id, resp.version))
     }
 
+    pub async fn create_view(&self, view: ProstView) -> Result<(u32, CatalogVersion)> {
+        let request = CreateViewRequest { view: Some(view) };
+        let resp = self.inner.create_view(request).await?;
+        // TODO: handle error in `resp.status` here
+        Ok((resp.view_id, resp.version))
+    }
+
     pub async fn create_materialized_source(
         &self,
         source: ProstSource,
@@ -261,6 +268,27 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn alter_table(
+        &self,
+        source: ProstSource,
+        table: ProstTable,
+    ) -> Result<CatalogVersion> {
+        let request = AlterTableRequest {
+            source: Some(source),
+            table: Some(table),
+        };
+
+        let resp = self.inner.alter_table(request).await?;
+        Ok(resp.version)
+    }
+
+    /// Returns false if the table is not truncated since other relations depend on it.
+    pub async fn truncate_table(&self, table_id: u32) -> Result<bool> {
+        let request = TruncateTableRequest { table_id };
+        let resp = self.inner.truncate_table(request).await?;
+        Ok(resp.truncated)
+    }
+
     pub async fn drop_source(&self, source_id: u32) -> Result<CatalogVersion> {
         let request = DropSourceRequest { source_id };
         let resp = self.inner.drop_source(request).await?;
@@ -273,6 +301,12 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn drop_view(&self, view_id: u32) -> Result<CatalogVersion> {
+        let request = DropViewRequest { view_id };
+        let resp = self.inner.drop_view(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_index(&self, index_id: IndexId) -> Result<CatalogVersion> {
         let request = DropIndexRequest {
             index_id: index_id.index_id,
@@ -827,13 +861,17 @@ macro_rules! for_all_meta_rpc {
             ,{ ddl_client, create_schema, CreateSchemaRequest, CreateSchemaResponse }
             ,{ ddl_client, create_database, CreateDatabaseRequest, CreateDatabaseResponse }
             ,{ ddl_client, create_index, CreateIndexRequest, CreateIndexResponse }
+            ,{ ddl_client, create_view, CreateViewRequest, CreateViewResponse }
             ,{ ddl_client, drop_materialized_source, DropMaterializedSourceRequest, DropMaterializedSourceResponse }
+            ,{ ddl_client, alter_table, AlterTableRequest, AlterTableResponse }
+            ,{ ddl_client, truncate_table, TruncateTableRequest, TruncateTableResponse }
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
             ,{ ddl_client, drop_sink, DropSinkRequest, DropSinkResponse }
             ,{ ddl_client, drop_database, DropDatabaseRequest, DropDatabaseResponse }
             ,{ ddl_client, drop_schema, DropSchemaRequest, DropSchemaResponse }
             ,{ ddl_client, drop_index, DropIndexRequest, DropIndexResponse }
+            ,{ ddl_client, drop_view, DropViewRequest, DropViewResponse }
             ,{ ddl_client, risectl_list_state_tables, RisectlListStateTablesRequest, RisectlListStateTablesResponse }
             ,{ hummock_client, unpin_version_before, UnpinVersionBeforeRequest, UnpinVersionBeforeResponse }
             ,{ hummock_client, get_current_version, GetCurrentVersionRequest, GetCurrentVersionResponse }
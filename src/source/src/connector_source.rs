@@ -24,7 +24,7 @@ use risingwave_common::catalog::{ColumnId, TableId};
 use risingwave_common::error::{internal_error, Result, RwError, ToRwResult};
 use risingwave_common::util::select_all;
 use risingwave_connector::source::{
-    Column, ConnectorProperties, ConnectorState, SourceMessage, SplitId, SplitMetaData,
+    Column, ConnectorProperties, ConnectorState, SourceMessage, SplitId, SplitImpl, SplitMetaData,
     SplitReaderImpl,
 };
 
@@ -68,6 +68,9 @@ pub struct ConnectorSourceReader {
     parser: Arc<SourceParserImpl>,
     columns: Vec<SourceColumnDesc>,
 
+    metrics: Arc<SourceMetrics>,
+    context: SourceContext,
+
     // merge all streams of inner reader into one
     // TODO: make this static dispatch instead of box
     stream: BoxStream<'static, Result<Vec<SourceMessage>>>,
@@ -136,6 +139,8 @@ impl InnerConnectorSourceReader {
 impl ConnectorSourceReader {
     #[try_stream(boxed, ok = StreamChunkWithState, error = RwError)]
     pub async fn into_stream(self) {
+        let actor_id = self.context.actor_id.to_string();
+        let source_id = self.context.source_id.to_string();
         #[for_await]
         for batch in self.stream {
             let batch = batch?;
@@ -145,9 +150,20 @@ impl ConnectorSourceReader {
 
             for msg in batch {
                 if let Some(content) = msg.payload {
-                    split_offset_mapping.insert(msg.split_id, msg.offset);
-                    if let Err(e) = self.parser.parse(content.as_ref(), builder.row_writer()) {
+                    split_offset_mapping.insert(msg.split_id.clone(), msg.offset);
+                    // The schema registry is unavailable even after retries, and the message
+                    // can't be parsed without the schema, so the source fails instead of skipping
+                    // the message.
+                    self.parser.resolve_schema(content.as_ref()).await?;
+                    let parsed =
+                        self.parser
+                            .parse(&msg.split_id, content.as_ref(), builder.row_writer());
+                    if let Err(e) = parsed {
                         tracing::warn!("message parsing failed {}, skipping", e.to_string());
+                        self.metrics
+                            .source_parse_error_count
+                            .with_label_values(&[&actor_id, &source_id])
+                            .inc();
                         continue;
                     }
                 }
@@ -196,14 +212,26 @@ impl ConnectorSource {
     ) -> Result<ConnectorSourceReader> {
         let config = self.config.clone();
         let columns = self.get_target_columns(column_ids)?;
+        // The new readers start the splits over, e.g. emit the header line of a CSV file again.
+        if let SourceParserImpl::Csv(parser) = self.parser.as_ref() {
+            parser.clear_headers();
+        }
         let source_metrics = metrics.clone();
 
         let to_reader_splits = match splits {
             Some(vec_split_impl) => vec_split_impl
                 .into_iter()
+                // No reader is created for fully read S3 objects, which are immutable, while local
+                // files may grow beyond the size when they are listed.
+                .filter(|split| !matches!(split, SplitImpl::S3(s) if s.offset >= s.size))
                 .map(|split| Some(vec![split]))
                 .collect::<Vec<ConnectorState>>(),
-            None => vec![None],
+            None => vec![],
+        };
+        // Wait on a dummy reader if there is no split to read.
+        let to_reader_splits = match to_reader_splits.is_empty() {
+            true => vec![None],
+            false => to_reader_splits,
         };
         let readers =
             try_join_all(to_reader_splits.into_iter().map(|split| {
@@ -223,6 +251,8 @@ impl ConnectorSource {
         Ok(ConnectorSourceReader {
             parser: self.parser.clone(),
             columns,
+            metrics,
+            context,
             stream,
         })
     }
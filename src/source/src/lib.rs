@@ -21,6 +21,7 @@
 #![feature(result_option_inspect)]
 #![feature(generators)]
 #![feature(hash_drain_filter)]
+#![feature(let_else)]
 
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -54,6 +55,9 @@ pub enum SourceFormat {
     Protobuf,
     DebeziumJson,
     Avro,
+    Csv,
+    CanalJson,
+    Maxwell,
 }
 
 #[derive(Debug, EnumAsInner)]
@@ -18,7 +18,7 @@ use std::sync::{Arc, Weak};
 
 use itertools::Itertools;
 use parking_lot::Mutex;
-use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+use risingwave_common::catalog::{column_default_from_protobuf, ColumnDesc, ColumnId, TableId};
 use risingwave_common::error::ErrorCode::{ConnectorError, InternalError, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::DataType;
@@ -143,13 +143,18 @@ impl TableSourceManager {
                 .cloned()
                 .map(|c| ColumnDesc::from(c.column_desc.unwrap()))
                 .collect_vec();
+            let column_defaults = info
+                .columns
+                .iter()
+                .map(|c| column_default_from_protobuf(c).flatten())
+                .collect_vec();
             let row_id_index = info.row_id_index.as_ref().map(|index| index.index as _);
             let pk_column_ids = info.pk_column_ids.clone();
 
             // Table sources do not need columns and format
             let strong_ref = Arc::new(SourceDesc {
                 columns: columns.iter().map(SourceColumnDesc::from).collect(),
-                source: SourceImpl::Table(TableSource::new(columns)),
+                source: SourceImpl::Table(TableSource::new(columns, column_defaults)),
                 format: SourceFormat::Invalid,
                 row_id_index,
                 pk_column_ids,
@@ -210,6 +215,12 @@ impl SourceDescBuilder {
         }
     }
 
+    /// Update the source info after the source is altered. The source desc built afterwards will
+    /// have the new columns.
+    pub fn update_info(&mut self, info: ProstSourceInfo) {
+        self.info = info;
+    }
+
     pub async fn build(&self) -> Result<SourceDescRef> {
         let Self { id, info, mgr } = self;
         match &info {
@@ -235,6 +246,9 @@ impl SourceDescBuilder {
             RowFormatType::Protobuf => SourceFormat::Protobuf,
             RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
             RowFormatType::Avro => SourceFormat::Avro,
+            RowFormatType::Csv => SourceFormat::Csv,
+            RowFormatType::CanalJson => SourceFormat::CanalJson,
+            RowFormatType::Maxwell => SourceFormat::Maxwell,
             RowFormatType::RowUnspecified => unreachable!(),
         };
 
@@ -243,9 +257,13 @@ impl SourceDescBuilder {
                 "protobuf file location not provided".to_string(),
             )));
         }
-        let source_parser_rs =
-            SourceParserImpl::create(&format, &info.properties, info.row_schema_location.as_str())
-                .await;
+        let source_parser_rs = SourceParserImpl::create(
+            &format,
+            &info.properties,
+            info.row_schema_location.as_str(),
+            info.use_schema_registry,
+        )
+        .await;
         let parser = if let Ok(source_parser) = source_parser_rs {
             source_parser
         } else {
@@ -267,8 +285,15 @@ impl SourceDescBuilder {
             "source should have at least one pk column"
         );
 
-        let config = ConnectorProperties::extract(info.properties.clone())
+        let mut config = ConnectorProperties::extract(info.properties.clone())
             .map_err(|e| RwError::from(ConnectorError(e.into())))?;
+        if matches!(parser.as_ref(), SourceParserImpl::Csv(parser) if parser.has_header())
+            && !config.set_header_line()
+        {
+            return Err(RwError::from(ProtocolError(
+                "csv header is only supported by file sources".to_string(),
+            )));
+        }
 
         let source = SourceImpl::Connector(ConnectorSource {
             config,
@@ -316,6 +341,7 @@ mod tests {
             .map(|c| ColumnCatalog {
                 column_desc: Some(c.to_owned()),
                 is_hidden: false,
+                default_value: vec![],
             })
             .collect();
         let info = StreamSourceInfo {
@@ -325,6 +351,7 @@ mod tests {
             row_id_index: Some(ColumnIndex { index: 0 }),
             pk_column_ids: vec![0],
             columns,
+            ..Default::default()
         };
         let source_id = TableId::default();
 
@@ -367,6 +394,7 @@ mod tests {
                         .to_protobuf(),
                     ),
                     is_hidden: false,
+                    default_value: vec![],
                 })
                 .collect(),
             pk_column_ids: vec![1],
@@ -19,6 +19,7 @@ use prometheus::{register_int_counter_vec_with_registry, Registry};
 pub struct SourceMetrics {
     pub registry: Registry,
     pub partition_input_count: GenericCounterVec<AtomicU64>,
+    pub source_parse_error_count: GenericCounterVec<AtomicU64>,
 }
 
 impl SourceMetrics {
@@ -30,9 +31,17 @@ impl SourceMetrics {
             registry
         )
         .unwrap();
+        let source_parse_error_count = register_int_counter_vec_with_registry!(
+            "source_parse_error_count",
+            "Total number of messages that failed to be parsed and have been skipped",
+            &["actor_id", "source_id"],
+            registry
+        )
+        .unwrap();
         SourceMetrics {
             registry,
             partition_input_count,
+            source_parse_error_count,
         }
     }
 
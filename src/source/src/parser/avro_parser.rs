@@ -16,13 +16,17 @@ use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::future::Future;
 use std::path::Path;
+use std::sync::Arc;
 
 use apache_avro::types::Value;
-use apache_avro::{Reader, Schema};
+use apache_avro::{from_avro_datum, Reader, Schema};
 use chrono::{Datelike, NaiveDate};
 use itertools::Itertools;
+use parking_lot::RwLock;
 use risingwave_common::array::{ListValue, StructValue};
-use risingwave_common::error::ErrorCode::{InternalError, InvalidConfigValue, ProtocolError};
+use risingwave_common::error::ErrorCode::{
+    InternalError, InvalidConfigValue, ItemNotFound, ProtocolError,
+};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{
     DataType, Datum, NaiveDateTimeWrapper, NaiveDateWrapper, OrderedF32, OrderedF64, ScalarImpl,
@@ -31,6 +35,9 @@ use risingwave_connector::aws_utils::{default_conn_config, s3_client, AwsConfigV
 use risingwave_pb::plan_common::ColumnDesc;
 use url::Url;
 
+use crate::parser::schema_registry::{
+    extract_schema_id, get_subject_by_topic, ConfluentSchema, SchemaRegistryClient,
+};
 use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
 const AVRO_SCHEMA_LOCATION_S3_REGION: &str = "region";
@@ -39,13 +46,57 @@ pub fn unix_epoch_days() -> i32 {
     NaiveDate::from_ymd(1970, 1, 1).num_days_from_ce()
 }
 
+/// Caches the writer schemas fetched from the schema registry by their ids.
+#[derive(Debug)]
+struct ConfluentSchemaResolver {
+    writer_schemas: RwLock<HashMap<i32, Arc<Schema>>>,
+    client: SchemaRegistryClient,
+}
+
+impl ConfluentSchemaResolver {
+    fn new(client: SchemaRegistryClient) -> Self {
+        Self {
+            writer_schemas: RwLock::new(HashMap::new()),
+            client,
+        }
+    }
+
+    fn get_cached(&self, schema_id: i32) -> Option<Arc<Schema>> {
+        self.writer_schemas.read().get(&schema_id).cloned()
+    }
+
+    fn insert(&self, schema: &ConfluentSchema) -> Result<Arc<Schema>> {
+        let parsed = Arc::new(Schema::parse_str(&schema.content).map_err(|e| {
+            RwError::from(ProtocolError(format!(
+                "failed to parse avro schema of id {}: {}",
+                schema.id, e
+            )))
+        })?);
+        self.writer_schemas
+            .write()
+            .insert(schema.id, parsed.clone());
+        Ok(parsed)
+    }
+}
+
 #[derive(Debug)]
 pub struct AvroParser {
+    /// The reader schema. With the schema registry, it's the latest schema when the source is
+    /// created, and the messages written with other versions of the schema are resolved into it.
     schema: Schema,
+    schema_resolver: Option<ConfluentSchemaResolver>,
 }
 
 impl AvroParser {
-    pub async fn new(schema_location: &str, props: HashMap<String, String>) -> Result<Self> {
+    pub async fn new(
+        schema_location: &str,
+        props: HashMap<String, String>,
+        use_schema_registry: bool,
+    ) -> Result<Self> {
+        if use_schema_registry {
+            return Self::new_with_schema_registry(schema_location, &props).await;
+        }
+
         let url = Url::parse(schema_location).map_err(|e| {
             InternalError(format!("failed to parse url ({}): {}", schema_location, e))
         })?;
@@ -75,12 +126,79 @@ impl AvroParser {
                 )))),
             };
         if let Ok(schema) = arvo_schema {
-            Ok(Self { schema })
+            Ok(Self {
+                schema,
+                schema_resolver: None,
+            })
         } else {
             Err(arvo_schema.err().unwrap())
         }
     }
 
+    async fn new_with_schema_registry(url: &str, props: &HashMap<String, String>) -> Result<Self> {
+        let subject = get_subject_by_topic(props)?;
+        let resolver = ConfluentSchemaResolver::new(SchemaRegistryClient::new(url));
+        let latest = resolver.client.get_schema_by_subject(&subject).await?;
+        let schema = resolver.insert(&latest)?.as_ref().clone();
+        Ok(Self {
+            schema,
+            schema_resolver: Some(resolver),
+        })
+    }
+
+    /// Fetches the writer schema of the message from the schema registry if it's not cached yet,
+    /// so that the message can be parsed by [`SourceParser::parse`] afterwards. An error is only
+    /// returned if the schema registry is unavailable after retries, while a malformed message,
+    /// including one with an unknown schema id, is left to be rejected by [`SourceParser::parse`].
+    pub async fn resolve_schema(&self, payload: &[u8]) -> Result<()> {
+        let Some(resolver) = &self.schema_resolver else {
+            return Ok(());
+        };
+        let Ok((schema_id, _)) = extract_schema_id(payload) else {
+            return Ok(());
+        };
+        if resolver.get_cached(schema_id).is_some() {
+            return Ok(());
+        }
+        // The messages whose schema can't be resolved however many times it's retried, i.e. the
+        // id is unknown or the schema is invalid, are left to fail parsing and get skipped.
+        let schema = match resolver.client.get_schema_by_id(schema_id).await {
+            Ok(schema) => schema,
+            Err(e) if matches!(e.inner(), ItemNotFound(_)) => {
+                tracing::warn!("failed to resolve avro schema of id {}: {}", schema_id, e);
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        if let Err(e) = resolver.insert(&schema) {
+            tracing::warn!("{}", e);
+        }
+        Ok(())
+    }
+
+    fn read_value(&self, payload: &[u8]) -> Result<Value> {
+        let Some(resolver) = &self.schema_resolver else {
+            let mut reader = Reader::with_schema(&self.schema, payload)
+                .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+            return match reader.next() {
+                Some(value) => value.map_err(|e| RwError::from(ProtocolError(e.to_string()))),
+                None => Err(RwError::from(ProtocolError(
+                    "avro parse unexpected eof".to_string(),
+                ))),
+            };
+        };
+
+        let (schema_id, mut datum) = extract_schema_id(payload)?;
+        let writer_schema = resolver.get_cached(schema_id).ok_or_else(|| {
+            RwError::from(ProtocolError(format!(
+                "avro schema of id {} is not resolved",
+                schema_id
+            )))
+        })?;
+        from_avro_datum(&writer_schema, &mut datum, Some(&self.schema))
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))
+    }
+
     pub fn map_to_columns(&self) -> Result<Vec<ColumnDesc>> {
         // there must be a record at top level
         if let Schema::Record { fields, .. } = &self.schema {
@@ -237,28 +355,21 @@ fn from_avro_value(value: Value) -> Result<Datum> {
 
 impl SourceParser for AvroParser {
     fn parse(&self, payload: &[u8], writer: SourceStreamChunkRowWriter<'_>) -> Result<WriteGuard> {
-        match Reader::with_schema(&self.schema, payload) {
-            Ok(mut reader) => match reader.next() {
-                Some(Ok(Value::Record(fields))) => writer.insert(|column| {
-                    let tuple = fields.iter().find(|val| column.name.eq(&val.0)).unwrap();
-                    from_avro_value(tuple.1.clone()).map_err(|e| {
-                        tracing::error!(
-                            "failed to process value ({}): {}",
-                            String::from_utf8_lossy(payload),
-                            e
-                        );
+        match self.read_value(payload)? {
+            Value::Record(fields) => writer.insert(|column| {
+                let tuple = fields.iter().find(|val| column.name.eq(&val.0)).unwrap();
+                from_avro_value(tuple.1.clone()).map_err(|e| {
+                    tracing::error!(
+                        "failed to process value ({}): {}",
+                        String::from_utf8_lossy(payload),
                         e
-                    })
-                }),
-                Some(Ok(_)) => Err(RwError::from(ProtocolError(
-                    "avro parse unexpected value".to_string(),
-                ))),
-                Some(Err(e)) => Err(RwError::from(ProtocolError(e.to_string()))),
-                None => Err(RwError::from(ProtocolError(
-                    "avro parse unexpected eof".to_string(),
-                ))),
-            },
-            Err(e) => Err(RwError::from(ProtocolError(e.to_string()))),
+                    );
+                    e
+                })
+            }),
+            _ => Err(RwError::from(ProtocolError(
+                "avro parse unexpected value".to_string(),
+            ))),
         }
     }
 }
@@ -448,7 +559,7 @@ mod test {
 
     async fn new_avro_parser_from_local(file_name: &str) -> error::Result<AvroParser> {
         let schema_path = "file://".to_owned() + &test_data_path(file_name);
-        AvroParser::new(schema_path.as_str(), HashMap::new()).await
+        AvroParser::new(schema_path.as_str(), HashMap::new(), false).await
     }
 
     #[tokio::test]
@@ -641,4 +752,86 @@ mod test {
         let avro_parser = avro_parser_rs.unwrap();
         println!("avro_parser = {:?}", avro_parser);
     }
+
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_avro_parser_with_schema_registry() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let schema_v1 = r#"{"type":"record","name":"t","fields":[
+            {"name":"id","type":"int"},{"name":"name","type":"string"}]}"#;
+        // A field with default is added in v2, which is the latest one.
+        let schema_v2 = r#"{"type":"record","name":"t","fields":[
+            {"name":"id","type":"int"},{"name":"name","type":"string"},
+            {"name":"age","type":"int","default":18}]}"#;
+
+        let server = MockServer::start().await;
+        for (api, id, schema) in [
+            ("/subjects/t-value/versions/latest", 2, schema_v2),
+            ("/schemas/ids/1", 1, schema_v1),
+        ] {
+            let body = serde_json::json!({ "id": id, "schema": schema }).to_string();
+            Mock::given(method("GET"))
+                .and(path(api))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/4"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let props = HashMap::from([("kafka.topic".to_string(), "t".to_string())]);
+        let parser = AvroParser::new(&server.uri(), props, true).await.unwrap();
+        assert_eq!(
+            parser.map_to_columns().unwrap().len(),
+            3,
+            "columns should be mapped from the latest schema"
+        );
+
+        // A message written with v1.
+        let writer_schema = Schema::parse_str(schema_v1).unwrap();
+        let mut record = Record::new(&writer_schema).unwrap();
+        record.put("id", 1_i32);
+        record.put("name", "a".to_string());
+        let mut payload = vec![0, 0, 0, 0, 1];
+        payload.extend(apache_avro::to_avro_datum(&writer_schema, record).unwrap());
+
+        let columns = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, ColumnId::from(0)),
+            SourceColumnDesc::simple("name", DataType::Varchar, ColumnId::from(1)),
+            SourceColumnDesc::simple("age", DataType::Int32, ColumnId::from(2)),
+        ];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(columns, 1);
+        // The schema of id 1 is not fetched yet.
+        parser.parse(&payload, builder.row_writer()).unwrap_err();
+        parser.resolve_schema(&payload).await.unwrap();
+        parser.parse(&payload, builder.row_writer()).unwrap();
+
+        // A malformed message is rejected by parsing rather than resolving.
+        parser.resolve_schema(&[1, 2]).await.unwrap();
+        parser.parse(&[1, 2], builder.row_writer()).unwrap_err();
+        // So is a message with an unknown schema id, for which the schema registry returns 404.
+        let payload = [0, 0, 0, 0, 3, 0];
+        parser.resolve_schema(&payload).await.unwrap();
+        parser.parse(&payload, builder.row_writer()).unwrap_err();
+        // Only the unavailability of the schema registry is returned by resolving.
+        let payload = [0, 0, 0, 0, 4, 0];
+        parser.resolve_schema(&payload).await.unwrap_err();
+
+        let chunk = builder.finish();
+        let (op, row) = chunk.rows().next().unwrap();
+        assert_eq!(op, Op::Insert);
+        assert_eq!(
+            row.to_owned_row().0,
+            vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Utf8("a".to_string())),
+                Some(ScalarImpl::Int32(18)),
+            ]
+        );
+    }
 }
@@ -0,0 +1,237 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::parser::common::{json_parse_value, str_parse_value};
+use crate::{SourceColumnDesc, SourceParser, SourceStreamChunkRowWriter, WriteGuard};
+
+const CANAL_INSERT_EVENT: &str = "INSERT";
+const CANAL_UPDATE_EVENT: &str = "UPDATE";
+const CANAL_DELETE_EVENT: &str = "DELETE";
+
+/// The flat message of Canal, which carries a batch of rows changed by the same statement.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanalJsonEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub is_ddl: bool,
+    pub data: Option<Vec<BTreeMap<String, Value>>>,
+    /// The old values of the updated columns of each row in `data`.
+    pub old: Option<Vec<BTreeMap<String, Value>>>,
+}
+
+#[derive(Debug)]
+pub struct CanalJsonParser;
+
+/// Canal encodes all non-null values as strings.
+fn canal_parse_value(data_type: &DataType, value: Option<&Value>) -> Result<Datum> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(v)) => Ok(Some(str_parse_value(data_type, v).map_err(|e| {
+            RwError::from(ProtocolError(format!(
+                "failed to parse type '{}' from canal: {}",
+                data_type, e
+            )))
+        })?)),
+        Some(v) => Ok(json_parse_value(data_type, Some(v))?),
+    }
+}
+
+/// Parses the columns of a row, taking the value in `old` first if it's given. All rows of a
+/// message are parsed before writing, so that a malformed row won't leave the message partially
+/// written.
+fn parse_row(
+    descs: &[SourceColumnDesc],
+    row: &BTreeMap<String, Value>,
+    old: Option<&BTreeMap<String, Value>>,
+) -> Result<Vec<Datum>> {
+    descs
+        .iter()
+        .filter(|desc| !desc.skip_parse)
+        .map(|desc| {
+            let value = old
+                .and_then(|old| old.get(&desc.name))
+                .or_else(|| row.get(&desc.name));
+            canal_parse_value(&desc.data_type, value)
+        })
+        .collect()
+}
+
+impl SourceParser for CanalJsonParser {
+    fn parse(
+        &self,
+        payload: &[u8],
+        mut writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        let event: CanalJsonEvent = serde_json::from_slice(payload)
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+
+        if event.is_ddl {
+            return Ok(writer.skip());
+        }
+
+        let data = match event.data {
+            Some(data) if !data.is_empty() => data,
+            _ => {
+                return Err(RwError::from(ProtocolError(format!(
+                    "data is missing for canal {} event",
+                    event.event_type
+                ))))
+            }
+        };
+
+        let mut guard = None;
+        match event.event_type.as_str() {
+            CANAL_INSERT_EVENT | CANAL_DELETE_EVENT => {
+                let rows = data
+                    .iter()
+                    .map(|row| parse_row(writer.descs, row, None))
+                    .collect::<Result<Vec<_>>>()?;
+                for row in rows {
+                    let mut datums = row.into_iter();
+                    let f = |_: &SourceColumnDesc| -> Result<Datum> { Ok(datums.next().unwrap()) };
+                    guard = Some(if event.event_type == CANAL_INSERT_EVENT {
+                        writer.reborrow().insert(f)?
+                    } else {
+                        writer.reborrow().delete(f)?
+                    });
+                }
+            }
+            CANAL_UPDATE_EVENT => {
+                let old = event.old.unwrap_or_default();
+                if old.len() != data.len() {
+                    return Err(RwError::from(ProtocolError(format!(
+                        "canal update event has {} rows in data but {} in old",
+                        data.len(),
+                        old.len()
+                    ))));
+                }
+                let rows = data
+                    .iter()
+                    .zip(&old)
+                    .map(|(row, old)| {
+                        Ok((
+                            parse_row(writer.descs, row, Some(old))?,
+                            parse_row(writer.descs, row, None)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                for (before, after) in rows {
+                    let mut datums = before.into_iter().zip(after);
+                    guard = Some(writer.reborrow().update(|_| Ok(datums.next().unwrap()))?);
+                }
+            }
+            _ => {
+                return Err(RwError::from(ProtocolError(format!(
+                    "unknown canal event type: {}",
+                    event.event_type
+                ))))
+            }
+        }
+
+        Ok(guard.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use risingwave_common::array::{Op, Row};
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use super::*;
+    use crate::SourceStreamChunkBuilder;
+
+    fn get_test_columns() -> Vec<SourceColumnDesc> {
+        vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("price", DataType::Float64, 2.into()),
+        ]
+    }
+
+    fn parse_one(payload: &[u8]) -> Result<Vec<(Op, Row)>> {
+        let mut builder = SourceStreamChunkBuilder::with_capacity(get_test_columns(), 4);
+        CanalJsonParser.parse(payload, builder.row_writer())?;
+        let chunk = builder.finish();
+        Ok(chunk
+            .rows()
+            .map(|(op, row_ref)| (op, row_ref.to_owned_row()))
+            .collect())
+    }
+
+    fn row(id: i32, name: &str, price: Option<f64>) -> Row {
+        Row(vec![
+            Some(ScalarImpl::Int32(id)),
+            Some(ScalarImpl::Utf8(name.to_string())),
+            price.map(|p| ScalarImpl::Float64(p.into())),
+        ])
+    }
+
+    #[test]
+    fn test_canal_json_parser_insert() {
+        let data = br#"{"data":[{"id":"1","name":"apple","price":"1.5"},{"id":"2","name":"banana","price":null}],"database":"test","es":1668582000000,"id":7,"isDdl":false,"mysqlType":{"id":"int","name":"varchar(255)","price":"double"},"old":null,"pkNames":["id"],"sql":"","sqlType":{"id":4,"name":12,"price":8},"table":"fruit","ts":1668582000123,"type":"INSERT"}"#;
+
+        assert_eq!(
+            parse_one(data).unwrap(),
+            vec![
+                (Op::Insert, row(1, "apple", Some(1.5))),
+                (Op::Insert, row(2, "banana", None)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canal_json_parser_update() {
+        let data = br#"{"data":[{"id":"1","name":"apple","price":"2.5"},{"id":"2","name":"cherry","price":"3"}],"database":"test","es":1668582000000,"id":8,"isDdl":false,"mysqlType":{"id":"int","name":"varchar(255)","price":"double"},"old":[{"price":"1.5"},{"name":"banana","price":null}],"pkNames":["id"],"sql":"","sqlType":{"id":4,"name":12,"price":8},"table":"fruit","ts":1668582000123,"type":"UPDATE"}"#;
+
+        assert_eq!(
+            parse_one(data).unwrap(),
+            vec![
+                (Op::UpdateDelete, row(1, "apple", Some(1.5))),
+                (Op::UpdateInsert, row(1, "apple", Some(2.5))),
+                (Op::UpdateDelete, row(2, "banana", None)),
+                (Op::UpdateInsert, row(2, "cherry", Some(3.0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canal_json_parser_delete() {
+        let data = br#"{"data":[{"id":"1","name":"apple","price":"2.5"}],"database":"test","es":1668582000000,"id":9,"isDdl":false,"mysqlType":{"id":"int","name":"varchar(255)","price":"double"},"old":null,"pkNames":["id"],"sql":"","sqlType":{"id":4,"name":12,"price":8},"table":"fruit","ts":1668582000123,"type":"DELETE"}"#;
+
+        assert_eq!(
+            parse_one(data).unwrap(),
+            vec![(Op::Delete, row(1, "apple", Some(2.5)))]
+        );
+    }
+
+    #[test]
+    fn test_canal_json_parser_ddl_and_malformed() {
+        let ddl = br#"{"data":null,"database":"test","es":1668582000000,"id":10,"isDdl":true,"mysqlType":null,"old":null,"pkNames":null,"sql":"ALTER TABLE fruit ADD COLUMN color varchar(255)","sqlType":null,"table":"fruit","ts":1668582000123,"type":"ALTER"}"#;
+        assert_eq!(parse_one(ddl).unwrap(), vec![]);
+
+        // Nothing is written if any row of the batch is malformed.
+        let malformed = br#"{"data":[{"id":"1","name":"apple","price":"1.5"},{"id":"two","name":"banana","price":null}],"isDdl":false,"old":null,"type":"INSERT"}"#;
+        assert!(parse_one(malformed).is_err());
+    }
+}
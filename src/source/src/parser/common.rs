@@ -16,9 +16,10 @@ use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use num_traits::FromPrimitive;
 use risingwave_common::array::{ListValue, StructValue};
-use risingwave_common::types::{DataType, Datum, Decimal, ScalarImpl};
+use risingwave_common::types::{DataType, Datum, Decimal, JsonbVal, ScalarImpl};
 use risingwave_expr::vector_op::cast::{
-    str_to_date, str_to_time, str_to_timestamp, str_to_timestampz,
+    str_parse, str_to_bool, str_to_bytea, str_to_date, str_to_jsonb, str_to_time, str_to_timestamp,
+    str_to_timestampz,
 };
 use serde_json::Value;
 #[cfg(any(
@@ -108,6 +109,12 @@ fn do_parse_json_value(dtype: &DataType, v: &Value) -> Result<ScalarImpl> {
                 return Err(anyhow!(err_msg));
             }
         }
+        DataType::Bytea => str_to_bytea(ensure_str!(v, "bytea"))?.into(),
+        DataType::Jsonb => v
+            .to_string()
+            .parse::<JsonbVal>()
+            .map_err(|e| anyhow!("expect jsonb: {}", e))?
+            .into(),
         DataType::Interval => unimplemented!(),
     };
     Ok(v)
@@ -123,6 +130,28 @@ pub(crate) fn json_parse_value(dtype: &DataType, value: Option<&Value>) -> Resul
     }
 }
 
+/// Parses a value from its text representation, e.g. a field of csv or a column of Canal.
+pub(crate) fn str_parse_value(data_type: &DataType, v: &str) -> Result<ScalarImpl> {
+    let v = match data_type {
+        DataType::Boolean => str_to_bool(v)?.into(),
+        DataType::Int16 => str_parse::<i16>(v)?.into(),
+        DataType::Int32 => str_parse::<i32>(v)?.into(),
+        DataType::Int64 => str_parse::<i64>(v)?.into(),
+        DataType::Float32 => str_parse::<f32>(v)?.into(),
+        DataType::Float64 => str_parse::<f64>(v)?.into(),
+        DataType::Decimal => str_parse::<Decimal>(v)?.into(),
+        DataType::Varchar => v.to_string().into(),
+        DataType::Date => str_to_date(v)?.into(),
+        DataType::Time => str_to_time(v)?.into(),
+        DataType::Timestamp => str_to_timestamp(v)?.into(),
+        DataType::Timestampz => str_to_timestampz(v)?.into(),
+        DataType::Bytea => str_to_bytea(v)?.into(),
+        DataType::Jsonb => str_to_jsonb(v)?.into(),
+        _ => return Err(anyhow!("unsupported type {} in text", data_type)),
+    };
+    Ok(v)
+}
+
 #[cfg(any(
     target_feature = "sse4.2",
     target_feature = "avx2",
@@ -0,0 +1,280 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use parking_lot::RwLock;
+use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum};
+use risingwave_connector::source::SplitId;
+
+use crate::parser::common::str_parse_value;
+use crate::{SourceStreamChunkRowWriter, WriteGuard};
+
+const CSV_DELIMITER_KEY: &str = "csv.delimiter";
+const CSV_QUOTE_KEY: &str = "csv.quote";
+const CSV_HEADER_KEY: &str = "csv.header";
+const CSV_NULL_STRING_KEY: &str = "csv.null_string";
+
+/// Parser for CSV format, where each message is a line of CSV.
+///
+/// Without header, i.e. `csv.header = 'false'` by default, the fields are mapped to the columns by
+/// position. With header, the first line of each split, i.e. the file, is taken as its header and
+/// skipped, and the fields of the lines after it are mapped to the columns by name. When a file is
+/// resumed from the middle, its header line is read again by the file reader before the rest of
+/// the file, see `ConnectorProperties::set_header_line`, so the headers are cleared by
+/// [`CsvParser::clear_headers`] whenever the readers are rebuilt.
+#[derive(Debug)]
+pub struct CsvParser {
+    delimiter: u8,
+    quote: u8,
+    /// The fields equal to it are parsed as null. Empty by default.
+    null_string: String,
+    header: bool,
+    /// The fields of the header of each split being read.
+    headers: RwLock<HashMap<SplitId, Vec<String>>>,
+}
+
+impl CsvParser {
+    pub fn new(properties: &HashMap<String, String>) -> Result<Self> {
+        let get_char = |key: &str, default: u8| match properties.get(key).map(String::as_str) {
+            None => Ok(default),
+            Some("\\t") => Ok(b'\t'),
+            Some(value) if value.len() == 1 => Ok(value.as_bytes()[0]),
+            Some(value) => Err(RwError::from(ProtocolError(format!(
+                "'{}' must be a single ASCII character, got '{}'",
+                key, value
+            )))),
+        };
+        let header = match properties.get(CSV_HEADER_KEY) {
+            None => false,
+            Some(value) => value.to_lowercase().parse().map_err(|_| {
+                RwError::from(ProtocolError(format!(
+                    "'{}' must be 'true' or 'false', got '{}'",
+                    CSV_HEADER_KEY, value
+                )))
+            })?,
+        };
+
+        Ok(Self {
+            delimiter: get_char(CSV_DELIMITER_KEY, b',')?,
+            quote: get_char(CSV_QUOTE_KEY, b'"')?,
+            null_string: properties
+                .get(CSV_NULL_STRING_KEY)
+                .cloned()
+                .unwrap_or_default(),
+            header,
+            headers: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Whether the first line of a file is a header, i.e. `csv.header = 'true'`.
+    pub fn has_header(&self) -> bool {
+        self.header
+    }
+
+    /// Clears the headers of the splits, which is called before the splits are read by new readers.
+    pub fn clear_headers(&self) {
+        self.headers.write().clear();
+    }
+
+    fn read_fields(&self, payload: &[u8]) -> Result<Vec<String>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .from_reader(payload);
+        let record = reader
+            .records()
+            .next()
+            .ok_or_else(|| RwError::from(ProtocolError("empty csv line".to_string())))?
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        Ok(record.iter().map(str::to_string).collect())
+    }
+
+    fn parse_field(&self, data_type: &DataType, field: Option<&str>) -> Result<Datum> {
+        match field {
+            None => Ok(None),
+            Some(field) if field == self.null_string => Ok(None),
+            Some(field) => Ok(Some(str_parse_value(data_type, field).map_err(|e| {
+                anyhow!("failed to parse type '{}' from csv: {}", data_type, e)
+            })?)),
+        }
+    }
+}
+
+impl CsvParser {
+    /// Parses a line of CSV in the split `split_id`. Unlike other formats, the parsing depends on
+    /// the header seen before in the same split, so [`crate::SourceParser`] is not implemented.
+    pub fn parse(
+        &self,
+        split_id: &SplitId,
+        payload: &[u8],
+        writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        let fields = self.read_fields(payload)?;
+
+        if self.header {
+            if let Some(headers) = self.headers.read().get(split_id) {
+                return writer.insert(|desc| {
+                    let field = headers
+                        .iter()
+                        .position(|h| h.eq_ignore_ascii_case(&desc.name))
+                        .and_then(|idx| fields.get(idx));
+                    self.parse_field(&desc.data_type, field.map(String::as_str))
+                });
+            }
+            self.headers.write().insert(split_id.clone(), fields);
+            return Ok(writer.skip());
+        }
+
+        let mut fields = fields.iter();
+        writer.insert(|desc| {
+            let field = fields.next().ok_or_else(|| {
+                RwError::from(ProtocolError(format!(
+                    "missing field for column '{}' in csv line",
+                    desc.name
+                )))
+            })?;
+            self.parse_field(&desc.data_type, Some(field))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+    use risingwave_common::array::Op;
+    use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceStreamChunkBuilder};
+
+    fn get_test_columns() -> Vec<SourceColumnDesc> {
+        vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("price", DataType::Float64, 2.into()),
+        ]
+    }
+
+    fn parse_lines(parser: &CsvParser, lines: &[(&str, &str)]) -> Vec<Vec<Datum>> {
+        let mut builder = SourceStreamChunkBuilder::with_capacity(get_test_columns(), lines.len());
+        for (split_id, line) in lines {
+            let _ = parser.parse(
+                &SplitId::from(*split_id),
+                line.as_bytes(),
+                builder.row_writer(),
+            );
+        }
+        let chunk = builder.finish();
+        chunk
+            .rows()
+            .map(|(op, row)| {
+                assert_eq!(op, Op::Insert);
+                (0..3).map(|i| row.value_at(i).to_owned_datum()).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_parser_by_position() {
+        let properties = hashmap! {
+            CSV_DELIMITER_KEY.to_string() => "|".to_string(),
+            CSV_QUOTE_KEY.to_string() => "'".to_string(),
+            CSV_NULL_STRING_KEY.to_string() => "NULL".to_string(),
+        };
+        let parser = CsvParser::new(&properties).unwrap();
+
+        let rows = parse_lines(
+            &parser,
+            &[
+                ("a.csv", "1|'a|b'|1.5"),
+                ("a.csv", "2||NULL"),
+                // Malformed lines are skipped.
+                ("a.csv", "3|c"),
+                ("a.csv", "x|d|1.0"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("a|b".to_string())),
+                    Some(ScalarImpl::Float64(1.5.into())),
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("".to_string())),
+                    None,
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_parser_by_header() {
+        let properties = hashmap! {
+            CSV_HEADER_KEY.to_string() => "true".to_string(),
+        };
+        let parser = CsvParser::new(&properties).unwrap();
+
+        let rows = parse_lines(
+            &parser,
+            &[
+                ("a.csv", "price,extra,ID,name"),
+                ("b.csv", "name,id,price"),
+                ("a.csv", "2.5,x,2,b"),
+                ("b.csv", "c,4,4.5"),
+                ("a.csv", ",y,3,"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("b".to_string())),
+                    Some(ScalarImpl::Float64(2.5.into())),
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(4)),
+                    Some(ScalarImpl::Utf8("c".to_string())),
+                    Some(ScalarImpl::Float64(4.5.into())),
+                ],
+                vec![Some(ScalarImpl::Int32(3)), None, None],
+            ]
+        );
+
+        // The readers are rebuilt and emit the header of the resumed split again.
+        parser.clear_headers();
+        let rows = parse_lines(&parser, &[("a.csv", "id,name,price"), ("a.csv", "5,d,5.5")]);
+        assert_eq!(
+            rows,
+            vec![vec![
+                Some(ScalarImpl::Int32(5)),
+                Some(ScalarImpl::Utf8("d".to_string())),
+                Some(ScalarImpl::Float64(5.5.into())),
+            ]]
+        );
+
+        assert!(CsvParser::new(&hashmap! {
+            CSV_DELIMITER_KEY.to_string() => "||".to_string(),
+        })
+        .is_err());
+    }
+}
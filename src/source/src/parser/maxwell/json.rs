@@ -0,0 +1,161 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::{Result, RwError};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::parser::common::json_parse_value;
+use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
+
+const MAXWELL_INSERT_OP: &str = "insert";
+const MAXWELL_UPDATE_OP: &str = "update";
+const MAXWELL_DELETE_OP: &str = "delete";
+const MAXWELL_BOOTSTRAP_INSERT_OP: &str = "bootstrap-insert";
+const MAXWELL_BOOTSTRAP_START_OP: &str = "bootstrap-start";
+const MAXWELL_BOOTSTRAP_COMPLETE_OP: &str = "bootstrap-complete";
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaxwellEvent {
+    #[serde(rename = "type")]
+    pub op: String,
+    pub data: Option<BTreeMap<String, Value>>,
+    /// The old values of the updated columns.
+    pub old: Option<BTreeMap<String, Value>>,
+}
+
+#[derive(Debug)]
+pub struct MaxwellParser;
+
+impl SourceParser for MaxwellParser {
+    fn parse(&self, payload: &[u8], writer: SourceStreamChunkRowWriter<'_>) -> Result<WriteGuard> {
+        let event: MaxwellEvent = serde_json::from_slice(payload)
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+
+        match event.op.as_str() {
+            MAXWELL_BOOTSTRAP_START_OP | MAXWELL_BOOTSTRAP_COMPLETE_OP => return Ok(writer.skip()),
+            MAXWELL_INSERT_OP
+            | MAXWELL_BOOTSTRAP_INSERT_OP
+            | MAXWELL_UPDATE_OP
+            | MAXWELL_DELETE_OP => {}
+            _ => {
+                return Err(RwError::from(ProtocolError(format!(
+                    "unknown maxwell op: {}",
+                    event.op
+                ))))
+            }
+        }
+
+        let data = event.data.as_ref().ok_or_else(|| {
+            RwError::from(ProtocolError(format!(
+                "data is missing for maxwell {} event",
+                event.op
+            )))
+        })?;
+
+        match event.op.as_str() {
+            MAXWELL_UPDATE_OP => {
+                let old = event.old.unwrap_or_default();
+
+                writer.update(|column| {
+                    let before = old.get(&column.name).or_else(|| data.get(&column.name));
+                    let before = json_parse_value(&column.data_type, before)?;
+                    let after = json_parse_value(&column.data_type, data.get(&column.name))?;
+
+                    Ok((before, after))
+                })
+            }
+            MAXWELL_DELETE_OP => writer.delete(|column| {
+                json_parse_value(&column.data_type, data.get(&column.name)).map_err(Into::into)
+            }),
+            _ => writer.insert(|column| {
+                json_parse_value(&column.data_type, data.get(&column.name)).map_err(Into::into)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use risingwave_common::array::{Op, Row};
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceStreamChunkBuilder};
+
+    fn parse_one(payload: &[u8]) -> Vec<(Op, Row)> {
+        let columns = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("price", DataType::Float64, 2.into()),
+        ];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(columns, 2);
+        MaxwellParser.parse(payload, builder.row_writer()).unwrap();
+        let chunk = builder.finish();
+        chunk
+            .rows()
+            .map(|(op, row_ref)| (op, row_ref.to_owned_row()))
+            .collect()
+    }
+
+    fn row(id: i32, name: &str, price: Option<f64>) -> Row {
+        Row(vec![
+            Some(ScalarImpl::Int32(id)),
+            Some(ScalarImpl::Utf8(name.to_string())),
+            price.map(|p| ScalarImpl::Float64(p.into())),
+        ])
+    }
+
+    #[test]
+    fn test_maxwell_parser_insert() {
+        let data = br#"{"database":"test","table":"fruit","type":"insert","ts":1668582000,"xid":940752,"commit":true,"data":{"id":1,"name":"apple","price":1.5}}"#;
+        assert_eq!(
+            parse_one(data),
+            vec![(Op::Insert, row(1, "apple", Some(1.5)))]
+        );
+
+        let data = br#"{"database":"test","table":"fruit","type":"bootstrap-insert","ts":1668582000,"data":{"id":2,"name":"banana","price":null}}"#;
+        assert_eq!(parse_one(data), vec![(Op::Insert, row(2, "banana", None))]);
+    }
+
+    #[test]
+    fn test_maxwell_parser_update() {
+        let data = br#"{"database":"test","table":"fruit","type":"update","ts":1668582000,"xid":940753,"commit":true,"data":{"id":1,"name":"apple","price":2.5},"old":{"price":null}}"#;
+        assert_eq!(
+            parse_one(data),
+            vec![
+                (Op::UpdateDelete, row(1, "apple", None)),
+                (Op::UpdateInsert, row(1, "apple", Some(2.5))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maxwell_parser_delete() {
+        let data = br#"{"database":"test","table":"fruit","type":"delete","ts":1668582000,"xid":940754,"commit":true,"data":{"id":1,"name":"apple","price":2.5}}"#;
+        assert_eq!(
+            parse_one(data),
+            vec![(Op::Delete, row(1, "apple", Some(2.5)))]
+        );
+    }
+
+    #[test]
+    fn test_maxwell_parser_bootstrap_start() {
+        let data = br#"{"database":"test","table":"fruit","type":"bootstrap-start","ts":1668582000,"data":{}}"#;
+        assert_eq!(parse_one(data), vec![]);
+    }
+}
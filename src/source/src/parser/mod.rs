@@ -17,22 +17,30 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 pub use avro_parser::*;
+pub use canal::*;
+pub use csv_parser::*;
 pub use debezium::*;
 use itertools::Itertools;
 pub use json_parser::*;
+pub use maxwell::*;
 pub use pb_parser::*;
 use risingwave_common::array::{ArrayBuilderImpl, Op, StreamChunk};
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::Datum;
+use risingwave_connector::source::SplitId;
 
 use crate::{SourceColumnDesc, SourceFormat};
 
 mod avro_parser;
+mod canal;
 mod common;
+mod csv_parser;
 mod debezium;
 mod json_parser;
+mod maxwell;
 mod pb_parser;
+mod schema_registry;
 // mod protobuf_parser;
 
 /// A builder for building a [`StreamChunk`] from [`SourceColumnDesc`].
@@ -242,6 +250,21 @@ impl SourceStreamChunkRowWriter<'_> {
     ) -> Result<WriteGuard> {
         self.do_action::<OpActionUpdate>(f)
     }
+
+    /// Write nothing for a message carrying no record, e.g. the header line of CSV.
+    pub fn skip(self) -> WriteGuard {
+        WriteGuard(())
+    }
+
+    /// Reborrow the writer so that more than one record can be written for a message carrying a
+    /// batch of rows, e.g. the `data` array of Canal.
+    pub fn reborrow(&mut self) -> SourceStreamChunkRowWriter<'_> {
+        SourceStreamChunkRowWriter {
+            descs: self.descs,
+            builders: self.builders,
+            op_builder: self.op_builder,
+        }
+    }
 }
 
 /// `SourceParser` is the message parser, `ChunkReader` will parse the messages in `SourceReader`
@@ -255,7 +278,8 @@ pub trait SourceParser: Send + Sync + Debug + 'static {
     ///
     /// - `self`: A needs to be a member method because some format like Protobuf needs to be
     ///   pre-compiled.
-    /// - writer: Write exactly one record during a `parse` call.
+    /// - writer: Write exactly one record during a `parse` call, unless the message carries a batch
+    ///   of rows, which are written through [`SourceStreamChunkRowWriter::reborrow`].
     ///
     /// # Returns
     ///
@@ -269,11 +293,16 @@ pub enum SourceParserImpl {
     Protobuf(ProtobufParser),
     DebeziumJson(DebeziumJsonParser),
     Avro(AvroParser),
+    Csv(CsvParser),
+    CanalJson(CanalJsonParser),
+    Maxwell(MaxwellParser),
 }
 
 impl SourceParserImpl {
+    /// Parses the payload of a message in the split `split_id`.
     pub fn parse(
         &self,
+        split_id: &SplitId,
         payload: &[u8],
         writer: SourceStreamChunkRowWriter<'_>,
     ) -> Result<WriteGuard> {
@@ -282,6 +311,20 @@ impl SourceParserImpl {
             Self::Protobuf(parser) => parser.parse(payload, writer),
             Self::DebeziumJson(parser) => parser.parse(payload, writer),
             Self::Avro(avro_parser) => avro_parser.parse(payload, writer),
+            Self::Csv(parser) => parser.parse(split_id, payload, writer),
+            Self::CanalJson(parser) => parser.parse(payload, writer),
+            Self::Maxwell(parser) => parser.parse(payload, writer),
+        }
+    }
+
+    /// Fetches the schema that the message is encoded with from the schema registry if needed, so
+    /// that it can be parsed by [`Self::parse`] afterwards. Unlike [`Self::parse`], an error means
+    /// the schema registry is unavailable rather than the message is malformed, e.g. its schema id
+    /// is unknown, so the message should not be skipped.
+    pub async fn resolve_schema(&self, payload: &[u8]) -> Result<()> {
+        match self {
+            Self::Avro(avro_parser) => avro_parser.resolve_schema(payload).await,
+            _ => Ok(()),
         }
     }
 
@@ -289,6 +332,7 @@ impl SourceParserImpl {
         format: &SourceFormat,
         properties: &HashMap<String, String>,
         schema_location: &str,
+        use_schema_registry: bool,
     ) -> Result<Arc<Self>> {
         const PROTOBUF_MESSAGE_KEY: &str = "proto.message";
         let parser = match format {
@@ -300,12 +344,23 @@ impl SourceParserImpl {
                         PROTOBUF_MESSAGE_KEY
                     )))
                 })?;
-                SourceParserImpl::Protobuf(ProtobufParser::new(schema_location, message_name)?)
+                SourceParserImpl::Protobuf(
+                    ProtobufParser::new(
+                        schema_location,
+                        message_name,
+                        properties,
+                        use_schema_registry,
+                    )
+                    .await?,
+                )
             }
             SourceFormat::DebeziumJson => SourceParserImpl::DebeziumJson(DebeziumJsonParser),
-            SourceFormat::Avro => {
-                SourceParserImpl::Avro(AvroParser::new(schema_location, properties.clone()).await?)
-            }
+            SourceFormat::Avro => SourceParserImpl::Avro(
+                AvroParser::new(schema_location, properties.clone(), use_schema_registry).await?,
+            ),
+            SourceFormat::Csv => SourceParserImpl::Csv(CsvParser::new(properties)?),
+            SourceFormat::CanalJson => SourceParserImpl::CanalJson(CanalJsonParser),
+            SourceFormat::Maxwell => SourceParserImpl::Maxwell(MaxwellParser),
             _ => {
                 return Err(RwError::from(ProtocolError(
                     "format not support".to_string(),
@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use futures::future::BoxFuture;
+use futures::FutureExt;
 use itertools::Itertools;
+use prost::encoding::decode_varint;
 use prost_reflect::{
     Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
     ReflectMessage, Value,
@@ -26,19 +30,53 @@ use risingwave_common::types::{DataType, Datum, Decimal, OrderedF32, OrderedF64,
 use risingwave_pb::plan_common::ColumnDesc;
 use url::Url;
 
+use crate::parser::schema_registry::{
+    extract_schema_id, get_subject_by_topic, ConfluentSchema, SchemaRegistryClient,
+};
 use crate::{SourceParser, WriteGuard};
 
+/// Parser for Protobuf messages. With the schema registry, the latest schema of the subject is
+/// fetched when the source is created, and the messages written with other versions of the schema
+/// are decoded with it as well, which is compatible as long as the field numbers are not reused.
 #[derive(Debug, Clone)]
 pub struct ProtobufParser {
     pub message_descriptor: MessageDescriptor,
 }
 
 impl ProtobufParser {
-    pub fn new(location: &str, message_name: &str) -> Result<Self> {
+    pub async fn new(
+        location: &str,
+        message_name: &str,
+        props: &HashMap<String, String>,
+        use_schema_registry: bool,
+    ) -> Result<Self> {
+        let schema_bytes = if use_schema_registry {
+            Self::load_from_schema_registry(location, props).await?
+        } else {
+            Self::load_from_location(location)?
+        };
+
+        let pool = DescriptorPool::decode(&schema_bytes[..]).map_err(|e| {
+            ProtocolError(format!(
+                "cannot build descriptor pool from schema: {}, error: {}",
+                location, e
+            ))
+        })?;
+        let message_descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
+            ProtocolError(format!(
+                "cannot find message {} in schema: {}.\n poll is {:?}",
+                message_name, location, pool
+            ))
+        })?;
+        Ok(Self { message_descriptor })
+    }
+
+    /// Reads the binary schema, i.e. an encoded `FileDescriptorSet`, from the location.
+    fn load_from_location(location: &str) -> Result<Vec<u8>> {
         let url = Url::parse(location)
             .map_err(|e| InternalError(format!("failed to parse url ({}): {}", location, e)))?;
 
-        let schema_bytes = match url.scheme() {
+        match url.scheme() {
             "file" => {
                 let path = url.to_file_path().map_err(|_| {
                     RwError::from(InternalError(format!("illegal path: {}", location)))
@@ -61,21 +99,29 @@ impl ProtobufParser {
                 "path scheme {} is not supported",
                 scheme
             )))),
-        }?;
+        }
+    }
 
-        let pool = DescriptorPool::decode(&schema_bytes[..]).map_err(|e| {
-            ProtocolError(format!(
-                "cannot build descriptor pool from schema: {}, error: {}",
-                location, e
-            ))
-        })?;
-        let message_descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
-            ProtocolError(format!(
-                "cannot find message {} in schema: {}.\n poll is {:?}",
-                message_name, location, pool
-            ))
-        })?;
-        Ok(Self { message_descriptor })
+    /// Fetches the latest schema of the subject of the topic from the schema registry at `url`,
+    /// along with the schemas it references, and encodes them into a `FileDescriptorSet`.
+    async fn load_from_schema_registry(
+        url: &str,
+        props: &HashMap<String, String>,
+    ) -> Result<Vec<u8>> {
+        let client = SchemaRegistryClient::new(url);
+        let subject = get_subject_by_topic(props)?;
+        let schema = client
+            .get_schema_by_subject_version(&subject, "latest", true)
+            .await?;
+
+        let mut files = vec![];
+        fetch_file_descriptors(&client, schema, &mut HashSet::new(), &mut files).await?;
+        let mut schema_bytes = vec![];
+        for file in files {
+            // The field `file` of `FileDescriptorSet`.
+            prost::encoding::bytes::encode(1, &file, &mut schema_bytes);
+        }
+        Ok(schema_bytes)
     }
 
     /// read binary schema from a local file
@@ -134,6 +180,39 @@ impl ProtobufParser {
     }
 }
 
+/// Collects the encoded `FileDescriptorProto`s of the Protobuf schema and the schemas it references
+/// recursively into `files`, where the dependencies are placed before the dependents.
+fn fetch_file_descriptors<'a>(
+    client: &'a SchemaRegistryClient,
+    schema: ConfluentSchema,
+    visited: &'a mut HashSet<(String, i32)>,
+    files: &'a mut Vec<Vec<u8>>,
+) -> BoxFuture<'a, Result<()>> {
+    async move {
+        for reference in &schema.references {
+            if visited.insert((reference.subject.clone(), reference.version)) {
+                let dependency = client
+                    .get_schema_by_subject_version(
+                        &reference.subject,
+                        &reference.version.to_string(),
+                        true,
+                    )
+                    .await?;
+                fetch_file_descriptors(client, dependency, visited, files).await?;
+            }
+        }
+        let file = base64::decode(&schema.content).map_err(|e| {
+            RwError::from(ProtocolError(format!(
+                "failed to decode protobuf schema of id {}: {}",
+                schema.id, e
+            )))
+        })?;
+        files.push(file);
+        Ok(())
+    }
+    .boxed()
+}
+
 fn from_protobuf_value(field_desc: &FieldDescriptor, value: &Value) -> Result<Datum> {
     let v = match value {
         Value::Bool(v) => ScalarImpl::Bool(*v),
@@ -234,14 +313,39 @@ fn protobuf_type_mapping(field_descriptor: &FieldDescriptor) -> Result<DataType>
     Ok(t)
 }
 
+/// Strips the header of the Confluent wire format if any, i.e. a magic byte, the schema id and the
+/// indexes of the message type in the schema, which are a zigzag varint of the count followed by
+/// the indexes themselves. A plain protobuf message never starts with the magic byte `0`, as it's
+/// an invalid tag of field number `0`.
+fn strip_confluent_header(payload: &[u8]) -> Result<&[u8]> {
+    if payload.first() != Some(&0) {
+        return Ok(payload);
+    }
+    let (_, mut rest) = extract_schema_id(payload)?;
+    let decode_zigzag = |buf: &mut &[u8]| -> Result<i64> {
+        let n = decode_varint(buf).map_err(|e| {
+            RwError::from(ProtocolError(format!("invalid confluent header: {}", e)))
+        })?;
+        Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+    };
+    let count = decode_zigzag(&mut rest)?;
+    for _ in 0..count {
+        decode_zigzag(&mut rest)?;
+    }
+    Ok(rest)
+}
+
 impl SourceParser for ProtobufParser {
     fn parse(
         &self,
         payload: &[u8],
         writer: crate::SourceStreamChunkRowWriter<'_>,
     ) -> Result<WriteGuard> {
-        let message = DynamicMessage::decode(self.message_descriptor.clone(), payload)
-            .map_err(|e| ProtocolError(format!("parse message failed: {}", e)))?;
+        let message = DynamicMessage::decode(
+            self.message_descriptor.clone(),
+            strip_confluent_header(payload)?,
+        )
+        .map_err(|e| ProtocolError(format!("parse message failed: {}", e)))?;
         writer.insert(|column_desc| {
             let field_desc = message
                 .descriptor()
@@ -290,11 +394,31 @@ mod test {
     static PRE_GEN_PROTO_DATA: &[u8] = b"\x08\x7b\x12\x0c\x74\x65\x73\x74\x20\x61\x64\x64\x72\x65\x73\x73\x1a\x09\x74\x65\x73\x74\x20\x63\x69\x74\x79\x20\xc8\x03\x2d\x19\x04\x9e\x3f\x32\x0a\x32\x30\x32\x31\x2d\x30\x31\x2d\x30\x31";
 
     #[test]
-    fn test_simple_schema() -> Result<()> {
+    fn test_strip_confluent_header() {
+        assert_eq!(
+            strip_confluent_header(PRE_GEN_PROTO_DATA).unwrap(),
+            PRE_GEN_PROTO_DATA
+        );
+        // Schema id 1 with the message indexes `[0]`, which is encoded as a single `0`.
+        let payload = [&[0, 0, 0, 0, 1, 0][..], PRE_GEN_PROTO_DATA].concat();
+        assert_eq!(
+            strip_confluent_header(&payload).unwrap(),
+            PRE_GEN_PROTO_DATA
+        );
+        // Schema id 1 with the message indexes `[1, 3]`.
+        let payload = [&[0, 0, 0, 0, 1, 4, 2, 6][..], PRE_GEN_PROTO_DATA].concat();
+        assert_eq!(
+            strip_confluent_header(&payload).unwrap(),
+            PRE_GEN_PROTO_DATA
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simple_schema() -> Result<()> {
         let location = schema_dir() + "/simple-schema";
         let message_name = "test.TestRecord";
         println!("location: {}", location);
-        let parser = ProtobufParser::new(&location, message_name)?;
+        let parser = ProtobufParser::new(&location, message_name, &HashMap::new(), false).await?;
         let value = DynamicMessage::decode(parser.message_descriptor, PRE_GEN_PROTO_DATA).unwrap();
 
         assert_eq!(
@@ -325,12 +449,12 @@ mod test {
         Ok(())
     }
 
-    #[test]
-    fn test_complex_schema() -> Result<()> {
+    #[tokio::test]
+    async fn test_complex_schema() -> Result<()> {
         let location = schema_dir() + "/complex-schema";
         let message_name = "test.User";
 
-        let parser = ProtobufParser::new(&location, message_name)?;
+        let parser = ProtobufParser::new(&location, message_name, &HashMap::new(), false).await?;
         let columns = parser.map_to_columns().unwrap();
 
         assert_eq!(columns[0].name, "id".to_string());
@@ -370,4 +494,90 @@ mod test {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_schema_registry() {
+        use prost::Message;
+        use prost_types::field_descriptor_proto::{Label, Type};
+        use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorProto};
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // The descriptor set of the single file `simple-schema.proto`, whose header is the tag of
+        // the field `file` and a 2-byte length.
+        let simple_schema = std::fs::read("src/test_data/simple-schema").unwrap()[3..].to_vec();
+        // `record.proto` refers to `simple-schema.proto` registered under another subject.
+        let record_schema = FileDescriptorProto {
+            name: Some("record.proto".to_string()),
+            package: Some("test".to_string()),
+            dependency: vec!["simple-schema.proto".to_string()],
+            message_type: vec![DescriptorProto {
+                name: Some("Wrapper".to_string()),
+                field: vec![FieldDescriptorProto {
+                    name: Some("record".to_string()),
+                    number: Some(1),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::Message as i32),
+                    type_name: Some(".test.TestRecord".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }
+        .encode_to_vec();
+
+        let server = MockServer::start().await;
+        for (api, body) in [
+            (
+                "/subjects/t-value/versions/latest",
+                serde_json::json!({
+                    "id": 2,
+                    "schema": base64::encode(&record_schema),
+                    "references": [
+                        { "name": "simple-schema.proto", "subject": "simple", "version": 1 }
+                    ],
+                }),
+            ),
+            (
+                "/subjects/simple/versions/1",
+                serde_json::json!({ "id": 1, "schema": base64::encode(&simple_schema) }),
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(api))
+                .and(query_param("format", "serialized"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body.to_string()))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let props = HashMap::from([("kafka.topic".to_string(), "t".to_string())]);
+        let parser = ProtobufParser::new(&server.uri(), "test.Wrapper", &props, true)
+            .await
+            .unwrap();
+        let columns = parser.map_to_columns().unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].name, "record");
+        assert_eq!(columns[0].type_name, "test.TestRecord");
+        assert_eq!(columns[0].field_descs.len(), 6);
+
+        // A message in the Confluent wire format with schema id 2 and the message indexes `[0]`.
+        let mut payload = vec![0, 0, 0, 0, 2, 0];
+        prost::encoding::bytes::encode(1, &PRE_GEN_PROTO_DATA.to_vec(), &mut payload);
+        let message = DynamicMessage::decode(
+            parser.message_descriptor.clone(),
+            strip_confluent_header(&payload).unwrap(),
+        )
+        .unwrap();
+        let record = message.get_field_by_name("record").unwrap().into_owned();
+        let record = record.as_message().unwrap();
+        assert_eq!(
+            record.get_field_by_name("id").unwrap().into_owned(),
+            Value::I32(123)
+        );
+    }
 }
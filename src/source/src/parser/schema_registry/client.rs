@@ -0,0 +1,268 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use reqwest::{Client as HttpClient, Response, StatusCode};
+use risingwave_common::error::ErrorCode::{InternalError, ItemNotFound, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use serde_derive::Deserialize;
+use url::Url;
+
+/// The maximum number of attempts of a request, which is retried on connection failures and
+/// server errors with exponential backoff.
+const MAX_ATTEMPTS: usize = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// A schema fetched from the schema registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfluentSchema {
+    pub id: i32,
+    /// The text of the schema, e.g. the JSON of an Avro schema, or a `FileDescriptorProto` encoded
+    /// in base64 if a Protobuf schema is fetched in the serialized format.
+    pub content: String,
+    /// The other schemas that the schema depends on, e.g. the imports of a Protobuf schema.
+    pub references: Vec<SchemaReference>,
+}
+
+/// A reference to a schema registered under another subject.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SchemaReference {
+    /// The name that the schema is referred by, e.g. the path of an import of a Protobuf schema.
+    pub name: String,
+    pub subject: String,
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetByIdResp {
+    schema: String,
+    #[serde(default)]
+    references: Vec<SchemaReference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetBySubjectResp {
+    id: i32,
+    schema: String,
+    #[serde(default)]
+    references: Vec<SchemaReference>,
+}
+
+/// A client of the Confluent Schema Registry, see
+/// <https://docs.confluent.io/platform/current/schema-registry/develop/api.html>.
+#[derive(Debug, Clone)]
+pub struct SchemaRegistryClient {
+    base_path: String,
+    /// Shared by the requests to reuse the connections. Both http and https are supported.
+    client: HttpClient,
+}
+
+impl SchemaRegistryClient {
+    pub fn new(base_path: &str) -> Self {
+        Self {
+            base_path: base_path.trim_end_matches('/').to_string(),
+            client: HttpClient::new(),
+        }
+    }
+
+    /// Gets the schema by its globally unique id.
+    pub async fn get_schema_by_id(&self, id: i32) -> Result<ConfluentSchema> {
+        let resp: GetByIdResp = self.get(&format!("schemas/ids/{}", id)).await?;
+        Ok(ConfluentSchema {
+            id,
+            content: resp.schema,
+            references: resp.references,
+        })
+    }
+
+    /// Gets the latest version of the schema registered under the subject.
+    pub async fn get_schema_by_subject(&self, subject: &str) -> Result<ConfluentSchema> {
+        self.get_schema_by_subject_version(subject, "latest", false)
+            .await
+    }
+
+    /// Gets a version of the schema registered under the subject, e.g. `latest`. If `serialized`,
+    /// a Protobuf schema is returned as a `FileDescriptorProto` encoded in base64 rather than the
+    /// text of the `.proto` file.
+    pub async fn get_schema_by_subject_version(
+        &self,
+        subject: &str,
+        version: &str,
+        serialized: bool,
+    ) -> Result<ConfluentSchema> {
+        let format = if serialized { "?format=serialized" } else { "" };
+        let resp: GetBySubjectResp = self
+            .get(&format!(
+                "subjects/{}/versions/{}{}",
+                subject, version, format
+            ))
+            .await?;
+        Ok(ConfluentSchema {
+            id: resp.id,
+            content: resp.schema,
+            references: resp.references,
+        })
+    }
+
+    /// Requests the API and returns the response. A client error response, e.g. of an unknown id,
+    /// is returned as [`ItemNotFound`].
+    async fn get<T>(&self, api: &str) -> Result<T>
+    where
+        T: for<'a> serde::Deserialize<'a>,
+    {
+        let url = format!("{}/{}", self.base_path, api);
+        let url = Url::parse(&url).map_err(|e| {
+            RwError::from(InternalError(format!(
+                "invalid schema registry url {}: {}",
+                url, e
+            )))
+        })?;
+        let res = self.request_with_retry(&url).await?;
+        let status = res.status();
+        let body = res
+            .bytes()
+            .await
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        if status != StatusCode::OK {
+            let msg = format!(
+                "schema registry returned {} for {}: {}",
+                status,
+                url,
+                String::from_utf8_lossy(&body)
+            );
+            // A client error means the request can't be served at all, e.g. the id is unknown,
+            // while a server error may be transient.
+            return Err(RwError::from(match status.is_client_error() {
+                true => ItemNotFound(msg),
+                false => ProtocolError(msg),
+            }));
+        }
+        serde_json::from_slice(&body).map_err(|e| RwError::from(ProtocolError(e.to_string())))
+    }
+
+    async fn request_with_retry(&self, url: &Url) -> Result<Response> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            let err = match self.client.get(url.clone()).send().await {
+                Ok(res) if !res.status().is_server_error() || attempt == MAX_ATTEMPTS => {
+                    return Ok(res)
+                }
+                Ok(res) => res.status().to_string(),
+                Err(e) if attempt == MAX_ATTEMPTS => {
+                    return Err(RwError::from(ProtocolError(format!(
+                        "failed to request schema registry {}: {}",
+                        url, e
+                    ))))
+                }
+                Err(e) => e.to_string(),
+            };
+            tracing::warn!(
+                "failed to request schema registry {} (attempt {}): {}, retrying",
+                url,
+                attempt,
+                err
+            );
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_schema_registry_client() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/subjects/t-value/versions/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"subject":"t-value","version":2,"id":7,"schema":"\"string\""}"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"schema":"\"int\""}"#))
+            .mount(&server)
+            .await;
+
+        let client = SchemaRegistryClient::new(&format!("{}/", server.uri()));
+        assert_eq!(
+            client.get_schema_by_subject("t-value").await.unwrap(),
+            ConfluentSchema {
+                id: 7,
+                content: "\"string\"".to_string(),
+                references: vec![],
+            }
+        );
+        assert_eq!(
+            client.get_schema_by_id(3).await.unwrap(),
+            ConfluentSchema {
+                id: 3,
+                content: "\"int\"".to_string(),
+                references: vec![],
+            }
+        );
+        assert!(client.get_schema_by_id(4).await.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg_attr(madsim, ignore)] // MockServer is not supported in simulation.
+    async fn test_schema_registry_client_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"schema":"\"int\""}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Client errors are not retried.
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/2"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = SchemaRegistryClient::new(&server.uri());
+        assert_eq!(
+            client.get_schema_by_id(1).await.unwrap(),
+            ConfluentSchema {
+                id: 1,
+                content: "\"int\"".to_string(),
+                references: vec![],
+            }
+        );
+        assert!(matches!(
+            client.get_schema_by_id(2).await.unwrap_err().inner(),
+            ItemNotFound(_)
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod client;
+
+use std::collections::HashMap;
+
+pub use client::*;
+use risingwave_common::error::ErrorCode::{InvalidConfigValue, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+
+const KAFKA_TOPIC_KEY: &str = "kafka.topic";
+
+/// The first byte of a message in the Confluent wire format.
+const MAGIC_BYTE: u8 = 0;
+
+/// Returns the subject of the message values of the Kafka topic, following the default
+/// `TopicNameStrategy` of Confluent, i.e. `<topic>-value`.
+pub(crate) fn get_subject_by_topic(properties: &HashMap<String, String>) -> Result<String> {
+    let topic = properties.get(KAFKA_TOPIC_KEY).ok_or_else(|| {
+        RwError::from(InvalidConfigValue {
+            config_entry: KAFKA_TOPIC_KEY.to_string(),
+            config_value: "NONE".to_string(),
+        })
+    })?;
+    Ok(format!("{}-value", topic))
+}
+
+/// Extracts the schema id from a message in the Confluent wire format, which is a magic byte
+/// followed by the schema id as a 4-byte big-endian integer, and returns it with the rest of the
+/// message.
+pub(crate) fn extract_schema_id(payload: &[u8]) -> Result<(i32, &[u8])> {
+    match payload {
+        [MAGIC_BYTE, b0, b1, b2, b3, rest @ ..] => {
+            Ok((i32::from_be_bytes([*b0, *b1, *b2, *b3]), rest))
+        }
+        _ => Err(RwError::from(ProtocolError(
+            "message is not in the confluent wire format".to_string(),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_schema_id() {
+        let (id, rest) = extract_schema_id(&[0, 0, 0, 1, 2, 42]).unwrap();
+        assert_eq!(id, 258);
+        assert_eq!(rest, &[42]);
+
+        assert!(extract_schema_id(&[1, 0, 0, 1, 2, 42]).is_err());
+        assert!(extract_schema_id(&[0, 0, 1]).is_err());
+    }
+}
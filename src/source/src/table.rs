@@ -12,61 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use anyhow::Context;
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use rand::seq::IteratorRandom;
+use risingwave_common::array::column::Column;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::{ColumnDesc, ColumnId};
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum};
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::StreamChunkWithState;
 
+type ChangesItem = (StreamChunk, oneshot::Sender<usize>);
+
 #[derive(Debug)]
 struct TableSourceCore {
     /// The senders of the changes channel.
     ///
     /// When a `StreamReader` is created, a channel will be created and the sender will be
     /// saved here. The insert statement will take one channel randomly.
-    changes_txs: Vec<mpsc::UnboundedSender<(StreamChunk, oneshot::Sender<usize>)>>,
+    changes_txs: Vec<mpsc::UnboundedSender<ChangesItem>>,
 }
 
-/// [`TableSource`] is a special internal source to handle table updates from user,
-/// including insert/delete/update statements via SQL interface.
-///
-/// Changed rows will be send to the associated "materialize" streaming task, then be written to the
-/// state store. Therefore, [`TableSource`] can be simply be treated as a channel without side
-/// effects.
-#[derive(Debug)]
-pub struct TableSource {
-    core: RwLock<TableSourceCore>,
-
-    /// All columns in this table.
-    column_descs: Vec<ColumnDesc>,
-}
-
-impl TableSource {
-    pub fn new(column_descs: Vec<ColumnDesc>) -> Self {
-        let core = TableSourceCore {
-            changes_txs: vec![],
-        };
-
-        Self {
-            core: RwLock::new(core),
-            column_descs,
-        }
-    }
-
-    /// Asynchronously write stream chunk into table. Changes written here will be simply passed to
-    /// the associated streaming task via channel, and then be materialized to storage there.
-    ///
-    /// Returns an oneshot channel which will be notified when the chunk is taken by some reader,
-    /// and the `usize` represents the cardinality of this chunk.
-    pub fn write_chunk(&self, mut chunk: StreamChunk) -> Result<oneshot::Receiver<usize>> {
+impl TableSourceCore {
+    /// Send the chunk to a random reader, the `notifier` will be notified when the chunk is taken.
+    fn send_chunk(
+        core: &RwLock<Self>,
+        mut chunk: StreamChunk,
+        mut notifier: oneshot::Sender<usize>,
+    ) -> Result<()> {
         loop {
-            let core = self.core.upgradable_read();
+            let core = core.upgradable_read();
 
             // The `changes_txs` should not be empty normally, since we ensured that the channels
             // between the `TableSource` and the `SourceExecutor`s are ready before we making the
@@ -82,24 +64,16 @@ impl TableSource {
                 .choose(&mut rand::thread_rng())
                 .context("no available table reader in streaming source executors")?;
 
-            #[cfg(debug_assertions)]
-            risingwave_common::util::schema_check::schema_check(
-                self.column_descs.iter().map(|c| &c.data_type),
-                chunk.columns(),
-            )
-            .expect("table source write chunk schema check failed");
-
-            let (notifier_tx, notifier_rx) = oneshot::channel();
-
-            match tx.send((chunk, notifier_tx)) {
-                Ok(_) => return Ok(notifier_rx),
+            match tx.send((chunk, notifier)) {
+                Ok(_) => return Ok(()),
 
                 // It's possible that the source executor is scaled in or migrated, so the channel
                 // is closed. In this case, we should remove the closed channel and retry.
-                Err(SendError((chunk_, _))) => {
+                Err(SendError((chunk_, notifier_))) => {
                     tracing::info!("find one closed table source channel, remove it and retry");
 
                     chunk = chunk_;
+                    notifier = notifier_;
                     RwLockUpgradableReadGuard::upgrade(core)
                         .changes_txs
                         .swap_remove(index);
@@ -109,17 +83,90 @@ impl TableSource {
     }
 }
 
-/// [`TableStreamReader`] reads changes from a certain table continuously.
-/// This struct should be only used for associated materialize task, thus the reader should be
-/// created only once. Further streaming task relying on this table source should follow the
-/// structure of "`MView` on `MView`".
+/// [`TableSource`] is a special internal source to handle table updates from user,
+/// including insert/delete/update statements via SQL interface.
+///
+/// Changed rows will be send to the associated "materialize" streaming task, then be written to the
+/// state store. Therefore, [`TableSource`] can be simply be treated as a channel without side
+/// effects.
+#[derive(Debug)]
+pub struct TableSource {
+    core: Arc<RwLock<TableSourceCore>>,
+
+    /// All columns in this table with their defaults, which are used to fill the columns missing
+    /// in the written chunks. May be updated by `ALTER TABLE`.
+    columns: RwLock<Vec<(ColumnDesc, Datum)>>,
+}
+
+impl TableSource {
+    pub fn new(column_descs: Vec<ColumnDesc>, column_defaults: Vec<Datum>) -> Self {
+        let core = TableSourceCore {
+            changes_txs: vec![],
+        };
+
+        Self {
+            core: Arc::new(RwLock::new(core)),
+            columns: RwLock::new(column_descs.into_iter().zip_eq(column_defaults).collect()),
+        }
+    }
+
+    /// Get the columns of this table.
+    pub fn column_descs(&self) -> Vec<ColumnDesc> {
+        self.columns.read().iter().map(|(c, _)| c.clone()).collect()
+    }
+
+    /// Update the columns of this table after it's altered. New columns can only be appended.
+    ///
+    /// This is called by every source executor of the table, so it's a no-op if the columns have
+    /// already been updated.
+    pub fn update_columns(&self, column_descs: Vec<ColumnDesc>, column_defaults: Vec<Datum>) {
+        let mut current = self.columns.write();
+        assert!(
+            current.len() <= column_descs.len(),
+            "columns of table source can only be appended"
+        );
+        *current = column_descs.into_iter().zip_eq(column_defaults).collect();
+    }
+
+    /// Asynchronously write stream chunk into table. Changes written here will be simply passed to
+    /// the associated streaming task via channel, and then be materialized to storage there.
+    ///
+    /// Returns an oneshot channel which will be notified when the chunk is taken by some reader,
+    /// and the `usize` represents the cardinality of this chunk.
+    pub fn write_chunk(&self, chunk: StreamChunk) -> Result<oneshot::Receiver<usize>> {
+        // The chunk may be built with the columns before an `ALTER TABLE ADD COLUMN`, so only
+        // the leading columns are checked. The appended columns will be filled with their
+        // defaults by the reader.
+        #[cfg(debug_assertions)]
+        risingwave_common::util::schema_check::schema_check(
+            self.columns
+                .read()
+                .iter()
+                .take(chunk.columns().len())
+                .map(|(c, _)| &c.data_type)
+                .collect_vec(),
+            chunk.columns(),
+        )
+        .expect("table source write chunk schema check failed");
+
+        let (notifier_tx, notifier_rx) = oneshot::channel();
+        TableSourceCore::send_chunk(&self.core, chunk, notifier_tx)?;
+        Ok(notifier_rx)
+    }
+}
+
 #[derive(Debug)]
 pub struct TableStreamReader {
+    core: Arc<RwLock<TableSourceCore>>,
+
     /// The receiver of the changes channel.
-    rx: mpsc::UnboundedReceiver<(StreamChunk, oneshot::Sender<usize>)>,
+    rx: mpsc::UnboundedReceiver<ChangesItem>,
 
     /// Mappings from the source column to the column to be read.
     column_indices: Vec<usize>,
+
+    /// Data types and defaults of the columns to be read.
+    columns: Vec<(DataType, Datum)>,
 }
 
 impl TableStreamReader {
@@ -128,10 +175,22 @@ impl TableStreamReader {
         while let Some((chunk, notifier)) = self.rx.recv().await {
             let (ops, columns, bitmap) = chunk.into_inner();
 
+            // Columns missing in the chunk are the ones appended by `ALTER TABLE ADD COLUMN` after
+            // the chunk is built, fill them with their defaults.
             let selected_columns = self
                 .column_indices
                 .iter()
-                .map(|i| columns[*i].clone())
+                .zip_eq(&self.columns)
+                .map(|(i, (data_type, default))| match columns.get(*i) {
+                    Some(column) => column.clone(),
+                    None => {
+                        let mut builder = data_type.create_array_builder(ops.len());
+                        for _ in 0..ops.len() {
+                            builder.append_datum(default);
+                        }
+                        Column::from(builder.finish())
+                    }
+                })
                 .collect();
             let chunk = StreamChunk::new(ops, selected_columns, bitmap);
 
@@ -143,25 +202,50 @@ impl TableStreamReader {
     }
 }
 
+impl Drop for TableStreamReader {
+    /// The reader is dropped when it's replaced, e.g. after `ALTER TABLE`, or when the source
+    /// executor is stopped. Forward the chunks buffered in the channel to the other readers instead
+    /// of failing the statements that wrote them.
+    fn drop(&mut self) {
+        self.rx.close();
+        while let Ok((chunk, notifier)) = self.rx.try_recv() {
+            if let Err(e) = TableSourceCore::send_chunk(&self.core, chunk, notifier) {
+                tracing::warn!("failed to forward buffered chunk of table source: {}", e);
+                break;
+            }
+        }
+    }
+}
+
 impl TableSource {
     /// Create a new stream reader.
     #[expect(clippy::unused_async)]
     pub async fn stream_reader(&self, column_ids: Vec<ColumnId>) -> Result<TableStreamReader> {
-        let column_indices = column_ids
+        let table_columns = self.columns.read();
+        let (column_indices, columns) = column_ids
             .into_iter()
             .map(|id| {
-                self.column_descs
+                table_columns
                     .iter()
-                    .position(|c| c.column_id == id)
+                    .position(|(c, _)| c.column_id == id)
+                    .map(|i| {
+                        let (column_desc, default) = &table_columns[i];
+                        (i, (column_desc.data_type.clone(), default.clone()))
+                    })
                     .expect("column id not exists")
             })
-            .collect();
+            .unzip();
 
         let mut core = self.core.write();
         let (tx, rx) = mpsc::unbounded_channel();
         core.changes_txs.push(tx);
 
-        Ok(TableStreamReader { rx, column_indices })
+        Ok(TableStreamReader {
+            core: self.core.clone(),
+            rx,
+            column_indices,
+            columns,
+        })
     }
 }
 
@@ -194,6 +278,7 @@ pub mod test_utils {
                         .to_protobuf(),
                     ),
                     is_hidden: false,
+                    default_value: vec![],
                 })
                 .collect(),
             pk_column_ids,
@@ -211,7 +296,7 @@ mod tests {
     use itertools::Itertools;
     use risingwave_common::array::{Array, I64Array, Op};
     use risingwave_common::column_nonnull;
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_storage::memory::MemoryStateStore;
     use risingwave_storage::Keyspace;
 
@@ -221,10 +306,10 @@ mod tests {
         let store = MemoryStateStore::new();
         let _keyspace = Keyspace::table_root(store, &Default::default());
 
-        TableSource::new(vec![ColumnDesc::unnamed(
-            ColumnId::from(0),
-            DataType::Int64,
-        )])
+        TableSource::new(
+            vec![ColumnDesc::unnamed(ColumnId::from(0), DataType::Int64)],
+            vec![None],
+        )
     }
 
     #[tokio::test]
@@ -264,4 +349,70 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_table_source_forward_on_replace() -> Result<()> {
+        let source = new_source();
+        let reader = source.stream_reader(vec![ColumnId::from(0)]).await?;
+
+        let chunk = StreamChunk::new(
+            vec![Op::Insert],
+            vec![column_nonnull!(I64Array, [233])],
+            None,
+        );
+        let notifier = source.write_chunk(chunk).unwrap();
+
+        // Replace the reader before the chunk is taken, the chunk is forwarded to the new one.
+        let mut new_reader = source
+            .stream_reader(vec![ColumnId::from(0)])
+            .await?
+            .into_stream();
+        drop(reader);
+
+        assert_matches!(new_reader.next().await.unwrap()?, chunk => {
+            let columns = chunk.chunk.columns();
+            assert_eq!(columns[0].array_ref().as_int64().iter().collect_vec(), vec![Some(233)]);
+        });
+        assert_eq!(notifier.await.unwrap(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_source_with_appended_column() -> Result<()> {
+        let source = new_source();
+        source.update_columns(
+            vec![
+                ColumnDesc::unnamed(ColumnId::from(0), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::from(1), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::from(2), DataType::Int64),
+            ],
+            vec![None, None, Some(ScalarImpl::Int64(1))],
+        );
+        let mut reader = source
+            .stream_reader(vec![
+                ColumnId::from(0),
+                ColumnId::from(1),
+                ColumnId::from(2),
+            ])
+            .await?
+            .into_stream();
+
+        // The chunk is built with the columns before the table is altered.
+        let chunk = StreamChunk::new(
+            vec![Op::Insert],
+            vec![column_nonnull!(I64Array, [233])],
+            None,
+        );
+        source.write_chunk(chunk).unwrap();
+
+        assert_matches!(reader.next().await.unwrap()?, chunk => {
+            let columns = chunk.chunk.columns();
+            assert_eq!(columns[0].array_ref().as_int64().iter().collect_vec(), vec![Some(233)]);
+            assert_eq!(columns[1].array_ref().as_int64().iter().collect_vec(), vec![None]);
+            assert_eq!(columns[2].array_ref().as_int64().iter().collect_vec(), vec![Some(1)]);
+        });
+
+        Ok(())
+    }
 }
@@ -33,7 +33,7 @@ use serde::{Deserialize, Serialize};
 pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
     AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
-    ReferentialAction, TableConstraint,
+    ReferentialAction, SourceWatermark, TableConstraint,
 };
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
@@ -692,6 +692,7 @@ pub enum ShowObject {
     Database,
     Schema,
     MaterializedView { schema: Option<Ident> },
+    View { schema: Option<Ident> },
     Source { schema: Option<Ident> },
     Sink { schema: Option<Ident> },
     MaterializedSource { schema: Option<Ident> },
@@ -717,6 +718,7 @@ impl fmt::Display for ShowObject {
             ShowObject::MaterializedView { schema } => {
                 write!(f, "MATERIALIZED VIEWS{}", fmt_schema(schema))
             }
+            ShowObject::View { schema } => write!(f, "VIEWS{}", fmt_schema(schema)),
             ShowObject::Source { schema } => write!(f, "SOURCES{}", fmt_schema(schema)),
             ShowObject::MaterializedSource { schema } => {
                 write!(f, "MATERIALIZED SOURCES{}", fmt_schema(schema))
@@ -801,6 +803,82 @@ impl fmt::Display for ExplainOptions {
     }
 }
 
+/// The source of a `COPY` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopySource {
+    Table {
+        /// The name of the table to copy from or to.
+        table_name: ObjectName,
+        /// A list of column names to copy. Empty list means that all columns are copied.
+        columns: Vec<Ident>,
+    },
+    Query(Box<Query>),
+}
+
+impl fmt::Display for CopySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopySource::Table {
+                table_name,
+                columns,
+            } => {
+                write!(f, "{}", table_name)?;
+                if !columns.is_empty() {
+                    write!(f, " ({})", display_comma_separated(columns))?;
+                }
+                Ok(())
+            }
+            CopySource::Query(query) => write!(f, "({})", query),
+        }
+    }
+}
+
+/// The target of a `COPY` statement. Only the client connection is supported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopyTarget {
+    Stdin,
+    Stdout,
+}
+
+impl fmt::Display for CopyTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyTarget::Stdin => write!(f, "STDIN"),
+            CopyTarget::Stdout => write!(f, "STDOUT"),
+        }
+    }
+}
+
+/// An option in the `WITH (..)` clause of a `COPY` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopyOption {
+    /// FORMAT format_name
+    Format(Ident),
+    /// DELIMITER 'delimiter_character'
+    Delimiter(char),
+    /// NULL 'null_string'
+    Null(String),
+    /// HEADER \[ boolean \]
+    Header(bool),
+}
+
+impl fmt::Display for CopyOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyOption::Format(name) => write!(f, "FORMAT {}", name),
+            CopyOption::Delimiter(char) => write!(f, "DELIMITER '{}'", char),
+            CopyOption::Null(string) => {
+                write!(f, "NULL '{}'", value::escape_single_quote_string(string))
+            }
+            CopyOption::Header(true) => write!(f, "HEADER"),
+            CopyOption::Header(false) => write!(f, "HEADER false"),
+        }
+    }
+}
+
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -821,11 +899,16 @@ pub enum Statement {
         /// A SQL query that specifies what to insert
         source: Box<Query>,
     },
+    /// COPY
     Copy {
-        /// TABLE
-        table_name: ObjectName,
-        /// COLUMNS
-        columns: Vec<Ident>,
+        /// The table or query to copy from or to
+        source: CopySource,
+        /// If true, is a 'COPY TO' statement. If false is a 'COPY FROM'
+        to: bool,
+        /// STDIN or STDOUT
+        target: CopyTarget,
+        /// WITH options
+        options: Vec<CopyOption>,
         /// VALUES a vector of values to be copied
         values: Vec<Option<String>>,
     },
@@ -854,6 +937,8 @@ pub enum Statement {
         columns: Vec<Ident>,
         query: Box<Query>,
         with_options: Vec<SqlOption>,
+        /// `EMIT ...`, only for materialized views
+        emit_mode: Option<EmitMode>,
     },
     /// CREATE TABLE
     CreateTable {
@@ -1052,26 +1137,33 @@ impl fmt::Display for Statement {
             }
 
             Statement::Copy {
-                table_name,
-                columns,
+                source,
+                to,
+                target,
+                options,
                 values,
             } => {
-                write!(f, "COPY {}", table_name)?;
-                if !columns.is_empty() {
-                    write!(f, " ({})", display_comma_separated(columns))?;
+                write!(f, "COPY {}", source)?;
+                if *to {
+                    write!(f, " TO {}", target)?;
+                } else {
+                    write!(f, " FROM {}", target)?;
+                }
+                if !options.is_empty() {
+                    write!(f, " ({})", display_comma_separated(options))?;
+                }
+                if values.is_empty() {
+                    return Ok(());
                 }
-                write!(f, " FROM stdin; ")?;
-                if !values.is_empty() {
-                    writeln!(f)?;
-                    let mut delim = "";
-                    for v in values {
-                        write!(f, "{}", delim)?;
-                        delim = "\t";
-                        if let Some(v) = v {
-                            write!(f, "{}", v)?;
-                        } else {
-                            write!(f, "\\N")?;
-                        }
+                writeln!(f, "; ")?;
+                let mut delim = "";
+                for v in values {
+                    write!(f, "{}", delim)?;
+                    delim = "\t";
+                    if let Some(v) = v {
+                        write!(f, "{}", v)?;
+                    } else {
+                        write!(f, "\\N")?;
                     }
                 }
                 write!(f, "\n\\.")
@@ -1118,6 +1210,7 @@ impl fmt::Display for Statement {
                 query,
                 materialized,
                 with_options,
+                emit_mode,
             } => {
                 write!(
                     f,
@@ -1132,7 +1225,11 @@ impl fmt::Display for Statement {
                 if !columns.is_empty() {
                     write!(f, " ({})", display_comma_separated(columns))?;
                 }
-                write!(f, " AS {}", query)
+                write!(f, " AS {}", query)?;
+                if let Some(emit_mode) = emit_mode {
+                    write!(f, " EMIT {}", emit_mode)?;
+                }
+                Ok(())
             }
             Statement::CreateTable {
                 name,
@@ -1409,6 +1506,25 @@ impl fmt::Display for OnInsert {
     }
 }
 
+/// When the results of a materialized view are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EmitMode {
+    /// Emit the changes immediately, which is the default.
+    Immediately,
+    /// Emit the result of a window only once after it's closed by the watermark.
+    OnWindowClose,
+}
+
+impl fmt::Display for EmitMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmitMode::Immediately => write!(f, "IMMEDIATELY"),
+            EmitMode::OnWindowClose => write!(f, "ON WINDOW CLOSE"),
+        }
+    }
+}
+
 /// Privileges granted in a GRANT statement or revoked in a REVOKE statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -75,6 +75,7 @@ pub struct CreateSourceStatement {
     pub if_not_exists: bool,
     pub columns: Vec<ColumnDef>,
     pub constraints: Vec<TableConstraint>,
+    pub source_watermarks: Vec<SourceWatermark>,
     pub source_name: ObjectName,
     pub with_properties: WithProperties,
     pub source_schema: SourceSchema,
@@ -88,6 +89,9 @@ pub enum SourceSchema {
     Json,             // Keyword::JSON
     DebeziumJson,     // Keyword::DEBEZIUM_JSON
     Avro(AvroSchema), // Keyword::AVRO
+    Csv,              // Keyword::CSV
+    CanalJson,        // Keyword::CANAL_JSON
+    Maxwell,          // Keyword::MAXWELL
 }
 
 impl ParseTo for SourceSchema {
@@ -102,9 +106,17 @@ impl ParseTo for SourceSchema {
         } else if p.parse_keywords(&[Keyword::AVRO]) {
             impl_parse_to!(avro_schema: AvroSchema, p);
             SourceSchema::Avro(avro_schema)
+        } else if p.parse_keywords(&[Keyword::CSV]) {
+            SourceSchema::Csv
+        } else if p.parse_keywords(&[Keyword::CANAL_JSON]) {
+            SourceSchema::CanalJson
+        } else if p.parse_keywords(&[Keyword::MAXWELL]) {
+            SourceSchema::Maxwell
         } else {
             return Err(ParserError::ParserError(
-                "expected JSON | PROTOBUF | DEBEZIUM JSON | AVRO after ROW FORMAT".to_string(),
+                "expected JSON | PROTOBUF | DEBEZIUM JSON | AVRO | CSV | CANAL_JSON | MAXWELL \
+                 after ROW FORMAT"
+                    .to_string(),
             ));
         };
         Ok(schema)
@@ -118,6 +130,9 @@ impl fmt::Display for SourceSchema {
             SourceSchema::Json => write!(f, "JSON"),
             SourceSchema::DebeziumJson => write!(f, "DEBEZIUM JSON"),
             SourceSchema::Avro(avro_schema) => write!(f, "AVRO {}", avro_schema),
+            SourceSchema::Csv => write!(f, "CSV"),
+            SourceSchema::CanalJson => write!(f, "CANAL_JSON"),
+            SourceSchema::Maxwell => write!(f, "MAXWELL"),
         }
     }
 }
@@ -126,6 +141,7 @@ impl fmt::Display for SourceSchema {
 //     [Keyword::MESSAGE],
 //     message_name: AstString,
 //     [Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION],
+//     use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
 //     row_schema_location: AstString,
 // });
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -133,6 +149,8 @@ impl fmt::Display for SourceSchema {
 pub struct ProtobufSchema {
     pub message_name: AstString,
     pub row_schema_location: AstString,
+    /// Whether `row_schema_location` is the url of a Confluent Schema Registry.
+    pub use_schema_registry: bool,
 }
 
 impl ParseTo for ProtobufSchema {
@@ -140,10 +158,15 @@ impl ParseTo for ProtobufSchema {
         impl_parse_to!([Keyword::MESSAGE], p);
         impl_parse_to!(message_name: AstString, p);
         impl_parse_to!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], p);
+        impl_parse_to!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            p
+        );
         impl_parse_to!(row_schema_location: AstString, p);
         Ok(Self {
             message_name,
             row_schema_location,
+            use_schema_registry,
         })
     }
 }
@@ -154,6 +177,11 @@ impl fmt::Display for ProtobufSchema {
         impl_fmt_display!([Keyword::MESSAGE], v);
         impl_fmt_display!(message_name, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], v);
+        impl_fmt_display!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            v,
+            self
+        );
         impl_fmt_display!(row_schema_location, v, self);
         v.iter().join(" ").fmt(f)
     }
@@ -163,6 +191,7 @@ impl fmt::Display for ProtobufSchema {
 //     [Keyword::MESSAGE],
 //     message_name: AstString,
 //     [Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION],
+//     use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
 //     row_schema_location: AstString,
 // });
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -170,6 +199,8 @@ impl fmt::Display for ProtobufSchema {
 pub struct AvroSchema {
     pub message_name: AstString,
     pub row_schema_location: AstString,
+    /// Whether `row_schema_location` is the url of a Confluent Schema Registry.
+    pub use_schema_registry: bool,
 }
 
 impl ParseTo for AvroSchema {
@@ -177,10 +208,15 @@ impl ParseTo for AvroSchema {
         impl_parse_to!([Keyword::MESSAGE], p);
         impl_parse_to!(message_name: AstString, p);
         impl_parse_to!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], p);
+        impl_parse_to!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            p
+        );
         impl_parse_to!(row_schema_location: AstString, p);
         Ok(Self {
             message_name,
             row_schema_location,
+            use_schema_registry,
         })
     }
 }
@@ -191,6 +227,11 @@ impl fmt::Display for AvroSchema {
         impl_fmt_display!([Keyword::MESSAGE], v);
         impl_fmt_display!(message_name, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], v);
+        impl_fmt_display!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            v,
+            self
+        );
         impl_fmt_display!(row_schema_location, v, self);
         v.iter().join(" ").fmt(f)
     }
@@ -202,7 +243,7 @@ impl ParseTo for CreateSourceStatement {
         impl_parse_to!(source_name: ObjectName, p);
 
         // parse columns
-        let (columns, constraints) = p.parse_columns()?;
+        let (columns, constraints, source_watermarks) = p.parse_columns_with_watermark()?;
 
         impl_parse_to!(with_properties: WithProperties, p);
         impl_parse_to!([Keyword::ROW, Keyword::FORMAT], p);
@@ -211,6 +252,7 @@ impl ParseTo for CreateSourceStatement {
             if_not_exists,
             columns,
             constraints,
+            source_watermarks,
             source_name,
             with_properties,
             source_schema,
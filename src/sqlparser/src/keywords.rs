@@ -107,6 +107,7 @@ define_keywords!(
     CACHE,
     CALL,
     CALLED,
+    CANAL_JSON,
     CARDINALITY,
     CASCADE,
     CASCADED,
@@ -134,6 +135,7 @@ define_keywords!(
     COMMITTED,
     CONCURRENTLY,
     CONDITION,
+    CONFLUENT,
     CONNECT,
     CONSTRAINT,
     CONTAINS,
@@ -178,6 +180,7 @@ define_keywords!(
     DEFAULT,
     DEFERRABLE,
     DELETE,
+    DELIMITER,
     DENSE_RANK,
     DEREF,
     DESC,
@@ -195,6 +198,7 @@ define_keywords!(
     EACH,
     ELEMENT,
     ELSE,
+    EMIT,
     ENCRYPTED,
     END,
     END_EXEC = "END-EXEC",
@@ -247,6 +251,7 @@ define_keywords!(
     IF,
     IGNORE,
     ILIKE,
+    IMMEDIATELY,
     IN,
     INCLUDE,
     INDEX,
@@ -267,6 +272,7 @@ define_keywords!(
     ISOLATION,
     JOIN,
     JSON,
+    JSONB,
     KEY,
     LANGUAGE,
     LARGE,
@@ -288,6 +294,7 @@ define_keywords!(
     MATCH,
     MATERIALIZED,
     MAX,
+    MAXWELL,
     MEMBER,
     MERGE,
     MESSAGE,
@@ -377,6 +384,7 @@ define_keywords!(
     REFERENCES,
     REFERENCING,
     REGCLASS,
+    REGISTRY,
     REGR_AVGX,
     REGR_AVGY,
     REGR_COUNT,
@@ -445,6 +453,7 @@ define_keywords!(
     STDDEV_POP,
     STDDEV_SAMP,
     STDIN,
+    STDOUT,
     STORED,
     STRING,
     STRUCT,
@@ -517,6 +526,7 @@ define_keywords!(
     VIEW,
     VIEWS,
     VIRTUAL,
+    WATERMARK,
     WHEN,
     WHENEVER,
     WHERE,
@@ -554,6 +564,7 @@ pub const RESERVED_FOR_TABLE_ALIAS: &[Keyword] = &[
     Keyword::UNION,
     Keyword::EXCEPT,
     Keyword::INTERSECT,
+    Keyword::EMIT,
     // Reserved only as a table alias in the `FROM`/`JOIN` clauses:
     Keyword::ON,
     Keyword::JOIN,
@@ -592,6 +603,7 @@ pub const RESERVED_FOR_COLUMN_ALIAS: &[Keyword] = &[
     Keyword::UNION,
     Keyword::EXCEPT,
     Keyword::INTERSECT,
+    Keyword::EMIT,
     Keyword::CLUSTER,
     // Reserved only as a column alias in the `SELECT` clause
     Keyword::FROM,
@@ -1024,6 +1024,9 @@ impl Parser {
             Token::TildeAsterisk => Some(BinaryOperator::PGRegexIMatch),
             Token::ExclamationMarkTilde => Some(BinaryOperator::PGRegexNotMatch),
             Token::ExclamationMarkTildeAsterisk => Some(BinaryOperator::PGRegexNotIMatch),
+            Token::Arrow => Some(BinaryOperator::Arrow),
+            Token::LongArrow => Some(BinaryOperator::LongArrow),
+            Token::AtArrow => Some(BinaryOperator::AtArrow),
             Token::Word(w) => match w.keyword {
                 Keyword::AND => Some(BinaryOperator::And),
                 Keyword::OR => Some(BinaryOperator::Or),
@@ -1211,6 +1214,7 @@ impl Parser {
             Token::Pipe => Ok(21),
             Token::Caret | Token::Sharp | Token::ShiftRight | Token::ShiftLeft => Ok(22),
             Token::Ampersand => Ok(23),
+            Token::Arrow | Token::LongArrow | Token::AtArrow => Ok(25),
             Token::Plus | Token::Minus => Ok(Self::PLUS_MINUS_PREC),
             Token::Mul | Token::Div | Token::Mod | Token::Concat => Ok(40),
             Token::DoubleColon => Ok(50),
@@ -1501,6 +1505,11 @@ impl Parser {
         let with_options = self.parse_options(Keyword::WITH)?;
         self.expect_keyword(Keyword::AS)?;
         let query = Box::new(self.parse_query()?);
+        let emit_mode = if materialized {
+            self.parse_emit_mode()?
+        } else {
+            None
+        };
         // Optional `WITH [ CASCADED | LOCAL ] CHECK OPTION` is widely supported here.
         Ok(Statement::CreateView {
             name,
@@ -1509,9 +1518,27 @@ impl Parser {
             materialized,
             or_replace,
             with_options,
+            emit_mode,
         })
     }
 
+    /// Parse an optional `EMIT IMMEDIATELY` or `EMIT ON WINDOW CLOSE` clause.
+    pub fn parse_emit_mode(&mut self) -> Result<Option<EmitMode>, ParserError> {
+        if !self.parse_keyword(Keyword::EMIT) {
+            return Ok(None);
+        }
+        if self.parse_keyword(Keyword::IMMEDIATELY) {
+            Ok(Some(EmitMode::Immediately))
+        } else if self.parse_keywords(&[Keyword::ON, Keyword::WINDOW, Keyword::CLOSE]) {
+            Ok(Some(EmitMode::OnWindowClose))
+        } else {
+            self.expected(
+                "IMMEDIATELY or ON WINDOW CLOSE after EMIT",
+                self.peek_token(),
+            )
+        }
+    }
+
     // CREATE [OR REPLACE]?
     // [MATERIALIZED] SOURCE
     // [IF NOT EXISTS]?
@@ -1519,7 +1546,7 @@ impl Parser {
     // [COLUMNS]?
     // [WITH (properties)]?
     // ROW FORMAT <row_format: Ident>
-    // [ROW SCHEMA LOCATION <row_schema_location: String>]?
+    // [ROW SCHEMA LOCATION [CONFLUENT SCHEMA REGISTRY]? <row_schema_location: String>]?
     pub fn parse_create_source(
         &mut self,
         is_materialized: bool,
@@ -1626,15 +1653,37 @@ impl Parser {
     }
 
     pub fn parse_columns(&mut self) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>), ParserError> {
+        let (columns, constraints, _) = self.parse_columns_inner(false)?;
+        Ok((columns, constraints))
+    }
+
+    /// Parse the column definitions of a source, which may also contain the watermark
+    /// definitions.
+    pub fn parse_columns_with_watermark(
+        &mut self,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
+        self.parse_columns_inner(true)
+    }
+
+    fn parse_columns_inner(
+        &mut self,
+        allow_watermark: bool,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
         let mut columns = vec![];
         let mut constraints = vec![];
+        let mut watermarks = vec![];
         if !self.consume_token(&Token::LParen) || self.consume_token(&Token::RParen) {
-            return Ok((columns, constraints));
+            return Ok((columns, constraints, watermarks));
         }
 
         loop {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
+            } else if allow_watermark
+                && matches!(self.peek_token(), Token::Word(w) if w.keyword == Keyword::WATERMARK)
+                && matches!(self.peek_nth_token(1), Token::Word(w) if w.keyword == Keyword::FOR)
+            {
+                watermarks.push(self.parse_source_watermark()?);
             } else if let Token::Word(_) = self.peek_token() {
                 columns.push(self.parse_column_def()?);
             } else {
@@ -1649,7 +1698,15 @@ impl Parser {
             }
         }
 
-        Ok((columns, constraints))
+        Ok((columns, constraints, watermarks))
+    }
+
+    fn parse_source_watermark(&mut self) -> Result<SourceWatermark, ParserError> {
+        self.expect_keywords(&[Keyword::WATERMARK, Keyword::FOR])?;
+        let column = self.parse_identifier_non_reserved()?;
+        self.expect_keyword(Keyword::AS)?;
+        let expr = self.parse_expr()?;
+        Ok(SourceWatermark { column, expr })
     }
 
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
@@ -1937,19 +1994,109 @@ impl Parser {
     }
 
     /// Parse a copy statement
+    /// Parse a copy statement
+    /// ```sql
+    /// COPY { table_name [ ( column_name [, ...] ) ] | ( query ) }
+    ///     { FROM STDIN | TO STDOUT } [ [ WITH ] ( option [, ...] ) ]
+    /// ```
     pub fn parse_copy(&mut self) -> Result<Statement, ParserError> {
-        let table_name = self.parse_object_name()?;
-        let columns = self.parse_parenthesized_column_list(Optional)?;
-        self.expect_keywords(&[Keyword::FROM, Keyword::STDIN])?;
-        self.expect_token(&Token::SemiColon)?;
-        let values = self.parse_tsv();
+        let source = if self.consume_token(&Token::LParen) {
+            let query = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
+            CopySource::Query(Box::new(query))
+        } else {
+            let table_name = self.parse_object_name()?;
+            let columns = self.parse_parenthesized_column_list(Optional)?;
+            CopySource::Table {
+                table_name,
+                columns,
+            }
+        };
+        let to = match self.expect_one_of_keywords(&[Keyword::FROM, Keyword::TO])? {
+            Keyword::FROM => false,
+            Keyword::TO => true,
+            _ => unreachable!(),
+        };
+        let target = if to {
+            self.expect_keyword(Keyword::STDOUT)?;
+            CopyTarget::Stdout
+        } else {
+            self.expect_keyword(Keyword::STDIN)?;
+            CopyTarget::Stdin
+        };
+        if matches!(source, CopySource::Query(_)) && !to {
+            return parser_err!("COPY FROM cannot be used with a query".to_string());
+        }
+        let _ = self.parse_keyword(Keyword::WITH);
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_copy_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        // The data may follow the statement directly, e.g. in the output of `pg_dump`.
+        let values = if !to && self.consume_token(&Token::SemiColon) {
+            self.parse_tsv()
+        } else {
+            vec![]
+        };
         Ok(Statement::Copy {
-            table_name,
-            columns,
+            source,
+            to,
+            target,
+            options,
             values,
         })
     }
 
+    fn parse_copy_option(&mut self) -> Result<CopyOption, ParserError> {
+        let keyword = self.expect_one_of_keywords(&[
+            Keyword::FORMAT,
+            Keyword::DELIMITER,
+            Keyword::NULL,
+            Keyword::HEADER,
+        ])?;
+        let option = match keyword {
+            Keyword::FORMAT => CopyOption::Format(self.parse_identifier()?),
+            Keyword::DELIMITER => {
+                let delimiter = self.parse_literal_string()?;
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) => CopyOption::Delimiter(char),
+                    _ => {
+                        return parser_err!(
+                            "COPY delimiter must be a single one-byte character".to_string()
+                        )
+                    }
+                }
+            }
+            Keyword::NULL => CopyOption::Null(self.parse_literal_string()?),
+            Keyword::HEADER => {
+                let header = match self.peek_token() {
+                    Token::Comma | Token::RParen => true,
+                    _ => match self.next_token() {
+                        Token::Word(w)
+                            if w.keyword == Keyword::TRUE || w.keyword == Keyword::ON =>
+                        {
+                            true
+                        }
+                        Token::Word(w)
+                            if w.keyword == Keyword::FALSE
+                                || w.value.eq_ignore_ascii_case("off") =>
+                        {
+                            false
+                        }
+                        unexpected => return self.expected("boolean", unexpected),
+                    },
+                };
+                CopyOption::Header(header)
+            }
+            _ => unreachable!(),
+        };
+        Ok(option)
+    }
+
     /// Parse a tab separated values in
     /// COPY payload
     fn parse_tsv(&mut self) -> Vec<Option<String>> {
@@ -2158,6 +2305,7 @@ impl Parser {
                 }
                 Keyword::STRUCT => Ok(DataType::Struct(self.parse_struct_data_type()?)),
                 Keyword::BYTEA => Ok(DataType::Bytea),
+                Keyword::JSONB => Ok(DataType::Jsonb),
                 Keyword::NUMERIC | Keyword::DECIMAL | Keyword::DEC => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     Ok(DataType::Decimal(precision, scale))
@@ -2683,16 +2831,20 @@ impl Parser {
 
     pub fn parse_set(&mut self) -> Result<Statement, ParserError> {
         let modifier = self.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
+        if self.parse_keywords(&[Keyword::TIME, Keyword::ZONE]) {
+            // `SET TIME ZONE <value>` is an alias for `SET timezone = <value>`.
+            let value = self.parse_set_variable_value()?;
+            return Ok(Statement::SetVariable {
+                local: modifier == Some(Keyword::LOCAL),
+                variable: Ident::new("timezone"),
+                value: vec![value],
+            });
+        }
         let variable = self.parse_identifier()?;
         if self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::TO) {
             let mut values = vec![];
             loop {
-                let token = self.peek_token();
-                let value = match (self.parse_value(), token) {
-                    (Ok(value), _) => SetVariableValue::Literal(value),
-                    (Err(_), Token::Word(ident)) => SetVariableValue::Ident(ident.to_ident()),
-                    (Err(_), unexpected) => self.expected("variable value", unexpected)?,
-                };
+                let value = self.parse_set_variable_value()?;
                 values.push(value);
                 if self.consume_token(&Token::Comma) {
                     continue;
@@ -2729,6 +2881,15 @@ impl Parser {
         }
     }
 
+    fn parse_set_variable_value(&mut self) -> Result<SetVariableValue, ParserError> {
+        let token = self.peek_token();
+        match (self.parse_value(), token) {
+            (Ok(value), _) => Ok(SetVariableValue::Literal(value)),
+            (Err(_), Token::Word(ident)) => Ok(SetVariableValue::Ident(ident.to_ident())),
+            (Err(_), unexpected) => self.expected("variable value", unexpected),
+        }
+    }
+
     /// If have `databases`,`tables`,`columns`,`schemas` and `materialized views` after show,
     /// return `Statement::ShowCommand` or `Statement::ShowColumn`,
     /// otherwise, return `Statement::ShowVariable`.
@@ -2741,6 +2902,11 @@ impl Parser {
                         schema: self.parse_from_and_identifier()?,
                     }));
                 }
+                Keyword::VIEWS => {
+                    return Ok(Statement::ShowObjects(ShowObject::View {
+                        schema: self.parse_from_and_identifier()?,
+                    }));
+                }
                 Keyword::SOURCES => {
                     return Ok(Statement::ShowObjects(ShowObject::Source {
                         schema: self.parse_from_and_identifier()?,
@@ -2771,6 +2937,13 @@ impl Parser {
                             .expected("VIEWS or SOURCES after MATERIALIZED", self.peek_token());
                     }
                 }
+                Keyword::TIME => {
+                    if self.parse_keyword(Keyword::ZONE) {
+                        return Ok(Statement::ShowVariable {
+                            variable: vec![Ident::new("timezone")],
+                        });
+                    }
+                }
                 Keyword::COLUMNS => {
                     if self.parse_keyword(Keyword::FROM) {
                         return Ok(Statement::ShowObjects(ShowObject::Columns {
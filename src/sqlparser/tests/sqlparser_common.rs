@@ -2966,8 +2966,10 @@ fn parse_create_view() {
             or_replace,
             materialized,
             with_options,
+            emit_mode,
         } => {
             assert_eq!("myschema.myview", name.to_string());
+            assert_eq!(emit_mode, None);
             assert_eq!(Vec::<Ident>::new(), columns);
             assert_eq!("SELECT foo FROM bar", query.to_string());
             assert!(!materialized);
@@ -3012,8 +3014,10 @@ fn parse_create_view_with_columns() {
             with_options,
             query,
             materialized,
+            emit_mode,
         } => {
             assert_eq!("v", name.to_string());
+            assert_eq!(emit_mode, None);
             assert_eq!(columns, vec![Ident::new("has"), Ident::new("cols")]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1, 2", query.to_string());
@@ -3034,8 +3038,10 @@ fn parse_create_or_replace_view() {
             with_options,
             query,
             materialized,
+            emit_mode,
         } => {
             assert_eq!("v", name.to_string());
+            assert_eq!(emit_mode, None);
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1", query.to_string());
@@ -3061,8 +3067,10 @@ fn parse_create_or_replace_materialized_view() {
             with_options,
             query,
             materialized,
+            emit_mode,
         } => {
             assert_eq!("v", name.to_string());
+            assert_eq!(emit_mode, None);
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1", query.to_string());
@@ -3084,8 +3092,10 @@ fn parse_create_materialized_view() {
             query,
             materialized,
             with_options,
+            emit_mode,
         } => {
             assert_eq!("myschema.myview", name.to_string());
+            assert_eq!(emit_mode, None);
             assert_eq!(Vec::<Ident>::new(), columns);
             assert_eq!("SELECT foo FROM bar", query.to_string());
             assert!(materialized);
@@ -3096,6 +3106,37 @@ fn parse_create_materialized_view() {
     }
 }
 
+#[test]
+fn parse_create_materialized_view_emit_mode() {
+    let sql =
+        "CREATE MATERIALIZED VIEW v AS SELECT w, count(*) FROM t GROUP BY w EMIT ON WINDOW CLOSE";
+    match verified_stmt(sql) {
+        Statement::CreateView {
+            emit_mode, query, ..
+        } => {
+            assert_eq!(emit_mode, Some(EmitMode::OnWindowClose));
+            assert_eq!("SELECT w, count(*) FROM t GROUP BY w", query.to_string());
+        }
+        _ => unreachable!(),
+    }
+
+    let sql = "CREATE MATERIALIZED VIEW v AS SELECT * FROM t EMIT IMMEDIATELY";
+    match verified_stmt(sql) {
+        Statement::CreateView { emit_mode, .. } => {
+            assert_eq!(emit_mode, Some(EmitMode::Immediately));
+        }
+        _ => unreachable!(),
+    }
+
+    let res = parse_sql_statements("CREATE MATERIALIZED VIEW v AS SELECT * FROM t EMIT ON CLOSE");
+    assert_eq!(
+        ParserError::ParserError(
+            "Expected IMMEDIATELY or ON WINDOW CLOSE after EMIT, found: CLOSE".to_string()
+        ),
+        res.unwrap_err()
+    );
+}
+
 #[test]
 fn parse_drop_table() {
     let sql = "DROP TABLE foo";
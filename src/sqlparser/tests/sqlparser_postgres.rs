@@ -382,6 +382,39 @@ PHP	₱ USD $
     // assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_copy_stdin_stdout() {
+    let stmt = verified_stmt("COPY t (a, b) FROM STDIN (FORMAT csv, DELIMITER ',', HEADER)");
+    assert_eq!(
+        stmt,
+        Statement::Copy {
+            source: CopySource::Table {
+                table_name: ObjectName(vec!["t".into()]),
+                columns: vec!["a".into(), "b".into()],
+            },
+            to: false,
+            target: CopyTarget::Stdin,
+            options: vec![
+                CopyOption::Format("csv".into()),
+                CopyOption::Delimiter(','),
+                CopyOption::Header(true),
+            ],
+            values: vec![],
+        }
+    );
+
+    verified_stmt("COPY t FROM STDIN (NULL 'null', HEADER false)");
+    verified_stmt("COPY t TO STDOUT");
+    verified_stmt("COPY (SELECT * FROM t WHERE a > 1) TO STDOUT (FORMAT text)");
+    one_statement_parses_to(
+        "COPY t FROM STDIN WITH (FORMAT csv)",
+        "COPY t FROM STDIN (FORMAT csv)",
+    );
+
+    assert!(parse_sql_statements("COPY (SELECT 1) FROM STDIN").is_err());
+    assert!(parse_sql_statements("COPY t FROM STDIN (DELIMITER '||')").is_err());
+}
+
 #[test]
 fn parse_set() {
     let stmt = verified_stmt("SET a = b");
@@ -438,6 +471,11 @@ fn parse_set() {
 
     one_statement_parses_to("SET a TO b", "SET a = b");
     one_statement_parses_to("SET SESSION a = b", "SET a = b");
+    one_statement_parses_to(
+        "SET TIME ZONE 'Asia/Shanghai'",
+        "SET timezone = 'Asia/Shanghai'",
+    );
+    one_statement_parses_to("SET TIME ZONE LOCAL", "SET timezone = LOCAL");
 
     assert_eq!(
         parse_sql_statements("SET"),
@@ -477,7 +515,9 @@ fn parse_show() {
         Statement::ShowVariable {
             variable: vec!["ALL".into(), "ALL".into()]
         }
-    )
+    );
+
+    one_statement_parses_to("SHOW TIME ZONE", "SHOW timezone");
 }
 
 #[test]
@@ -688,6 +728,42 @@ fn parse_pg_regex_match_ops() {
     }
 }
 
+#[test]
+fn parse_pg_json_ops() {
+    let pg_json_ops = &[
+        ("->", BinaryOperator::Arrow),
+        ("->>", BinaryOperator::LongArrow),
+        ("@>", BinaryOperator::AtArrow),
+    ];
+
+    for (str_op, op) in pg_json_ops {
+        let select = verified_only_select(&format!("SELECT a {} 'b'", &str_op));
+        assert_eq!(
+            SelectItem::UnnamedExpr(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: op.clone(),
+                right: Box::new(Expr::Value(Value::SingleQuotedString("b".into()))),
+            }),
+            select.projection[0]
+        );
+    }
+
+    // `->` binds tighter than comparisons.
+    let select = verified_only_select("SELECT a -> 'b' = c");
+    assert_eq!(
+        SelectItem::UnnamedExpr(Expr::BinaryOp {
+            left: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Arrow,
+                right: Box::new(Expr::Value(Value::SingleQuotedString("b".into()))),
+            }),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Identifier(Ident::new("c"))),
+        }),
+        select.projection[0]
+    );
+}
+
 #[test]
 fn test_transaction_statement() {
     let statement = verified_stmt("SET TRANSACTION SNAPSHOT '000003A1-1'");
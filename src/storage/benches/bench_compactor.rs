@@ -36,8 +36,9 @@ use risingwave_storage::hummock::sstable::SstableIteratorReadOptions;
 use risingwave_storage::hummock::sstable_store::SstableStoreRef;
 use risingwave_storage::hummock::value::HummockValue;
 use risingwave_storage::hummock::{
-    CachePolicy, CompactorSstableStore, CompressionAlgorithm, MemoryLimiter, SstableBuilder,
-    SstableBuilderOptions, SstableIterator, SstableStore, SstableWriterOptions, TieredCache,
+    CachePolicy, CompactorSstableStore, CompressionAlgorithm, DeleteRangeAggregator, MemoryLimiter,
+    SstableBuilder, SstableBuilderOptions, SstableIterator, SstableStore, SstableWriterOptions,
+    TieredCache,
 };
 use risingwave_storage::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
@@ -181,6 +182,7 @@ async fn compact<I: HummockIterator<Direction = Forward>>(iter: I, sstable_store
         Arc::new(StateStoreMetrics::unused()),
         iter,
         DummyCompactionFilter,
+        Arc::new(DeleteRangeAggregator::default()),
     )
     .await
     .unwrap();
@@ -411,6 +411,7 @@ mod tests {
                             .into(),
                     ),
                     is_hidden: true,
+                    default_value: vec![],
                 },
                 ProstColumnCatalog {
                     column_desc: Some(
@@ -424,6 +425,7 @@ mod tests {
                             .into(),
                     ),
                     is_hidden: false,
+                    default_value: vec![],
                 },
                 ProstColumnCatalog {
                     column_desc: Some(
@@ -437,6 +439,7 @@ mod tests {
                             .into(),
                     ),
                     is_hidden: false,
+                    default_value: vec![],
                 },
                 ProstColumnCatalog {
                     column_desc: Some(
@@ -450,6 +453,7 @@ mod tests {
                             .into(),
                     ),
                     is_hidden: false,
+                    default_value: vec![],
                 },
             ],
             pk: vec![
@@ -476,6 +480,7 @@ mod tests {
             vnode_col_idx: None,
             value_indices: vec![0],
             definition: "".into(),
+            version: 0,
         }
     }
 
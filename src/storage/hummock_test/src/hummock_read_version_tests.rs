@@ -136,6 +136,7 @@ async fn test_read_version_basic() {
                 stale_key_count: 1,
                 total_key_count: 1,
                 divide_version: 0,
+                range_tombstone_count: 0,
             },
             epoch_id_vec_for_clear,
             batch_id_vec_for_clear,
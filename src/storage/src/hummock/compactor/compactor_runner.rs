@@ -27,7 +27,10 @@ use crate::hummock::compactor::{
     CompactOutput, CompactionFilter, Compactor, CompactorContext, CompactorSstableStoreRef,
 };
 use crate::hummock::iterator::{Forward, HummockIterator, UnorderedMergeIteratorInner};
-use crate::hummock::{CachePolicy, CompressionAlgorithm, HummockResult, SstableBuilderOptions};
+use crate::hummock::{
+    CachePolicy, CompressionAlgorithm, DeleteRangeAggregator, HummockResult, SstableBuilderOptions,
+};
+use crate::monitor::StoreLocalStatistic;
 
 #[derive(Clone)]
 pub struct CompactorRunner {
@@ -86,11 +89,13 @@ impl CompactorRunner {
         task_progress: Arc<TaskProgress>,
     ) -> HummockResult<CompactOutput> {
         let iter = self.build_sst_iter()?;
+        let del_agg = self.build_delete_range_aggregator().await?;
         let ssts = self
             .compactor
             .compact_key_range(
                 iter,
                 compaction_filter,
+                del_agg,
                 filter_key_extractor,
                 Some(task_progress),
             )
@@ -98,6 +103,25 @@ impl CompactorRunner {
         Ok((self.split_index, ssts))
     }
 
+    /// Collects the range tombstones of all input ssts.
+    async fn build_delete_range_aggregator(&self) -> HummockResult<Arc<DeleteRangeAggregator>> {
+        let mut del_agg = DeleteRangeAggregator::new(
+            self.compact_task.watermark,
+            self.compact_task.gc_delete_keys,
+        );
+        let mut stats = StoreLocalStatistic::default();
+        for table_info in self
+            .compact_task
+            .input_ssts
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+        {
+            let table = self.sstable_store.sstable(table_info, &mut stats).await?;
+            del_agg.add_tombstones(table.value().meta.range_tombstone_list.clone());
+        }
+        Ok(Arc::new(del_agg))
+    }
+
     /// Build the merge iterator based on the given input ssts.
     fn build_sst_iter(&self) -> HummockResult<impl HummockIterator<Direction = Forward>> {
         let mut table_iters = Vec::new();
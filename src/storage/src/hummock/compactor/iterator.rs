@@ -205,7 +205,7 @@ impl ConcatSstableIterator {
     }
 
     /// Resets the iterator, loads the specified SST, and seeks in that SST to `seek_key` if given.
-    async fn seek_idx(&mut self, idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
+    async fn seek_idx(&mut self, mut idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
         self.sstable_iter.take();
         let seek_key: Option<&[u8]> = match (seek_key, self.key_range.left.is_empty()) {
             (Some(seek_key), false) => {
@@ -219,12 +219,17 @@ impl ConcatSstableIterator {
             (None, false) => Some(&self.key_range.left),
         };
 
-        if idx < self.tables.len() {
+        while idx < self.tables.len() {
             let table_info = &self.tables[idx];
             let table = self
                 .sstable_store
                 .sstable(table_info, &mut self.stats)
                 .await?;
+            // SSTs containing only range tombstones have no key to iterate.
+            if table.value().meta.key_count == 0 {
+                idx += 1;
+                continue;
+            }
             let block_metas = &table.value().meta.block_metas;
             let start_index = match seek_key {
                 None => 0,
@@ -268,6 +273,7 @@ impl ConcatSstableIterator {
 
             self.sstable_iter = Some(sstable_iter);
             self.cur_idx = idx;
+            break;
         }
         Ok(())
     }
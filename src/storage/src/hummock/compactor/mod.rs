@@ -68,8 +68,8 @@ use crate::hummock::multi_builder::{SplitTableOutput, TableBuilderFactory};
 use crate::hummock::utils::MemoryLimiter;
 use crate::hummock::vacuum::Vacuum;
 use crate::hummock::{
-    validate_ssts, BatchSstableWriterFactory, CachePolicy, HummockError, SstableBuilder,
-    SstableIdManagerRef, SstableWriterFactory, StreamingSstableWriterFactory,
+    validate_ssts, BatchSstableWriterFactory, CachePolicy, DeleteRangeAggregator, HummockError,
+    SstableBuilder, SstableIdManagerRef, SstableWriterFactory, StreamingSstableWriterFactory,
 };
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
@@ -520,6 +520,7 @@ impl Compactor {
         stats: Arc<StateStoreMetrics>,
         mut iter: impl HummockIterator<Direction = Forward>,
         mut compaction_filter: impl CompactionFilter,
+        del_agg: Arc<DeleteRangeAggregator>,
     ) -> HummockResult<()>
     where
         F: TableBuilderFactory,
@@ -576,6 +577,11 @@ impl Compactor {
                 drop = true;
             }
 
+            // Keys deleted by range tombstones below `watermark` are invisible to any reader.
+            if !drop && del_agg.should_delete(user_key(iter_key), epoch) {
+                drop = true;
+            }
+
             if epoch <= task_config.watermark {
                 watermark_can_see_last_key = true;
             }
@@ -628,6 +634,7 @@ impl Compactor {
         &self,
         iter: impl HummockIterator<Direction = Forward>,
         compaction_filter: impl CompactionFilter,
+        del_agg: Arc<DeleteRangeAggregator>,
         filter_key_extractor: Arc<FilterKeyExtractorImpl>,
         task_progress: Option<Arc<TaskProgress>>,
     ) -> HummockResult<Vec<SstableInfo>> {
@@ -646,6 +653,7 @@ impl Compactor {
                 StreamingSstableWriterFactory::new(self.context.sstable_store.clone()),
                 iter,
                 compaction_filter,
+                del_agg,
                 filter_key_extractor,
                 get_id_time.clone(),
                 task_progress.clone(),
@@ -656,6 +664,7 @@ impl Compactor {
                 BatchSstableWriterFactory::new(self.context.sstable_store.clone()),
                 iter,
                 compaction_filter,
+                del_agg,
                 filter_key_extractor,
                 get_id_time.clone(),
                 task_progress.clone(),
@@ -717,6 +726,7 @@ impl Compactor {
         writer_factory: F,
         iter: impl HummockIterator<Direction = Forward>,
         compaction_filter: impl CompactionFilter,
+        del_agg: Arc<DeleteRangeAggregator>,
         filter_key_extractor: Arc<FilterKeyExtractorImpl>,
         get_id_time: Arc<AtomicU64>,
         task_progress: Option<Arc<TaskProgress>>,
@@ -735,6 +745,7 @@ impl Compactor {
             builder_factory,
             self.context.stats.clone(),
             task_progress,
+            del_agg.clone(),
         );
         Compactor::compact_and_build_sst(
             &mut sst_builder,
@@ -742,6 +753,7 @@ impl Compactor {
             self.context.stats.clone(),
             iter,
             compaction_filter,
+            del_agg,
         )
         .await?;
         sst_builder.finish().await
@@ -817,12 +829,17 @@ async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>)
         let mut indexes = vec![];
         // preload the meta and get the smallest key to split sub_compaction
         for sstable_info in sstable_infos {
+            let table = context
+                .sstable_store
+                .sstable(sstable_info, &mut StoreLocalStatistic::default())
+                .await
+                .unwrap();
+            // Range tombstones are not split among sub-compactions yet.
+            if !table.value().meta.range_tombstone_list.is_empty() {
+                return;
+            }
             indexes.extend(
-                context
-                    .sstable_store
-                    .sstable(sstable_info, &mut StoreLocalStatistic::default())
-                    .await
-                    .unwrap()
+                table
                     .value()
                     .meta
                     .block_metas
@@ -35,7 +35,9 @@ use crate::hummock::shared_buffer::shared_buffer_uploader::UploadTaskPayload;
 use crate::hummock::shared_buffer::{build_ordered_merge_iter, UncommittedData};
 use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::state_store::ForwardIter;
-use crate::hummock::{CachePolicy, HummockError, HummockResult, SstableBuilderOptions};
+use crate::hummock::{
+    CachePolicy, DeleteRangeAggregator, HummockError, HummockResult, SstableBuilderOptions,
+};
 use crate::monitor::StoreLocalStatistic;
 
 /// Flush shared buffer to level0. Resulted SSTs are grouped by compaction group.
@@ -101,6 +103,9 @@ async fn compact_shared_buffer(
     context: Arc<Context>,
     payload: UploadTaskPayload,
 ) -> HummockResult<Vec<SstableInfo>> {
+    let mut local_stats = StoreLocalStatistic::default();
+    let del_agg = build_delete_range_aggregator(&payload, &context, &mut local_stats).await?;
+
     let mut size_and_start_user_keys = payload
         .iter()
         .flat_map(|data_list| {
@@ -128,10 +133,15 @@ async fn compact_shared_buffer(
         splits.push(KeyRange::new(key_before_last.clone(), Bytes::new()));
     };
     let sstable_size = (context.options.sstable_size_mb as u64) << 20;
-    let parallelism = std::cmp::min(
-        context.options.share_buffers_sync_parallelism as u64,
-        size_and_start_user_keys.len() as u64,
-    );
+    // Range tombstones are not split among sub-compactions yet.
+    let parallelism = if del_agg.is_empty() {
+        std::cmp::min(
+            context.options.share_buffers_sync_parallelism as u64,
+            size_and_start_user_keys.len() as u64,
+        )
+    } else {
+        1
+    };
     let sub_compaction_data_size = if compact_data_size > sstable_size && parallelism > 1 {
         compact_data_size / parallelism
     } else {
@@ -192,7 +202,6 @@ async fn compact_shared_buffer(
     let mut output_ssts = Vec::with_capacity(parallelism);
     let mut compaction_futures = vec![];
 
-    let mut local_stats = StoreLocalStatistic::default();
     for (split_index, key_range) in splits.into_iter().enumerate() {
         let compactor = SharedBufferCompactRunner::new(
             split_index,
@@ -210,8 +219,12 @@ async fn compact_shared_buffer(
         .await?;
         let compaction_executor = context.compaction_executor.clone();
         let multi_filter_key_extractor = multi_filter_key_extractor.clone();
-        let handle = compaction_executor
-            .spawn(async move { compactor.run(iter, multi_filter_key_extractor).await });
+        let del_agg = del_agg.clone();
+        let handle = compaction_executor.spawn(async move {
+            compactor
+                .run(iter, del_agg, multi_filter_key_extractor)
+                .await
+        });
         compaction_futures.push(handle);
     }
     local_stats.report(stats.as_ref());
@@ -263,6 +276,28 @@ async fn compact_shared_buffer(
     }
 }
 
+/// Collects the range tombstones in the shared buffer. Nothing is deleted by them in the flush,
+/// since there's no watermark for the uncommitted data.
+async fn build_delete_range_aggregator(
+    payload: &UploadTaskPayload,
+    context: &Context,
+    local_stats: &mut StoreLocalStatistic,
+) -> HummockResult<Arc<DeleteRangeAggregator>> {
+    let mut del_agg = DeleteRangeAggregator::new(0, false);
+    for data in payload.iter().flatten() {
+        match data {
+            UncommittedData::Sst((_, info)) => {
+                let table = context.sstable_store.sstable(info, local_stats).await?;
+                del_agg.add_tombstones(table.value().meta.range_tombstone_list.clone());
+            }
+            UncommittedData::Batch(batch) => {
+                del_agg.add_tombstones(batch.get_delete_range_tombstones().to_vec());
+            }
+        }
+    }
+    Ok(Arc::new(del_agg))
+}
+
 pub struct SharedBufferCompactRunner {
     compactor: Compactor,
     split_index: usize,
@@ -287,12 +322,19 @@ impl SharedBufferCompactRunner {
     pub async fn run(
         &self,
         iter: impl HummockIterator<Direction = Forward>,
+        del_agg: Arc<DeleteRangeAggregator>,
         filter_key_extractor: Arc<FilterKeyExtractorImpl>,
     ) -> HummockResult<CompactOutput> {
         let dummy_compaction_filter = DummyCompactionFilter {};
         let ssts = self
             .compactor
-            .compact_key_range(iter, dummy_compaction_filter, filter_key_extractor, None)
+            .compact_key_range(
+                iter,
+                dummy_compaction_filter,
+                del_agg,
+                filter_key_extractor,
+                None,
+            )
             .await?;
         Ok((self.split_index, ssts))
     }
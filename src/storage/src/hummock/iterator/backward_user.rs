@@ -24,7 +24,7 @@ use crate::hummock::iterator::{
 };
 use crate::hummock::local_version::pinned_version::PinnedVersion;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{BackwardSstableIterator, HummockResult};
+use crate::hummock::{BackwardSstableIterator, DeleteRangeAggregator, HummockResult};
 use crate::monitor::StoreLocalStatistic;
 
 /// [`BackwardUserIterator`] can be used by user directly.
@@ -59,6 +59,9 @@ pub struct BackwardUserIterator<I: HummockIterator<Direction = Backward>> {
     /// Ensures the SSTs needed by `iterator` won't be vacuumed.
     _version: Option<PinnedVersion>,
 
+    /// Range tombstones visible at `read_epoch`.
+    del_agg: DeleteRangeAggregator,
+
     /// Store scan statistic
     stats: StoreLocalStatistic,
 }
@@ -84,9 +87,17 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
             min_epoch,
             stats: StoreLocalStatistic::default(),
             _version: version,
+            del_agg: DeleteRangeAggregator::default(),
         }
     }
 
+    /// Hides the keys deleted by range tombstones in `del_agg`, whose watermark should be
+    /// `read_epoch`.
+    pub(crate) fn with_delete_range_aggregator(mut self, del_agg: DeleteRangeAggregator) -> Self {
+        self.del_agg = del_agg;
+        self
+    }
+
     fn out_of_range(&self, key: &[u8]) -> bool {
         match &self.key_range.0 {
             Included(begin_key) => key < begin_key.as_slice(),
@@ -177,12 +188,13 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
 
                 // 1 and 2(a)
                 match self.iterator.value() {
-                    HummockValue::Put(val) => {
+                    HummockValue::Put(val) if !self.del_agg.should_delete(key, epoch) => {
                         self.last_val.clear();
                         self.last_val.extend_from_slice(val);
                         self.last_delete = false;
                     }
-                    HummockValue::Delete => {
+                    // Deleted by a delete operation or a range tombstone.
+                    _ => {
                         self.last_delete = true;
                     }
                 }
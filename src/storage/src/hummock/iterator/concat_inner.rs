@@ -62,16 +62,22 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
     }
 
     /// Seeks to a table, and then seeks to the key if `seek_key` is given.
-    async fn seek_idx(&mut self, idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
-        if idx >= self.tables.len() {
-            if let Some(old_iter) = self.sstable_iter.take() {
-                old_iter.collect_local_statistic(&mut self.stats);
-            }
-        } else {
-            let table = self
+    async fn seek_idx(&mut self, mut idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
+        let mut table = None;
+        while idx < self.tables.len() {
+            let holder = self
                 .sstable_store
                 .sstable(&self.tables[idx], &mut self.stats)
                 .await?;
+            // SSTs containing only range tombstones have no key to iterate.
+            if holder.value().meta.key_count > 0 {
+                table = Some(holder);
+                break;
+            }
+            idx += 1;
+        }
+
+        if let Some(table) = table {
             let mut sstable_iter =
                 TI::create(table, self.sstable_store.clone(), self.read_options.clone());
 
@@ -87,6 +93,8 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
 
             self.sstable_iter = Some(sstable_iter);
             self.cur_idx = idx;
+        } else if let Some(old_iter) = self.sstable_iter.take() {
+            old_iter.collect_local_statistic(&mut self.stats);
         }
         Ok(())
     }
@@ -24,7 +24,7 @@ use crate::hummock::iterator::{
 };
 use crate::hummock::local_version::pinned_version::PinnedVersion;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{HummockResult, SstableIterator};
+use crate::hummock::{DeleteRangeAggregator, HummockResult, SstableIterator};
 use crate::monitor::StoreLocalStatistic;
 
 /// [`UserIterator`] can be used by user directly.
@@ -53,6 +53,9 @@ pub struct UserIterator<I: HummockIterator<Direction = Forward>> {
     /// Ensures the SSTs needed by `iterator` won't be vacuumed.
     _version: Option<PinnedVersion>,
 
+    /// Range tombstones visible at `read_epoch`.
+    del_agg: DeleteRangeAggregator,
+
     stats: StoreLocalStatistic,
 }
 
@@ -76,9 +79,17 @@ impl<I: HummockIterator<Direction = Forward>> UserIterator<I> {
             min_epoch,
             stats: StoreLocalStatistic::default(),
             _version: version,
+            del_agg: DeleteRangeAggregator::default(),
         }
     }
 
+    /// Hides the keys deleted by range tombstones in `del_agg`, whose watermark should be
+    /// `read_epoch`.
+    pub(crate) fn with_delete_range_aggregator(mut self, del_agg: DeleteRangeAggregator) -> Self {
+        self.del_agg = del_agg;
+        self
+    }
+
     /// Gets the iterator move to the next step.
     ///
     /// Returned result:
@@ -103,7 +114,7 @@ impl<I: HummockIterator<Direction = Forward>> UserIterator<I> {
 
                 // handle delete operation
                 match self.iterator.value() {
-                    HummockValue::Put(val) => {
+                    HummockValue::Put(val) if !self.del_agg.should_delete(key, epoch) => {
                         self.last_val.clear();
                         self.last_val.extend_from_slice(val);
 
@@ -117,10 +128,11 @@ impl<I: HummockIterator<Direction = Forward>> UserIterator<I> {
                         self.stats.processed_key_count += 1;
                         return Ok(());
                     }
-                    // It means that the key is deleted from the storage.
+                    // It means that the key is deleted from the storage, either by a delete
+                    // operation or by a range tombstone.
                     // Deleted kv and the previous versions (if any) of the key should not be
                     // returned to user.
-                    HummockValue::Delete => {
+                    _ => {
                         self.stats.skip_delete_key_count += 1;
                     }
                 }
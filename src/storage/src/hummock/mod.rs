@@ -315,10 +315,24 @@ pub async fn get_from_sstable_info(
     internal_key: &[u8],
     check_bloom_filter: bool,
     local_stats: &mut StoreLocalStatistic,
+    del_agg: &mut DeleteRangeAggregator,
 ) -> HummockResult<Option<HummockValue<Bytes>>> {
     let sstable = sstable_store_ref.sstable(sstable_info, local_stats).await?;
 
     let ukey = user_key(internal_key);
+    // Range tombstones should be collected even if the key is surely not in the sstable, since they
+    // may delete the key in older ones.
+    if sstable_info.range_tombstone_count > 0 {
+        del_agg.add_tombstones(
+            sstable
+                .value()
+                .meta
+                .range_tombstone_list
+                .iter()
+                .filter(|tombstone| tombstone.contains(ukey))
+                .cloned(),
+        );
+    }
     if check_bloom_filter && !hit_sstable_bloom_filter(sstable.value(), ukey, local_stats) {
         return Ok(None);
     }
@@ -339,6 +353,9 @@ pub async fn get_from_sstable_info(
     // Iterator gets us the key, we tell if it's the key we want
     // or key next to it.
     let value = match key::user_key(iter.key()) == ukey {
+        true if del_agg.should_delete(ukey, key::get_epoch(iter.key())) => {
+            Some(HummockValue::Delete)
+        }
         true => Some(iter.value().to_bytes()),
         false => None,
     };
@@ -370,6 +387,7 @@ pub async fn get_from_order_sorted_uncommitted_data(
     local_stats: &mut StoreLocalStatistic,
     key: &[u8],
     check_bloom_filter: bool,
+    del_agg: &mut DeleteRangeAggregator,
 ) -> StorageResult<(Option<HummockValue<Bytes>>, i32)> {
     let mut table_counts = 0;
     let epoch = key::get_epoch(internal_key);
@@ -378,7 +396,7 @@ pub async fn get_from_order_sorted_uncommitted_data(
             match data {
                 UncommittedData::Batch(batch) => {
                     assert!(batch.epoch() <= epoch, "batch'epoch greater than epoch");
-                    if let Some(data) = get_from_batch(&batch, key, local_stats) {
+                    if let Some(data) = get_from_batch(&batch, key, local_stats, del_agg) {
                         return Ok((Some(data), table_counts));
                     }
                 }
@@ -392,6 +410,7 @@ pub async fn get_from_order_sorted_uncommitted_data(
                         internal_key,
                         check_bloom_filter,
                         local_stats,
+                        del_agg,
                     )
                     .await?
                     {
@@ -409,9 +428,21 @@ pub fn get_from_batch(
     batch: &SharedBufferBatch,
     key: &[u8],
     local_stats: &mut StoreLocalStatistic,
+    del_agg: &mut DeleteRangeAggregator,
 ) -> Option<HummockValue<Bytes>> {
+    del_agg.add_tombstones(
+        batch
+            .get_delete_range_tombstones()
+            .iter()
+            .filter(|tombstone| tombstone.contains(key))
+            .cloned(),
+    );
     batch.get(key).map(|v| {
         local_stats.get_shared_buffer_hit_counts += 1;
-        v
+        if del_agg.should_delete(key, batch.epoch()) {
+            HummockValue::Delete
+        } else {
+            v
+        }
     })
 }
@@ -29,7 +29,7 @@ use crate::hummock::iterator::{
 };
 use crate::hummock::utils::MemoryTracker;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{key, HummockEpoch, HummockResult, MemoryLimiter};
+use crate::hummock::{key, DeleteRangeTombstone, HummockEpoch, HummockResult, MemoryLimiter};
 use crate::storage_value::StorageValue;
 
 pub(crate) type SharedBufferItem = (Bytes, HummockValue<Bytes>);
@@ -37,6 +37,8 @@ pub type SharedBufferBatchId = u64;
 
 pub(crate) struct SharedBufferBatchInner {
     payload: Vec<SharedBufferItem>,
+    /// Sorted by start key.
+    range_tombstones: Vec<DeleteRangeTombstone>,
     size: usize,
     _tracker: Option<MemoryTracker>,
     batch_id: SharedBufferBatchId,
@@ -54,15 +56,15 @@ impl Debug for SharedBufferBatchInner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "SharedBufferBatchInner {{ payload: {:?}, size: {} }}",
-            self.payload, self.size
+            "SharedBufferBatchInner {{ payload: {:?}, range_tombstones: {:?}, size: {} }}",
+            self.payload, self.range_tombstones, self.size
         )
     }
 }
 
 impl PartialEq for SharedBufferBatchInner {
     fn eq(&self, other: &Self) -> bool {
-        self.payload == other.payload
+        self.payload == other.payload && self.range_tombstones == other.range_tombstones
     }
 }
 
@@ -91,6 +93,7 @@ impl SharedBufferBatch {
         Self {
             inner: Arc::new(SharedBufferBatchInner {
                 payload: sorted_items,
+                range_tombstones: vec![],
                 size,
                 _tracker: None,
                 batch_id: SHARED_BUFFER_BATCH_ID_GENERATOR.fetch_add(1, Relaxed),
@@ -106,7 +109,22 @@ impl SharedBufferBatch {
         limiter: Option<&MemoryLimiter>,
         table_id: TableId,
     ) -> Self {
-        let size = Self::measure_batch_size(&sorted_items);
+        Self::build_with_delete_range(sorted_items, vec![], epoch, limiter, table_id).await
+    }
+
+    pub async fn build_with_delete_range(
+        sorted_items: Vec<SharedBufferItem>,
+        mut range_tombstones: Vec<DeleteRangeTombstone>,
+        epoch: HummockEpoch,
+        limiter: Option<&MemoryLimiter>,
+        table_id: TableId,
+    ) -> Self {
+        range_tombstones.sort_by(|a, b| a.start_user_key.cmp(&b.start_user_key));
+        let size = Self::measure_batch_size(&sorted_items)
+            + range_tombstones
+                .iter()
+                .map(|tombstone| tombstone.encoded_size())
+                .sum::<usize>();
         let tracker = if let Some(limiter) = limiter {
             limiter.require_memory(size as u64).await
         } else {
@@ -121,6 +139,7 @@ impl SharedBufferBatch {
         Self {
             inner: Arc::new(SharedBufferBatchInner {
                 payload: sorted_items,
+                range_tombstones,
                 size,
                 _tracker: tracker,
                 batch_id: SHARED_BUFFER_BATCH_ID_GENERATOR.fetch_add(1, Relaxed),
@@ -181,12 +200,38 @@ impl SharedBufferBatch {
         &self.inner.last().unwrap().0
     }
 
+    /// The smallest user key of the batch, including the start keys of range tombstones.
     pub fn start_user_key(&self) -> &[u8] {
-        key::user_key(&self.inner.first().unwrap().0)
+        let start_key = self.inner.first().map(|(key, _)| key::user_key(key));
+        let tombstone_start_key = self
+            .inner
+            .range_tombstones
+            .first()
+            .map(|tombstone| tombstone.start_user_key.as_slice());
+        match (start_key, tombstone_start_key) {
+            (Some(a), Some(b)) => std::cmp::min(a, b),
+            (a, b) => a.or(b).expect("empty batch"),
+        }
     }
 
+    /// The largest user key of the batch, including the end keys of range tombstones. Note that
+    /// the end keys of range tombstones are exclusive, so the range may be a little wider.
     pub fn end_user_key(&self) -> &[u8] {
-        key::user_key(&self.inner.last().unwrap().0)
+        let end_key = self.inner.last().map(|(key, _)| key::user_key(key));
+        let tombstone_end_key = self
+            .inner
+            .range_tombstones
+            .iter()
+            .map(|tombstone| tombstone.end_user_key.as_slice())
+            .max();
+        match (end_key, tombstone_end_key) {
+            (Some(a), Some(b)) => std::cmp::max(a, b),
+            (a, b) => a.or(b).expect("empty batch"),
+        }
+    }
+
+    pub fn get_delete_range_tombstones(&self) -> &[DeleteRangeTombstone] {
+        &self.inner.range_tombstones
     }
 
     pub fn epoch(&self) -> u64 {
@@ -241,6 +286,28 @@ impl SharedBufferBatch {
         let sorted_items = Self::build_shared_buffer_item_batches(kv_pairs, epoch);
         SharedBufferBatch::build(sorted_items, epoch, memory_limit, table_id).await
     }
+
+    /// Builds a batch containing only range tombstones, which delete keys in `[start, end)` written
+    /// before `epoch`.
+    pub async fn build_delete_range_batch(
+        epoch: HummockEpoch,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        table_id: TableId,
+        memory_limit: Option<&MemoryLimiter>,
+    ) -> Self {
+        let range_tombstones = delete_ranges
+            .into_iter()
+            .map(|(start, end)| DeleteRangeTombstone::new(start.to_vec(), end.to_vec(), epoch))
+            .collect();
+        SharedBufferBatch::build_with_delete_range(
+            vec![],
+            range_tombstones,
+            epoch,
+            memory_limit,
+            table_id,
+        )
+        .await
+    }
 }
 
 pub struct SharedBufferBatchIterator<D: HummockIteratorDirection> {
@@ -46,7 +46,7 @@ impl BackwardSstableIterator {
     pub fn new(sstable: TableHolder, sstable_store: SstableStoreRef) -> Self {
         Self {
             block_iter: None,
-            cur_idx: sstable.value().meta.block_metas.len() - 1,
+            cur_idx: sstable.value().meta.block_metas.len().saturating_sub(1),
             sst: sstable,
             sstable_store,
             stats: StoreLocalStatistic::default(),
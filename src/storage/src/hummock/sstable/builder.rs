@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::sync::Arc;
 
@@ -21,13 +22,14 @@ use risingwave_hummock_sdk::filter_key_extractor::{
     FilterKeyExtractorImpl, FullKeyFilterKeyExtractor,
 };
 use risingwave_hummock_sdk::key::{get_table_id, user_key};
+use risingwave_hummock_sdk::VersionedComparator;
 use risingwave_pb::hummock::SstableInfo;
 
 use super::bloom::Bloom;
 use super::utils::CompressionAlgorithm;
 use super::{
-    BlockBuilder, BlockBuilderOptions, BlockMeta, SstableMeta, SstableWriter, DEFAULT_BLOCK_SIZE,
-    DEFAULT_ENTRY_SIZE, DEFAULT_RESTART_INTERVAL, VERSION,
+    BlockBuilder, BlockBuilderOptions, BlockMeta, DeleteRangeTombstone, SstableMeta, SstableWriter,
+    DEFAULT_BLOCK_SIZE, DEFAULT_ENTRY_SIZE, DEFAULT_RESTART_INTERVAL, VERSION,
 };
 use crate::hummock::value::HummockValue;
 use crate::hummock::HummockResult;
@@ -97,6 +99,7 @@ pub struct SstableBuilder<W: SstableWriter> {
     user_key_hashes: Vec<u32>,
     last_full_key: Vec<u8>,
     key_count: usize,
+    range_tombstones: Vec<DeleteRangeTombstone>,
     sstable_id: u64,
     raw_value: BytesMut,
     filter_key_extractor: Arc<FilterKeyExtractorImpl>,
@@ -141,6 +144,7 @@ impl<W: SstableWriter> SstableBuilder<W> {
             raw_value: BytesMut::new(),
             last_full_key: vec![],
             key_count: 0,
+            range_tombstones: vec![],
             sstable_id,
             filter_key_extractor,
             last_bloom_filter_key_length: 0,
@@ -212,6 +216,12 @@ impl<W: SstableWriter> SstableBuilder<W> {
         Ok(())
     }
 
+    /// Add range tombstones to sstable. The caller should make sure that the tombstones are within
+    /// the key range of this sstable.
+    pub fn add_delete_range(&mut self, tombstones: Vec<DeleteRangeTombstone>) {
+        self.range_tombstones.extend(tombstones);
+    }
+
     /// Finish building sst.
     ///
     /// Unlike most LSM-Tree implementations, sstable meta and data are encoded separately.
@@ -225,8 +235,30 @@ impl<W: SstableWriter> SstableBuilder<W> {
     /// | Block 0 | ... | Block N-1 | N (4B) |
     /// ```
     pub async fn finish(mut self) -> HummockResult<SstableBuilderOutput<W::Output>> {
-        let smallest_key = self.block_metas[0].smallest_key.clone();
-        let largest_key = self.last_full_key.clone();
+        let mut smallest_key = self
+            .block_metas
+            .first()
+            .map(|block_meta| block_meta.smallest_key.clone())
+            .unwrap_or_default();
+        let mut largest_key = self.last_full_key.clone();
+        // The key range of sstable should cover the range tombstones as well.
+        for tombstone in &self.range_tombstones {
+            let start_key = tombstone.start_full_key();
+            if smallest_key.is_empty()
+                || VersionedComparator::compare_key(&start_key, &smallest_key) == Ordering::Less
+            {
+                smallest_key = start_key;
+            }
+            let end_key = tombstone.end_full_key();
+            if largest_key.is_empty()
+                || VersionedComparator::compare_key(&end_key, &largest_key) == Ordering::Greater
+            {
+                largest_key = end_key;
+            }
+            if let Some(table_id) = get_table_id(&tombstone.start_user_key) {
+                self.table_ids.insert(table_id);
+            }
+        }
 
         self.build_block().await?;
         let meta_offset = self.writer.data_len() as u64;
@@ -247,6 +279,7 @@ impl<W: SstableWriter> SstableBuilder<W> {
             key_count: self.key_count as u32,
             smallest_key,
             largest_key,
+            range_tombstone_list: self.range_tombstones,
             version: VERSION,
             meta_offset,
         };
@@ -264,6 +297,7 @@ impl<W: SstableWriter> SstableBuilder<W> {
             stale_key_count: self.stale_key_count,
             total_key_count: self.total_key_count,
             divide_version: 0,
+            range_tombstone_count: meta.range_tombstone_list.len() as u64,
         };
         tracing::trace!(
             "meta_size {} bloom_filter_size {}  add_key_counts {} ",
@@ -272,8 +306,14 @@ impl<W: SstableWriter> SstableBuilder<W> {
             self.key_count,
         );
         let bloom_filter_size = meta.bloom_filter.len();
-        let avg_key_size = self.total_key_size / self.key_count;
-        let avg_value_size = self.total_value_size / self.key_count;
+        let (avg_key_size, avg_value_size) = if self.key_count == 0 {
+            (0, 0)
+        } else {
+            (
+                self.total_key_size / self.key_count,
+                self.total_value_size / self.key_count,
+            )
+        };
 
         let writer_output = self.writer.finish(meta).await?;
         Ok(SstableBuilderOutput::<W::Output> {
@@ -285,6 +325,10 @@ impl<W: SstableWriter> SstableBuilder<W> {
         })
     }
 
+    pub fn last_full_key(&self) -> &[u8] {
+        &self.last_full_key
+    }
+
     pub fn approximate_len(&self) -> usize {
         self.writer.data_len()
             + self.block_builder.approximate_len()
@@ -311,7 +355,7 @@ impl<W: SstableWriter> SstableBuilder<W> {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.user_key_hashes.is_empty()
+        self.user_key_hashes.is_empty() && self.range_tombstones.is_empty()
     }
 
     /// Returns true if we roughly reached capacity
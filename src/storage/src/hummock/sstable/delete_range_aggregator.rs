@@ -0,0 +1,252 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::{Buf, BufMut};
+use risingwave_hummock_sdk::key::key_with_epoch;
+use risingwave_hummock_sdk::HummockEpoch;
+
+use super::utils::{get_length_prefixed_slice, put_length_prefixed_slice};
+
+/// A range tombstone which deletes all the versions of user keys in
+/// `[start_user_key, end_user_key)` whose epoch is smaller than `sequence`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeleteRangeTombstone {
+    pub start_user_key: Vec<u8>,
+    pub end_user_key: Vec<u8>,
+    pub sequence: HummockEpoch,
+}
+
+impl DeleteRangeTombstone {
+    pub fn new(start_user_key: Vec<u8>, end_user_key: Vec<u8>, sequence: HummockEpoch) -> Self {
+        Self {
+            start_user_key,
+            end_user_key,
+            sequence,
+        }
+    }
+
+    /// Format:
+    ///
+    /// ```plain
+    /// | start key len (4B) | start key | end key len (4B) | end key | sequence (8B) |
+    /// ```
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        put_length_prefixed_slice(buf, &self.start_user_key);
+        put_length_prefixed_slice(buf, &self.end_user_key);
+        buf.put_u64_le(self.sequence);
+    }
+
+    pub fn decode(buf: &mut &[u8]) -> Self {
+        let start_user_key = get_length_prefixed_slice(buf);
+        let end_user_key = get_length_prefixed_slice(buf);
+        let sequence = buf.get_u64_le();
+        Self {
+            start_user_key,
+            end_user_key,
+            sequence,
+        }
+    }
+
+    #[inline]
+    pub fn encoded_size(&self) -> usize {
+        16 /* key lens + sequence */ + self.start_user_key.len() + self.end_user_key.len()
+    }
+
+    pub fn contains(&self, user_key: &[u8]) -> bool {
+        self.start_user_key.as_slice() <= user_key && user_key < self.end_user_key.as_slice()
+    }
+
+    /// The smallest full key covered by the tombstone, used as the left bound of the SST.
+    pub fn start_full_key(&self) -> Vec<u8> {
+        key_with_epoch(self.start_user_key.clone(), HummockEpoch::MAX)
+    }
+
+    /// An inclusive upper bound of the full keys covered by the tombstone, used as the right bound
+    /// of the SST. If the end key is the immediate successor of some user key, which is always the
+    /// case when the tombstone is cut by SST boundaries, the bound is the last version of that key,
+    /// so that SSTs remain non-overlapping in user key.
+    pub fn end_full_key(&self) -> Vec<u8> {
+        match self.end_user_key.split_last() {
+            Some((0, prefix)) => key_with_epoch(prefix.to_vec(), 0),
+            _ => key_with_epoch(self.end_user_key.clone(), HummockEpoch::MAX),
+        }
+    }
+}
+
+/// [`DeleteRangeAggregator`] collects the range tombstones of several write batches or SSTs, and
+/// tells whether a key is deleted by them when read at `watermark`.
+///
+/// For reads, `watermark` is the read epoch. For compaction, it's the watermark of the compact
+/// task, below which no one will read any more.
+#[derive(Default, Debug)]
+pub struct DeleteRangeAggregator {
+    /// Sorted by `start_user_key`.
+    tombstones: Vec<DeleteRangeTombstone>,
+    watermark: HummockEpoch,
+    gc_delete_keys: bool,
+    /// Versions written no later than `min_epoch` are expired by the TTL of the table. Only set
+    /// for reads, since compaction relies on the compaction filter instead.
+    min_epoch: Option<HummockEpoch>,
+}
+
+impl DeleteRangeAggregator {
+    pub fn new(watermark: HummockEpoch, gc_delete_keys: bool) -> Self {
+        Self {
+            tombstones: vec![],
+            watermark,
+            gc_delete_keys,
+            min_epoch: None,
+        }
+    }
+
+    /// Treats the versions written no later than `min_epoch` as deleted.
+    pub fn with_min_epoch(mut self, min_epoch: HummockEpoch) -> Self {
+        self.min_epoch = Some(min_epoch);
+        self
+    }
+
+    pub fn add_tombstones(&mut self, tombstones: impl IntoIterator<Item = DeleteRangeTombstone>) {
+        let len = self.tombstones.len();
+        self.tombstones.extend(tombstones);
+        if self.tombstones.len() != len {
+            self.tombstones
+                .sort_by(|a, b| a.start_user_key.cmp(&b.start_user_key));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    /// Returns whether the version of `user_key` written in `epoch` is deleted by any tombstone
+    /// visible at `watermark`, or is expired.
+    pub fn should_delete(&self, user_key: &[u8], epoch: HummockEpoch) -> bool {
+        if self.min_epoch.map_or(false, |min_epoch| epoch <= min_epoch) {
+            return true;
+        }
+        let idx = self
+            .tombstones
+            .partition_point(|tombstone| tombstone.start_user_key.as_slice() <= user_key);
+        self.tombstones[..idx].iter().any(|tombstone| {
+            epoch < tombstone.sequence
+                && tombstone.sequence <= self.watermark
+                && user_key < &tombstone.end_user_key[..]
+        })
+    }
+
+    /// Returns the tombstones overlapping with `[start_user_key, end_user_key)`, cut to the range.
+    /// Empty bound means unbounded.
+    ///
+    /// If `gc_delete_keys` is set, i.e. the output is in the bottom level, all the keys covered by
+    /// the tombstones below `watermark` have been dropped, so these tombstones are not needed any
+    /// more.
+    pub fn get_tombstone_between(
+        &self,
+        start_user_key: &[u8],
+        end_user_key: &[u8],
+    ) -> Vec<DeleteRangeTombstone> {
+        self.tombstones
+            .iter()
+            .filter(|tombstone| !self.gc_delete_keys || tombstone.sequence > self.watermark)
+            .filter_map(|tombstone| {
+                let start = std::cmp::max(&tombstone.start_user_key[..], start_user_key);
+                let end = if end_user_key.is_empty() {
+                    &tombstone.end_user_key[..]
+                } else {
+                    std::cmp::min(&tombstone.end_user_key[..], end_user_key)
+                };
+                (start < end).then(|| {
+                    DeleteRangeTombstone::new(start.to_vec(), end.to_vec(), tombstone.sequence)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_range_aggregator() {
+        let mut agg = DeleteRangeAggregator::new(10, false);
+        agg.add_tombstones(vec![
+            DeleteRangeTombstone::new(b"bbb".to_vec(), b"ddd".to_vec(), 5),
+            DeleteRangeTombstone::new(b"aaa".to_vec(), b"ccc".to_vec(), 8),
+            // Not visible at the watermark.
+            DeleteRangeTombstone::new(b"eee".to_vec(), b"fff".to_vec(), 12),
+        ]);
+
+        assert!(agg.should_delete(b"aaa", 7));
+        assert!(!agg.should_delete(b"aaa", 8));
+        assert!(agg.should_delete(b"bbb", 4));
+        assert!(agg.should_delete(b"ccc", 4));
+        assert!(!agg.should_delete(b"ccc", 5));
+        assert!(!agg.should_delete(b"ddd", 1));
+        assert!(!agg.should_delete(b"eee", 1));
+
+        assert_eq!(
+            agg.get_tombstone_between(b"bbc", b""),
+            vec![
+                DeleteRangeTombstone::new(b"bbc".to_vec(), b"ccc".to_vec(), 8),
+                DeleteRangeTombstone::new(b"bbc".to_vec(), b"ddd".to_vec(), 5),
+                DeleteRangeTombstone::new(b"eee".to_vec(), b"fff".to_vec(), 12),
+            ]
+        );
+        assert_eq!(
+            agg.get_tombstone_between(b"", b"abc"),
+            vec![DeleteRangeTombstone::new(
+                b"aaa".to_vec(),
+                b"abc".to_vec(),
+                8
+            )]
+        );
+
+        assert_eq!(
+            agg.get_tombstone_between(b"ddd", b"zzz"),
+            vec![DeleteRangeTombstone::new(
+                b"eee".to_vec(),
+                b"fff".to_vec(),
+                12
+            )]
+        );
+
+        let agg = DeleteRangeAggregator::new(10, false).with_min_epoch(3);
+        assert!(agg.should_delete(b"aaa", 3));
+        assert!(!agg.should_delete(b"aaa", 4));
+
+        let mut agg = DeleteRangeAggregator::new(10, true);
+        agg.add_tombstones(vec![
+            DeleteRangeTombstone::new(b"aaa".to_vec(), b"ccc".to_vec(), 8),
+            DeleteRangeTombstone::new(b"eee".to_vec(), b"fff".to_vec(), 12),
+        ]);
+        assert_eq!(
+            agg.get_tombstone_between(b"", b""),
+            vec![DeleteRangeTombstone::new(
+                b"eee".to_vec(),
+                b"fff".to_vec(),
+                12
+            )]
+        );
+    }
+
+    #[test]
+    fn test_delete_range_tombstone_enc_dec() {
+        let tombstone = DeleteRangeTombstone::new(b"aaa".to_vec(), b"bbbb".to_vec(), 233);
+        let mut buf = vec![];
+        tombstone.encode(&mut buf);
+        assert_eq!(buf.len(), tombstone.encoded_size());
+        assert_eq!(DeleteRangeTombstone::decode(&mut &buf[..]), tombstone);
+    }
+}
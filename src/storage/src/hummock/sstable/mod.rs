@@ -26,6 +26,8 @@ mod bloom;
 use bloom::Bloom;
 pub mod builder;
 pub use builder::*;
+mod delete_range_aggregator;
+pub use delete_range_aggregator::*;
 pub mod writer;
 pub use writer::*;
 mod forward_sstable_iterator;
@@ -50,7 +52,9 @@ use super::{HummockError, HummockResult};
 
 const DEFAULT_META_BUFFER_CAPACITY: usize = 4096;
 const MAGIC: u32 = 0x5785ab73;
-const VERSION: u32 = 1;
+const VERSION: u32 = 2;
+/// SSTs written before range tombstones were introduced, which can still be read.
+const LEGACY_VERSION: u32 = 1;
 
 /// [`Sstable`] is a handle for accessing SST.
 #[derive(Clone)]
@@ -115,6 +119,7 @@ impl Sstable {
             stale_key_count: 0,
             total_key_count: self.meta.key_count as u64,
             divide_version: 0,
+            range_tombstone_count: self.meta.range_tombstone_list.len() as u64,
         }
     }
 }
@@ -168,6 +173,8 @@ pub struct SstableMeta {
     pub smallest_key: Vec<u8>,
     pub largest_key: Vec<u8>,
     pub meta_offset: u64,
+    /// Range tombstones written by `delete_range`. Only available since format version 2.
+    pub range_tombstone_list: Vec<DeleteRangeTombstone>,
     /// Format version, for further compatibility.
     pub version: u32,
 }
@@ -181,7 +188,8 @@ impl SstableMeta {
     /// | bloom filter len (4B) | bloom filter |
     /// | estimated size (4B) | key count (4B) |
     /// | smallest key len (4B) | smallest key |
-    /// | largest key len (4B) | largest key |
+    /// | largest key len (4B) | largest key | meta offset (8B) |
+    /// | M (4B) | range tombstone 0 | ... | range tombstone M-1 |
     /// | checksum (8B) | version (4B) | magic (4B) |
     /// ```
     pub fn encode_to_bytes(&self) -> Vec<u8> {
@@ -202,6 +210,10 @@ impl SstableMeta {
         put_length_prefixed_slice(buf, &self.smallest_key);
         put_length_prefixed_slice(buf, &self.largest_key);
         buf.put_u64_le(self.meta_offset);
+        buf.put_u32_le(self.range_tombstone_list.len() as u32);
+        for tombstone in &self.range_tombstone_list {
+            tombstone.encode(buf);
+        }
         let checksum = xxhash64_checksum(&buf[start_offset..]);
         buf.put_u64_le(checksum);
         buf.put_u32_le(VERSION);
@@ -219,7 +231,7 @@ impl SstableMeta {
 
         cursor -= 4;
         let version = (&buf[cursor..cursor + 4]).get_u32_le();
-        if version != VERSION {
+        if version != VERSION && version != LEGACY_VERSION {
             return Err(HummockError::invalid_format_version(version));
         }
 
@@ -239,6 +251,14 @@ impl SstableMeta {
         let smallest_key = get_length_prefixed_slice(buf);
         let largest_key = get_length_prefixed_slice(buf);
         let meta_offset = buf.get_u64_le();
+        let range_tombstone_list = if version != LEGACY_VERSION {
+            let tombstone_count = buf.get_u32_le() as usize;
+            (0..tombstone_count)
+                .map(|_| DeleteRangeTombstone::decode(buf))
+                .collect()
+        } else {
+            vec![]
+        };
 
         Ok(Self {
             block_metas,
@@ -248,6 +268,7 @@ impl SstableMeta {
             smallest_key,
             largest_key,
             meta_offset,
+            range_tombstone_list,
             version,
         })
     }
@@ -269,6 +290,12 @@ impl SstableMeta {
             + 4 // key len
             + self.largest_key.len()
             + 8 // footer
+            + 4 // range tombstone count
+            + self
+            .range_tombstone_list
+            .iter()
+            .map(|tombstone| tombstone.encoded_size())
+            .sum::<usize>()
             + 8 // checksum
             + 4 // version
             + 4 // magic
@@ -307,6 +334,11 @@ mod tests {
             smallest_key: b"0-smallest-key".to_vec(),
             largest_key: b"9-largest-key".to_vec(),
             meta_offset: 123,
+            range_tombstone_list: vec![DeleteRangeTombstone::new(
+                b"1-start-key".to_vec(),
+                b"3-end-key".to_vec(),
+                233,
+            )],
             version: VERSION,
         };
         let sz = meta.encoded_size();
@@ -16,7 +16,7 @@ use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering::SeqCst;
 use std::sync::Arc;
 
-use risingwave_hummock_sdk::key::FullKey;
+use risingwave_hummock_sdk::key::{user_key, FullKey};
 use risingwave_hummock_sdk::HummockEpoch;
 use risingwave_pb::hummock::SstableInfo;
 use tokio::task::JoinHandle;
@@ -25,8 +25,8 @@ use crate::hummock::compactor::task_progress::TaskProgress;
 use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::value::HummockValue;
 use crate::hummock::{
-    BatchUploadWriter, CachePolicy, HummockResult, MemoryLimiter, SstableBuilder,
-    SstableBuilderOptions, SstableWriter, SstableWriterOptions,
+    BatchUploadWriter, CachePolicy, DeleteRangeAggregator, HummockResult, MemoryLimiter,
+    SstableBuilder, SstableBuilderOptions, SstableWriter, SstableWriterOptions,
 };
 use crate::monitor::StateStoreMetrics;
 
@@ -63,6 +63,13 @@ where
 
     /// Update the number of sealed Sstables.
     task_progress: Option<Arc<TaskProgress>>,
+
+    /// Range tombstones to be split into the tables along with the keys.
+    del_agg: Arc<DeleteRangeAggregator>,
+
+    /// The exclusive upper bound of user keys in the last sealed table, which is also the lower
+    /// bound of range tombstones in the current table. Empty means unbounded.
+    last_sealed_key: Vec<u8>,
 }
 
 impl<F> CapacitySplitTableBuilder<F>
@@ -74,6 +81,7 @@ where
         builder_factory: F,
         stats: Arc<StateStoreMetrics>,
         task_progress: Option<Arc<TaskProgress>>,
+        del_agg: Arc<DeleteRangeAggregator>,
     ) -> Self {
         Self {
             builder_factory,
@@ -81,6 +89,8 @@ where
             current_builder: None,
             stats,
             task_progress,
+            del_agg,
+            last_sealed_key: vec![],
         }
     }
 
@@ -91,6 +101,8 @@ where
             current_builder: None,
             stats: Arc::new(StateStoreMetrics::unused()),
             task_progress: None,
+            del_agg: Arc::new(DeleteRangeAggregator::default()),
+            last_sealed_key: vec![],
         }
     }
 
@@ -155,7 +167,27 @@ where
     /// If there's no builder created, or current one is already sealed before, then this function
     /// will be no-op.
     pub async fn seal_current(&mut self) -> HummockResult<()> {
-        if let Some(builder) = self.current_builder.take() {
+        self.seal_current_impl(false).await
+    }
+
+    /// Seals the current builder. Range tombstones are cut by the last user key of the table, so
+    /// that the tables don't overlap with each other. The last table takes all the remaining ones.
+    async fn seal_current_impl(&mut self, is_last: bool) -> HummockResult<()> {
+        if let Some(mut builder) = self.current_builder.take() {
+            if !self.del_agg.is_empty() {
+                let upper_bound = if is_last {
+                    vec![]
+                } else {
+                    let mut key = user_key(builder.last_full_key()).to_vec();
+                    key.push(0);
+                    key
+                };
+                builder.add_delete_range(
+                    self.del_agg
+                        .get_tombstone_between(&self.last_sealed_key, &upper_bound),
+                );
+                self.last_sealed_key = upper_bound;
+            }
             let builder_output = builder.finish().await?;
 
             {
@@ -200,7 +232,17 @@ where
 
     /// Finalizes all the tables to be ids, blocks and metadata.
     pub async fn finish(mut self) -> HummockResult<Vec<SplitTableOutput>> {
-        self.seal_current().await?;
+        // The remaining range tombstones need a table even if there's no key left.
+        if self.current_builder.is_none()
+            && !self
+                .del_agg
+                .get_tombstone_between(&self.last_sealed_key, &[])
+                .is_empty()
+        {
+            let builder = self.builder_factory.open_builder().await?;
+            self.current_builder = Some(builder);
+        }
+        self.seal_current_impl(true).await?;
         Ok(self.sst_outputs)
     }
 }
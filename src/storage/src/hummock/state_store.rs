@@ -35,8 +35,8 @@ use super::iterator::{
 use super::utils::{search_sst_idx, validate_epoch};
 use super::{
     get_from_order_sorted_uncommitted_data, get_from_sstable_info, hit_sstable_bloom_filter,
-    BackwardSstableIterator, HummockStorage, HummockStorageIterator, SstableIterator,
-    SstableIteratorType,
+    BackwardSstableIterator, DeleteRangeAggregator, HummockStorage, HummockStorageIterator,
+    SstableIterator, SstableIteratorType,
 };
 use crate::error::StorageResult;
 use crate::hummock::iterator::{
@@ -126,6 +126,10 @@ impl HummockStorage {
 
         let mut table_counts = 0;
         let internal_key = key_with_epoch(key.to_vec(), epoch);
+        let mut del_agg = DeleteRangeAggregator::new(epoch, false);
+        if read_options.retention_seconds.is_some() {
+            del_agg = del_agg.with_min_epoch(read_options.min_epoch());
+        }
 
         // Query shared buffer. Return the value without iterating SSTs if found
         for uncommitted_data in shared_buffer_data {
@@ -137,6 +141,7 @@ impl HummockStorage {
                 &mut local_stats,
                 key,
                 check_bloom_filter,
+                &mut del_agg,
             )
             .await?;
             if let Some(v) = value {
@@ -153,6 +158,7 @@ impl HummockStorage {
                 &mut local_stats,
                 key,
                 check_bloom_filter,
+                &mut del_agg,
             )
             .await?;
             if let Some(v) = value {
@@ -181,6 +187,7 @@ impl HummockStorage {
                             &internal_key,
                             check_bloom_filter,
                             &mut local_stats,
+                            &mut del_agg,
                         )
                         .await?
                         {
@@ -219,6 +226,7 @@ impl HummockStorage {
                         &internal_key,
                         check_bloom_filter,
                         &mut local_stats,
+                        &mut del_agg,
                     )
                     .await?
                     {
@@ -505,6 +513,14 @@ impl StateStore for HummockStorage {
         self.storage_core.ingest_batch(kv_pairs, write_options)
     }
 
+    fn delete_range(
+        &self,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        self.storage_core.delete_range(delete_ranges, write_options)
+    }
+
     /// Returns an iterator that scan from the begin key to the end key
     /// The result is based on a snapshot corresponding to the given `epoch`.
     fn iter<R, B>(
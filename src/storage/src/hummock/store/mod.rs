@@ -41,6 +41,7 @@ macro_rules! define_local_state_store_associated_type {
         type GetFuture<'a> = impl GetFutureTrait<'a>;
         type IterFuture<'a> = impl IterFutureTrait<'a, Self::Iter>;
         type IngestKVBatchFuture<'a> = impl IngestKVBatchFutureTrait<'a>;
+        type DeleteRangeFuture<'a> = impl IngestKVBatchFutureTrait<'a>;
     };
 }
 
@@ -56,6 +57,8 @@ pub trait StateStore: Send + Sync + 'static + Clone {
 
     type IngestKVBatchFuture<'a>: IngestKVBatchFutureTrait<'a>;
 
+    type DeleteRangeFuture<'a>: IngestKVBatchFutureTrait<'a>;
+
     /// Point gets a value from the state store.
     /// The result is based on a snapshot corresponding to the given `epoch`.
     fn get<'a>(
@@ -95,6 +98,14 @@ pub trait StateStore: Send + Sync + 'static + Clone {
         kv_pairs: Vec<(Bytes, StorageValue)>,
         write_options: WriteOptions,
     ) -> Self::IngestKVBatchFuture<'_>;
+
+    /// Deletes all the keys in `[start, end)` of each range with epoch smaller than the given
+    /// `epoch`, by writing range tombstones.
+    fn delete_range(
+        &self,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_>;
 }
 
 #[derive(Default, Clone)]
@@ -105,7 +116,7 @@ pub struct ReadOptions {
     pub prefix_hint: Option<Vec<u8>>,
     pub check_bloom_filter: bool,
 
-    // TODO: support min_epoch
+    /// Versions older than `retention_seconds` before the read epoch are invisible.
     pub retention_seconds: Option<u32>,
     pub table_id: TableId,
 }
@@ -115,7 +126,7 @@ pub fn gen_min_epoch(base_epoch: u64, retention_seconds: Option<&u32>) -> u64 {
     match retention_seconds {
         Some(retention_seconds_u32) => {
             base_epoch
-                .subtract_ms((retention_seconds_u32 * 1000) as u64)
+                .subtract_ms(*retention_seconds_u32 as u64 * 1000)
                 .0
         }
         None => 0,
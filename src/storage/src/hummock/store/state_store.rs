@@ -50,8 +50,8 @@ use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::utils::{prune_ssts, search_sst_idx, validate_epoch};
 use crate::hummock::{
-    get_from_batch, get_from_sstable_info, hit_sstable_bloom_filter, HummockResult, MemoryLimiter,
-    SstableIdManager, SstableIdManagerRef, SstableIterator,
+    get_from_batch, get_from_sstable_info, hit_sstable_bloom_filter, DeleteRangeAggregator,
+    HummockResult, MemoryLimiter, SstableIdManager, SstableIdManagerRef, SstableIterator,
 };
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 use crate::storage_value::StorageValue;
@@ -179,12 +179,20 @@ impl HummockStorageCore {
 
         let mut table_counts = 0;
         let internal_key = key_with_epoch(key.to_vec(), epoch);
+        let mut del_agg = DeleteRangeAggregator::new(epoch, false);
+        if read_options.retention_seconds.is_some() {
+            // Expired rows should not be visible even if they have not been compacted yet.
+            del_agg = del_agg.with_min_epoch(gen_min_epoch(
+                epoch,
+                read_options.retention_seconds.as_ref(),
+            ));
+        }
         let mut local_stats = StoreLocalStatistic::default();
 
         // 1. read staging data
         // 2. order guarantee: imm -> sst
         for imm in staging_imm {
-            if let Some(data) = get_from_batch(&imm, key, &mut local_stats) {
+            if let Some(data) = get_from_batch(&imm, key, &mut local_stats, &mut del_agg) {
                 return Ok(data.into_user_value());
             }
         }
@@ -198,6 +206,7 @@ impl HummockStorageCore {
                 &internal_key,
                 read_options.check_bloom_filter,
                 &mut local_stats,
+                &mut del_agg,
             )
             .await?
             {
@@ -226,6 +235,7 @@ impl HummockStorageCore {
                             &internal_key,
                             read_options.check_bloom_filter,
                             &mut local_stats,
+                            &mut del_agg,
                         )
                         .await?
                         {
@@ -265,6 +275,7 @@ impl HummockStorageCore {
                         &internal_key,
                         read_options.check_bloom_filter,
                         &mut local_stats,
+                        &mut del_agg,
                     )
                     .await?
                     {
@@ -307,6 +318,12 @@ impl HummockStorageCore {
         };
 
         let mut local_stats = StoreLocalStatistic::default();
+        // Range tombstones are collected from all the data overlapping with `key_range`, regardless
+        // of the bloom filter.
+        let mut del_agg = DeleteRangeAggregator::new(epoch, false);
+        for imm in &imms {
+            del_agg.add_tombstones(imm.get_delete_range_tombstones().iter().cloned());
+        }
         let mut staging_iters = Vec::with_capacity(imms.len() + uncommitted_ssts.len());
         self.stats
             .iter_merge_sstable_counts
@@ -323,6 +340,14 @@ impl HummockStorageCore {
                 .sstable(&sstable_info, &mut local_stats)
                 .in_span(Span::enter_with_local_parent("get_sstable"))
                 .await?;
+            del_agg.add_tombstones(
+                table_holder
+                    .value()
+                    .meta
+                    .range_tombstone_list
+                    .iter()
+                    .cloned(),
+            );
             if let Some(prefix) = read_options.prefix_hint.as_ref() {
                 if !hit_sstable_bloom_filter(table_holder.value(), prefix, &mut local_stats) {
                     continue;
@@ -351,6 +376,17 @@ impl HummockStorageCore {
             if table_infos.is_empty() {
                 continue;
             }
+            for table_info in table_infos
+                .iter()
+                .filter(|table_info| table_info.range_tombstone_count > 0)
+            {
+                let sstable = self
+                    .sstable_store
+                    .sstable(table_info, &mut local_stats)
+                    .in_span(Span::enter_with_local_parent("get_sstable"))
+                    .await?;
+                del_agg.add_tombstones(sstable.value().meta.range_tombstone_list.iter().cloned());
+            }
 
             if level.level_type == LevelType::Nonoverlapping as i32 {
                 debug_assert!(can_concat(&table_infos));
@@ -447,7 +483,8 @@ impl HummockStorageCore {
         // the epoch_range left bound for iterator read
         let min_epoch = gen_min_epoch(epoch, read_options.retention_seconds.as_ref());
         let mut user_iter =
-            UserIterator::new(merge_iter, key_range, epoch, min_epoch, Some(committed));
+            UserIterator::new(merge_iter, key_range, epoch, min_epoch, Some(committed))
+                .with_delete_range_aggregator(del_agg);
         user_iter
             .rewind()
             .in_span(Span::enter_with_local_parent("rewind"))
@@ -529,6 +566,33 @@ impl StateStore for HummockStorage {
             Ok(imm_size)
         }
     }
+
+    fn delete_range(
+        &self,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            let imm = SharedBufferBatch::build_delete_range_batch(
+                write_options.epoch,
+                delete_ranges,
+                write_options.table_id,
+                Some(self.core.memory_limiter.as_ref()),
+            )
+            .await;
+            let imm_size = imm.size();
+            self.core
+                .update(VersionUpdate::Staging(StagingData::ImmMem(imm.clone())));
+
+            // insert imm to uploader
+            self.core
+                .event_sender
+                .send(HummockEvent::ImmToUploader(imm))
+                .unwrap();
+
+            Ok(imm_size)
+        }
+    }
 }
 
 impl HummockStorage {
@@ -104,6 +104,7 @@ pub fn gen_dummy_sst_info(id: HummockSstableId, batches: Vec<SharedBufferBatch>)
         stale_key_count: 0,
         total_key_count: 0,
         divide_version: 0,
+        range_tombstone_count: 0,
     }
 }
 
@@ -176,6 +177,7 @@ pub async fn put_sst(
         stale_key_count: 0,
         total_key_count: 0,
         divide_version: 0,
+        range_tombstone_count: 0,
     };
     let writer_output = writer.finish(meta).await?;
     writer_output.await.unwrap()?;
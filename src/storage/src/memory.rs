@@ -21,6 +21,7 @@ use std::ops::{Bound, RangeBounds};
 use std::sync::{Arc, LazyLock};
 
 use bytes::Bytes;
+use itertools::Itertools;
 use parking_lot::RwLock;
 use risingwave_hummock_sdk::HummockReadEpoch;
 
@@ -292,6 +293,32 @@ impl StateStore for MemoryStateStore {
         }
     }
 
+    fn delete_range(
+        &self,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            let epoch = write_options.epoch;
+            let mut inner = self.inner.write();
+            let mut size: usize = 0;
+            for (start, end) in delete_ranges {
+                size += start.len() + end.len();
+                // Keys written in `epoch` are not affected by the deletion.
+                let deleted_keys = inner
+                    .range(to_bytes_range(start..end))
+                    .map(|((key, _), _)| key.clone())
+                    .dedup()
+                    .filter(|key| !inner.contains_key(&(key.clone(), Reverse(epoch))))
+                    .collect_vec();
+                for key in deleted_keys {
+                    inner.insert((key, Reverse(epoch)), None);
+                }
+            }
+            Ok(size)
+        }
+    }
+
     fn iter<R, B>(
         &self,
         _prefix_hint: Option<Vec<u8>>,
@@ -204,6 +204,24 @@ where
         }
     }
 
+    fn delete_range(
+        &self,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            if delete_ranges.is_empty() {
+                return Ok(0);
+            }
+
+            self.inner
+                .delete_range(delete_ranges, write_options)
+                .verbose_stack_trace("store_delete_range")
+                .await
+                .inspect_err(|e| error!("Failed in delete_range: {:?}", e))
+        }
+    }
+
     fn iter<R, B>(
         &self,
         prefix_hint: Option<Vec<u8>>,
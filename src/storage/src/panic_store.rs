@@ -84,6 +84,16 @@ impl StateStore for PanicStateStore {
         }
     }
 
+    fn delete_range(
+        &self,
+        _delete_ranges: Vec<(Bytes, Bytes)>,
+        _write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            panic!("should not write the state store!");
+        }
+    }
+
     fn iter<R, B>(
         &self,
         _prefix_hint: Option<Vec<u8>>,
@@ -45,6 +45,7 @@ macro_rules! define_state_store_associated_type {
     () => {
         type GetFuture<'a> = impl GetFutureTrait<'a>;
         type IngestBatchFuture<'a> = impl IngestBatchFutureTrait<'a>;
+        type DeleteRangeFuture<'a> = impl IngestBatchFutureTrait<'a>;
         type WaitEpochFuture<'a> = impl EmptyFutureTrait<'a>;
         type SyncFuture<'a> = impl SyncFutureTrait<'a>;
 
@@ -89,6 +90,8 @@ pub trait StateStore: Send + Sync + 'static + Clone {
 
     type IngestBatchFuture<'a>: IngestBatchFutureTrait<'a>;
 
+    type DeleteRangeFuture<'a>: IngestBatchFutureTrait<'a>;
+
     type WaitEpochFuture<'a>: EmptyFutureTrait<'a>;
 
     type SyncFuture<'a>: SyncFutureTrait<'a>;
@@ -157,6 +160,15 @@ pub trait StateStore: Send + Sync + 'static + Clone {
         write_options: WriteOptions,
     ) -> Self::IngestBatchFuture<'_>;
 
+    /// Deletes all the keys in `[start, end)` of each range with the epoch provided in
+    /// `write_options`, by writing range tombstones to the state store. Only versions written
+    /// before the epoch are deleted, while the ones written in the same epoch are kept.
+    fn delete_range(
+        &self,
+        delete_ranges: Vec<(Bytes, Bytes)>,
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_>;
+
     /// Opens and returns an iterator for given `prefix_hint` and `full_key_range`
     /// Internally, `prefix_hint` will be used to for checking `bloom_filter` and
     /// `full_key_range` used for iter. (if the `prefix_hint` not None, it should be be included in
@@ -236,7 +248,7 @@ impl ReadOptions {
         let epoch = Epoch(self.epoch);
         match self.retention_seconds.as_ref() {
             Some(retention_seconds_u32) => {
-                epoch.subtract_ms((retention_seconds_u32 * 1000) as u64).0
+                epoch.subtract_ms(*retention_seconds_u32 as u64 * 1000).0
             }
             None => 0,
         }
@@ -98,7 +98,8 @@ impl<S: StateStore> std::fmt::Debug for StorageTable<S> {
 impl<S: StateStore> StorageTable<S> {
     /// Create a  [`StorageTable`] given a complete set of `columns` and a partial
     /// set of `column_ids`. The output will only contains columns with the given ids in the same
-    /// order.
+    /// order. `appended_value_defaults` are the defaults of the trailing value columns appended by
+    /// `ALTER TABLE ADD COLUMN`.
     #[allow(clippy::too_many_arguments)]
     pub fn new_partial(
         store: S,
@@ -110,6 +111,7 @@ impl<S: StateStore> StorageTable<S> {
         distribution: Distribution,
         table_options: TableOption,
         value_indices: Vec<usize>,
+        appended_value_defaults: Vec<Datum>,
     ) -> Self {
         Self::new_inner(
             store,
@@ -121,6 +123,7 @@ impl<S: StateStore> StorageTable<S> {
             distribution,
             table_options,
             value_indices,
+            appended_value_defaults,
         )
     }
 
@@ -143,6 +146,7 @@ impl<S: StateStore> StorageTable<S> {
             Distribution::fallback(),
             Default::default(),
             value_indices,
+            vec![],
         )
     }
 }
@@ -162,6 +166,7 @@ impl<S: StateStore> StorageTable<S> {
         }: Distribution,
         table_option: TableOption,
         value_indices: Vec<usize>,
+        appended_value_defaults: Vec<Datum>,
     ) -> Self {
         assert_eq!(order_types.len(), pk_indices.len());
 
@@ -179,7 +184,8 @@ impl<S: StateStore> StorageTable<S> {
             .collect();
         let all_data_types = table_columns.iter().map(|d| d.data_type.clone()).collect();
         let pk_serializer = OrderedRowSerde::new(pk_data_types, order_types);
-        let row_deserializer = RowDeserializer::new(all_data_types);
+        let row_deserializer =
+            RowDeserializer::with_appended_defaults(all_data_types, appended_value_defaults);
 
         let dist_key_in_pk_indices = dist_key_indices
             .iter()
@@ -306,6 +306,7 @@ async fn test_row_based_storage_table_point_get_in_batch_mode() {
         Distribution::fallback(),
         TableOption::default(),
         value_indices,
+        vec![],
     );
     let epoch = EpochPair::new_test_epoch(1);
     state.init_epoch(epoch);
@@ -387,6 +388,7 @@ async fn test_row_based_storage_table_scan_in_batch_mode() {
         Distribution::fallback(),
         TableOption::default(),
         value_indices,
+        vec![],
     );
     let epoch = EpochPair::new_test_epoch(1);
     state.init_epoch(epoch);
@@ -26,8 +26,8 @@ use futures_async_stream::try_stream;
 use itertools::{izip, Itertools};
 use risingwave_common::array::{Op, Row, RowDeserializer, StreamChunk, Vis};
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::catalog::{ColumnDesc, TableId, TableOption};
-use risingwave_common::types::VirtualNode;
+use risingwave_common::catalog::{column_default_from_protobuf, ColumnDesc, TableId, TableOption};
+use risingwave_common::types::{ScalarImpl, VirtualNode};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::ordered::OrderedRowSerde;
 use risingwave_common::util::sort_util::OrderType;
@@ -100,6 +100,10 @@ pub struct StateTable<S: StateStore> {
 
     /// the epoch flush to the state store last time
     epoch: Option<EpochPair>,
+
+    /// The watermark of the first primary key column, below which the rows will be cleaned on
+    /// the next commit.
+    pending_watermark: Option<ScalarImpl>,
 }
 
 // initialize
@@ -178,6 +182,32 @@ impl<S: StateStore> StateTable<S> {
                 let vnode_col_idx = vnode_col_idx.index as usize;
                 pk_indices.iter().position(|&i| vnode_col_idx == i)
             });
+        let (row_deserializer, value_indices) =
+            Self::derive_value_indices(table_catalog, &table_columns);
+        Self {
+            mem_table: MemTable::new(),
+            keyspace,
+            pk_serde,
+            row_deserializer,
+            pk_indices: pk_indices.to_vec(),
+            dist_key_indices,
+            dist_key_in_pk_indices,
+            vnodes,
+            table_option: TableOption::build_table_option(table_catalog.get_properties()),
+            disable_sanity_check: false,
+            vnode_col_idx_in_pk,
+            value_indices,
+            epoch: None,
+            pending_watermark: None,
+        }
+    }
+
+    /// Returns the deserializer of the value columns and the value indices, which is `None` if all
+    /// columns are stored in order.
+    fn derive_value_indices(
+        table_catalog: &Table,
+        table_columns: &[ColumnDesc],
+    ) -> (RowDeserializer, Option<Vec<usize>>) {
         let input_value_indices = table_catalog
             .value_indices
             .iter()
@@ -189,6 +219,14 @@ impl<S: StateStore> StateTable<S> {
             .map(|idx| table_columns[*idx].data_type.clone())
             .collect();
 
+        // The trailing value columns with defaults are appended by `ALTER TABLE ADD COLUMN`.
+        let mut appended_defaults = input_value_indices
+            .iter()
+            .rev()
+            .map_while(|&idx| column_default_from_protobuf(&table_catalog.columns[idx]))
+            .collect_vec();
+        appended_defaults.reverse();
+
         let no_shuffle_value_indices = (0..table_columns.len()).collect_vec();
 
         // if value_indices is the no shuffle full columns and
@@ -198,21 +236,10 @@ impl<S: StateStore> StateTable<S> {
             true => None,
             false => Some(input_value_indices),
         };
-        Self {
-            mem_table: MemTable::new(),
-            keyspace,
-            pk_serde,
-            row_deserializer: RowDeserializer::new(data_types),
-            pk_indices: pk_indices.to_vec(),
-            dist_key_indices,
-            dist_key_in_pk_indices,
-            vnodes,
-            table_option: TableOption::build_table_option(table_catalog.get_properties()),
-            disable_sanity_check: false,
-            vnode_col_idx_in_pk,
+        (
+            RowDeserializer::with_appended_defaults(data_types, appended_defaults),
             value_indices,
-            epoch: None,
-        }
+        )
     }
 
     /// Create a state table without distribution, used for unit tests.
@@ -309,6 +336,7 @@ impl<S: StateStore> StateTable<S> {
             vnode_col_idx_in_pk: None,
             value_indices: Some(value_indices),
             epoch: None,
+            pending_watermark: None,
         }
     }
 
@@ -317,7 +345,7 @@ impl<S: StateStore> StateTable<S> {
         self.disable_sanity_check = true;
     }
 
-    fn table_id(&self) -> TableId {
+    pub fn table_id(&self) -> TableId {
         self.keyspace.table_id()
     }
 
@@ -367,6 +395,11 @@ impl<S: StateStore> StateTable<S> {
         &self.pk_indices
     }
 
+    /// Get the vnodes owned by the state table.
+    pub fn vnodes(&self) -> &Arc<Bitmap> {
+        &self.vnodes
+    }
+
     pub fn pk_serde(&self) -> &OrderedRowSerde {
         &self.pk_serde
     }
@@ -447,6 +480,25 @@ impl<S: StateStore> StateTable<S> {
 
         std::mem::replace(&mut self.vnodes, new_vnodes)
     }
+
+    /// Update the columns of the state table with the altered table catalog. Only appending new
+    /// columns is supported, so the primary key and the distribution are kept unchanged. Rows
+    /// written before will be read with the defaults of the appended columns.
+    pub fn update_columns(&mut self, table_catalog: &Table) {
+        assert!(
+            !self.is_dirty(),
+            "columns should only be updated when state table is clean"
+        );
+        let table_columns: Vec<ColumnDesc> = table_catalog
+            .columns
+            .iter()
+            .map(|col| col.column_desc.as_ref().unwrap().into())
+            .collect();
+        let (row_deserializer, value_indices) =
+            Self::derive_value_indices(table_catalog, &table_columns);
+        self.row_deserializer = row_deserializer;
+        self.value_indices = value_indices;
+    }
 }
 
 // write
@@ -576,6 +628,41 @@ impl<S: StateStore> StateTable<S> {
         Ok(())
     }
 
+    /// Update the watermark of the first primary key column. All rows whose first primary key
+    /// column is less than the watermark will be cleaned on the next commit, which is only
+    /// supported when the column is in ascending order.
+    ///
+    /// Note that rows written in the same epoch of the cleaning are kept, and will be cleaned by
+    /// the following watermarks.
+    pub fn update_watermark(&mut self, watermark: ScalarImpl) {
+        trace!(table_id = %self.table_id(), watermark = ?watermark, "update watermark");
+        self.pending_watermark = Some(watermark);
+    }
+
+    /// Delete all the rows in the vnodes owned by the state table with range tombstones in the
+    /// current epoch, which is used for `TRUNCATE`. Rows written after the call in the same epoch
+    /// are kept.
+    pub async fn truncate(&mut self) -> StorageResult<()> {
+        assert!(
+            !self.is_dirty(),
+            "state table should be clean when truncated"
+        );
+        let mut write_batch = self.keyspace.start_write_batch(WriteOptions {
+            epoch: self.epoch(),
+            table_id: self.table_id(),
+        });
+        for vnode in self
+            .vnodes
+            .iter()
+            .enumerate()
+            .filter_map(|(vnode, owned)| owned.then_some(vnode as VirtualNode))
+        {
+            write_batch.delete_prefix([vnode]);
+        }
+        write_batch.ingest().await?;
+        Ok(())
+    }
+
     /// used for unit test, and do not need to assert epoch.
     pub async fn commit_for_test(&mut self, new_epoch: EpochPair) -> StorageResult<()> {
         let mem_table = std::mem::take(&mut self.mem_table).into_parts();
@@ -628,6 +715,23 @@ impl<S: StateStore> StateTable<S> {
                 }
             }
         }
+        if let Some(watermark) = self.pending_watermark.take() {
+            if self.pk_serde.get_order_types().first() == Some(&OrderType::Ascending) {
+                let prefix_serializer = self.pk_serde.prefix(1);
+                let watermark = serialize_pk(&Row::new(vec![Some(watermark)]), &prefix_serializer);
+                for vnode in self
+                    .vnodes
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(vnode, owned)| owned.then_some(vnode as VirtualNode))
+                {
+                    // `[vnode]` is less than any key under the vnode.
+                    let start = vec![vnode];
+                    let end = [&[vnode][..], &watermark].concat();
+                    write_batch.delete_range(start, end);
+                }
+            }
+        }
         write_batch.ingest().await?;
         Ok(())
     }
@@ -660,6 +764,23 @@ impl<S: StateStore> StateTable<S> {
         Ok(())
     }
 
+    /// Whether the value in storage matches the expected one. The values are compared after
+    /// deserialization if the bytes differ, since rows written before `ALTER TABLE ADD COLUMN` are
+    /// shorter than the ones with the appended columns filled with their defaults.
+    fn stored_value_matches(&self, stored_value: Option<&[u8]>, expected: &[u8]) -> bool {
+        match stored_value {
+            None => false,
+            Some(stored_value) if stored_value == expected => true,
+            Some(stored_value) => matches!(
+                (
+                    self.row_deserializer.deserialize(stored_value),
+                    self.row_deserializer.deserialize(expected),
+                ),
+                (Ok(stored_row), Ok(expected_row)) if stored_row == expected_row
+            ),
+        }
+    }
+
     /// Make sure that the key to delete should exist in storage and the value should be matched.
     async fn do_delete_sanity_check(
         &self,
@@ -672,7 +793,7 @@ impl<S: StateStore> StateTable<S> {
             .get(key, false, self.get_read_option(epoch))
             .await?;
 
-        if stored_value.is_none() || stored_value.as_ref().unwrap() != old_row {
+        if !self.stored_value_matches(stored_value.as_deref(), old_row) {
             let (vnode, key) = deserialize_pk_with_vnode(key, &self.pk_serde).unwrap();
             let stored_row =
                 stored_value.map(|bytes| self.row_deserializer.deserialize(bytes).unwrap());
@@ -702,7 +823,7 @@ impl<S: StateStore> StateTable<S> {
             .get(key, false, self.get_read_option(epoch))
             .await?;
 
-        if stored_value.is_none() || stored_value.as_ref().unwrap() != old_row {
+        if !self.stored_value_matches(stored_value.as_deref(), old_row) {
             let (vnode, key) = deserialize_pk_with_vnode(key, &self.pk_serde).unwrap();
             let expected_row = self.row_deserializer.deserialize(old_row).unwrap();
             let stored_row =
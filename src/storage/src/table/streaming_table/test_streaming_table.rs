@@ -1189,3 +1189,54 @@ async fn test_state_table_iter_with_shuffle_value_indices() {
     let res = iter.next().await;
     assert!(res.is_none());
 }
+
+#[tokio::test]
+async fn test_state_table_update_watermark() {
+    let state_store = MemoryStateStore::new();
+    let column_descs = vec![
+        ColumnDesc::unnamed(ColumnId::from(0), DataType::Int64),
+        ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+    ];
+    let order_types = vec![OrderType::Ascending, OrderType::Ascending];
+    let pk_index = vec![0_usize, 1_usize];
+    let mut state_table = StateTable::new_without_distribution(
+        state_store.clone(),
+        TableId::from(0x42),
+        column_descs,
+        order_types,
+        pk_index,
+    );
+
+    let mut epoch = EpochPair::new_test_epoch(1);
+    state_table.init_epoch(epoch);
+
+    let row = |time: i64, value: i32| Row(vec![Some(time.into()), Some(value.into())]);
+    for (time, value) in [(1, 1), (2, 2), (3, 3), (3, 4)] {
+        state_table.insert(row(time, value));
+    }
+    epoch.inc();
+    state_table.commit_for_test(epoch).await.unwrap();
+
+    // Rows whose first pk column is less than the watermark are cleaned, while the ones written
+    // in the same epoch are kept.
+    state_table.update_watermark(3_i64.into());
+    state_table.insert(row(0, 0));
+    epoch.inc();
+    state_table.commit_for_test(epoch).await.unwrap();
+
+    {
+        let iter = state_table.iter().await.unwrap();
+        pin_mut!(iter);
+        let mut rows = vec![];
+        while let Some(row) = iter.next().await {
+            rows.push(row.unwrap().into_owned());
+        }
+        assert_eq!(rows, vec![row(0, 0), row(3, 3), row(3, 4)]);
+    }
+
+    // The watermark is applied only once.
+    state_table.insert(row(1, 1));
+    epoch.inc();
+    state_table.commit_for_test(epoch).await.unwrap();
+    assert!(state_table.get_row(&row(1, 1)).await.unwrap().is_some());
+}
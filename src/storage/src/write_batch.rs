@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use bytes::Bytes;
+use risingwave_hummock_sdk::key::next_key;
 
 use crate::error::StorageResult;
 use crate::hummock::HummockError;
@@ -26,6 +27,8 @@ pub struct WriteBatch<'a, S: StateStore> {
 
     batch: Vec<(Bytes, StorageValue)>,
 
+    delete_ranges: Vec<(Bytes, Bytes)>,
+
     write_options: WriteOptions,
 }
 
@@ -38,6 +41,7 @@ where
         Self {
             store,
             batch: Vec::new(),
+            delete_ranges: Vec::new(),
             write_options,
         }
     }
@@ -47,6 +51,7 @@ where
         Self {
             store,
             batch: Vec::with_capacity(capacity),
+            delete_ranges: Vec::new(),
             write_options,
         }
     }
@@ -84,9 +89,19 @@ where
         self.batch.is_empty()
     }
 
+    /// Deletes all the keys in `[start, end)` written before the epoch of this batch.
+    pub fn delete_range(&mut self, start: impl Into<Bytes>, end: impl Into<Bytes>) {
+        self.delete_ranges.push((start.into(), end.into()));
+    }
+
     /// Ingests this batch into the associated state store.
     pub async fn ingest(mut self) -> StorageResult<()> {
         self.preprocess()?;
+        if !self.delete_ranges.is_empty() {
+            self.store
+                .delete_range(self.delete_ranges, self.write_options.clone())
+                .await?;
+        }
         self.store
             .ingest_batch(self.batch, self.write_options)
             .await?;
@@ -132,6 +147,21 @@ impl<'a, S: StateStore> KeySpaceWriteBatch<'a, S> {
         self.do_push(key.as_ref(), StorageValue::new_delete());
     }
 
+    /// Deletes all the keys in `[start, end)`, with both bounds prepended by the prefix of
+    /// `keyspace`.
+    pub fn delete_range(&mut self, start: impl AsRef<[u8]>, end: impl AsRef<[u8]>) {
+        let start = self.keyspace.prefixed_key(start);
+        let end = self.keyspace.prefixed_key(end);
+        self.global.delete_range(start, end);
+    }
+
+    /// Deletes all the keys with `prefix`, which is prepended by the prefix of `keyspace`.
+    pub fn delete_prefix(&mut self, prefix: impl AsRef<[u8]>) {
+        let start = self.keyspace.prefixed_key(prefix);
+        let end = next_key(&start);
+        self.global.delete_range(start, end);
+    }
+
     pub async fn ingest(self) -> StorageResult<()> {
         self.global.ingest().await
     }
@@ -144,7 +174,7 @@ mod tests {
 
     use crate::memory::MemoryStateStore;
     use crate::storage_value::StorageValue;
-    use crate::store::WriteOptions;
+    use crate::store::{ReadOptions, WriteOptions};
     use crate::Keyspace;
 
     #[tokio::test]
@@ -166,4 +196,50 @@ mod tests {
             .await
             .expect_err("Should panic here because of duplicate key.");
     }
+
+    #[tokio::test]
+    async fn test_write_batch_delete_range() {
+        let state_store = MemoryStateStore::new();
+        let key_space = Keyspace::table_root(state_store, &TableId::from(0x118));
+        let read_options = |epoch| ReadOptions {
+            epoch,
+            table_id: Default::default(),
+            retention_seconds: None,
+        };
+
+        let mut key_space_batch = key_space.start_write_batch(WriteOptions {
+            epoch: 1,
+            table_id: Default::default(),
+        });
+        key_space_batch.put(Bytes::from("aa"), StorageValue::new_put("111"));
+        key_space_batch.put(Bytes::from("bb"), StorageValue::new_put("111"));
+        key_space_batch.put(Bytes::from("cc"), StorageValue::new_put("111"));
+        key_space_batch.ingest().await.unwrap();
+
+        let mut key_space_batch = key_space.start_write_batch(WriteOptions {
+            epoch: 2,
+            table_id: Default::default(),
+        });
+        key_space_batch.delete_range(Bytes::from("aa"), Bytes::from("cc"));
+        // Writes in the same epoch are not affected by the deletion.
+        key_space_batch.put(Bytes::from("bb"), StorageValue::new_put("222"));
+        key_space_batch.ingest().await.unwrap();
+
+        let values = key_space
+            .scan(None, read_options(2))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![Bytes::from("222"), Bytes::from("111")]);
+        assert_eq!(
+            key_space.get("aa", true, read_options(1)).await.unwrap(),
+            Some(Bytes::from("111"))
+        );
+        assert_eq!(
+            key_space.get("aa", true, read_options(2)).await.unwrap(),
+            None
+        );
+    }
 }
@@ -53,7 +53,7 @@ impl<S: StateStore> Debug for AggGroup<S> {
 }
 
 /// We assume the first state of aggregation is always `StreamingRowCountAgg`.
-const ROW_COUNT_COLUMN: usize = 0;
+pub const ROW_COUNT_COLUMN: usize = 0;
 
 /// Information about the changes built by `AggState::build_changes`.
 pub struct AggChangesInfo {
@@ -23,7 +23,7 @@ use futures_async_stream::try_stream;
 use risingwave_common::bail;
 
 use super::error::StreamExecutorError;
-use super::{Barrier, BoxedMessageStream, Message, StreamChunk, StreamExecutorResult};
+use super::{Barrier, BoxedMessageStream, Message, StreamChunk, StreamExecutorResult, Watermark};
 use crate::executor::monitor::StreamingMetrics;
 use crate::task::ActorId;
 
@@ -35,6 +35,8 @@ pub enum AlignedMessage {
     Barrier(Barrier),
     Left(StreamChunk),
     Right(StreamChunk),
+    WatermarkLeft(Watermark),
+    WatermarkRight(Watermark),
 }
 
 #[try_stream(ok = AlignedMessage, error = StreamExecutorError)]
@@ -61,6 +63,9 @@ pub async fn barrier_align(
                 while let Some(msg) = right.next().await {
                     match msg? {
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
+                        }
                         Message::Barrier(_) => {
                             bail!("right barrier received while left stream end");
                         }
@@ -73,6 +78,9 @@ pub async fn barrier_align(
                 while let Some(msg) = left.next().await {
                     match msg? {
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
+                        }
                         Message::Barrier(_) => {
                             bail!("left barrier received while right stream end");
                         }
@@ -82,6 +90,7 @@ pub async fn barrier_align(
             }
             Either::Left((Some(msg), _)) => match msg? {
                 Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkLeft(watermark),
                 Message::Barrier(_) => loop {
                     let start_time = Instant::now();
                     // received left barrier, waiting for right barrier
@@ -91,6 +100,9 @@ pub async fn barrier_align(
                         .context("failed to poll right message, stream closed unexpectedly")??
                     {
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
+                        }
                         Message::Barrier(barrier) => {
                             yield AlignedMessage::Barrier(barrier);
                             metrics
@@ -104,6 +116,7 @@ pub async fn barrier_align(
             },
             Either::Right((Some(msg), _)) => match msg? {
                 Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkRight(watermark),
                 Message::Barrier(_) => loop {
                     let start_time = Instant::now();
                     // received right barrier, waiting for left barrier
@@ -113,6 +126,9 @@ pub async fn barrier_align(
                         .context("failed to poll left message, stream closed unexpectedly")??
                     {
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
+                        }
                         Message::Barrier(barrier) => {
                             yield AlignedMessage::Barrier(barrier);
                             metrics
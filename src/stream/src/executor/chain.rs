@@ -110,6 +110,15 @@ impl ChainExecutor {
                     self.progress.finish(barrier.epoch.curr);
                     yield Message::Barrier(barrier);
                 }
+                Message::Watermark(watermark) => {
+                    if let Some(idx) = self
+                        .upstream_indices
+                        .iter()
+                        .position(|&i| i == watermark.col_idx)
+                    {
+                        yield Message::Watermark(watermark.with_idx(idx));
+                    }
+                }
             }
         }
     }
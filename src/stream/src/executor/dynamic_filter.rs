@@ -328,6 +328,10 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                         }
                     }
                 }
+                AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {
+                    // Rows filtered out before may be emitted when the right side changes, so the
+                    // watermarks can't be propagated.
+                }
                 AlignedMessage::Barrier(barrier) => {
                     // Flush the difference between the `prev_value` and `current_value`
                     let curr: Datum = current_epoch_value.clone().flatten();
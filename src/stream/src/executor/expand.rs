@@ -72,6 +72,8 @@ impl ExpandExecutor {
                         yield Message::Chunk(stream_chunk)
                     }
                 }
+                // The watermark columns may be set to null by the subsets.
+                Message::Watermark(_) => {}
                 m => yield m,
             }
         }
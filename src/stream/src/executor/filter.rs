@@ -15,14 +15,14 @@
 use std::fmt::{Debug, Formatter};
 
 use itertools::Itertools;
-use risingwave_common::array::{Array, ArrayImpl, Op, StreamChunk, Vis};
+use risingwave_common::array::{Array, ArrayImpl, ArrayRef, Op, StreamChunk, Vis};
 use risingwave_common::buffer::BitmapBuilder;
 use risingwave_common::catalog::Schema;
 use risingwave_expr::expr::BoxedExpression;
 
 use super::{
     ActorContextRef, Executor, ExecutorInfo, PkIndicesRef, SimpleExecutor, SimpleExecutorWrapper,
-    StreamExecutorResult,
+    StreamExecutorResult, Watermark,
 };
 use crate::common::InfallibleExpression;
 
@@ -84,19 +84,14 @@ impl Debug for SimpleFilterExecutor {
     }
 }
 
-impl SimpleExecutor for SimpleFilterExecutor {
-    fn map_filter_chunk(
-        &mut self,
+impl SimpleFilterExecutor {
+    /// Filters the compacted `chunk` with the boolean array `pred_output`. An update pair with only
+    /// one side retained is rewritten as an insert or a delete.
+    pub(super) fn filter(
         chunk: StreamChunk,
+        pred_output: ArrayRef,
     ) -> StreamExecutorResult<Option<StreamChunk>> {
-        let chunk = chunk.compact();
-
         let (data_chunk, ops) = chunk.into_parts();
-
-        let pred_output = self.expr.eval_infallible(&data_chunk, |err| {
-            self.ctx.on_compute_error(err, self.identity())
-        });
-
         let (columns, vis) = data_chunk.into_parts();
 
         let n = ops.len();
@@ -168,6 +163,25 @@ impl SimpleExecutor for SimpleFilterExecutor {
             None
         })
     }
+}
+
+impl SimpleExecutor for SimpleFilterExecutor {
+    fn map_filter_chunk(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let chunk = chunk.compact();
+
+        let pred_output = self.expr.eval_infallible(chunk.data_chunk(), |err| {
+            self.ctx.on_compute_error(err, self.identity())
+        });
+
+        Self::filter(chunk, pred_output)
+    }
+
+    fn handle_watermark(&mut self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>> {
+        Ok(vec![watermark])
+    }
 
     fn schema(&self) -> &Schema {
         &self.info.schema
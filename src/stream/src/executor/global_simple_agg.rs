@@ -296,6 +296,8 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                     )
                     .await?;
                 }
+                // The output of simple agg has no group key to carry the watermark.
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     if let Some(chunk) = Self::flush_data(
                         &info.schema,
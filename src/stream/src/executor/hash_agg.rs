@@ -14,18 +14,20 @@
 
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::ops::Bound::{Excluded, Included};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use futures::{stream, StreamExt};
+use futures::{pin_mut, stream, StreamExt};
 use futures_async_stream::try_stream;
 use iter_chunks::IterChunks;
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::{Row, StreamChunk};
+use risingwave_common::array::{Op, Row, StreamChunk};
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
 use risingwave_common::hash::{HashCode, HashKey, PrecomputedBuildHasher};
+use risingwave_common::types::{ScalarImpl, VirtualNode};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::hash_util::Crc32FastBuilder;
 use risingwave_storage::table::streaming_table::state_table::StateTable;
@@ -35,10 +37,12 @@ use super::aggregation::{agg_call_filter_res, iter_table_storage, AggStateStorag
 use super::{expect_first_barrier, ActorContextRef, Executor, PkIndicesRef, StreamExecutorResult};
 use crate::cache::{cache_may_stale, EvictableHashMap, ExecutorCache, LruManagerRef};
 use crate::error::StreamResult;
-use crate::executor::aggregation::{generate_agg_schema, AggCall, AggChangesInfo, AggGroup};
+use crate::executor::aggregation::{
+    generate_agg_schema, AggCall, AggChangesInfo, AggGroup, ROW_COUNT_COLUMN,
+};
 use crate::executor::error::StreamExecutorError;
 use crate::executor::monitor::StreamingMetrics;
-use crate::executor::{BoxedMessageStream, Message, PkIndices};
+use crate::executor::{BoxedMessageStream, Message, PkIndices, Watermark};
 
 type AggGroupMap<K, S> = ExecutorCache<K, Option<Box<AggGroup<S>>>, PrecomputedBuildHasher>;
 
@@ -52,6 +56,10 @@ type AggGroupMap<K, S> = ExecutorCache<K, Option<Box<AggGroup<S>>>, PrecomputedB
 /// * Upon a barrier is received, the executor will call `.flush` on the storage backend, so that
 ///   all modifications will be flushed to the storage backend. Meanwhile, the executor will go
 ///   through `modified_keys`, and produce a stream chunk based on the state changes.
+///
+/// In the emit-on-window-close mode, the changes are only written to the result table. The first
+/// group key is a window column, and once the watermark on it passes a window, the final results
+/// of the window are emitted exactly once as insertions, so the output is append-only.
 pub struct HashAggExecutor<K: HashKey, S: StateStore> {
     input: Box<dyn Executor>,
 
@@ -115,6 +123,17 @@ struct HashAggExecutorExtra<K: HashKey, S: StateStore> {
 
     /// The maximum size of the chunk produced by executor at a time.
     chunk_size: usize,
+
+    /// The latest watermark on the first group key, which is applied to clean the states of
+    /// closed groups and emitted on the next barrier.
+    buffered_watermark: Option<Watermark>,
+
+    /// Whether to emit the results of a group only once its window is closed by the watermark.
+    emit_on_window_close: bool,
+
+    /// The watermark below which the results of all windows have been emitted. Only used in the
+    /// emit-on-window-close mode.
+    emitted_watermark: Option<ScalarImpl>,
 }
 
 impl<K: HashKey, S: StateStore> Executor for HashAggExecutor<K, S> {
@@ -151,6 +170,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         lru_manager: Option<LruManagerRef>,
         metrics: Arc<StreamingMetrics>,
         chunk_size: usize,
+        emit_on_window_close: bool,
     ) -> StreamResult<Self> {
         let input_info = input.info();
         let schema = generate_agg_schema(input.as_ref(), &agg_calls, Some(&group_key_indices));
@@ -176,6 +196,9 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 total_lookup_count: AtomicU64::new(0),
                 metrics,
                 chunk_size,
+                buffered_watermark: None,
+                emit_on_window_close,
+                emitted_watermark: None,
             },
             _phantom: PhantomData,
         })
@@ -347,10 +370,13 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             ref total_lookup_count,
             ref metrics,
             ref chunk_size,
+            ref emit_on_window_close,
+            ref mut emitted_watermark,
             ..
         }: &'a mut HashAggExecutorExtra<K, S>,
         agg_groups: &'a mut AggGroupMap<K, S>,
         epoch: EpochPair,
+        watermark: Option<ScalarImpl>,
     ) {
         let actor_id_str = ctx.id.to_string();
         metrics
@@ -427,12 +453,62 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                     .map(|builder| Ok::<_, StreamExecutorError>(builder.finish().into()))
                     .try_collect()?;
 
-                let chunk = StreamChunk::new(new_ops, columns, None);
+                // The changes of the open windows are not emitted.
+                if !*emit_on_window_close {
+                    let chunk = StreamChunk::new(new_ops, columns, None);
 
-                trace!("output_chunk: {:?}", &chunk);
-                yield chunk;
+                    trace!("output_chunk: {:?}", &chunk);
+                    yield chunk;
+                }
             }
+        }
 
+        // --- Emit the final results of the windows closed by the watermark ---
+        // This must be done before committing the result table, where the closed windows written
+        // in the previous epochs are cleaned.
+        if *emit_on_window_close && let Some(watermark) = watermark {
+            // The groups with a null window are never closed, so they are skipped.
+            let lower_bound = match emitted_watermark.take() {
+                Some(emitted) => Included(Row::new(vec![Some(emitted)])),
+                None => Excluded(Row::new(vec![None])),
+            };
+            let range = (lower_bound, Excluded(Row::new(vec![Some(watermark.clone())])));
+            let vnodes = result_table.vnodes().clone();
+
+            let data_types = schema.data_types();
+            let mut closed_rows = Vec::with_capacity(*chunk_size);
+            for vnode in vnodes
+                .iter()
+                .enumerate()
+                .filter(|(_, owned)| *owned)
+                .map(|(vnode, _)| vnode as VirtualNode)
+            {
+                let rows = result_table.iter_with_pk_range(&range, vnode).await?;
+                pin_mut!(rows);
+                while let Some(row) = rows.next().await {
+                    let row = row?;
+                    // Skip the groups that have been emptied by retractions.
+                    if row[group_key_indices.len() + ROW_COUNT_COLUMN]
+                        .as_ref()
+                        .map_or(true, |row_count| *row_count.as_int64() == 0)
+                    {
+                        continue;
+                    }
+                    closed_rows.push((Op::Insert, row.into_owned()));
+                    if closed_rows.len() == *chunk_size {
+                        yield StreamChunk::from_rows(&closed_rows, &data_types);
+                        closed_rows.clear();
+                    }
+                }
+            }
+            if !closed_rows.is_empty() {
+                yield StreamChunk::from_rows(&closed_rows, &data_types);
+            }
+
+            *emitted_watermark = Some(watermark);
+        }
+
+        if dirty_cnt > 0 {
             // Commit agg result of all groups.
             result_table.commit(epoch).await?;
 
@@ -445,7 +521,6 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 state_table.commit_no_data_expected(epoch);
             });
             result_table.commit_no_data_expected(epoch);
-            return Ok(());
         }
     }
 
@@ -483,12 +558,39 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 Message::Chunk(chunk) => {
                     Self::apply_chunk(&mut extra, &mut agg_states, chunk).await?;
                 }
+                Message::Watermark(watermark) => {
+                    // Only the watermark on the first group key can be used to clean the states,
+                    // which are ordered by the group keys first.
+                    if extra.group_key_indices.first() == Some(&watermark.col_idx) {
+                        extra.buffered_watermark = Some(watermark.with_idx(0));
+                    }
+                }
                 Message::Barrier(barrier) => {
+                    // No more changes will be applied to the groups below the watermark, so their
+                    // states are cleaned when the epoch is committed. The entries in the cache will
+                    // never be visited again, and will be evicted eventually.
+                    let watermark = extra.buffered_watermark.take();
+                    if let Some(watermark) = &watermark {
+                        iter_table_storage(&mut extra.storages).for_each(|state_table| {
+                            state_table.update_watermark(watermark.val.clone());
+                        });
+                        extra.result_table.update_watermark(watermark.val.clone());
+                    }
+
                     #[for_await]
-                    for chunk in Self::flush_data(&mut extra, &mut agg_states, barrier.epoch) {
+                    for chunk in Self::flush_data(
+                        &mut extra,
+                        &mut agg_states,
+                        barrier.epoch,
+                        watermark.as_ref().map(|watermark| watermark.val.clone()),
+                    ) {
                         yield Message::Chunk(chunk?);
                     }
 
+                    if let Some(watermark) = watermark {
+                        yield Message::Watermark(watermark);
+                    }
+
                     // Update the vnode bitmap for state tables of all agg calls if asked.
                     if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(extra.ctx.id) {
                         iter_table_storage(&mut extra.storages).for_each(|state_table| {
@@ -534,7 +636,7 @@ mod tests {
     use crate::executor::monitor::StreamingMetrics;
     use crate::executor::test_utils::agg_executor::{create_agg_state_table, create_result_table};
     use crate::executor::test_utils::*;
-    use crate::executor::{ActorContext, Executor, HashAggExecutor, Message, PkIndices};
+    use crate::executor::{ActorContext, Executor, HashAggExecutor, Message, PkIndices, Watermark};
 
     #[allow(clippy::too_many_arguments)]
     fn new_boxed_hash_agg_executor<S: StateStore>(
@@ -546,6 +648,7 @@ mod tests {
         group_by_cache_size: usize,
         extreme_cache_size: usize,
         executor_id: u64,
+        emit_on_window_close: bool,
     ) -> Box<dyn Executor> {
         let agg_state_tables = agg_calls
             .iter()
@@ -583,6 +686,7 @@ mod tests {
             None,
             Arc::new(StreamingMetrics::unused()),
             1024,
+            emit_on_window_close,
         )
         .unwrap()
         .boxed()
@@ -610,6 +714,178 @@ mod tests {
         test_local_hash_aggregation_min_append_only(MemoryStateStore::new()).await
     }
 
+    #[tokio::test]
+    async fn test_hash_aggregation_watermark() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, PkIndices::new());
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 1
+            + 2 1
+            + 2 2",
+        ));
+        // Only the watermark on the group key is emitted, after the changes of the epoch.
+        tx.push_watermark(0, DataType::Int64, 2_i64.into());
+        tx.push_watermark(1, DataType::Int64, 2_i64.into());
+        tx.push_barrier(2, false);
+
+        let agg_calls = vec![AggCall {
+            kind: AggKind::Count,
+            args: AggArgs::None,
+            return_type: DataType::Int64,
+            order_pairs: vec![],
+            append_only: false,
+            filter: None,
+        }];
+
+        let hash_agg = new_boxed_hash_agg_executor(
+            MemoryStateStore::new(),
+            Box::new(source),
+            agg_calls,
+            vec![0],
+            vec![],
+            1 << 16,
+            1 << 10,
+            1,
+            false,
+        );
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I
+                + 1 1
+                + 2 2"
+            )
+            .sorted_rows(),
+        );
+        assert_eq!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(0, DataType::Int64, 2_i64.into()))
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_aggregation_emit_on_window_close() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, PkIndices::new());
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 1
+            + 1 2
+            + 2 3",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 2 4
+            + 3 .",
+        ));
+        tx.push_watermark(0, DataType::Int64, 2_i64.into());
+        tx.push_barrier(3, false);
+        tx.push_watermark(0, DataType::Int64, 4_i64.into());
+        tx.push_barrier(4, false);
+
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: false,
+                filter: None,
+            },
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: false,
+                filter: None,
+            },
+        ];
+
+        let hash_agg = new_boxed_hash_agg_executor(
+            MemoryStateStore::new(),
+            Box::new(source),
+            agg_calls,
+            vec![0],
+            vec![],
+            1 << 16,
+            1 << 10,
+            1,
+            true,
+        );
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        // The windows are still open, so nothing is emitted.
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // Window 1 is closed by the watermark.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I
+                + 1 2 2"
+            )
+            .sorted_rows(),
+        );
+        assert_eq!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(0, DataType::Int64, 2_i64.into()))
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // Window 2 and 3 are closed, and window 1 is not emitted again.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I
+                + 2 2 2
+                + 3 1 0"
+            )
+            .sorted_rows(),
+        );
+        assert_eq!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(0, DataType::Int64, 4_i64.into()))
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+    }
+
     async fn test_local_hash_aggregation_count<S: StateStore>(store: S) {
         let schema = Schema {
             fields: vec![Field::unnamed(DataType::Int64)],
@@ -670,6 +946,7 @@ mod tests {
             1 << 16,
             1 << 10,
             1,
+            false,
         );
         let mut hash_agg = hash_agg.execute();
 
@@ -772,6 +1049,7 @@ mod tests {
             1 << 16,
             1 << 10,
             1,
+            false,
         );
         let mut hash_agg = hash_agg.execute();
 
@@ -866,6 +1144,7 @@ mod tests {
             1 << 16,
             1 << 10,
             1,
+            false,
         );
         let mut hash_agg = hash_agg.execute();
 
@@ -965,6 +1244,7 @@ mod tests {
             1 << 16,
             1 << 10,
             1,
+            false,
         );
         let mut hash_agg = hash_agg.execute();
 
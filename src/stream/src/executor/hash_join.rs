@@ -24,7 +24,7 @@ use risingwave_common::array::{Op, Row, RowRef, StreamChunk};
 use risingwave_common::bail;
 use risingwave_common::catalog::Schema;
 use risingwave_common::hash::HashKey;
-use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_expr::expr::BoxedExpression;
 use risingwave_storage::table::streaming_table::state_table::StateTable;
@@ -37,6 +37,7 @@ use super::managed_state::join::*;
 use super::monitor::StreamingMetrics;
 use super::{
     ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+    Watermark,
 };
 use crate::cache::LruManagerRef;
 use crate::common::{InfallibleExpression, StreamChunkBuilder};
@@ -166,6 +167,8 @@ struct JoinSide<K: HashKey, S: StateStore> {
     all_data_types: Vec<DataType>,
     /// The start position for the side in output new columns
     start_pos: usize,
+    /// The latest watermark received on the first join key of this side.
+    watermark: Option<ScalarImpl>,
 }
 
 impl<K: HashKey, S: StateStore> std::fmt::Debug for JoinSide<K, S> {
@@ -238,6 +241,8 @@ pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitiv
     metrics: Arc<StreamingMetrics>,
     /// The maximum size of the chunk produced by executor at a time
     chunk_size: usize,
+    /// The watermark on the join key which has been used to clean the states and emitted.
+    emitted_watermark: Option<ScalarImpl>,
 }
 
 impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> std::fmt::Debug
@@ -567,6 +572,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 all_data_types: state_all_data_types_l,
                 pk_indices: state_pk_indices_l,
                 start_pos: 0,
+                watermark: None,
             },
             side_r: JoinSide {
                 ht: JoinHashMap::new(
@@ -589,6 +595,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 all_data_types: state_all_data_types_r,
                 pk_indices: state_pk_indices_r,
                 start_pos: side_l_column_n,
+                watermark: None,
             },
             pk_indices,
             output_indices,
@@ -598,6 +605,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             append_only_optimize,
             metrics,
             chunk_size,
+            emitted_watermark: None,
         }
     }
 
@@ -674,6 +682,16 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         })?;
                     }
                 }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    for watermark in self.handle_watermark(SideType::Left, watermark) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    for watermark in self.handle_watermark(SideType::Right, watermark) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
                 AlignedMessage::Barrier(barrier) => {
                     self.flush_data(barrier.epoch).await?;
 
@@ -714,6 +732,66 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         }
     }
 
+    /// Records the watermark on the first join key from one side. Once both sides have made
+    /// progress, rows with smaller join keys will never be matched again, so they are cleaned from
+    /// the states of both sides, and the watermark is emitted on the join key columns of the
+    /// output.
+    fn handle_watermark(
+        &mut self,
+        side: SideTypePrimitive,
+        watermark: Watermark,
+    ) -> Vec<Watermark> {
+        // Rows of outer, semi and anti joins may be emitted or retracted long after the state of
+        // the other side is cleaned, so only inner join is supported.
+        if T != JoinType::Inner {
+            return vec![];
+        }
+        let side_update = if side == SideType::Left {
+            &mut self.side_l
+        } else {
+            &mut self.side_r
+        };
+        if side_update.join_key_indices.first() != Some(&watermark.col_idx) {
+            return vec![];
+        }
+        // Rows with null keys are ordered first in the states, which must be kept if they can be
+        // matched by a null-safe equal condition.
+        if side_update.ht.null_matched().contains(0) {
+            return vec![];
+        }
+        side_update.watermark = Some(watermark.val);
+
+        let (Some(watermark_l), Some(watermark_r)) =
+            (&self.side_l.watermark, &self.side_r.watermark) else {
+            return vec![];
+        };
+        let min_watermark = watermark_l.min(watermark_r).clone();
+        if matches!(&self.emitted_watermark, Some(emitted) if emitted >= &min_watermark) {
+            return vec![];
+        }
+        self.emitted_watermark = Some(min_watermark.clone());
+
+        // The entries of the cleaned keys in the cache will never be visited again, and will be
+        // evicted eventually.
+        self.side_l.ht.update_watermark(min_watermark.clone());
+        self.side_r.ht.update_watermark(min_watermark.clone());
+
+        let key_l = self.side_l.join_key_indices[0];
+        let key_r = self.side_r.start_pos + self.side_r.join_key_indices[0];
+        self.output_indices
+            .iter()
+            .enumerate()
+            .filter(|(_, &idx)| idx == key_l || idx == key_r)
+            .map(|(output_idx, &idx)| {
+                Watermark::new(
+                    output_idx,
+                    self.output_data_types[idx].clone(),
+                    min_watermark.clone(),
+                )
+            })
+            .collect()
+    }
+
     async fn flush_data(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
         // All changes to the state has been buffered in the mem-table of the state table. Just
         // `commit` them here.
@@ -1215,6 +1293,44 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_with_watermark() {
+        let (mut tx_l, mut tx_r, mut hash_join) =
+            create_executor::<{ JoinType::Inner }>(false, false);
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        // Watermarks on non-key columns are ignored.
+        tx_l.push_watermark(1, DataType::Int64, 100_i64.into());
+        // The watermark is not emitted until both sides have made progress.
+        tx_l.push_watermark(0, DataType::Int64, 3_i64.into());
+        tx_r.push_watermark(0, DataType::Int64, 2_i64.into());
+        for output_idx in [0, 2] {
+            assert_eq!(
+                hash_join.next().await.unwrap().unwrap(),
+                Message::Watermark(Watermark::new(output_idx, DataType::Int64, 2_i64.into()))
+            );
+        }
+
+        tx_r.push_watermark(0, DataType::Int64, 5_i64.into());
+        for output_idx in [0, 2] {
+            assert_eq!(
+                hash_join.next().await.unwrap().unwrap(),
+                Message::Watermark(Watermark::new(output_idx, DataType::Int64, 3_i64.into()))
+            );
+        }
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert!(matches!(
+            hash_join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        ));
+    }
+
     #[tokio::test]
     async fn test_streaming_null_safe_hash_inner_join() {
         let chunk_l1 = StreamChunk::from_pretty(
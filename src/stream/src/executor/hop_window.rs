@@ -18,7 +18,7 @@ use futures::StreamExt;
 use futures_async_stream::try_stream;
 use num_traits::CheckedSub;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::{DataChunk, StreamChunk, Vis};
+use risingwave_common::array::{DataChunk, Row, StreamChunk, Vis};
 use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
 use risingwave_expr::expr::{Expression, InputRefExpression, LiteralExpression};
@@ -26,7 +26,7 @@ use risingwave_expr::ExprError;
 use risingwave_pb::expr::expr_node;
 
 use super::error::StreamExecutorError;
-use super::{ActorContextRef, BoxedExecutor, Executor, ExecutorInfo, Message};
+use super::{ActorContextRef, BoxedExecutor, Executor, ExecutorInfo, Message, Watermark};
 use crate::common::InfallibleExpression;
 
 pub struct HopWindowExecutor {
@@ -243,6 +243,40 @@ impl HopWindowExecutor {
                     let new_chunk = StreamChunk::new(ops.clone(), new_cols, None);
                     yield Message::Chunk(new_chunk);
                 }
+            } else if let Message::Watermark(watermark) = msg {
+                // The smallest window of the rows after the watermark starts at `hop_start` of
+                // the watermark, so the watermarks of the window columns can be derived.
+                let window_start = if watermark.col_idx == time_col_idx {
+                    let mut row = Row::new(vec![None; window_start_col_index]);
+                    row.0[time_col_idx] = Some(watermark.val.clone());
+                    hop_start
+                        .eval_row_infallible(&row, |err| ctx.on_compute_error(err, &info.identity))
+                } else {
+                    None
+                };
+                for (out_idx, &idx) in output_indices.iter().enumerate() {
+                    if idx == watermark.col_idx {
+                        yield Message::Watermark(watermark.clone().with_idx(out_idx));
+                    } else if let Some(window_start) = &window_start {
+                        let window_start_row = Row::new(vec![Some(window_start.clone())]);
+                        let expr = if idx == window_start_col_index {
+                            &window_start_exprs[0]
+                        } else if idx == window_end_col_index {
+                            &window_end_exprs[0]
+                        } else {
+                            continue;
+                        };
+                        if let Some(val) = expr.eval_row_infallible(&window_start_row, |err| {
+                            ctx.on_compute_error(err, &info.identity)
+                        }) {
+                            yield Message::Watermark(Watermark::new(
+                                out_idx,
+                                output_type.clone(),
+                                val,
+                            ));
+                        }
+                    }
+                }
             } else {
                 yield msg;
                 continue;
@@ -253,13 +287,14 @@ impl HopWindowExecutor {
 
 #[cfg(test)]
 mod tests {
+    use chrono::NaiveDateTime;
     use futures::StreamExt;
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::{DataType, IntervalUnit};
+    use risingwave_common::types::{DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl};
 
     use crate::executor::test_utils::MockSource;
-    use crate::executor::{ActorContext, Executor, ExecutorInfo, StreamChunk};
+    use crate::executor::{ActorContext, Executor, ExecutorInfo, StreamChunk, Watermark};
 
     #[tokio::test]
     async fn test_execute() {
@@ -421,4 +456,54 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_watermark() {
+        let field1 = Field::unnamed(DataType::Int64);
+        let field2 = Field::with_name(DataType::Timestamp, "created_at");
+        let schema = Schema::new(vec![field1, field2]);
+        let (mut tx, input) = MockSource::channel(schema.clone(), vec![0]);
+
+        let executor = super::HopWindowExecutor::new(
+            ActorContext::create(123),
+            input.boxed(),
+            ExecutorInfo {
+                schema,
+                pk_indices: vec![0],
+                identity: "test".to_string(),
+            },
+            1,
+            IntervalUnit::from_minutes(15),
+            IntervalUnit::from_minutes(30),
+            vec![3, 1, 2],
+        )
+        .boxed();
+        let mut stream = executor.execute();
+
+        let timestamp = |s: &str| {
+            ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper::new(
+                NaiveDateTime::parse_from_str(&format!("2022-02-02 {}", s), "%Y-%m-%d %H:%M:%S")
+                    .unwrap(),
+            ))
+        };
+        tx.push_watermark(0, DataType::Int64, 5_i64.into());
+        tx.push_watermark(1, DataType::Timestamp, timestamp("10:05:00"));
+        tx.push_barrier(1, true);
+
+        // The watermark of the column not in the output is dropped.
+        let mut watermarks = vec![];
+        for _ in 0..3 {
+            let msg = stream.next().await.unwrap().unwrap();
+            watermarks.push(msg.into_watermark().unwrap());
+        }
+        assert_eq!(
+            watermarks,
+            vec![
+                Watermark::new(0, DataType::Timestamp, timestamp("10:15:00")),
+                Watermark::new(1, DataType::Timestamp, timestamp("10:05:00")),
+                Watermark::new(2, DataType::Timestamp, timestamp("09:45:00")),
+            ]
+        );
+        assert!(stream.next().await.unwrap().unwrap().is_stop());
+    }
 }
@@ -0,0 +1,738 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Bound::{self, *};
+use std::sync::Arc;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row, RowRef, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{
+    CheckedAdd, DataType, IntervalUnit, ScalarImpl, ToOwnedDatum, VirtualNode,
+};
+use risingwave_common::util::epoch::EpochPair;
+use risingwave_common::util::hash_util::Crc32FastBuilder;
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::table::DEFAULT_VNODE;
+use risingwave_storage::StateStore;
+
+use super::barrier_align::*;
+use super::error::{StreamExecutorError, StreamExecutorResult};
+use super::monitor::StreamingMetrics;
+use super::{
+    ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+    Watermark,
+};
+use crate::common::{InfallibleExpression, StreamChunkBuilder};
+use crate::executor::expect_first_barrier_from_aligned_stream;
+
+/// Parameters of one side of the [`IntervalJoinExecutor`].
+pub struct IntervalJoinParams {
+    pub join_key_indices: Vec<usize>,
+    pub time_key_index: usize,
+    /// Positions of the distribution keys of the state table in `join_key_indices`, used to
+    /// locate the vnode of the rows to match with the join key from the other side.
+    pub dist_key_in_join_key: Vec<usize>,
+}
+
+struct IntervalJoinSide<S: StateStore> {
+    params: IntervalJoinParams,
+    /// Stores all the rows of this side, ordered by the time key.
+    state_table: StateTable<S>,
+    /// The start position of the columns of this side in the joined row.
+    start_pos: usize,
+    /// The watermark of the time key from upstream. Rows with a smaller time will never arrive
+    /// again. Without watermarks, no rows of the other side are evicted.
+    watermark: Option<ScalarImpl>,
+    /// The watermark emitted on the time key column of this side in the joined rows.
+    emitted_watermark: Option<ScalarImpl>,
+}
+
+impl<S: StateStore> IntervalJoinSide<S> {
+    fn vnode_of(&self, join_key: &Row) -> VirtualNode {
+        if self.params.dist_key_in_join_key.is_empty() {
+            DEFAULT_VNODE
+        } else {
+            join_key
+                .by_indices(&self.params.dist_key_in_join_key)
+                .hash_row(&Crc32FastBuilder {})
+                .to_vnode()
+        }
+    }
+
+    fn advance_watermark(&mut self, time: &ScalarImpl) {
+        if self.watermark.as_ref().map_or(true, |w| time > w) {
+            self.watermark = Some(time.clone());
+        }
+    }
+
+    /// Deletes the rows with time smaller than `bound`, which can never be matched again.
+    async fn evict(&mut self, bound: Option<ScalarImpl>) -> StreamExecutorResult<()> {
+        let Some(bound) = bound else {
+            return Ok(());
+        };
+        let range = (Unbounded, Excluded(Row::new(vec![Some(bound)])));
+        let vnodes = self.state_table.vnodes().clone();
+
+        let mut expired_rows = vec![];
+        for vnode in vnodes
+            .iter()
+            .enumerate()
+            .filter(|(_, owned)| *owned)
+            .map(|(vnode, _)| vnode as VirtualNode)
+        {
+            let rows = self.state_table.iter_with_pk_range(&range, vnode).await?;
+            pin_mut!(rows);
+            while let Some(row) = rows.next().await {
+                expired_rows.push(row?.into_owned());
+            }
+        }
+        for row in expired_rows {
+            self.state_table.delete(row);
+        }
+        Ok(())
+    }
+}
+
+/// Shifts the time by the interval. Returns `None` on overflow.
+fn shift_time(time: &ScalarImpl, interval: IntervalUnit) -> Option<ScalarImpl> {
+    match time {
+        ScalarImpl::NaiveDateTime(time) => time.checked_add(interval).map(ScalarImpl::from),
+        // `timestamp with time zone` in microseconds. Intervals of months are rejected by the
+        // optimizer.
+        ScalarImpl::Int64(time) => interval
+            .total_ms()
+            .checked_mul(1000)
+            .and_then(|us| time.checked_add(us))
+            .map(ScalarImpl::from),
+        _ => unreachable!("unsupported time key: {:?}", time),
+    }
+}
+
+/// [`IntervalJoinExecutor`] joins two append-only streams with the equal join keys and a time
+/// band, i.e. `left.time BETWEEN right.time + lower_bound AND right.time + upper_bound`.
+///
+/// Each side is stored in a state table ordered by time, so that the matched rows are found with
+/// a range scan. As the watermark of the time key of one side advances, the rows of the other side
+/// that can never be matched again are evicted on barriers, which keeps the state bounded.
+pub struct IntervalJoinExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    input_l: Option<BoxedExecutor>,
+    input_r: Option<BoxedExecutor>,
+    side_l: IntervalJoinSide<S>,
+    side_r: IntervalJoinSide<S>,
+    lower_bound: IntervalUnit,
+    upper_bound: IntervalUnit,
+    /// The non-equi condition, evaluated on the joined rows.
+    cond: Option<BoxedExpression>,
+    /// Data types of the joined rows, before applying `output_indices`.
+    output_data_types: Vec<DataType>,
+    output_indices: Vec<usize>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    metrics: Arc<StreamingMetrics>,
+    /// The maximum size of the chunk produced by executor at a time.
+    chunk_size: usize,
+}
+
+impl<S: StateStore> IntervalJoinExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input_l: BoxedExecutor,
+        input_r: BoxedExecutor,
+        params_l: IntervalJoinParams,
+        params_r: IntervalJoinParams,
+        lower_bound: IntervalUnit,
+        upper_bound: IntervalUnit,
+        cond: Option<BoxedExpression>,
+        pk_indices: PkIndices,
+        output_indices: Vec<usize>,
+        executor_id: u64,
+        mut state_table_l: StateTable<S>,
+        mut state_table_r: StateTable<S>,
+        metrics: Arc<StreamingMetrics>,
+        chunk_size: usize,
+    ) -> Self {
+        // Rows may be deleted from the upstream after they are evicted.
+        state_table_l.disable_sanity_check();
+        state_table_r.disable_sanity_check();
+
+        let original_schema: Schema = input_l
+            .schema()
+            .fields
+            .iter()
+            .chain(input_r.schema().fields.iter())
+            .cloned()
+            .collect();
+        let schema = output_indices
+            .iter()
+            .map(|&idx| original_schema[idx].clone())
+            .collect();
+        let start_pos_r = input_l.schema().len();
+
+        Self {
+            ctx,
+            input_l: Some(input_l),
+            input_r: Some(input_r),
+            side_l: IntervalJoinSide {
+                params: params_l,
+                state_table: state_table_l,
+                start_pos: 0,
+                watermark: None,
+                emitted_watermark: None,
+            },
+            side_r: IntervalJoinSide {
+                params: params_r,
+                state_table: state_table_r,
+                start_pos: start_pos_r,
+                watermark: None,
+                emitted_watermark: None,
+            },
+            lower_bound,
+            upper_bound,
+            cond,
+            output_data_types: original_schema.data_types(),
+            output_indices,
+            schema,
+            pk_indices,
+            identity: format!("IntervalJoinExecutor {:X}", executor_id),
+            metrics,
+            chunk_size,
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let input_l = self.input_l.take().unwrap();
+        let input_r = self.input_r.take().unwrap();
+        let aligned_stream = barrier_align(
+            input_l.execute(),
+            input_r.execute(),
+            self.ctx.id,
+            self.metrics.clone(),
+        );
+        pin_mut!(aligned_stream);
+
+        let barrier = expect_first_barrier_from_aligned_stream(&mut aligned_stream).await?;
+        self.side_l.state_table.init_epoch(barrier.epoch);
+        self.side_r.state_table.init_epoch(barrier.epoch);
+
+        // The first barrier message should be propagated.
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in aligned_stream {
+            match msg? {
+                AlignedMessage::Left(chunk) => {
+                    // A left row matches the right rows in
+                    // `[left.time - upper_bound, left.time - lower_bound]`.
+                    #[for_await]
+                    for chunk in Self::join_oneside(
+                        &self.ctx,
+                        &self.identity,
+                        &mut self.side_l,
+                        &mut self.side_r,
+                        (self.upper_bound.negative(), self.lower_bound.negative()),
+                        &self.output_data_types,
+                        &mut self.cond,
+                        chunk,
+                        self.chunk_size,
+                    ) {
+                        yield Message::Chunk(chunk?.reorder_columns(&self.output_indices));
+                    }
+                }
+                AlignedMessage::Right(chunk) => {
+                    // A right row matches the left rows in
+                    // `[right.time + lower_bound, right.time + upper_bound]`.
+                    #[for_await]
+                    for chunk in Self::join_oneside(
+                        &self.ctx,
+                        &self.identity,
+                        &mut self.side_r,
+                        &mut self.side_l,
+                        (self.lower_bound, self.upper_bound),
+                        &self.output_data_types,
+                        &mut self.cond,
+                        chunk,
+                        self.chunk_size,
+                    ) {
+                        yield Message::Chunk(chunk?.reorder_columns(&self.output_indices));
+                    }
+                }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    if watermark.col_idx == self.side_l.params.time_key_index {
+                        self.side_l.advance_watermark(&watermark.val);
+                        for watermark in self.derive_watermarks() {
+                            yield Message::Watermark(watermark);
+                        }
+                    }
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    if watermark.col_idx == self.side_r.params.time_key_index {
+                        self.side_r.advance_watermark(&watermark.val);
+                        for watermark in self.derive_watermarks() {
+                            yield Message::Watermark(watermark);
+                        }
+                    }
+                }
+                AlignedMessage::Barrier(barrier) => {
+                    self.flush_data(barrier.epoch).await?;
+
+                    // Update the vnode bitmap for state tables of both sides if asked.
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        self.side_l
+                            .state_table
+                            .update_vnode_bitmap(vnode_bitmap.clone());
+                        self.side_r.state_table.update_vnode_bitmap(vnode_bitmap);
+                    }
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+
+    /// Derives the watermarks on the time key columns of the output once both sides have made
+    /// progress. Future left rows have time no less than `watermark_l`, and future right rows only
+    /// match the left rows with time no less than `watermark_r + lower_bound`, so the left time of
+    /// the joined rows is bounded by the smaller one, and vice versa.
+    fn derive_watermarks(&mut self) -> Vec<Watermark> {
+        let (Some(watermark_l), Some(watermark_r)) =
+            (&self.side_l.watermark, &self.side_r.watermark) else {
+            return vec![];
+        };
+        let derived_l =
+            shift_time(watermark_r, self.lower_bound).map(|w| w.min(watermark_l.clone()));
+        let derived_r = shift_time(watermark_l, self.upper_bound.negative())
+            .map(|w| w.min(watermark_r.clone()));
+
+        let mut watermarks = vec![];
+        for (side, derived) in [(&mut self.side_l, derived_l), (&mut self.side_r, derived_r)] {
+            let Some(derived) = derived else {
+                continue;
+            };
+            if matches!(&side.emitted_watermark, Some(emitted) if emitted >= &derived) {
+                continue;
+            }
+            side.emitted_watermark = Some(derived.clone());
+
+            let time_idx = side.start_pos + side.params.time_key_index;
+            watermarks.extend(
+                self.output_indices
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &idx)| idx == time_idx)
+                    .map(|(output_idx, &idx)| {
+                        Watermark::new(
+                            output_idx,
+                            self.output_data_types[idx].clone(),
+                            derived.clone(),
+                        )
+                    }),
+            );
+        }
+        watermarks
+    }
+
+    async fn flush_data(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
+        // Future right rows have time no less than the right watermark, so they only match the
+        // left rows with time no less than `watermark_r + lower_bound`, and vice versa.
+        let bound_l = self
+            .side_r
+            .watermark
+            .as_ref()
+            .and_then(|w| shift_time(w, self.lower_bound));
+        let bound_r = self
+            .side_l
+            .watermark
+            .as_ref()
+            .and_then(|w| shift_time(w, self.upper_bound.negative()));
+        self.side_l.evict(bound_l).await?;
+        self.side_r.evict(bound_r).await?;
+
+        self.side_l.state_table.commit(epoch).await?;
+        self.side_r.state_table.commit(epoch).await?;
+        Ok(())
+    }
+
+    fn row_concat(
+        row_update: &RowRef<'_>,
+        update_start_pos: usize,
+        row_matched: &Row,
+        matched_start_pos: usize,
+    ) -> Row {
+        let mut new_row = vec![None; row_update.size() + row_matched.size()];
+
+        for (i, datum_ref) in row_update.values().enumerate() {
+            new_row[i + update_start_pos] = datum_ref.to_owned_datum();
+        }
+        for i in 0..row_matched.size() {
+            new_row[i + matched_start_pos] = row_matched[i].clone();
+        }
+        Row(new_row)
+    }
+
+    /// Joins the chunk from `side_update` with the rows of `side_match` whose time is in
+    /// `[time + offsets.0, time + offsets.1]`, and then updates the state of `side_update`.
+    #[try_stream(ok = StreamChunk, error = StreamExecutorError)]
+    #[expect(clippy::too_many_arguments)]
+    async fn join_oneside<'a>(
+        ctx: &'a ActorContextRef,
+        identity: &'a str,
+        side_update: &'a mut IntervalJoinSide<S>,
+        side_match: &'a mut IntervalJoinSide<S>,
+        offsets: (IntervalUnit, IntervalUnit),
+        output_data_types: &'a [DataType],
+        cond: &'a mut Option<BoxedExpression>,
+        chunk: StreamChunk,
+        chunk_size: usize,
+    ) {
+        let mut builder = StreamChunkBuilder::new(
+            chunk_size,
+            output_data_types,
+            side_update.start_pos,
+            side_match.start_pos,
+        )?;
+
+        for (op, row) in chunk.rows() {
+            let join_key = row.row_by_indices(&side_update.params.join_key_indices);
+            let time = row
+                .value_at(side_update.params.time_key_index)
+                .to_owned_datum();
+            // Rows with NULL keys can never be matched, so they are not stored either.
+            let Some(time) = time else {
+                continue;
+            };
+            if join_key.0.iter().any(|datum| datum.is_none()) {
+                continue;
+            }
+
+            let op = match op {
+                Op::Insert | Op::UpdateInsert => Op::Insert,
+                Op::Delete | Op::UpdateDelete => Op::Delete,
+            };
+
+            let to_bound = |offset: IntervalUnit| -> Bound<Row> {
+                match shift_time(&time, offset) {
+                    Some(bound) => Included(Row::new(vec![Some(bound)])),
+                    None => Unbounded,
+                }
+            };
+            let range = (to_bound(offsets.0), to_bound(offsets.1));
+            let vnode = side_match.vnode_of(&join_key);
+
+            let matched_rows = side_match
+                .state_table
+                .iter_with_pk_range(&range, vnode)
+                .await?;
+            pin_mut!(matched_rows);
+            while let Some(matched_row) = matched_rows.next().await {
+                let matched_row = matched_row?;
+                // Rows of other join keys in the same vnode are also scanned.
+                if side_match
+                    .params
+                    .join_key_indices
+                    .iter()
+                    .zip(join_key.values())
+                    .any(|(&idx, datum)| &matched_row[idx] != datum)
+                {
+                    continue;
+                }
+                if let Some(cond) = cond.as_ref() {
+                    let joined_row = Self::row_concat(
+                        &row,
+                        side_update.start_pos,
+                        &matched_row,
+                        side_match.start_pos,
+                    );
+                    let cond_match = cond
+                        .eval_row_infallible(&joined_row, |err| ctx.on_compute_error(err, identity))
+                        .map(|s| *s.as_bool())
+                        .unwrap_or(false);
+                    if !cond_match {
+                        continue;
+                    }
+                }
+                if let Some(chunk) = builder.append_row(op, &row, &matched_row)? {
+                    yield chunk;
+                }
+            }
+
+            match op {
+                Op::Insert => side_update.state_table.insert(row.to_owned_row()),
+                _ => side_update.state_table.delete(row.to_owned_row()),
+            }
+        }
+
+        if let Some(chunk) = builder.take()? {
+            yield chunk;
+        }
+    }
+}
+
+impl<S: StateStore> Executor for IntervalJoinExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::{MessageSender, MockSource};
+    use crate::executor::ActorContext;
+
+    fn create_state_table(
+        mem_state: MemoryStateStore,
+        table_id: u32,
+    ) -> StateTable<MemoryStateStore> {
+        // (time, key, id), ordered by time.
+        let column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(2), DataType::Int64),
+        ];
+        StateTable::new_without_distribution(
+            mem_state,
+            TableId::new(table_id),
+            column_descs,
+            vec![OrderType::Ascending; 3],
+            vec![0, 1, 2],
+        )
+    }
+
+    fn create_executor() -> (MessageSender, MessageSender, BoxedMessageStream) {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (tx_l, source_l) = MockSource::channel(schema.clone(), vec![2]);
+        let (tx_r, source_r) = MockSource::channel(schema, vec![2]);
+        let params = || IntervalJoinParams {
+            join_key_indices: vec![1],
+            time_key_index: 0,
+            dist_key_in_join_key: vec![],
+        };
+
+        let mem_state = MemoryStateStore::new();
+        let executor = IntervalJoinExecutor::new(
+            ActorContext::create(123),
+            Box::new(source_l),
+            Box::new(source_r),
+            params(),
+            params(),
+            // `left.time BETWEEN right.time - 2ms AND right.time + 1ms`, with time in us.
+            IntervalUnit::from_millis(-2),
+            IntervalUnit::from_millis(1),
+            None,
+            vec![2, 5],
+            vec![0, 1, 2, 3, 4, 5],
+            1,
+            create_state_table(mem_state.clone(), 0),
+            create_state_table(mem_state, 1),
+            Arc::new(StreamingMetrics::unused()),
+            1024,
+        );
+        (tx_l, tx_r, Box::new(executor).execute())
+    }
+
+    #[tokio::test]
+    async fn test_interval_join() {
+        let (mut tx_l, mut tx_r, mut join) = create_executor();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            " I    I I
+            + 1000 1 1
+            + 3000 1 2
+            + 3000 2 3",
+        ));
+        // Left rows are stored but have no match yet.
+        let chunk = join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty("I I I I I I")
+        );
+
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            " I    I I
+            + 2000 1 4
+            + 2000 2 5",
+        ));
+        let chunk = join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I    I I I    I I
+                + 1000 1 1 2000 1 4
+                + 3000 1 2 2000 1 4
+                + 3000 2 3 2000 2 5"
+            )
+        );
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert!(matches!(
+            join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        ));
+
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            " I    I I
+            + 2500 1 6",
+        ));
+        let chunk = join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I    I I I    I I
+                + 1000 1 1 2500 1 6
+                + 3000 1 2 2500 1 6"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interval_join_eviction() {
+        let (mut tx_l, mut tx_r, mut join) = create_executor();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        join.next().await.unwrap().unwrap();
+
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            " I    I I
+            + 1000 1 1",
+        ));
+        join.next().await.unwrap().unwrap();
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            " I     I I
+            + 10000 2 2",
+        ));
+        join.next().await.unwrap().unwrap();
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert!(matches!(
+            join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        ));
+
+        // Nothing is evicted without watermarks, even if a larger time has been seen, since
+        // rows may arrive out of order.
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            " I    I I
+            + 1000 1 3",
+        ));
+        let chunk = join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I    I I I    I I
+                + 1000 1 3 1000 1 1"
+            )
+        );
+
+        tx_l.push_watermark(0, DataType::Int64, ScalarImpl::Int64(10000));
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+        assert!(matches!(
+            join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        ));
+
+        // The right row at 1000 has been evicted on the barrier, since the left watermark is
+        // 10000 and future left rows can only match right rows after 9000.
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            " I    I I
+            + 1000 1 4",
+        ));
+        let chunk = join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty("I I I I I I")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interval_join_watermark() {
+        let (mut tx_l, mut tx_r, mut join) = create_executor();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        join.next().await.unwrap().unwrap();
+
+        // Watermarks on the non-time columns are ignored.
+        tx_l.push_watermark(1, DataType::Int64, ScalarImpl::Int64(100));
+        // No watermark is emitted until both sides have made progress.
+        tx_l.push_watermark(0, DataType::Int64, ScalarImpl::Int64(10000));
+        tx_r.push_watermark(0, DataType::Int64, ScalarImpl::Int64(5000));
+
+        // Future right rows after 5000 can match the stored left rows after 3000, and future
+        // left rows after 10000 can only match the right rows after 9000.
+        assert_eq!(
+            join.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(0, DataType::Int64, 3000_i64.into()))
+        );
+        assert_eq!(
+            join.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(3, DataType::Int64, 5000_i64.into()))
+        );
+
+        // Only the advanced watermark is emitted.
+        tx_r.push_watermark(0, DataType::Int64, ScalarImpl::Int64(6000));
+        assert_eq!(
+            join.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(0, DataType::Int64, 4000_i64.into()))
+        );
+        assert_eq!(
+            join.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(3, DataType::Int64, 6000_i64.into()))
+        );
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert!(matches!(
+            join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        ));
+    }
+}
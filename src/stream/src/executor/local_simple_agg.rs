@@ -119,6 +119,8 @@ impl LocalSimpleAggExecutor {
                     Self::apply_chunk(&ctx, &info.identity, &agg_calls, &mut aggregators, chunk)?;
                     is_dirty = true;
                 }
+                // The output of simple agg has no group key to carry the watermark.
+                Message::Watermark(_) => {}
                 m @ Message::Barrier(_) => {
                     if is_dirty {
                         is_dirty = false;
@@ -98,6 +98,8 @@ pub async fn poll_until_barrier(stream: impl MessageStream, expected_barrier: Ba
     for item in stream {
         match item? {
             c @ Message::Chunk(_) => yield c,
+            // Lookup join doesn't make use of watermarks yet.
+            Message::Watermark(_) => {}
             Message::Barrier(b) => {
                 if b.epoch != expected_barrier.epoch {
                     return Err(StreamExecutorError::align_barrier(expected_barrier, b));
@@ -138,6 +140,9 @@ pub async fn align_barrier(left: impl MessageStream, right: impl MessageStream)
                 Some(Either::Left(Ok(c @ Message::Chunk(_)))) => {
                     yield Either::Left(c);
                 }
+                // Lookup join doesn't make use of watermarks yet.
+                Some(Either::Left(Ok(Message::Watermark(_))))
+                | Some(Either::Right(Ok(Message::Watermark(_)))) => {}
                 Some(Either::Left(Ok(Message::Barrier(b)))) => {
                     yield Either::Left(Message::Barrier(b.clone()));
                     break 'inner (SideStatus::LeftBarrier, b);
@@ -213,6 +218,9 @@ pub async fn stream_lookup_arrange_prev_epoch(
                     yield ArrangeMessage::Barrier(barrier);
                     stream_side_end = true;
                 }
+                Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                    unreachable!("watermarks are filtered out by `align_barrier`")
+                }
                 Either::Right(Message::Barrier(barrier)) => {
                     if stream_side_end {
                         yield ArrangeMessage::ArrangeReady(
@@ -239,7 +247,7 @@ pub async fn stream_lookup_arrange_prev_epoch(
                     yield ArrangeMessage::Barrier(b);
                     break;
                 }
-                Either::Right(_) => unreachable!(),
+                Either::Left(Message::Watermark(_)) | Either::Right(_) => unreachable!(),
             }
         }
 
@@ -293,6 +301,9 @@ pub async fn stream_lookup_arrange_this_epoch(
                 Either::Left(Message::Barrier(barrier)) => {
                     break 'inner Status::StreamReady(barrier);
                 }
+                Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                    unreachable!("watermarks are filtered out by `align_barrier`")
+                }
                 Either::Right(Message::Barrier(barrier)) => {
                     yield ArrangeMessage::ArrangeReady(std::mem::take(&mut arrange_buf), barrier);
                     for msg in std::mem::take(&mut stream_buf) {
@@ -316,7 +327,7 @@ pub async fn stream_lookup_arrange_this_epoch(
                         yield ArrangeMessage::Barrier(b);
                         break;
                     }
-                    Either::Right(_) => unreachable!(),
+                    Either::Left(Message::Watermark(_)) | Either::Right(_) => unreachable!(),
                 }
             },
             // Stream is done in this epoch, but arrangement is not ready -- we wait for the
@@ -327,7 +338,7 @@ pub async fn stream_lookup_arrange_this_epoch(
                     .await
                     .expect("unexpected close of barrier aligner")?
                 {
-                    Either::Left(_) => unreachable!(),
+                    Either::Left(_) | Either::Right(Message::Watermark(_)) => unreachable!(),
                     Either::Right(Message::Chunk(chunk)) => {
                         arrange_buf.push(chunk);
                     }
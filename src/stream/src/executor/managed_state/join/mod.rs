@@ -454,6 +454,13 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
         Ok(entry_state)
     }
 
+    /// Cleans the rows whose first join key is less than the watermark from the state tables when
+    /// the next epoch is committed.
+    pub fn update_watermark(&mut self, watermark: ScalarImpl) {
+        self.state.table.update_watermark(watermark.clone());
+        self.degree_state.table.update_watermark(watermark);
+    }
+
     pub async fn flush(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
         self.metrics.flush();
         self.state.table.commit(epoch).await?;
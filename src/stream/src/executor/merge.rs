@@ -19,13 +19,14 @@ use anyhow::anyhow;
 use futures::{pin_mut, Stream, StreamExt};
 use futures_async_stream::try_stream;
 use risingwave_common::catalog::Schema;
+use risingwave_common::types::ScalarImpl;
 
 use super::error::StreamExecutorError;
 use super::exchange::input::BoxedInput;
 use super::*;
 use crate::executor::exchange::input::new_input;
 use crate::executor::monitor::StreamingMetrics;
-use crate::task::{FragmentId, SharedContext};
+use crate::task::{ActorId, FragmentId, SharedContext};
 
 /// `MergeExecutor` merges data from multiple channels. Dataflow from one channel
 /// will be stopped on barrier.
@@ -83,7 +84,7 @@ impl MergeExecutor {
 
     #[cfg(test)]
     pub fn for_test(inputs: Vec<tokio::sync::mpsc::Receiver<Message>>) -> Self {
-        use super::exchange::input::LocalInput;
+        use super::exchange::input::{Input, LocalInput};
 
         Self::new(
             Schema::default(),
@@ -92,7 +93,11 @@ impl MergeExecutor {
             514,
             1919,
             1024,
-            inputs.into_iter().map(LocalInput::for_test).collect(),
+            inputs
+                .into_iter()
+                .enumerate()
+                .map(|(idx, input)| LocalInput::new(input, idx as ActorId).boxed_input())
+                .collect(),
             SharedContext::for_test().into(),
             810,
             StreamingMetrics::unused().into(),
@@ -124,6 +129,7 @@ impl MergeExecutor {
                         .with_label_values(&[&actor_id_str])
                         .inc_by(chunk.cardinality() as _);
                 }
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     tracing::trace!(
                         target: "events::barrier::path",
@@ -203,6 +209,10 @@ pub struct SelectReceivers {
     barrier: Option<Barrier>,
     last_base: usize,
     actor_id: u32,
+    /// The latest watermarks of each column from each upstream.
+    watermarks: HashMap<usize, HashMap<ActorId, ScalarImpl>>,
+    /// The latest watermarks of each column emitted, which are the minimum of the upstreams.
+    emitted_watermarks: HashMap<usize, ScalarImpl>,
 }
 
 impl SelectReceivers {
@@ -215,6 +225,8 @@ impl SelectReceivers {
             last_base: 0,
             actor_id,
             barrier: None,
+            watermarks: HashMap::new(),
+            emitted_watermarks: HashMap::new(),
         }
     }
 
@@ -234,8 +246,39 @@ impl SelectReceivers {
 
         self.upstreams
             .retain(|u| !upstream_actor_ids.contains(&u.actor_id()));
+        for watermarks in self.watermarks.values_mut() {
+            watermarks.retain(|actor_id, _| !upstream_actor_ids.contains(actor_id));
+        }
         self.last_base = 0;
     }
+
+    /// Buffer the watermark from an upstream, and return the watermark to emit if the minimum
+    /// watermark of all the upstreams advances.
+    fn handle_watermark(
+        &mut self,
+        upstream_actor_id: ActorId,
+        watermark: Watermark,
+    ) -> Option<Watermark> {
+        let num_upstreams = self.upstreams.len() + self.blocks.len();
+        let watermarks = self.watermarks.entry(watermark.col_idx).or_default();
+        watermarks.insert(upstream_actor_id, watermark.val.clone());
+        if watermarks.len() < num_upstreams {
+            return None;
+        }
+
+        let min_watermark = watermarks.values().min().unwrap().clone();
+        match self.emitted_watermarks.get(&watermark.col_idx) {
+            Some(emitted) if emitted >= &min_watermark => None,
+            _ => {
+                self.emitted_watermarks
+                    .insert(watermark.col_idx, min_watermark.clone());
+                Some(Watermark {
+                    val: min_watermark,
+                    ..watermark
+                })
+            }
+        }
+    }
 }
 
 impl Stream for SelectReceivers {
@@ -273,6 +316,14 @@ impl Stream for SelectReceivers {
                         self.last_base = (idx + 1) % self.upstreams.len();
                         return Poll::Ready(Some(Ok(message)));
                     }
+                    Some(Ok(Message::Watermark(watermark))) => {
+                        let upstream_actor_id = self.upstreams[idx].actor_id();
+                        if let Some(watermark) = self.handle_watermark(upstream_actor_id, watermark)
+                        {
+                            self.last_base = (idx + 1) % self.upstreams.len();
+                            return Poll::Ready(Some(Ok(Message::Watermark(watermark))));
+                        }
+                    }
                 },
             }
         }
@@ -328,6 +379,38 @@ mod tests {
         StreamChunk::new(ops, vec![], None)
     }
 
+    #[tokio::test]
+    async fn test_merger_watermark() {
+        let (tx1, rx1) = tokio::sync::mpsc::channel(16);
+        let (tx2, rx2) = tokio::sync::mpsc::channel(16);
+        let merger = MergeExecutor::for_test(vec![rx1, rx2]);
+        let mut merger = merger.boxed().execute();
+
+        let watermark =
+            |val: i64| Message::Watermark(Watermark::new(0, DataType::Int64, val.into()));
+
+        tx1.send(watermark(10)).await.unwrap();
+        tx2.send(watermark(5)).await.unwrap();
+        // The minimum of the upstreams is emitted.
+        assert_eq!(merger.next().await.unwrap().unwrap(), watermark(5));
+
+        tx2.send(watermark(20)).await.unwrap();
+        assert_eq!(merger.next().await.unwrap().unwrap(), watermark(10));
+
+        // The watermark is not emitted if the minimum doesn't advance.
+        tx2.send(watermark(30)).await.unwrap();
+        tx1.send(watermark(25)).await.unwrap();
+        assert_eq!(merger.next().await.unwrap().unwrap(), watermark(25));
+
+        tx1.send(Message::Barrier(Barrier::new_test_barrier(1).with_stop()))
+            .await
+            .unwrap();
+        tx2.send(Message::Barrier(Barrier::new_test_barrier(1).with_stop()))
+            .await
+            .unwrap();
+        assert!(merger.next().await.unwrap().unwrap().is_stop());
+    }
+
     #[tokio::test]
     async fn test_merger() {
         const CHANNEL_NUMBER: usize = 10;
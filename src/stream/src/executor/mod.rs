@@ -26,18 +26,21 @@ use risingwave_common::array::column::Column;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_common::util::epoch::EpochPair;
+use risingwave_common::util::value_encoding::{deserialize_datum, serialize_datum_to_bytes};
 use risingwave_connector::source::SplitImpl;
+use risingwave_pb::catalog::{Source as ProstSource, Table as ProstTable};
 use risingwave_pb::data::Epoch as ProstEpoch;
 use risingwave_pb::stream_plan::add_mutation::Dispatchers;
 use risingwave_pb::stream_plan::barrier::Mutation as ProstMutation;
 use risingwave_pb::stream_plan::stream_message::StreamMessage;
 use risingwave_pb::stream_plan::update_mutation::{DispatcherUpdate, MergeUpdate};
 use risingwave_pb::stream_plan::{
-    AddMutation, Barrier as ProstBarrier, Dispatcher as ProstDispatcher, PauseMutation,
-    ResumeMutation, SourceChangeSplitMutation, StopMutation, StreamMessage as ProstStreamMessage,
-    UpdateMutation,
+    AddMutation, AlterTableMutation, Barrier as ProstBarrier, Dispatcher as ProstDispatcher,
+    PauseMutation, ResumeMutation, SourceChangeSplitMutation, StopMutation,
+    StreamMessage as ProstStreamMessage, TruncateTableMutation, UpdateMutation,
+    Watermark as ProstWatermark,
 };
 use smallvec::SmallVec;
 
@@ -61,22 +64,26 @@ mod global_simple_agg;
 mod hash_agg;
 pub mod hash_join;
 mod hop_window;
+mod interval_join;
 mod local_simple_agg;
 mod lookup;
 mod lookup_union;
 mod managed_state;
 mod merge;
 mod mview;
+mod over_window;
 mod project;
 mod project_set;
 mod rearranged_chain;
 mod receiver;
+mod session_window;
 mod simple;
 mod sink;
 pub mod source;
 pub mod subtask;
 mod top_n;
 mod union;
+mod watermark_filter;
 mod wrapper;
 
 #[cfg(test)]
@@ -97,22 +104,26 @@ pub use global_simple_agg::GlobalSimpleAggExecutor;
 pub use hash_agg::HashAggExecutor;
 pub use hash_join::*;
 pub use hop_window::HopWindowExecutor;
+pub use interval_join::{IntervalJoinExecutor, IntervalJoinParams};
 pub use local_simple_agg::LocalSimpleAggExecutor;
 pub use lookup::*;
 pub use lookup_union::LookupUnionExecutor;
 pub use managed_state::join::JoinManagedCache;
 pub use merge::MergeExecutor;
 pub use mview::*;
+pub use over_window::OverWindowExecutor;
 pub use project::ProjectExecutor;
 pub use project_set::*;
 pub use rearranged_chain::RearrangedChainExecutor;
 pub use receiver::ReceiverExecutor;
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
+pub use session_window::SessionWindowExecutor;
 use simple::{SimpleExecutor, SimpleExecutorWrapper};
 pub use sink::SinkExecutor;
 pub use source::*;
 pub use top_n::{AppendOnlyTopNExecutor, GroupTopNExecutor, TopNExecutor};
 pub use union::UnionExecutor;
+pub use watermark_filter::WatermarkFilterExecutor;
 pub use wrapper::WrapperExecutor;
 
 use self::barrier_align::AlignedMessageStream;
@@ -204,6 +215,11 @@ pub enum Mutation {
     SourceChangeSplit(HashMap<ActorId, Vec<SplitImpl>>),
     Pause,
     Resume,
+    AlterTable {
+        source: ProstSource,
+        table: ProstTable,
+    },
+    TruncateTable(u32),
 }
 
 #[derive(Debug, Clone)]
@@ -291,6 +307,22 @@ impl Barrier {
             })
     }
 
+    /// Returns the new table catalog if this barrier is to alter the schema of the table with
+    /// `table_id`.
+    pub fn as_alter_table(&self, table_id: u32) -> Option<&ProstTable> {
+        self.mutation
+            .as_deref()
+            .and_then(|mutation| match mutation {
+                Mutation::AlterTable { table, .. } if table.id == table_id => Some(table),
+                _ => None,
+            })
+    }
+
+    /// Whether this barrier is to truncate the table with `table_id`.
+    pub fn is_truncate_table(&self, table_id: u32) -> bool {
+        matches!(self.mutation.as_deref(), Some(Mutation::TruncateTable(id)) if *id == table_id)
+    }
+
     /// Returns the new vnode bitmap if this barrier is to update the vnode bitmap for the actor
     /// with `actor_id`.
     ///
@@ -387,6 +419,17 @@ impl Mutation {
             }
             Mutation::Pause => ProstMutation::Pause(PauseMutation {}),
             Mutation::Resume => ProstMutation::Resume(ResumeMutation {}),
+            Mutation::AlterTable { source, table } => {
+                ProstMutation::AlterTable(AlterTableMutation {
+                    source: Some(source.clone()),
+                    table: Some(table.clone()),
+                })
+            }
+            Mutation::TruncateTable(table_id) => {
+                ProstMutation::TruncateTable(TruncateTableMutation {
+                    table_id: *table_id,
+                })
+            }
         }
     }
 
@@ -472,6 +515,11 @@ impl Mutation {
             }
             ProstMutation::Pause(_) => Mutation::Pause,
             ProstMutation::Resume(_) => Mutation::Resume,
+            ProstMutation::AlterTable(alter) => Mutation::AlterTable {
+                source: alter.get_source()?.clone(),
+                table: alter.get_table()?.clone(),
+            },
+            ProstMutation::TruncateTable(truncate) => Mutation::TruncateTable(truncate.table_id),
         };
         Ok(mutation)
     }
@@ -515,10 +563,51 @@ impl Barrier {
     }
 }
 
+/// A watermark of a column, which promises that no rows with the column less than the value will
+/// arrive afterwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub col_idx: usize,
+    pub data_type: DataType,
+    pub val: ScalarImpl,
+}
+
+impl Watermark {
+    pub fn new(col_idx: usize, data_type: DataType, val: ScalarImpl) -> Self {
+        Self {
+            col_idx,
+            data_type,
+            val,
+        }
+    }
+
+    /// Returns the watermark of the same value for another column.
+    pub fn with_idx(self, col_idx: usize) -> Self {
+        Self { col_idx, ..self }
+    }
+
+    pub fn to_protobuf(&self) -> ProstWatermark {
+        ProstWatermark {
+            column_idx: self.col_idx as u32,
+            data_type: Some(self.data_type.to_protobuf()),
+            val: serialize_datum_to_bytes(Some(&self.val)),
+        }
+    }
+
+    pub fn from_protobuf(prost: &ProstWatermark) -> StreamResult<Self> {
+        let data_type = DataType::from(prost.get_data_type()?);
+        let val = deserialize_datum(&prost.val[..], &data_type)
+            .context("failed to deserialize watermark")?
+            .context("watermark should not be null")?;
+        Ok(Self::new(prost.column_idx as usize, data_type, val))
+    }
+}
+
 #[derive(Debug, EnumAsInner, PartialEq)]
 pub enum Message {
     Chunk(StreamChunk),
     Barrier(Barrier),
+    Watermark(Watermark),
 }
 
 impl<'a> TryFrom<&'a Message> for &'a Barrier {
@@ -526,7 +615,7 @@ impl<'a> TryFrom<&'a Message> for &'a Barrier {
 
     fn try_from(m: &'a Message) -> std::result::Result<Self, Self::Error> {
         match m {
-            Message::Chunk(_) => Err(()),
+            Message::Chunk(_) | Message::Watermark(_) => Err(()),
             Message::Barrier(b) => Ok(b),
         }
     }
@@ -555,6 +644,7 @@ impl Message {
                 StreamMessage::StreamChunk(prost_stream_chunk)
             }
             Self::Barrier(barrier) => StreamMessage::Barrier(barrier.clone().to_protobuf()),
+            Self::Watermark(watermark) => StreamMessage::Watermark(watermark.to_protobuf()),
         };
         let prost_stream_msg = ProstStreamMessage {
             stream_message: Some(prost),
@@ -570,6 +660,9 @@ impl Message {
             StreamMessage::Barrier(ref barrier) => {
                 Message::Barrier(Barrier::from_protobuf(barrier)?)
             }
+            StreamMessage::Watermark(ref watermark) => {
+                Message::Watermark(Watermark::from_protobuf(watermark)?)
+            }
         };
         Ok(res)
     }
@@ -65,6 +65,7 @@ pub struct StreamingMetrics {
     pub barrier_sync_latency: Histogram,
 
     pub sink_commit_duration: HistogramVec,
+    pub sink_row_count: GenericCounterVec<AtomicU64>,
 }
 
 impl StreamingMetrics {
@@ -330,6 +331,13 @@ impl StreamingMetrics {
             registry
         )
         .unwrap();
+        let sink_row_count = register_int_counter_vec_with_registry!(
+            "sink_row_count",
+            "Total number of rows written to sink",
+            &["executor_id", "connector"],
+            registry
+        )
+        .unwrap();
 
         Self {
             registry,
@@ -366,6 +374,7 @@ impl StreamingMetrics {
             barrier_inflight_latency,
             barrier_sync_latency,
             sink_commit_duration,
+            sink_row_count,
         }
     }
 
@@ -127,6 +127,8 @@ impl<S: StateStore> MaterializeExecutor<S> {
         for msg in input {
             let msg = msg?;
             yield match msg {
+                // Watermarks are not used by the downstream of materialized views yet.
+                Message::Watermark(_) => continue,
                 Message::Chunk(chunk) => {
                     self.state_table.write_chunk(chunk.clone());
                     Message::Chunk(chunk)
@@ -139,6 +141,18 @@ impl<S: StateStore> MaterializeExecutor<S> {
                         let _ = self.state_table.update_vnode_bitmap(vnode_bitmap);
                     }
 
+                    // Update the columns for the state table if the table is altered. Chunks after
+                    // this barrier will contain the appended columns.
+                    if let Some(table) = b.as_alter_table(self.state_table.table_id().table_id) {
+                        self.state_table.update_columns(table);
+                    }
+
+                    // Delete all the rows written before this barrier if the table is truncated.
+                    // The table has no downstream, so there's no need to emit the deletions.
+                    if b.is_truncate_table(self.state_table.table_id().table_id) {
+                        self.state_table.truncate().await?;
+                    }
+
                     Message::Barrier(b)
                 }
             }
@@ -284,4 +298,103 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_materialize_executor_truncate() {
+        let memory_state_store = MemoryStateStore::new();
+        let table_id = TableId::new(1);
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let column_ids = vec![0.into(), 1.into()];
+
+        let chunk1 = StreamChunk::from_pretty(
+            " i i
+            + 1 4
+            + 2 5",
+        );
+        // Rows after the truncation are kept, including the ones with truncated keys.
+        let chunk2 = StreamChunk::from_pretty(
+            " i i
+            + 1 7
+            + 3 6",
+        );
+
+        let source = MockSource::with_messages(
+            schema.clone(),
+            PkIndices::new(),
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk1),
+                Message::Barrier(
+                    Barrier::new_test_barrier(2)
+                        .with_mutation(Mutation::TruncateTable(table_id.table_id)),
+                ),
+                Message::Chunk(chunk2),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        );
+
+        let column_descs = vec![
+            ColumnDesc::unnamed(column_ids[0], DataType::Int32),
+            ColumnDesc::unnamed(column_ids[1], DataType::Int32),
+        ];
+        let table = StorageTable::for_test(
+            memory_state_store.clone(),
+            table_id,
+            column_descs,
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+
+        let mut materialize_executor = Box::new(MaterializeExecutor::for_test(
+            Box::new(source),
+            memory_state_store,
+            table_id,
+            vec![OrderPair::new(0, OrderType::Ascending)],
+            column_ids,
+            1,
+        ))
+        .execute();
+        materialize_executor.next().await.transpose().unwrap();
+        materialize_executor.next().await.transpose().unwrap();
+
+        let get_row = |pk: i32| {
+            let table = &table;
+            async move {
+                table
+                    .get_row(
+                        &Row(vec![Some(pk.into())]),
+                        HummockReadEpoch::NoWait(u64::MAX),
+                    )
+                    .await
+                    .unwrap()
+            }
+        };
+
+        // The truncation barrier. All the rows written before are deleted.
+        match materialize_executor.next().await.transpose().unwrap() {
+            Some(Message::Barrier(_)) => {
+                assert_eq!(get_row(1).await, None);
+                assert_eq!(get_row(2).await, None);
+            }
+            _ => unreachable!(),
+        }
+        materialize_executor.next().await.transpose().unwrap();
+        match materialize_executor.next().await.transpose().unwrap() {
+            Some(Message::Barrier(_)) => {
+                assert_eq!(
+                    get_row(1).await,
+                    Some(Row(vec![Some(1_i32.into()), Some(7_i32.into())]))
+                );
+                assert_eq!(get_row(2).await, None);
+                assert_eq!(
+                    get_row(3).await,
+                    Some(Row(vec![Some(3_i32.into()), Some(6_i32.into())]))
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
 }
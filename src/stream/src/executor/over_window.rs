@@ -0,0 +1,395 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::util::sort_util::{compare_rows, OrderPair, OrderType};
+use risingwave_expr::window_function::{Partition, WindowFuncCall};
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor,
+    ExecutorInfo, Message, PkIndicesRef, StreamExecutorResult,
+};
+use crate::cache::{cache_may_stale, EvictableHashMap, ExecutorCache, LruManagerRef};
+
+/// [`OverWindowExecutor`] evaluates window functions (`.. OVER (PARTITION BY .. ORDER BY ..)`) on
+/// its input and appends the results to each row.
+///
+/// All input rows are kept in the state table, ordered by the partition key, the `ORDER BY` of
+/// the window and the stream key. When a chunk arrives, every partition it touches is evaluated
+/// before and after applying the changes, and the differences of the output rows are emitted. The
+/// sorted rows of the recently touched partitions are cached, so that a partition is only loaded
+/// from the state table when it's missing in the cache.
+pub struct OverWindowExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    input: BoxedExecutor,
+    info: ExecutorInfo,
+
+    calls: Vec<WindowFuncCall>,
+    partition_by: Vec<usize>,
+    order_by: Vec<OrderPair>,
+
+    /// The order of rows within a partition, i.e. `order_by` followed by the stream key, which
+    /// identifies a row uniquely.
+    row_order: Vec<OrderPair>,
+    state_table: StateTable<S>,
+
+    /// Cache of the partitions, from the partition key to the rows sorted by `row_order`.
+    cache: ExecutorCache<Row, Vec<Row>>,
+}
+
+impl<S: StateStore> OverWindowExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        executor_id: u64,
+        calls: Vec<WindowFuncCall>,
+        partition_by: Vec<usize>,
+        order_by: Vec<OrderPair>,
+        state_table: StateTable<S>,
+        cache_size: usize,
+        lru_manager: Option<LruManagerRef>,
+    ) -> Self {
+        let input_info = input.info();
+        let mut schema = input_info.schema;
+        calls.iter().for_each(|call| {
+            schema.fields.push(Field::unnamed(call.return_type.clone()));
+        });
+
+        let mut row_order = order_by.clone();
+        for &idx in &input_info.pk_indices {
+            if !row_order.iter().any(|pair| pair.column_idx == idx) {
+                row_order.push(OrderPair::new(idx, OrderType::Ascending));
+            }
+        }
+
+        Self {
+            ctx,
+            input,
+            info: ExecutorInfo {
+                schema,
+                pk_indices: input_info.pk_indices,
+                identity: format!("OverWindowExecutor {:X}", executor_id),
+            },
+            calls,
+            partition_by,
+            order_by,
+            row_order,
+            state_table,
+            cache: match lru_manager {
+                Some(lru_manager) => ExecutorCache::Managed(lru_manager.create_cache()),
+                None => ExecutorCache::Local(EvictableHashMap::new(cache_size)),
+            },
+        }
+    }
+}
+
+impl<S: StateStore> Executor for OverWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+impl<S: StateStore> OverWindowExecutor<S> {
+    /// Loads all rows of a partition from the state table, sorted by `row_order`.
+    async fn load_partition(&self, partition_key: &Row) -> StreamExecutorResult<Vec<Row>> {
+        let mut rows = vec![];
+        let state_rows = self.state_table.iter_with_pk_prefix(partition_key).await?;
+        pin_mut!(state_rows);
+        while let Some(row) = state_rows.next().await {
+            rows.push(row?.into_owned());
+        }
+        self.sort_rows(&mut rows)?;
+        Ok(rows)
+    }
+
+    fn sort_rows(&self, rows: &mut [Row]) -> StreamExecutorResult<()> {
+        let mut error = None;
+        rows.sort_by(|a, b| {
+            compare_rows(a, b, &self.row_order).unwrap_or_else(|e| {
+                error.get_or_insert(e);
+                std::cmp::Ordering::Equal
+            })
+        });
+        match error {
+            Some(e) => Err(anyhow::anyhow!(e.to_string()).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Evaluates the window functions over a partition and returns the output rows, keyed by the
+    /// stream key.
+    fn evaluate_partition(&self, rows: &[Row]) -> StreamExecutorResult<Vec<(Row, Row)>> {
+        if rows.is_empty() {
+            return Ok(vec![]);
+        }
+        let partition = Partition::new(rows, &self.order_by)?;
+        let outputs = self
+            .calls
+            .iter()
+            .map(|call| call.evaluate_partition(&partition))
+            .collect::<risingwave_expr::Result<Vec<_>>>()?;
+        Ok(rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut datums = row.0.clone();
+                datums.extend(outputs.iter().map(|output| output[i].clone()));
+                (row.by_indices(&self.info.pk_indices), Row::new(datums))
+            })
+            .collect())
+    }
+
+    async fn apply_chunk(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        // Group the changes by partition, in the order of their first appearance.
+        let mut partition_changes: Vec<(Row, Vec<(Op, Row)>)> = vec![];
+        let mut partition_positions: HashMap<Row, usize> = HashMap::new();
+        for (op, row_ref) in chunk.rows() {
+            let row = row_ref.to_owned_row();
+            let partition_key = row.by_indices(&self.partition_by);
+            let pos = *partition_positions
+                .entry(partition_key.clone())
+                .or_insert_with(|| {
+                    partition_changes.push((partition_key, vec![]));
+                    partition_changes.len() - 1
+                });
+            partition_changes[pos].1.push((op, row));
+        }
+
+        let mut output = vec![];
+        for (partition_key, changes) in partition_changes {
+            let mut rows = match self.cache.pop(&partition_key) {
+                Some(rows) => rows,
+                None => self.load_partition(&partition_key).await?,
+            };
+            let old_outputs = self.evaluate_partition(&rows)?;
+
+            for (op, row) in changes {
+                let pos = rows.binary_search_by(|probe| {
+                    compare_rows(probe, &row, &self.row_order).unwrap_or(std::cmp::Ordering::Equal)
+                });
+                match op {
+                    Op::Insert | Op::UpdateInsert => {
+                        let pos = pos.unwrap_or_else(|pos| pos);
+                        rows.insert(pos, row.clone());
+                        self.state_table.insert(row);
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        if let Ok(pos) = pos {
+                            rows.remove(pos);
+                        }
+                        self.state_table.delete(row);
+                    }
+                }
+            }
+            let new_outputs = self.evaluate_partition(&rows)?;
+            self.cache.put(partition_key, rows);
+
+            // Emit the differences between the old and new output of the partition.
+            let mut old_outputs: HashMap<Row, Row> = old_outputs.into_iter().collect();
+            let mut inserts = vec![];
+            for (key, new_row) in new_outputs {
+                match old_outputs.remove(&key) {
+                    Some(old_row) if old_row == new_row => {}
+                    Some(old_row) => {
+                        inserts.push((Op::UpdateDelete, old_row));
+                        inserts.push((Op::UpdateInsert, new_row));
+                    }
+                    None => inserts.push((Op::Insert, new_row)),
+                }
+            }
+            output.extend(
+                old_outputs
+                    .into_values()
+                    .map(|old_row| (Op::Delete, old_row)),
+            );
+            output.extend(inserts);
+        }
+
+        if output.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(StreamChunk::from_rows(
+                &output,
+                &self.info.schema.data_types(),
+            )))
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self: Box<Self>) {
+        let mut input = self.input.execute();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        self.cache.update_epoch(barrier.epoch.curr);
+
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            let msg = msg?;
+            match msg {
+                Message::Chunk(chunk) => {
+                    if let Some(chunk) = self.apply_chunk(chunk).await? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                Message::Watermark(_) => {}
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(barrier.epoch).await?;
+                    self.cache.evict();
+
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        let previous_vnode_bitmap =
+                            self.state_table.update_vnode_bitmap(vnode_bitmap.clone());
+                        if cache_may_stale(&previous_vnode_bitmap, &vnode_bitmap) {
+                            self.cache.clear();
+                        }
+                    }
+                    self.cache.update_epoch(barrier.epoch.curr);
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::types::DataType;
+    use risingwave_expr::window_function::window_function_prost;
+    use risingwave_pb::expr::agg_call::Type as AggTypeProst;
+    use risingwave_pb::expr::window_function::Type as WindowFunctionTypeProst;
+
+    use super::*;
+    use crate::executor::test_utils::top_n_executor::create_in_memory_state_table;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::{ActorContext, Barrier};
+
+    #[tokio::test]
+    async fn test_over_window_executor() {
+        // (partition, order, pk)
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let source = MockSource::with_messages(
+            schema,
+            vec![2],
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I I I
+                    + 1 2 1
+                    + 1 4 2
+                    + 2 1 3",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I I I
+                    + 1 1 4
+                    - 2 1 3",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        );
+        let state_table = create_in_memory_state_table(
+            &[DataType::Int64, DataType::Int64, DataType::Int64],
+            &[
+                OrderType::Ascending,
+                OrderType::Ascending,
+                OrderType::Ascending,
+            ],
+            &[0, 1, 2],
+        );
+        let row_number = window_function_prost(
+            WindowFunctionTypeProst::RowNumber,
+            AggTypeProst::Unspecified,
+            None,
+            DataType::Int64,
+            None,
+        );
+        let executor = Box::new(OverWindowExecutor::new(
+            ActorContext::create(0),
+            Box::new(source),
+            1,
+            vec![WindowFuncCall::from_protobuf(&row_number).unwrap()],
+            vec![0],
+            vec![OrderPair::new(1, OrderType::Ascending)],
+            state_table,
+            1024,
+            None,
+        ));
+        let mut executor = executor.execute();
+
+        // consume the init barrier
+        executor.next().await.unwrap().unwrap();
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                " I I I I
+                + 1 2 1 1
+                + 1 4 2 2
+                + 2 1 3 1",
+            ),
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                "  I I I I
+                +  1 1 4 1
+                U- 1 2 1 1
+                U+ 1 2 1 2
+                U- 1 4 2 2
+                U+ 1 4 2 3
+                -  2 1 3 1",
+            ),
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+}
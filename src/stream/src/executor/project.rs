@@ -16,13 +16,13 @@ use std::fmt::{Debug, Formatter};
 
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::StreamChunk;
+use risingwave_common::array::{Row, StreamChunk};
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_expr::expr::BoxedExpression;
 
 use super::{
     ActorContextRef, Executor, ExecutorInfo, PkIndices, PkIndicesRef, SimpleExecutor,
-    SimpleExecutorWrapper, StreamExecutorResult,
+    SimpleExecutorWrapper, StreamExecutorResult, Watermark,
 };
 use crate::common::InfallibleExpression;
 
@@ -35,6 +35,7 @@ impl ProjectExecutor {
         pk_indices: PkIndices,
         exprs: Vec<BoxedExpression>,
         execuotr_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
     ) -> Self {
         let info = ExecutorInfo {
             schema: input.schema().to_owned(),
@@ -43,7 +44,7 @@ impl ProjectExecutor {
         };
         SimpleExecutorWrapper {
             input,
-            inner: SimpleProjectExecutor::new(ctx, info, exprs, execuotr_id),
+            inner: SimpleProjectExecutor::new(ctx, info, exprs, execuotr_id, watermark_derivations),
         }
     }
 }
@@ -54,9 +55,15 @@ impl ProjectExecutor {
 pub struct SimpleProjectExecutor {
     ctx: ActorContextRef,
     info: ExecutorInfo,
+    input_len: usize,
 
     /// Expressions of the current projection.
     exprs: Vec<BoxedExpression>,
+
+    /// Pairs of `(input_col_idx, output_col_idx)`, where the output expression is monotonic on
+    /// the input column, so that the watermark of the output column can be derived by evaluating
+    /// the expression on the watermark of the input column.
+    watermark_derivations: Vec<(usize, usize)>,
 }
 
 impl SimpleProjectExecutor {
@@ -65,6 +72,7 @@ impl SimpleProjectExecutor {
         input_info: ExecutorInfo,
         exprs: Vec<BoxedExpression>,
         executor_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
     ) -> Self {
         let schema = Schema {
             fields: exprs
@@ -74,12 +82,14 @@ impl SimpleProjectExecutor {
         };
         Self {
             ctx,
+            input_len: input_info.schema.len(),
             info: ExecutorInfo {
                 schema,
                 pk_indices: input_info.pk_indices,
                 identity: format!("ProjectExecutor {:X}", executor_id),
             },
             exprs,
+            watermark_derivations,
         }
     }
 }
@@ -115,6 +125,30 @@ impl SimpleExecutor for SimpleProjectExecutor {
         Ok(Some(new_chunk))
     }
 
+    fn handle_watermark(&mut self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>> {
+        let mut input_row = None;
+        let mut ret = vec![];
+        for &(input_idx, output_idx) in &self.watermark_derivations {
+            if input_idx != watermark.col_idx {
+                continue;
+            }
+            // Only the watermark column is set, since the expression only depends on it.
+            let input_row = input_row.get_or_insert_with(|| {
+                let mut row = Row::new(vec![None; self.input_len]);
+                row.0[input_idx] = Some(watermark.val.clone());
+                row
+            });
+            let expr = &self.exprs[output_idx];
+            let val = expr.eval_row_infallible(input_row, |err| {
+                self.ctx.on_compute_error(err, &self.info.identity)
+            });
+            if let Some(val) = val {
+                ret.push(Watermark::new(output_idx, expr.return_type(), val));
+            }
+        }
+        Ok(ret)
+    }
+
     fn schema(&self) -> &Schema {
         &self.info.schema
     }
@@ -136,7 +170,7 @@ mod tests {
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::types::DataType;
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
-    use risingwave_expr::expr::InputRefExpression;
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
     use risingwave_pb::expr::expr_node::Type;
 
     use super::super::test_utils::MockSource;
@@ -180,6 +214,7 @@ mod tests {
             vec![],
             vec![test_expr],
             1,
+            vec![],
         ));
         let mut project = project.execute();
 
@@ -206,4 +241,60 @@ mod tests {
 
         assert!(project.next().await.unwrap().unwrap().is_stop());
     }
+
+    #[tokio::test]
+    async fn test_watermark_projection() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, PkIndices::new());
+
+        let a_expr = InputRefExpression::new(DataType::Int64, 0);
+        let b_expr = InputRefExpression::new(DataType::Int64, 1);
+        let a_plus_one = new_binary_expr(
+            Type::Add,
+            DataType::Int64,
+            Box::new(InputRefExpression::new(DataType::Int64, 0)),
+            Box::new(LiteralExpression::new(DataType::Int64, Some(1_i64.into()))),
+        )
+        .unwrap();
+
+        let project = Box::new(ProjectExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            vec![],
+            vec![Box::new(a_expr), Box::new(b_expr), a_plus_one],
+            1,
+            vec![(0, 0), (0, 2)],
+        ));
+        let mut project = project.execute();
+
+        tx.push_barrier(1, false);
+        tx.push_watermark(0, DataType::Int64, 10_i64.into());
+        tx.push_watermark(1, DataType::Int64, 20_i64.into());
+        tx.push_barrier(2, true);
+
+        project
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+        let w1 = project.next().await.unwrap().unwrap();
+        assert_eq!(
+            w1.into_watermark().unwrap(),
+            Watermark::new(0, DataType::Int64, 10_i64.into())
+        );
+        let w2 = project.next().await.unwrap().unwrap();
+        assert_eq!(
+            w2.into_watermark().unwrap(),
+            Watermark::new(2, DataType::Int64, 11_i64.into())
+        );
+        // The watermark of column 1 can't be derived.
+        assert!(project.next().await.unwrap().unwrap().is_stop());
+    }
 }
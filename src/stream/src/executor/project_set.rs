@@ -191,6 +191,8 @@ impl ProjectSetExecutor {
 
                     yield Message::Chunk(StreamChunk::from_parts(ret_ops, chunk));
                 }
+                // TODO: derive the watermarks of the columns passed through.
+                Message::Watermark(_) => {}
                 m => yield m,
             }
         }
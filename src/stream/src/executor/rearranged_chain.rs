@@ -84,6 +84,7 @@ impl RearrangedMessage {
         match msg {
             Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::RearrangedBarrier(barrier),
+            Message::Watermark(_) => unreachable!("watermarks are not emitted by the snapshot"),
         }
     }
 
@@ -91,6 +92,9 @@ impl RearrangedMessage {
         match msg {
             Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::PhantomBarrier(barrier),
+            Message::Watermark(_) => {
+                unreachable!("watermarks are not emitted by the upstream materialized view")
+            }
         }
     }
 }
@@ -133,6 +133,7 @@ impl Executor for ReceiverExecutor {
                             .with_label_values(&[&actor_id_str])
                             .inc_by(chunk.cardinality() as _);
                     }
+                    Message::Watermark(_) => {}
                     Message::Barrier(barrier) => {
                         tracing::trace!(
                             target: "events::barrier::path",
@@ -0,0 +1,584 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{
+    CheckedAdd, DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl,
+};
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::table::DEFAULT_VNODE;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor,
+    ExecutorInfo, Message, PkIndicesRef, StreamExecutorResult,
+};
+
+type Session = (NaiveDateTimeWrapper, NaiveDateTimeWrapper);
+
+/// [`SessionWindowExecutor`] appends the `window_start` and `window_end` of the session to each
+/// row. Rows whose times are less than `gap` apart belong to the same session, whose
+/// `window_start` is the earliest time and `window_end` is the latest time plus `gap`.
+///
+/// All input rows with non-null time are kept in the state table, ordered by the time and the
+/// stream key, and the sessions are cached in memory. A change only affects the sessions that
+/// overlap with `[time, time + gap)`, so the rows of them are loaded from the state table and
+/// assigned to sessions before and after applying the change, and the differences of the output
+/// rows are emitted. For example, a late row may bridge two sessions, in which case the rows of
+/// both are retracted and emitted again with the merged session.
+///
+/// Rows with time less than the watermark will never arrive again, so a session ending no later
+/// than the watermark can never change. The rows of such sessions are evicted from the state table
+/// on barriers, and the sessions are removed from memory.
+pub struct SessionWindowExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    input: BoxedExecutor,
+    info: ExecutorInfo,
+
+    time_col_idx: usize,
+    gap: IntervalUnit,
+
+    state_table: StateTable<S>,
+    /// The sessions of the rows in the state table, from `window_start` to `window_end`.
+    sessions: BTreeMap<NaiveDateTimeWrapper, NaiveDateTimeWrapper>,
+    /// The watermark of the time column from upstream. Without watermarks, nothing is evicted.
+    watermark: Option<NaiveDateTimeWrapper>,
+}
+
+impl<S: StateStore> SessionWindowExecutor<S> {
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        executor_id: u64,
+        time_col_idx: usize,
+        gap: IntervalUnit,
+        mut state_table: StateTable<S>,
+    ) -> Self {
+        // Rows may be deleted from the upstream after they are evicted.
+        state_table.disable_sanity_check();
+
+        let input_info = input.info();
+        let mut schema = input_info.schema;
+        schema.fields.extend([
+            Field::with_name(DataType::Timestamp, "window_start"),
+            Field::with_name(DataType::Timestamp, "window_end"),
+        ]);
+
+        Self {
+            ctx,
+            input,
+            info: ExecutorInfo {
+                schema,
+                pk_indices: input_info.pk_indices,
+                identity: format!("SessionWindowExecutor {:X}", executor_id),
+            },
+            time_col_idx,
+            gap,
+            state_table,
+            sessions: BTreeMap::new(),
+            watermark: None,
+        }
+    }
+}
+
+impl<S: StateStore> Executor for SessionWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+impl<S: StateStore> SessionWindowExecutor<S> {
+    fn time_of(&self, row: &Row) -> Option<NaiveDateTimeWrapper> {
+        row.0[self.time_col_idx]
+            .as_ref()
+            .map(|time| *time.as_naivedatetime())
+    }
+
+    fn end_of(&self, time: NaiveDateTimeWrapper) -> StreamExecutorResult<NaiveDateTimeWrapper> {
+        time.checked_add(self.gap).ok_or_else(|| {
+            anyhow::anyhow!("window_end of session overflows: {} + {}", time, self.gap).into()
+        })
+    }
+
+    /// Rebuilds the sessions from all rows in the state table, which only contains the rows of the
+    /// sessions not evicted yet.
+    async fn recover_sessions(&mut self) -> StreamExecutorResult<()> {
+        let mut times = vec![];
+        {
+            let state_rows = self.state_table.iter().await?;
+            pin_mut!(state_rows);
+            while let Some(row) = state_rows.next().await {
+                times.extend(self.time_of(&row?));
+            }
+        }
+        self.sessions = self.assign_sessions(times)?.into_iter().collect();
+        Ok(())
+    }
+
+    /// Splits the times, sorted in ascending order, into sessions. Returns the session of each
+    /// time.
+    fn assign_sessions(
+        &self,
+        times: impl IntoIterator<Item = NaiveDateTimeWrapper>,
+    ) -> StreamExecutorResult<Vec<Session>> {
+        let mut sessions: Vec<(Session, usize)> = vec![];
+        for time in times {
+            match sessions.last_mut() {
+                Some(((_, end), count)) if time < *end => {
+                    *end = self.end_of(time)?;
+                    *count += 1;
+                }
+                _ => sessions.push(((time, self.end_of(time)?), 1)),
+            }
+        }
+        Ok(sessions
+            .into_iter()
+            .flat_map(|(session, count)| std::iter::repeat(session).take(count))
+            .collect())
+    }
+
+    /// Assigns the rows, sorted by time, to sessions and returns the output rows, keyed by the
+    /// stream key, along with the distinct sessions.
+    fn evaluate_rows(&self, rows: &[Row]) -> StreamExecutorResult<(Vec<(Row, Row)>, Vec<Session>)> {
+        let mut sessions =
+            self.assign_sessions(rows.iter().map(|row| self.time_of(row).unwrap()))?;
+        let outputs = rows
+            .iter()
+            .zip(&sessions)
+            .map(|(row, (start, end))| {
+                let mut datums = row.0.clone();
+                datums.push(Some(ScalarImpl::NaiveDateTime(*start)));
+                datums.push(Some(ScalarImpl::NaiveDateTime(*end)));
+                (row.by_indices(&self.info.pk_indices), Row::new(datums))
+            })
+            .collect();
+        sessions.dedup();
+        Ok((outputs, sessions))
+    }
+
+    /// Applies a change of a row with non-null time, and appends the differences of the output
+    /// rows to `output`.
+    async fn apply_change(
+        &mut self,
+        op: Op,
+        row: Row,
+        time: NaiveDateTimeWrapper,
+        output: &mut Vec<(Op, Row)>,
+    ) -> StreamExecutorResult<()> {
+        // Only the sessions overlapping with `[time, time + gap)` can be merged by an insertion.
+        // For a deletion, that's exactly the session containing the row.
+        let mut lo = time;
+        let mut hi = self.end_of(time)?;
+        let affected: Vec<Session> = self
+            .sessions
+            .range(..hi)
+            .rev()
+            .take_while(|(_, end)| **end > time)
+            .map(|(start, end)| (*start, *end))
+            .collect();
+        if let Some((start, _)) = affected.last() {
+            lo = lo.min(*start);
+        }
+        if let Some((_, end)) = affected.first() {
+            hi = hi.max(*end);
+        }
+
+        let mut rows = vec![];
+        {
+            let range = (
+                Included(Row::new(vec![Some(ScalarImpl::NaiveDateTime(lo))])),
+                Excluded(Row::new(vec![Some(ScalarImpl::NaiveDateTime(hi))])),
+            );
+            let state_rows = self
+                .state_table
+                .iter_with_pk_range(&range, DEFAULT_VNODE)
+                .await?;
+            pin_mut!(state_rows);
+            while let Some(row) = state_rows.next().await {
+                rows.push(row?.into_owned());
+            }
+        }
+        let (old_outputs, _) = self.evaluate_rows(&rows)?;
+
+        match op {
+            Op::Insert | Op::UpdateInsert => {
+                let pos = rows.partition_point(|probe| self.time_of(probe).unwrap() <= time);
+                rows.insert(pos, row.clone());
+                self.state_table.insert(row);
+            }
+            Op::Delete | Op::UpdateDelete => {
+                if let Some(pos) = rows.iter().position(|probe| probe == &row) {
+                    rows.remove(pos);
+                }
+                self.state_table.delete(row);
+            }
+        }
+        let (new_outputs, new_sessions) = self.evaluate_rows(&rows)?;
+
+        for (start, _) in affected {
+            self.sessions.remove(&start);
+        }
+        self.sessions.extend(new_sessions);
+
+        // Emit the differences between the old and new output of the affected rows.
+        let mut old_outputs: HashMap<Row, Row> = old_outputs.into_iter().collect();
+        let mut inserts = vec![];
+        for (key, new_row) in new_outputs {
+            match old_outputs.remove(&key) {
+                Some(old_row) if old_row == new_row => {}
+                Some(old_row) => {
+                    inserts.push((Op::UpdateDelete, old_row));
+                    inserts.push((Op::UpdateInsert, new_row));
+                }
+                None => inserts.push((Op::Insert, new_row)),
+            }
+        }
+        output.extend(
+            old_outputs
+                .into_values()
+                .map(|old_row| (Op::Delete, old_row)),
+        );
+        output.extend(inserts);
+        Ok(())
+    }
+
+    /// Removes the sessions ending no later than the watermark, and deletes their rows from the
+    /// state table.
+    async fn evict(&mut self) -> StreamExecutorResult<()> {
+        let Some(watermark) = self.watermark else {
+            return Ok(());
+        };
+        // The sessions are disjoint, so the closed ones are the first sessions in the map, and
+        // all rows before the end of the last closed session belong to them.
+        let mut evicted_end = None;
+        while let Some(entry) = self.sessions.first_entry() {
+            if *entry.get() > watermark {
+                break;
+            }
+            evicted_end = Some(entry.remove());
+        }
+        let Some(evicted_end) = evicted_end else {
+            return Ok(());
+        };
+
+        let mut expired_rows = vec![];
+        {
+            let range = (
+                Unbounded,
+                Excluded(Row::new(vec![Some(ScalarImpl::NaiveDateTime(evicted_end))])),
+            );
+            let state_rows = self
+                .state_table
+                .iter_with_pk_range(&range, DEFAULT_VNODE)
+                .await?;
+            pin_mut!(state_rows);
+            while let Some(row) = state_rows.next().await {
+                expired_rows.push(row?.into_owned());
+            }
+        }
+        for row in expired_rows {
+            self.state_table.delete(row);
+        }
+        Ok(())
+    }
+
+    async fn apply_chunk(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let mut output = vec![];
+        for (op, row_ref) in chunk.rows() {
+            let row = row_ref.to_owned_row();
+            match self.time_of(&row) {
+                Some(time) => self.apply_change(op, row, time, &mut output).await?,
+                // Rows with null time don't belong to any session, so they're not stored.
+                None => {
+                    let mut datums = row.0;
+                    datums.extend([None, None]);
+                    output.push((op, Row::new(datums)));
+                }
+            }
+        }
+
+        if output.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(StreamChunk::from_rows(
+                &output,
+                &self.info.schema.data_types(),
+            )))
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self: Box<Self>) {
+        let mut input = self.input.execute();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        self.recover_sessions().await?;
+
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            let msg = msg?;
+            match msg {
+                Message::Chunk(chunk) => {
+                    if let Some(chunk) = self.apply_chunk(chunk).await? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                Message::Watermark(watermark) => {
+                    // The rows of the open sessions before the watermark may still be updated
+                    // when their sessions change, so the watermark is not propagated.
+                    if watermark.col_idx == self.time_col_idx {
+                        let time = *watermark.val.as_naivedatetime();
+                        if self.watermark.map_or(true, |w| time > w) {
+                            self.watermark = Some(time);
+                        }
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    self.evict().await?;
+                    self.state_table.commit(barrier.epoch).await?;
+
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        let _ = self.state_table.update_vnode_bitmap(vnode_bitmap);
+                        self.recover_sessions().await?;
+                    }
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use chrono::NaiveDateTime;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::util::sort_util::OrderType;
+
+    use super::*;
+    use crate::executor::test_utils::top_n_executor::create_in_memory_state_table;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::{ActorContext, Barrier, Watermark};
+
+    #[tokio::test]
+    async fn test_session_window_executor() {
+        // (id, time)
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Timestamp),
+            ],
+        };
+        let source = MockSource::with_messages(
+            schema,
+            vec![0],
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I TS
+                    + 1 2022-01-01T10:00:00
+                    + 2 2022-01-01T10:10:00
+                    + 3 .",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                // Row 5 bridges the two sessions.
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I TS
+                    + 4 2022-01-01T10:04:00
+                    + 5 2022-01-01T10:07:00",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I TS
+                    - 5 2022-01-01T10:07:00",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(4)),
+            ],
+        );
+        let state_table = create_in_memory_state_table(
+            &[DataType::Int64, DataType::Timestamp],
+            &[OrderType::Ascending, OrderType::Ascending],
+            &[1, 0],
+        );
+        let executor = Box::new(SessionWindowExecutor::new(
+            ActorContext::create(0),
+            Box::new(source),
+            1,
+            1,
+            IntervalUnit::from_minutes(5),
+            state_table,
+        ));
+        let mut executor = executor.execute();
+
+        // consume the init barrier
+        executor.next().await.unwrap().unwrap();
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                " I TS                  TS                  TS
+                + 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:05:00
+                + 2 2022-01-01T10:10:00 2022-01-01T10:10:00 2022-01-01T10:15:00
+                + 3 .                   .                   .",
+            ),
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                "  I TS                  TS                  TS
+                U- 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:05:00
+                U+ 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:09:00
+                +  4 2022-01-01T10:04:00 2022-01-01T10:00:00 2022-01-01T10:09:00
+                U- 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:09:00
+                U+ 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                U- 4 2022-01-01T10:04:00 2022-01-01T10:00:00 2022-01-01T10:09:00
+                U+ 4 2022-01-01T10:04:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                +  5 2022-01-01T10:07:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                U- 2 2022-01-01T10:10:00 2022-01-01T10:10:00 2022-01-01T10:15:00
+                U+ 2 2022-01-01T10:10:00 2022-01-01T10:00:00 2022-01-01T10:15:00",
+            ),
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // Deleting row 5 splits the session again.
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                "  I TS                  TS                  TS
+                -  5 2022-01-01T10:07:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                U- 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                U+ 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:09:00
+                U- 4 2022-01-01T10:04:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                U+ 4 2022-01-01T10:04:00 2022-01-01T10:00:00 2022-01-01T10:09:00
+                U- 2 2022-01-01T10:10:00 2022-01-01T10:00:00 2022-01-01T10:15:00
+                U+ 2 2022-01-01T10:10:00 2022-01-01T10:10:00 2022-01-01T10:15:00",
+            ),
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+    #[tokio::test]
+    async fn test_session_window_eviction() {
+        let timestamp = |s: &str| {
+            ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper::new(
+                NaiveDateTime::parse_from_str(&format!("2022-01-01 {}", s), "%Y-%m-%d %H:%M:%S")
+                    .unwrap(),
+            ))
+        };
+        // (id, time)
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Timestamp),
+            ],
+        };
+        let source = MockSource::with_messages(
+            schema,
+            vec![0],
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I TS
+                    + 1 2022-01-01T10:00:00
+                    + 2 2022-01-01T10:02:00
+                    + 3 2022-01-01T10:20:00",
+                )),
+                Message::Watermark(Watermark::new(
+                    1,
+                    DataType::Timestamp,
+                    timestamp("10:10:00"),
+                )),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                // A late row which should have been filtered by the upstream.
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I TS
+                    + 4 2022-01-01T10:06:00",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        );
+        let state_table = create_in_memory_state_table(
+            &[DataType::Int64, DataType::Timestamp],
+            &[OrderType::Ascending, OrderType::Ascending],
+            &[1, 0],
+        );
+        let executor = Box::new(SessionWindowExecutor::new(
+            ActorContext::create(0),
+            Box::new(source),
+            1,
+            1,
+            IntervalUnit::from_minutes(5),
+            state_table,
+        ));
+        let mut executor = executor.execute();
+
+        // consume the init barrier
+        executor.next().await.unwrap().unwrap();
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                "  I TS                  TS                  TS
+                +  1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:05:00
+                U- 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:05:00
+                U+ 1 2022-01-01T10:00:00 2022-01-01T10:00:00 2022-01-01T10:07:00
+                +  2 2022-01-01T10:02:00 2022-01-01T10:00:00 2022-01-01T10:07:00
+                +  3 2022-01-01T10:20:00 2022-01-01T10:20:00 2022-01-01T10:25:00",
+            ),
+        );
+        // The watermark is not propagated.
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // The session ending at 10:07 has been evicted on the barrier, so the late row starts a
+        // new session instead of being merged into it.
+        let res = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                " I TS                  TS                  TS
+                + 4 2022-01-01T10:06:00 2022-01-01T10:06:00 2022-01-01T10:11:00",
+            ),
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+}
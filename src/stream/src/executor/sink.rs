@@ -37,8 +37,11 @@ pub struct SinkExecutor<S: StateStore> {
     pk_indices: PkIndices,
 }
 
-async fn build_sink(config: SinkConfig) -> StreamExecutorResult<Box<SinkImpl>> {
-    Ok(Box::new(SinkImpl::new(config).await?))
+async fn build_sink(
+    config: SinkConfig,
+    pk_indices: PkIndices,
+) -> StreamExecutorResult<Box<SinkImpl>> {
+    Ok(Box::new(SinkImpl::new(config, pk_indices).await?))
 }
 
 impl<S: StateStore> SinkExecutor<S> {
@@ -52,20 +55,21 @@ impl<S: StateStore> SinkExecutor<S> {
         // This field can be used to distinguish a specific actor in parallelism to prevent
         // transaction execution errors
         properties.insert("identifier".to_string(), format!("sink-{:?}", executor_id));
+        let pk_indices = materialize_executor.pk_indices().to_vec();
         Self {
             input: materialize_executor,
             _store,
             metrics,
             properties,
             identity: format!("SinkExecutor_{:?}", executor_id),
-            pk_indices: Default::default(), // todo
+            pk_indices,
         }
     }
 
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(self) {
         let sink_config = SinkConfig::from_hashmap(self.properties.clone())?;
-        let mut sink = build_sink(sink_config.clone()).await?;
+        let mut sink = build_sink(sink_config.clone(), self.pk_indices.clone()).await?;
 
         // the flag is required because kafka transaction requires at least one
         // message, so we should abort the transaction if the flag is true.
@@ -92,16 +96,24 @@ impl<S: StateStore> SinkExecutor<S> {
                     }
 
                     let visible_chunk = chunk.clone().compact();
+                    let cardinality = visible_chunk.cardinality();
                     if let Err(e) = sink.write_batch(visible_chunk, &schema).await {
                         sink.abort().await?;
                         return Err(e.into());
                     }
+                    self.metrics
+                        .sink_row_count
+                        .with_label_values(&[self.identity.as_str(), sink_config.get_connector()])
+                        .inc_by(cardinality as u64);
                     empty_epoch_flag = false;
 
                     yield Message::Chunk(chunk);
                 }
+                Message::Watermark(w) => yield Message::Watermark(w),
                 Message::Barrier(barrier) => {
-                    if in_transaction {
+                    // The transaction is only committed on checkpoint barriers, so that the
+                    // output of the sink never goes ahead of the state to recover from.
+                    if in_transaction && barrier.checkpoint {
                         if empty_epoch_flag {
                             sink.abort().await?;
                             tracing::debug!(
@@ -119,9 +131,9 @@ impl<S: StateStore> SinkExecutor<S> {
                                 ])
                                 .observe(start_time.elapsed().as_millis() as f64);
                         }
+                        in_transaction = false;
+                        empty_epoch_flag = true;
                     }
-                    in_transaction = false;
-                    empty_epoch_flag = true;
                     epoch = barrier.epoch.curr;
                     yield Message::Barrier(barrier);
                 }
@@ -132,7 +132,7 @@ mod tests {
     async fn test_pause_and_resume() {
         let (barrier_tx, barrier_rx) = mpsc::unbounded_channel();
 
-        let table_source = TableSource::new(vec![]);
+        let table_source = TableSource::new(vec![], vec![]);
         let source_stream = table_source
             .stream_reader(vec![])
             .await
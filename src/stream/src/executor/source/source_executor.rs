@@ -21,9 +21,14 @@ use futures_async_stream::try_stream;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::{ArrayBuilder, I64ArrayBuilder, Op, StreamChunk};
-use risingwave_common::catalog::{ColumnId, Schema, TableId};
+use risingwave_common::catalog::{
+    column_default_from_protobuf, ColumnDesc, ColumnId, Schema, TableId,
+};
 use risingwave_common::util::epoch::UNIX_SINGULARITY_DATE_EPOCH;
 use risingwave_connector::source::{ConnectorState, SplitId, SplitImpl, SplitMetaData};
+use risingwave_pb::catalog::source::Info as ProstSourceInfo;
+use risingwave_pb::catalog::Source as ProstSource;
+use risingwave_pb::stream_plan::source_node::Info as SourceNodeInfo;
 use risingwave_source::connector_source::SourceContext;
 use risingwave_source::row_id::RowIdGenerator;
 use risingwave_source::*;
@@ -253,7 +258,7 @@ impl<S: StateStore> SourceExecutor<S> {
             .await
             .unwrap();
 
-        let source_desc = self
+        let mut source_desc = self
             .source_builder
             .build()
             .await
@@ -330,6 +335,12 @@ impl<S: StateStore> SourceExecutor<S> {
                             }
                             Mutation::Pause => stream.pause_source(),
                             Mutation::Resume => stream.resume_source(),
+                            Mutation::AlterTable { source, .. }
+                                if source.id == self.source_id.table_id() =>
+                            {
+                                self.apply_alter_table(&mut source_desc, &mut stream, source)
+                                    .await?
+                            }
                             Mutation::Update {
                                 vnode_bitmaps,
                                 actor_splits,
@@ -428,6 +439,93 @@ impl<S: StateStore> SourceExecutor<S> {
         Ok(())
     }
 
+    /// Apply the new columns of the altered table, the appended columns will be output since this
+    /// barrier.
+    ///
+    /// Note that the reader is rebuilt. For a table source, the DML chunks buffered in the channel
+    /// of the previous reader are forwarded to the new one when the previous reader is dropped.
+    /// For a materialized source, the new reader starts from the latest offsets of the splits.
+    async fn apply_alter_table(
+        &mut self,
+        source_desc: &mut SourceDescRef,
+        stream: &mut SourceReaderStream,
+        source: &ProstSource,
+    ) -> StreamExecutorResult<()> {
+        let (info, columns) = match &source.info {
+            Some(ProstSourceInfo::TableSource(info)) => {
+                (SourceNodeInfo::TableSource(info.clone()), &info.columns)
+            }
+            Some(ProstSourceInfo::StreamSource(info)) => {
+                (SourceNodeInfo::StreamSource(info.clone()), &info.columns)
+            }
+            None => return Ok(()),
+        };
+        let column_descs: Vec<ColumnDesc> = columns
+            .iter()
+            .map(|c| c.column_desc.as_ref().unwrap().into())
+            .collect();
+        if let SourceImpl::Table(table_source) = &source_desc.source {
+            let column_defaults = columns
+                .iter()
+                .map(|c| column_default_from_protobuf(c).flatten())
+                .collect();
+            table_source.update_columns(column_descs.clone(), column_defaults);
+        }
+
+        let added_columns = column_descs
+            .iter()
+            .filter(|c| !self.column_ids.contains(&c.column_id))
+            .collect_vec();
+        if added_columns.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(
+            "actor {:?} apply added columns {:?} of source",
+            self.ctx.id,
+            added_columns
+        );
+        for column in added_columns {
+            self.column_ids.push(column.column_id);
+            self.schema.fields.push(column.into());
+        }
+
+        // Replace the source reader with a new one of the new columns.
+        let state = if matches!(source_desc.source, SourceImpl::Connector(_)) {
+            // The columns to parse are fixed in the connector source, so rebuild it with the new
+            // info.
+            self.source_builder.update_info(info);
+            *source_desc = self
+                .source_builder
+                .build()
+                .await
+                .map_err(StreamExecutorError::connector_error)?;
+            self.latest_split_states().await?
+        } else {
+            None
+        };
+        let reader = self.build_stream_source_reader(source_desc, state).await?;
+        stream.replace_source_stream(reader);
+
+        Ok(())
+    }
+
+    /// Returns the splits assigned to this actor with the latest offsets read.
+    async fn latest_split_states(&mut self) -> StreamExecutorResult<ConnectorState> {
+        let mut states = Vec::with_capacity(self.stream_source_splits.len());
+        for split in self.stream_source_splits.clone() {
+            let state = match self.state_cache.get(&split.id()) {
+                Some(state) => state.clone(),
+                None => self
+                    .split_state_store
+                    .try_recover_from_state_store(&split)
+                    .await?
+                    .unwrap_or(split),
+            };
+            states.push(state);
+        }
+        Ok((!states.is_empty()).then_some(states))
+    }
+
     async fn replace_stream_reader_with_target_state(
         &mut self,
         source_desc: &SourceDescRef,
@@ -701,6 +799,7 @@ mod tests {
                     ..Default::default()
                 }),
                 is_hidden: false,
+                default_value: vec![],
             },
             ProstColumnCatalog {
                 column_desc: Some(ProstColumnDesc {
@@ -713,6 +812,7 @@ mod tests {
                     ..Default::default()
                 }),
                 is_hidden: false,
+                default_value: vec![],
             },
         ];
 
@@ -723,6 +823,7 @@ mod tests {
             row_id_index: Some(ProstColumnIndex { index: 0 }),
             columns,
             pk_column_ids: vec![0],
+            ..Default::default()
         }
     }
 
@@ -125,6 +125,7 @@ pub fn default_source_internal_table(id: u32) -> ProstTable {
                 ..Default::default()
             }),
             is_hidden: false,
+            default_value: vec![],
         }
     };
 
@@ -110,6 +110,8 @@ where
             let msg = msg?;
             match msg {
                 Message::Chunk(chunk) => yield Message::Chunk(self.inner.apply_chunk(chunk).await?),
+                // The output of top-n is not append-only, so watermarks can't be passed through.
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     self.inner.flush_data(barrier.epoch).await?;
 
@@ -0,0 +1,454 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{BoolArray, Row, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{ScalarImpl, VirtualNode};
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::error::{StreamExecutorError, StreamExecutorResult};
+use super::filter::SimpleFilterExecutor;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor,
+    ExecutorInfo, Message, PkIndicesRef, Watermark,
+};
+use crate::common::InfallibleExpression;
+
+/// [`WatermarkFilterExecutor`] generates the watermarks of a source. The watermark of a column is
+/// the maximum of its watermark expression over all rows seen so far, and the rows whose value of
+/// the column is less than the watermark, or null, are considered late and filtered out.
+///
+/// The watermarks are persisted for each vnode on barriers. On recovery, they are restored before
+/// any rows are admitted, so that the rows filtered out before won't be admitted again.
+pub struct WatermarkFilterExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    input: BoxedExecutor,
+    info: ExecutorInfo,
+
+    /// The index of the watermark column, and the expression to generate its watermark.
+    watermark_descs: Vec<(usize, BoxedExpression)>,
+
+    /// Stores `(vnode, watermark_0, watermark_1, ...)` for each owned vnode.
+    table: StateTable<S>,
+}
+
+impl<S: StateStore> WatermarkFilterExecutor<S> {
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        watermark_descs: Vec<(usize, BoxedExpression)>,
+        mut table: StateTable<S>,
+        executor_id: u64,
+    ) -> Self {
+        let info = input.info();
+        // The rows of the vnodes are overwritten each time the watermarks advance.
+        table.disable_sanity_check();
+
+        Self {
+            ctx,
+            input,
+            info: ExecutorInfo {
+                schema: info.schema,
+                pk_indices: info.pk_indices,
+                identity: format!("WatermarkFilterExecutor {:X}", executor_id),
+            },
+            watermark_descs,
+            table,
+        }
+    }
+}
+
+impl<S: StateStore> Executor for WatermarkFilterExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+impl<S: StateStore> WatermarkFilterExecutor<S> {
+    /// Restores the watermarks as the minimum of those persisted for the owned vnodes, which is
+    /// conservative if the vnodes are rescheduled from other actors. A vnode without persisted
+    /// watermarks makes the watermarks `None`.
+    async fn recover_watermarks(
+        table: &StateTable<S>,
+        watermark_num: usize,
+    ) -> StreamExecutorResult<Vec<Option<ScalarImpl>>> {
+        let vnodes = table.vnodes().clone();
+        let mut recovered: Option<Vec<Option<ScalarImpl>>> = None;
+        for vnode in vnodes
+            .iter()
+            .enumerate()
+            .filter_map(|(vnode, owned)| owned.then_some(vnode as VirtualNode))
+        {
+            let pk = Row::new(vec![Some(ScalarImpl::Int16(vnode as i16))]);
+            let persisted = match table.get_row(&pk).await? {
+                Some(row) => row.0.into_iter().skip(1).collect_vec(),
+                None => vec![None; watermark_num],
+            };
+            recovered = Some(match recovered {
+                None => persisted,
+                Some(recovered) => recovered
+                    .into_iter()
+                    .zip_eq(persisted)
+                    .map(|(a, b)| match (a, b) {
+                        (Some(a), Some(b)) => Some(if b < a { b } else { a }),
+                        _ => None,
+                    })
+                    .collect(),
+            });
+        }
+        Ok(recovered.unwrap_or_else(|| vec![None; watermark_num]))
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(self) {
+        let Self {
+            ctx,
+            input,
+            info,
+            watermark_descs,
+            mut table,
+        } = self;
+
+        let mut input = input.execute();
+        let first_barrier = expect_first_barrier(&mut input).await?;
+        table.init_epoch(first_barrier.epoch);
+
+        let mut watermarks = Self::recover_watermarks(&table, watermark_descs.len()).await?;
+        let mut persisted_watermarks = watermarks.clone();
+
+        // The first barrier message should be propagated.
+        yield Message::Barrier(first_barrier);
+
+        for ((col_idx, _), watermark) in watermark_descs.iter().zip_eq(&watermarks) {
+            if let Some(watermark) = watermark {
+                yield Message::Watermark(Watermark::new(
+                    *col_idx,
+                    info.schema.fields[*col_idx].data_type.clone(),
+                    watermark.clone(),
+                ));
+            }
+        }
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    let chunk = chunk.compact();
+
+                    // Filter out the late rows with the watermarks before this chunk.
+                    let data_chunk = chunk.data_chunk();
+                    let retained = (0..data_chunk.capacity())
+                        .map(|row_idx| {
+                            watermark_descs.iter().zip_eq(&watermarks).all(
+                                |((col_idx, _), watermark)| match data_chunk
+                                    .column_at(*col_idx)
+                                    .array_ref()
+                                    .value_at(row_idx)
+                                {
+                                    None => false,
+                                    Some(value) => watermark
+                                        .as_ref()
+                                        .map_or(true, |w| value >= w.as_scalar_ref_impl()),
+                                },
+                            )
+                        })
+                        .collect_vec();
+
+                    // Advance the watermarks with the retained rows.
+                    let mut new_watermarks = vec![];
+                    for ((col_idx, expr), watermark) in
+                        watermark_descs.iter().zip_eq(watermarks.iter_mut())
+                    {
+                        let output = expr.eval_infallible(data_chunk, |err| {
+                            ctx.on_compute_error(err, &info.identity)
+                        });
+                        let max = output
+                            .iter()
+                            .zip_eq(&retained)
+                            .filter_map(|(datum, retained)| datum.filter(|_| *retained))
+                            .reduce(|max, value| if value > max { value } else { max });
+                        let Some(max) = max else { continue };
+                        if watermark
+                            .as_ref()
+                            .map_or(true, |w| max > w.as_scalar_ref_impl())
+                        {
+                            let max = max.into_scalar_impl();
+                            *watermark = Some(max.clone());
+                            new_watermarks.push(Watermark::new(
+                                *col_idx,
+                                info.schema.fields[*col_idx].data_type.clone(),
+                                max,
+                            ));
+                        }
+                    }
+
+                    let pred_output = Arc::new(
+                        BoolArray::from_slice(&retained.into_iter().map(Some).collect_vec()).into(),
+                    );
+                    if let Some(chunk) = SimpleFilterExecutor::filter(chunk, pred_output)? {
+                        yield Message::Chunk(chunk);
+                    }
+                    for watermark in new_watermarks {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+                // The watermarks are generated here, so those from upstream are ignored.
+                Message::Watermark(_) => {}
+                Message::Barrier(barrier) => {
+                    if watermarks != persisted_watermarks {
+                        let vnodes = table.vnodes().clone();
+                        for vnode in vnodes
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(vnode, owned)| owned.then_some(vnode as VirtualNode))
+                        {
+                            let row = std::iter::once(Some(ScalarImpl::Int16(vnode as i16)))
+                                .chain(watermarks.iter().cloned())
+                                .collect();
+                            table.insert(Row::new(row));
+                        }
+                        persisted_watermarks = watermarks.clone();
+                    }
+                    table.commit(barrier.epoch).await?;
+
+                    // Update the vnode bitmap of the state table if asked, and restore the
+                    // watermarks of the newly owned vnodes, which may be smaller.
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(ctx.id) {
+                        let _ = table.update_vnode_bitmap(vnode_bitmap);
+                        watermarks =
+                            Self::recover_watermarks(&table, watermark_descs.len()).await?;
+                        persisted_watermarks = watermarks.clone();
+                    }
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
+    use risingwave_pb::expr::expr_node::Type;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::super::test_utils::{MessageSender, MockSource};
+    use super::*;
+    use crate::executor::ActorContext;
+
+    fn create_watermark_filter(mem_state: MemoryStateStore) -> (MessageSender, BoxedMessageStream) {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (tx, source) = MockSource::channel(schema, vec![0]);
+
+        // WATERMARK FOR col1 AS col1 - 1
+        let watermark_expr = new_binary_expr(
+            Type::Subtract,
+            DataType::Int64,
+            Box::new(InputRefExpression::new(DataType::Int64, 1)),
+            Box::new(LiteralExpression::new(DataType::Int64, Some(1_i64.into()))),
+        )
+        .unwrap();
+
+        // (vnode, watermark of col1)
+        let table = StateTable::new_without_distribution(
+            mem_state,
+            TableId::new(1),
+            vec![
+                ColumnDesc::unnamed(ColumnId::new(0), DataType::Int16),
+                ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+            ],
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+
+        let watermark_filter = Box::new(WatermarkFilterExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            vec![(1, watermark_expr)],
+            table,
+            1,
+        ));
+        (tx, watermark_filter.execute())
+    }
+
+    #[tokio::test]
+    async fn test_watermark_filter() {
+        let (mut tx, mut watermark_filter) = create_watermark_filter(MemoryStateStore::new());
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 2 12
+            + 3 11",
+        ));
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 4 9
+            + 5 .
+            + 6 15",
+        ));
+        tx.push_barrier(2, false);
+
+        watermark_filter
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+        assert_eq!(
+            watermark_filter
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap()
+                .compact(),
+            StreamChunk::from_pretty(
+                " I I
+                + 1 10
+                + 2 12
+                + 3 11",
+            )
+        );
+        assert_eq!(
+            watermark_filter.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(1, DataType::Int64, 11_i64.into()))
+        );
+
+        // The late row and the row with null watermark column are filtered out.
+        assert_eq!(
+            watermark_filter
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap()
+                .compact(),
+            StreamChunk::from_pretty(
+                " I I
+                + 6 15",
+            )
+        );
+        assert_eq!(
+            watermark_filter.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(1, DataType::Int64, 14_i64.into()))
+        );
+        watermark_filter
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watermark_filter_recovery() {
+        let mem_state = MemoryStateStore::new();
+
+        let (mut tx, mut watermark_filter) = create_watermark_filter(mem_state.clone());
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10",
+        ));
+        tx.push_barrier(2, false);
+        watermark_filter.next().await.unwrap().unwrap();
+        watermark_filter.next().await.unwrap().unwrap();
+        watermark_filter.next().await.unwrap().unwrap();
+        watermark_filter
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+
+        // Recover from the persisted watermark.
+        let (mut tx, mut watermark_filter) = create_watermark_filter(mem_state);
+        tx.push_barrier(3, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 2 8
+            + 3 12",
+        ));
+        tx.push_barrier(4, false);
+
+        watermark_filter
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+        assert_eq!(
+            watermark_filter.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(1, DataType::Int64, 9_i64.into()))
+        );
+        // The row late to the restored watermark is filtered out.
+        assert_eq!(
+            watermark_filter
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap()
+                .compact(),
+            StreamChunk::from_pretty(
+                " I I
+                + 3 12",
+            )
+        );
+        assert_eq!(
+            watermark_filter.next().await.unwrap().unwrap(),
+            Message::Watermark(Watermark::new(1, DataType::Int64, 11_i64.into()))
+        );
+    }
+}
@@ -28,9 +28,11 @@ pub async fn schema_check(info: Arc<ExecutorInfo>, input: impl MessageStream) {
         let message = message?;
 
         if let Message::Chunk(chunk) = &message {
+            // Chunks of a table may contain extra columns appended by `ALTER TABLE ADD COLUMN`
+            // after the executor is built, which are not checked here.
             risingwave_common::util::schema_check::schema_check(
                 info.schema.fields().iter().map(|f| &f.data_type),
-                chunk.columns(),
+                chunk.columns().iter().take(info.schema.len()),
             )
             .unwrap_or_else(|e| panic!("schema check failed on {}: {}", info.identity, e));
         }
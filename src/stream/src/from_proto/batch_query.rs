@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use itertools::Itertools;
-use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId, TableOption};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, TableDesc, TableId, TableOption};
 use risingwave_common::util::sort_util::OrderType;
 use risingwave_pb::plan_common::{OrderType as ProstOrderType, StorageTableDesc};
 use risingwave_storage::table::batch_table::storage_table::StorageTable;
@@ -85,6 +85,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
             .iter()
             .map(|&k| k as usize)
             .collect_vec();
+        let appended_value_defaults = TableDesc::appended_value_defaults_from_protobuf(table_desc);
         let table = StorageTable::new_partial(
             state_store,
             table_id,
@@ -95,6 +96,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
             distribution,
             table_option,
             value_indices,
+            appended_value_defaults,
         );
 
         let schema = table.schema().clone();
@@ -42,6 +42,7 @@ pub struct HashAggExecutorDispatcherArgs<S: StateStore> {
     lru_manager: Option<LruManagerRef>,
     metrics: Arc<StreamingMetrics>,
     chunk_size: usize,
+    emit_on_window_close: bool,
 }
 
 impl<S: StateStore> HashKeyDispatcher for HashAggExecutorDispatcherArgs<S> {
@@ -62,6 +63,7 @@ impl<S: StateStore> HashKeyDispatcher for HashAggExecutorDispatcherArgs<S> {
             self.lru_manager,
             self.metrics,
             self.chunk_size,
+            self.emit_on_window_close,
         )?
         .boxed())
     }
@@ -124,6 +126,7 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             lru_manager: stream.context.lru_manager.clone(),
             metrics: params.executor_stats,
             chunk_size: params.env.config().developer.stream_chunk_size,
+            emit_on_window_close: node.emit_on_window_close,
         };
         args.dispatch()
     }
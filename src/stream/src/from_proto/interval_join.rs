@@ -0,0 +1,100 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_expr::expr::build_from_prost;
+use risingwave_pb::catalog::Table;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+use super::*;
+use crate::executor::{IntervalJoinExecutor, IntervalJoinParams};
+
+pub struct IntervalJoinExecutorBuilder;
+
+impl ExecutorBuilder for IntervalJoinExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::IntervalJoin)?;
+        let vnodes = Arc::new(
+            params
+                .vnode_bitmap
+                .expect("vnodes not set for interval join"),
+        );
+
+        let [source_l, source_r]: [_; 2] = params.input.try_into().unwrap();
+
+        let table_l = node.get_left_table()?;
+        let table_r = node.get_right_table()?;
+
+        let params_l = build_join_params(node.get_left_key(), node.left_time_key, table_l);
+        let params_r = build_join_params(node.get_right_key(), node.right_time_key, table_r);
+
+        let condition = match node.get_condition() {
+            Ok(cond_prost) => Some(build_from_prost(cond_prost)?),
+            Err(_) => None,
+        };
+        let output_indices = node
+            .get_output_indices()
+            .iter()
+            .map(|&x| x as usize)
+            .collect_vec();
+
+        let state_table_l =
+            StateTable::from_table_catalog(table_l, store.clone(), Some(vnodes.clone()));
+        let state_table_r = StateTable::from_table_catalog(table_r, store, Some(vnodes));
+
+        Ok(Box::new(IntervalJoinExecutor::new(
+            params.actor_context,
+            source_l,
+            source_r,
+            params_l,
+            params_r,
+            node.get_lower_bound()?.into(),
+            node.get_upper_bound()?.into(),
+            condition,
+            params.pk_indices,
+            output_indices,
+            params.executor_id,
+            state_table_l,
+            state_table_r,
+            params.executor_stats,
+            params.env.config().developer.stream_chunk_size,
+        )))
+    }
+}
+
+fn build_join_params(join_key: &[i32], time_key: u32, table: &Table) -> IntervalJoinParams {
+    let join_key_indices = join_key.iter().map(|&key| key as usize).collect_vec();
+    // The state table is distributed by (part of) the join key.
+    let dist_key_in_join_key = table
+        .distribution_key
+        .iter()
+        .map(|&dist_key| {
+            join_key_indices
+                .iter()
+                .position(|&key| key == dist_key as usize)
+                .expect("join key should contain dist key.")
+        })
+        .collect_vec();
+    IntervalJoinParams {
+        join_key_indices,
+        time_key_index: time_key as usize,
+        dist_key_in_join_key,
+    }
+}
@@ -25,18 +25,22 @@ mod group_top_n;
 mod hash_agg;
 mod hash_join;
 mod hop_window;
+mod interval_join;
 mod local_simple_agg;
 mod lookup;
 mod lookup_union;
 mod merge;
 mod mview;
+mod over_window;
 mod project;
 mod project_set;
+mod session_window;
 mod sink;
 mod source;
 mod top_n;
 mod top_n_appendonly;
 mod union;
+mod watermark_filter;
 
 // import for submodules
 use itertools::Itertools;
@@ -55,18 +59,22 @@ use self::group_top_n::GroupTopNExecutorBuilder;
 use self::hash_agg::*;
 use self::hash_join::*;
 use self::hop_window::*;
+use self::interval_join::*;
 use self::local_simple_agg::*;
 use self::lookup::*;
 use self::lookup_union::*;
 use self::merge::*;
 use self::mview::*;
+use self::over_window::*;
 use self::project::*;
 use self::project_set::*;
+use self::session_window::*;
 use self::sink::*;
 use self::source::*;
 use self::top_n::*;
 use self::top_n_appendonly::*;
 use self::union::*;
+use self::watermark_filter::*;
 use crate::error::StreamResult;
 use crate::executor::{BoxedExecutor, Executor, ExecutorInfo};
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
@@ -129,5 +137,9 @@ pub fn create_executor(
         NodeBody::DynamicFilter => DynamicFilterExecutorBuilder,
         NodeBody::ProjectSet => ProjectSetExecutorBuilder,
         NodeBody::GroupTopN => GroupTopNExecutorBuilder,
+        NodeBody::OverWindow => OverWindowExecutorBuilder,
+        NodeBody::IntervalJoin => IntervalJoinExecutorBuilder,
+        NodeBody::WatermarkFilter => WatermarkFilterExecutorBuilder,
+        NodeBody::SessionWindow => SessionWindowExecutorBuilder,
     }
 }
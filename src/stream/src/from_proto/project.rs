@@ -33,6 +33,12 @@ impl ExecutorBuilder for ProjectExecutorBuilder {
             .iter()
             .map(build_from_prost)
             .try_collect()?;
+        let watermark_derivations = node
+            .get_watermark_input_key()
+            .iter()
+            .zip_eq(node.get_watermark_output_key())
+            .map(|(&input_key, &output_key)| (input_key as usize, output_key as usize))
+            .collect_vec();
 
         Ok(ProjectExecutor::new(
             params.actor_context,
@@ -40,6 +46,7 @@ impl ExecutorBuilder for ProjectExecutorBuilder {
             params.pk_indices,
             project_exprs,
             params.executor_id,
+            watermark_derivations,
         )
         .boxed())
     }
@@ -0,0 +1,57 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_expr::expr::build_from_prost;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+use super::*;
+use crate::executor::WatermarkFilterExecutor;
+
+pub struct WatermarkFilterExecutorBuilder;
+
+impl ExecutorBuilder for WatermarkFilterExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::WatermarkFilter)?;
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+        let watermark_descs = node
+            .watermark_descs
+            .iter()
+            .map(|desc| {
+                Ok((
+                    desc.watermark_idx as usize,
+                    build_from_prost(desc.get_expr()?)?,
+                ))
+            })
+            .collect::<StreamResult<Vec<_>>>()?;
+
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let table = StateTable::from_table_catalog(node.get_table()?, store, vnodes);
+
+        Ok(WatermarkFilterExecutor::new(
+            params.actor_context,
+            input,
+            watermark_descs,
+            table,
+            params.executor_id,
+        )
+        .boxed())
+    }
+}
@@ -470,6 +470,9 @@ impl LocalStreamManagerCore {
                     | NodeBody::Chain(_)
                     | NodeBody::DynamicFilter(_)
                     | NodeBody::GroupTopN(_)
+                    | NodeBody::OverWindow(_)
+                    | NodeBody::IntervalJoin(_)
+                    | NodeBody::SessionWindow(_)
             )
         }
         let is_stateful = is_stateful_executor(node);
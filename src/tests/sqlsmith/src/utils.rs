@@ -105,6 +105,8 @@ pub fn data_type_name_to_ast_data_type(type_name: DataTypeName) -> Option<DataTy
         DataTypeName::Timestampz => Some(DataType::Timestamp(true)),
         DataTypeName::Time => Some(DataType::Time(false)),
         DataTypeName::Interval => Some(DataType::Interval),
+        DataTypeName::Bytea => Some(DataType::Bytea),
+        DataTypeName::Jsonb => Some(DataType::Jsonb),
         DataTypeName::Struct | DataTypeName::List => None,
     }
 }
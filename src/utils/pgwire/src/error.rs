@@ -24,19 +24,19 @@ pub type PsqlResult<T> = std::result::Result<T, PsqlError>;
 #[derive(Error, Debug)]
 pub enum PsqlError {
     #[error("Startup Error when connect to session: {0}")]
-    StartupError(BoxedError),
+    StartupError(#[source] BoxedError),
 
     #[error("PasswordError: {0}")]
     PasswordError(IoError),
 
     #[error("QueryError: {0}")]
-    QueryError(BoxedError),
+    QueryError(#[source] BoxedError),
 
     #[error("ParseError: {0}")]
-    ParseError(BoxedError),
+    ParseError(#[source] BoxedError),
 
     #[error("ExecuteError: {0}")]
-    ExecuteError(BoxedError),
+    ExecuteError(#[source] BoxedError),
 
     #[error("{0}")]
     IoError(#[from] IoError),
@@ -46,6 +46,26 @@ pub enum PsqlError {
     Internal(#[from] anyhow::Error),
 }
 
+/// Error returned when a running statement is canceled. Any error with it in the source chain is
+/// reported to the client with SQLSTATE `57014 query_canceled`.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryCanceled {
+    /// Canceled by a cancel request from the client, e.g. ctrl-c in psql.
+    #[error("canceling statement due to user request")]
+    UserRequest,
+
+    /// Canceled because the statement runs longer than `statement_timeout`.
+    #[error("canceling statement due to statement timeout")]
+    StatementTimeout,
+}
+
+impl QueryCanceled {
+    /// Returns whether `err` or any of its sources is a [`QueryCanceled`].
+    pub fn is_in_chain(err: &(dyn std::error::Error + 'static)) -> bool {
+        std::iter::successors(Some(err), |e| e.source()).any(|e| e.is::<QueryCanceled>())
+    }
+}
+
 impl PsqlError {
     pub fn no_statement() -> Self {
         PsqlError::Internal(anyhow!("No statement found".to_string()))
@@ -54,4 +74,26 @@ impl PsqlError {
     pub fn no_portal() -> Self {
         PsqlError::Internal(anyhow!("No portal found".to_string()))
     }
+
+    pub fn no_row_count() -> Self {
+        PsqlError::Internal(anyhow!("No affected row count in the result".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_canceled_in_chain() {
+        let err = PsqlError::QueryError(Box::new(QueryCanceled::StatementTimeout));
+        assert!(QueryCanceled::is_in_chain(&err));
+        assert_eq!(
+            err.to_string(),
+            "QueryError: canceling statement due to statement timeout"
+        );
+
+        let err = PsqlError::QueryError("boom".into());
+        assert!(!QueryCanceled::is_in_chain(&err));
+    }
 }
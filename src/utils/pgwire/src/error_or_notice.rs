@@ -28,6 +28,14 @@ impl<'a> ErrorOrNoticeMessage<'a> {
         }
     }
 
+    pub fn query_canceled(message: &'a str) -> Self {
+        Self {
+            severity: Severity::Error,
+            state: SqlState::QUERY_CANCELED,
+            message,
+        }
+    }
+
     pub fn notice(message: &'a str) -> Self {
         Self {
             severity: Severity::Notice,
@@ -74,6 +82,7 @@ impl Severity {
 pub enum Code {
     E00000,
     E01000,
+    E57014,
     EXX000,
 }
 
@@ -84,6 +93,8 @@ pub struct SqlState(Code);
 impl SqlState {
     /// Class XX — Internal Error
     pub const INTERNAL_ERROR: SqlState = SqlState(Code::EXX000);
+    /// Class 57 — Operator Intervention
+    pub const QUERY_CANCELED: SqlState = SqlState(Code::E57014);
     /// Class 00 — Successful Completion
     pub const SUCCESSFUL_COMPLETION: SqlState = SqlState(Code::E00000);
     /// Class 01 — Warning
@@ -93,6 +104,7 @@ impl SqlState {
         match &self.0 {
             Code::E00000 => "00000",
             Code::E01000 => "01000",
+            Code::E57014 => "57014",
             Code::EXX000 => "XX000",
         }
     }
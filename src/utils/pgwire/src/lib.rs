@@ -23,4 +23,5 @@ pub mod pg_message;
 pub mod pg_protocol;
 pub mod pg_response;
 pub mod pg_server;
+pub mod scram;
 pub mod types;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::vec::IntoIter;
@@ -488,6 +489,32 @@ impl PreparedStatement {
                     };
                     format!("'{}'::INTERVAL", tmp)
                 }
+                TypeOid::Bytea => {
+                    let tmp = if param_format {
+                        let mut tmp = String::from("\\x");
+                        raw_param
+                            .iter()
+                            .for_each(|b| write!(tmp, "{:02x}", b).unwrap());
+                        tmp
+                    } else {
+                        cstr_to_str(raw_param).unwrap().to_string()
+                    };
+                    format!("'{}'::BYTEA", tmp.replace('\'', "''"))
+                }
+                TypeOid::Jsonb => {
+                    let tmp = if param_format {
+                        // The binary format is a version number followed by the text.
+                        if raw_param.is_empty() {
+                            return Err(PsqlError::Internal(anyhow!(
+                                "invalid binary jsonb parameter: missing version number"
+                            )));
+                        }
+                        cstr_to_str(&raw_param.slice(1..)).unwrap().to_string()
+                    } else {
+                        cstr_to_str(raw_param).unwrap().to_string()
+                    };
+                    format!("'{}'::JSONB", tmp.replace('\'', "''"))
+                }
             };
             params.push(str)
         }
@@ -516,6 +543,8 @@ impl PreparedStatement {
                     params.push("'2022-10-01 12:00:00+01:00'::timestamptz".to_string())
                 }
                 TypeOid::Interval => params.push("'2 months ago'::interval".to_string()),
+                TypeOid::Bytea => params.push("'\\x'::BYTEA".to_string()),
+                TypeOid::Jsonb => params.push("'null'::JSONB".to_string()),
             };
         }
         Ok(params)
@@ -735,6 +764,13 @@ mod tests {
                 "'2021-01-07 06:13:20'::TIMESTAMP"
             ]
         );
+
+        // Quotes in the text of BYTEA are escaped.
+        let raw_params = vec!["a'b".into()];
+        let type_description = vec![TypeOid::Bytea];
+        let params =
+            PreparedStatement::parse_params(&type_description, &raw_params, false).unwrap();
+        assert_eq!(params, vec!["'a''b'::BYTEA"]);
     }
 
     #[test]
@@ -834,5 +870,10 @@ mod tests {
                 "'1 mons 1 days 00:00:24'::INTERVAL"
             ]
         );
+
+        // An empty JSONB parameter lacks the version number.
+        let raw_params = vec![Bytes::new()];
+        let type_description = vec![TypeOid::Jsonb];
+        assert!(PreparedStatement::parse_params(&type_description, &raw_params, true).is_err());
     }
 }
@@ -47,7 +47,11 @@ impl PgFieldDescriptor {
             | TypeOid::Time
             | TypeOid::Timestamptz => 8,
             TypeOid::SmallInt => 2,
-            TypeOid::Varchar | TypeOid::Decimal | TypeOid::Interval => -1,
+            TypeOid::Varchar
+            | TypeOid::Decimal
+            | TypeOid::Interval
+            | TypeOid::Bytea
+            | TypeOid::Jsonb => -1,
         };
 
         Self {
@@ -105,6 +109,8 @@ pub enum TypeOid {
     Timestamptz,
     Decimal,
     Interval,
+    Bytea,
+    Jsonb,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -129,6 +135,8 @@ impl TypeOid {
             1184 => Ok(TypeOid::Timestamptz),
             1700 => Ok(TypeOid::Decimal),
             1186 => Ok(TypeOid::Interval),
+            17 => Ok(TypeOid::Bytea),
+            3802 => Ok(TypeOid::Jsonb),
             v => Err(TypeOidError(v)),
         }
     }
@@ -154,6 +162,8 @@ impl TypeOid {
             TypeOid::Timestamptz => 1184,
             TypeOid::Decimal => 1700,
             TypeOid::Interval => 1186,
+            TypeOid::Bytea => 17,
+            TypeOid::Jsonb => 3802,
         }
     }
 }
@@ -177,6 +187,8 @@ impl FromStr for TypeOid {
             "timestamptz" => Ok(TypeOid::Timestamptz),
             "decimal" => Ok(TypeOid::Decimal),
             "interval" => Ok(TypeOid::Interval),
+            "bytea" => Ok(TypeOid::Bytea),
+            "jsonb" => Ok(TypeOid::Jsonb),
             _ => Err(TypeOidError(0)),
         }
     }
@@ -21,6 +21,7 @@ use byteorder::{BigEndian, ByteOrder};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
+use crate::error::QueryCanceled;
 use crate::error_or_notice::ErrorOrNoticeMessage;
 use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use crate::pg_response::StatementType;
@@ -42,6 +43,9 @@ pub enum FeMessage {
     CancelQuery(FeCancelMessage),
     Terminate,
     Flush,
+    CopyData(FeCopyDataMessage),
+    CopyDone,
+    CopyFail(FeCopyFailMessage),
 }
 
 pub struct FeStartupMessage {
@@ -125,6 +129,17 @@ pub struct FeCloseMessage {
     pub name: Bytes,
 }
 
+/// A chunk of the data stream of `COPY FROM STDIN`.
+#[derive(Debug)]
+pub struct FeCopyDataMessage {
+    pub data: Bytes,
+}
+
+#[derive(Debug)]
+pub struct FeCopyFailMessage {
+    pub message: Bytes,
+}
+
 pub struct FeCancelMessage {
     pub target_process_id: i32,
     pub target_secret_key: i32,
@@ -246,10 +261,52 @@ impl FeParseMessage {
 }
 
 impl FePasswordMessage {
+    /// The message is also used for SASL responses, whose format depends on the authentication
+    /// method. So the body is kept as is, except for the null terminator of a password.
     pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
-        let password = read_null_terminated(&mut buf)?;
+        if buf.last() == Some(&0) {
+            buf.truncate(buf.len() - 1);
+        }
+
+        Ok(FeMessage::Password(FePasswordMessage { password: buf }))
+    }
+}
+
+/// The first message of a SASL exchange, sent as a [`FePasswordMessage`].
+pub struct FeSaslInitialResponse {
+    pub mechanism: Bytes,
+    pub data: Bytes,
+}
+
+impl FeSaslInitialResponse {
+    pub fn parse(mut buf: Bytes) -> Result<Self> {
+        if !buf.contains(&0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid SASLInitialResponse message",
+            ));
+        }
+        let mechanism = read_null_terminated(&mut buf)?;
+        // The length of the data, or -1 if there is no data.
+        if buf.remaining() < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid SASLInitialResponse message",
+            ));
+        }
+        let len = buf.get_i32();
+        let data = if len < 0 {
+            Bytes::new()
+        } else if len as usize == buf.remaining() {
+            buf
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid SASLInitialResponse message",
+            ));
+        };
 
-        Ok(FeMessage::Password(FePasswordMessage { password }))
+        Ok(FeSaslInitialResponse { mechanism, data })
     }
 }
 
@@ -278,6 +335,13 @@ impl FeCloseMessage {
     }
 }
 
+impl FeCopyFailMessage {
+    pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
+        let message = read_null_terminated(&mut buf)?;
+        Ok(FeMessage::CopyFail(FeCopyFailMessage { message }))
+    }
+}
+
 impl FeMessage {
     /// Read one message from the stream.
     pub async fn read(stream: &mut (impl AsyncRead + Unpin)) -> Result<FeMessage> {
@@ -301,6 +365,9 @@ impl FeMessage {
             b'C' => FeCloseMessage::parse(sql_bytes),
             b'p' => FePasswordMessage::parse(sql_bytes),
             b'H' => Ok(FeMessage::Flush),
+            b'd' => Ok(FeMessage::CopyData(FeCopyDataMessage { data: sql_bytes })),
+            b'c' => Ok(FeMessage::CopyDone),
+            b'f' => FeCopyFailMessage::parse(sql_bytes),
             _ => Err(std::io::Error::new(
                 ErrorKind::InvalidInput,
                 format!("Unsupported tag of regular message: {}", val),
@@ -365,6 +432,9 @@ pub enum BeMessage<'a> {
     AuthenticationOk,
     AuthenticationCleartextPassword,
     AuthenticationMd5Password(&'a [u8; 4]),
+    AuthenticationSasl(&'a [&'a str]),
+    AuthenticationSaslContinue(&'a [u8]),
+    AuthenticationSaslFinal(&'a [u8]),
     CommandComplete(BeCommandCompleteMessage),
     NoticeResponse(&'a str),
     // Single byte - used in response to SSLRequest/GSSENCRequest.
@@ -382,6 +452,11 @@ pub enum BeMessage<'a> {
     RowDescription(&'a [PgFieldDescriptor]),
     ErrorResponse(BoxedError),
     CloseComplete,
+    // The number of columns to copy, all in text format.
+    CopyInResponse(usize),
+    CopyOutResponse(usize),
+    CopyData(&'a [u8]),
+    CopyDone,
 
     // 0: process ID, 1: secret key
     BackendKeyData((i32, i32)),
@@ -438,6 +513,43 @@ impl<'a> BeMessage<'a> {
                 buf.put_slice(&salt[..]);
             }
 
+            // AuthenticationSASL
+            // +-----+-----------+-----------+-----------------+------+
+            // | 'R' | int32 len | int32(10) | str mechanism.. | '\0' |
+            // +-----+-----------+-----------+-----------------+------+
+            //
+            // Each mechanism is null-terminated, and the list is terminated by an extra null.
+            BeMessage::AuthenticationSasl(mechanisms) => {
+                buf.put_u8(b'R');
+                write_body(buf, |buf| {
+                    buf.put_i32(10);
+                    for mechanism in mechanisms.iter() {
+                        write_cstr(buf, mechanism.as_bytes())?;
+                    }
+                    buf.put_u8(0);
+                    Ok(())
+                })?;
+            }
+
+            // AuthenticationSASLContinue / AuthenticationSASLFinal
+            // +-----+-----------+-----------------------------+-------+
+            // | 'R' | int32 len | int32(11) for continue      | bytes |
+            // |     |           | int32(12) for final         |       |
+            // +-----+-----------+-----------------------------+-------+
+            BeMessage::AuthenticationSaslContinue(data)
+            | BeMessage::AuthenticationSaslFinal(data) => {
+                let code = match message {
+                    BeMessage::AuthenticationSaslContinue(_) => 11,
+                    _ => 12,
+                };
+                buf.put_u8(b'R');
+                write_body(buf, |buf| {
+                    buf.put_i32(code);
+                    buf.put_slice(data);
+                    Ok(())
+                })?;
+            }
+
             // ParameterStatus
             // +-----+-----------+----------+------+-----------+------+
             // | 'S' | int32 len | str name | '\0' | str value | '\0' |
@@ -638,12 +750,60 @@ impl<'a> BeMessage<'a> {
 
             BeMessage::ErrorResponse(error) => {
                 // For all the errors set Severity to Error and error code to
-                // 'internal error'.
+                // 'internal error', except for canceled queries which get 'query canceled'.
 
                 // 'E' signalizes ErrorResponse messages
                 buf.put_u8(b'E');
                 let msg = error.to_string();
-                write_err_or_notice(buf, &ErrorOrNoticeMessage::internal_error(&msg));
+                let message = if QueryCanceled::is_in_chain(&**error) {
+                    ErrorOrNoticeMessage::query_canceled(&msg)
+                } else {
+                    ErrorOrNoticeMessage::internal_error(&msg)
+                };
+                write_err_or_notice(buf, &message);
+            }
+
+            // CopyInResponse / CopyOutResponse
+            // +-------------+-----------+---------------------+--------------+------------------+
+            // | 'G' for in  | int32 len | int8 overall format | int16 colNum | int16 formatCode |
+            // | 'H' for out |           |                     |              |       ...        |
+            // +-------------+-----------+---------------------+--------------+------------------+
+            BeMessage::CopyInResponse(num_columns) | BeMessage::CopyOutResponse(num_columns) => {
+                let tag = match message {
+                    BeMessage::CopyInResponse(_) => b'G',
+                    _ => b'H',
+                };
+                buf.put_u8(tag);
+                write_body(buf, |buf| {
+                    // Only the textual format is supported.
+                    buf.put_i8(0);
+                    buf.put_i16(*num_columns as i16);
+                    for _ in 0..*num_columns {
+                        buf.put_i16(0);
+                    }
+                    Ok(())
+                })?;
+            }
+
+            // CopyData
+            // +-----+-----------+-------+
+            // | 'd' | int32 len | bytes |
+            // +-----+-----------+-------+
+            BeMessage::CopyData(data) => {
+                buf.put_u8(b'd');
+                write_body(buf, |buf| {
+                    buf.put_slice(data);
+                    Ok(())
+                })?;
+            }
+
+            // CopyDone
+            // +-----+----------+
+            // | 'c' | int32(4) |
+            // +-----+----------+
+            BeMessage::CopyDone => {
+                buf.put_u8(b'c');
+                buf.put_i32(4);
             }
 
             BeMessage::BackendKeyData((process_id, secret_key)) => {
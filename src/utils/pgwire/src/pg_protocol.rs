@@ -25,6 +25,7 @@ use futures::stream::StreamExt;
 use futures::Stream;
 use openssl::ssl::{SslAcceptor, SslContext, SslContextRef, SslMethod};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
 use tokio_openssl::SslStream;
 use tracing::log::trace;
 
@@ -34,10 +35,14 @@ use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use crate::pg_message::{
     BeCommandCompleteMessage, BeMessage, BeParameterStatusMessage, FeBindMessage, FeCancelMessage,
     FeCloseMessage, FeDescribeMessage, FeExecuteMessage, FeMessage, FeParseMessage,
-    FePasswordMessage, FeStartupMessage,
+    FePasswordMessage, FeSaslInitialResponse, FeStartupMessage,
 };
 use crate::pg_response::RowSetResult;
-use crate::pg_server::{Session, SessionManager, UserAuthenticator};
+use crate::pg_server::{BoxedError, Session, SessionManager, UserAuthenticator};
+use crate::scram::{ScramExchange, ScramSecret, SCRAM_SHA_256};
+
+/// The number of pieces of `COPY FROM STDIN` data buffered before they are consumed.
+const COPY_DATA_CHANNEL_SIZE: usize = 16;
 
 /// The state machine for each psql connection.
 /// Read pg messages from tcp stream and write results back.
@@ -64,6 +69,9 @@ where
     // Used for ssl connection.
     // If None, not expected to build ssl connection (panic).
     tls_context: Option<SslContext>,
+
+    // The ongoing SCRAM exchange, after the client-first-message is received.
+    scram_exchange: Option<ScramExchange>,
 }
 
 /// Configures TLS encryption for connections.
@@ -145,6 +153,7 @@ where
             tls_context: tls_config
                 .as_ref()
                 .and_then(|e| build_ssl_ctx_from_config(e).ok()),
+            scram_exchange: None,
         }
     }
 
@@ -213,6 +222,8 @@ where
             FeMessage::Sync => self.stream.write_no_flush(&BeMessage::ReadyForQuery)?,
             FeMessage::Close(m) => self.process_close_msg(m)?,
             FeMessage::Flush => self.stream.flush().await?,
+            // Copy messages are only expected during `COPY FROM STDIN`, ignore stray ones.
+            FeMessage::CopyData(_) | FeMessage::CopyDone | FeMessage::CopyFail(_) => {}
         }
         self.stream.flush().await?;
         Ok(false)
@@ -277,6 +288,10 @@ where
                 self.stream
                     .write_no_flush(&BeMessage::AuthenticationMd5Password(salt))?;
             }
+            UserAuthenticator::ScramSha256(_) => {
+                self.stream
+                    .write_no_flush(&BeMessage::AuthenticationSasl(&[SCRAM_SHA_256]))?;
+            }
         }
         self.session = Some(session);
         self.state = PgProtocolState::Regular;
@@ -285,6 +300,10 @@ where
 
     fn process_password_msg(&mut self, msg: FePasswordMessage) -> PsqlResult<()> {
         let authenticator = self.session.as_ref().unwrap().user_authenticator();
+        if let UserAuthenticator::ScramSha256(secret) = authenticator {
+            let secret = secret.clone();
+            return self.process_sasl_msg(msg, secret);
+        }
         if !authenticator.authenticate(&msg.password) {
             return Err(PsqlError::PasswordError(IoError::new(
                 ErrorKind::InvalidInput,
@@ -298,6 +317,40 @@ where
         Ok(())
     }
 
+    /// Processes the SASLInitialResponse or SASLResponse of a SCRAM-SHA-256 exchange.
+    fn process_sasl_msg(&mut self, msg: FePasswordMessage, secret: ScramSecret) -> PsqlResult<()> {
+        match self.scram_exchange.take() {
+            None => {
+                let response =
+                    FeSaslInitialResponse::parse(msg.password).map_err(PsqlError::PasswordError)?;
+                if response.mechanism != SCRAM_SHA_256.as_bytes() {
+                    return Err(PsqlError::PasswordError(IoError::new(
+                        ErrorKind::InvalidInput,
+                        "Unsupported SASL authentication mechanism",
+                    )));
+                }
+                let exchange = ScramExchange::start(secret, &response.data)
+                    .map_err(PsqlError::PasswordError)?;
+                self.stream
+                    .write_no_flush(&BeMessage::AuthenticationSaslContinue(
+                        exchange.server_first().as_bytes(),
+                    ))?;
+                self.scram_exchange = Some(exchange);
+            }
+            Some(exchange) => {
+                let server_final = exchange
+                    .finish(&msg.password)
+                    .map_err(PsqlError::PasswordError)?;
+                self.stream
+                    .write_no_flush(&BeMessage::AuthenticationSaslFinal(server_final.as_bytes()))?;
+                self.stream.write_no_flush(&BeMessage::AuthenticationOk)?;
+                self.stream.write_parameter_status_msg_no_flush()?;
+                self.stream.write_no_flush(&BeMessage::ReadyForQuery)?;
+            }
+        }
+        Ok(())
+    }
+
     fn process_cancel_msg(&mut self, m: FeCancelMessage) -> PsqlResult<()> {
         let session_id = (m.target_process_id, m.target_secret_key);
         self.session_mgr.cancel_queries_in_session(session_id);
@@ -321,7 +374,53 @@ where
                 .write_no_flush(&BeMessage::NoticeResponse(&notice))?;
         }
 
-        if res.is_empty() {
+        if let Some(num_columns) = res.copy_in_columns() {
+            self.stream
+                .write(&BeMessage::CopyInResponse(num_columns))
+                .await?;
+            // The data is passed to the session while it's being read, so that it isn't buffered
+            // as a whole.
+            let (sender, receiver) = mpsc::channel(COPY_DATA_CHANNEL_SIZE);
+            let data = futures::stream::unfold(receiver, |mut receiver| async move {
+                receiver.recv().await.map(|piece| (piece, receiver))
+            })
+            .boxed();
+            let (read_result, res) =
+                tokio::join!(self.read_copy_data(sender), session.run_copy_in(sql, data));
+            read_result?;
+            let res = res.map_err(|err| PsqlError::QueryError(err))?;
+            self.stream
+                .write_no_flush(&BeMessage::CommandComplete(BeCommandCompleteMessage {
+                    stmt_type: res.get_stmt_type(),
+                    rows_cnt: res
+                        .get_effected_rows_cnt()
+                        .ok_or_else(PsqlError::no_row_count)?,
+                }))?;
+        } else if res.is_copy_out() {
+            self.stream
+                .write_no_flush(&BeMessage::CopyOutResponse(res.get_row_desc().len()))?;
+            if let Some(header) = res.copy_out_header() {
+                self.stream.write_no_flush(&BeMessage::CopyData(header))?;
+            }
+
+            let mut rows_cnt = 0;
+
+            while let Some(row_set) = res.values_stream().next().await {
+                let row_set = row_set.map_err(|err| PsqlError::QueryError(err))?;
+                for row in row_set {
+                    let line = row[0].as_deref().unwrap_or_default();
+                    self.stream.write_no_flush(&BeMessage::CopyData(line))?;
+                    rows_cnt += 1;
+                }
+            }
+
+            self.stream.write_no_flush(&BeMessage::CopyDone)?;
+            self.stream
+                .write_no_flush(&BeMessage::CommandComplete(BeCommandCompleteMessage {
+                    stmt_type: res.get_stmt_type(),
+                    rows_cnt,
+                }))?;
+        } else if res.is_empty() {
             self.stream.write_no_flush(&BeMessage::EmptyQueryResponse)?;
         } else if res.is_query() {
             self.stream
@@ -348,7 +447,7 @@ where
                     stmt_type: res.get_stmt_type(),
                     rows_cnt: res
                         .get_effected_rows_cnt()
-                        .expect("row count should be set"),
+                        .ok_or_else(PsqlError::no_row_count)?,
                 }))?;
         }
 
@@ -356,6 +455,34 @@ where
         Ok(())
     }
 
+    /// Reads the data sent by the client in copy-in mode until `CopyDone`, and sends it to
+    /// `sender`. The data is discarded if the receiver is gone, e.g. the session has failed to
+    /// insert the data.
+    async fn read_copy_data(
+        &mut self,
+        sender: mpsc::Sender<Result<Bytes, BoxedError>>,
+    ) -> PsqlResult<()> {
+        loop {
+            let err = match self.read_message().await? {
+                FeMessage::CopyData(msg) => {
+                    let _ = sender.send(Ok(msg.data)).await;
+                    continue;
+                }
+                FeMessage::CopyDone => return Ok(()),
+                FeMessage::CopyFail(msg) => format!(
+                    "COPY from stdin failed: {}",
+                    String::from_utf8_lossy(&msg.message)
+                ),
+                // Flush and Sync may be sent by the client during copy-in, and are ignored.
+                FeMessage::Flush | FeMessage::Sync => continue,
+                _ => "unexpected message type during COPY from stdin".to_string(),
+            };
+            // Fail the session as well, so that it stops inserting the data.
+            let _ = sender.send(Err(err.clone().into())).await;
+            return Err(PsqlError::QueryError(err.into()));
+        }
+    }
+
     fn process_terminate(&mut self) {
         self.is_terminate = true;
     }
@@ -37,6 +37,7 @@ pub enum StatementType {
     EXPLAIN,
     CREATE_TABLE,
     CREATE_MATERIALIZED_VIEW,
+    CREATE_VIEW,
     CREATE_SOURCE,
     CREATE_SINK,
     CREATE_DATABASE,
@@ -47,12 +48,15 @@ pub enum StatementType {
     GRANT_PRIVILEGE,
     DROP_TABLE,
     DROP_MATERIALIZED_VIEW,
+    DROP_VIEW,
     DROP_INDEX,
     DROP_SOURCE,
     DROP_SINK,
     DROP_SCHEMA,
     DROP_DATABASE,
     DROP_USER,
+    ALTER_TABLE,
+    TRUNCATE_TABLE,
     REVOKE_PRIVILEGE,
     // Introduce ORDER_BY statement type cuz Calcite unvalidated AST has SqlKind.ORDER_BY. Note
     // that Statement Type is not designed to be one to one mapping with SqlKind.
@@ -89,6 +93,10 @@ where
     notice: Option<String>,
     values_stream: Option<VS>,
     row_desc: Vec<PgFieldDescriptor>,
+    // The number of columns that `COPY FROM STDIN` expects from the client.
+    copy_in_columns: Option<usize>,
+    // The header line sent before the rows of `COPY TO STDOUT`, which is not counted as a row.
+    copy_out_header: Option<Vec<u8>>,
 }
 
 impl<VS> std::fmt::Debug for PgResponse<VS>
@@ -149,6 +157,8 @@ where
             values_stream: None,
             row_desc: vec![],
             notice: None,
+            copy_in_columns: None,
+            copy_out_header: None,
         }
     }
 
@@ -160,6 +170,8 @@ where
             values_stream: None,
             row_desc: vec![],
             notice: Some(notice),
+            copy_in_columns: None,
+            copy_out_header: None,
         }
     }
 
@@ -175,6 +187,41 @@ where
             values_stream: Some(values_stream),
             row_desc,
             notice: None,
+            copy_in_columns: None,
+            copy_out_header: None,
+        }
+    }
+
+    /// Creates the response of `COPY FROM STDIN`, which makes the server ask the client for the
+    /// data of `num_columns` columns. The data is then passed to
+    /// [`Session::run_copy_in`](crate::pg_server::Session::run_copy_in).
+    pub fn new_for_copy_in(num_columns: usize) -> Self {
+        Self {
+            stmt_type: StatementType::COPY,
+            row_cnt: None,
+            values_stream: None,
+            row_desc: vec![],
+            notice: None,
+            copy_in_columns: Some(num_columns),
+            copy_out_header: None,
+        }
+    }
+
+    /// Creates the response of `COPY TO STDOUT`. See [`Self::is_copy_out`] for the values stream.
+    /// The optional `header` line is sent before the rows.
+    pub fn new_for_copy_out(
+        values_stream: VS,
+        row_desc: Vec<PgFieldDescriptor>,
+        header: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            stmt_type: StatementType::COPY,
+            row_cnt: None,
+            values_stream: Some(values_stream),
+            row_desc,
+            notice: None,
+            copy_in_columns: None,
+            copy_out_header: header,
         }
     }
 
@@ -198,6 +245,25 @@ where
         self.stmt_type == StatementType::EMPTY
     }
 
+    /// Returns the number of columns if this is the response of `COPY FROM STDIN`.
+    pub fn copy_in_columns(&self) -> Option<usize> {
+        self.copy_in_columns
+    }
+
+    /// Whether this is the response of `COPY TO STDOUT`. Each row of its values stream consists of
+    /// a single value, which is an encoded line of the output, while the row description is the
+    /// one of the copied query.
+    pub fn is_copy_out(&self) -> bool {
+        self.stmt_type == StatementType::COPY
+            && self.row_cnt.is_none()
+            && self.values_stream.is_some()
+    }
+
+    /// Returns the header line of `COPY TO STDOUT` if any.
+    pub fn copy_out_header(&self) -> Option<&[u8]> {
+        self.copy_out_header.as_deref()
+    }
+
     pub fn get_row_desc(&self) -> Vec<PgFieldDescriptor> {
         self.row_desc.clone()
     }
@@ -16,14 +16,19 @@ use std::io;
 use std::result::Result;
 use std::sync::Arc;
 
+use bytes::Bytes;
+use futures::stream::BoxStream;
 use futures::Stream;
 use tokio::net::TcpListener;
 
 use crate::pg_field_descriptor::PgFieldDescriptor;
 use crate::pg_protocol::{PgProtocol, TlsConfig};
 use crate::pg_response::{PgResponse, RowSetResult};
+use crate::scram::ScramSecret;
 
 pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+/// The data of `COPY FROM STDIN`, in the pieces sent by the client.
+pub type CopyDataStream = BoxStream<'static, Result<Bytes, BoxedError>>;
 pub type SessionId = (i32, i32);
 /// The interface for a database system behind pgwire protocol.
 /// We can mock it for testing purpose.
@@ -56,6 +61,15 @@ where
         sql: &str,
         format: bool,
     ) -> Result<PgResponse<VS>, BoxedError>;
+    /// Runs a `COPY FROM STDIN` statement with the `data` received from the client, after
+    /// [`Self::run_statement`] returned a response of
+    /// [`PgResponse::new_for_copy_in`](crate::pg_response::PgResponse::new_for_copy_in). The data
+    /// is streamed while it's being received.
+    async fn run_copy_in(
+        self: Arc<Self>,
+        sql: &str,
+        data: CopyDataStream,
+    ) -> Result<PgResponse<VS>, BoxedError>;
     async fn infer_return_type(
         self: Arc<Self>,
         sql: &str,
@@ -76,6 +90,8 @@ pub enum UserAuthenticator {
         encrypted_password: Vec<u8>,
         salt: [u8; 4],
     },
+    // password verifier of SCRAM-SHA-256, authenticated by a SASL exchange.
+    ScramSha256(ScramSecret),
 }
 
 impl UserAuthenticator {
@@ -86,6 +102,8 @@ impl UserAuthenticator {
             UserAuthenticator::Md5WithSalt {
                 encrypted_password, ..
             } => encrypted_password == password,
+            // Authenticated by `ScramExchange` instead.
+            UserAuthenticator::ScramSha256(_) => false,
         }
     }
 }
@@ -138,7 +156,9 @@ mod tests {
 
     use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
     use crate::pg_response::{PgResponse, RowSetResult, StatementType};
-    use crate::pg_server::{pg_serve, Session, SessionId, SessionManager, UserAuthenticator};
+    use crate::pg_server::{
+        pg_serve, CopyDataStream, Session, SessionId, SessionManager, UserAuthenticator,
+    };
     use crate::types::Row;
 
     struct MockSessionManager {}
@@ -197,6 +217,15 @@ mod tests {
             ))
         }
 
+        async fn run_copy_in(
+            self: Arc<Self>,
+            _sql: &str,
+            _data: CopyDataStream,
+        ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, Box<dyn Error + Send + Sync>>
+        {
+            Ok(PgResponse::empty_result(StatementType::COPY))
+        }
+
         fn user_authenticator(&self) -> &UserAuthenticator {
             &UserAuthenticator::None
         }
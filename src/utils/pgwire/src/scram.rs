@@ -0,0 +1,320 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SCRAM-SHA-256 authentication, as specified in RFC 5802 and RFC 7677. Channel binding is not
+//! supported.
+
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind, Result};
+
+use hmac::{Hmac, Mac};
+use itertools::Itertools;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// The name of the SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The default iteration count of PostgreSQL.
+const SCRAM_ITERATIONS: u32 = 4096;
+const SCRAM_SALT_LEN: usize = 16;
+const SCRAM_NONCE_LEN: usize = 18;
+
+/// The verifier of a password stored by the server, from which the password cannot be recovered.
+/// It is formatted the same as in PostgreSQL:
+/// `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramSecret {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: Vec<u8>,
+    server_key: Vec<u8>,
+}
+
+impl ScramSecret {
+    /// Creates the secret of `password` with a random salt.
+    pub fn new(password: &str) -> Self {
+        let mut salt = vec![0; SCRAM_SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::with_salt(password.as_bytes(), salt, SCRAM_ITERATIONS)
+    }
+
+    fn with_salt(password: &[u8], salt: Vec<u8>, iterations: u32) -> Self {
+        let salted_password = hi(password, &salt, iterations);
+        let client_key = hmac(&salted_password, b"Client Key");
+        let stored_key = Sha256::digest(client_key).to_vec();
+        let server_key = hmac(&salted_password, b"Server Key").to_vec();
+        Self {
+            iterations,
+            salt,
+            stored_key,
+            server_key,
+        }
+    }
+
+    /// Parses a secret formatted by [`ScramSecret`]'s `Display`. Returns `None` if `s` is not a
+    /// valid secret.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix(SCRAM_SHA_256)?.strip_prefix('$')?;
+        let (iterations_and_salt, keys) = s.split_once('$')?;
+        let (iterations, salt) = iterations_and_salt.split_once(':')?;
+        let (stored_key, server_key) = keys.split_once(':')?;
+        let secret = Self {
+            iterations: iterations.parse().ok()?,
+            salt: base64::decode(salt).ok()?,
+            stored_key: base64::decode(stored_key).ok()?,
+            server_key: base64::decode(server_key).ok()?,
+        };
+        (secret.iterations > 0 && secret.stored_key.len() == 32 && secret.server_key.len() == 32)
+            .then_some(secret)
+    }
+}
+
+impl fmt::Display for ScramSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            base64::encode(&self.salt),
+            base64::encode(&self.stored_key),
+            base64::encode(&self.server_key)
+        )
+    }
+}
+
+/// The server side of a SCRAM exchange, started by the client-first-message of the client.
+pub struct ScramExchange {
+    secret: ScramSecret,
+    /// The GS2 header of the client-first-message, which the client must send back in the
+    /// client-final-message.
+    gs2_header: String,
+    client_first_bare: String,
+    nonce: String,
+    server_first: String,
+}
+
+impl ScramExchange {
+    /// Starts an exchange with the `client_first` message. The server-first-message to send is
+    /// then given by [`Self::server_first`].
+    pub fn start(secret: ScramSecret, client_first: &[u8]) -> Result<Self> {
+        let mut server_nonce = [0; SCRAM_NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut server_nonce);
+        Self::start_with_nonce(secret, client_first, &base64::encode(server_nonce))
+    }
+
+    fn start_with_nonce(
+        secret: ScramSecret,
+        client_first: &[u8],
+        server_nonce: &str,
+    ) -> Result<Self> {
+        let client_first = std::str::from_utf8(client_first).map_err(|_| invalid_message())?;
+
+        // gs2-header = gs2-cbind-flag "," [ authzid ] ","
+        let (cbind_flag, rest) = client_first.split_once(',').ok_or_else(invalid_message)?;
+        match cbind_flag {
+            "n" | "y" => {}
+            _ if cbind_flag.starts_with("p=") => {
+                return Err(IoError::new(
+                    ErrorKind::Unsupported,
+                    "SCRAM channel binding is not supported",
+                ))
+            }
+            _ => return Err(invalid_message()),
+        }
+        let (authzid, client_first_bare) = rest.split_once(',').ok_or_else(invalid_message)?;
+        if !authzid.is_empty() {
+            return Err(IoError::new(
+                ErrorKind::Unsupported,
+                "SCRAM authorization identity is not supported",
+            ));
+        }
+        let gs2_header = client_first[..client_first.len() - client_first_bare.len()].to_string();
+
+        // client-first-message-bare = [ reserved-mext "," ] username "," nonce [ "," extensions ]
+        // The user name is ignored, as the one in the startup message is used, like PostgreSQL.
+        let mut attributes = client_first_bare.split(',');
+        match attributes.next() {
+            Some(username) if username.starts_with("n=") => {}
+            Some(mext) if mext.starts_with("m=") => {
+                return Err(IoError::new(
+                    ErrorKind::Unsupported,
+                    "SCRAM mandatory extensions are not supported",
+                ))
+            }
+            _ => return Err(invalid_message()),
+        }
+        let client_nonce = attributes
+            .next()
+            .and_then(|nonce| nonce.strip_prefix("r="))
+            .filter(|nonce| !nonce.is_empty())
+            .ok_or_else(invalid_message)?;
+
+        let nonce = format!("{}{}", client_nonce, server_nonce);
+        let server_first = format!(
+            "r={},s={},i={}",
+            nonce,
+            base64::encode(&secret.salt),
+            secret.iterations
+        );
+        Ok(Self {
+            secret,
+            gs2_header,
+            client_first_bare: client_first_bare.to_string(),
+            nonce,
+            server_first,
+        })
+    }
+
+    /// The server-first-message to send to the client.
+    pub fn server_first(&self) -> &str {
+        &self.server_first
+    }
+
+    /// Verifies the proof in the `client_final` message. Returns the server-final-message to send
+    /// to the client if the client is authenticated.
+    pub fn finish(self, client_final: &[u8]) -> Result<String> {
+        let client_final = std::str::from_utf8(client_final).map_err(|_| invalid_message())?;
+
+        // client-final-message = channel-binding "," nonce ["," extensions] "," proof
+        let (client_final_without_proof, proof) = client_final
+            .rsplit_once(",p=")
+            .ok_or_else(invalid_message)?;
+        let mut attributes = client_final_without_proof.split(',');
+        let channel_binding = attributes
+            .next()
+            .and_then(|c| c.strip_prefix("c="))
+            .ok_or_else(invalid_message)?;
+        if base64::decode(channel_binding).ok().as_deref() != Some(self.gs2_header.as_bytes()) {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "unexpected SCRAM channel-binding attribute in client-final-message",
+            ));
+        }
+        let nonce = attributes
+            .next()
+            .and_then(|r| r.strip_prefix("r="))
+            .ok_or_else(invalid_message)?;
+        if nonce != self.nonce {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "SCRAM nonce does not match",
+            ));
+        }
+        let proof = base64::decode(proof).map_err(|_| invalid_message())?;
+        if proof.len() != self.secret.stored_key.len() {
+            return Err(invalid_message());
+        }
+
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, self.server_first, client_final_without_proof
+        );
+        let client_signature = hmac(&self.secret.stored_key, auth_message.as_bytes());
+        let client_key: Vec<u8> = proof
+            .iter()
+            .zip_eq(client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        // Compares in constant time so that the time taken doesn't leak how much of the stored
+        // key matches.
+        let stored_key = Sha256::digest(client_key);
+        if !bool::from(stored_key.as_slice().ct_eq(&self.secret.stored_key)) {
+            return Err(IoError::new(ErrorKind::InvalidInput, "Invalid password"));
+        }
+
+        let server_signature = hmac(&self.secret.server_key, auth_message.as_bytes());
+        Ok(format!("v={}", base64::encode(server_signature)))
+    }
+}
+
+fn invalid_message() -> IoError {
+    IoError::new(ErrorKind::InvalidInput, "malformed SCRAM message")
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// `Hi(str, salt, i)` of RFC 5802, i.e. PBKDF2 with HMAC-SHA-256.
+fn hi(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut u = hmac(password, &[salt, &1u32.to_be_bytes()[..]].concat());
+    let mut result = u;
+    for _ in 1..iterations {
+        u = hmac(password, &u);
+        result.iter_mut().zip_eq(u).for_each(|(r, u)| *r ^= u);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example exchange of RFC 7677, with user "user" and password "pencil".
+    #[test]
+    fn test_scram_exchange() {
+        let secret = ScramSecret::with_salt(
+            b"pencil",
+            base64::decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap(),
+            4096,
+        );
+        assert_eq!(
+            ScramSecret::parse(&secret.to_string()),
+            Some(secret.clone())
+        );
+
+        let exchange = ScramExchange::start_with_nonce(
+            secret,
+            b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO",
+            "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0",
+        )
+        .unwrap();
+        assert_eq!(
+            exchange.server_first(),
+            "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096"
+        );
+        let server_final = exchange
+            .finish(b"c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=")
+            .unwrap();
+        assert_eq!(
+            server_final,
+            "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="
+        );
+    }
+
+    #[test]
+    fn test_scram_wrong_password() {
+        let exchange = ScramExchange::start_with_nonce(
+            ScramSecret::new("secret"),
+            b"n,,n=,r=rOprNGfwEbeRWgbNEkqO",
+            "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0",
+        )
+        .unwrap();
+        assert!(exchange
+            .finish(b"c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=")
+            .is_err());
+
+        assert!(ScramExchange::start(
+            ScramSecret::new("secret"),
+            b"p=tls-server-end-point,,n=,r=abc"
+        )
+        .is_err());
+        assert!(ScramSecret::parse("md5827ccb0eea8a706c4c34a16891f84e7b").is_none());
+    }
+}